@@ -80,6 +80,9 @@ pub trait UsersInfoClientV1: Send + Sync {
     /// Delete a user by ID.
     async fn delete_user(&self, ctx: SecurityContext, id: Uuid) -> Result<(), UsersInfoError>;
 
+    /// Restore a soft-deleted user by ID.
+    async fn restore_user(&self, ctx: SecurityContext, id: Uuid) -> Result<User, UsersInfoError>;
+
     /// Create a new city.
     async fn create_city(
         &self,
@@ -113,6 +116,13 @@ pub trait UsersInfoClientV1: Send + Sync {
 
     /// Delete an address by ID.
     async fn delete_address(&self, ctx: SecurityContext, id: Uuid) -> Result<(), UsersInfoError>;
+
+    /// Restore a soft-deleted address by ID.
+    async fn restore_address(
+        &self,
+        ctx: SecurityContext,
+        id: Uuid,
+    ) -> Result<Address, UsersInfoError>;
 }
 
 /// Streaming interface for users (Version 1).
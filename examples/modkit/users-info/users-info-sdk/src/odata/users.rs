@@ -19,6 +19,9 @@ pub struct UserQuery {
     #[odata(filter(kind = "String"))]
     pub email: String,
 
+    #[odata(filter(kind = "String"))]
+    pub display_name: String,
+
     #[odata(filter(kind = "DateTimeUtc"))]
     pub created_at: OffsetDateTime,
 }
@@ -42,5 +45,7 @@ impl Schema for UserSchema {
 
 pub const USER_ID: FieldRef<UserSchema, Uuid> = FieldRef::new(UserFilterField::Id);
 pub const USER_EMAIL: FieldRef<UserSchema, String> = FieldRef::new(UserFilterField::Email);
+pub const USER_DISPLAY_NAME: FieldRef<UserSchema, String> =
+    FieldRef::new(UserFilterField::DisplayName);
 pub const USER_CREATED_AT: FieldRef<UserSchema, OffsetDateTime> =
     FieldRef::new(UserFilterField::CreatedAt);
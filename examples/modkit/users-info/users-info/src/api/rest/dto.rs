@@ -4,6 +4,8 @@ use time::OffsetDateTime;
 use users_info_sdk::{Address, City, NewAddress, NewCity, NewUser, User, UserFull, UserPatch};
 use uuid::Uuid;
 
+use crate::domain::service::{BulkUserResult, ExpandedUser};
+
 /// REST DTO for user representation with serde/utoipa
 #[derive(Debug, Clone)]
 #[modkit_macros::api_dto(request, response)]
@@ -49,6 +51,45 @@ pub struct UserFullDto {
     pub city: Option<CityDto>,
 }
 
+/// REST DTO for a user list item with the relations requested via `$expand`
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct UserListItemDto {
+    #[serde(flatten)]
+    pub user: UserDto,
+    /// Present only with `$expand=addresses`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub addresses: Option<Vec<AddressDto>>,
+}
+
+/// REST DTO for creating several users at once
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request)]
+pub struct BulkCreateUsersReq {
+    pub users: Vec<CreateUserReq>,
+}
+
+/// REST DTO for one row of a bulk user creation
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct BulkCreateUserRowDto {
+    /// Position of the row in the request's `users` array
+    pub index: usize,
+    /// Present when the row was created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<UserDto>,
+    /// Present when the row was rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// REST DTO for the per-row outcome of a bulk user creation
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct BulkCreateUsersResp {
+    pub results: Vec<BulkCreateUserRowDto>,
+}
+
 // Conversion implementations between REST DTOs and contract models
 impl From<User> for UserDto {
     fn from(user: User) -> Self {
@@ -63,6 +104,23 @@ impl From<User> for UserDto {
     }
 }
 
+impl From<BulkUserResult> for BulkCreateUserRowDto {
+    fn from(row: BulkUserResult) -> Self {
+        match row.result {
+            Ok(user) => Self {
+                index: row.index,
+                user: Some(user.into()),
+                error: None,
+            },
+            Err(e) => Self {
+                index: row.index,
+                user: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
+
 impl From<CreateUserReq> for NewUser {
     fn from(req: CreateUserReq) -> Self {
         Self {
@@ -83,6 +141,17 @@ impl From<UpdateUserReq> for UserPatch {
     }
 }
 
+impl From<ExpandedUser> for UserListItemDto {
+    fn from(expanded: ExpandedUser) -> Self {
+        Self {
+            user: expanded.user.into(),
+            addresses: expanded
+                .addresses
+                .map(|addresses| addresses.into_iter().map(AddressDto::from).collect()),
+        }
+    }
+}
+
 impl From<UserFull> for UserFullDto {
     fn from(user_full: UserFull) -> Self {
         Self {
@@ -231,7 +300,7 @@ pub struct UserEvent {
 
 impl From<&crate::domain::events::UserDomainEvent> for UserEvent {
     fn from(e: &crate::domain::events::UserDomainEvent) -> Self {
-        use crate::domain::events::UserDomainEvent::{Created, Deleted, Updated};
+        use crate::domain::events::UserDomainEvent::{Created, Deleted, Restored, Updated};
         match e {
             Created { id, at } => Self {
                 kind: "created".into(),
@@ -248,6 +317,11 @@ impl From<&crate::domain::events::UserDomainEvent> for UserEvent {
                 id: *id,
                 at: *at,
             },
+            Restored { id, at } => Self {
+                kind: "restored".into(),
+                id: *id,
+                at: *at,
+            },
         }
     }
 }
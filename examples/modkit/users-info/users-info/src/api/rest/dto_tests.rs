@@ -39,6 +39,13 @@ fn maps_all_domain_event_variants() {
     assert_eq!(deleted_event.kind, "deleted");
     assert_eq!(deleted_event.id, id);
     assert_eq!(deleted_event.at, at);
+
+    // Test Restored event
+    let restored = UserDomainEvent::Restored { id, at };
+    let restored_event = UserEvent::from(&restored);
+    assert_eq!(restored_event.kind, "restored");
+    assert_eq!(restored_event.id, id);
+    assert_eq!(restored_event.at, at);
 }
 
 #[test]
@@ -1,7 +1,7 @@
 use modkit::api::problem::{Problem, ValidationViolation};
 use modkit_canonical_errors::{CanonicalError, FieldViolation, InvalidArgument};
 
-use crate::domain::error::DomainError;
+use crate::domain::error::{DomainError, join_ids};
 
 // Resource-scoped canonical error types for each entity in this module.
 #[modkit_canonical_errors::resource_error("gts.hx.example1.users.user.v1~")]
@@ -43,6 +43,28 @@ fn domain_error_to_canonical(e: &DomainError) -> CanonicalError {
                 .create()
         }
 
+        DomainError::Conflict { id } => {
+            UserResourceError::already_exists(format!("User {id} conflicts with an existing user"))
+                .with_resource(id.to_string())
+                .create()
+        }
+
+        DomainError::DeletedAddressExists {
+            user_id,
+            address_id,
+        } => AddressResourceError::already_exists(format!(
+            "User {user_id} has a deleted address; restore it first"
+        ))
+        .with_resource(address_id.to_string())
+        .create(),
+
+        DomainError::UserHasAddresses { id, addresses } => {
+            UserResourceError::aborted(format!("User still has addresses: {}", join_ids(addresses)))
+                .with_resource(id.to_string())
+                .with_reason("DEPENDENT_ADDRESSES")
+                .create()
+        }
+
         DomainError::InvalidEmail { email } => UserResourceError::invalid_argument()
             .with_field_violation(
                 "email",
@@ -72,8 +94,8 @@ fn domain_error_to_canonical(e: &DomainError) -> CanonicalError {
             CanonicalError::internal("An internal database error occurred").create()
         }
 
-        DomainError::Forbidden => UserResourceError::permission_denied()
-            .with_reason("ACCESS_DENIED")
+        DomainError::Forbidden { reason } => UserResourceError::permission_denied()
+            .with_reason(reason.map_or("ACCESS_DENIED", |r| r.code()))
             .create(),
 
         DomainError::InternalError => {
@@ -109,6 +131,12 @@ fn canonical_to_problem(ce: &CanonicalError) -> Problem {
             serde_json::Value::String(rn.to_owned()),
         );
     }
+    if let CanonicalError::PermissionDenied { ctx: denied, .. } = ce {
+        ctx.insert(
+            "reason".to_owned(),
+            serde_json::Value::String(denied.reason.clone()),
+        );
+    }
     if !ctx.is_empty() {
         problem = problem.with_context(serde_json::Value::Object(ctx));
     }
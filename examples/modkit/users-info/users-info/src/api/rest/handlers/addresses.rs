@@ -4,8 +4,11 @@ use axum::response::IntoResponse;
 use tracing::field::Empty;
 use uuid::Uuid;
 
+use modkit::api::odata::OData;
+
 use super::{
-    AddressDto, ApiResult, Json, JsonBody, PutAddressReq, SecurityContext, info, no_content,
+    AddressDto, ApiResult, Json, JsonBody, JsonPage, PutAddressReq, SecurityContext, info,
+    no_content, page_to_projected_json,
 };
 use crate::module::ConcreteAppServices;
 
@@ -37,6 +40,37 @@ pub async fn get_user_address(
     Ok(Json(AddressDto::from(address)))
 }
 
+/// List addresses of a specific user with cursor-based pagination
+#[tracing::instrument(
+    skip(svc, query, ctx),
+    fields(
+        user.id = %user_id,
+        limit = query.limit,
+        request_id = Empty,
+        requester.id = %ctx.subject_id()
+    )
+)]
+pub async fn list_user_addresses(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<std::sync::Arc<ConcreteAppServices>>,
+    Path(user_id): Path<Uuid>,
+    OData(query): OData,
+) -> ApiResult<JsonPage<serde_json::Value>> {
+    info!(
+        user_id = %user_id,
+        requester_id = %ctx.subject_id(),
+        "Listing user addresses"
+    );
+
+    let page = svc
+        .addresses
+        .list_user_addresses(&ctx, user_id, &query)
+        .await?;
+    let page = page.map_items(AddressDto::from);
+
+    Ok(Json(page_to_projected_json(&page, query.selected_fields())))
+}
+
 /// Upsert address for a specific user (PUT = create or replace)
 #[tracing::instrument(
     skip(svc, req_body, ctx),
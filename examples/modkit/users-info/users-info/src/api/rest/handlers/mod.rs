@@ -2,7 +2,7 @@ use tracing::info;
 
 use crate::api::rest::dto::{
     AddressDto, CityDto, CreateCityReq, PutAddressReq, UpdateCityReq, UpdateUserReq, UserDto,
-    UserEvent, UserFullDto,
+    UserEvent, UserFullDto, UserListItemDto,
 };
 
 use modkit::api::prelude::*;
@@ -20,9 +20,12 @@ mod users;
 // ==================== User Handlers ====================
 
 pub(crate) use users::create_user;
+pub(crate) use users::create_users_bulk;
 pub(crate) use users::delete_user;
 pub(crate) use users::get_user;
 pub(crate) use users::list_users;
+pub(crate) use users::restore_user;
+pub(crate) use users::search_users;
 pub(crate) use users::update_user;
 
 // ==================== Event Handlers (SSE) ====================
@@ -41,4 +44,5 @@ pub(crate) use cities::update_city;
 
 pub(crate) use addresses::delete_user_address;
 pub(crate) use addresses::get_user_address;
+pub(crate) use addresses::list_user_addresses;
 pub(crate) use addresses::put_user_address;
@@ -1,5 +1,5 @@
 use axum::Extension;
-use axum::extract::Path;
+use axum::extract::{Path, Query};
 use axum::http::Uri;
 use axum::response::IntoResponse;
 use tracing::field::Empty;
@@ -9,14 +9,26 @@ use modkit::api::odata::OData;
 
 use super::{
     ApiResult, Json, JsonBody, JsonPage, SecurityContext, UpdateUserReq, UserDto, UserFullDto,
-    apply_select, created_json, info, no_content, page_to_projected_json,
+    UserListItemDto, apply_select, created_json, info, no_content, page_to_projected_json,
 };
-use crate::api::rest::dto::CreateUserReq;
+use crate::api::rest::dto::{
+    BulkCreateUserRowDto, BulkCreateUsersReq, BulkCreateUsersResp, CreateUserReq,
+};
+use crate::domain::repos::SoftDeleted;
 use crate::module::ConcreteAppServices;
 
-/// List users with cursor-based pagination and optional field projection via $select
+/// Query parameters for `list_users` beyond the `OData` ones
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct ListUsersQuery {
+    /// Also return soft-deleted users
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+/// List users with cursor-based pagination, optional field projection via $select
+/// and related entities via $expand
 #[tracing::instrument(
-    skip(svc, query, ctx),
+    skip(svc, query, params, ctx),
     fields(
         limit = query.limit,
         request_id = Empty,
@@ -27,13 +39,69 @@ pub async fn list_users(
     Extension(ctx): Extension<SecurityContext>,
     Extension(svc): Extension<std::sync::Arc<ConcreteAppServices>>,
     OData(query): OData,
+    Query(params): Query<ListUsersQuery>,
 ) -> ApiResult<JsonPage<serde_json::Value>> {
     info!(
         user_id = %ctx.subject_id(),
+        include_deleted = params.include_deleted,
         "Listing users with cursor pagination"
     );
 
-    let page = svc.users.list_users_page(&ctx, &query).await?;
+    let deleted = if params.include_deleted {
+        SoftDeleted::Include
+    } else {
+        SoftDeleted::Exclude
+    };
+    let page = svc
+        .users
+        .list_users_page_expanded(&ctx, &query, deleted)
+        .await?;
+    let page = page.map_items(UserListItemDto::from);
+
+    // Expanded relations stay in the projection even when not listed in $select
+    let selected = query.selected_fields().map(|fields| {
+        let mut fields = fields.to_vec();
+        fields.extend(
+            query
+                .expanded_relations()
+                .unwrap_or_default()
+                .iter()
+                .cloned(),
+        );
+        fields
+    });
+
+    Ok(Json(page_to_projected_json(&page, selected.as_deref())))
+}
+
+/// Query parameters for `search_users` beyond the `OData` ones
+#[derive(Debug, serde::Deserialize)]
+pub struct SearchUsersQuery {
+    /// Case-insensitive substring of the email or display name
+    pub q: String,
+}
+
+/// Search users by email or display name with cursor-based pagination
+#[tracing::instrument(
+    skip(svc, query, params, ctx),
+    fields(
+        limit = query.limit,
+        request_id = Empty,
+        user.id = %ctx.subject_id()
+    )
+)]
+pub async fn search_users(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<std::sync::Arc<ConcreteAppServices>>,
+    OData(query): OData,
+    Query(params): Query<SearchUsersQuery>,
+) -> ApiResult<JsonPage<serde_json::Value>> {
+    info!(
+        user_id = %ctx.subject_id(),
+        "Searching users"
+    );
+
+    let page = svc.users.search_users(&ctx, &params.q, &query).await?;
     let page = page.map_items(UserDto::from);
 
     Ok(Json(page_to_projected_json(&page, query.selected_fields())))
@@ -110,6 +178,37 @@ pub async fn create_user(
     Ok(created_json(UserDto::from(user), &uri, &id_str).into_response())
 }
 
+/// Create several users at once, reporting the outcome per row
+#[tracing::instrument(
+    skip(svc, req_body, ctx),
+    fields(
+        batch_size = req_body.users.len(),
+        request_id = Empty,
+        creator.id = %ctx.subject_id()
+    )
+)]
+pub async fn create_users_bulk(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<std::sync::Arc<ConcreteAppServices>>,
+    Json(req_body): Json<BulkCreateUsersReq>,
+) -> ApiResult<JsonBody<BulkCreateUsersResp>> {
+    info!(
+        batch_size = req_body.users.len(),
+        creator_id = %ctx.subject_id(),
+        "Creating users in bulk"
+    );
+
+    let new_users = req_body.users.into_iter().map(Into::into).collect();
+    let results = svc.users.create_users_bulk(&ctx, new_users).await?;
+
+    Ok(Json(BulkCreateUsersResp {
+        results: results
+            .into_iter()
+            .map(BulkCreateUserRowDto::from)
+            .collect(),
+    }))
+}
+
 /// Update an existing user
 #[tracing::instrument(
     skip(svc, req_body, ctx),
@@ -159,3 +258,27 @@ pub async fn delete_user(
     svc.users.delete_user(&ctx, id).await?;
     Ok(no_content().into_response())
 }
+
+/// Restore a soft-deleted user by ID
+#[tracing::instrument(
+    skip(svc, ctx),
+    fields(
+        user.id = %id,
+        request_id = Empty,
+        restorer.id = %ctx.subject_id()
+    )
+)]
+pub async fn restore_user(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<std::sync::Arc<ConcreteAppServices>>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<JsonBody<UserDto>> {
+    info!(
+        user_id = %id,
+        restorer_id = %ctx.subject_id(),
+        "Restoring user"
+    );
+
+    let user = svc.users.restore_user(&ctx, id).await?;
+    Ok(Json(UserDto::from(user)))
+}
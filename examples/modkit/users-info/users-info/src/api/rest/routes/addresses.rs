@@ -1,7 +1,8 @@
 use super::{License, dto, handlers};
 use axum::Router;
 use modkit::api::OpenApiRegistry;
-use modkit::api::operation_builder::OperationBuilder;
+use modkit::api::operation_builder::{OperationBuilder, OperationBuilderODataExt};
+use users_info_sdk::odata::AddressFilterField;
 
 const API_TAG: &str = "Users Info Addresses";
 
@@ -23,6 +24,37 @@ pub(super) fn register_address_routes(mut router: Router, openapi: &dyn OpenApiR
         .error_500(openapi)
         .register(router, openapi);
 
+    // GET /users-info/v1/users/{id}/addresses - List user's addresses
+    router = OperationBuilder::get("/users-info/v1/users/{id}/addresses")
+        .operation_id("users_info.list_user_addresses")
+        .authenticated()
+        .require_license_features::<License>([])
+        .summary("List user addresses")
+        .description("Retrieve a paginated list of the addresses of a specific user")
+        .tag(API_TAG)
+        .path_param("id", "User UUID")
+        .query_param_typed(
+            "limit",
+            false,
+            "Maximum number of addresses to return",
+            "integer",
+        )
+        .query_param("cursor", false, "Cursor for pagination")
+        .handler(handlers::list_user_addresses)
+        .json_response_with_schema::<modkit_odata::Page<dto::AddressDto>>(
+            openapi,
+            http::StatusCode::OK,
+            "Paginated list of user addresses",
+        )
+        .with_odata_filter::<AddressFilterField>()
+        .with_odata_select()
+        .with_odata_orderby::<AddressFilterField>()
+        .error_400(openapi)
+        .error_401(openapi)
+        .error_403(openapi)
+        .error_500(openapi)
+        .register(router, openapi);
+
     // PUT /users-info/v1/users/{id}/address - Upsert user's address
     router = OperationBuilder::put("/users-info/v1/users/{id}/address")
         .operation_id("users_info.put_user_address")
@@ -30,6 +30,7 @@ pub(super) fn register_city_routes(mut router: Router, openapi: &dyn OpenApiRegi
         )
         .with_odata_filter::<CityFilterField>()
         .with_odata_select()
+        .with_odata_count()
         .with_odata_orderby::<CityFilterField>()
         .error_400(openapi)
         .error_500(openapi)
@@ -4,6 +4,8 @@ use modkit::api::OpenApiRegistry;
 use modkit::api::operation_builder::{OperationBuilder, OperationBuilderODataExt};
 use users_info_sdk::odata::UserFilterField;
 
+use crate::domain::service::USER_EXPANDABLE;
+
 const API_TAG: &str = "Users Info";
 
 pub(super) fn register_user_routes(mut router: Router, openapi: &dyn OpenApiRegistry) -> Router {
@@ -22,14 +24,54 @@ pub(super) fn register_user_routes(mut router: Router, openapi: &dyn OpenApiRegi
         )
         .require_license_features::<License>([])
         .query_param("cursor", false, "Cursor for pagination")
+        .query_param_typed(
+            "include_deleted",
+            false,
+            "Include soft-deleted users (default false)",
+            "boolean",
+        )
         .handler(handlers::list_users)
-        .json_response_with_schema::<modkit_odata::Page<dto::UserDto>>(
+        .json_response_with_schema::<modkit_odata::Page<dto::UserListItemDto>>(
             openapi,
             http::StatusCode::OK,
             "Paginated list of users",
         )
         .with_odata_filter::<UserFilterField>()
         .with_odata_select()
+        .with_odata_count()
+        .with_odata_expand(USER_EXPANDABLE)
+        .with_odata_orderby::<UserFilterField>()
+        .error_400(openapi)
+        .error_500(openapi)
+        .register(router, openapi);
+
+    // GET /users-info/v1/users/search - Search users by email or display name
+    router = OperationBuilder::get("/users-info/v1/users/search")
+        .operation_id("users_info.search_users")
+        .summary("Search users")
+        .description(
+            "Case-insensitive substring search over user email and display name, \
+             limited to the caller's scope",
+        )
+        .tag(API_TAG)
+        .authenticated()
+        .require_license_features::<License>([])
+        .query_param("q", true, "Search term")
+        .query_param_typed(
+            "limit",
+            false,
+            "Maximum number of users to return",
+            "integer",
+        )
+        .query_param("cursor", false, "Cursor for pagination")
+        .handler(handlers::search_users)
+        .json_response_with_schema::<modkit_odata::Page<dto::UserDto>>(
+            openapi,
+            http::StatusCode::OK,
+            "Paginated list of matching users",
+        )
+        .with_odata_filter::<UserFilterField>()
+        .with_odata_select()
         .with_odata_orderby::<UserFilterField>()
         .error_400(openapi)
         .error_500(openapi)
@@ -75,6 +117,29 @@ pub(super) fn register_user_routes(mut router: Router, openapi: &dyn OpenApiRegi
         .error_500(openapi)
         .register(router, openapi);
 
+    // POST /users-info/v1/users/bulk - Create several users with per-row results
+    router = OperationBuilder::post("/users-info/v1/users/bulk")
+        .operation_id("users_info.create_users_bulk")
+        .authenticated()
+        .require_license_features::<License>([])
+        .summary("Create users in bulk")
+        .description(
+            "Create a batch of users in one transaction; rows that fail validation, \
+             authorization or email/ID uniqueness are reported and skipped",
+        )
+        .tag(API_TAG)
+        .json_request::<dto::BulkCreateUsersReq>(openapi, "Users to create")
+        .handler(handlers::create_users_bulk)
+        .json_response_with_schema::<dto::BulkCreateUsersResp>(
+            openapi,
+            http::StatusCode::OK,
+            "Per-row creation results",
+        )
+        .error_400(openapi)
+        .error_401(openapi)
+        .error_500(openapi)
+        .register(router, openapi);
+
     // PATCH /users-info/v1/users/{id} - Partially update a user
     router = OperationBuilder::patch("/users-info/v1/users/{id}")
         .operation_id("users_info.update_user")
@@ -101,11 +166,33 @@ pub(super) fn register_user_routes(mut router: Router, openapi: &dyn OpenApiRegi
         .authenticated()
         .require_license_features::<License>([])
         .summary("Delete user")
-        .description("Delete a user by their UUID")
+        .description(
+            "Delete a user by their UUID; fails with 409 while the user still has \
+             addresses unless the module is configured to cascade",
+        )
         .tag(API_TAG)
         .path_param("id", "User UUID")
         .handler(handlers::delete_user)
         .json_response(http::StatusCode::NO_CONTENT, "User deleted successfully")
+        .error_400(openapi)
+        .error_401(openapi)
+        .error_403(openapi)
+        .error_404(openapi)
+        .error_409(openapi)
+        .error_500(openapi)
+        .register(router, openapi);
+
+    // POST /users-info/v1/users/{id}/restore - Restore a soft-deleted user
+    router = OperationBuilder::post("/users-info/v1/users/{id}/restore")
+        .operation_id("users_info.restore_user")
+        .authenticated()
+        .require_license_features::<License>([])
+        .summary("Restore user")
+        .description("Restore a soft-deleted user by their UUID; a no-op for live users")
+        .tag(API_TAG)
+        .path_param("id", "User UUID")
+        .handler(handlers::restore_user)
+        .json_response_with_schema::<dto::UserDto>(openapi, http::StatusCode::OK, "Restored user")
         .error_401(openapi)
         .error_403(openapi)
         .error_404(openapi)
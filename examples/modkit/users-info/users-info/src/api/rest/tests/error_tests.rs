@@ -0,0 +1,76 @@
+use axum::body::Body;
+use http::{Request, StatusCode};
+use modkit::api::OpenApiRegistryImpl;
+use tower::ServiceExt;
+use users_info_sdk::{NewAddress, NewCity};
+use uuid::Uuid;
+
+use crate::api::rest::routes;
+use crate::domain::service::ServiceConfig;
+use crate::test_support::{build_services, ctx_allow_tenants, inmem_db, seed_user};
+
+#[tokio::test]
+async fn delete_user_with_addresses_returns_409() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, user_id, tenant, "rest409@example.com", "Rest").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+    let city = services
+        .cities
+        .create_city(
+            &ctx,
+            NewCity {
+                id: None,
+                tenant_id: tenant,
+                name: "Conflict City".to_owned(),
+                country: "CC".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+    let address = services
+        .addresses
+        .create_address(
+            &ctx,
+            NewAddress {
+                id: None,
+                tenant_id: tenant,
+                user_id,
+                city_id: city.id,
+                street: "9 Conflict St".to_owned(),
+                postal_code: "40900".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let router = routes::register_routes(
+        axum::Router::new(),
+        &OpenApiRegistryImpl::default(),
+        services,
+    )
+    .layer(axum::Extension(ctx));
+
+    let response = router
+        .oneshot(
+            Request::delete(format!("/users-info/v1/users/{user_id}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        problem["detail"],
+        format!("User still has addresses: {}", address.id)
+    );
+}
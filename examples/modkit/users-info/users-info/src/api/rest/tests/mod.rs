@@ -1,3 +1,7 @@
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod sse_tests;
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod error_tests;
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::domain::service::UserDeletePolicy;
+
 /// Configuration for the `users_info` module
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -12,6 +14,9 @@ pub struct UsersInfoConfig {
     pub audit_base_url: String,
     #[serde(default = "default_notifications_base_url")]
     pub notifications_base_url: String,
+    /// Whether deleting a user with addresses is refused or cascades.
+    #[serde(default)]
+    pub user_delete_policy: UserDeletePolicy,
 }
 
 impl Default for UsersInfoConfig {
@@ -21,6 +26,7 @@ impl Default for UsersInfoConfig {
             max_page_size: default_max_page_size(),
             audit_base_url: default_audit_base_url(),
             notifications_base_url: default_notifications_base_url(),
+            user_delete_policy: UserDeletePolicy::default(),
         }
     }
 }
@@ -1,3 +1,4 @@
+use authz_resolver_sdk::DenyKind;
 use modkit_db::DbError;
 use modkit_db::secure::InfraError;
 use modkit_db::secure::ScopeError;
@@ -16,6 +17,15 @@ pub enum DomainError {
     #[error("User with email '{email}' already exists")]
     EmailAlreadyExists { email: String },
 
+    #[error("User {id} conflicts with an existing user")]
+    Conflict { id: Uuid },
+
+    #[error("User {user_id} has a deleted address {address_id}; restore it first")]
+    DeletedAddressExists { user_id: Uuid, address_id: Uuid },
+
+    #[error("User {id} still has addresses: {addresses:?}")]
+    UserHasAddresses { id: Uuid, addresses: Vec<Uuid> },
+
     #[error("Invalid email format: '{email}'")]
     InvalidEmail { email: String },
 
@@ -34,8 +44,9 @@ pub enum DomainError {
     #[error("{entity_type} not found: {id}")]
     NotFound { entity_type: String, id: Uuid },
 
+    /// `reason` is `None` when access was denied outside the `AuthZ` flow.
     #[error("Access denied")]
-    Forbidden,
+    Forbidden { reason: Option<DenyKind> },
 
     #[error("Internal error")]
     InternalError,
@@ -52,6 +63,24 @@ impl DomainError {
         Self::EmailAlreadyExists { email }
     }
 
+    #[must_use]
+    pub fn conflict(id: Uuid) -> Self {
+        Self::Conflict { id }
+    }
+
+    #[must_use]
+    pub fn deleted_address_exists(user_id: Uuid, address_id: Uuid) -> Self {
+        Self::DeletedAddressExists {
+            user_id,
+            address_id,
+        }
+    }
+
+    #[must_use]
+    pub fn user_has_addresses(id: Uuid, addresses: Vec<Uuid>) -> Self {
+        Self::UserHasAddresses { id, addresses }
+    }
+
     #[must_use]
     pub fn invalid_email(email: String) -> Self {
         Self::InvalidEmail { email }
@@ -96,11 +125,26 @@ impl DomainError {
     }
 }
 
+/// Comma-separated IDs, as reported for blocking dependents in SDK and REST errors.
+pub(crate) fn join_ids(ids: &[Uuid]) -> String {
+    ids.iter()
+        .map(Uuid::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 /// Convert domain errors to SDK errors for public API consumption.
 impl From<DomainError> for UsersInfoError {
     fn from(domain_error: DomainError) -> Self {
         match domain_error {
             DomainError::EmailAlreadyExists { email } => UsersInfoError::conflict(email),
+            DomainError::Conflict { id } => UsersInfoError::conflict(id.to_string()),
+            DomainError::DeletedAddressExists { address_id, .. } => {
+                UsersInfoError::conflict(address_id.to_string())
+            }
+            DomainError::UserHasAddresses { addresses, .. } => {
+                UsersInfoError::conflict(join_ids(&addresses))
+            }
             DomainError::InvalidEmail { email } => {
                 UsersInfoError::validation(format!("Invalid email: {email}"))
             }
@@ -116,7 +160,7 @@ impl From<DomainError> for UsersInfoError {
             DomainError::UserNotFound { id } | DomainError::NotFound { id, .. } => {
                 UsersInfoError::not_found(id)
             }
-            DomainError::Forbidden => UsersInfoError::forbidden(),
+            DomainError::Forbidden { .. } => UsersInfoError::forbidden(),
             DomainError::Database { .. } | DomainError::InternalError => UsersInfoError::internal(),
         }
     }
@@ -145,10 +189,9 @@ impl From<ScopeError> for DomainError {
 impl From<authz_resolver_sdk::EnforcerError> for DomainError {
     fn from(e: authz_resolver_sdk::EnforcerError) -> Self {
         tracing::error!(error = %e, "AuthZ scope resolution failed");
-        match e {
-            authz_resolver_sdk::EnforcerError::Denied { .. }
-            | authz_resolver_sdk::EnforcerError::CompileFailed(_) => Self::Forbidden,
-            authz_resolver_sdk::EnforcerError::EvaluationFailed(_) => Self::InternalError,
+        match e.deny_kind() {
+            Some(kind) => Self::Forbidden { reason: Some(kind) },
+            None => Self::InternalError,
         }
     }
 }
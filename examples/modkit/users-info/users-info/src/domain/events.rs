@@ -9,4 +9,5 @@ pub enum UserDomainEvent {
     Created { id: Uuid, at: OffsetDateTime },
     Updated { id: Uuid, at: OffsetDateTime },
     Deleted { id: Uuid, at: OffsetDateTime },
+    Restored { id: Uuid, at: OffsetDateTime },
 }
@@ -134,6 +134,14 @@ impl UsersInfoClientV1 for UsersInfoLocalClient {
             .map_err(UsersInfoError::from)
     }
 
+    async fn restore_user(&self, ctx: SecurityContext, id: Uuid) -> Result<User, UsersInfoError> {
+        self.services
+            .users
+            .restore_user(&ctx, id)
+            .await
+            .map_err(UsersInfoError::from)
+    }
+
     async fn create_city(
         &self,
         ctx: SecurityContext,
@@ -197,4 +205,16 @@ impl UsersInfoClientV1 for UsersInfoLocalClient {
             .await
             .map_err(UsersInfoError::from)
     }
+
+    async fn restore_address(
+        &self,
+        ctx: SecurityContext,
+        id: Uuid,
+    ) -> Result<Address, UsersInfoError> {
+        self.services
+            .addresses
+            .restore_address(&ctx, id)
+            .await
+            .map_err(UsersInfoError::from)
+    }
 }
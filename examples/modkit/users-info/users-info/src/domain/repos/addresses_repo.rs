@@ -1,13 +1,18 @@
 use async_trait::async_trait;
-use modkit_db::secure::DBRunner;
+use modkit_db::secure::{DBRunner, ScopedLookup};
 use modkit_odata::{ODataQuery, Page};
 use modkit_security::AccessScope;
+use time::OffsetDateTime;
 use users_info_sdk::Address;
 use uuid::Uuid;
 
+use super::SoftDeleted;
 use crate::domain::error::DomainError;
 
 /// Repository trait for Address persistence operations.
+///
+/// Deletes are soft: they set `deleted_at`, and reads skip such rows unless
+/// asked for [`SoftDeleted::Include`].
 #[async_trait]
 pub trait AddressesRepository: Send + Sync {
     /// Find an address by ID within the given security scope.
@@ -16,14 +21,35 @@ pub trait AddressesRepository: Send + Sync {
         runner: &C,
         scope: &AccessScope,
         id: Uuid,
+        deleted: SoftDeleted,
     ) -> Result<Option<Address>, DomainError>;
 
+    /// Find a live address by ID within the given security scope, telling an
+    /// address outside the scope apart from a missing one.
+    async fn lookup<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        id: Uuid,
+    ) -> Result<ScopedLookup<Address>, DomainError>;
+
     /// List addresses with cursor-based pagination and `OData` filtering.
     async fn list_page<C: DBRunner>(
         &self,
         runner: &C,
         scope: &AccessScope,
         query: &ODataQuery,
+        deleted: SoftDeleted,
+    ) -> Result<Page<Address>, DomainError>;
+
+    /// List the live addresses of `user_id` with cursor-based pagination and
+    /// `OData` filtering.
+    async fn list_page_by_user_id<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        user_id: Uuid,
+        query: &ODataQuery,
     ) -> Result<Page<Address>, DomainError>;
 
     /// Find an address by user ID.
@@ -32,8 +58,17 @@ pub trait AddressesRepository: Send + Sync {
         runner: &C,
         scope: &AccessScope,
         user_id: Uuid,
+        deleted: SoftDeleted,
     ) -> Result<Option<Address>, DomainError>;
 
+    /// Find all addresses belonging to any of `user_ids`.
+    async fn list_by_user_ids<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        user_ids: &[Uuid],
+    ) -> Result<Vec<Address>, DomainError>;
+
     /// Create a new address.
     async fn create<C: DBRunner>(
         &self,
@@ -42,7 +77,8 @@ pub trait AddressesRepository: Send + Sync {
         address: Address,
     ) -> Result<Address, DomainError>;
 
-    /// Update an existing address.
+    /// Update a live address. Fails with `NotFound` if no live row matched
+    /// the scope, so a soft-deleted address is never revived.
     async fn update<C: DBRunner>(
         &self,
         runner: &C,
@@ -50,19 +86,43 @@ pub trait AddressesRepository: Send + Sync {
         address: Address,
     ) -> Result<Address, DomainError>;
 
-    /// Delete an address by ID.
+    /// Soft-delete a live address by ID, stamping it with `at`. Returns
+    /// `false` if no live row matched.
     async fn delete<C: DBRunner>(
         &self,
         runner: &C,
         scope: &AccessScope,
         id: Uuid,
+        at: OffsetDateTime,
+    ) -> Result<bool, DomainError>;
+
+    /// Clear `deleted_at` on a soft-deleted address. Returns `false` if no
+    /// soft-deleted row matched.
+    async fn restore<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        id: Uuid,
     ) -> Result<bool, DomainError>;
 
-    /// Delete all addresses for a given user ID.
+    /// Soft-delete all live addresses for a given user ID, stamping them
+    /// with `at`.
     async fn delete_by_user_id<C: DBRunner>(
         &self,
         runner: &C,
         scope: &AccessScope,
         user_id: Uuid,
+        at: OffsetDateTime,
+    ) -> Result<u64, DomainError>;
+
+    /// Restore the addresses of `user_id` soft-deleted at exactly `deleted_at`,
+    /// i.e. those removed together with the user. Returns how many were
+    /// restored.
+    async fn restore_by_user_id<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        user_id: Uuid,
+        deleted_at: OffsetDateTime,
     ) -> Result<u64, DomainError>;
 }
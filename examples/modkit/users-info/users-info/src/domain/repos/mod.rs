@@ -5,3 +5,13 @@ mod users_repo;
 pub(crate) use addresses_repo::AddressesRepository;
 pub(crate) use cities_repo::CitiesRepository;
 pub(crate) use users_repo::UsersRepository;
+
+/// Whether a repository read sees soft-deleted rows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SoftDeleted {
+    /// Only live rows (`deleted_at IS NULL`).
+    #[default]
+    Exclude,
+    /// Live and soft-deleted rows.
+    Include,
+}
@@ -1,10 +1,12 @@
 use async_trait::async_trait;
-use modkit_db::secure::DBRunner;
+use modkit_db::secure::{DBRunner, ScopedLookup};
 use modkit_odata::{ODataQuery, Page};
 use modkit_security::AccessScope;
+use time::OffsetDateTime;
 use users_info_sdk::User;
 use uuid::Uuid;
 
+use super::SoftDeleted;
 use crate::domain::error::DomainError;
 
 /// Repository trait for User persistence operations.
@@ -15,6 +17,9 @@ use crate::domain::error::DomainError;
 /// All methods accept:
 /// - `runner: &C` - secure DB runner (`&SecureConn` or `&SecureTx`)
 /// - `scope: &AccessScope` - security scope prepared by the service layer
+///
+/// Deletes are soft: they set `deleted_at`, and reads skip such rows unless
+/// asked for [`SoftDeleted::Include`].
 #[async_trait]
 pub trait UsersRepository: Send + Sync {
     /// Find a user by ID within the given security scope.
@@ -23,14 +28,35 @@ pub trait UsersRepository: Send + Sync {
         runner: &C,
         scope: &AccessScope,
         id: Uuid,
+        deleted: SoftDeleted,
     ) -> Result<Option<User>, DomainError>;
 
+    /// Find a live user by ID within the given security scope, telling a user
+    /// outside the scope apart from a missing one.
+    async fn lookup<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        id: Uuid,
+    ) -> Result<ScopedLookup<User>, DomainError>;
+
     /// List users with cursor-based pagination and `OData` filtering.
     async fn list_page<C: DBRunner>(
         &self,
         runner: &C,
         scope: &AccessScope,
         query: &ODataQuery,
+        deleted: SoftDeleted,
+    ) -> Result<Page<User>, DomainError>;
+
+    /// List live users whose email or display name contains `term`
+    /// (case-insensitive), with cursor-based pagination and `OData` filtering.
+    async fn search_page<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        term: &str,
+        query: &ODataQuery,
     ) -> Result<Page<User>, DomainError>;
 
     /// Create a new user.
@@ -49,15 +75,28 @@ pub trait UsersRepository: Send + Sync {
         user: User,
     ) -> Result<User, DomainError>;
 
-    /// Delete a user by ID.
+    /// Soft-delete a live user by ID, stamping it with `at`. Returns `false`
+    /// if no live row matched.
     async fn delete<C: DBRunner>(
         &self,
         runner: &C,
         scope: &AccessScope,
         id: Uuid,
+        at: OffsetDateTime,
     ) -> Result<bool, DomainError>;
 
+    /// Clear `deleted_at` on a soft-deleted user. Returns the `deleted_at` it
+    /// had, or `None` if no soft-deleted row matched.
+    async fn restore<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        id: Uuid,
+    ) -> Result<Option<OffsetDateTime>, DomainError>;
+
     /// Check if a user with the given ID exists within the scope.
+    ///
+    /// Soft-deleted users count, since their IDs stay reserved.
     async fn exists<C: DBRunner>(
         &self,
         runner: &C,
@@ -66,6 +105,8 @@ pub trait UsersRepository: Send + Sync {
     ) -> Result<bool, DomainError>;
 
     /// Count users matching the given email within the scope.
+    ///
+    /// Soft-deleted users count, since their emails stay reserved.
     async fn count_by_email<C: DBRunner>(
         &self,
         runner: &C,
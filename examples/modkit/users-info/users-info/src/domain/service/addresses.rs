@@ -4,12 +4,13 @@ use modkit_macros::domain_model;
 use tracing::{debug, info, instrument};
 
 use crate::domain::error::DomainError;
-use crate::domain::repos::{AddressesRepository, UsersRepository};
+use crate::domain::repos::{AddressesRepository, SoftDeleted, UsersRepository};
 use crate::domain::service::DbProvider;
 use authz_resolver_sdk::PolicyEnforcer;
 use authz_resolver_sdk::pep::AccessRequest;
 
 use super::{actions, resources};
+use modkit_db::secure::ScopedLookup;
 use modkit_odata::{ODataQuery, Page};
 use modkit_security::{AccessScope, SecurityContext, pep_properties};
 use resources::properties;
@@ -58,7 +59,7 @@ impl<R: AddressesRepository, U: UsersRepository> AddressesService<R, U> {
         let prefetch_scope = AccessScope::allow_all();
         let addr = self
             .repo
-            .get(&conn, &prefetch_scope, id)
+            .get(&conn, &prefetch_scope, id, SoftDeleted::Exclude)
             .await?
             .ok_or_else(|| DomainError::not_found("Address", id))?;
 
@@ -78,23 +79,36 @@ impl<R: AddressesRepository, U: UsersRepository> AddressesService<R, U> {
             .await?;
 
         // Unconstrained → PDP said "yes" without row-level filters; return prefetch.
-        // Constrained  → scoped re-read validates against PDP constraints.
+        // Constrained  → scoped re-read validates against PDP constraints; a row
+        // that exists only outside them is forbidden rather than not found.
         if scope.is_unconstrained() {
-            Ok(addr)
-        } else {
-            self.repo
-                .get(&conn, &scope, id)
-                .await?
-                .ok_or_else(|| DomainError::not_found("Address", id))
+            return Ok(addr);
+        }
+        match self.repo.lookup(&conn, &scope, id).await? {
+            ScopedLookup::Found(addr) => Ok(addr),
+            ScopedLookup::OutOfScope => Err(DomainError::Forbidden { reason: None }),
+            ScopedLookup::NotFound => Err(DomainError::not_found("Address", id)),
         }
     }
 
     /// List addresses with cursor-based pagination
-    #[instrument(skip(self, ctx, query))]
     pub async fn list_addresses_page(
         &self,
         ctx: &SecurityContext,
         query: &ODataQuery,
+    ) -> Result<Page<Address>, DomainError> {
+        self.list_addresses_page_with_deleted(ctx, query, SoftDeleted::Exclude)
+            .await
+    }
+
+    /// List addresses like [`Self::list_addresses_page`], optionally including
+    /// soft-deleted addresses.
+    #[instrument(skip(self, ctx, query))]
+    pub async fn list_addresses_page_with_deleted(
+        &self,
+        ctx: &SecurityContext,
+        query: &ODataQuery,
+        deleted: SoftDeleted,
     ) -> Result<Page<Address>, DomainError> {
         debug!("Listing addresses with cursor pagination");
 
@@ -105,12 +119,64 @@ impl<R: AddressesRepository, U: UsersRepository> AddressesService<R, U> {
             .access_scope(ctx, &resources::ADDRESS, actions::LIST, None)
             .await?;
 
-        let page = self.repo.list_page(&conn, &scope, query).await?;
+        let page = self.repo.list_page(&conn, &scope, query, deleted).await?;
 
         debug!("Successfully listed {} addresses in page", page.items.len());
         Ok(page)
     }
 
+    /// List the addresses of the given users that are visible in the caller's scope.
+    #[instrument(skip(self, ctx, user_ids), fields(user_count = user_ids.len()))]
+    pub async fn list_addresses_for_users(
+        &self,
+        ctx: &SecurityContext,
+        user_ids: &[Uuid],
+    ) -> Result<Vec<Address>, DomainError> {
+        debug!("Listing addresses for users");
+
+        let conn = self.db.conn().map_err(DomainError::from)?;
+
+        let scope = self
+            .policy_enforcer
+            .access_scope(ctx, &resources::ADDRESS, actions::LIST, None)
+            .await?;
+
+        self.repo.list_by_user_ids(&conn, &scope, user_ids).await
+    }
+
+    /// List the addresses of `user_id` with cursor-based pagination.
+    ///
+    /// The user filter is combined with the caller's `list` scope: addresses the
+    /// caller may not see are left out, so listing another user's addresses
+    /// under an owner-only scope yields an empty page.
+    #[instrument(skip(self, ctx, query), fields(user_id = %user_id))]
+    pub async fn list_user_addresses(
+        &self,
+        ctx: &SecurityContext,
+        user_id: Uuid,
+        query: &ODataQuery,
+    ) -> Result<Page<Address>, DomainError> {
+        debug!("Listing addresses for user");
+
+        let conn = self.db.conn().map_err(DomainError::from)?;
+
+        let scope = self
+            .policy_enforcer
+            .access_scope(ctx, &resources::ADDRESS, actions::LIST, None)
+            .await?;
+
+        let page = self
+            .repo
+            .list_page_by_user_id(&conn, &scope, user_id, query)
+            .await?;
+
+        debug!(
+            "Successfully listed {} addresses for user",
+            page.items.len()
+        );
+        Ok(page)
+    }
+
     #[instrument(skip(self, ctx), fields(user_id = %user_id))]
     pub async fn get_user_address(
         &self,
@@ -126,7 +192,10 @@ impl<R: AddressesRepository, U: UsersRepository> AddressesService<R, U> {
             .access_scope(ctx, &resources::ADDRESS, actions::GET, None)
             .await?;
 
-        let found = self.repo.get_by_user_id(&conn, &scope, user_id).await?;
+        let found = self
+            .repo
+            .get_by_user_id(&conn, &scope, user_id, SoftDeleted::Exclude)
+            .await?;
 
         Ok(found)
     }
@@ -158,15 +227,26 @@ impl<R: AddressesRepository, U: UsersRepository> AddressesService<R, U> {
 
         let user = self
             .users_repo
-            .get(&conn, &prefetch_scope, user_id)
+            .get(&conn, &prefetch_scope, user_id, SoftDeleted::Exclude)
             .await?
             .ok_or_else(|| DomainError::user_not_found(user_id))?;
 
         let existing = self
             .repo
-            .get_by_user_id(&conn, &prefetch_scope, user_id)
+            .get_by_user_id(&conn, &prefetch_scope, user_id, SoftDeleted::Exclude)
             .await?;
 
+        // A soft-deleted address still holds the user's slot. Bringing it
+        // back is a restore, authorized separately, never a side effect of PUT.
+        if existing.is_none()
+            && let Some(deleted) = self
+                .repo
+                .get_by_user_id(&conn, &prefetch_scope, user_id, SoftDeleted::Include)
+                .await?
+        {
+            return Err(DomainError::deleted_address_exists(user_id, deleted.id));
+        }
+
         let now = OffsetDateTime::now_utc();
 
         if let Some(existing_model) = existing {
@@ -246,7 +326,7 @@ impl<R: AddressesRepository, U: UsersRepository> AddressesService<R, U> {
         let prefetch_scope = AccessScope::allow_all();
         let existing = self
             .repo
-            .get_by_user_id(&conn, &prefetch_scope, user_id)
+            .get_by_user_id(&conn, &prefetch_scope, user_id, SoftDeleted::Exclude)
             .await?;
         let existing_model = existing.ok_or_else(|| DomainError::not_found("Address", user_id))?;
 
@@ -264,7 +344,10 @@ impl<R: AddressesRepository, U: UsersRepository> AddressesService<R, U> {
             )
             .await?;
 
-        let rows_affected = self.repo.delete_by_user_id(&conn, &scope, user_id).await?;
+        let rows_affected = self
+            .repo
+            .delete_by_user_id(&conn, &scope, user_id, OffsetDateTime::now_utc())
+            .await?;
 
         if rows_affected == 0 {
             return Err(DomainError::not_found("Address", user_id));
@@ -291,7 +374,12 @@ impl<R: AddressesRepository, U: UsersRepository> AddressesService<R, U> {
 
         let user = self
             .users_repo
-            .get(&conn, &prefetch_scope, new_address.user_id)
+            .get(
+                &conn,
+                &prefetch_scope,
+                new_address.user_id,
+                SoftDeleted::Exclude,
+            )
             .await?
             .ok_or_else(|| DomainError::user_not_found(new_address.user_id))?;
 
@@ -348,7 +436,7 @@ impl<R: AddressesRepository, U: UsersRepository> AddressesService<R, U> {
         let prefetch_scope = AccessScope::allow_all();
         let mut current = self
             .repo
-            .get(&conn, &prefetch_scope, id)
+            .get(&conn, &prefetch_scope, id, SoftDeleted::Exclude)
             .await?
             .ok_or_else(|| DomainError::not_found("Address", id))?;
 
@@ -393,7 +481,10 @@ impl<R: AddressesRepository, U: UsersRepository> AddressesService<R, U> {
         // Prefetch: load existing address to extract owner properties for PDP.
         // Authorization is enforced on the delete below via the narrowed scope.
         let prefetch_scope = AccessScope::allow_all();
-        let existing = self.repo.get(&conn, &prefetch_scope, id).await?;
+        let existing = self
+            .repo
+            .get(&conn, &prefetch_scope, id, SoftDeleted::Exclude)
+            .await?;
         let existing_model = existing.ok_or_else(|| DomainError::not_found("Address", id))?;
 
         let scope = self
@@ -410,7 +501,10 @@ impl<R: AddressesRepository, U: UsersRepository> AddressesService<R, U> {
             )
             .await?;
 
-        let deleted = self.repo.delete(&conn, &scope, id).await?;
+        let deleted = self
+            .repo
+            .delete(&conn, &scope, id, OffsetDateTime::now_utc())
+            .await?;
 
         if !deleted {
             return Err(DomainError::not_found("Address", id));
@@ -419,4 +513,59 @@ impl<R: AddressesRepository, U: UsersRepository> AddressesService<R, U> {
         info!("Successfully deleted address");
         Ok(())
     }
+
+    /// Restore a soft-deleted address.
+    ///
+    /// Authorized like [`Self::delete_address`]. Restoring an address that is
+    /// not deleted is a no-op that returns the address unchanged.
+    #[instrument(skip(self, ctx), fields(address_id = %id))]
+    pub async fn restore_address(
+        &self,
+        ctx: &SecurityContext,
+        id: Uuid,
+    ) -> Result<Address, DomainError> {
+        info!("Restoring address");
+
+        let conn = self.db.conn().map_err(DomainError::from)?;
+
+        // Prefetch (deleted rows included): load address to extract owner properties for PDP.
+        // Authorization is enforced on the restore below via the narrowed scope.
+        let prefetch_scope = AccessScope::allow_all();
+        let existing_model = self
+            .repo
+            .get(&conn, &prefetch_scope, id, SoftDeleted::Include)
+            .await?
+            .ok_or_else(|| DomainError::not_found("Address", id))?;
+
+        let scope = self
+            .policy_enforcer
+            .access_scope_with(
+                ctx,
+                &resources::ADDRESS,
+                actions::DELETE,
+                Some(id),
+                &AccessRequest::new()
+                    .resource_property(pep_properties::OWNER_TENANT_ID, existing_model.tenant_id)
+                    .resource_property(pep_properties::OWNER_ID, existing_model.user_id)
+                    .resource_property(properties::CITY_ID, existing_model.city_id),
+            )
+            .await?;
+
+        let restored = self.repo.restore(&conn, &scope, id).await?;
+
+        // Nothing restored: either the address is live already (no-op) or the
+        // row is outside the caller's scope.
+        let address = self
+            .repo
+            .get(&conn, &scope, id, SoftDeleted::Exclude)
+            .await?
+            .ok_or_else(|| DomainError::not_found("Address", id))?;
+
+        if restored {
+            info!("Successfully restored address");
+        } else {
+            debug!("Address is not deleted; nothing to restore");
+        }
+        Ok(address)
+    }
 }
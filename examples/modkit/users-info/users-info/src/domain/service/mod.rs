@@ -58,6 +58,7 @@
 use std::sync::Arc;
 
 use modkit_macros::domain_model;
+use serde::{Deserialize, Serialize};
 
 use crate::domain::events::UserDomainEvent;
 use crate::domain::ports::{AuditPort, EventPublisher, UsersMetricsPort};
@@ -144,10 +145,22 @@ pub(crate) mod actions {
 
 pub(crate) use addresses::AddressesService;
 pub(crate) use cities::CitiesService;
-pub(crate) use users::UsersService;
+pub(crate) use users::{BulkUserResult, ExpandedUser, USER_EXPANDABLE, UsersService};
 
 pub(crate) type DbProvider = DBProvider<modkit_db::DbError>;
 
+/// What `delete_user` does when the user still has live addresses.
+#[domain_model]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserDeletePolicy {
+    /// Refuse the delete and report the dependent addresses.
+    #[default]
+    Restrict,
+    /// Soft-delete the addresses together with the user in one transaction.
+    Cascade,
+}
+
 /// Configuration for the domain service
 #[domain_model]
 #[derive(Debug, Clone)]
@@ -155,6 +168,7 @@ pub struct ServiceConfig {
     pub max_display_name_length: usize,
     pub default_page_size: u32,
     pub max_page_size: u32,
+    pub user_delete_policy: UserDeletePolicy,
 }
 
 impl Default for ServiceConfig {
@@ -163,6 +177,7 @@ impl Default for ServiceConfig {
             max_display_name_length: 100,
             default_page_size: 50,
             max_page_size: 1000,
+            user_delete_policy: UserDeletePolicy::default(),
         }
     }
 }
@@ -192,7 +207,7 @@ pub(crate) struct AppServices<UR, CR, AR>
 where
     UR: UsersRepository + 'static,
     CR: CitiesRepository,
-    AR: AddressesRepository,
+    AR: AddressesRepository + 'static,
 {
     pub(crate) users: UsersService<UR, CR, AR>,
     pub(crate) cities: Arc<CitiesService<CR>>,
@@ -214,11 +229,32 @@ mod tests_entities;
 #[cfg(test)]
 mod tests_cursor_pagination;
 
+#[cfg(test)]
+mod tests_expand;
+
+#[cfg(test)]
+mod tests_query_fields;
+
+#[cfg(test)]
+mod tests_soft_delete;
+
+#[cfg(test)]
+mod tests_bulk_create;
+
+#[cfg(test)]
+mod tests_search;
+
+#[cfg(test)]
+mod tests_email_uniqueness;
+
+#[cfg(test)]
+mod tests_delete_policy;
+
 impl<UR, CR, AR> AppServices<UR, CR, AR>
 where
     UR: UsersRepository + 'static,
     CR: CitiesRepository,
-    AR: AddressesRepository,
+    AR: AddressesRepository + 'static,
 {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -260,6 +296,7 @@ where
                 config,
                 cities.clone(),
                 addresses.clone(),
+                addresses_repo,
                 metrics,
             ),
             cities,
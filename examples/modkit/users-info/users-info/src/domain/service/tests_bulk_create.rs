@@ -0,0 +1,161 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use modkit_odata::ODataQuery;
+use users_info_sdk::NewUser;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::ports::AuditPort;
+use crate::domain::service::ServiceConfig;
+use crate::test_support::{
+    build_services, build_services_with_audit, ctx_allow_tenants, inmem_db, seed_user,
+};
+
+/// Audit port counting `notify_user_created` calls.
+#[derive(Default)]
+struct CountingAuditPort {
+    created: AtomicUsize,
+}
+
+#[async_trait::async_trait]
+impl AuditPort for CountingAuditPort {
+    async fn get_user_access(&self, _id: Uuid) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn notify_user_created(&self) -> Result<(), DomainError> {
+        self.created.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+fn new_user(tenant_id: Uuid, email: &str) -> NewUser {
+    NewUser {
+        id: None,
+        tenant_id,
+        email: email.to_owned(),
+        display_name: format!("User {email}"),
+    }
+}
+
+#[tokio::test]
+async fn bulk_create_partitions_valid_and_duplicate_rows() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, Uuid::new_v4(), tenant, "taken@example.com", "Taken").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    let results = services
+        .users
+        .create_users_bulk(
+            &ctx,
+            vec![
+                new_user(tenant, "a@example.com"),
+                new_user(tenant, "taken@example.com"),
+                new_user(tenant, "b@example.com"),
+                new_user(tenant, "a@example.com"),
+                new_user(tenant, "not-an-email"),
+            ],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 5);
+    assert!(results.iter().enumerate().all(|(i, r)| r.index == i));
+
+    let created: Vec<usize> = results
+        .iter()
+        .filter(|r| r.result.is_ok())
+        .map(|r| r.index)
+        .collect();
+    assert_eq!(created, vec![0, 2]);
+
+    for index in [1, 3] {
+        assert!(
+            matches!(
+                &results[index].result,
+                Err(DomainError::EmailAlreadyExists { .. })
+            ),
+            "Expected duplicate email at row {index}, got: {:?}",
+            results[index].result
+        );
+    }
+    assert!(matches!(
+        results[4].result,
+        Err(DomainError::InvalidEmail { .. })
+    ));
+
+    let page = services
+        .users
+        .list_users_page(&ctx, &ODataQuery::default())
+        .await
+        .unwrap();
+    let mut emails: Vec<&str> = page.items.iter().map(|u| u.email.as_str()).collect();
+    emails.sort_unstable();
+    assert_eq!(
+        emails,
+        vec!["a@example.com", "b@example.com", "taken@example.com"]
+    );
+}
+
+#[tokio::test]
+async fn bulk_create_checks_tenant_scope_per_row() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let other_tenant = Uuid::new_v4();
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    let results = services
+        .users
+        .create_users_bulk(
+            &ctx,
+            vec![
+                new_user(other_tenant, "foreign@example.com"),
+                new_user(tenant, "own@example.com"),
+            ],
+        )
+        .await
+        .unwrap();
+
+    assert!(
+        matches!(results[0].result, Err(DomainError::Forbidden { .. })),
+        "Expected foreign-tenant row to be denied, got: {:?}",
+        results[0].result
+    );
+    let own = results[1].result.as_ref().unwrap();
+    assert_eq!(own.tenant_id, tenant);
+}
+
+#[tokio::test]
+async fn bulk_create_notifies_audit_per_created_user() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let audit = Arc::new(CountingAuditPort::default());
+
+    let services = build_services_with_audit(db, ServiceConfig::default(), audit.clone());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    let results = services
+        .users
+        .create_users_bulk(
+            &ctx,
+            vec![
+                new_user(tenant, "a@example.com"),
+                new_user(tenant, "a@example.com"),
+                new_user(tenant, "b@example.com"),
+            ],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(results.iter().filter(|r| r.result.is_ok()).count(), 2);
+    assert_eq!(audit.created.load(Ordering::SeqCst), 2);
+}
@@ -5,7 +5,10 @@ use modkit_odata::{CursorV1, ODataQuery};
 use uuid::Uuid;
 
 use crate::domain::service::ServiceConfig;
-use crate::test_support::{build_services, ctx_allow_tenants, ctx_deny_all, inmem_db, seed_user};
+use crate::test_support::{
+    build_services, ctx_allow_tenants, ctx_deny_all, inmem_db, seed_user,
+    seed_user_with_display_name,
+};
 
 async fn seed_users_sequential(db: &impl DBRunner, count: usize, tenant_id: Uuid) -> Vec<Uuid> {
     let mut ids = Vec::with_capacity(count);
@@ -80,3 +83,151 @@ async fn deny_all_returns_forbidden() {
         "Expected Forbidden error for anonymous context"
     );
 }
+
+#[tokio::test]
+async fn count_reports_scoped_filtered_total_independent_of_page() {
+    use modkit_sdk::odata::QueryBuilder;
+    use users_info_sdk::odata::{USER_EMAIL, UserSchema};
+
+    let db = inmem_db().await;
+    let tenant_id = Uuid::new_v4();
+    let other_tenant = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_users_sequential(&conn, 12, tenant_id).await;
+    for i in 0..4 {
+        let email = format!("other{i}@example.com");
+        seed_user(&conn, Uuid::new_v4(), other_tenant, &email, "Other").await;
+    }
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant_id]);
+
+    let query = ODataQuery::default().with_limit(5).with_count(true);
+    let first = services.users.list_users_page(&ctx, &query).await.unwrap();
+    assert_eq!(first.items.len(), 5);
+    assert_eq!(first.page_info.total_count, Some(12));
+
+    let cursor = CursorV1::decode(first.page_info.next_cursor.as_deref().unwrap()).unwrap();
+    let second = services
+        .users
+        .list_users_page(&ctx, &query.with_cursor(cursor))
+        .await
+        .unwrap();
+    assert_eq!(second.items.len(), 5);
+    assert_eq!(second.page_info.total_count, Some(12));
+
+    // user1, user10, user11
+    let filtered = QueryBuilder::<UserSchema>::new()
+        .filter(USER_EMAIL.contains("user1"))
+        .page_size(2)
+        .with_count()
+        .build();
+    let page = services
+        .users
+        .list_users_page(&ctx, &filtered)
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.page_info.total_count, Some(3));
+
+    let page = services
+        .users
+        .list_users_page(&ctx, &ODataQuery::default().with_limit(5))
+        .await
+        .unwrap();
+    assert_eq!(page.page_info.total_count, None);
+}
+
+#[tokio::test]
+async fn keyset_pages_are_stable_across_concurrent_insert() {
+    let db = inmem_db().await;
+    let tenant_id = Uuid::new_v4();
+    let other_tenant = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    let seeded = seed_users_sequential(&conn, 30, tenant_id).await;
+    seed_users_sequential(&conn, 3, other_tenant).await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant_id]);
+
+    let mut query = ODataQuery::default().with_limit(10);
+    let mut pages = Vec::new();
+
+    loop {
+        let page = services.users.list_users_page(&ctx, &query).await.unwrap();
+        pages.push(page.items.iter().map(|u| u.id).collect::<Vec<_>>());
+
+        if pages.len() == 1 {
+            // A row created between page fetches sorts before the cursor
+            seed_user(&conn, Uuid::new_v4(), tenant_id, "late@example.com", "Late").await;
+        }
+
+        match page.page_info.next_cursor {
+            Some(c) => query = query.clone().with_cursor(CursorV1::decode(&c).unwrap()),
+            None => break,
+        }
+    }
+
+    assert_eq!(pages.len(), 3);
+    assert!(pages.iter().all(|p| p.len() == 10));
+
+    // Newest first on (created_at, id): exactly the seeded rows, no duplicates or gaps
+    let fetched: Vec<Uuid> = pages.into_iter().flatten().collect();
+    let expected: Vec<Uuid> = seeded.into_iter().rev().collect();
+    assert_eq!(fetched, expected);
+}
+
+#[tokio::test]
+async fn orderby_multiple_keys_with_per_key_direction() {
+    let db = inmem_db().await;
+    let tenant_id = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    for (email, name) in [
+        ("a@example.com", Some("Bob")),
+        ("b@example.com", Some("Alice")),
+        ("c@example.com", None),
+        ("d@example.com", Some("Alice")),
+        ("e@example.com", Some("Bob")),
+        ("f@example.com", None),
+    ] {
+        seed_user_with_display_name(&conn, Uuid::new_v4(), tenant_id, email, name).await;
+    }
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant_id]);
+
+    // Pages of two cross from the NULL names (sorted first) into the named ones
+    let order = modkit::api::odata::parse_orderby("display_name asc,email desc").unwrap();
+    let mut query = ODataQuery::default().with_order(order).with_limit(2);
+    let mut pages = Vec::new();
+    loop {
+        let page = services.users.list_users_page(&ctx, &query).await.unwrap();
+        let emails: Vec<String> = page.items.iter().map(|u| u.email.clone()).collect();
+        pages.push((emails, page.page_info.prev_cursor.clone()));
+        match page.page_info.next_cursor {
+            Some(c) => query = query.clone().with_cursor(CursorV1::decode(&c).unwrap()),
+            None => break,
+        }
+    }
+
+    let fetched: Vec<Vec<String>> = pages.iter().map(|(emails, _)| emails.clone()).collect();
+    assert_eq!(
+        fetched,
+        [
+            ["f@example.com", "c@example.com"],
+            ["d@example.com", "b@example.com"],
+            ["e@example.com", "a@example.com"],
+        ]
+    );
+
+    // Walking back from the named rows lands on the NULL page again
+    let prev = pages[1]
+        .1
+        .as_deref()
+        .expect("second page has a prev cursor");
+    let query = query.with_cursor(CursorV1::decode(prev).unwrap());
+    let page = services.users.list_users_page(&ctx, &query).await.unwrap();
+    let emails: Vec<&str> = page.items.iter().map(|u| u.email.as_str()).collect();
+    assert_eq!(emails, ["f@example.com", "c@example.com"]);
+    assert!(page.items.iter().all(|u| u.display_name.is_empty()));
+}
@@ -0,0 +1,184 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use modkit_odata::ODataQuery;
+use modkit_security::SecurityContext;
+use users_info_sdk::{Address, NewAddress, NewCity, UsersInfoError};
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::repos::SoftDeleted;
+use crate::domain::service::{ServiceConfig, UserDeletePolicy};
+use crate::module::ConcreteAppServices;
+use crate::test_support::{build_services, ctx_allow_tenants, inmem_db, seed_user};
+
+async fn seed_address(
+    services: &ConcreteAppServices,
+    ctx: &SecurityContext,
+    tenant_id: Uuid,
+    user_id: Uuid,
+) -> Address {
+    let city = services
+        .cities
+        .create_city(
+            ctx,
+            NewCity {
+                id: None,
+                tenant_id,
+                name: "Delete City".to_owned(),
+                country: "DC".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+    services
+        .addresses
+        .create_address(
+            ctx,
+            NewAddress {
+                id: None,
+                tenant_id,
+                user_id,
+                city_id: city.id,
+                street: "1 Dependent St".to_owned(),
+                postal_code: "12345".to_owned(),
+            },
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn restrict_blocks_delete_of_user_with_address() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, user_id, tenant, "restrict@example.com", "Restrict").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+    let address = seed_address(&services, &ctx, tenant, user_id).await;
+
+    let err = services.users.delete_user(&ctx, user_id).await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            DomainError::UserHasAddresses { id, ref addresses }
+                if id == user_id && addresses == &[address.id]
+        ),
+        "Expected UserHasAddresses, got: {err:?}"
+    );
+    assert!(matches!(
+        UsersInfoError::from(err),
+        UsersInfoError::Conflict { identifier } if identifier == address.id.to_string()
+    ));
+
+    // Both rows are untouched.
+    services.users.get_user(&ctx, user_id).await.unwrap();
+    services
+        .addresses
+        .get_address(&ctx, address.id)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn cascade_deletes_user_and_address() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, user_id, tenant, "cascade@example.com", "Cascade").await;
+
+    let config = ServiceConfig {
+        user_delete_policy: UserDeletePolicy::Cascade,
+        ..ServiceConfig::default()
+    };
+    let services = build_services(db.clone(), config);
+    let ctx = ctx_allow_tenants(&[tenant]);
+    let address = seed_address(&services, &ctx, tenant, user_id).await;
+
+    services.users.delete_user(&ctx, user_id).await.unwrap();
+
+    let err = services.users.get_user(&ctx, user_id).await.unwrap_err();
+    assert!(matches!(err, DomainError::UserNotFound { id } if id == user_id));
+    let err = services
+        .addresses
+        .get_address(&ctx, address.id)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, DomainError::NotFound { id, .. } if id == address.id),
+        "Expected the address to be deleted, got: {err:?}"
+    );
+
+    // The cascade is a soft delete, so the address is still recoverable.
+    let page = services
+        .addresses
+        .list_addresses_page_with_deleted(&ctx, &ODataQuery::default(), SoftDeleted::Include)
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 1);
+}
+
+#[tokio::test]
+async fn restoring_user_restores_cascaded_address() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, user_id, tenant, "revive@example.com", "Revive").await;
+
+    let config = ServiceConfig {
+        user_delete_policy: UserDeletePolicy::Cascade,
+        ..ServiceConfig::default()
+    };
+    let services = build_services(db.clone(), config);
+    let ctx = ctx_allow_tenants(&[tenant]);
+    let address = seed_address(&services, &ctx, tenant, user_id).await;
+
+    services.users.delete_user(&ctx, user_id).await.unwrap();
+    services.users.restore_user(&ctx, user_id).await.unwrap();
+
+    let restored = services
+        .addresses
+        .get_address(&ctx, address.id)
+        .await
+        .unwrap();
+    assert_eq!(restored.user_id, user_id);
+}
+
+#[tokio::test]
+async fn restoring_user_keeps_separately_deleted_address_deleted() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, user_id, tenant, "apart@example.com", "Apart").await;
+
+    let config = ServiceConfig {
+        user_delete_policy: UserDeletePolicy::Cascade,
+        ..ServiceConfig::default()
+    };
+    let services = build_services(db.clone(), config);
+    let ctx = ctx_allow_tenants(&[tenant]);
+    let address = seed_address(&services, &ctx, tenant, user_id).await;
+
+    services
+        .addresses
+        .delete_address(&ctx, address.id)
+        .await
+        .unwrap();
+    services.users.delete_user(&ctx, user_id).await.unwrap();
+    services.users.restore_user(&ctx, user_id).await.unwrap();
+
+    let err = services
+        .addresses
+        .get_address(&ctx, address.id)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, DomainError::NotFound { id, .. } if id == address.id),
+        "Expected the address to stay deleted, got: {err:?}"
+    );
+}
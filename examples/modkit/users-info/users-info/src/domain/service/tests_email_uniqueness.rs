@@ -0,0 +1,141 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use modkit_security::AccessScope;
+use time::OffsetDateTime;
+use users_info_sdk::{NewUser, User, UsersInfoError};
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::repos::UsersRepository;
+use crate::domain::service::ServiceConfig;
+use crate::infra::storage::OrmUsersRepository;
+use crate::test_support::{build_services, ctx_allow_tenants, inmem_db, seed_user};
+
+fn new_user(tenant_id: Uuid, email: &str) -> NewUser {
+    NewUser {
+        id: None,
+        tenant_id,
+        email: email.to_owned(),
+        display_name: format!("User {email}"),
+    }
+}
+
+#[tokio::test]
+async fn duplicate_email_in_same_tenant_is_conflict() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    services
+        .users
+        .create_user(&ctx, new_user(tenant, "dup@example.com"))
+        .await
+        .unwrap();
+    let err = services
+        .users
+        .create_user(&ctx, new_user(tenant, "dup@example.com"))
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, DomainError::EmailAlreadyExists { ref email } if email == "dup@example.com"),
+        "Expected EmailAlreadyExists, got: {err:?}"
+    );
+    assert!(matches!(
+        UsersInfoError::from(err),
+        UsersInfoError::Conflict { identifier } if identifier == "dup@example.com"
+    ));
+}
+
+#[tokio::test]
+async fn same_email_in_different_tenant_succeeds() {
+    let db = inmem_db().await;
+    let tenant1 = Uuid::new_v4();
+    let tenant2 = Uuid::new_v4();
+    let services = build_services(db.clone(), ServiceConfig::default());
+
+    let first = services
+        .users
+        .create_user(
+            &ctx_allow_tenants(&[tenant1]),
+            new_user(tenant1, "shared@example.com"),
+        )
+        .await
+        .unwrap();
+    let second = services
+        .users
+        .create_user(
+            &ctx_allow_tenants(&[tenant2]),
+            new_user(tenant2, "shared@example.com"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(first.tenant_id, tenant1);
+    assert_eq!(second.tenant_id, tenant2);
+    assert_eq!(second.email, "shared@example.com");
+}
+
+#[tokio::test]
+async fn repo_maps_unique_violation_to_email_conflict() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, Uuid::new_v4(), tenant, "race@example.com", "Race").await;
+
+    // Insert directly, as a concurrent request that passed the pre-check would.
+    let repo = OrmUsersRepository::new(ServiceConfig::default().limit_cfg());
+    let now = OffsetDateTime::now_utc();
+    let user = User {
+        id: Uuid::now_v7(),
+        tenant_id: tenant,
+        email: "race@example.com".to_owned(),
+        display_name: "Race".to_owned(),
+        created_at: now,
+        updated_at: now,
+    };
+    let err = repo
+        .create(&conn, &AccessScope::for_tenant(tenant), user)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, DomainError::EmailAlreadyExists { ref email } if email == "race@example.com"),
+        "Expected EmailAlreadyExists, got: {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn repo_maps_other_unique_violations_to_generic_conflict() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    let id = Uuid::new_v4();
+    seed_user(&conn, id, tenant, "first@example.com", "First").await;
+
+    // Same primary key, different email: not an email clash.
+    let repo = OrmUsersRepository::new(ServiceConfig::default().limit_cfg());
+    let now = OffsetDateTime::now_utc();
+    let user = User {
+        id,
+        tenant_id: tenant,
+        email: "second@example.com".to_owned(),
+        display_name: "Second".to_owned(),
+        created_at: now,
+        updated_at: now,
+    };
+    let err = repo
+        .create(&conn, &AccessScope::for_tenant(tenant), user)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, DomainError::Conflict { id: conflict_id } if conflict_id == id),
+        "Expected Conflict, got: {err:?}"
+    );
+    assert!(matches!(
+        UsersInfoError::from(err),
+        UsersInfoError::Conflict { identifier } if identifier == id.to_string()
+    ));
+}
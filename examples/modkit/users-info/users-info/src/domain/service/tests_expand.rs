@@ -0,0 +1,121 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use modkit_db::secure::{AccessScope, secure_insert};
+use modkit_odata::ODataQuery;
+use sea_orm::Set;
+use time::OffsetDateTime;
+use users_info_sdk::{NewAddress, NewCity};
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::repos::SoftDeleted;
+use crate::domain::service::ServiceConfig;
+use crate::infra::storage::entity::address::{ActiveModel as AddressAM, Entity as AddressEntity};
+use crate::test_support::{build_services, ctx_allow_tenants, inmem_db, seed_user};
+
+#[tokio::test]
+async fn expand_addresses_embeds_only_scoped_rows() {
+    let db = inmem_db().await;
+    let tenant1 = Uuid::new_v4();
+    let tenant2 = Uuid::new_v4();
+    let with_address = Uuid::new_v4();
+    let without_address = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, with_address, tenant1, "a@example.com", "A").await;
+    seed_user(&conn, without_address, tenant1, "b@example.com", "B").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant1]);
+
+    let city = services
+        .cities
+        .create_city(
+            &ctx,
+            NewCity {
+                id: None,
+                tenant_id: tenant1,
+                name: "Expand City".to_owned(),
+                country: "EC".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+    let address = services
+        .addresses
+        .create_address(
+            &ctx,
+            NewAddress {
+                id: None,
+                tenant_id: tenant1,
+                user_id: with_address,
+                city_id: city.id,
+                street: "1 Scoped St".to_owned(),
+                postal_code: "11111".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+
+    // A row in another tenant pointing at a tenant1 user must not leak through $expand
+    let now = OffsetDateTime::now_utc();
+    let foreign = AddressAM {
+        id: Set(Uuid::new_v4()),
+        tenant_id: Set(tenant2),
+        user_id: Set(without_address),
+        city_id: Set(city.id),
+        street: Set("2 Foreign St".to_owned()),
+        postal_code: Set("22222".to_owned()),
+        created_at: Set(now),
+        updated_at: Set(now),
+        deleted_at: Set(None),
+    };
+    secure_insert::<AddressEntity>(foreign, &AccessScope::for_tenants(vec![tenant2]), &conn)
+        .await
+        .unwrap();
+
+    let query = ODataQuery::default().with_expand(vec!["addresses".to_owned()]);
+    let page = services
+        .users
+        .list_users_page_expanded(&ctx, &query, SoftDeleted::Exclude)
+        .await
+        .unwrap();
+
+    assert_eq!(page.items.len(), 2);
+    for item in &page.items {
+        let addresses = item.addresses.as_ref().expect("addresses expanded");
+        if item.user.id == with_address {
+            assert_eq!(addresses.len(), 1);
+            assert_eq!(addresses[0].id, address.id);
+        } else {
+            assert!(addresses.is_empty(), "foreign-tenant address leaked");
+        }
+    }
+
+    // Without $expand nothing is embedded
+    let page = services
+        .users
+        .list_users_page_expanded(&ctx, &ODataQuery::default(), SoftDeleted::Exclude)
+        .await
+        .unwrap();
+    assert!(page.items.iter().all(|item| item.addresses.is_none()));
+}
+
+#[tokio::test]
+async fn unknown_expand_target_is_rejected() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    let query = ODataQuery::default().with_expand(vec!["manager".to_owned()]);
+    let err = services
+        .users
+        .list_users_page_expanded(&ctx, &query, SoftDeleted::Exclude)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, DomainError::Validation { ref field, .. } if field == "$expand"),
+        "Expected validation error for unknown expand target, got: {err:?}"
+    );
+}
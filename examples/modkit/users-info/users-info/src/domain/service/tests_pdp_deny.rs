@@ -4,11 +4,14 @@ use uuid::Uuid;
 
 use std::sync::Arc;
 
+use authz_resolver_sdk::DenyKind;
+use modkit::api::problem::Problem;
+
 use crate::domain::error::DomainError;
 use crate::domain::service::ServiceConfig;
 use crate::test_support::{
-    DenyAllAuthZResolver, build_services, build_services_with_authz, ctx_allow_tenants, inmem_db,
-    seed_user,
+    DenyAllAuthZResolver, build_services, build_services_with_authz, ctx_allow_tenants,
+    ctx_deny_all, inmem_db, seed_user,
 };
 use users_info_sdk::{NewAddress, NewCity, NewUser};
 
@@ -39,7 +42,7 @@ async fn pdp_denied_returns_forbidden_for_list_users() {
         .unwrap_err();
 
     assert!(
-        matches!(err, DomainError::Forbidden),
+        matches!(err, DomainError::Forbidden { .. }),
         "Expected DomainError::Forbidden from explicit PDP denial, got: {err:?}"
     );
 }
@@ -63,7 +66,7 @@ async fn pdp_denied_returns_forbidden_for_get_user() {
     let err = services.users.get_user(&ctx, user_id).await.unwrap_err();
 
     assert!(
-        matches!(err, DomainError::Forbidden),
+        matches!(err, DomainError::Forbidden { .. }),
         "Expected DomainError::Forbidden for get_user, got: {err:?}"
     );
 }
@@ -96,7 +99,7 @@ async fn pdp_denied_returns_forbidden_for_create_user() {
         .unwrap_err();
 
     assert!(
-        matches!(err, DomainError::Forbidden),
+        matches!(err, DomainError::Forbidden { .. }),
         "Expected DomainError::Forbidden for create_user, got: {err:?}"
     );
 }
@@ -131,7 +134,7 @@ async fn pdp_denied_returns_forbidden_for_update_user() {
         .unwrap_err();
 
     assert!(
-        matches!(err, DomainError::Forbidden),
+        matches!(err, DomainError::Forbidden { .. }),
         "Expected DomainError::Forbidden for update_user, got: {err:?}"
     );
 }
@@ -155,7 +158,7 @@ async fn pdp_denied_returns_forbidden_for_delete_user() {
     let err = services.users.delete_user(&ctx, user_id).await.unwrap_err();
 
     assert!(
-        matches!(err, DomainError::Forbidden),
+        matches!(err, DomainError::Forbidden { .. }),
         "Expected DomainError::Forbidden for delete_user, got: {err:?}"
     );
 }
@@ -180,7 +183,7 @@ async fn pdp_denied_returns_forbidden_for_list_addresses() {
         .unwrap_err();
 
     assert!(
-        matches!(err, DomainError::Forbidden),
+        matches!(err, DomainError::Forbidden { .. }),
         "Expected DomainError::Forbidden for list_addresses, got: {err:?}"
     );
 }
@@ -251,7 +254,7 @@ async fn pdp_denied_returns_forbidden_for_get_address() {
         .unwrap_err();
 
     assert!(
-        matches!(err, DomainError::Forbidden),
+        matches!(err, DomainError::Forbidden { .. }),
         "Expected DomainError::Forbidden for get_address, got: {err:?}"
     );
 }
@@ -308,7 +311,7 @@ async fn pdp_denied_returns_forbidden_for_create_address() {
         .unwrap_err();
 
     assert!(
-        matches!(err, DomainError::Forbidden),
+        matches!(err, DomainError::Forbidden { .. }),
         "Expected DomainError::Forbidden for create_address, got: {err:?}"
     );
 }
@@ -377,7 +380,7 @@ async fn pdp_denied_returns_forbidden_for_delete_address() {
         .unwrap_err();
 
     assert!(
-        matches!(err, DomainError::Forbidden),
+        matches!(err, DomainError::Forbidden { .. }),
         "Expected DomainError::Forbidden for delete_address, got: {err:?}"
     );
 }
@@ -406,11 +409,55 @@ async fn decision_false_returns_forbidden_for_list_users() {
         .unwrap_err();
 
     assert!(
-        matches!(err, DomainError::Forbidden),
+        matches!(
+            err,
+            DomainError::Forbidden {
+                reason: Some(DenyKind::PdpDenied)
+            }
+        ),
         "Expected DomainError::Forbidden from decision=false, got: {err:?}"
     );
 }
 
+/// Explicit `decision=false` and missing constraints carry distinct reason
+/// codes but both map to HTTP 403.
+#[tokio::test]
+async fn deny_paths_have_distinct_reasons_and_map_to_403() {
+    let db = inmem_db().await;
+    let tenant_id = Uuid::new_v4();
+
+    let deny_services = build_services_with_authz(
+        db.clone(),
+        ServiceConfig::default(),
+        Arc::new(DenyAllAuthZResolver),
+    );
+    let explicit = deny_services
+        .users
+        .list_users_page(
+            &ctx_allow_tenants(&[tenant_id]),
+            &modkit_odata::ODataQuery::default(),
+        )
+        .await
+        .unwrap_err();
+
+    // Anonymous context -> no tenant -> empty constraints -> ConstraintsRequiredButAbsent
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let absent = services
+        .users
+        .list_users_page(&ctx_deny_all(), &modkit_odata::ODataQuery::default())
+        .await
+        .unwrap_err();
+
+    let explicit = Problem::from(explicit);
+    let absent = Problem::from(absent);
+    assert_eq!(explicit.status, http::StatusCode::FORBIDDEN);
+    assert_eq!(absent.status, http::StatusCode::FORBIDDEN);
+
+    let reason = |p: &Problem| p.context.as_ref().unwrap()["reason"].clone();
+    assert_eq!(reason(&explicit), "PDP_DENIED");
+    assert_eq!(reason(&absent), "CONSTRAINTS_REQUIRED_BUT_ABSENT");
+}
+
 /// PDP returns `decision=false` on `create_address` → `DomainError::Forbidden`.
 #[tokio::test]
 async fn decision_false_returns_forbidden_for_create_address() {
@@ -463,7 +510,7 @@ async fn decision_false_returns_forbidden_for_create_address() {
         .unwrap_err();
 
     assert!(
-        matches!(err, DomainError::Forbidden),
+        matches!(err, DomainError::Forbidden { .. }),
         "Expected DomainError::Forbidden from decision=false, got: {err:?}"
     );
 }
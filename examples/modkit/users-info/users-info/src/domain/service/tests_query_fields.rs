@@ -0,0 +1,88 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use modkit_odata::ODataQuery;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::repos::SoftDeleted;
+use crate::domain::service::ServiceConfig;
+use crate::test_support::{build_services, ctx_allow_tenants, inmem_db, seed_user};
+
+fn filtered(raw: &str) -> ODataQuery {
+    let parsed = modkit_odata::parse_filter_string(raw).unwrap();
+    ODataQuery::default().with_filter(parsed.into_expr())
+}
+
+fn assert_rejected(err: &DomainError, param: &str, name: &str) {
+    assert!(
+        matches!(err, DomainError::Validation { field, message } if field == param && message.contains(name)),
+        "Expected validation error for {param} naming '{name}', got: {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn unknown_select_field_is_rejected() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    let query = ODataQuery::default().with_select(vec!["id".to_owned(), "ssn".to_owned()]);
+    let err = services
+        .users
+        .list_users_page(&ctx, &query)
+        .await
+        .unwrap_err();
+
+    assert_rejected(&err, "$select", "ssn");
+}
+
+#[tokio::test]
+async fn known_select_fields_pass() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, Uuid::new_v4(), tenant, "a@example.com", "A").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    let query =
+        ODataQuery::default().with_select(vec!["Email".to_owned(), "display_name".to_owned()]);
+    let page = services.users.list_users_page(&ctx, &query).await.unwrap();
+    assert_eq!(page.items.len(), 1);
+
+    // Expanded relations may be selected alongside the user fields
+    let query = ODataQuery::default()
+        .with_select(vec!["id".to_owned(), "addresses".to_owned()])
+        .with_expand(vec!["addresses".to_owned()]);
+    let page = services
+        .users
+        .list_users_page_expanded(&ctx, &query, SoftDeleted::Exclude)
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 1);
+}
+
+#[tokio::test]
+async fn unknown_filter_and_orderby_fields_are_rejected() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    let err = services
+        .users
+        .list_users_page(&ctx, &filtered("email eq 'a' and secret eq 1"))
+        .await
+        .unwrap_err();
+    assert_rejected(&err, "$filter", "secret");
+
+    let order = modkit::api::odata::parse_orderby("email asc,salary desc").unwrap();
+    let err = services
+        .users
+        .list_users_page(&ctx, &ODataQuery::default().with_order(order))
+        .await
+        .unwrap_err();
+    assert_rejected(&err, "$orderby", "salary");
+}
@@ -1,12 +1,15 @@
 #![allow(clippy::unwrap_used, clippy::expect_used)]
 
+use modkit_odata::ODataQuery;
 use uuid::Uuid;
 
 use std::sync::Arc;
 
+use crate::domain::error::DomainError;
 use crate::domain::service::ServiceConfig;
 use crate::test_support::{
-    OwnerCityAuthZResolver, build_services_with_authz, ctx_for_subject, inmem_db, seed_user,
+    OwnerCityAuthZResolver, OwnerOnlyAuthZResolver, build_services_with_authz, ctx_for_subject,
+    inmem_db, seed_user,
 };
 use users_info_sdk::{NewAddress, NewCity};
 
@@ -344,3 +347,156 @@ async fn city_scope_restricts_address_to_allowed_city() {
         "User 2 must not be able to delete user 1's address (owner scope)"
     );
 }
+
+/// Users list their own addresses; listing another user's addresses combines the
+/// user filter with `eq(owner_id, subject.id)` and yields an empty page.
+#[tokio::test]
+async fn owner_scope_limits_listing_user_addresses() {
+    let db = inmem_db().await;
+    let tenant_id = Uuid::new_v4();
+    let user_a = Uuid::new_v4();
+    let user_b = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, user_a, tenant_id, "a@example.com", "User A").await;
+    seed_user(&conn, user_b, tenant_id, "b@example.com", "User B").await;
+
+    let services = build_services_with_authz(
+        db.clone(),
+        ServiceConfig::default(),
+        Arc::new(OwnerCityAuthZResolver),
+    );
+
+    let ctx_a = ctx_for_subject(user_a, tenant_id);
+    let ctx_b = ctx_for_subject(user_b, tenant_id);
+
+    let city = services
+        .cities
+        .create_city(
+            &ctx_a,
+            NewCity {
+                id: None,
+                tenant_id,
+                name: "Shared City".to_owned(),
+                country: "SC".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let addr_a = services
+        .addresses
+        .create_address(
+            &ctx_a,
+            NewAddress {
+                id: None,
+                tenant_id,
+                user_id: user_a,
+                city_id: city.id,
+                street: "A Street".to_owned(),
+                postal_code: "11111".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let own = services
+        .addresses
+        .list_user_addresses(&ctx_a, user_a, &ODataQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        own.items.iter().map(|a| a.id).collect::<Vec<_>>(),
+        vec![addr_a.id],
+        "Owner must see their own addresses"
+    );
+
+    let foreign = services
+        .addresses
+        .list_user_addresses(&ctx_b, user_a, &ODataQuery::default())
+        .await
+        .unwrap();
+    assert!(
+        foreign.items.is_empty(),
+        "User B must not list user A's addresses"
+    );
+}
+
+/// `get_address` under an owner-only scope: the owner reads their address,
+/// another user gets `Forbidden` for it, and an unknown ID is `NotFound`.
+#[tokio::test]
+async fn get_address_distinguishes_out_of_scope_from_missing() {
+    let db = inmem_db().await;
+    let tenant_id = Uuid::new_v4();
+    let user_a = Uuid::new_v4();
+    let user_b = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, user_a, tenant_id, "a@example.com", "User A").await;
+    seed_user(&conn, user_b, tenant_id, "b@example.com", "User B").await;
+
+    let services = build_services_with_authz(
+        db.clone(),
+        ServiceConfig::default(),
+        Arc::new(OwnerOnlyAuthZResolver),
+    );
+
+    let ctx_a = ctx_for_subject(user_a, tenant_id);
+    let ctx_b = ctx_for_subject(user_b, tenant_id);
+
+    let city = services
+        .cities
+        .create_city(
+            &ctx_a,
+            NewCity {
+                id: None,
+                tenant_id,
+                name: "Shared City".to_owned(),
+                country: "SC".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let addr_a = services
+        .addresses
+        .create_address(
+            &ctx_a,
+            NewAddress {
+                id: None,
+                tenant_id,
+                user_id: user_a,
+                city_id: city.id,
+                street: "A Street".to_owned(),
+                postal_code: "11111".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let own = services
+        .addresses
+        .get_address(&ctx_a, addr_a.id)
+        .await
+        .unwrap();
+    assert_eq!(own.id, addr_a.id);
+
+    // The address exists, but only outside user B's owner scope
+    let err = services
+        .addresses
+        .get_address(&ctx_b, addr_a.id)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, DomainError::Forbidden { .. }),
+        "Expected Forbidden for another user's address, got: {err:?}"
+    );
+
+    let err = services
+        .addresses
+        .get_address(&ctx_b, Uuid::new_v4())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, DomainError::NotFound { .. }),
+        "Expected NotFound for a missing address, got: {err:?}"
+    );
+}
@@ -0,0 +1,70 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use modkit_odata::ODataQuery;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::service::ServiceConfig;
+use crate::test_support::{build_services, ctx_allow_tenants, inmem_db, seed_user};
+
+#[tokio::test]
+async fn search_matches_within_caller_tenant_only() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let other_tenant = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    let own = Uuid::new_v4();
+    seed_user(&conn, own, tenant, "u@example.com", "U").await;
+    seed_user(&conn, Uuid::new_v4(), tenant, "v@test.org", "V").await;
+    seed_user(&conn, Uuid::new_v4(), other_tenant, "w@example.com", "W").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    for term in ["exa", "EXA", " Exa "] {
+        let page = services
+            .users
+            .search_users(&ctx, term, &ODataQuery::default())
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = page.items.iter().map(|u| u.id).collect();
+        assert_eq!(ids, vec![own], "search for {term:?}");
+    }
+}
+
+#[tokio::test]
+async fn search_matches_display_name() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    let id = Uuid::new_v4();
+    seed_user(&conn, id, tenant, "jd@corp.io", "Jane Doe").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    let page = services
+        .users
+        .search_users(&ctx, "doe", &ODataQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].id, id);
+}
+
+#[tokio::test]
+async fn empty_search_term_is_rejected() {
+    let db = inmem_db().await;
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[Uuid::new_v4()]);
+
+    let err = services
+        .users
+        .search_users(&ctx, "  ", &ODataQuery::default())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, DomainError::Validation { ref field, .. } if field == "q"),
+        "Expected validation error for q, got: {err:?}"
+    );
+}
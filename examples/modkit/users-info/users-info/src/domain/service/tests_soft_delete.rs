@@ -0,0 +1,288 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use modkit_odata::ODataQuery;
+use modkit_security::AccessScope;
+use time::OffsetDateTime;
+use users_info_sdk::{NewAddress, NewCity};
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::repos::{AddressesRepository, SoftDeleted};
+use crate::domain::service::ServiceConfig;
+use crate::infra::storage::OrmAddressesRepository;
+use crate::test_support::{build_services, ctx_allow_tenants, inmem_db, seed_user};
+
+#[tokio::test]
+async fn deleted_user_is_hidden_unless_included() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let kept = Uuid::new_v4();
+    let removed = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, kept, tenant, "kept@example.com", "Kept").await;
+    seed_user(&conn, removed, tenant, "removed@example.com", "Removed").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    services.users.delete_user(&ctx, removed).await.unwrap();
+
+    let page = services
+        .users
+        .list_users_page(&ctx, &ODataQuery::default())
+        .await
+        .unwrap();
+    let ids: Vec<Uuid> = page.items.iter().map(|u| u.id).collect();
+    assert_eq!(ids, vec![kept]);
+
+    let err = services.users.get_user(&ctx, removed).await.unwrap_err();
+    assert!(
+        matches!(err, DomainError::UserNotFound { id } if id == removed),
+        "Expected UserNotFound for a soft-deleted user, got: {err:?}"
+    );
+
+    let page = services
+        .users
+        .list_users_page_with_deleted(&ctx, &ODataQuery::default(), SoftDeleted::Include)
+        .await
+        .unwrap();
+    let ids: Vec<Uuid> = page.items.iter().map(|u| u.id).collect();
+    assert_eq!(ids.len(), 2);
+    assert!(ids.contains(&removed));
+}
+
+#[tokio::test]
+async fn deleting_twice_reports_not_found() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, user_id, tenant, "once@example.com", "Once").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    services.users.delete_user(&ctx, user_id).await.unwrap();
+    let err = services.users.delete_user(&ctx, user_id).await.unwrap_err();
+    assert!(
+        matches!(err, DomainError::UserNotFound { .. }),
+        "Expected UserNotFound on second delete, got: {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn restore_brings_user_back() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, user_id, tenant, "back@example.com", "Back").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    services.users.delete_user(&ctx, user_id).await.unwrap();
+    let restored = services.users.restore_user(&ctx, user_id).await.unwrap();
+    assert_eq!(restored.id, user_id);
+
+    let page = services
+        .users
+        .list_users_page(&ctx, &ODataQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].id, user_id);
+
+    // Restoring a live user is a no-op
+    let again = services.users.restore_user(&ctx, user_id).await.unwrap();
+    assert_eq!(again.id, user_id);
+}
+
+#[tokio::test]
+async fn cross_tenant_restore_is_denied() {
+    let db = inmem_db().await;
+    let owner_tenant = Uuid::new_v4();
+    let other_tenant = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, user_id, owner_tenant, "owned@example.com", "Owned").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let owner_ctx = ctx_allow_tenants(&[owner_tenant]);
+    let other_ctx = ctx_allow_tenants(&[other_tenant]);
+
+    services
+        .users
+        .delete_user(&owner_ctx, user_id)
+        .await
+        .unwrap();
+
+    let err = services
+        .users
+        .restore_user(&other_ctx, user_id)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, DomainError::UserNotFound { id } if id == user_id),
+        "Expected cross-tenant restore to report the user as missing, got: {err:?}"
+    );
+
+    // The user is still deleted for its owner
+    let err = services
+        .users
+        .get_user(&owner_ctx, user_id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DomainError::UserNotFound { .. }));
+}
+
+#[tokio::test]
+async fn update_racing_a_delete_does_not_revive_the_address() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, user_id, tenant, "racer@example.com", "Racer").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+    let city = services
+        .cities
+        .create_city(
+            &ctx,
+            NewCity {
+                id: None,
+                tenant_id: tenant,
+                name: "Race City".to_owned(),
+                country: "RC".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+    let mut address = services
+        .addresses
+        .create_address(
+            &ctx,
+            NewAddress {
+                id: None,
+                tenant_id: tenant,
+                user_id,
+                city_id: city.id,
+                street: "Old St".to_owned(),
+                postal_code: "11111".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+
+    // The address is deleted between an update's prefetch and its write
+    let repo = OrmAddressesRepository::new(ServiceConfig::default().limit_cfg());
+    let scope = AccessScope::for_tenant(tenant);
+    let at = OffsetDateTime::now_utc();
+    assert!(repo.delete(&conn, &scope, address.id, at).await.unwrap());
+
+    address.street = "New St".to_owned();
+    let err = repo
+        .update(&conn, &scope, address.clone())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, DomainError::NotFound { id, .. } if id == address.id),
+        "Expected the update to miss the deleted row, got: {err:?}"
+    );
+
+    let stored = repo
+        .get(&conn, &scope, address.id, SoftDeleted::Include)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.street, "Old St");
+    assert!(
+        repo.get(&conn, &scope, address.id, SoftDeleted::Exclude)
+            .await
+            .unwrap()
+            .is_none(),
+        "The address must stay deleted"
+    );
+}
+
+#[tokio::test]
+async fn put_does_not_revive_a_deleted_address() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, user_id, tenant, "putter@example.com", "Putter").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+    let city = services
+        .cities
+        .create_city(
+            &ctx,
+            NewCity {
+                id: None,
+                tenant_id: tenant,
+                name: "Put City".to_owned(),
+                country: "PC".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+    let new_address = |street: &str| NewAddress {
+        id: None,
+        tenant_id: tenant,
+        user_id,
+        city_id: city.id,
+        street: street.to_owned(),
+        postal_code: "22222".to_owned(),
+    };
+
+    let address = services
+        .addresses
+        .put_user_address(&ctx, user_id, new_address("First St"))
+        .await
+        .unwrap();
+    services
+        .addresses
+        .delete_address(&ctx, address.id)
+        .await
+        .unwrap();
+
+    let err = services
+        .addresses
+        .put_user_address(&ctx, user_id, new_address("Second St"))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            DomainError::DeletedAddressExists { user_id: u, address_id }
+                if u == user_id && address_id == address.id
+        ),
+        "Expected PUT over a deleted address to conflict, got: {err:?}"
+    );
+    assert!(
+        services
+            .addresses
+            .get_user_address(&ctx, user_id)
+            .await
+            .unwrap()
+            .is_none(),
+        "The address must stay deleted"
+    );
+
+    // After an explicit restore, PUT updates the address again
+    services
+        .addresses
+        .restore_address(&ctx, address.id)
+        .await
+        .unwrap();
+    let updated = services
+        .addresses
+        .put_user_address(&ctx, user_id, new_address("Second St"))
+        .await
+        .unwrap();
+    assert_eq!(updated.id, address.id);
+    assert_eq!(updated.street, "Second St");
+}
@@ -1,12 +1,18 @@
 #![allow(clippy::unwrap_used, clippy::expect_used)]
 
+use authz_resolver_sdk::DenyKind;
 use uuid::Uuid;
 
 use crate::domain::error::DomainError;
+use crate::domain::repos::UsersRepository;
 use crate::domain::service::ServiceConfig;
+use crate::infra::storage::OrmUsersRepository;
 use crate::test_support::{build_services, ctx_allow_tenants, ctx_deny_all, inmem_db, seed_user};
 use modkit_db::DBProvider;
-use users_info_sdk::{NewAddress, NewCity, NewUser};
+use modkit_db::secure::ScopedLookup;
+use modkit_security::AccessScope;
+use time::OffsetDateTime;
+use users_info_sdk::{NewAddress, NewCity, NewUser, UserPatch};
 
 #[tokio::test]
 async fn tenant_scope_only_sees_its_tenant() {
@@ -50,11 +56,105 @@ async fn deny_all_returns_forbidden() {
         .await;
     let err = result.unwrap_err();
     assert!(
-        matches!(err, DomainError::Forbidden),
+        matches!(
+            err,
+            DomainError::Forbidden {
+                reason: Some(DenyKind::ConstraintsRequiredButAbsent)
+            }
+        ),
         "Expected DomainError::Forbidden for anonymous context, got: {err:?}"
     );
 }
 
+#[tokio::test]
+async fn update_missing_user_is_not_found() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    let missing = Uuid::new_v4();
+    let err = services
+        .users
+        .update_user(&ctx, missing, rename_patch())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, DomainError::UserNotFound { id } if id == missing),
+        "Expected UserNotFound for an absent user, got: {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn update_out_of_scope_user_is_forbidden() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let other_tenant = Uuid::new_v4();
+    let user = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, user, other_tenant, "other@example.com", "Other").await;
+
+    let services = build_services(db.clone(), ServiceConfig::default());
+    let ctx = ctx_allow_tenants(&[tenant]);
+
+    let err = services
+        .users
+        .update_user(&ctx, user, rename_patch())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, DomainError::Forbidden { reason: None }),
+        "Expected Forbidden for a user in another tenant, got: {err:?}"
+    );
+
+    let err = services.users.delete_user(&ctx, user).await.unwrap_err();
+    assert!(
+        matches!(err, DomainError::Forbidden { reason: None }),
+        "Expected Forbidden for deleting a user in another tenant, got: {err:?}"
+    );
+}
+
+/// The probe after the prefetch must not turn a user deleted in between into
+/// an out-of-scope one: soft-deleted rows are missing, whatever the scope.
+#[tokio::test]
+async fn scoped_lookup_treats_soft_deleted_user_as_missing() {
+    let db = inmem_db().await;
+    let tenant = Uuid::new_v4();
+    let other_tenant = Uuid::new_v4();
+    let user = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    seed_user(&conn, user, tenant, "gone@example.com", "Gone").await;
+
+    let repo = OrmUsersRepository::new(ServiceConfig::default().limit_cfg());
+    let own = AccessScope::for_tenant(tenant);
+    let other = AccessScope::for_tenant(other_tenant);
+    assert!(matches!(
+        repo.lookup(&conn, &own, user).await.unwrap(),
+        ScopedLookup::Found(ref u) if u.id == user
+    ));
+    assert_eq!(
+        repo.lookup(&conn, &other, user).await.unwrap(),
+        ScopedLookup::OutOfScope
+    );
+
+    assert!(
+        repo.delete(&conn, &own, user, OffsetDateTime::now_utc())
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        repo.lookup(&conn, &other, user).await.unwrap(),
+        ScopedLookup::NotFound
+    );
+}
+
+fn rename_patch() -> UserPatch {
+    UserPatch {
+        email: None,
+        display_name: Some("Renamed".to_owned()),
+    }
+}
+
 #[tokio::test]
 async fn create_user_with_transaction() {
     let db = inmem_db().await;
@@ -102,9 +202,10 @@ async fn dbprovider_transaction_smoke() {
                     id: Set(user_id),
                     tenant_id: Set(tenant_id),
                     email: Set("tx@example.com".to_owned()),
-                    display_name: Set("Tx User".to_owned()),
+                    display_name: Set(Some("Tx User".to_owned())),
                     created_at: Set(now),
                     updated_at: Set(now),
+                    deleted_at: Set(None),
                 };
                 let _ = secure_insert::<UserEntity>(user, &scope, tx).await?;
                 Ok(())
@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -8,19 +9,93 @@ use tracing::instrument;
 use crate::domain::error::DomainError;
 use crate::domain::events::UserDomainEvent;
 use crate::domain::ports::{AuditPort, EventPublisher, UsersMetricsPort};
-use crate::domain::repos::{AddressesRepository, CitiesRepository, UsersRepository};
+use crate::domain::repos::{AddressesRepository, CitiesRepository, SoftDeleted, UsersRepository};
 use crate::domain::service::DbProvider;
-use crate::domain::service::{AddressesService, CitiesService, ServiceConfig};
+use crate::domain::service::{AddressesService, CitiesService, ServiceConfig, UserDeletePolicy};
 use authz_resolver_sdk::PolicyEnforcer;
 use authz_resolver_sdk::pep::AccessRequest;
 
 use super::{actions, resources};
+use modkit_db::secure::{DBRunner, ScopedLookup, validate_tenant_in_scope};
+use modkit_odata::filter::FilterField;
 use modkit_odata::{ODataQuery, Page};
 use modkit_security::{AccessScope, SecurityContext, pep_properties};
 use time::OffsetDateTime;
-use users_info_sdk::{NewUser, User, UserFull, UserPatch};
+use users_info_sdk::odata::UserFilterField;
+use users_info_sdk::{Address, NewUser, User, UserFull, UserPatch};
 use uuid::Uuid;
 
+/// Relation name for embedding a user's addresses via `$expand`.
+pub const EXPAND_ADDRESSES: &str = "addresses";
+
+/// Relations that `$expand` may request on user lists.
+pub const USER_EXPANDABLE: &[&str] = &[EXPAND_ADDRESSES];
+
+/// Public user fields that `$select` may project.
+pub const USER_SELECTABLE: &[&str] = &[
+    "id",
+    "tenant_id",
+    "email",
+    "display_name",
+    "created_at",
+    "updated_at",
+];
+
+/// Reject `$select`/`$filter`/`$orderby` fields outside the public user model.
+///
+/// Expandable relations are selectable too, since the handler projects them
+/// alongside the user fields.
+fn validate_query_fields(query: &ODataQuery) -> Result<(), DomainError> {
+    if let Some(field) = query.unsupported_select(USER_SELECTABLE) {
+        let expandable = USER_EXPANDABLE
+            .iter()
+            .any(|r| r.eq_ignore_ascii_case(field));
+        if !expandable {
+            return Err(DomainError::validation(
+                "$select",
+                format!("unknown field '{field}'"),
+            ));
+        }
+    }
+
+    let filterable: Vec<&str> = UserFilterField::FIELDS
+        .iter()
+        .map(FilterField::name)
+        .collect();
+    if let Some(field) = query.unsupported_filter_field(&filterable) {
+        return Err(DomainError::validation(
+            "$filter",
+            format!("unknown field '{field}'"),
+        ));
+    }
+    if let Some(field) = query.unsupported_order_field(&filterable) {
+        return Err(DomainError::validation(
+            "$orderby",
+            format!("unknown field '{field}'"),
+        ));
+    }
+
+    Ok(())
+}
+
+/// A user with the relations requested via `$expand` embedded.
+#[domain_model]
+#[derive(Debug, Clone)]
+pub struct ExpandedUser {
+    pub user: User,
+    /// `Some` only when `addresses` was expanded.
+    pub addresses: Option<Vec<Address>>,
+}
+
+/// Outcome of one input row of [`UsersService::create_users_bulk`].
+#[domain_model]
+#[derive(Debug)]
+pub struct BulkUserResult {
+    /// Position of the row in the input batch.
+    pub index: usize,
+    pub result: Result<User, DomainError>,
+}
+
 /// Users service.
 ///
 /// # Design
@@ -33,8 +108,11 @@ use uuid::Uuid;
 /// - Centralizes DB error mapping in the domain layer
 /// - Maintains transaction safety via the task-local guard
 #[domain_model]
-pub struct UsersService<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository>
-{
+pub struct UsersService<
+    R: UsersRepository + 'static,
+    CR: CitiesRepository,
+    AR: AddressesRepository + 'static,
+> {
     db: Arc<DbProvider>,
     repo: Arc<R>,
     events: Arc<dyn EventPublisher<UserDomainEvent>>,
@@ -43,10 +121,11 @@ pub struct UsersService<R: UsersRepository + 'static, CR: CitiesRepository, AR:
     config: ServiceConfig,
     cities: Arc<CitiesService<CR>>,
     addresses: Arc<AddressesService<AR, R>>,
+    addresses_repo: Arc<AR>,
     metrics: Arc<dyn UsersMetricsPort>,
 }
 
-impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository>
+impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository + 'static>
     UsersService<R, CR, AR>
 {
     #[allow(clippy::too_many_arguments)]
@@ -59,6 +138,7 @@ impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository
         config: ServiceConfig,
         cities: Arc<CitiesService<CR>>,
         addresses: Arc<AddressesService<AR, R>>,
+        addresses_repo: Arc<AR>,
         metrics: Arc<dyn UsersMetricsPort>,
     ) -> Self {
         Self {
@@ -70,11 +150,17 @@ impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository
             config,
             cities,
             addresses,
+            addresses_repo,
             metrics,
         }
     }
 }
 
+/// Abort the surrounding transaction with a domain error.
+fn tx_err(e: DomainError) -> modkit_db::DbError {
+    modkit_db::DbError::Other(e.into())
+}
+
 /// Cap concurrent best-effort audit tasks to avoid unbounded spawns.
 static AUDIT_SEMAPHORE: Semaphore = Semaphore::const_new(10);
 
@@ -102,7 +188,7 @@ fn audit_get_user_access_best_effort(audit: &Arc<dyn AuditPort>, id: Uuid) {
 }
 
 // Business logic methods
-impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository>
+impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository + 'static>
     UsersService<R, CR, AR>
 {
     #[instrument(skip(self, ctx), fields(user_id = %id))]
@@ -118,7 +204,7 @@ impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository
         let prefetch_scope = AccessScope::allow_all();
         let user = self
             .repo
-            .get(&conn, &prefetch_scope, id)
+            .get(&conn, &prefetch_scope, id, SoftDeleted::Exclude)
             .await?
             .ok_or_else(|| DomainError::user_not_found(id))?;
 
@@ -141,7 +227,7 @@ impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository
             user
         } else {
             self.repo
-                .get(&conn, &scope, id)
+                .get(&conn, &scope, id, SoftDeleted::Exclude)
                 .await?
                 .ok_or_else(|| DomainError::user_not_found(id))?
         };
@@ -152,14 +238,28 @@ impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository
     }
 
     /// List users with cursor-based pagination
-    #[instrument(skip(self, ctx, query))]
     pub async fn list_users_page(
         &self,
         ctx: &SecurityContext,
         query: &ODataQuery,
+    ) -> Result<Page<User>, DomainError> {
+        self.list_users_page_with_deleted(ctx, query, SoftDeleted::Exclude)
+            .await
+    }
+
+    /// List users like [`Self::list_users_page`], optionally including
+    /// soft-deleted users.
+    #[instrument(skip(self, ctx, query))]
+    pub async fn list_users_page_with_deleted(
+        &self,
+        ctx: &SecurityContext,
+        query: &ODataQuery,
+        deleted: SoftDeleted,
     ) -> Result<Page<User>, DomainError> {
         tracing::debug!("Listing users with cursor pagination");
 
+        validate_query_fields(query)?;
+
         let conn = self.db.conn().map_err(DomainError::from)?;
 
         let scope = self
@@ -167,12 +267,93 @@ impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository
             .access_scope(ctx, &resources::USER, actions::LIST, None)
             .await?;
 
-        let page = self.repo.list_page(&conn, &scope, query).await?;
+        let page = self.repo.list_page(&conn, &scope, query, deleted).await?;
 
         tracing::debug!("Successfully listed {} users in page", page.items.len());
         Ok(page)
     }
 
+    /// Search users whose email or display name contains `term`, ignoring case.
+    ///
+    /// Matches are limited to the caller's scope; `query` supplies paging and
+    /// any further `OData` filtering. Soft-deleted users are not returned.
+    #[instrument(skip(self, ctx, query))]
+    pub async fn search_users(
+        &self,
+        ctx: &SecurityContext,
+        term: &str,
+        query: &ODataQuery,
+    ) -> Result<Page<User>, DomainError> {
+        tracing::debug!("Searching users");
+
+        let term = term.trim();
+        if term.is_empty() {
+            return Err(DomainError::validation(
+                "q",
+                "search term must not be empty",
+            ));
+        }
+        validate_query_fields(query)?;
+
+        let conn = self.db.conn().map_err(DomainError::from)?;
+
+        let scope = self
+            .policy_enforcer
+            .access_scope(ctx, &resources::USER, actions::LIST, None)
+            .await?;
+
+        let page = self.repo.search_page(&conn, &scope, term, query).await?;
+
+        tracing::debug!("Found {} users in page", page.items.len());
+        Ok(page)
+    }
+
+    /// List users like [`Self::list_users_page`], embedding the relations
+    /// requested via `$expand` (see [`USER_EXPANDABLE`]).
+    ///
+    /// Expanded rows are loaded under their own access scope, so a visible user
+    /// may come back with only the addresses the caller is allowed to see.
+    #[instrument(skip(self, ctx, query))]
+    pub async fn list_users_page_expanded(
+        &self,
+        ctx: &SecurityContext,
+        query: &ODataQuery,
+        deleted: SoftDeleted,
+    ) -> Result<Page<ExpandedUser>, DomainError> {
+        if let Some(target) = query.unsupported_expand(USER_EXPANDABLE) {
+            return Err(DomainError::validation(
+                "$expand",
+                format!("unknown expand target '{target}'"),
+            ));
+        }
+
+        let page = self
+            .list_users_page_with_deleted(ctx, query, deleted)
+            .await?;
+
+        let mut addresses = if query.expands(EXPAND_ADDRESSES) {
+            let user_ids: Vec<Uuid> = page.items.iter().map(|u| u.id).collect();
+            let mut by_user: HashMap<Uuid, Vec<Address>> = HashMap::new();
+            for address in self
+                .addresses
+                .list_addresses_for_users(ctx, &user_ids)
+                .await?
+            {
+                by_user.entry(address.user_id).or_default().push(address);
+            }
+            Some(by_user)
+        } else {
+            None
+        };
+
+        Ok(page.map_items(|user| {
+            let addresses = addresses
+                .as_mut()
+                .map(|by_user| by_user.remove(&user.id).unwrap_or_default());
+            ExpandedUser { user, addresses }
+        }))
+    }
+
     /// Create a new user.
     #[allow(clippy::cognitive_complexity)]
     #[instrument(
@@ -221,10 +402,10 @@ impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository
             updated_at: now,
         };
 
-        // SAFETY(multi-tenant bypass): Email and ID uniqueness are enforced
-        // globally across all tenants, not per-tenant. This intentionally
-        // bypasses tenant isolation so that a CREATE in tenant A is rejected
-        // if the same email/ID already exists in tenant B.
+        // SAFETY(multi-tenant bypass): ID uniqueness is enforced globally
+        // across all tenants. This intentionally bypasses tenant isolation so
+        // that a CREATE in tenant A is rejected if the same ID already exists
+        // in tenant B.
         let global = AccessScope::allow_all();
 
         if provided_id.is_some() && self.repo.exists(&conn, &global, id).await? {
@@ -234,9 +415,12 @@ impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository
             ));
         }
 
+        // Emails are unique per tenant, matching the `(tenant_id, email)`
+        // index. A concurrent insert that slips past this check still surfaces
+        // as `EmailAlreadyExists` from the repository.
         if self
             .repo
-            .count_by_email(&conn, &global, &user.email)
+            .count_by_email(&conn, &AccessScope::for_tenant(tenant_id), &user.email)
             .await?
             > 0
         {
@@ -259,6 +443,155 @@ impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository
         Ok(created_user)
     }
 
+    /// Create a batch of users, reporting a result per input row.
+    ///
+    /// Each row is validated and authorized on its own; rows that fail, or
+    /// whose ID duplicates an existing user or an earlier row of the batch,
+    /// or whose email does so within the same tenant, are reported and
+    /// skipped. The remaining rows are inserted in a
+    /// single transaction. Results come back in input order.
+    ///
+    /// # Errors
+    /// Returns an error only if the transaction itself fails; no row is
+    /// created in that case.
+    #[instrument(skip(self, ctx, new_users), fields(batch_size = new_users.len()))]
+    pub async fn create_users_bulk(
+        &self,
+        ctx: &SecurityContext,
+        new_users: Vec<NewUser>,
+    ) -> Result<Vec<BulkUserResult>, DomainError> {
+        tracing::info!("Creating users in bulk");
+
+        let mut results: Vec<Option<Result<User, DomainError>>> =
+            (0..new_users.len()).map(|_| None).collect();
+        let mut candidates = Vec::with_capacity(new_users.len());
+        let mut seen_emails = HashSet::new();
+        let mut seen_ids = HashSet::new();
+        let now = OffsetDateTime::now_utc();
+
+        for (index, new_user) in new_users.into_iter().enumerate() {
+            if let Err(e) = self.validate_new_user(&new_user) {
+                results[index] = Some(Err(e));
+                continue;
+            }
+
+            let scope = match self
+                .policy_enforcer
+                .access_scope_with(
+                    ctx,
+                    &resources::USER,
+                    actions::CREATE,
+                    None,
+                    &AccessRequest::new()
+                        .resource_property(pep_properties::OWNER_TENANT_ID, new_user.tenant_id),
+                )
+                .await
+            {
+                Ok(scope) => scope,
+                Err(e) => {
+                    results[index] = Some(Err(e.into()));
+                    continue;
+                }
+            };
+
+            // The insert would reject an out-of-scope tenant and abort the
+            // whole transaction, so check it up front.
+            if validate_tenant_in_scope(new_user.tenant_id, &scope).is_err() {
+                results[index] = Some(Err(DomainError::Forbidden { reason: None }));
+                continue;
+            }
+
+            if !seen_emails.insert((new_user.tenant_id, new_user.email.clone())) {
+                results[index] = Some(Err(DomainError::email_already_exists(new_user.email)));
+                continue;
+            }
+            if let Some(id) = new_user.id
+                && !seen_ids.insert(id)
+            {
+                results[index] = Some(Err(DomainError::validation(
+                    "id",
+                    "User with this ID already exists",
+                )));
+                continue;
+            }
+
+            let user = User {
+                id: new_user.id.unwrap_or_else(Uuid::now_v7),
+                tenant_id: new_user.tenant_id,
+                email: new_user.email,
+                display_name: new_user.display_name,
+                created_at: now,
+                updated_at: now,
+            };
+            candidates.push((index, new_user.id.is_some(), scope, user));
+        }
+
+        let repo = Arc::clone(&self.repo);
+        let outcomes = self
+            .db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    // SAFETY(multi-tenant bypass): see comment in create_user().
+                    let global = AccessScope::allow_all();
+                    let mut outcomes = Vec::with_capacity(candidates.len());
+                    for (index, id_provided, scope, user) in candidates {
+                        let outcome = if id_provided
+                            && repo.exists(tx, &global, user.id).await.map_err(tx_err)?
+                        {
+                            Err(DomainError::validation(
+                                "id",
+                                "User with this ID already exists",
+                            ))
+                        } else if repo
+                            .count_by_email(
+                                tx,
+                                &AccessScope::for_tenant(user.tenant_id),
+                                &user.email,
+                            )
+                            .await
+                            .map_err(tx_err)?
+                            > 0
+                        {
+                            Err(DomainError::email_already_exists(user.email))
+                        } else {
+                            Ok(repo.create(tx, &scope, user).await.map_err(tx_err)?)
+                        };
+                        outcomes.push((index, outcome));
+                    }
+                    Ok(outcomes)
+                })
+            })
+            .await?;
+
+        for (index, outcome) in outcomes {
+            if let Ok(user) = &outcome {
+                if let Err(e) = self.audit.notify_user_created().await {
+                    tracing::debug!("Notification service call failed (continuing): {}", e);
+                }
+                self.events.publish(&UserDomainEvent::Created {
+                    id: user.id,
+                    at: user.created_at,
+                });
+            }
+            results[index] = Some(outcome);
+        }
+
+        let results: Vec<BulkUserResult> = results
+            .into_iter()
+            .enumerate()
+            .map(|(index, result)| BulkUserResult {
+                index,
+                result: result.unwrap_or(Err(DomainError::InternalError)),
+            })
+            .collect();
+
+        tracing::info!(
+            created = results.iter().filter(|r| r.result.is_ok()).count(),
+            "Finished bulk user creation"
+        );
+        Ok(results)
+    }
+
     /// Update an existing user.
     #[instrument(skip(self, ctx), fields(user_id = %id))]
     pub async fn update_user(
@@ -278,7 +611,7 @@ impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository
         let prefetch_scope = AccessScope::allow_all();
         let mut current = self
             .repo
-            .get(&conn, &prefetch_scope, id)
+            .get(&conn, &prefetch_scope, id, SoftDeleted::Exclude)
             .await?
             .ok_or_else(|| DomainError::user_not_found(id))?;
 
@@ -293,13 +626,19 @@ impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository
                     .resource_property(pep_properties::OWNER_TENANT_ID, current.tenant_id),
             )
             .await?;
+        self.ensure_in_scope(&conn, &scope, id).await?;
 
         if let Some(ref new_email) = patch.email
             && new_email != &current.email
         {
-            // SAFETY(multi-tenant bypass): see comment in create_user().
-            let global = AccessScope::allow_all();
-            let count = self.repo.count_by_email(&conn, &global, new_email).await?;
+            let count = self
+                .repo
+                .count_by_email(
+                    &conn,
+                    &AccessScope::for_tenant(current.tenant_id),
+                    new_email,
+                )
+                .await?;
             if count > 0 {
                 return Err(DomainError::email_already_exists(new_email.clone()));
             }
@@ -336,7 +675,7 @@ impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository
         let prefetch_scope = AccessScope::allow_all();
         let prefetched = self
             .repo
-            .get(&conn, &prefetch_scope, id)
+            .get(&conn, &prefetch_scope, id, SoftDeleted::Exclude)
             .await?
             .ok_or_else(|| DomainError::user_not_found(id))?;
 
@@ -351,22 +690,169 @@ impl<R: UsersRepository + 'static, CR: CitiesRepository, AR: AddressesRepository
                     .resource_property(pep_properties::OWNER_TENANT_ID, prefetched.tenant_id),
             )
             .await?;
+        self.ensure_in_scope(&conn, &scope, id).await?;
 
-        let deleted = self.repo.delete(&conn, &scope, id).await?;
+        // Dependents live in the user's tenant; the user-level decision above
+        // covers them, so they are looked up and removed by tenant only.
+        let dependents_scope = AccessScope::for_tenant(prefetched.tenant_id);
+        // Cascaded addresses share the user's timestamp so a restore can tell
+        // them apart from addresses deleted on their own.
+        let now = OffsetDateTime::now_utc();
+
+        let deleted = match self.config.user_delete_policy {
+            UserDeletePolicy::Restrict => {
+                let repo = Arc::clone(&self.repo);
+                let addresses_repo = Arc::clone(&self.addresses_repo);
+                // Check and delete in one transaction so no address slips in between
+                let outcome = self
+                    .db
+                    .transaction(move |tx| {
+                        Box::pin(async move {
+                            let addresses = addresses_repo
+                                .list_by_user_ids(tx, &dependents_scope, &[id])
+                                .await
+                                .map_err(tx_err)?;
+                            if !addresses.is_empty() {
+                                return Ok(Err(addresses.into_iter().map(|a| a.id).collect()));
+                            }
+                            Ok(Ok(repo
+                                .delete(tx, &scope, id, now)
+                                .await
+                                .map_err(tx_err)?))
+                        })
+                    })
+                    .await?;
+                outcome.map_err(|addresses| DomainError::user_has_addresses(id, addresses))?
+            }
+            UserDeletePolicy::Cascade => {
+                let repo = Arc::clone(&self.repo);
+                let addresses_repo = Arc::clone(&self.addresses_repo);
+                self.db
+                    .transaction(move |tx| {
+                        Box::pin(async move {
+                            if !repo.delete(tx, &scope, id, now).await.map_err(tx_err)? {
+                                return Ok(false);
+                            }
+                            let removed = addresses_repo
+                                .delete_by_user_id(tx, &dependents_scope, id, now)
+                                .await
+                                .map_err(tx_err)?;
+                            tracing::debug!(removed, "Cascaded user delete to addresses");
+                            Ok(true)
+                        })
+                    })
+                    .await?
+            }
+        };
 
         if !deleted {
             return Err(DomainError::user_not_found(id));
         }
 
-        self.events.publish(&UserDomainEvent::Deleted {
-            id,
-            at: OffsetDateTime::now_utc(),
-        });
+        self.events
+            .publish(&UserDomainEvent::Deleted { id, at: now });
 
         tracing::info!("Successfully deleted user");
         Ok(())
     }
 
+    /// Restore a soft-deleted user.
+    ///
+    /// Authorized like [`Self::delete_user`]. Addresses removed by a cascading
+    /// delete of the user are restored with it; addresses deleted on their own
+    /// stay deleted. Restoring a user that is not deleted is a no-op that
+    /// returns the user unchanged.
+    #[instrument(skip(self, ctx), fields(user_id = %id))]
+    pub async fn restore_user(&self, ctx: &SecurityContext, id: Uuid) -> Result<User, DomainError> {
+        tracing::info!("Restoring user");
+
+        let conn = self.db.conn().map_err(DomainError::from)?;
+
+        // Prefetch (deleted rows included): load user to extract owner_tenant_id for PDP.
+        // Narrow scope + WHERE constraint provides TOCTOU protection.
+        let prefetch_scope = AccessScope::allow_all();
+        let prefetched = self
+            .repo
+            .get(&conn, &prefetch_scope, id, SoftDeleted::Include)
+            .await?
+            .ok_or_else(|| DomainError::user_not_found(id))?;
+
+        let scope = self
+            .policy_enforcer
+            .access_scope_with(
+                ctx,
+                &resources::USER,
+                actions::DELETE,
+                Some(id),
+                &AccessRequest::new()
+                    .resource_property(pep_properties::OWNER_TENANT_ID, prefetched.tenant_id),
+            )
+            .await?;
+
+        let dependents_scope = AccessScope::for_tenant(prefetched.tenant_id);
+        let repo = Arc::clone(&self.repo);
+        let addresses_repo = Arc::clone(&self.addresses_repo);
+        let restore_scope = scope.clone();
+        let restored = self
+            .db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let Some(deleted_at) =
+                        repo.restore(tx, &restore_scope, id).await.map_err(tx_err)?
+                    else {
+                        return Ok(false);
+                    };
+                    let revived = addresses_repo
+                        .restore_by_user_id(tx, &dependents_scope, id, deleted_at)
+                        .await
+                        .map_err(tx_err)?;
+                    tracing::debug!(revived, "Restored addresses deleted with the user");
+                    Ok(true)
+                })
+            })
+            .await?;
+
+        // Nothing restored: either the user is live already (no-op) or the
+        // row is outside the caller's scope.
+        let user = self
+            .repo
+            .get(&conn, &scope, id, SoftDeleted::Exclude)
+            .await?
+            .ok_or_else(|| DomainError::user_not_found(id))?;
+
+        if restored {
+            self.events.publish(&UserDomainEvent::Restored {
+                id,
+                at: OffsetDateTime::now_utc(),
+            });
+            tracing::info!("Successfully restored user");
+        } else {
+            tracing::debug!("User is not deleted; nothing to restore");
+        }
+        Ok(user)
+    }
+
+    /// Scope-aware existence probe for a user the unscoped prefetch found.
+    ///
+    /// A user that exists only outside the PDP's `scope` is reported as
+    /// `Forbidden`; one that is gone by now (e.g. soft-deleted since the
+    /// prefetch) stays `UserNotFound`.
+    async fn ensure_in_scope(
+        &self,
+        conn: &impl DBRunner,
+        scope: &AccessScope,
+        id: Uuid,
+    ) -> Result<(), DomainError> {
+        match self.repo.lookup(conn, scope, id).await? {
+            ScopedLookup::Found(_) => Ok(()),
+            ScopedLookup::OutOfScope => {
+                tracing::debug!("User is outside the caller's access scope");
+                Err(DomainError::Forbidden { reason: None })
+            }
+            ScopedLookup::NotFound => Err(DomainError::user_not_found(id)),
+        }
+    }
+
     fn validate_new_user(&self, new_user: &NewUser) -> Result<(), DomainError> {
         Self::validate_email(&new_user.email)?;
         self.validate_display_name(&new_user.display_name)?;
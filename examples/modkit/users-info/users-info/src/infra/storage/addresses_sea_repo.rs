@@ -1,20 +1,20 @@
 use async_trait::async_trait;
 
 use crate::domain::error::DomainError;
-use crate::domain::repos::AddressesRepository;
+use crate::domain::repos::{AddressesRepository, SoftDeleted};
 use crate::infra::storage::db::db_err;
 use crate::infra::storage::entity::address::{
     ActiveModel as AddressAM, Column as AddressColumn, Entity as AddressEntity,
 };
-use crate::infra::storage::odata_mapper::AddressODataMapper;
+use crate::infra::storage::odata_mapper::{AddressODataMapper, with_keyset_order};
+use crate::infra::storage::soft_delete::live_rows;
 use modkit_db::odata::{LimitCfg, paginate_odata};
-use modkit_db::secure::{
-    DBRunner, SecureDeleteExt, SecureEntityExt, secure_insert, secure_update_with_scope,
-};
+use modkit_db::secure::{DBRunner, ScopedLookup, SecureEntityExt, SecureUpdateExt, secure_insert};
 use modkit_odata::{ODataQuery, Page, SortDir};
 use modkit_security::AccessScope;
 use sea_orm::sea_query::Expr;
 use sea_orm::{EntityTrait, QueryFilter, Set};
+use time::OffsetDateTime;
 use users_info_sdk::Address;
 use users_info_sdk::odata::AddressFilterField;
 use uuid::Uuid;
@@ -39,9 +39,14 @@ impl AddressesRepository for OrmAddressesRepository {
         conn: &C,
         scope: &AccessScope,
         id: Uuid,
+        deleted: SoftDeleted,
     ) -> Result<Option<Address>, DomainError> {
         let found = AddressEntity::find()
-            .filter(sea_orm::Condition::all().add(Expr::col(AddressColumn::Id).eq(id)))
+            .filter(live_rows(
+                AddressColumn::DeletedAt,
+                sea_orm::Condition::all().add(Expr::col(AddressColumn::Id).eq(id)),
+                deleted,
+            ))
             .secure()
             .scope_with(scope)
             .one(conn)
@@ -50,18 +55,75 @@ impl AddressesRepository for OrmAddressesRepository {
         Ok(found.map(Into::into))
     }
 
+    async fn lookup<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        id: Uuid,
+    ) -> Result<ScopedLookup<Address>, DomainError> {
+        let found = AddressEntity::find()
+            .filter(live_rows(
+                AddressColumn::DeletedAt,
+                sea_orm::Condition::all().add(Expr::col(AddressColumn::Id).eq(id)),
+                SoftDeleted::Exclude,
+            ))
+            .secure()
+            .one_or_out_of_scope(scope, conn)
+            .await
+            .map_err(db_err)?;
+        Ok(found.map(Into::into))
+    }
+
     async fn list_page<C: DBRunner>(
         &self,
         conn: &C,
         scope: &AccessScope,
         query: &ODataQuery,
+        deleted: SoftDeleted,
     ) -> Result<Page<Address>, DomainError> {
-        let base_query = AddressEntity::find().secure().scope_with(scope);
+        let base_query = AddressEntity::find()
+            .secure()
+            .scope_with(scope)
+            .filter(live_rows(
+                AddressColumn::DeletedAt,
+                sea_orm::Condition::all(),
+                deleted,
+            ));
 
         let page = paginate_odata::<AddressFilterField, AddressODataMapper, _, _, _, _>(
             base_query,
             conn,
-            query,
+            &with_keyset_order(query),
+            ("id", SortDir::Desc),
+            self.limit_cfg,
+            Into::into,
+        )
+        .await
+        .map_err(db_err)?;
+
+        Ok(page)
+    }
+
+    async fn list_page_by_user_id<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        user_id: Uuid,
+        query: &ODataQuery,
+    ) -> Result<Page<Address>, DomainError> {
+        let base_query = AddressEntity::find()
+            .secure()
+            .scope_with(scope)
+            .filter(live_rows(
+                AddressColumn::DeletedAt,
+                sea_orm::Condition::all().add(Expr::col(AddressColumn::UserId).eq(user_id)),
+                SoftDeleted::Exclude,
+            ));
+
+        let page = paginate_odata::<AddressFilterField, AddressODataMapper, _, _, _, _>(
+            base_query,
+            conn,
+            &with_keyset_order(query),
             ("id", SortDir::Desc),
             self.limit_cfg,
             Into::into,
@@ -77,9 +139,14 @@ impl AddressesRepository for OrmAddressesRepository {
         conn: &C,
         scope: &AccessScope,
         user_id: Uuid,
+        deleted: SoftDeleted,
     ) -> Result<Option<Address>, DomainError> {
         let found = AddressEntity::find()
-            .filter(sea_orm::Condition::all().add(Expr::col(AddressColumn::UserId).eq(user_id)))
+            .filter(live_rows(
+                AddressColumn::DeletedAt,
+                sea_orm::Condition::all().add(Expr::col(AddressColumn::UserId).eq(user_id)),
+                deleted,
+            ))
             .secure()
             .scope_with(scope)
             .one(conn)
@@ -88,6 +155,30 @@ impl AddressesRepository for OrmAddressesRepository {
         Ok(found.map(Into::into))
     }
 
+    async fn list_by_user_ids<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        user_ids: &[Uuid],
+    ) -> Result<Vec<Address>, DomainError> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let found = AddressEntity::find()
+            .filter(live_rows(
+                AddressColumn::DeletedAt,
+                sea_orm::Condition::all()
+                    .add(Expr::col(AddressColumn::UserId).is_in(user_ids.iter().copied())),
+                SoftDeleted::Exclude,
+            ))
+            .secure()
+            .scope_with(scope)
+            .all(conn)
+            .await
+            .map_err(db_err)?;
+        Ok(found.into_iter().map(Into::into).collect())
+    }
+
     async fn create<C: DBRunner>(
         &self,
         conn: &C,
@@ -103,6 +194,7 @@ impl AddressesRepository for OrmAddressesRepository {
             postal_code: Set(address.postal_code.clone()),
             created_at: Set(address.created_at),
             updated_at: Set(address.updated_at),
+            deleted_at: Set(None),
         };
 
         let _ = secure_insert::<AddressEntity>(m, scope, conn)
@@ -118,19 +210,31 @@ impl AddressesRepository for OrmAddressesRepository {
         address: Address,
     ) -> Result<Address, DomainError> {
         let m = AddressAM {
-            id: Set(address.id),
-            tenant_id: Set(address.tenant_id),
             user_id: Set(address.user_id),
             city_id: Set(address.city_id),
             street: Set(address.street.clone()),
             postal_code: Set(address.postal_code.clone()),
-            created_at: Set(address.created_at),
             updated_at: Set(address.updated_at),
+            ..Default::default()
         };
 
-        let _ = secure_update_with_scope::<AddressEntity>(m, scope, address.id, conn)
+        // Only live rows: a concurrent delete must not be undone by the update
+        let result = AddressEntity::update_many()
+            .set(m)
+            .filter(live_rows(
+                AddressColumn::DeletedAt,
+                sea_orm::Condition::all().add(Expr::col(AddressColumn::Id).eq(address.id)),
+                SoftDeleted::Exclude,
+            ))
+            .secure()
+            .scope_with(scope)
+            .exec(conn)
             .await
             .map_err(db_err)?;
+
+        if result.rows_affected == 0 {
+            return Err(DomainError::not_found("Address", address.id));
+        }
         Ok(address)
     }
 
@@ -139,9 +243,40 @@ impl AddressesRepository for OrmAddressesRepository {
         conn: &C,
         scope: &AccessScope,
         id: Uuid,
+        at: OffsetDateTime,
+    ) -> Result<bool, DomainError> {
+        let result = AddressEntity::update_many()
+            .col_expr(AddressColumn::DeletedAt, Expr::value(at))
+            .filter(live_rows(
+                AddressColumn::DeletedAt,
+                sea_orm::Condition::all().add(Expr::col(AddressColumn::Id).eq(id)),
+                SoftDeleted::Exclude,
+            ))
+            .secure()
+            .scope_with(scope)
+            .exec(conn)
+            .await
+            .map_err(db_err)?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn restore<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        id: Uuid,
     ) -> Result<bool, DomainError> {
-        let result = AddressEntity::delete_many()
-            .filter(sea_orm::Condition::all().add(Expr::col(AddressColumn::Id).eq(id)))
+        let result = AddressEntity::update_many()
+            .col_expr(
+                AddressColumn::DeletedAt,
+                Expr::value(Option::<OffsetDateTime>::None),
+            )
+            .filter(
+                sea_orm::Condition::all()
+                    .add(Expr::col(AddressColumn::Id).eq(id))
+                    .add(Expr::col(AddressColumn::DeletedAt).is_not_null()),
+            )
             .secure()
             .scope_with(scope)
             .exec(conn)
@@ -156,9 +291,41 @@ impl AddressesRepository for OrmAddressesRepository {
         conn: &C,
         scope: &AccessScope,
         user_id: Uuid,
+        at: OffsetDateTime,
+    ) -> Result<u64, DomainError> {
+        let result = AddressEntity::update_many()
+            .col_expr(AddressColumn::DeletedAt, Expr::value(at))
+            .filter(live_rows(
+                AddressColumn::DeletedAt,
+                sea_orm::Condition::all().add(Expr::col(AddressColumn::UserId).eq(user_id)),
+                SoftDeleted::Exclude,
+            ))
+            .secure()
+            .scope_with(scope)
+            .exec(conn)
+            .await
+            .map_err(db_err)?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn restore_by_user_id<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        user_id: Uuid,
+        deleted_at: OffsetDateTime,
     ) -> Result<u64, DomainError> {
-        let result = AddressEntity::delete_many()
-            .filter(sea_orm::Condition::all().add(Expr::col(AddressColumn::UserId).eq(user_id)))
+        let result = AddressEntity::update_many()
+            .col_expr(
+                AddressColumn::DeletedAt,
+                Expr::value(Option::<OffsetDateTime>::None),
+            )
+            .filter(
+                sea_orm::Condition::all()
+                    .add(Expr::col(AddressColumn::UserId).eq(user_id))
+                    .add(Expr::col(AddressColumn::DeletedAt).eq(deleted_at)),
+            )
             .secure()
             .scope_with(scope)
             .exec(conn)
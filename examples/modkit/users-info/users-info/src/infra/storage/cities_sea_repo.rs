@@ -6,7 +6,7 @@ use crate::infra::storage::db::db_err;
 use crate::infra::storage::entity::city::{
     ActiveModel as CityAM, Column as CityColumn, Entity as CityEntity,
 };
-use crate::infra::storage::odata_mapper::CityODataMapper;
+use crate::infra::storage::odata_mapper::{CityODataMapper, with_keyset_order};
 use modkit_db::odata::{LimitCfg, paginate_odata};
 use modkit_db::secure::{
     DBRunner, SecureDeleteExt, SecureEntityExt, secure_insert, secure_update_with_scope,
@@ -61,7 +61,7 @@ impl CitiesRepository for OrmCitiesRepository {
         let page = paginate_odata::<CityFilterField, CityODataMapper, _, _, _, _>(
             base_query,
             conn,
-            query,
+            &with_keyset_order(query),
             ("id", SortDir::Desc),
             self.limit_cfg,
             Into::into,
@@ -11,9 +11,11 @@ pub struct Model {
     pub id: Uuid,
     pub tenant_id: Uuid,
     pub email: String,
-    pub display_name: String,
+    /// `None` for users stored without a display name (e.g. imported accounts).
+    pub display_name: Option<String>,
     pub created_at: OffsetDateTime,
     pub updated_at: OffsetDateTime,
+    pub deleted_at: Option<OffsetDateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -8,7 +8,7 @@ impl From<entity::user::Model> for User {
             id: e.id,
             tenant_id: e.tenant_id,
             email: e.email,
-            display_name: e.display_name,
+            display_name: e.display_name.unwrap_or_default(),
             created_at: e.created_at,
             updated_at: e.updated_at,
         }
@@ -22,7 +22,7 @@ impl From<&entity::user::Model> for User {
             id: e.id,
             tenant_id: e.tenant_id,
             email: e.email.clone(),
-            display_name: e.display_name.clone(),
+            display_name: e.display_name.clone().unwrap_or_default(),
             created_at: e.created_at,
             updated_at: e.updated_at,
         }
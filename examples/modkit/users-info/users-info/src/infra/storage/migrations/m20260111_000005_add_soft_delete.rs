@@ -0,0 +1,78 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        if backend == sea_orm::DatabaseBackend::MySql {
+            if !manager.has_column("users", "deleted_at").await? {
+                conn.execute_unprepared("ALTER TABLE users ADD COLUMN deleted_at TIMESTAMP NULL;")
+                    .await?;
+            }
+            if !manager.has_column("addresses", "deleted_at").await? {
+                conn.execute_unprepared(
+                    "ALTER TABLE addresses ADD COLUMN deleted_at TIMESTAMP NULL;",
+                )
+                .await?;
+            }
+
+            Ok(())
+        } else {
+            let sql = match backend {
+                sea_orm::DatabaseBackend::Postgres => {
+                    r"
+ALTER TABLE users ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ NULL;
+ALTER TABLE addresses ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ NULL;
+                    "
+                }
+                sea_orm::DatabaseBackend::Sqlite => {
+                    r"
+ALTER TABLE users ADD COLUMN deleted_at TEXT NULL;
+ALTER TABLE addresses ADD COLUMN deleted_at TEXT NULL;
+                    "
+                }
+                sea_orm::DatabaseBackend::MySql => unreachable!("handled above"),
+            };
+
+            conn.execute_unprepared(sql).await?;
+            Ok(())
+        }
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        match backend {
+            sea_orm::DatabaseBackend::Sqlite => Ok(()),
+            sea_orm::DatabaseBackend::MySql => {
+                if manager.has_column("users", "deleted_at").await? {
+                    conn.execute_unprepared("ALTER TABLE users DROP COLUMN deleted_at;")
+                        .await?;
+                }
+                if manager.has_column("addresses", "deleted_at").await? {
+                    conn.execute_unprepared("ALTER TABLE addresses DROP COLUMN deleted_at;")
+                        .await?;
+                }
+
+                Ok(())
+            }
+            sea_orm::DatabaseBackend::Postgres => {
+                conn.execute_unprepared(
+                    r"
+ALTER TABLE users DROP COLUMN IF EXISTS deleted_at;
+ALTER TABLE addresses DROP COLUMN IF EXISTS deleted_at;
+                    ",
+                )
+                .await?;
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let sql = match backend {
+            sea_orm::DatabaseBackend::MySql => {
+                "ALTER TABLE users MODIFY COLUMN display_name VARCHAR(255) NULL;"
+            }
+            sea_orm::DatabaseBackend::Postgres => {
+                "ALTER TABLE users ALTER COLUMN display_name DROP NOT NULL;"
+            }
+            sea_orm::DatabaseBackend::Sqlite => {
+                r"
+-- SQLite: Cannot alter column constraints, swap in a nullable column
+ALTER TABLE users ADD COLUMN display_name_nullable TEXT NULL;
+UPDATE users SET display_name_nullable = display_name;
+ALTER TABLE users DROP COLUMN display_name;
+ALTER TABLE users RENAME COLUMN display_name_nullable TO display_name;
+                "
+            }
+        };
+
+        conn.execute_unprepared(sql).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        if backend == sea_orm::DatabaseBackend::MySql {
+            conn.execute_unprepared(
+                "UPDATE users SET display_name = '' WHERE display_name IS NULL;",
+            )
+            .await?;
+            conn.execute_unprepared(
+                "ALTER TABLE users MODIFY COLUMN display_name VARCHAR(255) NOT NULL;",
+            )
+            .await?;
+            return Ok(());
+        }
+
+        let sql = match backend {
+            sea_orm::DatabaseBackend::Postgres => {
+                r"
+UPDATE users SET display_name = '' WHERE display_name IS NULL;
+ALTER TABLE users ALTER COLUMN display_name SET NOT NULL;
+                "
+            }
+            sea_orm::DatabaseBackend::Sqlite => {
+                r"
+-- SQLite: Cannot alter column constraints, swap in a NOT NULL column
+ALTER TABLE users ADD COLUMN display_name_required TEXT NOT NULL DEFAULT '';
+UPDATE users SET display_name_required = COALESCE(display_name, '');
+ALTER TABLE users DROP COLUMN display_name;
+ALTER TABLE users RENAME COLUMN display_name_required TO display_name;
+                "
+            }
+            sea_orm::DatabaseBackend::MySql => unreachable!("handled above"),
+        };
+
+        conn.execute_unprepared(sql).await?;
+        Ok(())
+    }
+}
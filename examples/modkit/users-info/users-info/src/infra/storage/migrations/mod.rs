@@ -4,6 +4,8 @@ mod m20260111_000001_initial;
 mod m20260111_000002_add_tenant_support;
 mod m20260111_000003_add_relationships;
 mod m20260111_000004_add_tenant_to_all_tables;
+mod m20260111_000005_add_soft_delete;
+mod m20260111_000006_nullable_display_name;
 
 pub struct Migrator;
 
@@ -15,6 +17,8 @@ impl MigratorTrait for Migrator {
             Box::new(m20260111_000002_add_tenant_support::Migration),
             Box::new(m20260111_000003_add_relationships::Migration),
             Box::new(m20260111_000004_add_tenant_to_all_tables::Migration),
+            Box::new(m20260111_000005_add_soft_delete::Migration),
+            Box::new(m20260111_000006_nullable_display_name::Migration),
         ]
     }
 }
@@ -32,6 +32,7 @@ pub mod odata_mapper;
 mod addresses_sea_repo;
 mod cities_sea_repo;
 mod db;
+mod soft_delete;
 mod users_sea_repo;
 
 pub use addresses_sea_repo::OrmAddressesRepository;
@@ -11,6 +11,7 @@ use modkit_db::odata::sea_orm_filter::{
     FieldToColumn, ODataFieldMapping, filter_node_to_condition,
 };
 use modkit_odata::filter::FilterNode;
+use modkit_odata::{ODataOrderBy, ODataQuery, OrderKey, SortDir};
 use sea_orm::Condition;
 
 use crate::infra::storage::entity::{
@@ -20,6 +21,20 @@ use crate::infra::storage::entity::{
 };
 use users_info_sdk::odata::{AddressFilterField, CityFilterField, UserFilterField};
 
+/// Apply the default keyset for list pages: newest first on `(created_at, id)`.
+///
+/// `paginate_odata` appends `id` as the tiebreaker, so cursors stay stable when
+/// rows share a timestamp or are inserted between page fetches. An explicit
+/// `$orderby` from the client takes precedence.
+pub(crate) fn with_keyset_order(query: &ODataQuery) -> ODataQuery {
+    query
+        .clone()
+        .with_default_order(ODataOrderBy(vec![OrderKey {
+            field: "created_at".to_owned(),
+            dir: SortDir::Desc,
+        }]))
+}
+
 /// Complete `OData` mapper for `users_info`.
 ///
 /// This is the only users_info-specific code needed for `OData` operations.
@@ -33,6 +48,7 @@ impl FieldToColumn<UserFilterField> for UserODataMapper {
         match field {
             UserFilterField::Id => Column::Id,
             UserFilterField::Email => Column::Email,
+            UserFilterField::DisplayName => Column::DisplayName,
             UserFilterField::CreatedAt => Column::CreatedAt,
         }
     }
@@ -45,6 +61,9 @@ impl ODataFieldMapping<UserFilterField> for UserODataMapper {
         match field {
             UserFilterField::Id => sea_orm::Value::Uuid(Some(Box::new(model.id))),
             UserFilterField::Email => sea_orm::Value::String(Some(Box::new(model.email.clone()))),
+            UserFilterField::DisplayName => {
+                sea_orm::Value::String(model.display_name.clone().map(Box::new))
+            }
             UserFilterField::CreatedAt => {
                 sea_orm::Value::TimeDateTimeWithTimeZone(Some(Box::new(model.created_at)))
             }
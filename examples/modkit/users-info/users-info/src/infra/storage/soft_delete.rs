@@ -0,0 +1,20 @@
+//! Soft-delete query helpers shared by the `SeaORM` repositories.
+
+use sea_orm::Condition;
+use sea_orm::sea_query::{Expr, IntoColumnRef};
+
+use crate::domain::repos::SoftDeleted;
+
+/// Restrict a query to live rows unless soft-deleted ones were asked for.
+///
+/// `deleted_at` is the entity's soft-delete timestamp column.
+pub fn live_rows<C: IntoColumnRef>(
+    deleted_at: C,
+    cond: Condition,
+    deleted: SoftDeleted,
+) -> Condition {
+    match deleted {
+        SoftDeleted::Exclude => cond.add(Expr::col(deleted_at).is_null()),
+        SoftDeleted::Include => cond,
+    }
+}
@@ -2,16 +2,22 @@ use async_trait::async_trait;
 
 use crate::infra::storage::db::db_err;
 use crate::infra::storage::entity::user::{ActiveModel as UserAM, Column, Entity as UserEntity};
-use crate::infra::storage::odata_mapper::UserODataMapper;
-use crate::{domain::error::DomainError, domain::repos::UsersRepository};
+use crate::infra::storage::odata_mapper::{UserODataMapper, with_keyset_order};
+use crate::infra::storage::soft_delete::live_rows;
+use crate::{
+    domain::error::DomainError, domain::repos::SoftDeleted, domain::repos::UsersRepository,
+};
+use modkit_db::odata::sea_orm_filter::escape_like;
 use modkit_db::odata::{LimitCfg, paginate_odata};
 use modkit_db::secure::{
-    DBRunner, SecureDeleteExt, SecureEntityExt, secure_insert, secure_update_with_scope,
+    DBRunner, ScopeError, ScopedLookup, SecureEntityExt, SecureUpdateExt, secure_insert,
+    secure_update_with_scope,
 };
 use modkit_odata::{ODataQuery, Page, SortDir};
 use modkit_security::AccessScope;
-use sea_orm::sea_query::Expr;
-use sea_orm::{EntityTrait, QueryFilter, Set};
+use sea_orm::sea_query::{Expr, Func};
+use sea_orm::{ActiveValue::NotSet, EntityTrait, QueryFilter, Set};
+use time::OffsetDateTime;
 use users_info_sdk::User;
 use users_info_sdk::odata::UserFilterField;
 use uuid::Uuid;
@@ -29,6 +35,26 @@ impl OrmUsersRepository {
     }
 }
 
+/// Name of the `(tenant_id, email)` unique index created by the migrations.
+const TENANT_EMAIL_INDEX: &str = "uk_users_tenant_email";
+
+/// Map a failed write on `user`, telling an email clash apart from other
+/// unique violations.
+///
+/// Postgres and `MySQL` name the violated index in the message; `SQLite`
+/// lists its columns instead.
+fn unique_violation_err(e: ScopeError, user: &User) -> DomainError {
+    if !e.is_unique_violation() {
+        return db_err(e);
+    }
+    let message = e.to_string();
+    if message.contains(TENANT_EMAIL_INDEX) || message.contains("users.email") {
+        DomainError::email_already_exists(user.email.clone())
+    } else {
+        DomainError::conflict(user.id)
+    }
+}
+
 #[async_trait]
 impl UsersRepository for OrmUsersRepository {
     async fn get<C: DBRunner>(
@@ -36,9 +62,14 @@ impl UsersRepository for OrmUsersRepository {
         conn: &C,
         scope: &AccessScope,
         id: Uuid,
+        deleted: SoftDeleted,
     ) -> Result<Option<User>, DomainError> {
         let found = UserEntity::find()
-            .filter(sea_orm::Condition::all().add(Expr::col(Column::Id).eq(id)))
+            .filter(live_rows(
+                Column::DeletedAt,
+                sea_orm::Condition::all().add(Expr::col(Column::Id).eq(id)),
+                deleted,
+            ))
             .secure()
             .scope_with(scope)
             .one(conn)
@@ -47,18 +78,80 @@ impl UsersRepository for OrmUsersRepository {
         Ok(found.map(Into::into))
     }
 
+    async fn lookup<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        id: Uuid,
+    ) -> Result<ScopedLookup<User>, DomainError> {
+        let found = UserEntity::find()
+            .filter(live_rows(
+                Column::DeletedAt,
+                sea_orm::Condition::all().add(Expr::col(Column::Id).eq(id)),
+                SoftDeleted::Exclude,
+            ))
+            .secure()
+            .one_or_out_of_scope(scope, conn)
+            .await
+            .map_err(db_err)?;
+        Ok(found.map(Into::into))
+    }
+
     async fn list_page<C: DBRunner>(
         &self,
         conn: &C,
         scope: &AccessScope,
         query: &ODataQuery,
+        deleted: SoftDeleted,
     ) -> Result<Page<User>, DomainError> {
-        let base_query = UserEntity::find().secure().scope_with(scope);
+        let base_query = UserEntity::find()
+            .secure()
+            .scope_with(scope)
+            .filter(live_rows(
+                Column::DeletedAt,
+                sea_orm::Condition::all(),
+                deleted,
+            ));
 
         let page = paginate_odata::<UserFilterField, UserODataMapper, _, _, _, _>(
             base_query,
             conn,
-            query,
+            &with_keyset_order(query),
+            ("id", SortDir::Desc),
+            self.limit_cfg,
+            Into::into,
+        )
+        .await
+        .map_err(db_err)?;
+
+        Ok(page)
+    }
+
+    async fn search_page<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        term: &str,
+        query: &ODataQuery,
+    ) -> Result<Page<User>, DomainError> {
+        let pattern = format!("%{}%", escape_like(&term.to_lowercase()));
+        let matches = sea_orm::Condition::any()
+            .add(Expr::expr(Func::lower(Expr::col(Column::Email))).like(pattern.as_str()))
+            .add(Expr::expr(Func::lower(Expr::col(Column::DisplayName))).like(pattern.as_str()));
+
+        let base_query = UserEntity::find()
+            .secure()
+            .scope_with(scope)
+            .filter(live_rows(
+                Column::DeletedAt,
+                sea_orm::Condition::all().add(matches),
+                SoftDeleted::Exclude,
+            ));
+
+        let page = paginate_odata::<UserFilterField, UserODataMapper, _, _, _, _>(
+            base_query,
+            conn,
+            &with_keyset_order(query),
             ("id", SortDir::Desc),
             self.limit_cfg,
             Into::into,
@@ -79,14 +172,15 @@ impl UsersRepository for OrmUsersRepository {
             id: Set(user.id),
             tenant_id: Set(user.tenant_id),
             email: Set(user.email.clone()),
-            display_name: Set(user.display_name.clone()),
+            display_name: Set(Some(user.display_name.clone())),
             created_at: Set(user.created_at),
             updated_at: Set(user.updated_at),
+            deleted_at: Set(None),
         };
 
         let _ = secure_insert::<UserEntity>(m, scope, conn)
             .await
-            .map_err(db_err)?;
+            .map_err(|e| unique_violation_err(e, &user))?;
         Ok(user)
     }
 
@@ -100,14 +194,15 @@ impl UsersRepository for OrmUsersRepository {
             id: Set(user.id),
             tenant_id: Set(user.tenant_id),
             email: Set(user.email.clone()),
-            display_name: Set(user.display_name.clone()),
+            display_name: Set(Some(user.display_name.clone())),
             created_at: Set(user.created_at),
             updated_at: Set(user.updated_at),
+            deleted_at: NotSet,
         };
 
         let _ = secure_update_with_scope::<UserEntity>(m, scope, user.id, conn)
             .await
-            .map_err(db_err)?;
+            .map_err(|e| unique_violation_err(e, &user))?;
         Ok(user)
     }
 
@@ -116,9 +211,15 @@ impl UsersRepository for OrmUsersRepository {
         conn: &C,
         scope: &AccessScope,
         id: Uuid,
+        at: OffsetDateTime,
     ) -> Result<bool, DomainError> {
-        let result = UserEntity::delete_many()
-            .filter(sea_orm::Condition::all().add(Expr::col(Column::Id).eq(id)))
+        let result = UserEntity::update_many()
+            .col_expr(Column::DeletedAt, Expr::value(at))
+            .filter(live_rows(
+                Column::DeletedAt,
+                sea_orm::Condition::all().add(Expr::col(Column::Id).eq(id)),
+                SoftDeleted::Exclude,
+            ))
             .secure()
             .scope_with(scope)
             .exec(conn)
@@ -128,6 +229,49 @@ impl UsersRepository for OrmUsersRepository {
         Ok(result.rows_affected > 0)
     }
 
+    async fn restore<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        id: Uuid,
+    ) -> Result<Option<OffsetDateTime>, DomainError> {
+        let Some(deleted_at) = UserEntity::find()
+            .filter(
+                sea_orm::Condition::all()
+                    .add(Expr::col(Column::Id).eq(id))
+                    .add(Expr::col(Column::DeletedAt).is_not_null()),
+            )
+            .secure()
+            .scope_with(scope)
+            .one(conn)
+            .await
+            .map_err(db_err)?
+            .and_then(|row| row.deleted_at)
+        else {
+            return Ok(None);
+        };
+
+        // Match the timestamp read above so a concurrent restore and
+        // re-delete is not mistaken for this one
+        let result = UserEntity::update_many()
+            .col_expr(
+                Column::DeletedAt,
+                Expr::value(Option::<OffsetDateTime>::None),
+            )
+            .filter(
+                sea_orm::Condition::all()
+                    .add(Expr::col(Column::Id).eq(id))
+                    .add(Expr::col(Column::DeletedAt).eq(deleted_at)),
+            )
+            .secure()
+            .scope_with(scope)
+            .exec(conn)
+            .await
+            .map_err(db_err)?;
+
+        Ok((result.rows_affected > 0).then_some(deleted_at))
+    }
+
     async fn exists<C: DBRunner>(
         &self,
         conn: &C,
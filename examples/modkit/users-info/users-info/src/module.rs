@@ -101,6 +101,7 @@ impl Module for UsersInfo {
             max_display_name_length: 100,
             default_page_size: cfg.default_page_size,
             max_page_size: cfg.max_page_size,
+            user_delete_policy: cfg.user_delete_policy,
         };
 
         // Create repository implementations
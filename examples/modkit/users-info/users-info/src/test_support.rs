@@ -77,6 +77,17 @@ pub async fn seed_user(
     tenant_id: Uuid,
     email: &str,
     display_name: &str,
+) {
+    seed_user_with_display_name(db, id, tenant_id, email, Some(display_name)).await;
+}
+
+/// Like [`seed_user`], but `None` stores the user without a display name.
+pub async fn seed_user_with_display_name(
+    db: &impl DBRunner,
+    id: Uuid,
+    tenant_id: Uuid,
+    email: &str,
+    display_name: Option<&str>,
 ) {
     use crate::infra::storage::entity::user::ActiveModel;
     use crate::infra::storage::entity::user::Entity as UserEntity;
@@ -87,9 +98,10 @@ pub async fn seed_user(
         id: Set(id),
         tenant_id: Set(tenant_id),
         email: Set(email.to_owned()),
-        display_name: Set(display_name.to_owned()),
+        display_name: Set(display_name.map(str::to_owned)),
         created_at: Set(now),
         updated_at: Set(now),
+        deleted_at: Set(None),
     };
 
     let scope = AccessScope::for_tenants(vec![tenant_id]);
@@ -208,6 +220,24 @@ pub fn build_services_with_authz(
     db: Db,
     config: ServiceConfig,
     authz: Arc<dyn AuthZResolverClient>,
+) -> Arc<ConcreteAppServices> {
+    build_services_with(db, config, authz, Arc::new(MockAuditPort))
+}
+
+/// Like [`build_services`], with `audit` in place of [`MockAuditPort`].
+pub fn build_services_with_audit(
+    db: Db,
+    config: ServiceConfig,
+    audit: Arc<dyn AuditPort>,
+) -> Arc<ConcreteAppServices> {
+    build_services_with(db, config, Arc::new(MockAuthZResolver), audit)
+}
+
+fn build_services_with(
+    db: Db,
+    config: ServiceConfig,
+    authz: Arc<dyn AuthZResolverClient>,
+    audit: Arc<dyn AuditPort>,
 ) -> Arc<ConcreteAppServices> {
     let limit_cfg = config.limit_cfg();
 
@@ -223,7 +253,7 @@ pub fn build_services_with_authz(
         addresses_repo,
         db,
         Arc::new(MockEventPublisher),
-        Arc::new(MockAuditPort),
+        audit,
         authz,
         config,
         Arc::new(MockUsersMetricsPort),
@@ -273,6 +303,8 @@ impl AuthZResolverClient for FailingAuthZResolver {
 /// - For mutations on `users_info.address`: enforces `owner_id` must equal
 ///   `subject.id` and echoes back `city_id` from resource properties as an
 ///   `eq` constraint (so PEP can enforce city restrictions at SQL level).
+/// - For `list` on `users_info.address`: enforces `owner_id` must equal
+///   `subject.id`, so users only ever list their own addresses.
 ///
 /// Tenant resolution: `context.tenant_context.root_id` if present, otherwise
 /// `subject.properties.tenant_id` (like a real PDP).
@@ -326,6 +358,13 @@ impl AuthZResolverClient for OwnerCityAuthZResolver {
         let is_address = request.resource.resource_type == "users_info.address";
         let is_mutation = matches!(request.action.name.as_str(), "create" | "update" | "delete");
 
+        if is_address && request.action.name == "list" {
+            predicates.push(Predicate::Eq(EqPredicate::new(
+                pep_properties::OWNER_ID,
+                request.subject.id,
+            )));
+        }
+
         if is_address && is_mutation {
             // Enforce owner_id == subject.id
             predicates.push(Predicate::Eq(EqPredicate::new(
@@ -352,3 +391,49 @@ impl AuthZResolverClient for OwnerCityAuthZResolver {
         })
     }
 }
+
+/// Mock `AuthZ` resolver that scopes every address request to its owner.
+///
+/// Always returns the subject's tenant as `owner_tenant_id` constraint and,
+/// for `users_info.address`, adds `eq(owner_id, subject.id)` — even when
+/// `require_constraints=false`, so the GET path re-reads the prefetched
+/// address under that constraint.
+pub struct OwnerOnlyAuthZResolver;
+
+#[async_trait]
+impl AuthZResolverClient for OwnerOnlyAuthZResolver {
+    async fn evaluate(
+        &self,
+        request: EvaluationRequest,
+    ) -> Result<EvaluationResponse, AuthZResolverError> {
+        let mut predicates: Vec<Predicate> = request
+            .subject
+            .properties
+            .get("tenant_id")
+            .and_then(|v| v.as_str())
+            .and_then(|s| Uuid::parse_str(s).ok())
+            .map(|tenant_id| {
+                Predicate::In(InPredicate::new(
+                    pep_properties::OWNER_TENANT_ID,
+                    [tenant_id],
+                ))
+            })
+            .into_iter()
+            .collect();
+
+        if request.resource.resource_type == "users_info.address" {
+            predicates.push(Predicate::Eq(EqPredicate::new(
+                pep_properties::OWNER_ID,
+                request.subject.id,
+            )));
+        }
+
+        Ok(EvaluationResponse {
+            decision: true,
+            context: EvaluationResponseContext {
+                constraints: vec![Constraint { predicates }],
+                ..Default::default()
+            },
+        })
+    }
+}
@@ -0,0 +1,277 @@
+//! Typed access to decoded JWT claims.
+//!
+//! [`Claims`] wraps the raw JSON payload returned by a [`KeyProvider`](crate::KeyProvider)
+//! and exposes parsed getters for the registered claims plus a generic
+//! [`Claims::custom`] for everything else.
+
+use std::collections::BTreeSet;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use time::OffsetDateTime;
+
+use crate::claims_error::ClaimsError;
+use crate::standard_claims::StandardClaim;
+use crate::validation::{RoleMapping, extract_audiences, parse_timestamp};
+
+/// Decoded JWT claims with typed accessors.
+///
+/// # Example
+/// ```
+/// use modkit_auth::Claims;
+/// use serde_json::json;
+///
+/// let claims = Claims::new(json!({
+///     "sub": "user-123",
+///     "aud": ["api", "web"],
+///     "roles": ["admin"]
+/// }));
+///
+/// assert_eq!(claims.subject().unwrap(), "user-123");
+/// assert_eq!(claims.audiences().unwrap(), vec!["api", "web"]);
+/// let roles: Option<Vec<String>> = claims.custom("roles").unwrap();
+/// assert_eq!(roles, Some(vec!["admin".to_owned()]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Claims(Value);
+
+impl Claims {
+    /// Wrap a raw claims payload.
+    #[must_use]
+    pub fn new(raw: Value) -> Self {
+        Self(raw)
+    }
+
+    /// The raw claims payload.
+    #[must_use]
+    pub fn as_value(&self) -> &Value {
+        &self.0
+    }
+
+    /// Unwrap into the raw claims payload.
+    #[must_use]
+    pub fn into_value(self) -> Value {
+        self.0
+    }
+
+    /// The `sub` claim.
+    ///
+    /// # Errors
+    /// `MissingClaim` if absent, `InvalidClaimFormat` if not a string.
+    pub fn subject(&self) -> Result<&str, ClaimsError> {
+        self.required_str(StandardClaim::SUB)
+    }
+
+    /// The `iss` claim.
+    ///
+    /// # Errors
+    /// `MissingClaim` if absent, `InvalidClaimFormat` if not a string.
+    pub fn issuer(&self) -> Result<&str, ClaimsError> {
+        self.required_str(StandardClaim::ISS)
+    }
+
+    /// The `aud` claim, normalized to a list; empty if absent.
+    ///
+    /// # Errors
+    /// `InvalidClaimFormat` if not a string or an array of strings.
+    pub fn audiences(&self) -> Result<Vec<String>, ClaimsError> {
+        self.0
+            .get(StandardClaim::AUD)
+            .map_or_else(|| Ok(Vec::new()), extract_audiences)
+    }
+
+    /// The `exp` claim, if present.
+    ///
+    /// # Errors
+    /// `InvalidClaimFormat` if not a valid unix timestamp.
+    pub fn expires_at(&self) -> Result<Option<OffsetDateTime>, ClaimsError> {
+        self.timestamp(StandardClaim::EXP)
+    }
+
+    /// The `iat` claim, if present.
+    ///
+    /// # Errors
+    /// `InvalidClaimFormat` if not a valid unix timestamp.
+    pub fn issued_at(&self) -> Result<Option<OffsetDateTime>, ClaimsError> {
+        self.timestamp(StandardClaim::IAT)
+    }
+
+    /// The canonical `roles` claim as a set; empty if absent.
+    ///
+    /// Apply a [`RoleMapping`] first to include provider-specific roles.
+    ///
+    /// # Errors
+    /// `InvalidClaimFormat` if not an array of strings.
+    pub fn roles(&self) -> Result<BTreeSet<String>, ClaimsError> {
+        Ok(self.custom(StandardClaim::ROLES)?.unwrap_or_default())
+    }
+
+    /// Flatten provider-specific role claims into the `roles` claim.
+    #[must_use]
+    pub fn with_role_mapping(mut self, mapping: &RoleMapping) -> Self {
+        mapping.apply(&mut self.0);
+        self
+    }
+
+    /// Deserialize an arbitrary claim into `T`; `None` if absent.
+    ///
+    /// # Errors
+    /// `InvalidClaimFormat` naming the claim if its value does not match `T`.
+    pub fn custom<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>, ClaimsError> {
+        self.0
+            .get(name)
+            .map(|value| {
+                T::deserialize(value).map_err(|e| ClaimsError::InvalidClaimFormat {
+                    field: name.to_owned(),
+                    reason: e.to_string(),
+                })
+            })
+            .transpose()
+    }
+
+    fn required_str(&self, name: &str) -> Result<&str, ClaimsError> {
+        self.0
+            .get(name)
+            .ok_or_else(|| ClaimsError::MissingClaim(name.to_owned()))?
+            .as_str()
+            .ok_or_else(|| ClaimsError::InvalidClaimFormat {
+                field: name.to_owned(),
+                reason: "must be a string".to_owned(),
+            })
+    }
+
+    fn timestamp(&self, name: &str) -> Result<Option<OffsetDateTime>, ClaimsError> {
+        self.0
+            .get(name)
+            .map(|value| parse_timestamp(value, name))
+            .transpose()
+    }
+}
+
+impl From<Value> for Claims {
+    fn from(raw: Value) -> Self {
+        Self::new(raw)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Org {
+        id: String,
+        plan: Plan,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Plan {
+        tier: String,
+        seats: u32,
+    }
+
+    #[test]
+    fn test_standard_accessors() {
+        let claims = Claims::new(json!({
+            "sub": "user-123",
+            "iss": "https://auth.example.com",
+            "aud": "api",
+            "exp": 1_700_003_600,
+            "iat": 1_700_000_000,
+        }));
+
+        assert_eq!(claims.subject().unwrap(), "user-123");
+        assert_eq!(claims.issuer().unwrap(), "https://auth.example.com");
+        assert_eq!(claims.audiences().unwrap(), vec!["api".to_owned()]);
+        assert_eq!(
+            claims
+                .expires_at()
+                .unwrap()
+                .map(OffsetDateTime::unix_timestamp),
+            Some(1_700_003_600)
+        );
+        assert_eq!(
+            claims
+                .issued_at()
+                .unwrap()
+                .map(OffsetDateTime::unix_timestamp),
+            Some(1_700_000_000)
+        );
+    }
+
+    #[test]
+    fn test_absent_standard_claims() {
+        let claims = Claims::new(json!({}));
+
+        assert!(matches!(
+            claims.subject(),
+            Err(ClaimsError::MissingClaim(ref c)) if c == "sub"
+        ));
+        assert!(claims.audiences().unwrap().is_empty());
+        assert_eq!(claims.expires_at().unwrap(), None);
+        assert_eq!(claims.issued_at().unwrap(), None);
+    }
+
+    #[test]
+    fn test_custom_nested_object_claim() {
+        let claims = Claims::new(json!({
+            "org": { "id": "acme", "plan": { "tier": "pro", "seats": 25 } }
+        }));
+
+        let org: Option<Org> = claims.custom("org").unwrap();
+        assert_eq!(
+            org,
+            Some(Org {
+                id: "acme".to_owned(),
+                plan: Plan {
+                    tier: "pro".to_owned(),
+                    seats: 25,
+                },
+            })
+        );
+        assert_eq!(claims.custom::<Org>("missing").unwrap(), None);
+    }
+
+    #[test]
+    fn test_roles_after_keycloak_mapping() {
+        let claims = Claims::new(json!({
+            "realm_access": { "roles": ["user"] },
+            "resource_access": { "orders-api": { "roles": ["orders:read"] } }
+        }));
+        assert!(claims.roles().unwrap().is_empty());
+
+        let mapping = RoleMapping {
+            resource_clients: vec!["orders-api".to_owned()],
+            ..RoleMapping::default()
+        };
+        let roles = claims.with_role_mapping(&mapping).roles().unwrap();
+
+        assert_eq!(
+            roles.into_iter().collect::<Vec<_>>(),
+            vec!["orders:read".to_owned(), "user".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_custom_wrong_type_returns_invalid_claim_format() {
+        let claims = Claims::new(json!({
+            "org": { "id": "acme", "plan": { "tier": "pro", "seats": "many" } },
+            "sub": 42,
+        }));
+
+        match claims.custom::<Org>("org") {
+            Err(ClaimsError::InvalidClaimFormat { field, reason }) => {
+                assert_eq!(field, "org");
+                assert!(reason.contains("invalid type"), "reason: {reason}");
+            }
+            other => panic!("expected InvalidClaimFormat, got {other:?}"),
+        }
+        assert!(matches!(
+            claims.subject(),
+            Err(ClaimsError::InvalidClaimFormat { ref field, .. }) if field == "sub"
+        ));
+    }
+}
@@ -24,6 +24,12 @@ pub enum ClaimsError {
     #[error("Token not yet valid (nbf check failed)")]
     NotYetValid,
 
+    #[error("Token too old: issued {age_seconds}s ago, max age is {max_age_seconds}s")]
+    TokenTooOld {
+        age_seconds: u64,
+        max_age_seconds: u64,
+    },
+
     #[error("Malformed claims: {0}")]
     Malformed(String),
 
@@ -47,6 +53,12 @@ pub enum ClaimsError {
 
     #[error("Unknown key ID: {0}")]
     UnknownKeyId(String),
+
+    #[error("No key provider for issuer: {0}")]
+    NoMatchingProvider(String),
+
+    #[error("Disallowed JWT algorithm: {0}")]
+    DisallowedAlgorithm(String),
 }
 
 // Conversion from ClaimsError to AuthError for backward compatibility
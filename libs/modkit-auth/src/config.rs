@@ -1,5 +1,15 @@
-use crate::validation::ValidationConfig;
+use crate::providers::{HmacKeyProvider, JwksKeyProvider};
+use crate::traits::KeyProvider;
+use crate::validation::{
+    AudienceMatch, DEFAULT_ALLOWED_ALGORITHMS, HMAC_ALGORITHMS, RoleMapping, ValidationConfig,
+};
+use jsonwebtoken::Algorithm;
+use modkit_utils::SecretString;
+use modkit_utils::var_expand::{ExpandVars, ExpandVarsError};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
 
 /// Main authentication configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,14 +26,47 @@ pub struct AuthConfig {
     #[serde(default)]
     pub audiences: Vec<String>,
 
+    /// Whether tokens must carry `any` (default) or `all` of `audiences`
+    #[serde(default)]
+    pub audience_match: AudienceMatch,
+
     /// Whether the `exp` claim is required (default: `true`).
     /// Set to `false` to allow tokens without an expiration claim.
     #[serde(default = "default_require_exp")]
     pub require_exp: bool,
 
+    /// Reject tokens issued (`iat`) more than this many seconds ago, whatever
+    /// their `exp` (default: no limit)
+    #[serde(default)]
+    pub max_token_age_seconds: Option<u64>,
+
+    /// Whether the `iat` claim is required (default: `false`)
+    #[serde(default)]
+    pub require_iat: bool,
+
+    /// Claims that must be present and non-empty; dotted paths address nested claims
+    #[serde(default)]
+    pub required_claims: Vec<String>,
+
+    /// How provider-specific role claims are flattened into `roles`; applied
+    /// by the [`SecurityContextMapping`](crate::SecurityContextMapping) built
+    /// from this config
+    #[serde(default)]
+    pub role_mapping: RoleMapping,
+
+    /// Signing algorithms tokens may declare (default: the RSA family); `none`
+    /// is never accepted and an empty list rejects every token
+    #[serde(default = "default_allowed_algorithms")]
+    pub allowed_algorithms: Vec<Algorithm>,
+
     /// JWKS configuration
     #[serde(default)]
     pub jwks: Option<JwksConfig>,
+
+    /// Shared-secret configuration, instead of `jwks`, for internal
+    /// service-to-service tokens
+    #[serde(default)]
+    pub hmac: Option<HmacConfig>,
 }
 
 fn default_leeway() -> i64 {
@@ -34,29 +77,117 @@ fn default_require_exp() -> bool {
     true
 }
 
+fn default_allowed_algorithms() -> Vec<Algorithm> {
+    DEFAULT_ALLOWED_ALGORITHMS.to_vec()
+}
+
 impl Default for AuthConfig {
     fn default() -> Self {
         Self {
             leeway_seconds: default_leeway(),
             issuers: Vec::new(),
             audiences: Vec::new(),
+            audience_match: AudienceMatch::Any,
             require_exp: default_require_exp(),
+            max_token_age_seconds: None,
+            require_iat: false,
+            required_claims: Vec::new(),
+            role_mapping: RoleMapping::default(),
+            allowed_algorithms: default_allowed_algorithms(),
             jwks: None,
+            hmac: None,
         }
     }
 }
 
+impl AuthConfig {
+    /// Build the key provider this config selects: a [`JwksKeyProvider`] for
+    /// `jwks` or an [`HmacKeyProvider`] for `hmac`, restricted to
+    /// `allowed_algorithms`.
+    ///
+    /// # Errors
+    /// - [`KeyProviderConfigError::ConflictingKeySources`] if both are set
+    /// - [`KeyProviderConfigError::NoKeySource`] if neither is set
+    /// - [`KeyProviderConfigError::NoHmacAlgorithm`] if `hmac` is set but
+    ///   `allowed_algorithms` lists no HMAC algorithm
+    /// - [`KeyProviderConfigError::Jwks`] if the JWKS client cannot be created
+    pub fn key_provider(&self) -> Result<Arc<dyn KeyProvider>, KeyProviderConfigError> {
+        match (&self.jwks, &self.hmac) {
+            (Some(_), Some(_)) => Err(KeyProviderConfigError::ConflictingKeySources),
+            (None, None) => Err(KeyProviderConfigError::NoKeySource),
+            (Some(jwks), None) => {
+                let provider = JwksKeyProvider::new(jwks.uri.clone())?
+                    .with_refresh_interval(Duration::from_secs(jwks.refresh_interval_seconds))
+                    .with_max_backoff(Duration::from_secs(jwks.max_backoff_seconds))
+                    .with_allowed_algorithms(self.allowed_algorithms.iter().copied());
+                Ok(Arc::new(provider))
+            }
+            (None, Some(hmac)) => {
+                let algorithms: Vec<Algorithm> = self
+                    .allowed_algorithms
+                    .iter()
+                    .copied()
+                    .filter(|alg| HMAC_ALGORITHMS.contains(alg))
+                    .collect();
+                if algorithms.is_empty() {
+                    return Err(KeyProviderConfigError::NoHmacAlgorithm);
+                }
+                Ok(Arc::new(
+                    HmacKeyProvider::new(hmac.secret.clone()).with_algorithms(algorithms),
+                ))
+            }
+        }
+    }
+}
+
+/// Why a key provider could not be assembled
+#[derive(Debug, Error)]
+pub enum KeyProviderConfigError {
+    #[error("both `jwks` and `hmac` are configured; a plugin verifies one kind of key")]
+    ConflictingKeySources,
+
+    #[error("neither `jwks` nor `hmac` is configured")]
+    NoKeySource,
+
+    #[error("`hmac` is configured but `allowed_algorithms` lists no HMAC algorithm")]
+    NoHmacAlgorithm,
+
+    #[error("issuer {0} would mix shared-secret and public-key providers")]
+    MixedKeyKinds(String),
+
+    #[error("failed to create JWKS client: {0}")]
+    Jwks(#[from] modkit_http::HttpError),
+}
+
 impl From<&AuthConfig> for ValidationConfig {
     fn from(config: &AuthConfig) -> Self {
         Self {
             allowed_issuers: config.issuers.clone(),
             allowed_audiences: config.audiences.clone(),
+            audience_match: config.audience_match,
             leeway_seconds: config.leeway_seconds,
             require_exp: config.require_exp,
+            max_token_age: config.max_token_age_seconds.map(Duration::from_secs),
+            require_iat: config.require_iat,
+            required_claims: config.required_claims.clone(),
+            allowed_algorithms: config.allowed_algorithms.iter().copied().collect(),
         }
     }
 }
 
+/// Expands `${VAR}` placeholders in `issuers`, `audiences`, the JWKS URI and
+/// the HMAC secret, so a module config holding an [`AuthConfig`] can be loaded
+/// with `ModuleCtx::config_expanded` and fail with `ConfigError::VarExpand`
+/// when a referenced variable is unset.
+impl ExpandVars for AuthConfig {
+    fn expand_vars(&mut self) -> Result<(), ExpandVarsError> {
+        self.issuers.expand_vars()?;
+        self.audiences.expand_vars()?;
+        self.jwks.expand_vars()?;
+        self.hmac.expand_vars()
+    }
+}
+
 /// JWKS endpoint configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JwksConfig {
@@ -72,6 +203,29 @@ pub struct JwksConfig {
     pub max_backoff_seconds: u64,
 }
 
+impl ExpandVars for JwksConfig {
+    fn expand_vars(&mut self) -> Result<(), ExpandVarsError> {
+        self.uri.expand_vars()
+    }
+}
+
+/// Shared HMAC secret configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HmacConfig {
+    /// Secret shared with the token issuer; never serialized
+    #[serde(skip_serializing)]
+    pub secret: SecretString,
+}
+
+impl ExpandVars for HmacConfig {
+    fn expand_vars(&mut self) -> Result<(), ExpandVarsError> {
+        let mut secret = self.secret.expose().to_owned();
+        secret.expand_vars()?;
+        self.secret = SecretString::new(secret);
+        Ok(())
+    }
+}
+
 fn default_refresh_interval() -> u64 {
     300
 }
@@ -91,7 +245,11 @@ mod tests {
         assert_eq!(config.leeway_seconds, 60);
         assert!(config.issuers.is_empty());
         assert!(config.audiences.is_empty());
+        assert_eq!(config.audience_match, AudienceMatch::Any);
         assert!(config.require_exp);
+        assert!(config.required_claims.is_empty());
+        assert_eq!(config.role_mapping, RoleMapping::default());
+        assert_eq!(config.allowed_algorithms, DEFAULT_ALLOWED_ALGORITHMS);
         assert!(config.jwks.is_none());
     }
 
@@ -101,12 +259,22 @@ mod tests {
             leeway_seconds: 120,
             issuers: vec!["https://auth.example.com".to_owned()],
             audiences: vec!["api".to_owned()],
+            audience_match: AudienceMatch::All,
             require_exp: true,
+            max_token_age_seconds: Some(43_200),
+            require_iat: true,
+            required_claims: vec!["tenant_id".to_owned()],
+            role_mapping: RoleMapping {
+                resource_clients: vec!["orders-api".to_owned()],
+                ..RoleMapping::default()
+            },
+            allowed_algorithms: vec![Algorithm::RS256],
             jwks: Some(JwksConfig {
                 uri: "https://auth.example.com/.well-known/jwks.json".to_owned(),
                 refresh_interval_seconds: 300,
                 max_backoff_seconds: 3600,
             }),
+            hmac: None,
         };
 
         let json = serde_json::to_string_pretty(&config).unwrap();
@@ -114,7 +282,11 @@ mod tests {
         assert_eq!(deserialized.leeway_seconds, 120);
         assert_eq!(deserialized.issuers, vec!["https://auth.example.com"]);
         assert_eq!(deserialized.audiences, vec!["api"]);
+        assert_eq!(deserialized.audience_match, AudienceMatch::All);
         assert!(deserialized.require_exp);
+        assert_eq!(deserialized.required_claims, vec!["tenant_id"]);
+        assert_eq!(deserialized.role_mapping, config.role_mapping);
+        assert_eq!(deserialized.allowed_algorithms, vec![Algorithm::RS256]);
         let jwks = deserialized.jwks.expect("jwks should be present");
         assert_eq!(jwks.uri, "https://auth.example.com/.well-known/jwks.json");
         assert_eq!(jwks.refresh_interval_seconds, 300);
@@ -145,14 +317,34 @@ mod tests {
             leeway_seconds: 30,
             issuers: vec!["https://auth.example.com".to_owned()],
             audiences: vec!["api".to_owned()],
+            audience_match: AudienceMatch::All,
             require_exp: true,
+            max_token_age_seconds: Some(43_200),
+            require_iat: false,
+            required_claims: vec!["org.id".to_owned()],
+            role_mapping: RoleMapping::default(),
+            allowed_algorithms: vec![Algorithm::RS256, Algorithm::PS256],
             jwks: None,
+            hmac: None,
         };
         let validation_config = ValidationConfig::from(&auth_config);
         assert_eq!(validation_config.allowed_issuers, auth_config.issuers);
         assert_eq!(validation_config.allowed_audiences, auth_config.audiences);
+        assert_eq!(validation_config.audience_match, AudienceMatch::All);
+        assert_eq!(
+            validation_config.max_token_age,
+            Some(Duration::from_hours(12))
+        );
         assert_eq!(validation_config.leeway_seconds, auth_config.leeway_seconds);
         assert!(validation_config.require_exp);
+        assert_eq!(
+            validation_config.required_claims,
+            auth_config.required_claims
+        );
+        assert_eq!(
+            validation_config.allowed_algorithms,
+            std::collections::HashSet::from([Algorithm::RS256, Algorithm::PS256])
+        );
     }
 
     #[test]
@@ -179,4 +371,91 @@ mod tests {
         assert_eq!(config.refresh_interval_seconds, 300);
         assert_eq!(config.max_backoff_seconds, 3600);
     }
+
+    #[tokio::test]
+    async fn test_key_provider_follows_the_configured_key_source() {
+        let jwks: AuthConfig =
+            serde_json::from_str(r#"{"jwks": {"uri": "https://auth.example.com/jwks"}}"#).unwrap();
+        let provider = jwks.key_provider().unwrap();
+        assert_eq!(provider.name(), "jwks");
+        assert!(!provider.uses_shared_secret());
+
+        let hmac: AuthConfig = serde_json::from_str(
+            r#"{"hmac": {"secret": "s3cret"}, "allowed_algorithms": ["HS256"]}"#,
+        )
+        .unwrap();
+        let provider = hmac.key_provider().unwrap();
+        assert_eq!(provider.name(), "hmac");
+        assert!(provider.uses_shared_secret());
+        assert!(
+            !serde_json::to_string(&hmac).unwrap().contains("s3cret"),
+            "the secret must not be serialized"
+        );
+    }
+
+    #[test]
+    fn test_key_provider_rejects_ambiguous_or_unusable_configs() {
+        let both: AuthConfig = serde_json::from_str(
+            r#"{"jwks": {"uri": "https://auth.example.com/jwks"}, "hmac": {"secret": "s"}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            both.key_provider().err(),
+            Some(KeyProviderConfigError::ConflictingKeySources)
+        ));
+
+        assert!(matches!(
+            AuthConfig::default().key_provider().err(),
+            Some(KeyProviderConfigError::NoKeySource)
+        ));
+
+        // Default allowed_algorithms are RSA only
+        let rsa_only: AuthConfig = serde_json::from_str(r#"{"hmac": {"secret": "s"}}"#).unwrap();
+        assert!(matches!(
+            rsa_only.key_provider().err(),
+            Some(KeyProviderConfigError::NoHmacAlgorithm)
+        ));
+    }
+
+    #[test]
+    fn test_expand_vars_resolves_jwks_url_from_env() {
+        let json = r#"{
+            "issuers": ["${AUTH_TEST_ISSUER}"],
+            "jwks": {"uri": "${AUTH_TEST_JWKS_URL}"}
+        }"#;
+        let mut config: AuthConfig = serde_json::from_str(json).unwrap();
+
+        temp_env::with_vars(
+            [
+                ("AUTH_TEST_ISSUER", Some("https://auth.example.com")),
+                (
+                    "AUTH_TEST_JWKS_URL",
+                    Some("https://auth.example.com/.well-known/jwks.json"),
+                ),
+            ],
+            || config.expand_vars().unwrap(),
+        );
+
+        assert_eq!(config.issuers, vec!["https://auth.example.com"]);
+        assert_eq!(
+            config.jwks.expect("jwks should be present").uri,
+            "https://auth.example.com/.well-known/jwks.json"
+        );
+    }
+
+    #[test]
+    fn test_expand_vars_missing_var_names_the_variable() {
+        let json = r#"{"jwks": {"uri": "${AUTH_TEST_MISSING_JWKS_URL}"}}"#;
+        let mut config: AuthConfig = serde_json::from_str(json).unwrap();
+
+        let err = temp_env::with_var_unset("AUTH_TEST_MISSING_JWKS_URL", || {
+            config.expand_vars().unwrap_err()
+        });
+
+        assert!(
+            matches!(err, ExpandVarsError::Var { ref name, .. } if name == "AUTH_TEST_MISSING_JWKS_URL"),
+            "unexpected error: {err}"
+        );
+        assert!(err.to_string().contains("AUTH_TEST_MISSING_JWKS_URL"));
+    }
 }
@@ -7,30 +7,47 @@ pub mod http_error;
 pub mod traits;
 
 // JWT / JWKS infrastructure
+pub mod claims;
 pub mod claims_error;
 pub mod config;
 pub mod metrics;
 pub mod providers;
+pub mod security_context;
 pub mod standard_claims;
 pub mod validation;
 
 // Outbound OAuth2 client credentials
 pub mod oauth2;
 
+#[cfg(test)]
+mod test_support;
+
 // Core exports
 pub use errors::AuthError;
 pub use traits::{KeyProvider, TokenValidator};
 
 // JWT / JWKS exports
+pub use claims::Claims;
 pub use claims_error::ClaimsError;
-pub use config::{AuthConfig, JwksConfig};
-pub use metrics::{AuthEvent, AuthMetricLabels, AuthMetrics, LoggingMetrics, NoOpMetrics};
-pub use providers::JwksKeyProvider;
+pub use config::{AuthConfig, HmacConfig, JwksConfig, KeyProviderConfigError};
+pub use metrics::{
+    AuthDecision, AuthEvent, AuthMetricLabels, AuthMetrics, AuthOutcome, LeveledLoggingMetrics,
+    LoggingMetrics, NoOpMetrics,
+};
+pub use providers::{
+    AlgorithmGuardKeyProvider, CachedJwks, ExpiryGuardKeyProvider, HmacKeyProvider,
+    IssuerKeyProvider, JwksKeyProvider, KeyRotation, ReloadableKeyProvider,
+};
+pub use security_context::SecurityContextMapping;
 pub use standard_claims::StandardClaim;
-pub use validation::{ValidationConfig, validate_claims};
+pub use validation::{
+    AudienceMatch, DEFAULT_ALLOWED_ALGORITHMS, HMAC_ALGORITHMS, RoleMapping, ValidationConfig,
+    token_age, validate_algorithm, validate_claims,
+};
 
 // Outbound OAuth2 exports
 pub use oauth2::{
-    BearerAuthLayer, ClientAuthMethod, FetchedToken, HttpClientBuilderExt, OAuthClientConfig,
-    SecretString, Token, TokenError, fetch_token,
+    BearerAuthLayer, ClientAuthMethod, DeviceAuthorization, DeviceFlow, DeviceFlowConfig,
+    FetchedToken, HttpClientBuilderExt, OAuthClientConfig, ScopeHint, SecretString, Token,
+    TokenError, TokenExchangeRequest, fetch_token,
 };
@@ -1,7 +1,14 @@
-/// Metrics tracking for auth events
-///
-/// This module provides a trait-based approach to metrics that can be
-/// implemented with various backends (Prometheus, `StatsD`, etc.)
+//! Metrics tracking for auth events
+//!
+//! This module provides a trait-based approach to metrics that can be
+//! implemented with various backends (Prometheus, `StatsD`, etc.)
+
+use std::time::Duration;
+
+use tracing::Level;
+
+use crate::errors::AuthError;
+
 /// Auth event types for metrics tracking
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AuthEvent {
@@ -22,6 +29,15 @@ pub enum AuthEvent {
 
     /// Opaque token validation failed
     OpaqueTokenInvalid,
+
+    /// Summary of a complete auth decision; see [`AuthDecision`]
+    Decision,
+
+    /// Lookup served from a provider cache (labelled with `cache`)
+    CacheHit,
+
+    /// Lookup not found in a provider cache (labelled with `cache`)
+    CacheMiss,
 }
 
 impl AuthEvent {
@@ -35,6 +51,9 @@ impl AuthEvent {
             AuthEvent::JwksRefreshFailure => "auth.jwks.refresh.fail",
             AuthEvent::OpaqueTokenValid => "auth.opaque.valid",
             AuthEvent::OpaqueTokenInvalid => "auth.opaque.invalid",
+            AuthEvent::Decision => "auth.decision",
+            AuthEvent::CacheHit => "auth.cache.hit",
+            AuthEvent::CacheMiss => "auth.cache.miss",
         }
     }
 }
@@ -54,6 +73,9 @@ pub struct AuthMetricLabels {
 
     /// Error type (for failures)
     pub error_type: Option<String>,
+
+    /// Cache name (for cache hits/misses, e.g. "jwks", "introspection")
+    pub cache: Option<String>,
 }
 
 impl AuthMetricLabels {
@@ -76,6 +98,97 @@ impl AuthMetricLabels {
         self.error_type = Some(error_type.into());
         self
     }
+
+    pub fn with_cache(mut self, cache: impl Into<String>) -> Self {
+        self.cache = Some(cache.into());
+        self
+    }
+}
+
+/// Outcome of an auth decision
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthOutcome {
+    /// The request was authenticated and authorized
+    Allowed,
+
+    /// Credentials were missing or invalid
+    Unauthenticated,
+
+    /// Credentials were valid but insufficient
+    Forbidden,
+
+    /// The decision could not be made (e.g., JWKS unavailable)
+    Error,
+}
+
+impl AuthOutcome {
+    /// Classify a failed decision by its error
+    #[must_use]
+    pub const fn from_error(err: &AuthError) -> Self {
+        match err {
+            AuthError::Forbidden => Self::Forbidden,
+            AuthError::JwksFetchFailed(_) | AuthError::Internal(_) => Self::Error,
+            _ => Self::Unauthenticated,
+        }
+    }
+
+    /// Get the log/label value for this outcome
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            AuthOutcome::Allowed => "allowed",
+            AuthOutcome::Unauthenticated => "unauthenticated",
+            AuthOutcome::Forbidden => "forbidden",
+            AuthOutcome::Error => "error",
+        }
+    }
+}
+
+/// Summary of one auth decision, recorded via [`AuthMetrics::record_decision`]
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct AuthDecision {
+    /// Authenticated subject (`sub`), if known
+    pub subject: Option<String>,
+
+    /// Token issuer, if known
+    pub issuer: Option<String>,
+
+    /// Plugin or provider that made the decision
+    pub plugin: Option<String>,
+
+    /// Outcome of the decision
+    pub outcome: AuthOutcome,
+
+    /// Time taken to reach the decision
+    pub latency: Duration,
+}
+
+impl AuthDecision {
+    pub fn new(outcome: AuthOutcome, latency: Duration) -> Self {
+        Self {
+            subject: None,
+            issuer: None,
+            plugin: None,
+            outcome,
+            latency,
+        }
+    }
+
+    pub fn with_subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = Some(subject.into());
+        self
+    }
+
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.issuer = Some(issuer.into());
+        self
+    }
+
+    pub fn with_plugin(mut self, plugin: impl Into<String>) -> Self {
+        self.plugin = Some(plugin.into());
+        self
+    }
 }
 
 /// Trait for metrics backends
@@ -85,6 +198,28 @@ pub trait AuthMetrics: Send + Sync {
 
     /// Record validation duration
     fn record_duration(&self, duration_ms: u64, labels: &AuthMetricLabels);
+
+    /// Record the summary of an auth decision
+    ///
+    /// Defaults to an [`AuthEvent::Decision`] event plus its duration, labelled
+    /// with the plugin, issuer and outcome. The subject is left out of the labels
+    /// to keep metric cardinality bounded.
+    fn record_decision(&self, decision: &AuthDecision) {
+        let mut labels = AuthMetricLabels {
+            provider: decision.plugin.clone(),
+            issuer: decision.issuer.clone(),
+            ..AuthMetricLabels::default()
+        };
+        if decision.outcome != AuthOutcome::Allowed {
+            labels.error_type = Some(decision.outcome.as_str().to_owned());
+        }
+        self.record_event(AuthEvent::Decision, &labels);
+        self.record_duration(duration_ms(decision.latency), &labels);
+    }
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
 }
 
 /// No-op metrics implementation (default)
@@ -102,9 +237,23 @@ impl AuthMetrics for NoOpMetrics {
 }
 
 /// Logging-based metrics implementation (for debugging)
+///
+/// Events and durations are logged at `DEBUG`. Each auth decision is logged as
+/// a single structured record with target `auth_decision` at `INFO`; use
+/// [`LoggingMetrics::with_decision_level`] for another level.
 #[derive(Debug, Clone, Copy)]
 pub struct LoggingMetrics;
 
+impl LoggingMetrics {
+    /// Log the per-decision record at `level` instead of `INFO`
+    #[must_use]
+    pub const fn with_decision_level(self, level: Level) -> LeveledLoggingMetrics {
+        LeveledLoggingMetrics {
+            decision_level: level,
+        }
+    }
+}
+
 impl AuthMetrics for LoggingMetrics {
     fn record_event(&self, event: AuthEvent, labels: &AuthMetricLabels) {
         tracing::debug!(
@@ -113,6 +262,7 @@ impl AuthMetrics for LoggingMetrics {
             issuer = ?labels.issuer,
             kid = ?labels.kid,
             error_type = ?labels.error_type,
+            cache = ?labels.cache,
             "Auth event recorded"
         );
     }
@@ -126,12 +276,66 @@ impl AuthMetrics for LoggingMetrics {
             "Validation duration recorded"
         );
     }
+
+    fn record_decision(&self, decision: &AuthDecision) {
+        log_decision(decision, Level::INFO);
+    }
+}
+
+/// [`LoggingMetrics`] with the per-decision record at a configured level
+#[derive(Debug, Clone, Copy)]
+pub struct LeveledLoggingMetrics {
+    decision_level: Level,
+}
+
+impl AuthMetrics for LeveledLoggingMetrics {
+    fn record_event(&self, event: AuthEvent, labels: &AuthMetricLabels) {
+        LoggingMetrics.record_event(event, labels);
+    }
+
+    fn record_duration(&self, duration_ms: u64, labels: &AuthMetricLabels) {
+        LoggingMetrics.record_duration(duration_ms, labels);
+    }
+
+    fn record_decision(&self, decision: &AuthDecision) {
+        log_decision(decision, self.decision_level);
+    }
+}
+
+fn log_decision(decision: &AuthDecision, level: Level) {
+    macro_rules! decision_event {
+        ($level:expr) => {
+            tracing::event!(
+                target: "auth_decision",
+                $level,
+                metric = AuthEvent::Decision.metric_name(),
+                subject = decision.subject.as_deref(),
+                issuer = decision.issuer.as_deref(),
+                plugin = decision.plugin.as_deref(),
+                outcome = decision.outcome.as_str(),
+                latency_ms = duration_ms(decision.latency),
+                "Auth decision"
+            )
+        };
+    }
+
+    // `tracing` needs the level at compile time
+    match level {
+        Level::ERROR => decision_event!(Level::ERROR),
+        Level::WARN => decision_event!(Level::WARN),
+        Level::INFO => decision_event!(Level::INFO),
+        Level::DEBUG => decision_event!(Level::DEBUG),
+        Level::TRACE => decision_event!(Level::TRACE),
+    }
 }
 
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::SubscriberExt;
 
     #[test]
     fn test_auth_event_metric_names() {
@@ -145,6 +349,8 @@ mod tests {
             AuthEvent::JwksRefreshFailure.metric_name(),
             "auth.jwks.refresh.fail"
         );
+        assert_eq!(AuthEvent::CacheHit.metric_name(), "auth.cache.hit");
+        assert_eq!(AuthEvent::CacheMiss.metric_name(), "auth.cache.miss");
     }
 
     #[test]
@@ -158,6 +364,7 @@ mod tests {
         assert_eq!(labels.issuer, Some("https://kc.example.com".to_owned()));
         assert_eq!(labels.kid, Some("key-123".to_owned()));
         assert_eq!(labels.error_type, None);
+        assert_eq!(labels.cache, None);
     }
 
     #[test]
@@ -181,4 +388,111 @@ mod tests {
         metrics.record_event(AuthEvent::JwtValid, &labels);
         metrics.record_duration(50, &labels);
     }
+
+    type CapturedEvent = (Level, HashMap<String, String>);
+
+    /// Fields of every `auth_decision` event emitted while capturing.
+    #[derive(Clone, Default)]
+    struct DecisionCapture {
+        events: Arc<Mutex<Vec<CapturedEvent>>>,
+    }
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for DecisionCapture {
+        fn on_event(
+            &self,
+            event: &tracing::Event<'_>,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            if event.metadata().target() != "auth_decision" {
+                return;
+            }
+            let mut fields = HashMap::new();
+            event.record(&mut FieldVisitor(&mut fields));
+            self.events
+                .lock()
+                .unwrap()
+                .push((*event.metadata().level(), fields));
+        }
+    }
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(field.name().to_owned(), format!("{value:?}"));
+        }
+
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            self.0.insert(field.name().to_owned(), value.to_owned());
+        }
+
+        fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+            self.0.insert(field.name().to_owned(), value.to_string());
+        }
+    }
+
+    fn capture_decisions(
+        metrics: &impl AuthMetrics,
+        decision: &AuthDecision,
+    ) -> Vec<CapturedEvent> {
+        let capture = DecisionCapture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        tracing::subscriber::with_default(subscriber, || metrics.record_decision(decision));
+        capture.events.lock().unwrap().clone()
+    }
+
+    #[test]
+    fn test_logging_metrics_records_successful_decision() {
+        let decision = AuthDecision::new(AuthOutcome::Allowed, Duration::from_millis(12))
+            .with_subject("user-123")
+            .with_issuer("https://kc.example.com")
+            .with_plugin("oidc");
+
+        let events = capture_decisions(&LoggingMetrics, &decision);
+
+        assert_eq!(events.len(), 1, "one record per decision");
+        let (level, fields) = &events[0];
+        assert_eq!(*level, Level::INFO);
+        assert_eq!(fields["metric"], "auth.decision");
+        assert_eq!(fields["subject"], "user-123");
+        assert_eq!(fields["issuer"], "https://kc.example.com");
+        assert_eq!(fields["plugin"], "oidc");
+        assert_eq!(fields["outcome"], "allowed");
+        assert_eq!(fields["latency_ms"], "12");
+    }
+
+    #[test]
+    fn test_logging_metrics_records_forbidden_decision_at_configured_level() {
+        let decision = AuthDecision::new(
+            AuthOutcome::from_error(&AuthError::Forbidden),
+            Duration::from_millis(3),
+        )
+        .with_subject("user-456")
+        .with_issuer("https://kc.example.com")
+        .with_plugin("oidc");
+        let metrics = LoggingMetrics.with_decision_level(Level::WARN);
+
+        let events = capture_decisions(&metrics, &decision);
+
+        assert_eq!(events.len(), 1);
+        let (level, fields) = &events[0];
+        assert_eq!(*level, Level::WARN);
+        assert_eq!(fields["outcome"], "forbidden");
+        assert_eq!(fields["subject"], "user-456");
+        assert_eq!(fields["issuer"], "https://kc.example.com");
+        assert_eq!(fields["plugin"], "oidc");
+        assert_eq!(fields["latency_ms"], "3");
+    }
+
+    #[test]
+    fn test_outcome_from_error() {
+        assert_eq!(
+            AuthOutcome::from_error(&AuthError::TokenExpired),
+            AuthOutcome::Unauthenticated
+        );
+        assert_eq!(
+            AuthOutcome::from_error(&AuthError::JwksFetchFailed("timeout".to_owned())),
+            AuthOutcome::Error
+        );
+    }
 }
@@ -0,0 +1,628 @@
+//! `OAuth2` device authorization grant (RFC 8628) for input-constrained
+//! clients such as CLI tools.
+//!
+//! The flow has two steps:
+//! 1. [`DeviceFlow::request_code`] obtains a device code and a user code; the
+//!    caller shows [`DeviceAuthorization::verification_uri`] and
+//!    [`DeviceAuthorization::user_code`] to the user.
+//! 2. [`DeviceFlow::poll_token`] polls the token endpoint until the user
+//!    approves, honoring `authorization_pending` and `slow_down`, and fails on
+//!    `expired_token`, `access_denied` or any other error. The issued token
+//!    comes back as a [`Token`] that refreshes with the issued refresh token.
+
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+use serde::Deserialize;
+use url::Url;
+
+use super::config::OAuthClientConfig;
+use super::error::TokenError;
+use super::token::Token;
+use super::types::{ClientAuthMethod, TokenResponse};
+use modkit_utils::SecretString;
+
+/// `grant_type` value for polling the token endpoint (RFC 8628 §3.4).
+pub const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Polling interval used when the server omits `interval` (RFC 8628 §3.2).
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Amount added to the polling interval on every `slow_down` (RFC 8628 §3.5).
+const SLOW_DOWN_STEP: Duration = Duration::from_secs(5);
+
+/// Configuration for the device authorization grant.
+///
+/// Device clients are usually public, so `client_secret` is optional; when
+/// set it is sent in the form body alongside `client_id`.
+///
+/// `Debug` is manually implemented to redact [`client_secret`](Self::client_secret).
+#[derive(Clone)]
+pub struct DeviceFlowConfig {
+    /// Device authorization endpoint URL.
+    pub device_authorization_endpoint: Url,
+
+    /// Token endpoint URL polled for the issued token.
+    pub token_endpoint: Url,
+
+    /// `OAuth2` client identifier.
+    pub client_id: String,
+
+    /// Optional client secret for confidential device clients.
+    pub client_secret: Option<SecretString>,
+
+    /// Requested scopes.
+    pub scopes: Vec<String>,
+
+    /// Fallback TTL when the token endpoint omits `expires_in` (default: 5 min).
+    pub default_ttl: Duration,
+
+    /// Override for the internal HTTP client configuration.
+    /// When `None`,
+    /// [`HttpClientConfig::token_endpoint()`](modkit_http::HttpClientConfig::token_endpoint)
+    /// is used.
+    pub http_config: Option<modkit_http::HttpClientConfig>,
+}
+
+impl DeviceFlowConfig {
+    /// Configuration for a public client with no scopes.
+    #[must_use]
+    pub fn new(
+        device_authorization_endpoint: Url,
+        token_endpoint: Url,
+        client_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            device_authorization_endpoint,
+            token_endpoint,
+            client_id: client_id.into(),
+            client_secret: None,
+            scopes: Vec::new(),
+            default_ttl: Duration::from_mins(5),
+            http_config: None,
+        }
+    }
+}
+
+/// `Debug` redacts `client_secret` to prevent accidental exposure in logs.
+impl fmt::Debug for DeviceFlowConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeviceFlowConfig")
+            .field(
+                "device_authorization_endpoint",
+                &self.device_authorization_endpoint,
+            )
+            .field("token_endpoint", &self.token_endpoint)
+            .field("client_id", &self.client_id)
+            .field(
+                "client_secret",
+                &self.client_secret.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("scopes", &self.scopes)
+            .field("default_ttl", &self.default_ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Device authorization response (RFC 8628 §3.2).
+///
+/// `Debug` is manually implemented to redact the device code.
+pub struct DeviceAuthorization {
+    device_code: SecretString,
+
+    /// Code the user enters at [`verification_uri`](Self::verification_uri).
+    pub user_code: String,
+
+    /// Where the user approves the request.
+    pub verification_uri: String,
+
+    /// Verification URI with the user code embedded, if the server offers one.
+    pub verification_uri_complete: Option<String>,
+
+    /// Lifetime of the device and user codes.
+    pub expires_in: Duration,
+
+    /// Minimum wait between token polls.
+    pub interval: Duration,
+}
+
+/// `Debug` redacts the device code to prevent accidental exposure in logs.
+impl fmt::Debug for DeviceAuthorization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeviceAuthorization")
+            .field("device_code", &"[REDACTED]")
+            .field("user_code", &self.user_code)
+            .field("verification_uri", &self.verification_uri)
+            .field("verification_uri_complete", &self.verification_uri_complete)
+            .field("expires_in", &self.expires_in)
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+struct DeviceAuthorizationResponse {
+    device_code: String,
+    user_code: String,
+    // Some providers predate the RFC and use `verification_url`.
+    #[serde(alias = "verification_url")]
+    verification_uri: String,
+    #[serde(default)]
+    verification_uri_complete: Option<String>,
+    expires_in: u64,
+    #[serde(default)]
+    interval: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+/// Result of a single token poll.
+enum PollOutcome {
+    Ready(TokenResponse),
+    Pending,
+    SlowDown,
+}
+
+/// Client for the device authorization grant.
+pub struct DeviceFlow {
+    client: modkit_http::HttpClient,
+    config: DeviceFlowConfig,
+}
+
+impl fmt::Debug for DeviceFlow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeviceFlow")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DeviceFlow {
+    /// Build a device flow client from the given configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TokenError::ConfigError`] if `client_id` is empty.
+    /// Returns [`TokenError::Http`] if the underlying `HttpClient` fails to
+    /// build.
+    pub fn new(config: DeviceFlowConfig) -> Result<Self, TokenError> {
+        if config.client_id.trim().is_empty() {
+            return Err(TokenError::ConfigError(
+                "client_id must not be empty".into(),
+            ));
+        }
+
+        let http_config = config
+            .http_config
+            .clone()
+            .unwrap_or_else(modkit_http::HttpClientConfig::token_endpoint);
+        let client = modkit_http::HttpClientBuilder::with_config(http_config)
+            .build()
+            .map_err(|e| {
+                TokenError::Http(crate::http_error::format_http_error(
+                    &e,
+                    "OAuth2 device authorization",
+                ))
+            })?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Request a device code and user code.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TokenError::Http`] if the request fails or returns a
+    /// non-success status.
+    /// Returns [`TokenError::InvalidResponse`] if the response cannot be parsed.
+    pub async fn request_code(&self) -> Result<DeviceAuthorization, TokenError> {
+        let scope = self.config.scopes.join(" ");
+        let mut fields = vec![("client_id", self.config.client_id.as_str())];
+        if !scope.is_empty() {
+            fields.push(("scope", &scope));
+        }
+        if let Some(ref secret) = self.config.client_secret {
+            fields.push(("client_secret", secret.expose()));
+        }
+
+        let http_err = |e| {
+            TokenError::Http(crate::http_error::format_http_error(
+                &e,
+                "OAuth2 device authorization",
+            ))
+        };
+        let resp: DeviceAuthorizationResponse = self
+            .client
+            .post(self.config.device_authorization_endpoint.as_str())
+            .form(fields.as_slice())
+            .map_err(http_err)?
+            .send()
+            .await
+            .map_err(http_err)?
+            .error_for_status()
+            .map_err(http_err)?
+            .json()
+            .await
+            .map_err(|e| {
+                TokenError::InvalidResponse(crate::http_error::format_http_error(
+                    &e,
+                    "OAuth2 device authorization",
+                ))
+            })?;
+
+        Ok(DeviceAuthorization {
+            device_code: SecretString::new(&resp.device_code),
+            user_code: resp.user_code,
+            verification_uri: resp.verification_uri,
+            verification_uri_complete: resp.verification_uri_complete,
+            expires_in: Duration::from_secs(resp.expires_in),
+            interval: resp.interval.map_or(DEFAULT_INTERVAL, Duration::from_secs),
+        })
+    }
+
+    /// Poll the token endpoint until the user approves the request.
+    ///
+    /// Waits [`DeviceAuthorization::interval`] before each poll and grows it
+    /// by 5 seconds on every `slow_down`.
+    ///
+    /// The returned [`Token`] renews itself with the `refresh_token` grant
+    /// (ask for `offline_access` or the provider's equivalent); if the server
+    /// issued no refresh token it stops serving once the access token expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TokenError::DeviceAuthorization`] if the codes expire
+    /// (`expired_token`, or `expires_in` elapses), the user denies access, or
+    /// the server reports any other `OAuth2` error.
+    /// Returns [`TokenError::Http`] on transport failures.
+    /// Returns [`TokenError::UnsupportedTokenType`] if the server returns a
+    /// non-Bearer token type.
+    pub async fn poll_token(
+        &self,
+        authorization: &DeviceAuthorization,
+    ) -> Result<Token, TokenError> {
+        let issued = poll_until_ready(authorization.interval, authorization.expires_in, || {
+            self.poll_once(authorization)
+        })
+        .await?;
+
+        let config = OAuthClientConfig {
+            token_endpoint: Some(self.config.token_endpoint.clone()),
+            client_id: self.config.client_id.clone(),
+            client_secret: self
+                .config
+                .client_secret
+                .clone()
+                .unwrap_or_else(|| SecretString::new(String::new())),
+            scopes: self.config.scopes.clone(),
+            auth_method: ClientAuthMethod::Form,
+            default_ttl: self.config.default_ttl,
+            http_config: self.config.http_config.clone(),
+            ..OAuthClientConfig::default()
+        };
+        Token::issued(
+            config,
+            &issued.access_token,
+            issued.expires_in,
+            issued.refresh_token.as_deref().map(SecretString::new),
+        )
+        .await
+    }
+
+    async fn poll_once(
+        &self,
+        authorization: &DeviceAuthorization,
+    ) -> Result<PollOutcome, TokenError> {
+        let mut fields = vec![
+            ("grant_type", DEVICE_CODE_GRANT_TYPE),
+            ("device_code", authorization.device_code.expose()),
+            ("client_id", self.config.client_id.as_str()),
+        ];
+        if let Some(ref secret) = self.config.client_secret {
+            fields.push(("client_secret", secret.expose()));
+        }
+
+        let http_err = |e| {
+            TokenError::Http(crate::http_error::format_http_error(
+                &e,
+                "OAuth2 device token",
+            ))
+        };
+        let response = self
+            .client
+            .post(self.config.token_endpoint.as_str())
+            .form(fields.as_slice())
+            .map_err(http_err)?
+            .send()
+            .await
+            .map_err(http_err)?;
+        let status = response.status();
+        let body = response.bytes().await.map_err(http_err)?;
+
+        if status.is_success() {
+            let token: TokenResponse = serde_json::from_slice(&body)
+                .map_err(|e| TokenError::InvalidResponse(format!("device token response: {e}")))?;
+            if let Some(ref tt) = token.token_type
+                && !tt.eq_ignore_ascii_case("bearer")
+            {
+                return Err(TokenError::UnsupportedTokenType(tt.clone()));
+            }
+            return Ok(PollOutcome::Ready(token));
+        }
+
+        // RFC 8628 §3.5: pending/slow_down come back as 400 error responses.
+        let Ok(error) = serde_json::from_slice::<ErrorResponse>(&body) else {
+            return Err(TokenError::Http(format!(
+                "OAuth2 device token HTTP {status}"
+            )));
+        };
+        match error.error.as_str() {
+            "authorization_pending" => Ok(PollOutcome::Pending),
+            "slow_down" => Ok(PollOutcome::SlowDown),
+            "expired_token" => Err(TokenError::DeviceAuthorization(
+                "device code expired before the user approved it".into(),
+            )),
+            "access_denied" => Err(TokenError::DeviceAuthorization(
+                "user denied the authorization request".into(),
+            )),
+            other => Err(TokenError::DeviceAuthorization(
+                match error.error_description {
+                    Some(description) => format!("{other}: {description}"),
+                    None => other.to_owned(),
+                },
+            )),
+        }
+    }
+}
+
+/// Drive `attempt` until it yields a token, sleeping `interval` before each
+/// call and giving up once `expires_in` has elapsed.
+async fn poll_until_ready<F, Fut>(
+    mut interval: Duration,
+    expires_in: Duration,
+    mut attempt: F,
+) -> Result<TokenResponse, TokenError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<PollOutcome, TokenError>>,
+{
+    let deadline = tokio::time::Instant::now() + expires_in;
+    loop {
+        tokio::time::sleep(interval).await;
+        if tokio::time::Instant::now() > deadline {
+            return Err(TokenError::DeviceAuthorization(
+                "device code expired before the user approved it".into(),
+            ));
+        }
+        match attempt().await? {
+            PollOutcome::Ready(token) => return Ok(token),
+            PollOutcome::Pending => {}
+            PollOutcome::SlowDown => {
+                interval += SLOW_DOWN_STEP;
+                tracing::debug!(?interval, "OAuth2 device flow: slowing down polling");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    use super::*;
+    use httpmock::prelude::*;
+
+    fn test_config(server: &MockServer) -> DeviceFlowConfig {
+        DeviceFlowConfig {
+            scopes: vec!["openid".to_owned(), "offline_access".to_owned()],
+            http_config: Some(modkit_http::HttpClientConfig::for_testing()),
+            ..DeviceFlowConfig::new(
+                Url::parse(&server.url("/device")).unwrap(),
+                Url::parse(&server.url("/token")).unwrap(),
+                "cli-client",
+            )
+        }
+    }
+
+    fn ready(token: &str) -> PollOutcome {
+        PollOutcome::Ready(TokenResponse {
+            access_token: token.to_owned(),
+            expires_in: Some(3600),
+            token_type: None,
+            refresh_token: None,
+        })
+    }
+
+    /// Run the polling loop over scripted outcomes, recording when each poll
+    /// happened relative to the start.
+    async fn run_script(
+        interval: Duration,
+        outcomes: Vec<PollOutcome>,
+    ) -> (Result<TokenResponse, TokenError>, Vec<Duration>) {
+        let start = tokio::time::Instant::now();
+        let script = Mutex::new(VecDeque::from(outcomes));
+        let polls = Mutex::new(Vec::new());
+        let result = poll_until_ready(interval, Duration::from_mins(10), || {
+            polls.lock().unwrap().push(start.elapsed());
+            let next = script
+                .lock()
+                .unwrap()
+                .pop_front()
+                .expect("script exhausted");
+            async move { Ok(next) }
+        })
+        .await;
+        (result, polls.into_inner().unwrap())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn polling_continues_while_pending_then_returns_token() {
+        let (result, polls) = run_script(
+            Duration::from_secs(5),
+            vec![
+                PollOutcome::Pending,
+                PollOutcome::Pending,
+                ready("tok-device"),
+            ],
+        )
+        .await;
+
+        assert_eq!(result.unwrap().access_token, "tok-device");
+        assert_eq!(
+            polls,
+            vec![
+                Duration::from_secs(5),
+                Duration::from_secs(10),
+                Duration::from_secs(15),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_down_increases_interval() {
+        let (result, polls) = run_script(
+            Duration::from_secs(5),
+            vec![
+                PollOutcome::SlowDown,
+                PollOutcome::Pending,
+                ready("tok-slow"),
+            ],
+        )
+        .await;
+
+        assert!(result.is_ok());
+        // 5s, then 10s after slow_down, and the larger interval sticks.
+        assert_eq!(
+            polls,
+            vec![
+                Duration::from_secs(5),
+                Duration::from_secs(15),
+                Duration::from_secs(25),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn polling_stops_when_codes_expire() {
+        let err = poll_until_ready(Duration::from_secs(5), Duration::from_secs(12), || async {
+            Ok(PollOutcome::Pending)
+        })
+        .await
+        .err()
+        .expect("polling should give up once the codes expire");
+
+        assert!(
+            matches!(err, TokenError::DeviceAuthorization(ref msg) if msg.contains("expired")),
+            "expected DeviceAuthorization error, got: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn request_code_parses_response() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/device")
+                .form_urlencoded_tuple("client_id", "cli-client")
+                .form_urlencoded_tuple("scope", "openid offline_access");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(
+                    r#"{"device_code":"dev-123","user_code":"WDJB-MJHT","verification_uri":"https://example.com/device","expires_in":1800,"interval":7}"#,
+                );
+        });
+
+        let flow = DeviceFlow::new(test_config(&server)).unwrap();
+        let authorization = flow.request_code().await.unwrap();
+
+        assert_eq!(authorization.user_code, "WDJB-MJHT");
+        assert_eq!(authorization.verification_uri, "https://example.com/device");
+        assert_eq!(authorization.expires_in, Duration::from_mins(30));
+        assert_eq!(authorization.interval, Duration::from_secs(7));
+        assert!(!format!("{authorization:?}").contains("dev-123"));
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn poll_once_maps_error_codes() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/token")
+                .form_urlencoded_tuple("grant_type", DEVICE_CODE_GRANT_TYPE)
+                .form_urlencoded_tuple("device_code", "dev-123");
+            then.status(400)
+                .header("content-type", "application/json")
+                .body(r#"{"error":"expired_token"}"#);
+        });
+
+        let flow = DeviceFlow::new(test_config(&server)).unwrap();
+        let authorization = DeviceAuthorization {
+            device_code: SecretString::new("dev-123"),
+            user_code: "WDJB-MJHT".to_owned(),
+            verification_uri: "https://example.com/device".to_owned(),
+            verification_uri_complete: None,
+            expires_in: Duration::from_mins(30),
+            interval: Duration::from_millis(1),
+        };
+        let err = flow.poll_token(&authorization).await.unwrap_err();
+
+        assert!(
+            matches!(err, TokenError::DeviceAuthorization(ref msg) if msg.contains("expired")),
+            "expected DeviceAuthorization error, got: {err}"
+        );
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn poll_token_returns_refreshing_token() {
+        let server = MockServer::start();
+        let device = server.mock(|when, then| {
+            when.method(POST)
+                .path("/token")
+                .form_urlencoded_tuple("grant_type", DEVICE_CODE_GRANT_TYPE);
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(
+                    r#"{"access_token":"tok-device","expires_in":3600,"token_type":"Bearer","refresh_token":"rt-1"}"#,
+                );
+        });
+        let refresh = server.mock(|when, then| {
+            when.method(POST)
+                .path("/token")
+                .form_urlencoded_tuple("grant_type", "refresh_token")
+                .form_urlencoded_tuple("refresh_token", "rt-1")
+                .form_urlencoded_tuple("client_id", "cli-client");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"access_token":"tok-refreshed","expires_in":3600}"#);
+        });
+
+        let flow = DeviceFlow::new(test_config(&server)).unwrap();
+        let authorization = DeviceAuthorization {
+            device_code: SecretString::new("dev-123"),
+            user_code: "WDJB-MJHT".to_owned(),
+            verification_uri: "https://example.com/device".to_owned(),
+            verification_uri_complete: None,
+            expires_in: Duration::from_mins(30),
+            interval: Duration::from_millis(1),
+        };
+        let token = flow.poll_token(&authorization).await.unwrap();
+
+        assert_eq!(token.get().unwrap().expose(), "tok-device");
+        device.assert();
+        refresh.assert_calls(0);
+
+        token.invalidate().await;
+        assert_eq!(token.get().unwrap().expose(), "tok-refreshed");
+        refresh.assert();
+    }
+}
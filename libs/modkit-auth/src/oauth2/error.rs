@@ -31,6 +31,16 @@ pub enum TokenError {
     /// The token watcher is not ready or has been shut down.
     #[error("token unavailable: {0}")]
     Unavailable(String),
+
+    /// The device authorization grant failed: the codes expired, the user
+    /// denied access, or the server returned another `OAuth2` error.
+    #[error("device authorization failed: {0}")]
+    DeviceAuthorization(String),
+
+    /// The access token is not a JWT or its payload does not match the
+    /// requested claims type.
+    #[error("invalid token claims: {0}")]
+    InvalidClaims(String),
 }
 
 #[cfg(test)]
@@ -65,6 +75,12 @@ mod tests {
         assert_eq!(e.to_string(), "unsupported token type: mac");
     }
 
+    #[test]
+    fn invalid_claims_renders() {
+        let e = TokenError::InvalidClaims("not a JWT".into());
+        assert_eq!(e.to_string(), "invalid token claims: not a JWT");
+    }
+
     #[test]
     fn unavailable_renders() {
         let e = TokenError::Unavailable("watcher shut down".into());
@@ -1,39 +1,161 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 
 use http::header::{AUTHORIZATION, HeaderName};
 use http::{HeaderValue, Request, Response};
+use tokio::sync::OnceCell;
 use tower::{Layer, Service};
 
 use super::token::Token;
 use modkit_http::HttpError;
 
+/// Scopes a single outbound request needs its bearer token issued for.
+///
+/// Insert into the request extensions to have [`BearerAuthLayer`] attach a
+/// token for exactly these scopes instead of the configured ones. Scope order
+/// and duplicates do not matter.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ScopeHint(BTreeSet<String>);
+
+impl ScopeHint {
+    /// Hint for a token carrying `scopes`.
+    pub fn new<I, S>(scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self(scopes.into_iter().map(Into::into).collect())
+    }
+
+    /// The requested scopes, sorted.
+    pub fn scopes(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+/// Scope sets cached per layer unless configured otherwise.
+const DEFAULT_SCOPED_TOKEN_CAPACITY: usize = 32;
+
+/// Tokens fetched for [`ScopeHint`]s, shared by every clone of a layer.
+///
+/// Each scope set is fetched once; a failed fetch leaves the cell empty so
+/// the next request retries it. Every cached token keeps its own refresh
+/// watcher, so the cache holds at most `capacity` scope sets and evicts the
+/// least recently used one; dropping an entry drops its watcher. The whole
+/// cache is cleared when the base token is invalidated.
+#[derive(Debug)]
+struct ScopedTokenCache {
+    capacity: usize,
+    /// Base token generation the entries were derived from
+    generation: u64,
+    tick: u64,
+    entries: HashMap<ScopeHint, ScopedEntry>,
+    /// Entries by last use, oldest first
+    by_use: BTreeMap<u64, ScopeHint>,
+}
+
+#[derive(Debug)]
+struct ScopedEntry {
+    last_used: u64,
+    cell: Arc<OnceCell<Token>>,
+}
+
+impl ScopedTokenCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            generation: 0,
+            tick: 0,
+            entries: HashMap::new(),
+            by_use: BTreeMap::new(),
+        }
+    }
+
+    /// Cell for `hint`, created if absent, marked as most recently used.
+    fn cell(&mut self, hint: &ScopeHint, generation: u64) -> Arc<OnceCell<Token>> {
+        if generation != self.generation {
+            self.entries.clear();
+            self.by_use.clear();
+            self.generation = generation;
+        }
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some(entry) = self.entries.get_mut(hint) {
+            self.by_use.remove(&entry.last_used);
+            self.by_use.insert(tick, hint.clone());
+            entry.last_used = tick;
+            return Arc::clone(&entry.cell);
+        }
+
+        while self.entries.len() >= self.capacity.max(1) {
+            let Some((_, oldest)) = self.by_use.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        let cell = Arc::new(OnceCell::new());
+        self.by_use.insert(tick, hint.clone());
+        self.entries.insert(
+            hint.clone(),
+            ScopedEntry {
+                last_used: tick,
+                cell: Arc::clone(&cell),
+            },
+        );
+        cell
+    }
+}
+
+type ScopedTokens = Arc<Mutex<ScopedTokenCache>>;
+
 /// Tower layer that injects a bearer token into outbound HTTP requests.
 ///
 /// Wraps an [`Token`] handle and sets the `Authorization: Bearer <token>`
 /// header (or a custom header) on every request before forwarding it to the
 /// inner service.
+///
+/// A request carrying a [`ScopeHint`] extension gets a token for those scopes
+/// instead, derived from the wrapped one with [`Token::with_scopes`] and
+/// cached per scope set (at most 32 sets unless configured with
+/// [`with_scoped_token_capacity`](Self::with_scoped_token_capacity)).
+/// Invalidating the wrapped token drops every scoped one.
 #[derive(Clone, Debug)]
 pub struct BearerAuthLayer {
     token: Token,
     header_name: HeaderName,
+    scoped: ScopedTokens,
 }
 
 impl BearerAuthLayer {
     /// Create a layer that injects `Authorization: Bearer <token>`.
     #[must_use]
     pub fn new(token: Token) -> Self {
+        Self::with_header_name(token, AUTHORIZATION)
+    }
+
+    /// Create a layer that injects `<header_name>: Bearer <token>`.
+    #[must_use]
+    pub fn with_header_name(token: Token, header_name: HeaderName) -> Self {
         Self {
             token,
-            header_name: AUTHORIZATION,
+            header_name,
+            scoped: Arc::new(Mutex::new(ScopedTokenCache::new(
+                DEFAULT_SCOPED_TOKEN_CAPACITY,
+            ))),
         }
     }
 
-    /// Create a layer that injects `<header_name>: Bearer <token>`.
+    /// Cache tokens for at most `capacity` distinct [`ScopeHint`]s, evicting
+    /// the least recently used beyond that.
     #[must_use]
-    pub fn with_header_name(token: Token, header_name: HeaderName) -> Self {
-        Self { token, header_name }
+    pub fn with_scoped_token_capacity(self, capacity: usize) -> Self {
+        Self {
+            scoped: Arc::new(Mutex::new(ScopedTokenCache::new(capacity))),
+            ..self
+        }
     }
 }
 
@@ -45,6 +167,7 @@ impl<S> Layer<S> for BearerAuthLayer {
             inner,
             token: self.token.clone(),
             header_name: self.header_name.clone(),
+            scoped: Arc::clone(&self.scoped),
         }
     }
 }
@@ -58,6 +181,34 @@ pub struct BearerAuthService<S> {
     inner: S,
     token: Token,
     header_name: HeaderName,
+    scoped: ScopedTokens,
+}
+
+impl<S> BearerAuthService<S> {
+    /// Token for `hint`, fetched on first use.
+    async fn scoped_token(
+        token: &Token,
+        scoped: &ScopedTokens,
+        hint: ScopeHint,
+    ) -> Result<Token, HttpError> {
+        let cell = scoped
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .cell(&hint, token.generation());
+        cell.get_or_try_init(|| token.with_scopes(hint.scopes()))
+            .await
+            .cloned()
+            .map_err(|e| HttpError::Transport(Box::new(e)))
+    }
+}
+
+/// `Bearer <token>` header value, marked sensitive.
+fn bearer_header(token: &Token) -> Result<HeaderValue, HttpError> {
+    let secret = token.get().map_err(|e| HttpError::Transport(Box::new(e)))?;
+    let raw = zeroize::Zeroizing::new(format!("Bearer {}", secret.expose()));
+    let mut value = HeaderValue::from_str(&raw).map_err(HttpError::InvalidHeaderValue)?;
+    value.set_sensitive(true);
+    Ok(value)
 }
 
 impl<S, B, ResBody> Service<Request<B>> for BearerAuthService<S>
@@ -79,28 +230,29 @@ where
     }
 
     fn call(&mut self, mut req: Request<B>) -> Self::Future {
-        let mut bearer_value = match self.token.get() {
-            Ok(secret) => {
-                let raw = zeroize::Zeroizing::new(format!("Bearer {}", secret.expose()));
-                match HeaderValue::from_str(&raw) {
-                    Ok(v) => v,
-                    Err(e) => return Box::pin(async { Err(HttpError::InvalidHeaderValue(e)) }),
-                }
-            }
-            Err(e) => {
-                return Box::pin(async { Err(HttpError::Transport(Box::new(e))) });
-            }
-        };
-        bearer_value.set_sensitive(true);
-
-        req.headers_mut()
-            .insert(self.header_name.clone(), bearer_value);
-
         // Clone-swap pattern (Tower Service contract).
         let clone = self.inner.clone();
         let mut inner = std::mem::replace(&mut self.inner, clone);
+        let header_name = self.header_name.clone();
+
+        if let Some(hint) = req.extensions().get::<ScopeHint>().cloned() {
+            let token = self.token.clone();
+            let scoped = Arc::clone(&self.scoped);
+            return Box::pin(async move {
+                let token = Self::scoped_token(&token, &scoped, hint).await?;
+                req.headers_mut()
+                    .insert(header_name, bearer_header(&token)?);
+                inner.call(req).await
+            });
+        }
 
-        Box::pin(async move { inner.call(req).await })
+        match bearer_header(&self.token) {
+            Ok(value) => {
+                req.headers_mut().insert(header_name, value);
+                Box::pin(async move { inner.call(req).await })
+            }
+            Err(e) => Box::pin(async { Err(e) }),
+        }
     }
 }
 
@@ -295,6 +447,154 @@ mod tests {
         );
     }
 
+    // -- scope hints ----------------------------------------------------------
+
+    /// Mock service recording the bearer header of every request.
+    #[derive(Clone, Default)]
+    struct RecordHeaderService {
+        seen: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Service<Request<Full<Bytes>>> for RecordHeaderService {
+        type Response = Response<Full<Bytes>>;
+        type Error = HttpError;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: Request<Full<Bytes>>) -> Self::Future {
+            let header = req.headers()[AUTHORIZATION].to_str().unwrap().to_owned();
+            self.seen.lock().unwrap().push(header);
+            Box::pin(async {
+                Ok(Response::builder()
+                    .status(StatusCode::OK)
+                    .body(Full::new(Bytes::new()))
+                    .unwrap())
+            })
+        }
+    }
+
+    fn hinted_request(scopes: &[&str]) -> Request<Full<Bytes>> {
+        let mut req = Request::builder()
+            .uri("http://example.com/api")
+            .body(Full::new(Bytes::new()))
+            .unwrap();
+        req.extensions_mut()
+            .insert(ScopeHint::new(scopes.iter().copied()));
+        req
+    }
+
+    #[tokio::test]
+    async fn scope_hints_fetch_one_token_per_scope_set() {
+        let server = MockServer::start();
+        let default_mock = scoped_token_mock(&server, None, "tok-default");
+        let read_mock = scoped_token_mock(&server, Some("orders.read users.read"), "tok-read");
+        let write_mock = scoped_token_mock(&server, Some("orders.write"), "tok-write");
+
+        let token = Token::new(test_config(&server)).await.unwrap();
+        let inner = RecordHeaderService::default();
+        let mut svc = BearerAuthLayer::new(token).layer(inner.clone());
+
+        let requests = [
+            hinted_request(&["users.read", "orders.read"]),
+            hinted_request(&["orders.write"]),
+            hinted_request(&["orders.read", "users.read"]),
+        ];
+        for req in requests {
+            Service::call(&mut svc, req).await.unwrap();
+        }
+        let plain = Request::builder()
+            .uri("http://example.com/api")
+            .body(Full::new(Bytes::new()))
+            .unwrap();
+        Service::call(&mut svc, plain).await.unwrap();
+
+        assert_eq!(
+            *inner.seen.lock().unwrap(),
+            [
+                "Bearer tok-read",
+                "Bearer tok-write",
+                "Bearer tok-read",
+                "Bearer tok-default"
+            ]
+        );
+        assert_eq!(read_mock.calls(), 1, "third request reuses the first token");
+        assert_eq!(write_mock.calls(), 1);
+        assert_eq!(default_mock.calls(), 1);
+    }
+
+    fn scoped_token_mock<'a>(
+        server: &'a MockServer,
+        scope: Option<&'static str>,
+        token: &'static str,
+    ) -> httpmock::Mock<'a> {
+        server.mock(|when, then| {
+            let when = when.method(POST).path("/token");
+            match scope {
+                Some(scope) => when.form_urlencoded_tuple("scope", scope),
+                None => when.form_urlencoded_tuple_missing("scope"),
+            };
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(token_json(token, 3600));
+        })
+    }
+
+    #[tokio::test]
+    async fn scoped_tokens_beyond_capacity_evict_least_recently_used() {
+        let server = MockServer::start();
+        let _default_mock = scoped_token_mock(&server, None, "tok-default");
+        let read_mock = scoped_token_mock(&server, Some("orders.read"), "tok-read");
+        let write_mock = scoped_token_mock(&server, Some("orders.write"), "tok-write");
+
+        let token = Token::new(test_config(&server)).await.unwrap();
+        let inner = RecordHeaderService::default();
+        let mut svc = BearerAuthLayer::new(token)
+            .with_scoped_token_capacity(1)
+            .layer(inner.clone());
+
+        for scopes in [["orders.read"], ["orders.write"], ["orders.read"]] {
+            Service::call(&mut svc, hinted_request(&scopes))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            *inner.seen.lock().unwrap(),
+            ["Bearer tok-read", "Bearer tok-write", "Bearer tok-read"]
+        );
+        assert_eq!(read_mock.calls(), 2, "evicted by the write scope");
+        assert_eq!(write_mock.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn invalidating_base_token_drops_scoped_tokens() {
+        let server = MockServer::start();
+        let default_mock = scoped_token_mock(&server, None, "tok-default");
+        let read_mock = scoped_token_mock(&server, Some("orders.read"), "tok-read");
+
+        let token = Token::new(test_config(&server)).await.unwrap();
+        let inner = RecordHeaderService::default();
+        let mut svc = BearerAuthLayer::new(token.clone()).layer(inner.clone());
+
+        Service::call(&mut svc, hinted_request(&["orders.read"]))
+            .await
+            .unwrap();
+        token.invalidate().await;
+        Service::call(&mut svc, hinted_request(&["orders.read"]))
+            .await
+            .unwrap();
+
+        assert_eq!(default_mock.calls(), 2, "initial fetch and invalidation");
+        assert_eq!(
+            read_mock.calls(),
+            2,
+            "scoped token refetched after invalidation"
+        );
+    }
+
     // -- debug safety ---------------------------------------------------------
 
     #[tokio::test]
@@ -1,10 +1,11 @@
-//! Outbound `OAuth2` client credentials flow.
+//! Outbound `OAuth2` client credentials, token exchange and device flows.
 //!
 //! This module implements token acquisition, caching, and automatic injection
 //! for outbound HTTP requests to vendor services secured with `OAuth2`.
 
 pub mod builder_ext;
 pub mod config;
+pub mod device;
 pub(crate) mod discovery;
 pub mod error;
 pub mod fetch;
@@ -15,8 +16,9 @@ pub mod types;
 
 pub use builder_ext::HttpClientBuilderExt;
 pub use config::OAuthClientConfig;
+pub use device::{DeviceAuthorization, DeviceFlow, DeviceFlowConfig};
 pub use error::TokenError;
 pub use fetch::{FetchedToken, fetch_token};
-pub use layer::BearerAuthLayer;
+pub use layer::{BearerAuthLayer, ScopeHint};
 pub use token::Token;
-pub use types::{ClientAuthMethod, SecretString};
+pub use types::{ClientAuthMethod, SecretString, TokenExchangeRequest};
@@ -1,3 +1,4 @@
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use aliri_clock::DurationSecs;
@@ -11,11 +12,27 @@ use zeroize::Zeroizing;
 
 use super::config::OAuthClientConfig;
 use super::error::TokenError;
-use super::types::ClientAuthMethod;
+use super::types::{ClientAuthMethod, TokenExchangeRequest};
 use modkit_utils::SecretString;
 
-/// Token source that exchanges client credentials for an access token using
-/// `modkit-http::HttpClient`.
+/// `grant_type` value for RFC 8693 token exchange.
+pub const TOKEN_EXCHANGE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+
+/// Grant presented to the token endpoint on every (re-)fetch.
+#[derive(Clone)]
+pub enum Grant {
+    /// RFC 6749 §4.4 client credentials.
+    ClientCredentials,
+    /// RFC 8693 token exchange on behalf of a subject token.
+    TokenExchange(TokenExchangeRequest),
+    /// RFC 6749 §6 refresh of a token issued by another grant (e.g. the
+    /// device flow). A rotated refresh token replaces the stored one; `None`
+    /// means the server issued none, so the token cannot be renewed.
+    RefreshToken(Arc<Mutex<Option<SecretString>>>),
+}
+
+/// Token source that exchanges client credentials (or a subject token) for
+/// an access token using `modkit-http::HttpClient`.
 ///
 /// It implements [`aliri_tokens::AsyncTokenSource`] so that
 /// `aliri_tokens` can drive refresh scheduling, jitter, and backoff.
@@ -28,10 +45,14 @@ pub struct OAuthTokenSource {
     /// empty.
     scopes: Option<String>,
     auth_method: ClientAuthMethod,
+    grant: Grant,
     extra_headers: Vec<(String, String)>,
     default_ttl: Duration,
     refresh_offset: Duration,
     min_refresh_period: Duration,
+    /// Token already issued out of band, handed out by the first
+    /// `request_token` call instead of contacting the endpoint.
+    seed: Option<TokenWithLifetime>,
 }
 
 impl OAuthTokenSource {
@@ -44,6 +65,17 @@ impl OAuthTokenSource {
     /// Returns [`TokenError::Http`] if the underlying `HttpClient` fails to
     /// build.
     pub fn new(config: &OAuthClientConfig) -> Result<Self, TokenError> {
+        Self::with_grant(config, Grant::ClientCredentials)
+    }
+
+    /// Build a token source that presents `grant` instead of client
+    /// credentials. A token exchange with its own scopes overrides the
+    /// configured ones.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::new`].
+    pub fn with_grant(config: &OAuthClientConfig, grant: Grant) -> Result<Self, TokenError> {
         let token_endpoint = config
             .token_endpoint
             .clone()
@@ -60,10 +92,12 @@ impl OAuthTokenSource {
                 TokenError::Http(crate::http_error::format_http_error(&e, "OAuth2 token"))
             })?;
 
-        let scopes = if config.scopes.is_empty() {
-            None
-        } else {
-            Some(config.scopes.join(" "))
+        let scopes = match &grant {
+            Grant::TokenExchange(request) if !request.scopes.is_empty() => {
+                Some(request.scopes.join(" "))
+            }
+            _ if config.scopes.is_empty() => None,
+            _ => Some(config.scopes.join(" ")),
         };
 
         Ok(Self {
@@ -73,12 +107,43 @@ impl OAuthTokenSource {
             client_secret: config.client_secret.clone(),
             scopes,
             auth_method: config.auth_method,
+            grant,
             extra_headers: config.extra_headers.clone(),
             default_ttl: config.default_ttl,
             refresh_offset: config.refresh_offset,
             min_refresh_period: config.min_refresh_period,
+            seed: None,
         })
     }
+
+    /// Serve `access_token` from the first [`request_token`] call.
+    ///
+    /// [`request_token`]: AsyncTokenSource::request_token
+    pub(crate) fn with_seed(mut self, access_token: &str, expires_in: Option<u64>) -> Self {
+        self.seed = Some(self.token_with_lifetime(access_token, expires_in));
+        self
+    }
+
+    /// Wrap `access_token` with the refresh schedule derived from its lifetime.
+    fn token_with_lifetime(
+        &self,
+        access_token: &str,
+        expires_in: Option<u64>,
+    ) -> TokenWithLifetime {
+        let lifetime_secs = expires_in.unwrap_or(self.default_ttl.as_secs());
+
+        // Compute per-token refresh parameters so that the stale time
+        // never exceeds the expiry time, even for short-lived tokens.
+        let (freshness, min_stale) = refresh_params(
+            lifetime_secs,
+            &self.refresh_offset,
+            &self.min_refresh_period,
+        );
+        let lifetime_config = TokenLifetimeConfig::new(freshness, min_stale);
+
+        let access_token = AccessToken::new(access_token.to_owned());
+        lifetime_config.create_token(&access_token, None::<&IdToken>, DurationSecs(lifetime_secs))
+    }
 }
 
 #[async_trait]
@@ -86,20 +151,61 @@ impl AsyncTokenSource for OAuthTokenSource {
     type Error = TokenError;
 
     async fn request_token(&mut self) -> Result<TokenWithLifetime, Self::Error> {
+        if let Some(token) = self.seed.take() {
+            return Ok(token);
+        }
+
+        // Copy the refresh token out of its lock; `Zeroizing` scrubs the copy.
+        let refresh_token = match &self.grant {
+            Grant::RefreshToken(stored) => Some(Zeroizing::new(
+                stored
+                    .lock()
+                    .map_err(|_| TokenError::Unavailable("refresh token lock poisoned".into()))?
+                    .as_ref()
+                    .ok_or_else(|| TokenError::Unavailable("no refresh token was issued".into()))?
+                    .expose()
+                    .to_owned(),
+            )),
+            _ => None,
+        };
+
         // -- build form fields ---------------------------------------------------
-        let mut fields: Vec<(&str, &str)> = vec![("grant_type", "client_credentials")];
+        let mut fields: Vec<(&str, &str)> = match &self.grant {
+            Grant::ClientCredentials => vec![("grant_type", "client_credentials")],
+            Grant::TokenExchange(request) => {
+                let mut fields = vec![
+                    ("grant_type", TOKEN_EXCHANGE_GRANT_TYPE),
+                    ("subject_token", request.subject_token.expose()),
+                    ("subject_token_type", request.subject_token_type.as_str()),
+                ];
+                if let Some(ref audience) = request.audience {
+                    fields.push(("audience", audience));
+                }
+                fields
+            }
+            Grant::RefreshToken(_) => vec![
+                ("grant_type", "refresh_token"),
+                (
+                    "refresh_token",
+                    refresh_token.as_deref().map_or("", String::as_str),
+                ),
+            ],
+        };
 
         if let Some(ref scope) = self.scopes {
             fields.push(("scope", scope));
         }
 
-        // For Form auth, credentials go into the form body.
+        // For Form auth, credentials go into the form body; public clients
+        // (empty secret) send only their `client_id`.
         // Wrap the temporary copy in `Zeroizing` so it is scrubbed on drop.
         let secret_expose;
         if self.auth_method == ClientAuthMethod::Form {
             secret_expose = Zeroizing::new(self.client_secret.expose().to_owned());
             fields.push(("client_id", &self.client_id));
-            fields.push(("client_secret", &secret_expose));
+            if !secret_expose.is_empty() {
+                fields.push(("client_secret", &secret_expose));
+            }
         }
 
         // -- build request -------------------------------------------------------
@@ -153,26 +259,14 @@ impl AsyncTokenSource for OAuthTokenSource {
             return Err(TokenError::UnsupportedTokenType(tt.clone()));
         }
 
-        // -- compute lifetime ----------------------------------------------------
-        let lifetime_secs = token_resp.expires_in.unwrap_or(self.default_ttl.as_secs());
-
-        // Compute per-token refresh parameters so that the stale time
-        // never exceeds the expiry time, even for short-lived tokens.
-        let (freshness, min_stale) = refresh_params(
-            lifetime_secs,
-            &self.refresh_offset,
-            &self.min_refresh_period,
-        );
-        let lifetime_config = TokenLifetimeConfig::new(freshness, min_stale);
-
-        let access_token = AccessToken::new(token_resp.access_token);
-        let token = lifetime_config.create_token(
-            &access_token,
-            None::<&IdToken>,
-            DurationSecs(lifetime_secs),
-        );
+        if let Grant::RefreshToken(stored) = &self.grant
+            && let Some(rotated) = token_resp.refresh_token.as_deref()
+            && let Ok(mut stored) = stored.lock()
+        {
+            *stored = Some(SecretString::new(rotated));
+        }
 
-        Ok(token)
+        Ok(self.token_with_lifetime(&token_resp.access_token, token_resp.expires_in))
     }
 }
 
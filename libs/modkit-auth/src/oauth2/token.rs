@@ -1,5 +1,6 @@
 use std::fmt;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use aliri_clock::DurationSecs;
@@ -7,10 +8,13 @@ use aliri_tokens::backoff::ErrorBackoffConfig;
 use aliri_tokens::jitter::RandomEarlyJitter;
 use aliri_tokens::{TokenStatus, TokenWatcher};
 use arc_swap::ArcSwap;
+use serde::de::DeserializeOwned;
 
 use super::config::OAuthClientConfig;
 use super::error::TokenError;
-use super::source::OAuthTokenSource;
+use super::source::{Grant, OAuthTokenSource};
+use super::types::TokenExchangeRequest;
+use crate::providers::unverified_claims;
 use modkit_utils::SecretString;
 
 /// Internal state holding the live watcher.
@@ -27,6 +31,18 @@ struct WatcherConfig {
     min_refresh_period: Duration,
 }
 
+/// Resolved config and grant a [`Token`] re-creates its source from.
+struct TokenOrigin {
+    config: OAuthClientConfig,
+    grant: Grant,
+}
+
+impl TokenOrigin {
+    fn source(&self) -> Result<OAuthTokenSource, TokenError> {
+        OAuthTokenSource::with_grant(&self.config, self.grant.clone())
+    }
+}
+
 /// Handle for obtaining `OAuth2` bearer tokens.
 ///
 /// Internally drives an `aliri_tokens::TokenWatcher` for background refresh and
@@ -37,8 +53,11 @@ struct WatcherConfig {
 #[derive(Clone)]
 pub struct Token {
     inner: Arc<ArcSwap<TokenInner>>,
-    source_factory: Arc<dyn Fn() -> Result<OAuthTokenSource, TokenError> + Send + Sync>,
+    origin: Arc<TokenOrigin>,
     watcher_config: Arc<WatcherConfig>,
+    /// Bumped by every successful [`Token::invalidate`], so holders of
+    /// tokens derived from this one know to drop them.
+    generation: Arc<AtomicU64>,
 }
 
 impl fmt::Debug for Token {
@@ -57,7 +76,27 @@ impl Token {
     ///
     /// Returns [`TokenError::ConfigError`] if the config is invalid.
     /// Returns [`TokenError::Http`] if the initial token fetch fails.
-    pub async fn new(mut config: OAuthClientConfig) -> Result<Self, TokenError> {
+    pub async fn new(config: OAuthClientConfig) -> Result<Self, TokenError> {
+        Self::spawn(config, Grant::ClientCredentials).await
+    }
+
+    /// Create a token handle that obtains its token via RFC 8693 token
+    /// exchange, presenting `request.subject_token` on every refresh.
+    ///
+    /// The client authenticates with `config`'s credentials exactly as for
+    /// [`Token::new`]; `request` decides the downstream audience and scopes.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Token::new`].
+    pub async fn token_exchange(
+        config: OAuthClientConfig,
+        request: TokenExchangeRequest,
+    ) -> Result<Self, TokenError> {
+        Self::spawn(config, Grant::TokenExchange(request)).await
+    }
+
+    async fn spawn(mut config: OAuthClientConfig, grant: Grant) -> Result<Self, TokenError> {
         config.validate()?;
 
         // Resolve issuer_url → token_endpoint via OIDC discovery (one-time).
@@ -80,19 +119,71 @@ impl Token {
             min_refresh_period: config.min_refresh_period,
         });
 
-        let source = OAuthTokenSource::new(&config)?;
-        let watcher = spawn_watcher(source, &watcher_config).await?;
+        let origin = Arc::new(TokenOrigin { config, grant });
+        let watcher = spawn_watcher(origin.source()?, &watcher_config).await?;
 
-        let source_factory: Arc<dyn Fn() -> Result<OAuthTokenSource, TokenError> + Send + Sync> =
-            Arc::new(move || OAuthTokenSource::new(&config));
+        Ok(Self {
+            inner: Arc::new(ArcSwap::from_pointee(TokenInner { watcher })),
+            origin,
+            watcher_config,
+            generation: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// Create a token handle around `access_token`, already issued by an
+    /// interactive grant, that refreshes with `refresh_token`.
+    ///
+    /// `config` must carry a resolved `token_endpoint`; it is not validated,
+    /// so public clients may leave `client_secret` empty. Without a refresh
+    /// token the handle stops serving once `access_token` expires.
+    pub(crate) async fn issued(
+        config: OAuthClientConfig,
+        access_token: &str,
+        expires_in: Option<u64>,
+        refresh_token: Option<SecretString>,
+    ) -> Result<Self, TokenError> {
+        let watcher_config = Arc::new(WatcherConfig {
+            jitter_max: config.jitter_max,
+            min_refresh_period: config.min_refresh_period,
+        });
+
+        let grant = Grant::RefreshToken(Arc::new(Mutex::new(refresh_token)));
+        let origin = Arc::new(TokenOrigin { config, grant });
+        let source = origin.source()?.with_seed(access_token, expires_in);
+        let watcher = spawn_watcher(source, &watcher_config).await?;
 
         Ok(Self {
             inner: Arc::new(ArcSwap::from_pointee(TokenInner { watcher })),
-            source_factory,
+            origin,
             watcher_config,
+            generation: Arc::new(AtomicU64::new(0)),
         })
     }
 
+    /// Create a separate token handle requesting `scopes` instead of this
+    /// token's scopes.
+    ///
+    /// The new handle uses the same client credentials and grant (for a token
+    /// exchange, the same subject token) and refreshes on its own.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Token::new`].
+    pub async fn with_scopes<I, S>(&self, scopes: I) -> Result<Self, TokenError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let scopes: Vec<String> = scopes.into_iter().map(Into::into).collect();
+        let mut config = self.origin.config.clone();
+        let mut grant = self.origin.grant.clone();
+        match &mut grant {
+            Grant::ClientCredentials | Grant::RefreshToken(_) => config.scopes = scopes,
+            Grant::TokenExchange(request) => request.scopes = scopes,
+        }
+        Self::spawn(config, grant).await
+    }
+
     /// Get the current bearer token.
     ///
     /// This is a lock-free read from the `ArcSwap`-cached watcher — it never
@@ -118,6 +209,21 @@ impl Token {
         Ok(SecretString::new(raw))
     }
 
+    /// Decode the payload of the current access token into `T`.
+    ///
+    /// Only useful when the provider issues JWT access tokens. The signature
+    /// is **not** verified: use this to inspect what was granted (scopes,
+    /// `exp`), never to make trust decisions.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TokenError::Unavailable`] as for [`Token::get`].
+    /// Returns [`TokenError::InvalidClaims`] if the token is opaque (not a
+    /// JWT) or its payload does not deserialize into `T`.
+    pub fn decode_claims<T: DeserializeOwned>(&self) -> Result<T, TokenError> {
+        decode_jwt_payload(self.get()?.expose())
+    }
+
     /// Force-replace the internal watcher with a freshly-spawned one.
     ///
     /// Use this after receiving a 401 from a downstream service to immediately
@@ -126,7 +232,7 @@ impl Token {
     /// If recreating the source or the initial token fetch fails, a warning is
     /// logged and the existing watcher is left in place.
     pub async fn invalidate(&self) {
-        let source = match (self.source_factory)() {
+        let source = match self.origin.source() {
             Ok(s) => s,
             Err(e) => {
                 tracing::warn!("OAuth2 token invalidation: failed to create source: {e}");
@@ -143,9 +249,25 @@ impl Token {
         };
 
         self.inner.store(Arc::new(TokenInner { watcher }));
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// How many times this token has been invalidated.
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
     }
 }
 
+/// Parse the payload segment of a compact JWT without verifying it.
+///
+/// Error messages never include the token itself.
+fn decode_jwt_payload<T: DeserializeOwned>(raw: &str) -> Result<T, TokenError> {
+    let claims = unverified_claims(raw)
+        .map_err(|e| TokenError::InvalidClaims(format!("access token is not a JWT: {e}")))?;
+    serde_json::from_value(claims)
+        .map_err(|e| TokenError::InvalidClaims(format!("JWT payload does not match: {e}")))
+}
+
 /// Spawn a [`TokenWatcher`] from the given source and config.
 async fn spawn_watcher(
     source: OAuthTokenSource,
@@ -162,6 +284,7 @@ async fn spawn_watcher(
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
     use super::*;
+    use crate::test_support::unsigned_jwt;
     use httpmock::prelude::*;
     use url::Url;
 
@@ -230,6 +353,72 @@ mod tests {
         );
     }
 
+    // -- token_exchange -------------------------------------------------------
+
+    #[tokio::test]
+    async fn token_exchange_sends_rfc8693_params() {
+        use super::super::types::token_type;
+
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/token")
+                .form_urlencoded_tuple(
+                    "grant_type",
+                    "urn:ietf:params:oauth:grant-type:token-exchange",
+                )
+                .form_urlencoded_tuple("subject_token", "inbound-token")
+                .form_urlencoded_tuple("subject_token_type", token_type::ACCESS_TOKEN)
+                .form_urlencoded_tuple("audience", "orders-api")
+                .form_urlencoded_tuple("scope", "orders.read orders.write");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(
+                    r#"{"access_token":"tok-exchanged","issued_token_type":"urn:ietf:params:oauth:token-type:access_token","token_type":"Bearer","expires_in":600}"#,
+                );
+        });
+
+        let request =
+            TokenExchangeRequest::new(SecretString::new("inbound-token"), token_type::ACCESS_TOKEN)
+                .with_audience("orders-api")
+                .with_scopes(["orders.read", "orders.write"]);
+        let config = OAuthClientConfig {
+            scopes: vec!["ignored".to_owned()],
+            ..test_config(&server)
+        };
+
+        let token = Token::token_exchange(config, request).await.unwrap();
+
+        assert_eq!(token.get().unwrap().expose(), "tok-exchanged");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn token_exchange_omits_absent_audience() {
+        use super::super::types::token_type;
+
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/token")
+                .body_includes("subject_token_type=urn%3Aietf%3Aparams%3Aoauth%3Atoken-type%3Ajwt")
+                .body_excludes("audience");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(token_json("tok-no-aud", 600));
+        });
+
+        let request = TokenExchangeRequest::new(SecretString::new("jwt"), token_type::JWT);
+        let token = Token::token_exchange(test_config(&server), request)
+            .await
+            .unwrap();
+
+        assert_eq!(token.get().unwrap().expose(), "tok-no-aud");
+        mock.assert();
+    }
+
     // -- get ------------------------------------------------------------------
 
     #[tokio::test]
@@ -249,6 +438,80 @@ mod tests {
         assert_eq!(secret.expose(), "tok-get-test");
     }
 
+    // -- decode_claims --------------------------------------------------------
+
+    #[derive(Debug, serde::Deserialize, PartialEq)]
+    struct AccessClaims {
+        sub: String,
+        scope: String,
+        exp: i64,
+    }
+
+    #[tokio::test]
+    async fn decode_claims_parses_jwt_access_token() {
+        let server = MockServer::start();
+        let jwt = unsigned_jwt(&serde_json::json!({
+            "sub": "svc-client",
+            "scope": "read write",
+            "exp": 1_900_000_000,
+        }));
+
+        let _mock = server.mock(|when, then| {
+            when.method(POST).path("/token");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(token_json(&jwt, 3600));
+        });
+
+        let token = Token::new(test_config(&server)).await.unwrap();
+        let claims: AccessClaims = token.decode_claims().unwrap();
+
+        assert_eq!(
+            claims,
+            AccessClaims {
+                sub: "svc-client".into(),
+                scope: "read write".into(),
+                exp: 1_900_000_000,
+            }
+        );
+        let raw: serde_json::Value = token.decode_claims().unwrap();
+        assert_eq!(raw["scope"], "read write");
+    }
+
+    #[tokio::test]
+    async fn decode_claims_rejects_opaque_token() {
+        let server = MockServer::start();
+
+        let _mock = server.mock(|when, then| {
+            when.method(POST).path("/token");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(token_json("opaque-secret-tok", 3600));
+        });
+
+        let token = Token::new(test_config(&server)).await.unwrap();
+        let err = token.decode_claims::<AccessClaims>().unwrap_err();
+
+        assert!(
+            matches!(err, TokenError::InvalidClaims(ref msg) if msg.contains("not a JWT")),
+            "expected InvalidClaims, got: {err}"
+        );
+        assert!(!err.to_string().contains("opaque-secret-tok"));
+    }
+
+    #[test]
+    fn decode_jwt_payload_reports_type_mismatch() {
+        let jwt = unsigned_jwt(&serde_json::json!({ "sub": "svc", "scope": 7, "exp": 1 }));
+        let err = decode_jwt_payload::<AccessClaims>(&jwt).unwrap_err();
+        assert!(
+            matches!(err, TokenError::InvalidClaims(ref msg) if msg.contains("invalid type")),
+            "expected InvalidClaims, got: {err}"
+        );
+
+        let err = decode_jwt_payload::<AccessClaims>("a.!!!.c").unwrap_err();
+        assert!(matches!(err, TokenError::InvalidClaims(_)));
+    }
+
     // -- invalidate -----------------------------------------------------------
 
     #[tokio::test]
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use serde::Deserialize;
 
 pub use modkit_utils::SecretString;
@@ -14,9 +16,84 @@ pub enum ClientAuthMethod {
     Form,
 }
 
+/// Token type identifiers defined by RFC 8693 §3.
+pub mod token_type {
+    /// An `OAuth2` access token.
+    pub const ACCESS_TOKEN: &str = "urn:ietf:params:oauth:token-type:access_token";
+    /// An `OAuth2` refresh token.
+    pub const REFRESH_TOKEN: &str = "urn:ietf:params:oauth:token-type:refresh_token";
+    /// An `OpenID Connect` ID token.
+    pub const ID_TOKEN: &str = "urn:ietf:params:oauth:token-type:id_token";
+    /// A JWT of unspecified purpose.
+    pub const JWT: &str = "urn:ietf:params:oauth:token-type:jwt";
+}
+
+/// Subject of an RFC 8693 token exchange.
+///
+/// The client presents `subject_token` (typically the caller's inbound token)
+/// and receives a token for the downstream `audience`/`scopes`. When `scopes`
+/// is empty, the client's configured scopes are requested instead.
+///
+/// `Debug` is manually implemented to redact [`subject_token`](Self::subject_token).
+#[derive(Clone)]
+pub struct TokenExchangeRequest {
+    /// Token representing the party on whose behalf the request is made.
+    pub subject_token: SecretString,
+    /// Type of `subject_token`, one of the [`token_type`] identifiers.
+    pub subject_token_type: String,
+    /// Logical name of the target service (`audience` parameter).
+    pub audience: Option<String>,
+    /// Scopes to request for the issued token.
+    pub scopes: Vec<String>,
+}
+
+impl TokenExchangeRequest {
+    /// Exchange `subject_token` of the given type, without audience or scopes.
+    #[must_use]
+    pub fn new(subject_token: SecretString, subject_token_type: impl Into<String>) -> Self {
+        Self {
+            subject_token,
+            subject_token_type: subject_token_type.into(),
+            audience: None,
+            scopes: Vec::new(),
+        }
+    }
+
+    /// Request a token for `audience`.
+    #[must_use]
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = Some(audience.into());
+        self
+    }
+
+    /// Request `scopes` instead of the client's configured scopes.
+    #[must_use]
+    pub fn with_scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// `Debug` redacts the subject token to prevent accidental exposure in logs.
+impl fmt::Debug for TokenExchangeRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenExchangeRequest")
+            .field("subject_token", &"[REDACTED]")
+            .field("subject_token_type", &self.subject_token_type)
+            .field("audience", &self.audience)
+            .field("scopes", &self.scopes)
+            .finish()
+    }
+}
+
 /// Deserialized `OAuth2` token endpoint response.
 ///
-/// Only the fields required by the client credentials flow are included.
+/// Only the fields required by the client credentials, device and refresh
+/// flows are included.
 /// Unknown fields are silently ignored during deserialization.
 ///
 /// **Intentionally `Deserialize`-only** — `Serialize` is not derived to
@@ -32,6 +109,9 @@ pub(crate) struct TokenResponse {
     /// The type of the token issued (optional; must be "Bearer" if present).
     #[serde(default)]
     pub token_type: Option<String>,
+    /// Refresh token, issued by grants that involve the user (RFC 6749 §5.1).
+    #[serde(default)]
+    pub refresh_token: Option<String>,
 }
 
 #[cfg(test)]
@@ -44,6 +124,16 @@ mod tests {
         assert_eq!(ClientAuthMethod::default(), ClientAuthMethod::Basic);
     }
 
+    #[test]
+    fn token_exchange_request_debug_redacts_subject_token() {
+        let request =
+            TokenExchangeRequest::new(SecretString::new("subject-secret"), token_type::JWT)
+                .with_audience("orders-api");
+        let dbg = format!("{request:?}");
+        assert!(!dbg.contains("subject-secret"), "leaked: {dbg}");
+        assert!(dbg.contains("orders-api"));
+    }
+
     #[test]
     fn deserialize_full_response() {
         let json = r#"{"access_token":"tok","expires_in":3600,"token_type":"Bearer"}"#;
@@ -0,0 +1,139 @@
+use super::CachedJwks;
+use crate::{claims_error::ClaimsError, traits::KeyProvider, validation::check_algorithm};
+use async_trait::async_trait;
+use jsonwebtoken::{Algorithm, Header};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Key provider that rejects tokens signed with an algorithm outside an allow-list.
+///
+/// The `alg` is read from the unverified header and checked before the inner
+/// provider looks up any key, so `alg: none` and algorithm-confusion tokens
+/// (e.g. `HS256` against an RSA key set) fail with
+/// [`ClaimsError::DisallowedAlgorithm`] regardless of the key material. The
+/// header returned by the inner provider is checked again, so the guard fails
+/// closed even if the two disagree.
+///
+/// [`JwksKeyProvider`](super::JwksKeyProvider) and
+/// [`HmacKeyProvider`](super::HmacKeyProvider) already enforce their own
+/// allow-list; the guard is for other providers, or to narrow a shared one.
+///
+/// # Example
+/// ```ignore
+/// let provider = AlgorithmGuardKeyProvider::new(Arc::new(jwks), config.allowed_algorithms.clone());
+/// ```
+#[derive(Clone)]
+pub struct AlgorithmGuardKeyProvider {
+    inner: Arc<dyn KeyProvider>,
+    allowed: HashSet<Algorithm>,
+}
+
+impl AlgorithmGuardKeyProvider {
+    /// Guard `inner`, accepting only tokens signed with one of `allowed`
+    #[must_use]
+    pub fn new(inner: Arc<dyn KeyProvider>, allowed: HashSet<Algorithm>) -> Self {
+        Self { inner, allowed }
+    }
+}
+
+#[async_trait]
+impl KeyProvider for AlgorithmGuardKeyProvider {
+    fn name(&self) -> &'static str {
+        "algorithm_guard"
+    }
+
+    async fn validate_and_decode(&self, token: &str) -> Result<(Header, Value), ClaimsError> {
+        // Strip "Bearer " prefix if present
+        let token = token.trim_start_matches("Bearer ").trim();
+
+        if let Err(e) = check_algorithm(token, &self.allowed) {
+            tracing::debug!(
+                provider = self.inner.name(),
+                error = %e,
+                "Rejected token algorithm before signature validation"
+            );
+            return Err(e);
+        }
+
+        let (header, claims) = self.inner.validate_and_decode(token).await?;
+        if !self.allowed.contains(&header.alg) {
+            return Err(ClaimsError::DisallowedAlgorithm(format!(
+                "{:?}",
+                header.alg
+            )));
+        }
+        Ok((header, claims))
+    }
+
+    async fn refresh_keys(&self) -> Result<(), ClaimsError> {
+        self.inner.refresh_keys().await
+    }
+    fn cached_jwks(&self, jwks_uri: &str) -> Option<CachedJwks> {
+        self.inner.cached_jwks(jwks_uri)
+    }
+
+    fn adopt_keys(&self, previous: &dyn KeyProvider) {
+        self.inner.adopt_keys(previous);
+    }
+
+    fn uses_shared_secret(&self) -> bool {
+        self.inner.uses_shared_secret()
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::test_support::{CountingProvider, unsigned_jwt_with_alg};
+    use serde_json::json;
+
+    fn guarded(allowed: &[Algorithm]) -> (AlgorithmGuardKeyProvider, Arc<CountingProvider>) {
+        let inner = Arc::new(CountingProvider::default());
+        (
+            AlgorithmGuardKeyProvider::new(inner.clone(), allowed.iter().copied().collect()),
+            inner,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_none_and_unexpected_algorithms_never_reach_inner() {
+        let (provider, inner) = guarded(&[Algorithm::RS256]);
+
+        for alg in ["none", "HS256"] {
+            let err = provider
+                .validate_and_decode(&format!(
+                    "Bearer {}",
+                    unsigned_jwt_with_alg(alg, &json!({ "sub": "u1" }))
+                ))
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(&err, ClaimsError::DisallowedAlgorithm(a) if a == alg),
+                "unexpected error: {err:?}"
+            );
+        }
+        assert_eq!(inner.calls(), 0);
+
+        provider
+            .validate_and_decode(&unsigned_jwt_with_alg("RS256", &json!({ "sub": "u1" })))
+            .await
+            .unwrap();
+        assert_eq!(inner.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_header_returned_by_inner_is_checked_too() {
+        // The unverified header claims RS384, but the inner provider reports RS256
+        let (provider, inner) = guarded(&[Algorithm::RS384]);
+
+        let err = provider
+            .validate_and_decode(&unsigned_jwt_with_alg("RS384", &json!({ "sub": "u1" })))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ClaimsError::DisallowedAlgorithm(_)));
+        assert_eq!(inner.calls(), 1);
+    }
+}
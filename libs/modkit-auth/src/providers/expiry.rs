@@ -0,0 +1,154 @@
+use super::{CachedJwks, unverified_claims};
+use crate::{claims_error::ClaimsError, standard_claims::StandardClaim, traits::KeyProvider};
+use async_trait::async_trait;
+use jsonwebtoken::Header;
+use serde_json::Value;
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+/// Key provider that rejects expired tokens before any signature work.
+///
+/// The `exp` claim is read from the unverified payload; a token that is past
+/// `exp` plus the leeway is rejected with [`ClaimsError::Expired`] without
+/// consulting the inner provider, which saves the JWKS lookup and signature
+/// check under token-replay load. Every other token — including ones with a
+/// missing, malformed or undecodable `exp` — goes to the inner provider, so
+/// this only ever rejects early and never accepts.
+///
+/// Use the same leeway as [`ValidationConfig`](crate::ValidationConfig) so a
+/// token is never rejected here that validation would have accepted.
+///
+/// # Example
+/// ```ignore
+/// let provider = ExpiryGuardKeyProvider::new(Arc::new(issuers), config.leeway_seconds);
+/// ```
+#[derive(Clone)]
+pub struct ExpiryGuardKeyProvider {
+    inner: Arc<dyn KeyProvider>,
+    leeway_seconds: i64,
+}
+
+impl ExpiryGuardKeyProvider {
+    /// Guard `inner`, tolerating clock skew of `leeway_seconds` (negative values count as 0)
+    #[must_use]
+    pub fn new(inner: Arc<dyn KeyProvider>, leeway_seconds: i64) -> Self {
+        Self {
+            inner,
+            leeway_seconds: leeway_seconds.max(0),
+        }
+    }
+
+    /// Whether the unverified `exp` is clearly in the past
+    fn is_expired(&self, token: &str) -> bool {
+        let Ok(claims) = unverified_claims(token) else {
+            return false;
+        };
+        claims
+            .get(StandardClaim::EXP)
+            .and_then(Value::as_i64)
+            .is_some_and(|exp| {
+                OffsetDateTime::now_utc().unix_timestamp() > exp.saturating_add(self.leeway_seconds)
+            })
+    }
+}
+
+#[async_trait]
+impl KeyProvider for ExpiryGuardKeyProvider {
+    fn name(&self) -> &'static str {
+        "expiry_guard"
+    }
+
+    async fn validate_and_decode(&self, token: &str) -> Result<(Header, Value), ClaimsError> {
+        // Strip "Bearer " prefix if present
+        let token = token.trim_start_matches("Bearer ").trim();
+
+        if self.is_expired(token) {
+            tracing::debug!(
+                provider = self.inner.name(),
+                "Rejected expired token before signature validation"
+            );
+            return Err(ClaimsError::Expired);
+        }
+        self.inner.validate_and_decode(token).await
+    }
+
+    async fn refresh_keys(&self) -> Result<(), ClaimsError> {
+        self.inner.refresh_keys().await
+    }
+    fn cached_jwks(&self, jwks_uri: &str) -> Option<CachedJwks> {
+        self.inner.cached_jwks(jwks_uri)
+    }
+
+    fn adopt_keys(&self, previous: &dyn KeyProvider) {
+        self.inner.adopt_keys(previous);
+    }
+
+    fn uses_shared_secret(&self) -> bool {
+        self.inner.uses_shared_secret()
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::test_support::{CountingProvider, unsigned_jwt};
+    use serde_json::json;
+
+    fn guarded(leeway_seconds: i64) -> (ExpiryGuardKeyProvider, Arc<CountingProvider>) {
+        let inner = Arc::new(CountingProvider::default());
+        (
+            ExpiryGuardKeyProvider::new(inner.clone(), leeway_seconds),
+            inner,
+        )
+    }
+
+    fn now() -> i64 {
+        OffsetDateTime::now_utc().unix_timestamp()
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_is_rejected_without_consulting_inner() {
+        let (provider, inner) = guarded(60);
+        let token = unsigned_jwt(&json!({ "sub": "u1", "exp": now() - 3600 }));
+
+        let err = provider
+            .validate_and_decode(&format!("Bearer {token}"))
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(err, ClaimsError::Expired),
+            "unexpected error: {err:?}"
+        );
+        assert_eq!(inner.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_tokens_within_leeway_are_delegated() {
+        let (provider, inner) = guarded(60);
+
+        for exp in [now() + 3600, now() - 30] {
+            let token = unsigned_jwt(&json!({ "sub": "u1", "exp": exp }));
+            let (_, claims) = provider.validate_and_decode(&token).await.unwrap();
+            assert_eq!(claims["sub"], "u1");
+        }
+        assert_eq!(inner.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_missing_or_malformed_exp_is_left_to_inner() {
+        let (provider, inner) = guarded(0);
+
+        for claims in [json!({ "sub": "u1" }), json!({ "exp": "yesterday" })] {
+            provider
+                .validate_and_decode(&unsigned_jwt(&claims))
+                .await
+                .unwrap();
+        }
+        let err = provider.validate_and_decode("not-a-jwt").await.unwrap_err();
+
+        assert!(matches!(err, ClaimsError::DecodeFailed(_)));
+        assert_eq!(inner.calls(), 3);
+    }
+}
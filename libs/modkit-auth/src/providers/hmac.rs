@@ -0,0 +1,197 @@
+use super::{jwks::decode_header_with_handler, verify_and_decode};
+use crate::{
+    claims_error::ClaimsError,
+    traits::KeyProvider,
+    validation::{HMAC_ALGORITHMS, check_algorithm},
+};
+use async_trait::async_trait;
+use jsonwebtoken::{Algorithm, DecodingKey, Header};
+use modkit_utils::SecretString;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Key provider for tokens signed with a shared HMAC secret (`HS256`/`HS384`/`HS512`).
+///
+/// Meant for internal service-to-service tokens, where both sides hold the
+/// same secret instead of publishing keys via JWKS. The provider only ever
+/// accepts HMAC algorithms: a token declaring `RS256`, `none` or anything else
+/// fails with [`ClaimsError::DisallowedAlgorithm`] before the signature is
+/// checked, so it cannot be confused with an asymmetric key set. A plugin
+/// picks either this provider or a JWKS one, never both: see
+/// [`AuthConfig::key_provider`](crate::AuthConfig::key_provider), and
+/// [`IssuerKeyProvider`](super::IssuerKeyProvider) refuses to mix them.
+///
+/// Like the JWKS provider, non-string custom header fields do not fail the
+/// header decoding; they are dropped.
+///
+/// # Example
+/// ```ignore
+/// let provider = HmacKeyProvider::new(SecretString::new(shared_secret));
+/// let validation = ValidationConfig::hmac();
+/// ```
+#[derive(Clone)]
+pub struct HmacKeyProvider {
+    secret: SecretString,
+    allowed: HashSet<Algorithm>,
+}
+
+impl HmacKeyProvider {
+    /// Validate tokens signed with `secret` using any of [`HMAC_ALGORITHMS`]
+    #[must_use]
+    pub fn new(secret: SecretString) -> Self {
+        Self {
+            secret,
+            allowed: HMAC_ALGORITHMS.iter().copied().collect(),
+        }
+    }
+
+    /// Restrict the accepted algorithms, e.g. to `HS256` only
+    ///
+    /// Non-HMAC algorithms in `algorithms` are ignored.
+    #[must_use]
+    pub fn with_algorithms(mut self, algorithms: impl IntoIterator<Item = Algorithm>) -> Self {
+        self.allowed = algorithms
+            .into_iter()
+            .filter(|alg| HMAC_ALGORITHMS.contains(alg))
+            .collect();
+        self
+    }
+}
+
+#[async_trait]
+impl KeyProvider for HmacKeyProvider {
+    fn name(&self) -> &'static str {
+        "hmac"
+    }
+
+    async fn validate_and_decode(&self, token: &str) -> Result<(Header, Value), ClaimsError> {
+        // Strip "Bearer " prefix if present
+        let token = token.trim_start_matches("Bearer ").trim();
+
+        check_algorithm(token, &self.allowed)?;
+        let header = decode_header_with_handler(token, &|_, _| None)
+            .map_err(|e| ClaimsError::DecodeFailed(format!("Invalid JWT header: {e}")))?;
+
+        let key = DecodingKey::from_secret(self.secret.expose().as_bytes());
+        let claims = verify_and_decode(token, &key, header.alg, &self.allowed)?;
+
+        Ok((header, claims))
+    }
+
+    fn uses_shared_secret(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+    use jsonwebtoken::{EncodingKey, encode};
+    use serde_json::json;
+
+    const SECRET: &str = "service-to-service-shared-secret";
+
+    fn sign(alg: Algorithm, secret: &str, claims: &Value) -> String {
+        encode(
+            &Header::new(alg),
+            claims,
+            &EncodingKey::from_secret(secret.as_bytes()),
+        )
+        .unwrap()
+    }
+
+    fn provider() -> HmacKeyProvider {
+        HmacKeyProvider::new(SecretString::new(SECRET))
+    }
+
+    #[tokio::test]
+    async fn test_hs256_token_validates() {
+        let claims = json!({"sub": "svc-a", "aud": "svc-b"});
+        let token = sign(Algorithm::HS256, SECRET, &claims);
+
+        let (header, decoded) = provider()
+            .validate_and_decode(&format!("Bearer {token}"))
+            .await
+            .unwrap();
+
+        assert_eq!(header.alg, Algorithm::HS256);
+        assert_eq!(decoded, claims);
+    }
+
+    #[tokio::test]
+    async fn test_tampered_token_fails_with_invalid_signature() {
+        let token = sign(Algorithm::HS256, SECRET, &json!({"sub": "svc-a"}));
+        let mut parts: Vec<&str> = token.split('.').collect();
+        let tampered_payload = URL_SAFE_NO_PAD.encode(br#"{"sub":"evil"}"#);
+        parts[1] = &tampered_payload;
+
+        let err = provider()
+            .validate_and_decode(&parts.join("."))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClaimsError::InvalidSignature), "{err:?}");
+
+        let wrong_secret = sign(Algorithm::HS256, "other-secret", &json!({"sub": "svc-a"}));
+        let err = provider()
+            .validate_and_decode(&wrong_secret)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClaimsError::InvalidSignature), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn test_non_hmac_algorithms_are_rejected() {
+        let payload = URL_SAFE_NO_PAD.encode(br#"{"sub":"svc-a"}"#);
+        for alg in ["RS256", "none"] {
+            let header = URL_SAFE_NO_PAD.encode(format!(r#"{{"alg":"{alg}"}}"#));
+            let err = provider()
+                .validate_and_decode(&format!("{header}.{payload}.sig"))
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(&err, ClaimsError::DisallowedAlgorithm(a) if a == alg),
+                "unexpected error: {err:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_non_string_header_fields_are_tolerated() {
+        let claims = json!({"sub": "svc-a"});
+        let header = URL_SAFE_NO_PAD.encode(br#"{"alg":"HS256","typ":"JWT","eap":1}"#);
+        let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+        let signing_input = format!("{header}.{payload}");
+        let signature = jsonwebtoken::crypto::sign(
+            signing_input.as_bytes(),
+            &EncodingKey::from_secret(SECRET.as_bytes()),
+            Algorithm::HS256,
+        )
+        .unwrap();
+
+        let (header, decoded) = provider()
+            .validate_and_decode(&format!("{signing_input}.{signature}"))
+            .await
+            .unwrap();
+
+        assert_eq!(header.alg, Algorithm::HS256);
+        assert_eq!(decoded, claims);
+    }
+
+    #[tokio::test]
+    async fn test_with_algorithms_restricts_hmac_family() {
+        let provider = provider().with_algorithms([Algorithm::HS256, Algorithm::RS256]);
+        let claims = json!({"sub": "svc-a"});
+
+        provider
+            .validate_and_decode(&sign(Algorithm::HS256, SECRET, &claims))
+            .await
+            .unwrap();
+        let err = provider
+            .validate_and_decode(&sign(Algorithm::HS512, SECRET, &claims))
+            .await
+            .unwrap_err();
+        assert!(matches!(&err, ClaimsError::DisallowedAlgorithm(a) if a == "HS512"));
+    }
+}
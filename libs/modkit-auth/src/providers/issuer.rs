@@ -0,0 +1,235 @@
+use super::{CachedJwks, unverified_claims};
+use crate::{
+    claims_error::ClaimsError, config::KeyProviderConfigError, standard_claims::StandardClaim,
+    traits::KeyProvider,
+};
+use async_trait::async_trait;
+use jsonwebtoken::Header;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Key provider that routes each token to the provider registered for its issuer.
+///
+/// The `iss` claim is read from the unverified payload only to pick a provider;
+/// the chosen provider still verifies the signature. Tokens from an issuer with
+/// no registered provider are rejected with [`ClaimsError::NoMatchingProvider`]
+/// without consulting any provider.
+///
+/// All registered providers verify the same kind of key: mixing a shared-secret
+/// provider such as [`HmacKeyProvider`](super::HmacKeyProvider) with JWKS ones is
+/// rejected, since a forged `iss` would otherwise let a caller pick which kind
+/// of key checks its token.
+///
+/// # Example
+/// ```ignore
+/// let provider = IssuerKeyProvider::new()
+///     .with_provider("https://a.example.com", Arc::new(JwksKeyProvider::new(jwks_a)?))?
+///     .with_provider("https://b.example.com", Arc::new(JwksKeyProvider::new(jwks_b)?))?;
+/// ```
+#[derive(Clone, Default)]
+pub struct IssuerKeyProvider {
+    providers: HashMap<String, Arc<dyn KeyProvider>>,
+}
+
+impl IssuerKeyProvider {
+    /// Create a provider with no registered issuers
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route tokens issued by `issuer` to `provider`, replacing any previous one
+    ///
+    /// # Errors
+    /// [`KeyProviderConfigError::MixedKeyKinds`] if `provider` and an already
+    /// registered one disagree on [`KeyProvider::uses_shared_secret`].
+    pub fn with_provider(
+        mut self,
+        issuer: impl Into<String>,
+        provider: Arc<dyn KeyProvider>,
+    ) -> Result<Self, KeyProviderConfigError> {
+        let issuer = issuer.into();
+        let shared_secret = provider.uses_shared_secret();
+        if self
+            .providers
+            .iter()
+            .any(|(other, p)| *other != issuer && p.uses_shared_secret() != shared_secret)
+        {
+            return Err(KeyProviderConfigError::MixedKeyKinds(issuer));
+        }
+        self.providers.insert(issuer, provider);
+        Ok(self)
+    }
+
+    /// Registered issuers, in no particular order
+    pub fn issuers(&self) -> impl Iterator<Item = &str> {
+        self.providers.keys().map(String::as_str)
+    }
+
+    /// Provider registered for the token's issuer
+    fn provider_for(&self, token: &str) -> Result<&Arc<dyn KeyProvider>, ClaimsError> {
+        let issuer = unverified_issuer(token)?;
+        self.providers
+            .get(&issuer)
+            .ok_or(ClaimsError::NoMatchingProvider(issuer))
+    }
+}
+
+#[async_trait]
+impl KeyProvider for IssuerKeyProvider {
+    fn name(&self) -> &'static str {
+        "issuer"
+    }
+
+    async fn validate_and_decode(&self, token: &str) -> Result<(Header, Value), ClaimsError> {
+        // Strip "Bearer " prefix if present
+        let token = token.trim_start_matches("Bearer ").trim();
+
+        self.provider_for(token)?.validate_and_decode(token).await
+    }
+
+    async fn refresh_keys(&self) -> Result<(), ClaimsError> {
+        // Refresh every provider; one failing issuer must not starve the others
+        let mut first_error = None;
+        for (issuer, provider) in &self.providers {
+            if let Err(e) = provider.refresh_keys().await {
+                tracing::warn!(issuer = %issuer, error = %e, "Key refresh failed");
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    fn cached_jwks(&self, jwks_uri: &str) -> Option<CachedJwks> {
+        self.providers
+            .values()
+            .find_map(|provider| provider.cached_jwks(jwks_uri))
+    }
+
+    fn adopt_keys(&self, previous: &dyn KeyProvider) {
+        for provider in self.providers.values() {
+            provider.adopt_keys(previous);
+        }
+    }
+
+    fn uses_shared_secret(&self) -> bool {
+        self.providers.values().any(|p| p.uses_shared_secret())
+    }
+}
+
+/// Read the `iss` claim from a JWT payload without verifying the signature
+fn unverified_issuer(token: &str) -> Result<String, ClaimsError> {
+    let claims = unverified_claims(token)?;
+
+    match claims.get(StandardClaim::ISS) {
+        Some(Value::String(iss)) => Ok(iss.clone()),
+        Some(_) => Err(ClaimsError::InvalidClaimFormat {
+            field: StandardClaim::ISS.to_owned(),
+            reason: "must be a string".to_owned(),
+        }),
+        None => Err(ClaimsError::MissingClaim(StandardClaim::ISS.to_owned())),
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::providers::HmacKeyProvider;
+    use crate::test_support::{CountingProvider, unsigned_jwt};
+    use modkit_utils::SecretString;
+    use serde_json::json;
+
+    fn two_issuers() -> (
+        IssuerKeyProvider,
+        Arc<CountingProvider>,
+        Arc<CountingProvider>,
+    ) {
+        let a = Arc::new(CountingProvider::default());
+        let b = Arc::new(CountingProvider::default());
+        let provider = IssuerKeyProvider::new()
+            .with_provider("https://a.example.com", a.clone())
+            .unwrap()
+            .with_provider("https://b.example.com", b.clone())
+            .unwrap();
+        (provider, a, b)
+    }
+
+    #[tokio::test]
+    async fn test_token_only_consults_its_issuers_provider() {
+        let (provider, a, b) = two_issuers();
+        let token = unsigned_jwt(&json!({ "iss": "https://a.example.com", "sub": "u1" }));
+
+        let (_, claims) = provider
+            .validate_and_decode(&format!("Bearer {token}"))
+            .await
+            .unwrap();
+
+        assert_eq!(claims["sub"], "u1");
+        assert_eq!(a.calls(), 1);
+        assert_eq!(b.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_issuer_yields_no_matching_provider() {
+        let (provider, a, b) = two_issuers();
+        let token = unsigned_jwt(&json!({ "iss": "https://evil.example.com" }));
+
+        let err = provider.validate_and_decode(&token).await.unwrap_err();
+
+        assert!(
+            matches!(err, ClaimsError::NoMatchingProvider(ref iss) if iss == "https://evil.example.com"),
+            "unexpected error: {err:?}"
+        );
+        assert_eq!(a.calls(), 0);
+        assert_eq!(b.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_missing_or_malformed_issuer_is_rejected() {
+        let (provider, a, _) = two_issuers();
+
+        let err = provider
+            .validate_and_decode(&unsigned_jwt(&json!({ "sub": "u1" })))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClaimsError::MissingClaim(ref c) if c == "iss"));
+
+        let err = provider
+            .validate_and_decode(&unsigned_jwt(&json!({ "iss": 42 })))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClaimsError::InvalidClaimFormat { ref field, .. } if field == "iss"));
+
+        let err = provider.validate_and_decode("not-a-jwt").await.unwrap_err();
+        assert!(matches!(err, ClaimsError::DecodeFailed(_)));
+
+        assert_eq!(a.calls(), 0);
+    }
+
+    #[test]
+    fn test_shared_secret_and_public_key_providers_cannot_be_mixed() {
+        let hmac = Arc::new(HmacKeyProvider::new(SecretString::new("shared")));
+
+        let err = two_issuers()
+            .0
+            .with_provider("https://svc.internal", hmac.clone())
+            .err()
+            .expect("mixing must be rejected");
+        assert!(
+            matches!(err, KeyProviderConfigError::MixedKeyKinds(ref iss) if iss == "https://svc.internal"),
+            "unexpected error: {err:?}"
+        );
+
+        // Replacing the only provider of an issuer is not a mix
+        IssuerKeyProvider::new()
+            .with_provider(
+                "https://svc.internal",
+                Arc::new(CountingProvider::default()),
+            )
+            .unwrap()
+            .with_provider("https://svc.internal", hmac)
+            .unwrap();
+    }
+}
@@ -1,8 +1,14 @@
-use crate::{claims_error::ClaimsError, traits::KeyProvider};
+use super::verify_and_decode;
+use crate::{
+    claims_error::ClaimsError,
+    metrics::{AuthEvent, AuthMetricLabels, AuthMetrics},
+    traits::KeyProvider,
+    validation::{DEFAULT_ALLOWED_ALGORITHMS, check_algorithm},
+};
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
-use jsonwebtoken::{DecodingKey, Header, decode_header};
+use jsonwebtoken::{Algorithm, DecodingKey, Header, decode_header};
 use serde::Deserialize;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
@@ -33,6 +39,51 @@ struct JwksResponse {
 /// Handler for non-string custom JWT header fields; return `Some` to keep as string, or `None` to drop.
 type HeaderExtrasHandler = dyn Fn(&str, &Value) -> Option<String> + Send + Sync;
 
+/// Callback invoked when a refresh changes the active key set.
+type RotationHandler = dyn Fn(&KeyRotation) + Send + Sync;
+
+/// Change in the active JWKS key set observed by a refresh.
+///
+/// Both lists are sorted; at least one of them is non-empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyRotation {
+    /// Key IDs present after the refresh but not before.
+    pub added: Vec<String>,
+    /// Key IDs present before the refresh but not after.
+    pub removed: Vec<String>,
+}
+
+impl KeyRotation {
+    /// Diff two key sets; `None` if they hold the same key IDs.
+    fn between<V>(old: &HashMap<String, V>, new: &HashMap<String, V>) -> Option<Self> {
+        let mut added: Vec<String> = new
+            .keys()
+            .filter(|kid| !old.contains_key(*kid))
+            .cloned()
+            .collect();
+        let mut removed: Vec<String> = old
+            .keys()
+            .filter(|kid| !new.contains_key(*kid))
+            .cloned()
+            .collect();
+        if added.is_empty() && removed.is_empty() {
+            return None;
+        }
+        added.sort_unstable();
+        removed.sort_unstable();
+        Some(Self { added, removed })
+    }
+}
+
+/// Keys a [`JwksKeyProvider`] fetched, handed to the provider replacing it on reload.
+///
+/// See [`KeyProvider::adopt_keys`].
+#[derive(Clone)]
+pub struct CachedJwks {
+    keys: Arc<HashMap<String, DecodingKey>>,
+    fetched_at: Instant,
+}
+
 /// Standard JWT header field names from RFC 7515 (JWS), RFC 7516 (JWE),
 /// RFC 7518 (JWA), RFC 7797 (b64), and RFC 8555 (ACME).
 const STANDARD_HEADER_FIELDS: &[&str] = &[
@@ -71,6 +122,15 @@ pub struct JwksKeyProvider {
     /// Called for each non-standard field whose value is not a JSON string.
     /// Return `Some(s)` to keep, `None` to drop.
     header_extras_handler: Option<Arc<HeaderExtrasHandler>>,
+
+    /// Optional callback fired after a refresh that adds or removes key IDs.
+    rotation_handler: Option<Arc<RotationHandler>>,
+
+    /// Optional metrics sink for key cache hits and misses.
+    metrics: Option<Arc<dyn AuthMetrics>>,
+
+    /// Signing algorithms tokens may declare (default: [`DEFAULT_ALLOWED_ALGORITHMS`])
+    allowed_algorithms: HashSet<Algorithm>,
 }
 
 #[derive(Debug, Default)]
@@ -113,6 +173,9 @@ impl JwksKeyProvider {
             max_backoff: Duration::from_hours(1),     // 1 hour
             on_demand_refresh_cooldown: Duration::from_mins(1), // 1 minute
             header_extras_handler: None,
+            rotation_handler: None,
+            metrics: None,
+            allowed_algorithms: DEFAULT_ALLOWED_ALGORITHMS.iter().copied().collect(),
         })
     }
 
@@ -165,6 +228,41 @@ impl JwksKeyProvider {
         self
     }
 
+    /// Set a callback fired when a refresh changes the active key set.
+    ///
+    /// Receives the added and removed key IDs, e.g. to log rotations or
+    /// pre-warm dependent caches. The first successful fetch reports every
+    /// key as added. Runs inline on the refreshing task, so keep it cheap.
+    pub fn with_on_rotation(
+        mut self,
+        handler: impl Fn(&KeyRotation) + Send + Sync + 'static,
+    ) -> Self {
+        self.rotation_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Report key cache lookups to a metrics sink.
+    ///
+    /// Each token validation records [`AuthEvent::CacheHit`] or
+    /// [`AuthEvent::CacheMiss`], labelled with `cache = "jwks"` and the `kid`.
+    pub fn with_metrics(mut self, metrics: Arc<dyn AuthMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Restrict the signing algorithms tokens may declare, typically to
+    /// `ValidationConfig::allowed_algorithms`.
+    ///
+    /// Checked before any key lookup, so a disallowed token never triggers an
+    /// on-demand refresh. An empty set rejects every token.
+    pub fn with_allowed_algorithms(
+        mut self,
+        algorithms: impl IntoIterator<Item = Algorithm>,
+    ) -> Self {
+        self.allowed_algorithms = algorithms.into_iter().collect();
+        self
+    }
+
     /// Fetch JWKS from the endpoint
     async fn fetch_jwks(&self) -> Result<HashMap<String, DecodingKey>, ClaimsError> {
         // HttpClient is Clone + Send + Sync, no locking needed
@@ -227,7 +325,11 @@ impl JwksKeyProvider {
         match self.fetch_jwks().await {
             Ok(new_keys) => {
                 // Update keys atomically
-                self.keys.store(Arc::new(new_keys));
+                let rotation = {
+                    let new_keys = Arc::new(new_keys);
+                    let old_keys = self.keys.swap(Arc::clone(&new_keys));
+                    KeyRotation::between(&old_keys, &new_keys)
+                };
 
                 // Update refresh state
                 let mut state = self.refresh_state.write().await;
@@ -235,6 +337,23 @@ impl JwksKeyProvider {
                 state.consecutive_failures = 0;
                 state.last_error = None;
 
+                if let Some(rotation) = rotation {
+                    // Newly published kids are no longer unknown
+                    for kid in &rotation.added {
+                        state.failed_kids.remove(kid);
+                    }
+                    drop(state);
+
+                    tracing::info!(
+                        added = ?rotation.added,
+                        removed = ?rotation.removed,
+                        "JWKS key set changed"
+                    );
+                    if let Some(handler) = &self.rotation_handler {
+                        handler(&rotation);
+                    }
+                }
+
                 Ok(())
             }
             Err(e) => {
@@ -340,43 +459,15 @@ impl JwksKeyProvider {
         keys.get(kid).cloned()
     }
 
-    /// Validate JWT signature and decode claims without re-parsing the header.
-    ///
-    /// Uses `jsonwebtoken::crypto::verify` directly instead of `decode()`,
-    /// because `decode()` internally calls `decode_header()` which fails
-    /// on non-string custom header fields (e.g. `"eap": 1`).
-    fn validate_token(
-        token: &str,
-        key: &DecodingKey,
-        header: &Header,
-    ) -> Result<Value, ClaimsError> {
-        // Enforce exactly three dot-separated segments: header.payload.signature
-        let parts: Vec<&str> = token.splitn(4, '.').collect();
-        if parts.len() != 3 {
-            return Err(ClaimsError::DecodeFailed("Invalid JWT structure".into()));
-        }
-        let signing_input = &token[..parts[0].len() + 1 + parts[1].len()];
-        let payload_b64 = parts[1];
-        let signature = parts[2];
-
-        // Verify signature over header.payload (the original signing input)
-        let valid =
-            jsonwebtoken::crypto::verify(signature, signing_input.as_bytes(), key, header.alg)
-                .map_err(|e| {
-                    ClaimsError::DecodeFailed(format!("JWT signature verification failed: {e}"))
-                })?;
-        if !valid {
-            return Err(ClaimsError::InvalidSignature);
+    /// Record a key cache hit or miss, if a metrics sink is configured
+    fn record_cache_lookup(&self, event: AuthEvent, kid: &str) {
+        if let Some(metrics) = &self.metrics {
+            let labels = AuthMetricLabels::default()
+                .with_provider(self.name())
+                .with_cache("jwks")
+                .with_kid(kid);
+            metrics.record_event(event, &labels);
         }
-
-        // Decode payload
-        let payload_bytes = URL_SAFE_NO_PAD
-            .decode(payload_b64.trim_end_matches('='))
-            .map_err(|e| ClaimsError::DecodeFailed(format!("JWT payload decode failed: {e}")))?;
-        let claims: Value = serde_json::from_slice(&payload_bytes)
-            .map_err(|e| ClaimsError::DecodeFailed(format!("JWT claims parse failed: {e}")))?;
-
-        Ok(claims)
     }
 }
 
@@ -390,6 +481,8 @@ impl KeyProvider for JwksKeyProvider {
         // Strip "Bearer " prefix if present
         let token = token.trim_start_matches("Bearer ").trim();
 
+        check_algorithm(token, &self.allowed_algorithms)?;
+
         // Decode header to get kid and algorithm
         let header = match &self.header_extras_handler {
             Some(handler) => decode_header_with_handler(token, handler.as_ref()),
@@ -404,8 +497,11 @@ impl KeyProvider for JwksKeyProvider {
 
         // Try to get key from cache
         let key = if let Some(k) = self.get_key(kid) {
+            self.record_cache_lookup(AuthEvent::CacheHit, kid);
             k
         } else {
+            self.record_cache_lookup(AuthEvent::CacheMiss, kid);
+
             // Key not in cache, try on-demand refresh
             self.on_demand_refresh(kid).await?;
 
@@ -415,7 +511,7 @@ impl KeyProvider for JwksKeyProvider {
         };
 
         // Validate signature and decode claims
-        let claims = Self::validate_token(token, &key, &header)?;
+        let claims = verify_and_decode(token, &key, header.alg, &self.allowed_algorithms)?;
 
         Ok((header, claims))
     }
@@ -427,6 +523,44 @@ impl KeyProvider for JwksKeyProvider {
             Ok(())
         }
     }
+
+    /// Keys from the last refresh, if it succeeded and `jwks_uri` is ours
+    fn cached_jwks(&self, jwks_uri: &str) -> Option<CachedJwks> {
+        if jwks_uri != self.jwks_uri {
+            return None;
+        }
+        let state = self.refresh_state.try_read().ok()?;
+        if state.consecutive_failures > 0 {
+            return None;
+        }
+        let keys = self.keys.load_full();
+        (!keys.is_empty()).then_some(CachedJwks {
+            keys,
+            fetched_at: state.last_refresh?,
+        })
+    }
+
+    /// Seed an unused provider with the keys `previous` holds for the same URL.
+    ///
+    /// Keys older than this provider's refresh interval are left behind, and
+    /// the next periodic refresh is due when it would have been for `previous`.
+    fn adopt_keys(&self, previous: &dyn KeyProvider) {
+        let Some(cached) = previous.cached_jwks(&self.jwks_uri) else {
+            return;
+        };
+        if cached.fetched_at.elapsed() >= self.refresh_interval {
+            return;
+        }
+        let Ok(mut state) = self.refresh_state.try_write() else {
+            return;
+        };
+        if state.last_refresh.is_some() {
+            return;
+        }
+        self.keys.store(cached.keys);
+        state.last_refresh = Some(cached.fetched_at);
+        tracing::debug!(jwks_uri = %self.jwks_uri, "Reused JWKS keys across reload");
+    }
 }
 
 /// Background task to periodically refresh JWKS
@@ -472,13 +606,10 @@ pub async fn run_jwks_refresh_task(
     }
 }
 
-/// Decode a JWT header, routing non-string custom fields through `handler`.
-///
-/// Returns `Some(s)` to keep the field, `None` to drop it.
-fn decode_header_with_handler(
+/// Decode the raw JSON object of a JWT header, without interpreting any field.
+pub(crate) fn decode_header_json(
     token: &str,
-    handler: &dyn Fn(&str, &Value) -> Option<String>,
-) -> Result<Header, jsonwebtoken::errors::Error> {
+) -> Result<serde_json::Map<String, Value>, jsonwebtoken::errors::Error> {
     let header_b64 = token
         .split('.')
         .next()
@@ -488,7 +619,17 @@ fn decode_header_with_handler(
         .decode(header_b64.trim_end_matches('='))
         .map_err(jsonwebtoken::errors::ErrorKind::Base64)?;
 
-    let mut json: serde_json::Map<String, Value> = serde_json::from_slice(&header_bytes)?;
+    Ok(serde_json::from_slice(&header_bytes)?)
+}
+
+/// Decode a JWT header, routing non-string custom fields through `handler`.
+///
+/// Returns `Some(s)` to keep the field, `None` to drop it.
+pub(crate) fn decode_header_with_handler(
+    token: &str,
+    handler: &dyn Fn(&str, &Value) -> Option<String>,
+) -> Result<Header, jsonwebtoken::errors::Error> {
+    let mut json = decode_header_json(token)?;
 
     json.retain(|key, value| {
         if STANDARD_HEADER_FIELDS.contains(&key.as_str()) || value.is_string() {
@@ -534,6 +675,9 @@ mod tests {
             max_backoff: Duration::from_hours(1),
             on_demand_refresh_cooldown: Duration::from_mins(1),
             header_extras_handler: None,
+            rotation_handler: None,
+            metrics: None,
+            allowed_algorithms: DEFAULT_ALLOWED_ALGORITHMS.iter().copied().collect(),
         }
     }
 
@@ -865,6 +1009,120 @@ mod tests {
         assert!(state.last_error.is_none());
     }
 
+    #[tokio::test]
+    async fn test_reload_with_unchanged_jwks_uri_reuses_cached_keys() {
+        let server = MockServer::start();
+        let jwks_mock = server.mock(|when, then| {
+            when.method(GET).path("/jwks");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(valid_jwks_json());
+        });
+        let other_mock = server.mock(|when, then| {
+            when.method(GET).path("/other-jwks");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(valid_jwks_json());
+        });
+        let jwks_url = server.url("/jwks");
+
+        let reloadable =
+            crate::ReloadableKeyProvider::new(Arc::new(test_provider_with_http(&jwks_url)));
+        reloadable.refresh_keys().await.unwrap();
+        assert_eq!(jwks_mock.calls(), 1);
+
+        // Only an unrelated setting changes
+        let mut same_uri = test_provider_with_http(&jwks_url);
+        same_uri.on_demand_refresh_cooldown = Duration::from_secs(5);
+        let same_uri = Arc::new(same_uri);
+        reloadable.reload(same_uri.clone());
+
+        assert!(same_uri.key_exists("test-key-1"));
+        reloadable.refresh_keys().await.unwrap();
+        assert_eq!(jwks_mock.calls(), 1, "reload must not refetch the JWKS");
+
+        // A new URL starts from an empty cache
+        let moved = Arc::new(test_provider_with_http(&server.url("/other-jwks")));
+        reloadable.reload(moved.clone());
+        assert!(!moved.key_exists("test-key-1"));
+        reloadable.refresh_keys().await.unwrap();
+        assert_eq!(other_mock.calls(), 1);
+    }
+
+    fn jwks_json_with_kids(kids: &[&str]) -> String {
+        let template: Value = serde_json::from_str(valid_jwks_json()).unwrap();
+        let keys: Vec<Value> = kids
+            .iter()
+            .map(|kid| {
+                let mut key = template["keys"][0].clone();
+                key["kid"] = Value::from(*kid);
+                key
+            })
+            .collect();
+        serde_json::json!({ "keys": keys }).to_string()
+    }
+
+    #[tokio::test]
+    async fn test_refresh_reports_key_rotation_diff() {
+        let server = MockServer::start();
+        let mut mock = server.mock(|when, then| {
+            when.method(GET).path("/jwks");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(jwks_json_with_kids(&["k1", "k2"]));
+        });
+
+        let rotations = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = Arc::clone(&rotations);
+        let provider = test_provider_with_http(&server.url("/jwks"))
+            .with_on_rotation(move |r| seen.lock().unwrap().push(r.clone()));
+
+        provider.perform_refresh().await.unwrap();
+        // Unchanged key set: no notification
+        provider.perform_refresh().await.unwrap();
+
+        // Rotate: k1 retired, k3 published
+        mock.delete();
+        mock = server.mock(|when, then| {
+            when.method(GET).path("/jwks");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(jwks_json_with_kids(&["k2", "k3"]));
+        });
+        provider
+            .refresh_state
+            .write()
+            .await
+            .failed_kids
+            .insert("k3".to_owned());
+        provider.perform_refresh().await.unwrap();
+        mock.assert();
+
+        assert_eq!(
+            *rotations.lock().unwrap(),
+            vec![
+                KeyRotation {
+                    added: vec!["k1".to_owned(), "k2".to_owned()],
+                    removed: vec![],
+                },
+                KeyRotation {
+                    added: vec!["k3".to_owned()],
+                    removed: vec!["k1".to_owned()],
+                },
+            ]
+        );
+        assert!(provider.get_key("k3").is_some());
+        assert!(provider.get_key("k1").is_none());
+        assert!(
+            !provider
+                .refresh_state
+                .read()
+                .await
+                .failed_kids
+                .contains("k3")
+        );
+    }
+
     #[tokio::test]
     async fn test_validate_and_decode_with_missing_kid() {
         let server = MockServer::start();
@@ -898,6 +1156,60 @@ mod tests {
         }
     }
 
+    /// `(metric name, cache label, kid label)`
+    type RecordedEvent = (&'static str, Option<String>, Option<String>);
+
+    #[derive(Default)]
+    struct RecordingMetrics {
+        events: std::sync::Mutex<Vec<RecordedEvent>>,
+    }
+
+    impl AuthMetrics for RecordingMetrics {
+        fn record_event(&self, event: AuthEvent, labels: &AuthMetricLabels) {
+            self.events.lock().unwrap().push((
+                event.metric_name(),
+                labels.cache.clone(),
+                labels.kid.clone(),
+            ));
+        }
+
+        fn record_duration(&self, _duration_ms: u64, _labels: &AuthMetricLabels) {}
+    }
+
+    #[tokio::test]
+    async fn test_validate_and_decode_records_cache_miss_then_hit() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/jwks");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(valid_jwks_json());
+        });
+
+        let metrics = Arc::new(RecordingMetrics::default());
+        let provider = test_provider_with_http(&server.url("/jwks"))
+            .with_metrics(Arc::clone(&metrics) as Arc<dyn AuthMetrics>);
+
+        // Known kid, bogus signature: the lookup is what is under test
+        let header_b64 = URL_SAFE_NO_PAD.encode(br#"{"alg":"RS256","kid":"test-key-1"}"#);
+        let payload_b64 = URL_SAFE_NO_PAD.encode(b"{}");
+        let token = format!("{header_b64}.{payload_b64}.invalid");
+
+        assert!(provider.validate_and_decode(&token).await.is_err());
+        assert!(provider.validate_and_decode(&token).await.is_err());
+        mock.assert_calls(1);
+
+        let cache = Some("jwks".to_owned());
+        let kid = Some("test-key-1".to_owned());
+        assert_eq!(
+            *metrics.events.lock().unwrap(),
+            vec![
+                ("auth.cache.miss", cache.clone(), kid.clone()),
+                ("auth.cache.hit", cache, kid),
+            ]
+        );
+    }
+
     #[test]
     fn test_decode_header_with_handler_coerces_non_string_extras() {
         use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
@@ -1161,6 +1473,53 @@ F8gvjIeiwVfp4nDnO2JFexiy
         );
     }
 
+    #[tokio::test]
+    async fn test_validate_and_decode_rejects_disallowed_algorithm_before_key_lookup() {
+        let server = MockServer::start();
+        let jwks = server.mock(|when, then| {
+            when.method(GET).path("/jwks");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(signed_jwks_json());
+        });
+        let provider = test_provider_with_http(&server.url("/jwks"));
+
+        let header_b64 = URL_SAFE_NO_PAD.encode(br#"{"alg":"HS256","kid":"sign-key-1"}"#);
+        let token = format!("{header_b64}.e30.sig");
+
+        let err = provider.validate_and_decode(&token).await.unwrap_err();
+
+        assert!(
+            matches!(&err, ClaimsError::DisallowedAlgorithm(a) if a == "HS256"),
+            "Expected DisallowedAlgorithm, got: {err:?}"
+        );
+        jwks.assert_calls(0);
+    }
+
+    #[tokio::test]
+    async fn test_validate_and_decode_honours_allowed_algorithms() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/jwks");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(signed_jwks_json());
+        });
+        let token = build_signed_jwt("sign-key-1", &serde_json::json!({"sub": "user-1"}));
+
+        let restricted = test_provider_with_http(&server.url("/jwks"))
+            .with_allowed_algorithms([jsonwebtoken::Algorithm::PS256]);
+        let err = restricted.validate_and_decode(&token).await.unwrap_err();
+        assert!(
+            matches!(&err, ClaimsError::DisallowedAlgorithm(a) if a == "RS256"),
+            "Expected DisallowedAlgorithm, got: {err:?}"
+        );
+
+        let allowed = test_provider_with_http(&server.url("/jwks"))
+            .with_allowed_algorithms([jsonwebtoken::Algorithm::RS256]);
+        assert!(allowed.validate_and_decode(&token).await.is_ok());
+    }
+
     /// Build a JWT with a custom header JSON (for non-string extras), properly signed.
     fn build_signed_jwt_custom_header(header_json: &str, claims: &serde_json::Value) -> String {
         let encoding_key = jsonwebtoken::EncodingKey::from_rsa_pem(TEST_RSA_PRIVATE_PEM)
@@ -1,3 +1,85 @@
+pub mod algorithm;
+pub mod expiry;
+pub mod hmac;
+pub mod issuer;
 pub mod jwks;
+pub mod reloadable;
 
-pub use jwks::JwksKeyProvider;
+pub use algorithm::AlgorithmGuardKeyProvider;
+pub use expiry::ExpiryGuardKeyProvider;
+pub use hmac::HmacKeyProvider;
+pub use issuer::IssuerKeyProvider;
+pub use jwks::{CachedJwks, JwksKeyProvider, KeyRotation};
+pub use reloadable::ReloadableKeyProvider;
+
+use crate::claims_error::ClaimsError;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use jsonwebtoken::{Algorithm, DecodingKey};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Decode the payload of a compact JWT without verifying the signature.
+///
+/// Only for routing, fast rejection and inspecting tokens this process
+/// obtained itself; never trust the result. Error messages never include the
+/// token.
+///
+/// # Errors
+/// [`ClaimsError::DecodeFailed`] if `token` does not have exactly three
+/// segments or its payload is not base64url-encoded JSON.
+pub fn unverified_claims(token: &str) -> Result<Value, ClaimsError> {
+    let mut segments = token.split('.');
+    let payload_b64 = match (segments.next(), segments.next(), segments.next()) {
+        (Some(_), Some(payload), Some(_)) if segments.next().is_none() => payload,
+        _ => return Err(ClaimsError::DecodeFailed("Invalid JWT format".into())),
+    };
+    let payload_bytes = URL_SAFE_NO_PAD
+        .decode(payload_b64.trim_end_matches('='))
+        .map_err(|e| ClaimsError::DecodeFailed(format!("JWT payload decode failed: {e}")))?;
+    serde_json::from_slice(&payload_bytes)
+        .map_err(|e| ClaimsError::DecodeFailed(format!("JWT claims parse failed: {e}")))
+}
+
+/// Validate the JWT signature with `key` and decode the claims.
+///
+/// `alg` must be in `allowed`, so every provider enforces its allow-list at
+/// the point the signature is checked, whatever it did before.
+///
+/// Uses `jsonwebtoken::crypto::verify` directly instead of `decode()`,
+/// because `decode()` internally calls `decode_header()` which fails
+/// on non-string custom header fields (e.g. `"eap": 1`).
+fn verify_and_decode(
+    token: &str,
+    key: &DecodingKey,
+    alg: Algorithm,
+    allowed: &HashSet<Algorithm>,
+) -> Result<Value, ClaimsError> {
+    if !allowed.contains(&alg) {
+        return Err(ClaimsError::DisallowedAlgorithm(format!("{alg:?}")));
+    }
+
+    // Enforce exactly three dot-separated segments: header.payload.signature
+    let parts: Vec<&str> = token.splitn(4, '.').collect();
+    if parts.len() != 3 {
+        return Err(ClaimsError::DecodeFailed("Invalid JWT structure".into()));
+    }
+    let signing_input = &token[..parts[0].len() + 1 + parts[1].len()];
+    let payload_b64 = parts[1];
+    let signature = parts[2];
+
+    // Verify signature over header.payload (the original signing input)
+    let valid = jsonwebtoken::crypto::verify(signature, signing_input.as_bytes(), key, alg)
+        .map_err(|e| {
+            ClaimsError::DecodeFailed(format!("JWT signature verification failed: {e}"))
+        })?;
+    if !valid {
+        return Err(ClaimsError::InvalidSignature);
+    }
+
+    // Decode payload
+    let payload_bytes = URL_SAFE_NO_PAD
+        .decode(payload_b64.trim_end_matches('='))
+        .map_err(|e| ClaimsError::DecodeFailed(format!("JWT payload decode failed: {e}")))?;
+    serde_json::from_slice(&payload_bytes)
+        .map_err(|e| ClaimsError::DecodeFailed(format!("JWT claims parse failed: {e}")))
+}
@@ -0,0 +1,147 @@
+use super::CachedJwks;
+use crate::{claims_error::ClaimsError, traits::KeyProvider};
+use arc_swap::ArcSwap;
+use async_trait::async_trait;
+use jsonwebtoken::Header;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Key provider whose inner provider can be replaced while the server runs.
+///
+/// Use it to apply configuration changes — a new issuer in an
+/// [`IssuerKeyProvider`](super::IssuerKeyProvider), a rotated JWKS URL — without
+/// rebuilding the middleware that holds it. Every call works on a single
+/// snapshot of the inner provider, so a [`reload`](Self::reload) racing with
+/// in-flight validations never mixes the old and new configuration.
+///
+/// Clones share the same slot: reloading through one clone is seen by all.
+///
+/// # Example
+/// ```ignore
+/// let provider = ReloadableKeyProvider::new(Arc::new(issuers_v1));
+/// let validator = provider.clone(); // hand to the auth middleware
+///
+/// // later, after the config changed
+/// provider.reload(Arc::new(issuers_v2));
+/// ```
+#[derive(Clone)]
+pub struct ReloadableKeyProvider {
+    current: Arc<ArcSwap<Arc<dyn KeyProvider>>>,
+}
+
+impl ReloadableKeyProvider {
+    /// Wrap `provider` as the initial configuration
+    #[must_use]
+    pub fn new(provider: Arc<dyn KeyProvider>) -> Self {
+        Self {
+            current: Arc::new(ArcSwap::from_pointee(provider)),
+        }
+    }
+
+    /// Atomically replace the inner provider, returning the previous one.
+    ///
+    /// Validations already in flight finish against the previous provider.
+    /// JWKS providers whose URL did not change start with the previous
+    /// provider's still-fresh keys instead of refetching them.
+    pub fn reload(&self, provider: Arc<dyn KeyProvider>) -> Arc<dyn KeyProvider> {
+        provider.adopt_keys(self.current().as_ref());
+        let previous = self.current.swap(Arc::new(provider));
+        tracing::info!(previous = previous.name(), "Key provider reloaded");
+        Arc::clone(&previous)
+    }
+
+    /// Snapshot of the current inner provider
+    #[must_use]
+    pub fn current(&self) -> Arc<dyn KeyProvider> {
+        Arc::clone(&self.current.load())
+    }
+}
+
+#[async_trait]
+impl KeyProvider for ReloadableKeyProvider {
+    fn name(&self) -> &'static str {
+        "reloadable"
+    }
+
+    async fn validate_and_decode(&self, token: &str) -> Result<(Header, Value), ClaimsError> {
+        self.current().validate_and_decode(token).await
+    }
+
+    async fn refresh_keys(&self) -> Result<(), ClaimsError> {
+        self.current().refresh_keys().await
+    }
+
+    fn cached_jwks(&self, jwks_uri: &str) -> Option<CachedJwks> {
+        self.current().cached_jwks(jwks_uri)
+    }
+
+    fn adopt_keys(&self, previous: &dyn KeyProvider) {
+        self.current().adopt_keys(previous);
+    }
+
+    fn uses_shared_secret(&self) -> bool {
+        self.current().uses_shared_secret()
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::providers::IssuerKeyProvider;
+    use crate::test_support::{CountingProvider, unsigned_jwt};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_reload_adding_issuer_accepts_its_tokens() {
+        let issuers_v1 = IssuerKeyProvider::new()
+            .with_provider(
+                "https://a.example.com",
+                Arc::new(CountingProvider::default()),
+            )
+            .unwrap();
+        let provider = ReloadableKeyProvider::new(Arc::new(issuers_v1));
+        // What the long-running server holds; never rebuilt below
+        let server_side = provider.clone();
+
+        let token = unsigned_jwt(&json!({ "iss": "https://b.example.com", "sub": "u1" }));
+        let err = server_side.validate_and_decode(&token).await.unwrap_err();
+        assert!(matches!(err, ClaimsError::NoMatchingProvider(_)));
+
+        let issuers_v2 = IssuerKeyProvider::new()
+            .with_provider(
+                "https://a.example.com",
+                Arc::new(CountingProvider::default()),
+            )
+            .unwrap()
+            .with_provider(
+                "https://b.example.com",
+                Arc::new(CountingProvider::default()),
+            )
+            .unwrap();
+        let previous = provider.reload(Arc::new(issuers_v2));
+        assert_eq!(previous.name(), "issuer");
+
+        let (_, claims) = server_side.validate_and_decode(&token).await.unwrap();
+        assert_eq!(claims["sub"], "u1");
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_taken_before_reload_keeps_old_config() {
+        let provider = ReloadableKeyProvider::new(Arc::new(
+            IssuerKeyProvider::new()
+                .with_provider(
+                    "https://a.example.com",
+                    Arc::new(CountingProvider::default()),
+                )
+                .unwrap(),
+        ));
+        let snapshot = provider.current();
+
+        provider.reload(Arc::new(IssuerKeyProvider::new()));
+
+        let token = unsigned_jwt(&json!({ "iss": "https://a.example.com" }));
+        assert!(snapshot.validate_and_decode(&token).await.is_ok());
+        assert!(provider.validate_and_decode(&token).await.is_err());
+    }
+}
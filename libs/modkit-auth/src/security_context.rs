@@ -0,0 +1,269 @@
+//! Mapping from validated [`Claims`] to a [`SecurityContext`].
+//!
+//! Handlers take an `Extension<SecurityContext>`; [`SecurityContextMapping`]
+//! describes which claims carry the tenant, subject type, scopes and roles so
+//! the gateway can build that context straight from a validated token.
+
+use modkit_security::SecurityContext;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::claims::Claims;
+use crate::claims_error::ClaimsError;
+use crate::config::AuthConfig;
+use crate::standard_claims::StandardClaim;
+use crate::validation::{RoleMapping, parse_uuid_array_from_value, parse_uuid_from_value};
+
+/// Which claims populate each [`SecurityContext`] field.
+///
+/// The subject is always `sub` and must be a UUID. The tenant claim may hold a
+/// single UUID or a non-empty array of UUIDs, in which case the first entry is
+/// the subject's home tenant.
+///
+/// # Example
+/// ```
+/// use modkit_auth::{Claims, SecurityContextMapping};
+/// use serde_json::json;
+///
+/// let claims = Claims::new(json!({
+///     "sub": "11111111-1111-1111-1111-111111111111",
+///     "tenant_id": "22222222-2222-2222-2222-222222222222",
+///     "scope": "users:read users:write",
+///     "roles": ["admin"]
+/// }));
+///
+/// let ctx = SecurityContextMapping::default()
+///     .security_context(&claims, None)
+///     .unwrap();
+/// assert!(ctx.require_tenant().is_ok());
+/// assert!(ctx.has_role("admin"));
+/// assert_eq!(ctx.token_scopes(), ["users:read", "users:write"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecurityContextMapping {
+    /// Claim holding the subject's tenant (default: `"tenant_id"`)
+    pub tenant_claim: String,
+
+    /// Claim holding the subject type, e.g. `"user"` (default: `"subject_type"`)
+    pub subject_type_claim: String,
+
+    /// Claim holding the token scopes, either a space-delimited string or an
+    /// array of strings (default: `"scope"`)
+    pub scope_claim: String,
+
+    /// Provider-specific role claims merged into the context roles
+    pub role_mapping: RoleMapping,
+}
+
+impl Default for SecurityContextMapping {
+    fn default() -> Self {
+        Self {
+            tenant_claim: "tenant_id".to_owned(),
+            subject_type_claim: "subject_type".to_owned(),
+            scope_claim: "scope".to_owned(),
+            role_mapping: RoleMapping::default(),
+        }
+    }
+}
+
+/// Default claim names with the role flattening configured in [`AuthConfig`].
+impl From<&AuthConfig> for SecurityContextMapping {
+    fn from(config: &AuthConfig) -> Self {
+        Self {
+            role_mapping: config.role_mapping.clone(),
+            ..Self::default()
+        }
+    }
+}
+
+impl SecurityContextMapping {
+    /// Build a [`SecurityContext`] from validated claims.
+    ///
+    /// `bearer_token` is kept on the context for PDP forwarding.
+    ///
+    /// # Errors
+    /// `MissingClaim` if `sub` or the tenant claim is absent, `InvalidClaimFormat`
+    /// if either is not a UUID or the subject type or scopes are malformed.
+    pub fn security_context(
+        &self,
+        claims: &Claims,
+        bearer_token: Option<&str>,
+    ) -> Result<SecurityContext, ClaimsError> {
+        let raw = claims.as_value();
+
+        let subject_id = parse_uuid_from_value(
+            raw.get(StandardClaim::SUB)
+                .ok_or_else(|| ClaimsError::MissingClaim(StandardClaim::SUB.to_owned()))?,
+            StandardClaim::SUB,
+        )?;
+        let tenant_id = self.tenant_id(raw)?;
+
+        let mut builder = SecurityContext::builder()
+            .subject_id(subject_id)
+            .subject_tenant_id(tenant_id)
+            .token_scopes(self.scopes(claims)?)
+            .roles(self.role_mapping.roles(raw).into_iter().collect());
+        if let Some(subject_type) = claims.custom::<String>(&self.subject_type_claim)? {
+            builder = builder.subject_type(&subject_type);
+        }
+        if let Some(token) = bearer_token {
+            builder = builder.bearer_token(token.to_owned());
+        }
+
+        builder
+            .build()
+            .map_err(|e| ClaimsError::Malformed(e.to_string()))
+    }
+
+    fn tenant_id(&self, raw: &Value) -> Result<Uuid, ClaimsError> {
+        let name = self.tenant_claim.as_str();
+        let value = raw
+            .get(name)
+            .ok_or_else(|| ClaimsError::MissingClaim(name.to_owned()))?;
+
+        if value.is_array() {
+            parse_uuid_array_from_value(value, name)?
+                .first()
+                .copied()
+                .ok_or_else(|| ClaimsError::InvalidClaimFormat {
+                    field: name.to_owned(),
+                    reason: "must not be empty".to_owned(),
+                })
+        } else {
+            parse_uuid_from_value(value, name)
+        }
+    }
+
+    fn scopes(&self, claims: &Claims) -> Result<Vec<String>, ClaimsError> {
+        match claims.as_value().get(&self.scope_claim) {
+            None => Ok(Vec::new()),
+            Some(Value::String(s)) => Ok(s.split_whitespace().map(str::to_owned).collect()),
+            Some(_) => Ok(claims
+                .custom::<Vec<String>>(&self.scope_claim)?
+                .unwrap_or_default()),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use modkit_security::TenantRequiredError;
+    use serde_json::json;
+
+    const SUBJECT: &str = "11111111-1111-1111-1111-111111111111";
+    const TENANT: &str = "22222222-2222-2222-2222-222222222222";
+
+    #[test]
+    fn test_token_with_tenant_and_roles() {
+        let claims = Claims::new(json!({
+            "sub": SUBJECT,
+            "tenant_id": TENANT,
+            "subject_type": "user",
+            "scope": "orders:read orders:write",
+            "roles": ["admin"],
+            "realm_access": { "roles": ["auditor"] }
+        }));
+
+        let ctx = SecurityContextMapping::default()
+            .security_context(&claims, Some("raw-token"))
+            .unwrap();
+
+        assert_eq!(ctx.subject_id(), Uuid::parse_str(SUBJECT).unwrap());
+        assert_eq!(ctx.require_tenant(), Ok(Uuid::parse_str(TENANT).unwrap()));
+        assert_eq!(ctx.subject_type(), Some("user"));
+        assert_eq!(ctx.token_scopes(), ["orders:read", "orders:write"]);
+        assert!(ctx.has_role("admin"));
+        assert!(ctx.has_role("auditor"));
+        assert!(!ctx.has_role("owner"));
+        assert!(ctx.bearer_token().is_some());
+    }
+
+    #[test]
+    fn test_custom_claim_names() {
+        let mapping = SecurityContextMapping {
+            tenant_claim: "tenants".to_owned(),
+            scope_claim: "scp".to_owned(),
+            role_mapping: RoleMapping {
+                realm_roles: false,
+                ..RoleMapping::default()
+            },
+            ..SecurityContextMapping::default()
+        };
+        let claims = Claims::new(json!({
+            "sub": SUBJECT,
+            "tenants": [TENANT, "33333333-3333-3333-3333-333333333333"],
+            "scp": ["orders:read"],
+            "realm_access": { "roles": ["auditor"] }
+        }));
+
+        let ctx = mapping.security_context(&claims, None).unwrap();
+
+        assert_eq!(ctx.subject_tenant_id(), Uuid::parse_str(TENANT).unwrap());
+        assert_eq!(ctx.token_scopes(), ["orders:read"]);
+        assert!(ctx.roles().is_empty());
+        assert_eq!(ctx.subject_type(), None);
+    }
+
+    #[test]
+    fn test_auth_config_role_mapping_reaches_context() {
+        let config: AuthConfig = serde_json::from_value(json!({
+            "role_mapping": { "realm_roles": false, "resource_clients": ["orders-api"] }
+        }))
+        .unwrap();
+        let claims = Claims::new(json!({
+            "sub": SUBJECT,
+            "tenant_id": TENANT,
+            "realm_access": { "roles": ["auditor"] },
+            "resource_access": {
+                "orders-api": { "roles": ["orders-admin"] },
+                "billing-api": { "roles": ["billing-admin"] }
+            }
+        }));
+
+        let ctx = SecurityContextMapping::from(&config)
+            .security_context(&claims, None)
+            .unwrap();
+
+        assert!(ctx.has_role("orders-admin"));
+        assert!(!ctx.has_role("billing-admin"));
+        assert!(!ctx.has_role("auditor"));
+    }
+
+    #[test]
+    fn test_nil_tenant_fails_require_tenant() {
+        let claims = Claims::new(json!({ "sub": SUBJECT, "tenant_id": Uuid::nil() }));
+
+        let ctx = SecurityContextMapping::default()
+            .security_context(&claims, None)
+            .unwrap();
+
+        assert_eq!(ctx.require_tenant(), Err(TenantRequiredError::Nil));
+    }
+
+    #[test]
+    fn test_missing_or_invalid_identity_claims() {
+        let mapping = SecurityContextMapping::default();
+
+        let missing_tenant = Claims::new(json!({ "sub": SUBJECT }));
+        assert!(matches!(
+            mapping.security_context(&missing_tenant, None),
+            Err(ClaimsError::MissingClaim(ref c)) if c == "tenant_id"
+        ));
+
+        let bad_subject = Claims::new(json!({ "sub": "user-123", "tenant_id": TENANT }));
+        assert!(matches!(
+            mapping.security_context(&bad_subject, None),
+            Err(ClaimsError::InvalidClaimFormat { ref field, .. }) if field == "sub"
+        ));
+
+        let empty_tenants = Claims::new(json!({ "sub": SUBJECT, "tenant_id": [] }));
+        assert!(matches!(
+            mapping.security_context(&empty_tenants, None),
+            Err(ClaimsError::InvalidClaimFormat { ref field, .. }) if field == "tenant_id"
+        ));
+    }
+}
@@ -105,6 +105,24 @@ impl StandardClaim {
     /// See: <https://openid.net/specs/openid-connect-core-1_0.html#IDToken>
     pub const AZP: &'static str = "azp";
 
+    // =========================================================================
+    // Role Claims (not part of `all_registered`)
+    // =========================================================================
+
+    /// Roles claim - the canonical, flat list of roles granted to the subject.
+    ///
+    /// Providers that nest roles elsewhere (e.g. Keycloak) are flattened into
+    /// this claim by [`RoleMapping`](crate::RoleMapping).
+    ///
+    /// See: <https://datatracker.ietf.org/doc/html/rfc9068#section-2.2.3.1>
+    pub const ROLES: &'static str = "roles";
+
+    /// Keycloak realm roles container (`realm_access.roles`).
+    pub const REALM_ACCESS: &'static str = "realm_access";
+
+    /// Keycloak per-client roles container (`resource_access.<client>.roles`).
+    pub const RESOURCE_ACCESS: &'static str = "resource_access";
+
     /// Returns a slice containing all standard JWT claim names (RFC 7519).
     ///
     /// This is useful for filtering out standard claims when collecting
@@ -145,6 +163,9 @@ mod tests {
         assert_eq!(StandardClaim::IAT, "iat");
         assert_eq!(StandardClaim::JTI, "jti");
         assert_eq!(StandardClaim::AZP, "azp");
+        assert_eq!(StandardClaim::ROLES, "roles");
+        assert_eq!(StandardClaim::REALM_ACCESS, "realm_access");
+        assert_eq!(StandardClaim::RESOURCE_ACCESS, "resource_access");
     }
 
     #[test]
@@ -0,0 +1,50 @@
+//! Fixtures shared by the unit tests of this crate.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use jsonwebtoken::{Algorithm, Header};
+use serde_json::Value;
+
+use crate::claims_error::ClaimsError;
+use crate::providers::unverified_claims;
+use crate::traits::KeyProvider;
+
+/// Compact JWT carrying `claims` under an RS256 header, with a dummy signature.
+pub fn unsigned_jwt(claims: &Value) -> String {
+    unsigned_jwt_with_alg("RS256", claims)
+}
+
+/// Like [`unsigned_jwt`], with `alg` in the header.
+pub fn unsigned_jwt_with_alg(alg: &str, claims: &Value) -> String {
+    let header = URL_SAFE_NO_PAD.encode(format!(r#"{{"alg":"{alg}","kid":"k1"}}"#));
+    let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+    format!("{header}.{payload}.sig")
+}
+
+/// Provider that skips signature checks, reports an RS256 header and counts
+/// how often it was consulted.
+#[derive(Default)]
+pub struct CountingProvider {
+    calls: AtomicUsize,
+}
+
+impl CountingProvider {
+    /// Number of tokens passed to this provider so far.
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl KeyProvider for CountingProvider {
+    fn name(&self) -> &'static str {
+        "counting"
+    }
+
+    async fn validate_and_decode(&self, token: &str) -> Result<(Header, Value), ClaimsError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok((Header::new(Algorithm::RS256), unverified_claims(token)?))
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{claims_error::ClaimsError, errors::AuthError};
+use crate::{claims_error::ClaimsError, errors::AuthError, providers::CachedJwks};
 use async_trait::async_trait;
 use jsonwebtoken::Header;
 use serde_json::Value;
@@ -32,4 +32,22 @@ pub trait KeyProvider: Send + Sync {
     async fn refresh_keys(&self) -> Result<(), ClaimsError> {
         Ok(())
     }
+
+    /// Optional: keys this provider (or one it wraps) cached from the JWKS
+    /// endpoint `jwks_uri`
+    fn cached_jwks(&self, _jwks_uri: &str) -> Option<CachedJwks> {
+        None
+    }
+
+    /// Optional: take over still-valid cached keys from `previous`, the
+    /// provider this one replaces on reload
+    fn adopt_keys(&self, _previous: &dyn KeyProvider) {}
+
+    /// Whether this provider verifies signatures with a shared secret instead
+    /// of published public keys (default: `false`)
+    ///
+    /// Wrapping providers report what they wrap.
+    fn uses_shared_secret(&self) -> bool {
+        false
+    }
 }
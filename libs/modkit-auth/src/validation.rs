@@ -1,8 +1,49 @@
 use crate::claims_error::ClaimsError;
+use crate::providers::jwks::decode_header_json;
 use crate::standard_claims::StandardClaim;
+use jsonwebtoken::Algorithm;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::str::FromStr;
+use std::time::Duration;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+/// How the token `aud` is matched against `allowed_audiences`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudienceMatch {
+    /// The token must list at least one allowed audience.
+    #[default]
+    Any,
+    /// The token must list every allowed audience.
+    All,
+}
+
+impl AudienceMatch {
+    fn matches(self, token_audiences: &[String], allowed: &[String]) -> bool {
+        let listed = |a: &String| token_audiences.contains(a);
+        match self {
+            Self::Any => allowed.iter().any(listed),
+            Self::All => allowed.iter().all(listed),
+        }
+    }
+}
+
+/// Signing algorithms accepted by default: the RSA family that JWKS keys are loaded for.
+pub const DEFAULT_ALLOWED_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+];
+
+/// Signing algorithms for tokens signed with a shared secret, see
+/// [`HmacKeyProvider`](crate::providers::HmacKeyProvider).
+pub const HMAC_ALGORITHMS: &[Algorithm] = &[Algorithm::HS256, Algorithm::HS384, Algorithm::HS512];
+
 /// Configuration for common validation
 #[derive(Debug, Clone)]
 pub struct ValidationConfig {
@@ -12,12 +53,32 @@ pub struct ValidationConfig {
     /// Allowed audiences (if empty, any audience is accepted)
     pub allowed_audiences: Vec<String>,
 
+    /// Whether the token must carry any or all of `allowed_audiences` (default: any)
+    pub audience_match: AudienceMatch,
+
     /// Leeway in seconds for time-based validations (exp, nbf)
     pub leeway_seconds: i64,
 
     /// Whether the `exp` claim is required (default: `true`).
     /// Set to `false` to allow tokens without an expiration claim.
     pub require_exp: bool,
+
+    /// Reject tokens issued (`iat`) longer ago than this, whatever their `exp`
+    /// (default: no limit)
+    pub max_token_age: Option<Duration>,
+
+    /// Whether the `iat` claim is required (default: `false`). Without it, a
+    /// token lacking `iat` skips the `max_token_age` check.
+    pub require_iat: bool,
+
+    /// Claims that must be present and non-empty; dotted paths (`org.id`)
+    /// address nested claims
+    pub required_claims: Vec<String>,
+
+    /// Signing algorithms a token header may declare (default:
+    /// [`DEFAULT_ALLOWED_ALGORITHMS`]). `none` is never accepted, and an empty
+    /// set rejects every token.
+    pub allowed_algorithms: HashSet<Algorithm>,
 }
 
 impl Default for ValidationConfig {
@@ -25,20 +86,81 @@ impl Default for ValidationConfig {
         Self {
             allowed_issuers: vec![],
             allowed_audiences: vec![],
+            audience_match: AudienceMatch::Any,
             leeway_seconds: 60,
             require_exp: true,
+            max_token_age: None,
+            require_iat: false,
+            required_claims: vec![],
+            allowed_algorithms: DEFAULT_ALLOWED_ALGORITHMS.iter().copied().collect(),
         }
     }
 }
 
+impl ValidationConfig {
+    /// Defaults for a shared-secret plugin: only [`HMAC_ALGORITHMS`] are allowed.
+    ///
+    /// A plugin validates either HMAC or asymmetric tokens, never both, so a
+    /// public key can never be used as an HMAC secret.
+    #[must_use]
+    pub fn hmac() -> Self {
+        Self {
+            allowed_algorithms: HMAC_ALGORITHMS.iter().copied().collect(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Check the `alg` of a token header against `config.allowed_algorithms`.
+///
+/// Runs on the unverified header, before any key is looked up, so a token
+/// cannot pick an algorithm the key material was not meant for (e.g. `HS256`
+/// with an RSA public key as the secret). `alg: none` and algorithms unknown
+/// to `jsonwebtoken` are rejected as well. [`JwksKeyProvider`] and
+/// [`HmacKeyProvider`] apply the same check with their own allow-list.
+///
+/// [`JwksKeyProvider`]: crate::JwksKeyProvider
+/// [`HmacKeyProvider`]: crate::HmacKeyProvider
+///
+/// # Errors
+/// Returns `ClaimsError::DisallowedAlgorithm` if the algorithm is not allowed,
+/// or `ClaimsError::DecodeFailed` if the header cannot be read.
+pub fn validate_algorithm(token: &str, config: &ValidationConfig) -> Result<(), ClaimsError> {
+    check_algorithm(token, &config.allowed_algorithms)
+}
+
+pub(crate) fn check_algorithm(
+    token: &str,
+    allowed: &HashSet<Algorithm>,
+) -> Result<(), ClaimsError> {
+    let token = token.trim_start_matches("Bearer ").trim();
+    let header = decode_header_json(token)
+        .map_err(|e| ClaimsError::DecodeFailed(format!("Invalid JWT header: {e}")))?;
+    let alg = header
+        .get("alg")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| ClaimsError::DecodeFailed("Missing alg in JWT header".into()))?;
+
+    match Algorithm::from_str(alg) {
+        Ok(algorithm) if allowed.contains(&algorithm) => Ok(()),
+        _ => Err(ClaimsError::DisallowedAlgorithm(alg.to_owned())),
+    }
+}
+
 /// Validate standard JWT claims in raw JSON against the given configuration.
 ///
 /// Checks performed:
 /// 1. **Issuer** (`iss`) — must match one of `config.allowed_issuers` (skipped if empty)
-/// 2. **Audience** (`aud`) — at least one must match `config.allowed_audiences` (skipped if empty)
+/// 2. **Audience** (`aud`) — must contain any (or, with [`AudienceMatch::All`], every)
+///    entry of `config.allowed_audiences` (skipped if empty)
 /// 3. **Expiration** (`exp`) — required by default; must not be in the past (with leeway).
 ///    Set `require_exp = false` to accept tokens without an `exp` claim.
 /// 4. **Not Before** (`nbf`) — must not be in the future (with leeway)
+/// 5. **Issued At** (`iat`) — required only with `require_iat`; the token must be
+///    no older than `config.max_token_age` (with leeway). Not read at all unless
+///    one of the two is set.
+/// 6. **Required claims** — every entry of `config.required_claims` must resolve to a
+///    value other than `null`, `""`, `[]` or `{}`
 ///
 /// # Errors
 /// Returns `ClaimsError` if any validation check fails.
@@ -74,14 +196,14 @@ pub fn validate_claims(
         }
     }
 
-    // 2. Validate audience (at least one must match)
+    // 2. Validate audience (any or all of the allowed ones must be present)
     if !config.allowed_audiences.is_empty() {
         if let Some(aud_value) = raw.get(StandardClaim::AUD) {
             let audiences = extract_audiences(aud_value)?;
-            let has_match = audiences
-                .iter()
-                .any(|a| config.allowed_audiences.contains(a));
-            if !has_match {
+            if !config
+                .audience_match
+                .matches(&audiences, &config.allowed_audiences)
+            {
                 return Err(ClaimsError::InvalidAudience {
                     expected: config.allowed_audiences.clone(),
                     actual: audiences,
@@ -125,9 +247,144 @@ pub fn validate_claims(
         }
     }
 
+    // 5. Validate token age with leeway; `iat` is only read when configured
+    if config.max_token_age.is_some() || config.require_iat {
+        match token_age(raw)? {
+            Some(age) => {
+                if let Some(max_age) = config.max_token_age
+                    && age > max_age.saturating_add(leeway.unsigned_abs())
+                {
+                    return Err(ClaimsError::TokenTooOld {
+                        age_seconds: age.as_secs(),
+                        max_age_seconds: max_age.as_secs(),
+                    });
+                }
+            }
+            None if config.require_iat => {
+                return Err(ClaimsError::MissingClaim(StandardClaim::IAT.to_owned()));
+            }
+            None => {}
+        }
+    }
+
+    // 6. Validate required claims
+    if let Some(missing) = config
+        .required_claims
+        .iter()
+        .find(|path| claim_at_path(raw, path).is_none_or(is_empty_claim))
+    {
+        return Err(ClaimsError::MissingClaim(missing.clone()));
+    }
+
     Ok(())
 }
 
+/// Resolve a dotted claim path (`org.id`) against the claims object.
+fn claim_at_path<'a>(raw: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.split('.')
+        .try_fold(raw, |value, segment| value.get(segment))
+}
+
+fn is_empty_claim(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => true,
+        serde_json::Value::String(s) => s.is_empty(),
+        serde_json::Value::Array(items) => items.is_empty(),
+        serde_json::Value::Object(map) => map.is_empty(),
+        serde_json::Value::Bool(_) | serde_json::Value::Number(_) => false,
+    }
+}
+
+/// Which provider-specific role claims are flattened into the canonical
+/// [`roles`](StandardClaim::ROLES) claim.
+///
+/// Keycloak nests roles under `realm_access.roles` and
+/// `resource_access.<client>.roles`. Roles already present in `roles` are kept.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoleMapping {
+    /// Include `realm_access.roles` (default: `true`)
+    pub realm_roles: bool,
+
+    /// Clients whose `resource_access.<client>.roles` are included; `"*"` includes
+    /// every client (default: none)
+    pub resource_clients: Vec<String>,
+
+    /// Prefix client roles with their client, as `<client>:<role>`, so they cannot
+    /// collide with realm roles (default: `false`)
+    pub prefix_client_roles: bool,
+}
+
+impl Default for RoleMapping {
+    fn default() -> Self {
+        Self {
+            realm_roles: true,
+            resource_clients: Vec::new(),
+            prefix_client_roles: false,
+        }
+    }
+}
+
+impl RoleMapping {
+    /// Collect the merged role set from a raw claims payload.
+    ///
+    /// Role containers that are absent or not shaped as expected are skipped, as
+    /// are non-string role entries.
+    #[must_use]
+    pub fn roles(&self, raw: &serde_json::Value) -> BTreeSet<String> {
+        let mut roles: BTreeSet<String> = string_items(raw.get(StandardClaim::ROLES))
+            .map(str::to_owned)
+            .collect();
+
+        if self.realm_roles {
+            let realm = raw.get(StandardClaim::REALM_ACCESS);
+            roles.extend(string_items(realm.and_then(|r| r.get("roles"))).map(str::to_owned));
+        }
+
+        if let Some(clients) = raw
+            .get(StandardClaim::RESOURCE_ACCESS)
+            .and_then(serde_json::Value::as_object)
+        {
+            let all = self.resource_clients.iter().any(|c| c == "*");
+            for (client, access) in clients {
+                if !all && !self.resource_clients.contains(client) {
+                    continue;
+                }
+                for role in string_items(access.get("roles")) {
+                    roles.insert(if self.prefix_client_roles {
+                        format!("{client}:{role}")
+                    } else {
+                        role.to_owned()
+                    });
+                }
+            }
+        }
+
+        roles
+    }
+
+    /// Replace the `roles` claim with the merged role set.
+    ///
+    /// Non-object payloads are left untouched.
+    pub fn apply(&self, raw: &mut serde_json::Value) {
+        let roles = self.roles(raw);
+        if let Some(obj) = raw.as_object_mut() {
+            obj.insert(
+                StandardClaim::ROLES.to_owned(),
+                roles.into_iter().map(serde_json::Value::String).collect(),
+            );
+        }
+    }
+}
+
+fn string_items(value: Option<&serde_json::Value>) -> impl Iterator<Item = &str> {
+    value
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(serde_json::Value::as_str)
+}
+
 /// Helper to parse a UUID from a JSON value.
 ///
 /// # Errors
@@ -190,6 +447,22 @@ pub fn parse_timestamp(
     })
 }
 
+/// Time elapsed since the token was issued, from its `iat` claim.
+///
+/// Returns `None` if the token has no `iat`; a token issued in the future has
+/// an age of zero.
+///
+/// # Errors
+/// Returns `ClaimsError::InvalidClaimFormat` if `iat` is not a valid unix timestamp.
+pub fn token_age(raw: &serde_json::Value) -> Result<Option<Duration>, ClaimsError> {
+    let Some(iat_value) = raw.get(StandardClaim::IAT) else {
+        return Ok(None);
+    };
+    let iat = parse_timestamp(iat_value, StandardClaim::IAT)?;
+    let age = OffsetDateTime::now_utc() - iat;
+    Ok(Some(Duration::try_from(age).unwrap_or(Duration::ZERO)))
+}
+
 /// Helper to extract string from JSON value.
 ///
 /// # Errors
@@ -237,6 +510,7 @@ pub fn extract_audiences(value: &serde_json::Value) -> Result<Vec<String>, Claim
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
     use super::*;
+    use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
     use serde_json::json;
 
     /// Unix timestamp for 9999-12-31T23:59:59Z — max representable date in `time` crate default range.
@@ -244,6 +518,63 @@ mod tests {
     /// Unix timestamp for -9999-01-01T00:00:00Z — min representable date in `time` crate default range.
     const MIN_UNIX_TIMESTAMP: i64 = -377_705_116_800;
 
+    fn jwt_with_header(header: &str) -> String {
+        let header = URL_SAFE_NO_PAD.encode(header);
+        let payload = URL_SAFE_NO_PAD.encode(r#"{"sub":"user-1"}"#);
+        format!("{header}.{payload}.sig")
+    }
+
+    #[test]
+    fn test_alg_none_is_rejected() {
+        let config = ValidationConfig::default();
+        for alg in ["none", "None", "NONE"] {
+            let token = jwt_with_header(&format!(r#"{{"alg":"{alg}","typ":"JWT"}}"#));
+            let err = validate_algorithm(&token, &config).unwrap_err();
+            assert!(
+                matches!(&err, ClaimsError::DisallowedAlgorithm(a) if a == alg),
+                "unexpected error: {err:?}"
+            );
+            assert_eq!(err.to_string(), format!("Disallowed JWT algorithm: {alg}"));
+        }
+    }
+
+    #[test]
+    fn test_unexpected_algorithm_is_rejected() {
+        let config = ValidationConfig {
+            allowed_algorithms: HashSet::from([Algorithm::RS256]),
+            ..Default::default()
+        };
+
+        let hs256 = jwt_with_header(r#"{"alg":"HS256","kid":"k1"}"#);
+        let err = validate_algorithm(&hs256, &config).unwrap_err();
+        assert!(
+            matches!(&err, ClaimsError::DisallowedAlgorithm(a) if a == "HS256"),
+            "unexpected error: {err:?}"
+        );
+
+        let rs256 = jwt_with_header(r#"{"alg":"RS256","kid":"k1"}"#);
+        assert!(validate_algorithm(&format!("Bearer {rs256}"), &config).is_ok());
+    }
+
+    #[test]
+    fn test_empty_allowed_algorithms_fails_closed() {
+        let config = ValidationConfig {
+            allowed_algorithms: HashSet::new(),
+            ..Default::default()
+        };
+        let token = jwt_with_header(r#"{"alg":"RS256"}"#);
+        assert!(matches!(
+            validate_algorithm(&token, &config),
+            Err(ClaimsError::DisallowedAlgorithm(_))
+        ));
+
+        let missing = jwt_with_header(r#"{"typ":"JWT"}"#);
+        assert!(matches!(
+            validate_algorithm(&missing, &ValidationConfig::default()),
+            Err(ClaimsError::DecodeFailed(_))
+        ));
+    }
+
     #[test]
     fn test_valid_claims_pass() {
         let now = time::OffsetDateTime::now_utc();
@@ -395,6 +726,96 @@ mod tests {
         assert!(validate_claims(&claims, &config).is_ok());
     }
 
+    fn max_age_config() -> ValidationConfig {
+        ValidationConfig {
+            max_token_age: Some(Duration::from_hours(12)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fresh_token_within_max_age_passes() {
+        let now = time::OffsetDateTime::now_utc();
+        let claims = json!({
+            "iat": (now - time::Duration::minutes(5)).unix_timestamp(),
+            "exp": (now + time::Duration::hours(1)).unix_timestamp(),
+        });
+        assert!(validate_claims(&claims, &max_age_config()).is_ok());
+        let age = token_age(&claims).unwrap().unwrap();
+        assert!(age >= Duration::from_mins(5) && age < Duration::from_mins(6));
+    }
+
+    #[test]
+    fn test_old_unexpired_token_fails_max_age() {
+        let now = time::OffsetDateTime::now_utc();
+        let claims = json!({
+            "iat": (now - time::Duration::hours(13)).unix_timestamp(),
+            "exp": (now + time::Duration::hours(1)).unix_timestamp(),
+        });
+        let err = validate_claims(&claims, &max_age_config()).unwrap_err();
+        match err {
+            ClaimsError::TokenTooOld {
+                age_seconds,
+                max_age_seconds,
+            } => {
+                assert!(age_seconds >= 13 * 3600);
+                assert_eq!(max_age_seconds, 12 * 3600);
+            }
+            other => panic!("expected TokenTooOld, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_missing_iat_follows_require_iat() {
+        let now = time::OffsetDateTime::now_utc();
+        let claims = json!({ "exp": (now + time::Duration::hours(1)).unix_timestamp() });
+        assert_eq!(token_age(&claims).unwrap(), None);
+        assert!(validate_claims(&claims, &max_age_config()).is_ok());
+
+        let config = ValidationConfig {
+            require_iat: true,
+            ..max_age_config()
+        };
+        let err = validate_claims(&claims, &config).unwrap_err();
+        match err {
+            ClaimsError::MissingClaim(claim) => assert_eq!(claim, StandardClaim::IAT),
+            other => panic!("expected MissingClaim(iat), got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_max_token_age_applies_leeway() {
+        let now = time::OffsetDateTime::now_utc();
+        let claims = json!({
+            "iat": (now - time::Duration::hours(12) - time::Duration::seconds(30)).unix_timestamp(),
+            "exp": (now + time::Duration::hours(1)).unix_timestamp(),
+        });
+        assert!(validate_claims(&claims, &max_age_config()).is_ok());
+
+        let strict = ValidationConfig {
+            leeway_seconds: 0,
+            ..max_age_config()
+        };
+        assert!(matches!(
+            validate_claims(&claims, &strict),
+            Err(ClaimsError::TokenTooOld { .. })
+        ));
+    }
+
+    #[test]
+    fn test_iat_ignored_unless_age_is_checked() {
+        let now = time::OffsetDateTime::now_utc();
+        let claims = json!({
+            "iat": "yesterday",
+            "exp": (now + time::Duration::hours(1)).unix_timestamp(),
+        });
+        assert!(validate_claims(&claims, &ValidationConfig::default()).is_ok());
+        assert!(matches!(
+            validate_claims(&claims, &max_age_config()),
+            Err(ClaimsError::InvalidClaimFormat { ref field, .. }) if field == StandardClaim::IAT
+        ));
+    }
+
     #[test]
     fn test_audience_array_match() {
         let now = time::OffsetDateTime::now_utc();
@@ -409,6 +830,56 @@ mod tests {
         assert!(validate_claims(&claims, &config).is_ok());
     }
 
+    fn audience_config(allowed: &[&str], audience_match: AudienceMatch) -> ValidationConfig {
+        ValidationConfig {
+            allowed_audiences: allowed.iter().map(|a| (*a).to_owned()).collect(),
+            audience_match,
+            require_exp: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_audience_match_any_and_all() {
+        let claims = json!({ "aud": ["api", "web"] });
+
+        let single = audience_config(&["api"], AudienceMatch::Any);
+        assert!(validate_claims(&claims, &single).is_ok());
+        let single = audience_config(&["api"], AudienceMatch::All);
+        assert!(validate_claims(&claims, &single).is_ok());
+
+        let any = audience_config(&["api", "admin"], AudienceMatch::Any);
+        assert!(validate_claims(&claims, &any).is_ok());
+
+        let all = audience_config(&["api", "admin"], AudienceMatch::All);
+        match validate_claims(&claims, &all) {
+            Err(ClaimsError::InvalidAudience { expected, actual }) => {
+                assert_eq!(expected, vec!["api", "admin"]);
+                assert_eq!(actual, vec!["api", "web"]);
+            }
+            other => panic!("expected InvalidAudience, got {other:?}"),
+        }
+
+        let both = audience_config(&["web", "api"], AudienceMatch::All);
+        assert!(validate_claims(&claims, &both).is_ok());
+    }
+
+    #[test]
+    fn test_audience_match_all_with_string_aud() {
+        let claims = json!({ "aud": "api" });
+
+        let one = audience_config(&["api"], AudienceMatch::All);
+        assert!(validate_claims(&claims, &one).is_ok());
+
+        let two = audience_config(&["api", "web"], AudienceMatch::All);
+        assert!(matches!(
+            validate_claims(&claims, &two),
+            Err(ClaimsError::InvalidAudience { .. })
+        ));
+        let two = audience_config(&["api", "web"], AudienceMatch::Any);
+        assert!(validate_claims(&claims, &two).is_ok());
+    }
+
     #[test]
     fn test_parse_uuid_from_value() {
         let uuid = Uuid::new_v4();
@@ -496,6 +967,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_required_claims_present_pass() {
+        let claims = json!({
+            "sub": "user-1",
+            "tenant_id": "t-1",
+            "org": { "plan": { "tier": "pro" } },
+        });
+        let config = ValidationConfig {
+            require_exp: false,
+            required_claims: vec![
+                "sub".to_owned(),
+                "tenant_id".to_owned(),
+                "org.plan.tier".to_owned(),
+            ],
+            ..Default::default()
+        };
+        assert!(validate_claims(&claims, &config).is_ok());
+    }
+
+    #[test]
+    fn test_missing_required_nested_claim_names_full_path() {
+        let config = ValidationConfig {
+            require_exp: false,
+            required_claims: vec!["sub".to_owned(), "org.plan.tier".to_owned()],
+            ..Default::default()
+        };
+
+        for claims in [
+            json!({ "sub": "user-1" }),
+            json!({ "sub": "user-1", "org": "acme" }),
+            json!({ "sub": "user-1", "org": { "plan": {} } }),
+            json!({ "sub": "user-1", "org": { "plan": { "tier": "" } } }),
+            json!({ "sub": "user-1", "org": { "plan": { "tier": null } } }),
+        ] {
+            let err = validate_claims(&claims, &config).unwrap_err();
+            match err {
+                ClaimsError::MissingClaim(claim) => assert_eq!(claim, "org.plan.tier"),
+                other => panic!("expected MissingClaim(org.plan.tier), got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_required_claim_fails() {
+        let config = ValidationConfig {
+            require_exp: false,
+            required_claims: vec!["tenant_id".to_owned()],
+            ..Default::default()
+        };
+        for value in [json!(""), json!([]), json!({}), json!(null)] {
+            let err = validate_claims(&json!({ "tenant_id": value }), &config).unwrap_err();
+            assert!(
+                matches!(err, ClaimsError::MissingClaim(ref c) if c == "tenant_id"),
+                "expected MissingClaim(tenant_id) for {value}, got {err:?}"
+            );
+        }
+        assert!(validate_claims(&json!({ "tenant_id": 0 }), &config).is_ok());
+    }
+
     #[test]
     fn test_nbf_overflow_returns_error() {
         let now = time::OffsetDateTime::now_utc();
@@ -557,4 +1087,98 @@ mod tests {
             }
         }
     }
+
+    fn keycloak_payload() -> serde_json::Value {
+        json!({
+            "exp": 1_893_456_000,
+            "iat": 1_893_455_700,
+            "jti": "0b5c3e2a-8f1d-4c6e-9a7b-2d4f6e8a0c1e",
+            "iss": "https://kc.example.com/realms/acme",
+            "aud": ["account", "orders-api"],
+            "sub": "f4e3d2c1-b0a9-4877-8665-544332211000",
+            "typ": "Bearer",
+            "azp": "web-app",
+            "session_state": "7c9e6679-7425-40de-944b-e07fc1f90ae7",
+            "realm_access": {
+                "roles": ["offline_access", "uma_authorization", "default-roles-acme", "admin"]
+            },
+            "resource_access": {
+                "orders-api": { "roles": ["orders:read", "orders:write", "admin"] },
+                "account": { "roles": ["manage-account", "view-profile"] }
+            },
+            "scope": "openid email profile",
+            "email_verified": true,
+            "preferred_username": "jdoe"
+        })
+    }
+
+    fn role_set(roles: &[&str]) -> BTreeSet<String> {
+        roles.iter().map(|r| (*r).to_owned()).collect()
+    }
+
+    #[test]
+    fn test_role_mapping_merges_realm_and_selected_client_roles() {
+        let mapping = RoleMapping {
+            resource_clients: vec!["orders-api".to_owned()],
+            ..RoleMapping::default()
+        };
+
+        assert_eq!(
+            mapping.roles(&keycloak_payload()),
+            role_set(&[
+                "admin",
+                "default-roles-acme",
+                "offline_access",
+                "orders:read",
+                "orders:write",
+                "uma_authorization",
+            ])
+        );
+    }
+
+    #[test]
+    fn test_role_mapping_all_clients_with_prefix() {
+        let mapping = RoleMapping {
+            realm_roles: false,
+            resource_clients: vec!["*".to_owned()],
+            prefix_client_roles: true,
+        };
+
+        assert_eq!(
+            mapping.roles(&keycloak_payload()),
+            role_set(&[
+                "account:manage-account",
+                "account:view-profile",
+                "orders-api:admin",
+                "orders-api:orders:read",
+                "orders-api:orders:write",
+            ])
+        );
+    }
+
+    #[test]
+    fn test_role_mapping_apply_keeps_existing_roles_and_skips_malformed() {
+        let mut raw = json!({
+            "roles": ["auditor"],
+            "realm_access": { "roles": ["admin", 7] },
+            "resource_access": { "orders-api": { "roles": "not-a-list" } }
+        });
+        let mapping = RoleMapping {
+            resource_clients: vec!["orders-api".to_owned()],
+            ..RoleMapping::default()
+        };
+
+        mapping.apply(&mut raw);
+
+        assert_eq!(raw["roles"], json!(["admin", "auditor"]));
+    }
+
+    #[test]
+    fn test_role_mapping_deserializes_with_defaults() {
+        let mapping: RoleMapping =
+            serde_json::from_value(json!({ "resource_clients": ["orders-api"] })).unwrap();
+        assert!(mapping.realm_roles);
+        assert!(!mapping.prefix_client_roles);
+        assert_eq!(mapping.resource_clients, vec!["orders-api".to_owned()]);
+    }
 }
@@ -1,7 +1,8 @@
-use std::{future::Future, marker::PhantomData, pin::Pin, sync::Arc};
+use std::{future::Future, marker::PhantomData, pin::Pin, sync::Arc, time::Duration};
 
-use crate::secure::{DbConn, DbTx, TxConfig};
-use crate::{Db, DbError};
+use crate::deadlock::RetryableError;
+use crate::secure::{DbConn, DbTx, TxConfig, TxRetryConfig};
+use crate::{Db, DbError, PoolStats};
 
 /// Thin, reusable DB entrypoint for application services.
 ///
@@ -59,6 +60,25 @@ where
         self.db.conn().map_err(E::from)
     }
 
+    /// Snapshot of the connection pool usage (size, idle, in-use, max).
+    ///
+    /// Unlike [`conn`](Self::conn), this is safe to call from inside a transaction.
+    #[must_use]
+    pub fn pool_stats(&self) -> PoolStats {
+        self.db.pool_stats()
+    }
+
+    /// Run `SELECT 1` against the pool with a timeout.
+    ///
+    /// Intended for readiness probes such as the gateway's `/readyz`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `E` (mapped from `DbError`) if the probe times out or the query fails.
+    pub async fn health_check(&self, timeout: Duration) -> Result<(), E> {
+        self.db.health_check(timeout).await.map_err(E::from)
+    }
+
     /// Execute a closure inside a database transaction.
     ///
     /// # Errors
@@ -96,4 +116,54 @@ where
     {
         self.db.transaction_ref_mapped_with_config(config, f).await
     }
+
+    /// Execute a closure inside a database transaction, re-running the whole
+    /// transaction when it fails with a retryable error.
+    ///
+    /// Only serialization failures and deadlocks (see [`RetryableError`]) are
+    /// retried, up to `retry.max_retries` times with exponential backoff.
+    /// Constraint violations, scope rejections and any other errors are returned
+    /// immediately. Because the closure may run more than once, it must be `Fn`
+    /// and must not have side effects outside the transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns `E` if:
+    /// - a non-retryable error occurs (begin, closure or commit)
+    /// - a retryable error persists after `retry.max_retries` retries
+    pub async fn transaction_with_retry<T, F>(
+        &self,
+        config: TxConfig,
+        retry: TxRetryConfig,
+        f: F,
+    ) -> Result<T, E>
+    where
+        E: RetryableError,
+        T: Send + 'static,
+        F: for<'a> Fn(&'a DbTx<'a>) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'a>>
+            + Send
+            + Sync,
+    {
+        let mut attempt = 0;
+        loop {
+            match self
+                .db
+                .transaction_ref_mapped_with_config(config.clone(), &f)
+                .await
+            {
+                Err(err) if attempt < retry.max_retries && err.is_retryable() => {
+                    let delay = retry.backoff(attempt);
+                    attempt += 1;
+                    tracing::debug!(
+                        attempt,
+                        max_retries = retry.max_retries,
+                        delay_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+                        "retrying transaction after serialization failure"
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                res => return res,
+            }
+        }
+    }
 }
@@ -7,10 +7,15 @@
 //! `InnoDB` detects deadlocks instantly and rolls back one transaction (the victim).
 //! SQLSTATE `40001` signals a serialization failure that is always safe to retry.
 //! This module provides detection helpers for use by callers that manage their
-//! own transaction lifecycle (e.g., the outbox sequencer, hierarchy mutations).
+//! own transaction lifecycle (e.g., the outbox sequencer, hierarchy mutations),
+//! and the [`RetryableError`] classification used by
+//! [`DBProvider::transaction_with_retry`](crate::DBProvider::transaction_with_retry).
 
 use sea_orm::DbErr;
 
+use crate::DbError;
+use crate::secure::ScopeError;
+
 /// SQLSTATE `40001` — serialization failure / deadlock.
 ///
 /// This code is used by both `PostgreSQL` (for serialization failures under
@@ -23,6 +28,9 @@ const SERIALIZATION_FAILURE_SQLSTATE: &str = "40001";
 /// transaction detects a read/write dependency conflict.
 const PG_SERIALIZATION_MSG: &str = "could not serialize access";
 
+/// SQLSTATE `40P01` — `PostgreSQL` `deadlock_detected`.
+const PG_DEADLOCK_SQLSTATE: &str = "40P01";
+
 /// Returns `true` if the error contains SQLSTATE `40001`.
 ///
 /// This matches both `MySQL`/`MariaDB` deadlocks and `PostgreSQL` serialization
@@ -51,7 +59,8 @@ pub fn is_deadlock(err: &DbErr) -> bool {
 /// This is a superset of [`is_deadlock`] — it matches SQLSTATE `40001` **and**
 /// the `PostgreSQL` `could not serialize access` message text.  Both deadlocks
 /// and serialization conflicts are retryable, and this function does not
-/// distinguish between them.
+/// distinguish between them.  `PostgreSQL` deadlocks carry their own SQLSTATE
+/// and are matched by [`is_pg_deadlock`] instead.
 ///
 /// Coverage:
 /// - **`PostgreSQL`**: `SERIALIZABLE` isolation conflicts
@@ -74,6 +83,70 @@ pub fn is_serialization_failure(err: &DbErr) -> bool {
     }
 }
 
+/// Returns `true` if the error contains the `PostgreSQL` deadlock SQLSTATE `40P01`.
+///
+/// `PostgreSQL` aborts one transaction of a lock cycle with `deadlock_detected`;
+/// like a serialization failure, the victim is safe to re-run.
+///
+/// Always returns `false` for non-runtime errors and for `MySQL`/`SQLite`
+/// (`InnoDB` reports deadlocks as `40001`, see [`is_deadlock`]).
+#[must_use]
+pub fn is_pg_deadlock(err: &DbErr) -> bool {
+    match err {
+        DbErr::Exec(runtime_err) | DbErr::Query(runtime_err) => {
+            runtime_err.to_string().contains(PG_DEADLOCK_SQLSTATE)
+        }
+        _ => false,
+    }
+}
+
+/// Errors that can tell whether the failed transaction is safe to re-run from `BEGIN`.
+///
+/// Only serialization failures and deadlocks are retryable. Constraint violations,
+/// scope rejections and every other error are returned to the caller as-is.
+///
+/// Implement this for a domain error type to use it with
+/// [`DBProvider::transaction_with_retry`](crate::DBProvider::transaction_with_retry);
+/// typically by delegating to the wrapped `DbError` / `ScopeError`.
+pub trait RetryableError {
+    /// Returns `true` if the transaction that produced this error may be retried.
+    fn is_retryable(&self) -> bool;
+}
+
+impl RetryableError for DbErr {
+    fn is_retryable(&self) -> bool {
+        is_serialization_failure(self) || is_pg_deadlock(self)
+    }
+}
+
+impl RetryableError for ScopeError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Db(err) => err.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+impl RetryableError for DbError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Sea(err) => err.is_retryable(),
+            #[cfg(any(feature = "pg", feature = "mysql", feature = "sqlite"))]
+            Self::Sqlx(err) => err
+                .as_database_error()
+                .and_then(sqlx::error::DatabaseError::code)
+                .is_some_and(|code| {
+                    code == SERIALIZATION_FAILURE_SQLSTATE || code == PG_DEADLOCK_SQLSTATE
+                }),
+            Self::Other(err) => err
+                .downcast_ref::<ScopeError>()
+                .is_some_and(RetryableError::is_retryable),
+            _ => false,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,6 +197,23 @@ mod tests {
         )));
     }
 
+    // -- is_pg_deadlock --
+
+    #[test]
+    fn pg_deadlock_sqlstate_detected() {
+        let err = exec_err("error returned from database: 40P01: deadlock detected");
+        assert!(is_pg_deadlock(&err));
+        assert!(!is_serialization_failure(&err));
+    }
+
+    #[test]
+    fn pg_deadlock_ignores_other_errors() {
+        assert!(!is_pg_deadlock(&exec_err(
+            "ERROR: 40001: could not serialize access"
+        )));
+        assert!(!is_pg_deadlock(&DbErr::Custom("40P01".into())));
+    }
+
     // -- is_serialization_failure negative cases --
 
     #[test]
@@ -133,4 +223,18 @@ mod tests {
         )));
         assert!(!is_serialization_failure(&exec_err("unique constraint")));
     }
+
+    // -- RetryableError classification --
+
+    #[test]
+    fn retryable_error_classifies_wrapped_errors() {
+        let retryable = || exec_err("ERROR: 40001: could not serialize access");
+        assert!(DbError::Sea(retryable()).is_retryable());
+        assert!(DbError::from(ScopeError::Db(retryable())).is_retryable());
+        assert!(DbError::Sea(exec_err("40P01: deadlock detected")).is_retryable());
+
+        assert!(!DbError::Sea(exec_err("UNIQUE constraint failed: t.id")).is_retryable());
+        assert!(!DbError::from(ScopeError::Denied("nope")).is_retryable());
+        assert!(!DbError::ConnRequestedInsideTx.is_retryable());
+    }
 }
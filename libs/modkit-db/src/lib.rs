@@ -238,6 +238,10 @@ pub enum DbError {
     /// ```
     #[error("Cannot create non-transactional connection inside an active transaction")]
     ConnRequestedInsideTx,
+
+    /// The health-check probe did not complete within the allotted time.
+    #[error("Database health check timed out after {0:?}")]
+    HealthCheckTimeout(Duration),
 }
 
 impl From<modkit_utils::var_expand::ExpandVarsError> for DbError {
@@ -301,6 +305,36 @@ impl Default for ConnectOpts {
     }
 }
 
+/// Point-in-time snapshot of connection pool usage.
+///
+/// Counts are sampled without locking the pool, so `idle + in_use` may briefly
+/// disagree with `size` while connections are being opened or returned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Connections currently open (idle and in use).
+    pub size: u32,
+    /// Open connections waiting in the pool.
+    pub idle: u32,
+    /// Open connections checked out by callers (including open transactions).
+    pub in_use: u32,
+    /// Configured upper bound on open connections.
+    pub max_size: u32,
+}
+
+#[cfg(any(feature = "pg", feature = "mysql", feature = "sqlite"))]
+impl PoolStats {
+    fn of<DB: sqlx::Database>(pool: &sqlx::Pool<DB>) -> Self {
+        let size = pool.size();
+        let idle = u32::try_from(pool.num_idle()).unwrap_or(u32::MAX).min(size);
+        Self {
+            size,
+            idle,
+            in_use: size - idle,
+            max_size: pool.options().get_max_connections(),
+        }
+    }
+}
+
 /// Main handle.
 #[derive(Debug, Clone)]
 pub(crate) struct DbHandle {
@@ -466,6 +500,43 @@ impl DbHandle {
     // NOTE: We intentionally do not expose raw `SQLx` pools from `DbHandle`.
     // Use `SecureConn` for all application-level DB access.
 
+    // --- Observability ---
+
+    /// Snapshot of the underlying connection pool usage.
+    #[must_use]
+    pub fn pool_stats(&self) -> PoolStats {
+        match self.engine {
+            #[cfg(feature = "pg")]
+            DbEngine::Postgres => PoolStats::of(self.sea.get_postgres_connection_pool()),
+            #[cfg(not(feature = "pg"))]
+            DbEngine::Postgres => PoolStats::default(),
+            #[cfg(feature = "mysql")]
+            DbEngine::MySql => PoolStats::of(self.sea.get_mysql_connection_pool()),
+            #[cfg(not(feature = "mysql"))]
+            DbEngine::MySql => PoolStats::default(),
+            #[cfg(feature = "sqlite")]
+            DbEngine::Sqlite => PoolStats::of(self.sea.get_sqlite_connection_pool()),
+            #[cfg(not(feature = "sqlite"))]
+            DbEngine::Sqlite => PoolStats::default(),
+        }
+    }
+
+    /// Run `SELECT 1` against the pool, failing if it does not finish within `timeout`.
+    ///
+    /// # Errors
+    /// Returns `DbError::HealthCheckTimeout` if the probe exceeds `timeout`
+    /// (including time spent waiting for a free connection), or the driver error
+    /// if the query itself fails.
+    pub async fn health_check(&self, timeout: Duration) -> Result<()> {
+        use sea_orm::{ConnectionTrait, Statement};
+
+        let stmt = Statement::from_string(self.sea.get_database_backend(), "SELECT 1");
+        tokio::time::timeout(timeout, self.sea.execute(stmt))
+            .await
+            .map_err(|_| DbError::HealthCheckTimeout(timeout))??;
+        Ok(())
+    }
+
     // --- SeaORM accessor ---
 
     /// Create a secure database wrapper for module code.
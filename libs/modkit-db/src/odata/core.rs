@@ -5,27 +5,38 @@ use std::collections::HashMap;
 
 use bigdecimal::{BigDecimal, ToPrimitive};
 use chrono::{NaiveDate, NaiveTime, Utc};
-use modkit_odata::{CursorV1, Error as ODataError, ODataOrderBy, ODataQuery, SortDir, ast as core};
+use modkit_odata::{
+    CursorV1, Error as ODataError, NullsOrder, ODataOrderBy, ODataQuery, SortDir, ast as core,
+};
 use rust_decimal::Decimal;
 use sea_orm::{
-    ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
-    sea_query::{Expr, Order},
+    ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+    QueryTrait, sea_query::Expr,
 };
 use thiserror::Error;
 
 use modkit_odata::filter::FieldKind;
 
 use crate::odata::LimitCfg;
-use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
+use crate::odata::sea_orm_filter::{key_after, key_equals, null_ordering, sea_order};
+use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner, record_sql};
 
-/// Type alias for cursor extraction function to reduce type complexity
-type CursorExtractor<E> = fn(&<E as EntityTrait>::Model) -> String;
+/// Encodes a field of a model as a cursor key.
+#[derive(Clone)]
+pub enum CursorExtractor<E: EntityTrait> {
+    /// The field is never NULL.
+    Value(fn(&E::Model) -> String),
+    /// The field may be NULL; `None` encodes a NULL key.
+    Nullable(fn(&E::Model) -> Option<String>),
+}
 
 #[derive(Clone)]
 pub struct Field<E: EntityTrait> {
     pub col: E::Column,
     pub kind: FieldKind,
     pub to_string_for_cursor: Option<CursorExtractor<E>>,
+    /// Placement of NULLs when ordering ascending; descending flips it
+    pub nulls: NullsOrder,
 }
 
 #[derive(Clone)]
@@ -53,6 +64,7 @@ impl<E: EntityTrait> FieldMap<E> {
                 col,
                 kind,
                 to_string_for_cursor: None,
+                nulls: NullsOrder::default(),
             },
         );
         self
@@ -63,22 +75,69 @@ impl<E: EntityTrait> FieldMap<E> {
         api_name: impl Into<String>,
         col: E::Column,
         kind: FieldKind,
-        to_string_for_cursor: CursorExtractor<E>,
+        to_string_for_cursor: fn(&E::Model) -> String,
     ) -> Self {
         self.map.insert(
             api_name.into().to_lowercase(),
             Field {
                 col,
                 kind,
-                to_string_for_cursor: Some(to_string_for_cursor),
+                to_string_for_cursor: Some(CursorExtractor::Value(to_string_for_cursor)),
+                nulls: NullsOrder::default(),
             },
         );
         self
     }
 
-    pub fn encode_model_key(&self, model: &E::Model, field_name: &str) -> Option<String> {
-        let f = self.get(field_name)?;
-        f.to_string_for_cursor.map(|f| f(model))
+    /// Like [`Self::insert_with_extractor`], for a nullable column: the
+    /// extractor returns `None` for a NULL value.
+    pub fn insert_with_nullable_extractor(
+        mut self,
+        api_name: impl Into<String>,
+        col: E::Column,
+        kind: FieldKind,
+        to_string_for_cursor: fn(&E::Model) -> Option<String>,
+    ) -> Self {
+        self.map.insert(
+            api_name.into().to_lowercase(),
+            Field {
+                col,
+                kind,
+                to_string_for_cursor: Some(CursorExtractor::Nullable(to_string_for_cursor)),
+                nulls: NullsOrder::default(),
+            },
+        );
+        self
+    }
+
+    /// Set where NULLs of an already inserted field sort in ascending order.
+    ///
+    /// Fields default to [`NullsOrder::First`] (the `OData` rule); unknown names are ignored.
+    pub fn with_nulls(mut self, api_name: &str, nulls: NullsOrder) -> Self {
+        if let Some(field) = self.map.get_mut(&api_name.to_lowercase()) {
+            field.nulls = nulls;
+        }
+        self
+    }
+
+    /// Encode `field_name` of `model` as a cursor key; `Ok(None)` is a NULL key.
+    ///
+    /// # Errors
+    /// Returns `ODataError::InvalidOrderByField` if the field is unknown or has
+    /// no cursor extractor.
+    pub fn encode_model_key(
+        &self,
+        model: &E::Model,
+        field_name: &str,
+    ) -> Result<Option<String>, ODataError> {
+        match self
+            .get(field_name)
+            .and_then(|f| f.to_string_for_cursor.as_ref())
+        {
+            Some(CursorExtractor::Value(f)) => Ok(Some(f(model))),
+            Some(CursorExtractor::Nullable(f)) => Ok(f(model)),
+            None => Err(ODataError::InvalidOrderByField(field_name.to_owned())),
+        }
     }
     #[must_use]
     pub fn get(&self, name: &str) -> Option<&Field<E>> {
@@ -349,20 +408,28 @@ where
         ));
     }
 
-    // Parse cursor values
+    // Determine if we're going backward
+    let is_backward = cursor.d == "bwd";
+
+    // Parse cursor values; backward walks every key in reverse
     let mut cursor_values = Vec::new();
     for (i, key_str) in cursor.k.iter().enumerate() {
         let order_key = &order.0[i];
         let field = fmap
             .get(&order_key.field)
             .ok_or_else(|| ODataBuildError::UnknownField(order_key.field.clone()))?;
-        let value = parse_cursor_value(field.kind, key_str)?;
-        cursor_values.push((field, value, order_key.dir));
+        let value = key_str
+            .as_deref()
+            .map(|s| parse_cursor_value(field.kind, s))
+            .transpose()?;
+        let dir = if is_backward {
+            order_key.dir.reverse()
+        } else {
+            order_key.dir
+        };
+        cursor_values.push((field, value, dir));
     }
 
-    // Determine if we're going backward
-    let is_backward = cursor.d == "bwd";
-
     // Build lexicographic condition
     // Forward (fwd):
     //   For ASC: (k0 > v0) OR (k0 = v0 AND k1 > v1) OR ...
@@ -370,6 +437,7 @@ where
     // Backward (bwd): Reverse the comparisons
     //   For ASC: (k0 < v0) OR (k0 = v0 AND k1 < v1) OR ...
     //   For DESC: (k0 > v0) OR (k0 = v0 AND k1 > v1) OR ...
+    // NULL keys compare by the field's NULL placement and match with IS NULL.
     let mut main_condition = Condition::any();
 
     for i in 0..cursor_values.len() {
@@ -377,25 +445,17 @@ where
 
         // Add equality conditions for all previous fields
         for (field, value, _) in cursor_values.iter().take(i) {
-            prefix_condition = prefix_condition.add(Expr::col(field.col).eq(value.clone()));
+            prefix_condition = prefix_condition.add(key_equals(field.col, value.as_ref()));
         }
 
         // Add the comparison condition for current field
         let (field, value, dir) = &cursor_values[i];
-        let comparison = if is_backward {
-            // Backward: reverse the comparison
-            match dir {
-                SortDir::Asc => Expr::col(field.col).lt(value.clone()),
-                SortDir::Desc => Expr::col(field.col).gt(value.clone()),
-            }
-        } else {
-            // Forward: normal comparison
-            match dir {
-                SortDir::Asc => Expr::col(field.col).gt(value.clone()),
-                SortDir::Desc => Expr::col(field.col).lt(value.clone()),
-            }
-        };
-        prefix_condition = prefix_condition.add(comparison);
+        prefix_condition = prefix_condition.add(key_after(
+            field.col,
+            value.as_ref(),
+            *dir,
+            dir.nulls(field.nulls),
+        ));
 
         main_condition = main_condition.add(prefix_condition);
     }
@@ -438,10 +498,7 @@ pub fn build_cursor_for_model<E: EntityTrait>(
 ) -> Result<CursorV1, ODataError> {
     let mut k = Vec::with_capacity(order.0.len());
     for key in &order.0 {
-        let s = fmap
-            .encode_model_key(model, &key.field)
-            .ok_or_else(|| ODataError::InvalidOrderByField(key.field.clone()))?;
-        k.push(s);
+        k.push(fmap.encode_model_key(model, &key.field)?);
     }
     Ok(CursorV1 {
         k,
@@ -670,12 +727,11 @@ where
                 .get(&order_key.field)
                 .ok_or_else(|| ODataBuildError::UnknownField(order_key.field.clone()))?;
 
-            let sea_order = match order_key.dir {
-                SortDir::Asc => Order::Asc,
-                SortDir::Desc => Order::Desc,
-            };
-
-            query = query.order_by(field.col, sea_order);
+            query = query.order_by_with_nulls(
+                field.col,
+                sea_order(order_key.dir),
+                null_ordering(order_key.dir.nulls(field.nulls)),
+            );
         }
 
         Ok(query)
@@ -710,12 +766,11 @@ where
         for order_key in &order.0 {
             let field = resolve_field(fld_map, &order_key.field)?;
 
-            let sea_order = match order_key.dir {
-                SortDir::Asc => Order::Asc,
-                SortDir::Desc => Order::Desc,
-            };
-
-            query = query.order_by(field.col, sea_order);
+            query = query.order_by_with_nulls(
+                field.col,
+                sea_order(order_key.dir),
+                null_ordering(order_key.dir.nulls(field.nulls)),
+            );
         }
 
         Ok(query)
@@ -786,6 +841,9 @@ fn clamp_limit(req: Option<u64>, cfg: LimitCfg) -> u64 {
 
 /// One-shot pagination combiner that handles filter → cursor predicate → order → overfetch/trim → build cursors.
 ///
+/// When `$count=true`, the total number of rows matching `select` and the filter
+/// is reported in `page_info.total_count`.
+///
 /// # Errors
 /// Returns `ODataError` if filter application, cursor validation, or database query fails.
 pub async fn paginate_with_odata<E, D, F, C>(
@@ -800,6 +858,7 @@ pub async fn paginate_with_odata<E, D, F, C>(
 where
     E: EntityTrait,
     E::Column: ColumnTrait + Copy,
+    E::Model: Sync,
     F: Fn(E::Model) -> D + Copy,
     C: DBRunner,
 {
@@ -837,6 +896,20 @@ where
         );
     }
 
+    // Count before the cursor predicate so the total is independent of the page
+    let total_count = if q.wants_count() {
+        record_sql(conn, |backend| s.build(backend));
+        #[allow(clippy::disallowed_methods)]
+        let total = match DBRunnerInternal::as_seaorm(conn) {
+            SeaOrmRunner::Conn(db) => s.clone().count(db).await,
+            SeaOrmRunner::Tx(tx) => s.clone().count(tx).await,
+        }
+        .map_err(|e| ODataError::Db(e.to_string()))?;
+        Some(total)
+    } else {
+        None
+    };
+
     // Check if we're paginating backward
     let is_backward = q.cursor.as_ref().is_some_and(|c| c.d == "bwd");
 
@@ -859,6 +932,7 @@ where
     // Apply limit
     s = s.limit(fetch);
 
+    record_sql(conn, |backend| s.build(backend));
     #[allow(clippy::disallowed_methods)]
     let mut rows = match DBRunnerInternal::as_seaorm(conn) {
         SeaOrmRunner::Conn(db) => s.all(db).await,
@@ -932,6 +1006,7 @@ where
             next_cursor,
             prev_cursor,
             limit,
+            total_count,
         },
     })
 }
@@ -104,7 +104,9 @@
 //! - Applies filters at the database level (not in application memory)
 //! - Supports indexed columns via field mappings for optimal query performance
 
-use crate::odata::{FieldMap, LimitCfg, paginate_with_odata};
+use std::borrow::Cow;
+
+use crate::odata::{FieldMap, LimitCfg, expr_to_condition, paginate_with_odata};
 use crate::secure::{DBRunner, ScopableEntity, SecureEntityExt};
 use modkit_odata::{Error as ODataError, ODataQuery, Page, SortDir};
 use modkit_security::AccessScope;
@@ -270,16 +272,36 @@ where
     pub async fn fetch<D, F>(self, q: &ODataQuery, map: F) -> Result<Page<D>, ODataError>
     where
         E: ScopableEntity,
+        E::Model: Sync,
         F: Fn(E::Model) -> D + Copy,
     {
-        // Apply security scope first - this enforces tenant isolation
-        let select = E::find().secure().scope_with(self.scope).inner;
+        // Combine security scope and $filter up front: the filter is ANDed as a
+        // parenthesized group, so it can only narrow what the scope allows
+        let (select, q) = match q.filter.as_deref() {
+            Some(ast) => {
+                let filter = expr_to_condition::<E>(ast, self.fmap)
+                    .map_err(|e| ODataError::InvalidFilter(e.to_string()))?;
+                let select = E::find()
+                    .secure()
+                    .scope_with_filter(self.scope, filter)
+                    .inner;
+                let unfiltered = ODataQuery {
+                    filter: None,
+                    ..q.clone()
+                };
+                (select, Cow::Owned(unfiltered))
+            }
+            None => (
+                E::find().secure().scope_with(self.scope).inner,
+                Cow::Borrowed(q),
+            ),
+        };
 
-        // Now apply OData filters, cursor, order, and limits
+        // Now apply cursor, order, and limits
         paginate_with_odata::<E, D, _, _>(
             select,
             self.conn,
-            q,
+            &q,
             self.fmap,
             self.tiebreaker,
             self.limits,
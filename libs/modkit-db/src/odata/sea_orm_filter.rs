@@ -10,13 +10,15 @@ use chrono::SecondsFormat;
 use modkit_odata::filter::{
     FieldKind, FilterField, FilterNode, FilterOp, ODataValue, convert_expr_to_filter_node,
 };
-use modkit_odata::{CursorV1, Error as ODataError, ODataOrderBy, Page, PageInfo, SortDir};
+use modkit_odata::{
+    CursorV1, Error as ODataError, NullsOrder, ODataOrderBy, Page, PageInfo, SortDir,
+};
 use sea_orm::{
-    Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
-    sea_query::{Expr, Order},
+    Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait,
+    sea_query::{Expr, IntoColumnRef, NullOrdering, Order, SimpleExpr},
 };
 
-use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
+use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner, record_sql};
 
 /// Trait for mapping DTO filter fields to `SeaORM` columns.
 ///
@@ -92,6 +94,14 @@ pub trait ODataFieldMapping<F: FilterField>: FieldToColumn<F> {
         field: F,
     ) -> sea_orm::Value;
 
+    /// Placement of NULLs of `field` when ordering ascending; descending flips it.
+    ///
+    /// Defaults to [`NullsOrder::First`] (the `OData` rule: null sorts lowest).
+    /// Override for nullable columns that should sort after every value.
+    fn nulls_order(_field: F) -> NullsOrder {
+        NullsOrder::First
+    }
+
     /// Extract cursor values for all fields in an order.
     ///
     /// This is a convenience method that can be overridden for optimization,
@@ -114,6 +124,67 @@ pub trait ODataFieldMapping<F: FilterField>: FieldToColumn<F> {
     }
 }
 
+/// Map an `OData` sort direction to `SeaORM`
+pub(crate) fn sea_order(dir: SortDir) -> Order {
+    match dir {
+        SortDir::Asc => Order::Asc,
+        SortDir::Desc => Order::Desc,
+    }
+}
+
+/// Map NULL placement to `SeaORM`, which renders it explicitly on every backend
+pub(crate) fn null_ordering(nulls: NullsOrder) -> NullOrdering {
+    match nulls {
+        NullsOrder::First => NullOrdering::First,
+        NullsOrder::Last => NullOrdering::Last,
+    }
+}
+
+/// Rows ordered strictly after the cursor key `value` on `col`, walking in `dir`
+/// with NULLs placed at `nulls` in that walk; a `None` value is a NULL key.
+pub(crate) fn key_after<C>(
+    col: C,
+    value: Option<&sea_orm::Value>,
+    dir: SortDir,
+    nulls: NullsOrder,
+) -> Condition
+where
+    C: IntoColumnRef + Copy,
+{
+    match (value, nulls) {
+        (None, NullsOrder::First) => Condition::all().add(Expr::col(col).is_not_null()),
+        (None, NullsOrder::Last) => Condition::all().add(Expr::value(false)),
+        (Some(v), _) => {
+            let cmp = match dir {
+                SortDir::Asc => Expr::col(col).gt(v.clone()),
+                SortDir::Desc => Expr::col(col).lt(v.clone()),
+            };
+            let after = Condition::any().add(cmp);
+            if nulls == NullsOrder::Last {
+                after.add(Expr::col(col).is_null())
+            } else {
+                after
+            }
+        }
+    }
+}
+
+/// Rows whose `col` equals the cursor key `value` (`IS NULL` for a NULL key).
+pub(crate) fn key_equals<C>(col: C, value: Option<&sea_orm::Value>) -> SimpleExpr
+where
+    C: IntoColumnRef + Copy,
+{
+    match value {
+        Some(v) => Expr::col(col).eq(v.clone()),
+        None => Expr::col(col).is_null(),
+    }
+}
+
+/// Whether a `SeaORM` value is SQL NULL.
+fn is_null(value: &sea_orm::Value) -> bool {
+    *value == value.as_null()
+}
+
 /// Convert a `FilterNode`<F> to a `SeaORM` Condition using a `FieldToColumn` mapping.
 ///
 /// This function provides generic traversal of the `FilterNode` AST and handles
@@ -461,14 +532,16 @@ fn clamp_limit(req: Option<u64>, cfg: LimitCfg) -> u64 {
 ///
 /// - `select`: Base `SeaORM` select query (already security-scoped if needed)
 /// - `conn`: Database connection
-/// - `query`: `OData` query with filter, order, cursor, and limit
+/// - `query`: `OData` query with filter, order, cursor, limit, and `$count`
 /// - `tiebreaker`: Default orderby field and direction for stable pagination
 /// - `limit_cfg`: Default and maximum page sizes
 /// - `model_to_domain`: Function to convert entity models to domain types
 ///
 /// # Returns
 ///
-/// A Page containing the results and pagination metadata (next/prev cursors)
+/// A Page containing the results and pagination metadata (next/prev cursors).
+/// When `$count=true`, `page_info.total_count` holds the number of rows matching
+/// the scope and filter, independent of the cursor and limit.
 ///
 /// # Example
 ///
@@ -497,6 +570,7 @@ where
     F: FilterField,
     M: ODataFieldMapping<F, Entity = E>,
     E: EntityTrait,
+    E::Model: Sync,
     Mapper: Fn(E::Model) -> D,
     C: DBRunner,
 {
@@ -521,18 +595,34 @@ where
         return Err(ODataError::FilterMismatch);
     }
 
-    let mut s = select.inner;
+    let mut select = select;
 
     // Apply filter using type-safe FilterNode
     if let Some(ast) = query.filter.as_deref() {
         let filter_node = convert_expr_to_filter_node::<F>(ast)
             .map_err(|e| ODataError::InvalidFilter(e.to_string()))?;
 
-        s = s.filter(
+        select = select.filter(
             filter_node_to_condition::<F, M>(&filter_node).map_err(ODataError::InvalidFilter)?,
         );
     }
 
+    // Total count shares the scope + filter WHERE clause, before cursor and limit
+    let total_count = if query.wants_count() {
+        record_sql(conn, |backend| select.inner.build(backend));
+        #[allow(clippy::disallowed_methods)]
+        let total = match DBRunnerInternal::as_seaorm(conn) {
+            SeaOrmRunner::Conn(db) => select.inner.clone().count(db).await,
+            SeaOrmRunner::Tx(tx) => select.inner.clone().count(tx).await,
+        }
+        .map_err(|e| ODataError::Db(e.to_string()))?;
+        Some(total)
+    } else {
+        None
+    };
+
+    let mut s = select.inner;
+
     let is_backward = query.cursor.as_ref().is_some_and(|c| c.d == "bwd");
 
     // Apply cursor predicate
@@ -551,16 +641,16 @@ where
     for order_key in &query_order.0 {
         let field = F::from_name(&order_key.field)
             .ok_or_else(|| ODataError::InvalidOrderByField(order_key.field.clone()))?;
-        let column = M::map_field(field);
-        let sea_order = match order_key.dir {
-            SortDir::Asc => Order::Asc,
-            SortDir::Desc => Order::Desc,
-        };
-        s = s.order_by(column, sea_order);
+        s = s.order_by_with_nulls(
+            M::map_field(field),
+            sea_order(order_key.dir),
+            null_ordering(order_key.dir.nulls(M::nulls_order(field))),
+        );
     }
 
     s = s.limit(fetch);
 
+    record_sql(conn, |backend| s.build(backend));
     #[allow(clippy::disallowed_methods)]
     let mut rows = match DBRunnerInternal::as_seaorm(conn) {
         SeaOrmRunner::Conn(db) => s.all(db).await,
@@ -625,6 +715,7 @@ where
             next_cursor,
             prev_cursor,
             limit,
+            total_count,
         },
     })
 }
@@ -664,46 +755,41 @@ where
         return Err(ODataError::InvalidCursor);
     }
 
-    // Parse all cursor values first
-    let mut cursor_values: Vec<(F, M::Column, sea_orm::Value, SortDir)> = Vec::new();
+    let is_backward = cursor.d == "bwd";
+
+    // Parse all cursor values first; backward walks every key in reverse
+    let mut cursor_values = Vec::new();
     for (i, key_str) in cursor.k.iter().enumerate() {
         let order_key = &order.0[i];
         let field = F::from_name(&order_key.field)
             .ok_or(ODataError::InvalidOrderByField(order_key.field.clone()))?;
-        let column = M::map_field(field);
-        let kind = field.kind();
-        let value = parse_cursor_value(kind, key_str).map_err(|_| ODataError::InvalidCursor)?;
-        cursor_values.push((field, column, value, order_key.dir));
+        let value = key_str
+            .as_deref()
+            .map(|s| parse_cursor_value(field.kind(), s))
+            .transpose()
+            .map_err(|_| ODataError::InvalidCursor)?;
+        let dir = if is_backward {
+            order_key.dir.reverse()
+        } else {
+            order_key.dir
+        };
+        let nulls = dir.nulls(M::nulls_order(field));
+        cursor_values.push((M::map_field(field), value, dir, nulls));
     }
 
-    let is_backward = cursor.d == "bwd";
     let mut main_condition = Condition::any();
 
     for i in 0..cursor_values.len() {
         let mut prefix_condition = Condition::all();
 
         // Add equality conditions for all previous fields
-        for (_field, column, value, _dir) in cursor_values.iter().take(i) {
-            prefix_condition = prefix_condition.add(Expr::col(*column).eq(value.clone()));
+        for (column, value, _, _) in cursor_values.iter().take(i) {
+            prefix_condition = prefix_condition.add(key_equals(*column, value.as_ref()));
         }
 
         // Add comparison for current field
-        let (_field, column, value, dir) = &cursor_values[i];
-        let comparison = if is_backward {
-            // Backward: reverse the comparison
-            match dir {
-                SortDir::Asc => Expr::col(*column).lt(value.clone()),
-                SortDir::Desc => Expr::col(*column).gt(value.clone()),
-            }
-        } else {
-            // Forward: normal comparison
-            match dir {
-                SortDir::Asc => Expr::col(*column).gt(value.clone()),
-                SortDir::Desc => Expr::col(*column).lt(value.clone()),
-            }
-        };
-
-        prefix_condition = prefix_condition.add(comparison);
+        let (column, value, dir, nulls) = &cursor_values[i];
+        prefix_condition = prefix_condition.add(key_after(*column, value.as_ref(), *dir, *nulls));
         main_condition = main_condition.add(prefix_condition);
     }
 
@@ -726,8 +812,11 @@ where
 
     let mut cursor_keys = Vec::new();
     for (field, value) in field_values {
-        let kind = field.kind();
-        let key_str = encode_cursor_value(&value, kind).map_err(|_| ODataError::InvalidCursor)?;
+        let key_str = if is_null(&value) {
+            None
+        } else {
+            Some(encode_cursor_value(&value, field.kind()).map_err(|_| ODataError::InvalidCursor)?)
+        };
         cursor_keys.push(key_str);
     }
 
@@ -4,6 +4,61 @@ use sea_orm::{ColumnTrait, Condition, EntityTrait, sea_query::Expr};
 use crate::secure::{AccessScope, ScopableEntity};
 use modkit_security::access_scope::{ScopeConstraint, ScopeFilter, ScopeValue, rg_tables};
 
+/// `tracing` target of the audit events emitted whenever an
+/// [`AccessScope::root`] scope is applied to a statement.
+pub const ROOT_SCOPE_AUDIT_TARGET: &str = "modkit_db::audit::root_scope";
+
+/// Statement kind recorded in root-scope audit events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScopedOperation {
+    Select,
+    Insert,
+    Update,
+    Delete,
+}
+
+impl ScopedOperation {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Select => "select",
+            Self::Insert => "insert",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// Emit an audit event if `scope` is a root scope; no-op otherwise.
+///
+/// Reads are recorded at `info`, writes at `warn`, both under
+/// [`ROOT_SCOPE_AUDIT_TARGET`] so they can be routed to an audit sink.
+pub fn audit_root_scope<E>(scope: &AccessScope, op: ScopedOperation)
+where
+    E: EntityTrait,
+{
+    if !scope.is_root() {
+        return;
+    }
+    let entity = E::default();
+    let entity = entity.table_name();
+    let operation = op.as_str();
+    if op == ScopedOperation::Select {
+        tracing::info!(
+            target: ROOT_SCOPE_AUDIT_TARGET,
+            entity,
+            operation,
+            "root scope bypassed row-level security"
+        );
+    } else {
+        tracing::warn!(
+            target: ROOT_SCOPE_AUDIT_TARGET,
+            entity,
+            operation,
+            "root scope bypassed row-level security"
+        );
+    }
+}
+
 /// Convert a [`ScopeValue`] to a `sea_query::SimpleExpr` for SQL binding.
 fn scope_value_to_sea_expr(v: &ScopeValue) -> sea_orm::sea_query::SimpleExpr {
     match v {
@@ -46,7 +101,7 @@ fn deny_all() -> Condition {
 /// | Scope | Behavior |
 /// |-------|----------|
 /// | deny-all (default) | `WHERE false` |
-/// | unconstrained (allow-all) | No filtering (`WHERE true`) |
+/// | unconstrained (allow-all, root) | No filtering (`WHERE true`) |
 /// | single constraint | AND of resolved filters |
 /// | multiple constraints | OR of ANDed filter groups |
 pub fn build_scope_condition<E>(scope: &AccessScope) -> Condition
@@ -80,6 +135,23 @@ where
     }
 }
 
+/// ANDs the access scope with a caller-supplied filter (e.g. an `OData` `$filter`).
+///
+/// The filter is nested as its own group, so any top-level `OR` it contains is
+/// rendered inside parentheses: `(<scope>) AND (<filter>)`. A crafted filter
+/// such as `tenant_id ne X or 1 eq 1` can therefore only narrow the rows the
+/// scope allows, never widen them.
+#[must_use]
+pub fn scope_and_filter<E>(scope: &AccessScope, filter: Condition) -> Condition
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+{
+    Condition::all()
+        .add(build_scope_condition::<E>(scope))
+        .add(Condition::all().add(filter))
+}
+
 /// Build SQL for a single constraint (AND of filters).
 ///
 /// Returns `None` if any filter references an unknown property (fail-closed).
@@ -237,6 +309,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_scope_and_filter_parenthesizes_injected_or() {
+        use sea_orm::{DbBackend, QueryFilter, QueryTrait};
+
+        let tenant = uuid::Uuid::new_v4();
+        let other = uuid::Uuid::new_v4();
+        let scope = AccessScope::for_tenant(tenant);
+
+        // `tenant_id ne <other> or 1 eq 1`
+        let injected = Condition::any()
+            .add(custom_prop_entity::Column::TenantId.ne(other))
+            .add(Expr::value(1).eq(1));
+
+        let sql = custom_prop_entity::Entity::find()
+            .filter(scope_and_filter::<custom_prop_entity::Entity>(
+                &scope, injected,
+            ))
+            .build(DbBackend::Sqlite)
+            .to_string();
+
+        let where_clause = sql.split_once(" WHERE ").unwrap().1;
+        assert!(
+            where_clause.starts_with(&format!(
+                "\"custom_prop_test\".\"tenant_id\" IN ('{tenant}')"
+            )),
+            "scope must come first: {where_clause}"
+        );
+        assert!(
+            where_clause.ends_with(&format!(
+                " AND (\"custom_prop_test\".\"tenant_id\" <> '{other}' OR 1 = 1)"
+            )),
+            "filter must be a parenthesized AND-ed group: {where_clause}"
+        );
+    }
+
+    #[test]
+    fn test_scope_and_filter_keeps_deny_all() {
+        use sea_orm::{DbBackend, QueryFilter, QueryTrait};
+
+        let sql = custom_prop_entity::Entity::find()
+            .filter(scope_and_filter::<custom_prop_entity::Entity>(
+                &AccessScope::default(),
+                Condition::any().add(Expr::value(1).eq(1)),
+            ))
+            .build(DbBackend::Sqlite)
+            .to_string();
+
+        assert!(sql.contains("WHERE FALSE AND 1 = 1"), "{sql}");
+    }
+
     #[test]
     fn test_custom_property_resolves() {
         let dept = uuid::Uuid::new_v4();
@@ -178,6 +178,22 @@ impl Db {
         })
     }
 
+    // --- Observability (forwarded, no `DbHandle` exposure) ---
+
+    /// Snapshot of the underlying connection pool usage.
+    #[must_use]
+    pub fn pool_stats(&self) -> crate::PoolStats {
+        self.handle.pool_stats()
+    }
+
+    /// Run `SELECT 1` with a timeout; suitable for readiness probes.
+    ///
+    /// # Errors
+    /// Returns `DbError::HealthCheckTimeout` on timeout, or the driver error if the query fails.
+    pub async fn health_check(&self, timeout: std::time::Duration) -> Result<(), DbError> {
+        self.handle.health_check(timeout).await
+    }
+
     // --- Advisory locks (forwarded, no `DbHandle` exposure) ---
 
     /// Acquire an advisory lock with the given key and module namespace.
@@ -5,7 +5,7 @@ use sea_orm::{
 };
 use std::marker::PhantomData;
 
-use crate::secure::cond::build_scope_condition;
+use crate::secure::cond::{ScopedOperation, audit_root_scope, build_scope_condition};
 use crate::secure::error::ScopeError;
 use crate::secure::{
     AccessScope, DBRunner, DBRunnerInternal, ScopableEntity, Scoped, SeaOrmRunner, SecureEntityExt,
@@ -65,6 +65,7 @@ where
     A::Entity: ScopableEntity + EntityTrait,
     <A::Entity as EntityTrait>::Column: ColumnTrait + Copy,
 {
+    audit_root_scope::<A::Entity>(scope, ScopedOperation::Insert);
     if scope.is_unconstrained() || A::Entity::IS_UNRESTRICTED {
         return Ok(());
     }
@@ -223,9 +224,10 @@ where
     E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
     E::Model: sea_orm::IntoActiveModel<E::ActiveModel> + sea_orm::ModelTrait<Entity = E>,
 {
+    audit_root_scope::<E>(scope, ScopedOperation::Update);
     let existing = E::find()
         .secure()
-        .scope_with(scope)
+        .scope_without_audit(scope)
         .and_id(id)?
         .one(runner)
         .await?;
@@ -377,7 +379,7 @@ where
         self,
         scope: &AccessScope,
     ) -> Result<SecureInsertOne<A, Scoped>, ScopeError> {
-        let _ = scope;
+        audit_root_scope::<A::Entity>(scope, ScopedOperation::Insert);
         Ok(SecureInsertOne {
             inner: self.inner,
             _state: PhantomData,
@@ -737,6 +739,7 @@ where
     ///
     #[must_use]
     pub fn scope_with(self, scope: &AccessScope) -> SecureUpdateMany<E, Scoped> {
+        audit_root_scope::<E>(scope, ScopedOperation::Update);
         let cond = build_scope_condition::<E>(scope);
         SecureUpdateMany {
             inner: self.inner.filter(cond),
@@ -835,6 +838,7 @@ where
     ///
     #[must_use]
     pub fn scope_with(self, scope: &AccessScope) -> SecureDeleteMany<E, Scoped> {
+        audit_root_scope::<E>(scope, ScopedOperation::Delete);
         let cond = build_scope_condition::<E>(scope);
         SecureDeleteMany {
             inner: self.inner.filter(cond),
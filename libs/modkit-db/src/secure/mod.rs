@@ -99,6 +99,7 @@
 //! | Tenants only | Filter by tenant column |
 //! | Resources only | Filter by ID column |
 //! | Both | AND them together |
+//! | Root (`AccessScope::root()`) | No filtering; audited under [`ROOT_SCOPE_AUDIT_TARGET`] |
 //!
 //! See the [docs module](docs) for comprehensive examples and usage patterns.
 
@@ -114,6 +115,7 @@ pub mod provider;
 mod runner;
 mod secure_conn;
 mod select;
+mod sql_capture;
 mod tests;
 mod tx_config;
 mod tx_error;
@@ -146,11 +148,14 @@ pub use db::{Db, DbConn, DbTx};
 pub use tx_error::{InfraError, TxError};
 
 // Transaction configuration (no SeaORM types leaked)
-pub use tx_config::{TxAccessMode, TxConfig, TxIsolationLevel};
+pub use tx_config::{TxAccessMode, TxConfig, TxIsolationLevel, TxRetryConfig};
+
+// Scope/filter combination
+pub use cond::{ROOT_SCOPE_AUDIT_TARGET, scope_and_filter};
 
 // Select operations
 pub use select::{
-    Scoped, SecureEntityExt, SecureFindRelatedExt, SecureSelect, SecureSelectTwo,
+    Scoped, ScopedLookup, SecureEntityExt, SecureFindRelatedExt, SecureSelect, SecureSelectTwo,
     SecureSelectTwoMany, Unscoped,
 };
 
@@ -161,6 +166,11 @@ pub use db_ops::{
     validate_tenant_in_scope,
 };
 
+// Debug-only SQL capture for secure selects
+#[cfg(debug_assertions)]
+pub use sql_capture::capture_sql;
+pub(crate) use sql_capture::record as record_sql;
+
 // Provider pattern for advanced tenant filtering
 pub use provider::{SimpleTenantFilter, TenantFilterProvider};
 
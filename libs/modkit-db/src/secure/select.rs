@@ -1,12 +1,16 @@
 use sea_orm::{
     ColumnTrait, EntityTrait, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
-    Related, sea_query::Expr,
+    QueryTrait, Related, sea_query::Expr,
 };
 use std::sync::Arc;
 
-use crate::secure::cond::build_scope_condition;
+use crate::secure::cond::{
+    ScopedOperation, audit_root_scope, build_scope_condition, scope_and_filter,
+};
 use crate::secure::error::ScopeError;
-use crate::secure::{AccessScope, DBRunner, DBRunnerInternal, ScopableEntity, SeaOrmRunner};
+use crate::secure::{
+    AccessScope, DBRunner, DBRunnerInternal, ScopableEntity, SeaOrmRunner, record_sql,
+};
 
 /// Typestate marker: query has not yet been scoped.
 /// Cannot execute queries in this state.
@@ -128,6 +132,37 @@ pub struct SecureSelectTwoMany<E: EntityTrait, F: EntityTrait, S> {
     pub(crate) state: S,
 }
 
+/// Outcome of [`SecureSelect::one_or_out_of_scope`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScopedLookup<T> {
+    /// A row matches the query within the scope.
+    Found(T),
+    /// A row matches the query, but only outside the scope.
+    OutOfScope,
+    /// No row matches the query at all.
+    NotFound,
+}
+
+impl<T> ScopedLookup<T> {
+    /// The in-scope row, if any; out-of-scope and missing rows both yield `None`.
+    #[must_use]
+    pub fn found(self) -> Option<T> {
+        match self {
+            Self::Found(row) => Some(row),
+            Self::OutOfScope | Self::NotFound => None,
+        }
+    }
+
+    /// Map the in-scope row, e.g. from an entity model to a domain type.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ScopedLookup<U> {
+        match self {
+            Self::Found(row) => ScopedLookup::Found(f(row)),
+            Self::OutOfScope => ScopedLookup::OutOfScope,
+            Self::NotFound => ScopedLookup::NotFound,
+        }
+    }
+}
+
 /// Extension trait to convert a regular `SeaORM` `Select` into a `SecureSelect`.
 pub trait SecureEntityExt<E: EntityTrait>: Sized {
     /// Convert this select query into a secure (unscoped) select.
@@ -165,6 +200,13 @@ where
     /// - Both → AND them together
     ///
     pub fn scope_with(self, scope: &AccessScope) -> SecureSelect<E, Scoped> {
+        audit_root_scope::<E>(scope, ScopedOperation::Select);
+        self.scope_without_audit(scope)
+    }
+
+    /// [`Self::scope_with`] without the root-scope audit event, for reads
+    /// that are part of a write the caller already audits.
+    pub(crate) fn scope_without_audit(self, scope: &AccessScope) -> SecureSelect<E, Scoped> {
         let cond = build_scope_condition::<E>(scope);
         SecureSelect {
             inner: self.inner.filter(cond),
@@ -174,11 +216,76 @@ where
         }
     }
 
+    /// Apply access control scope together with a caller-supplied filter.
+    ///
+    /// Equivalent to `.scope_with(scope).filter(filter)`, but built through
+    /// [`scope_and_filter`] so the filter is always a parenthesized group
+    /// combined with the scope by `AND` and cannot widen it.
+    pub fn scope_with_filter(
+        self,
+        scope: &AccessScope,
+        filter: sea_orm::Condition,
+    ) -> SecureSelect<E, Scoped> {
+        audit_root_scope::<E>(scope, ScopedOperation::Select);
+        SecureSelect {
+            inner: self.inner.filter(scope_and_filter::<E>(scope, filter)),
+            state: Scoped {
+                scope: Arc::new(scope.clone()),
+            },
+        }
+    }
+
+    /// Fetch at most one row within `scope`, telling a row outside the scope
+    /// apart from a missing one.
+    ///
+    /// The scoped query runs first. Only when it matches nothing is the query
+    /// run again without the scope, as a bare existence check whose row is
+    /// discarded. Filters must therefore be applied before `.secure()`, since
+    /// only those take part in the existence check.
+    ///
+    /// This reveals whether a row exists outside the caller's scope, so use it
+    /// only where that is acceptable, e.g. to answer 403 instead of 404.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let lookup = address::Entity::find()
+    ///     .filter(address::Column::Id.eq(id))
+    ///     .secure()
+    ///     .one_or_out_of_scope(&scope, conn)
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `ScopeError::Db` if either database query fails.
+    #[allow(clippy::disallowed_methods)]
+    pub async fn one_or_out_of_scope(
+        self,
+        scope: &AccessScope,
+        runner: &impl DBRunner,
+    ) -> Result<ScopedLookup<E::Model>, ScopeError> {
+        let unscoped = self.inner.clone();
+        if let Some(row) = self.scope_with(scope).one(runner).await? {
+            return Ok(ScopedLookup::Found(row));
+        }
+
+        let exists = match DBRunnerInternal::as_seaorm(runner) {
+            SeaOrmRunner::Conn(db) => unscoped.one(db).await?,
+            SeaOrmRunner::Tx(tx) => unscoped.one(tx).await?,
+        }
+        .is_some();
+        Ok(if exists {
+            ScopedLookup::OutOfScope
+        } else {
+            ScopedLookup::NotFound
+        })
+    }
+
     /// Apply access control scope using an `Arc<AccessScope>`.
     ///
     /// This is useful when you already have the scope in an `Arc` and want to
     /// avoid an extra clone.
     pub fn scope_with_arc(self, scope: Arc<AccessScope>) -> SecureSelect<E, Scoped> {
+        audit_root_scope::<E>(&scope, ScopedOperation::Select);
         let cond = build_scope_condition::<E>(&scope);
         SecureSelect {
             inner: self.inner.filter(cond),
@@ -198,6 +305,7 @@ where
     /// Returns `ScopeError::Db` if the database query fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn all(self, runner: &impl DBRunner) -> Result<Vec<E::Model>, ScopeError> {
+        record_sql(runner, |backend| self.inner.build(backend));
         match DBRunnerInternal::as_seaorm(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.all(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.all(tx).await?),
@@ -210,6 +318,7 @@ where
     /// Returns `ScopeError::Db` if the database query fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn one(self, runner: &impl DBRunner) -> Result<Option<E::Model>, ScopeError> {
+        record_sql(runner, |backend| self.inner.build(backend));
         match DBRunnerInternal::as_seaorm(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.one(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.one(tx).await?),
@@ -225,6 +334,7 @@ where
     where
         E::Model: sea_orm::FromQueryResult + Send + Sync,
     {
+        record_sql(runner, |backend| self.inner.build(backend));
         match DBRunnerInternal::as_seaorm(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.count(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.count(tx).await?),
@@ -399,6 +509,7 @@ where
         C: DBRunner,
         F: FnOnce(sea_orm::Select<E>) -> sea_orm::Selector<sea_orm::SelectModel<T>>,
     {
+        record_sql(runner, |backend| self.inner.build(backend));
         let selector = project(self.inner);
         match DBRunnerInternal::as_seaorm(runner) {
             SeaOrmRunner::Conn(db) => Ok(selector.all(db).await?),
@@ -616,6 +727,7 @@ where
         self,
         runner: &impl DBRunner,
     ) -> Result<Vec<(E::Model, Option<F::Model>)>, ScopeError> {
+        record_sql(runner, |backend| self.inner.build(backend));
         match DBRunnerInternal::as_seaorm(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.all(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.all(tx).await?),
@@ -631,6 +743,7 @@ where
         self,
         runner: &impl DBRunner,
     ) -> Result<Option<(E::Model, Option<F::Model>)>, ScopeError> {
+        record_sql(runner, |backend| self.inner.build(backend));
         match DBRunnerInternal::as_seaorm(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.one(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.one(tx).await?),
@@ -697,6 +810,7 @@ where
         self,
         runner: &impl DBRunner,
     ) -> Result<Vec<(E::Model, Vec<F::Model>)>, ScopeError> {
+        record_sql(runner, |backend| self.inner.build(backend));
         match DBRunnerInternal::as_seaorm(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.all(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.all(tx).await?),
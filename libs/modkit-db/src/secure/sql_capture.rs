@@ -0,0 +1,55 @@
+//! Debug-only capture of the SQL generated by secure selects.
+//!
+//! Lets tests and local debugging check that the scope predicate actually
+//! reaches the database, e.g. by asserting on the captured `WHERE` clause or
+//! running `EXPLAIN` on it by hand. Capturing is opt-in per task and compiled
+//! out of release builds.
+//!
+//! ```rust,ignore
+//! let captured = Arc::new(Mutex::new(Vec::new()));
+//! let sink = Arc::clone(&captured);
+//! let users = capture_sql(
+//!     move |sql| sink.lock().unwrap().push(sql.to_owned()),
+//!     User::find().secure().scope_with(&scope).all(&conn),
+//! )
+//! .await?;
+//! assert!(captured.lock().unwrap()[0].contains(r#""tenant_id" IN"#));
+//! ```
+
+use sea_orm::{DbBackend, Statement};
+
+use super::runner::DBRunnerInternal;
+
+#[cfg(debug_assertions)]
+tokio::task_local! {
+    static SQL_SINK: std::sync::Arc<dyn Fn(&str) + Send + Sync>;
+}
+
+/// Run `fut`, passing the SQL of every secure select it executes to `sink`.
+///
+/// Statements are rendered for the connection's backend with bind values
+/// inlined. A count is captured as the select it counts, and a projection as
+/// the select it projects. Only queries awaited
+/// by `fut` itself are seen; tasks it spawns are not.
+#[cfg(debug_assertions)]
+pub async fn capture_sql<F, S>(sink: S, fut: F) -> F::Output
+where
+    F: Future,
+    S: Fn(&str) + Send + Sync + 'static,
+{
+    SQL_SINK.scope(std::sync::Arc::new(sink), fut).await
+}
+
+/// Hand the statement from `build` to the current [`capture_sql`] sink, if any.
+///
+/// `build` only runs while capturing.
+pub fn record(runner: &impl DBRunnerInternal, build: impl FnOnce(DbBackend) -> Statement) {
+    #[cfg(debug_assertions)]
+    {
+        _ = SQL_SINK.try_with(|sink| sink(&build(runner.as_seaorm().backend()).to_string()));
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        _ = (runner, build);
+    }
+}
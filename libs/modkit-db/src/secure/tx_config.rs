@@ -37,6 +37,8 @@
 //! }
 //! ```
 
+use std::time::Duration;
+
 /// Transaction isolation level.
 ///
 /// Controls how transaction integrity is maintained when multiple transactions
@@ -151,6 +153,52 @@ impl TxConfig {
     }
 }
 
+/// Retry policy for [`DBProvider::transaction_with_retry`](crate::DBProvider::transaction_with_retry).
+///
+/// The whole transaction is re-run from `BEGIN` after a retryable failure, waiting
+/// `initial_backoff * 2^attempt` (capped at `max_backoff`) between attempts.
+///
+/// # Example
+///
+/// ```ignore
+/// use std::time::Duration;
+/// use modkit_db::secure::TxRetryConfig;
+///
+/// let retry = TxRetryConfig {
+///     max_retries: 5,
+///     ..TxRetryConfig::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxRetryConfig {
+    /// Retries after the first attempt; `0` disables retrying.
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between retries.
+    pub max_backoff: Duration,
+}
+
+impl Default for TxRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+impl TxRetryConfig {
+    /// Delay to wait before retry number `attempt` (zero-based).
+    #[must_use]
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+}
+
 // ============================================================================
 // SeaORM conversions (internal to modkit-db)
 // ============================================================================
@@ -209,6 +257,20 @@ mod tests {
         assert!(cfg.access_mode.is_none());
     }
 
+    #[test]
+    fn test_tx_retry_backoff_is_exponential_and_capped() {
+        let retry = TxRetryConfig {
+            max_retries: 10,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(50),
+        };
+        assert_eq!(retry.backoff(0), Duration::from_millis(10));
+        assert_eq!(retry.backoff(1), Duration::from_millis(20));
+        assert_eq!(retry.backoff(2), Duration::from_millis(40));
+        assert_eq!(retry.backoff(3), Duration::from_millis(50));
+        assert_eq!(retry.backoff(40), Duration::from_millis(50));
+    }
+
     #[test]
     fn test_isolation_level_conversion() {
         assert!(matches!(
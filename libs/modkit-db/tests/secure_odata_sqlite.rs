@@ -13,8 +13,8 @@ use modkit_db::odata::FieldMap;
 use modkit_db::odata::pager::OPager;
 use modkit_db::secure::{Db, DbConn, ScopableEntity, secure_insert};
 use modkit_db::{ConnectOpts, connect_db};
-use modkit_odata::ODataQuery;
 use modkit_odata::filter::FieldKind;
+use modkit_odata::{CursorV1, NullsOrder, ODataOrderBy, ODataQuery, OrderKey, SortDir};
 use modkit_security::{AccessScope, pep_properties};
 use sea_orm::Set;
 use sea_orm::entity::prelude::*;
@@ -33,6 +33,7 @@ mod ent {
         pub tenant_id: Uuid,
         pub name: String,
         pub score: i64,
+        pub nickname: Option<String>,
     }
 
     #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -100,6 +101,7 @@ impl mig::MigrationTrait for CreateSecureOdataTest {
                             .big_integer()
                             .not_null(),
                     )
+                    .col(mig::ColumnDef::new(mig::Alias::new("nickname")).string())
                     .to_owned(),
             )
             .await
@@ -190,3 +192,420 @@ async fn paginate_odata_works_with_secure_conn() {
 
     assert_eq!(page.items.len(), 2, "page size");
 }
+
+fn fmap_with_tenant() -> FieldMap<ent::Entity> {
+    FieldMap::new()
+        .insert_with_extractor("id", ent::Column::Id, FieldKind::I64, |m: &ent::Model| {
+            m.id.to_string()
+        })
+        .insert("tenant_id", ent::Column::TenantId, FieldKind::Uuid)
+        .insert("name", ent::Column::Name, FieldKind::String)
+        .insert("score", ent::Column::Score, FieldKind::I64)
+}
+
+fn filtered(raw: &str) -> ODataQuery {
+    let parsed = modkit_odata::parse_filter_string(raw).expect("parse filter");
+    ODataQuery::default()
+        .with_filter(parsed.into_expr())
+        .with_limit(100)
+}
+
+#[tokio::test]
+async fn injected_or_filter_cannot_escape_tenant_scope() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+
+    let other_tenant = Uuid::new_v4();
+    seed(
+        &conn,
+        other_tenant,
+        &AccessScope::for_tenants(vec![other_tenant]),
+    )
+    .await;
+
+    let fmap = fmap_with_tenant();
+
+    // Without the scope/filter grouping this would match every other tenant's rows
+    let q = filtered(&format!("tenant_id ne {} or score ge 0", test_db.tenant_id));
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.tenant_id)
+        .await
+        .expect("fetch");
+    assert_eq!(page.items.len(), 4);
+    assert!(page.items.iter().all(|t| *t == test_db.tenant_id));
+
+    // Asking for the other tenant explicitly yields nothing
+    let q = filtered(&format!(
+        "tenant_id eq {other_tenant} or tenant_id ne {other_tenant}"
+    ));
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.tenant_id)
+        .await
+        .expect("fetch");
+    assert!(page.items.iter().all(|t| *t == test_db.tenant_id));
+
+    // Literal tautologies are not valid filters at all
+    let q = filtered(&format!("tenant_id ne {other_tenant} or 1 eq 1"));
+    let err = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.tenant_id)
+        .await
+        .expect_err("literal comparison must be rejected");
+    assert!(
+        matches!(err, modkit_odata::Error::InvalidFilter(_)),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn scope_with_filter_keeps_tautology_inside_scope() {
+    use modkit_db::secure::SecureEntityExt;
+    use sea_orm::Condition;
+    use sea_orm::sea_query::Expr;
+
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+
+    let other_tenant = Uuid::new_v4();
+    seed(
+        &conn,
+        other_tenant,
+        &AccessScope::for_tenants(vec![other_tenant]),
+    )
+    .await;
+
+    // `tenant_id ne <own> or 1 eq 1`, built directly as a condition
+    let injected = Condition::any()
+        .add(ent::Column::TenantId.ne(test_db.tenant_id))
+        .add(Expr::value(1).eq(1));
+
+    let rows = ent::Entity::find()
+        .secure()
+        .scope_with_filter(&test_db.scope, injected)
+        .all(&conn)
+        .await
+        .expect("query");
+
+    assert_eq!(rows.len(), 4);
+    assert!(rows.iter().all(|r| r.tenant_id == test_db.tenant_id));
+}
+
+#[tokio::test]
+async fn one_or_out_of_scope_distinguishes_missing_rows() {
+    use modkit_db::secure::{ScopedLookup, SecureEntityExt};
+
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+
+    let other_tenant = Uuid::new_v4();
+    let other_scope = AccessScope::for_tenants(vec![other_tenant]);
+    let lookup = |name: &str| {
+        ent::Entity::find()
+            .filter(ent::Column::Name.eq(name.to_owned()))
+            .secure()
+    };
+
+    let found = lookup("alice")
+        .one_or_out_of_scope(&test_db.scope, &conn)
+        .await
+        .expect("query");
+    assert!(matches!(found, ScopedLookup::Found(ref m) if m.score == 10));
+
+    let out_of_scope = lookup("alice")
+        .one_or_out_of_scope(&other_scope, &conn)
+        .await
+        .expect("query");
+    assert_eq!(out_of_scope, ScopedLookup::OutOfScope);
+
+    let missing = lookup("mallory")
+        .one_or_out_of_scope(&test_db.scope, &conn)
+        .await
+        .expect("query");
+    assert_eq!(missing, ScopedLookup::NotFound);
+}
+
+fn ordered(keys: &[(&str, SortDir)]) -> ODataQuery {
+    ODataQuery::default()
+        .with_order(ODataOrderBy(
+            keys.iter()
+                .map(|(field, dir)| OrderKey {
+                    field: (*field).to_owned(),
+                    dir: *dir,
+                })
+                .collect(),
+        ))
+        .with_limit(100)
+}
+
+#[tokio::test]
+async fn multi_key_order_places_nulls_as_configured() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+
+    let rows = [
+        ("alice", Some("zed")),
+        ("bob", None),
+        ("charlie", Some("amy")),
+        ("dave", None),
+        ("erin", Some("amy")),
+    ];
+    for (name, nickname) in rows {
+        let am = ent::ActiveModel {
+            tenant_id: Set(test_db.tenant_id),
+            name: Set(name.to_owned()),
+            score: Set(0),
+            nickname: Set(nickname.map(str::to_owned)),
+            ..Default::default()
+        };
+        secure_insert::<ent::Entity>(am, &test_db.scope, &conn)
+            .await
+            .expect("insert");
+    }
+
+    let fmap = fmap_with_tenant().insert("nickname", ent::Column::Nickname, FieldKind::String);
+    let q = ordered(&[("nickname", SortDir::Asc), ("name", SortDir::Desc)]);
+
+    // Default: null sorts lowest, so NULLs come first ascending
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.name)
+        .await
+        .expect("fetch");
+    assert_eq!(page.items, ["dave", "bob", "erin", "charlie", "alice"]);
+
+    // Configured: NULLs after every value ascending...
+    let fmap = fmap.with_nulls("nickname", NullsOrder::Last);
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.name)
+        .await
+        .expect("fetch");
+    assert_eq!(page.items, ["erin", "charlie", "alice", "dave", "bob"]);
+
+    // ...and therefore before every value descending
+    let q = ordered(&[("nickname", SortDir::Desc), ("name", SortDir::Asc)]);
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.name)
+        .await
+        .expect("fetch");
+    assert_eq!(page.items, ["bob", "dave", "alice", "charlie", "erin"]);
+}
+
+// `capture_sql` is compiled out of release builds.
+#[cfg(debug_assertions)]
+#[tokio::test]
+async fn captured_sql_of_scoped_list_carries_tenant_predicate() {
+    use modkit_db::odata::LimitCfg;
+    use modkit_db::odata::sea_orm_filter::paginate_odata;
+    use modkit_db::secure::{SecureEntityExt, capture_sql};
+    use name_mapping::{NameField, NameMapper};
+    use std::sync::{Arc, Mutex};
+
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+
+    let captured = Arc::new(Mutex::new(Vec::<String>::new()));
+    let sink = Arc::clone(&captured);
+    let rows = capture_sql(
+        move |sql| sink.lock().unwrap().push(sql.to_owned()),
+        ent::Entity::find()
+            .secure()
+            .scope_with(&test_db.scope)
+            .all(&conn),
+    )
+    .await
+    .expect("list");
+    assert_eq!(rows.len(), 4);
+
+    let sql = captured.lock().unwrap().clone();
+    assert_eq!(sql.len(), 1, "{sql:?}");
+    assert!(
+        sql[0].contains(r#""secure_odata_test"."tenant_id" IN"#)
+            && sql[0].contains(&test_db.tenant_id.to_string()),
+        "tenant predicate missing: {}",
+        sql[0]
+    );
+
+    // Queries outside the capture scope are not seen
+    ent::Entity::find()
+        .secure()
+        .scope_with(&test_db.scope)
+        .all(&conn)
+        .await
+        .expect("list");
+    assert_eq!(captured.lock().unwrap().len(), 1);
+
+    // A paginated list with `$count` captures the count and the page query
+    captured.lock().unwrap().clear();
+    let q = ODataQuery::default().with_limit(2).with_count(true);
+    let page = capture_sql(
+        {
+            let sink = Arc::clone(&captured);
+            move |sql| sink.lock().unwrap().push(sql.to_owned())
+        },
+        paginate_odata::<NameField, NameMapper, _, _, _, _>(
+            ent::Entity::find().secure().scope_with(&test_db.scope),
+            &conn,
+            &q,
+            ("id", SortDir::Asc),
+            LimitCfg {
+                default: 25,
+                max: 100,
+            },
+            |m| m.name,
+        ),
+    )
+    .await
+    .expect("paginate");
+    assert_eq!(page.page_info.total_count, Some(4));
+
+    let sql = captured.lock().unwrap().clone();
+    assert_eq!(sql.len(), 2, "{sql:?}");
+    assert!(
+        !sql[0].contains("LIMIT") && sql[1].contains("LIMIT"),
+        "expected the count before the page: {sql:?}"
+    );
+    for statement in &sql {
+        assert!(
+            statement.contains(r#""secure_odata_test"."tenant_id" IN"#),
+            "tenant predicate missing: {statement}"
+        );
+    }
+}
+
+/// `paginate_odata` mapping for the test entity; only the capture test uses it.
+#[cfg(debug_assertions)]
+mod name_mapping {
+    use super::ent;
+    use modkit_db::odata::sea_orm_filter::{FieldToColumn, ODataFieldMapping};
+    use modkit_odata::filter::{FieldKind, FilterField};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum NameField {
+        Id,
+        Name,
+    }
+
+    impl FilterField for NameField {
+        const FIELDS: &'static [Self] = &[Self::Id, Self::Name];
+
+        fn name(&self) -> &'static str {
+            match self {
+                Self::Id => "id",
+                Self::Name => "name",
+            }
+        }
+
+        fn kind(&self) -> FieldKind {
+            match self {
+                Self::Id => FieldKind::I64,
+                Self::Name => FieldKind::String,
+            }
+        }
+    }
+
+    pub struct NameMapper;
+
+    impl FieldToColumn<NameField> for NameMapper {
+        type Column = ent::Column;
+
+        fn map_field(field: NameField) -> ent::Column {
+            match field {
+                NameField::Id => ent::Column::Id,
+                NameField::Name => ent::Column::Name,
+            }
+        }
+    }
+
+    impl ODataFieldMapping<NameField> for NameMapper {
+        type Entity = ent::Entity;
+
+        fn extract_cursor_value(model: &ent::Model, field: NameField) -> sea_orm::Value {
+            match field {
+                NameField::Id => sea_orm::Value::BigInt(Some(model.id)),
+                NameField::Name => sea_orm::Value::String(Some(Box::new(model.name.clone()))),
+            }
+        }
+    }
+}
+
+/// Follow `next_cursor` from the first page, then `prev_cursor` back from the
+/// last, returning the items seen in each direction in display order.
+async fn walk_pages(
+    test_db: &TestDb,
+    fmap: &FieldMap<ent::Entity>,
+    q: &ODataQuery,
+) -> (Vec<String>, Vec<String>) {
+    let conn = test_db.conn();
+    let fetch = |q: ODataQuery| {
+        let conn = &conn;
+        async move {
+            OPager::<ent::Entity, _>::new(&test_db.scope, conn, fmap)
+                .fetch(&q, |m| m.name)
+                .await
+                .expect("fetch")
+        }
+    };
+
+    let mut forward = Vec::new();
+    let mut page = fetch(q.clone()).await;
+    loop {
+        forward.extend(page.items.iter().cloned());
+        let Some(next) = page.page_info.next_cursor.as_deref() else {
+            break;
+        };
+        page = fetch(q.clone().with_cursor(CursorV1::decode(next).unwrap())).await;
+    }
+
+    let mut backward = page.items.clone();
+    while let Some(prev) = page.page_info.prev_cursor.as_deref() {
+        page = fetch(q.clone().with_cursor(CursorV1::decode(prev).unwrap())).await;
+        backward.splice(0..0, page.items.iter().cloned());
+    }
+
+    (forward, backward)
+}
+
+#[tokio::test]
+async fn keyset_pages_walk_across_null_keys() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+
+    for (name, nickname) in [
+        ("alice", Some("zed")),
+        ("bob", None),
+        ("charlie", Some("amy")),
+        ("dave", None),
+        ("erin", Some("amy")),
+    ] {
+        let am = ent::ActiveModel {
+            tenant_id: Set(test_db.tenant_id),
+            name: Set(name.to_owned()),
+            score: Set(0),
+            nickname: Set(nickname.map(str::to_owned)),
+            ..Default::default()
+        };
+        secure_insert::<ent::Entity>(am, &test_db.scope, &conn)
+            .await
+            .expect("insert");
+    }
+
+    let fmap = fmap_with_tenant().insert_with_nullable_extractor(
+        "nickname",
+        ent::Column::Nickname,
+        FieldKind::String,
+        |m: &ent::Model| m.nickname.clone(),
+    );
+    // Ties on nickname fall back to the default `id desc` tiebreaker
+    let q = ordered(&[("nickname", SortDir::Asc)]).with_limit(2);
+
+    let (forward, backward) = walk_pages(&test_db, &fmap, &q).await;
+    assert_eq!(forward, ["dave", "bob", "erin", "charlie", "alice"]);
+    assert_eq!(backward, forward);
+
+    let fmap = fmap.with_nulls("nickname", NullsOrder::Last);
+    let (forward, backward) = walk_pages(&test_db, &fmap, &q).await;
+    assert_eq!(forward, ["erin", "charlie", "alice", "dave", "bob"]);
+    assert_eq!(backward, forward);
+}
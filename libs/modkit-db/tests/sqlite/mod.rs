@@ -8,6 +8,8 @@
 mod concurrency_tests;
 mod manager;
 mod options;
+mod pool_health;
+mod root_scope;
 mod pooling_tests;
 mod secure_insert_tenant_validation;
 mod secure_select_project_all;
@@ -15,3 +17,4 @@ mod secure_update_tenant_safety;
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod sqlite_tests;
 mod transaction;
+mod tx_retry;
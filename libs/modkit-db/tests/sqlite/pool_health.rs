@@ -0,0 +1,47 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Pool observability tests: `pool_stats()` and `health_check()`.
+
+use std::time::Duration;
+
+use modkit_db::{ConnectOpts, DBProvider, DbError, connect_db};
+
+async fn provider() -> DBProvider<DbError> {
+    let db = connect_db("sqlite::memory:", ConnectOpts::default())
+        .await
+        .expect("connect sqlite memory");
+    DBProvider::new(db)
+}
+
+#[tokio::test]
+async fn health_check_succeeds_on_live_db() {
+    let db = provider().await;
+
+    db.health_check(Duration::from_secs(5))
+        .await
+        .expect("health check should pass on a live in-memory DB");
+}
+
+#[tokio::test]
+async fn pool_stats_reflect_open_transaction() {
+    let db = provider().await;
+    db.health_check(Duration::from_secs(5)).await.unwrap();
+
+    // Connections are returned to the pool asynchronously, so only the
+    // invariants are checked outside the transaction.
+    let before = db.pool_stats();
+    assert!(before.size >= 1, "pool should have an open connection");
+    assert!(before.size <= before.max_size);
+    assert_eq!(before.idle + before.in_use, before.size);
+
+    let probe = db.clone();
+    let during = db
+        .transaction(move |_tx| Box::pin(async move { Ok(probe.pool_stats()) }))
+        .await
+        .unwrap();
+    assert!(
+        during.in_use >= 1,
+        "open transaction should hold a connection: {during:?}"
+    );
+    assert_eq!(during.idle + during.in_use, during.size);
+}
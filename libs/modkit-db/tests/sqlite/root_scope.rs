@@ -0,0 +1,308 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Tests for the privileged `AccessScope::root()` scope.
+//!
+//! Root bypasses row-level filtering but every use must leave an audit event
+//! under `ROOT_SCOPE_AUDIT_TARGET` (reads at INFO, writes at WARN).
+
+use std::sync::{Arc, Mutex};
+
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::secure::{
+    Db, ROOT_SCOPE_AUDIT_TARGET, ScopableEntity, SecureDeleteExt, SecureEntityExt, pep_properties,
+    secure_insert, secure_update_with_scope,
+};
+use modkit_db::{ConnectOpts, connect_db};
+use modkit_security::AccessScope;
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm_migration::prelude as mig;
+use tracing_subscriber::layer::SubscriberExt;
+use uuid::Uuid;
+
+mod ent {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "root_scope_test")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub tenant_id: Uuid,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl ScopableEntity for ent::Entity {
+    fn tenant_col() -> Option<<Self as EntityTrait>::Column> {
+        Some(ent::Column::TenantId)
+    }
+
+    fn resource_col() -> Option<<Self as EntityTrait>::Column> {
+        Some(ent::Column::Id)
+    }
+
+    fn owner_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+
+    fn type_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+
+    fn resolve_property(property: &str) -> Option<<Self as EntityTrait>::Column> {
+        match property {
+            p if p == pep_properties::OWNER_TENANT_ID => Self::tenant_col(),
+            p if p == pep_properties::RESOURCE_ID => Self::resource_col(),
+            _ => None,
+        }
+    }
+}
+
+struct CreateRootScopeTest;
+
+impl mig::MigrationName for CreateRootScopeTest {
+    fn name(&self) -> &'static str {
+        "m001_create_root_scope_test"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateRootScopeTest {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("root_scope_test"))
+                    .if_not_exists()
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("id"))
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tenant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("root_scope_test"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Audit event fields captured from `ROOT_SCOPE_AUDIT_TARGET`.
+#[derive(Debug, Clone)]
+struct AuditEvent {
+    level: tracing::Level,
+    entity: String,
+    operation: String,
+}
+
+/// A tracing layer that captures root-scope audit events.
+#[derive(Clone, Default)]
+struct AuditCapture {
+    events: Arc<Mutex<Vec<AuditEvent>>>,
+}
+
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for AuditCapture {
+    fn on_event(
+        &self,
+        event: &tracing::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        if event.metadata().target() != ROOT_SCOPE_AUDIT_TARGET {
+            return;
+        }
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        self.events.lock().unwrap().push(AuditEvent {
+            level: *event.metadata().level(),
+            entity: visitor.entity,
+            operation: visitor.operation,
+        });
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    entity: String,
+    operation: String,
+}
+
+impl tracing::field::Visit for FieldVisitor {
+    fn record_debug(&mut self, _field: &tracing::field::Field, _value: &dyn std::fmt::Debug) {}
+
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        match field.name() {
+            "entity" => value.clone_into(&mut self.entity),
+            "operation" => value.clone_into(&mut self.operation),
+            _ => {}
+        }
+    }
+}
+
+async fn setup(name: &str) -> Db {
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        ..Default::default()
+    };
+    let dsn = format!("sqlite:file:{name}?mode=memory&cache=shared");
+    let db = connect_db(&dsn, opts).await.expect("connect");
+    run_migrations_for_testing(&db, vec![Box::new(CreateRootScopeTest)])
+        .await
+        .expect("migrate");
+    db
+}
+
+async fn seed(db: &Db, tenant_id: Uuid) {
+    let conn = db.conn().unwrap();
+    let am = ent::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        tenant_id: Set(tenant_id),
+    };
+    secure_insert::<ent::Entity>(am, &AccessScope::for_tenant(tenant_id), &conn)
+        .await
+        .expect("seed");
+}
+
+#[tokio::test]
+async fn root_scope_reads_across_tenants_and_is_audited() {
+    let db = setup("memdb_root_scope_read").await;
+    let (t1, t2) = (Uuid::new_v4(), Uuid::new_v4());
+    seed(&db, t1).await;
+    seed(&db, t2).await;
+
+    let capture = AuditCapture::default();
+    let events = Arc::clone(&capture.events);
+    let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(capture));
+
+    let conn = db.conn().unwrap();
+    let tenant_rows = ent::Entity::find()
+        .secure()
+        .scope_with(&AccessScope::for_tenant(t1))
+        .all(&conn)
+        .await
+        .unwrap();
+    assert_eq!(tenant_rows.len(), 1);
+    assert!(
+        events.lock().unwrap().is_empty(),
+        "regular scopes must not emit root audit events"
+    );
+
+    let rows = ent::Entity::find()
+        .secure()
+        .scope_with(&AccessScope::root())
+        .all(&conn)
+        .await
+        .unwrap();
+    let mut tenants: Vec<Uuid> = rows.iter().map(|r| r.tenant_id).collect();
+    tenants.sort_unstable();
+    let mut expected = vec![t1, t2];
+    expected.sort_unstable();
+    assert_eq!(tenants, expected, "root scope must see all tenants");
+
+    let events = events.lock().unwrap().clone();
+    assert_eq!(events.len(), 1, "{events:?}");
+    assert_eq!(events[0].level, tracing::Level::INFO);
+    assert_eq!(events[0].entity, "root_scope_test");
+    assert_eq!(events[0].operation, "select");
+}
+
+#[tokio::test]
+async fn root_scope_writes_are_audited_at_warn() {
+    let db = setup("memdb_root_scope_write").await;
+    let (t1, t2) = (Uuid::new_v4(), Uuid::new_v4());
+    seed(&db, t1).await;
+
+    let capture = AuditCapture::default();
+    let events = Arc::clone(&capture.events);
+    let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(capture));
+
+    let conn = db.conn().unwrap();
+    let am = ent::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        tenant_id: Set(t2),
+    };
+    secure_insert::<ent::Entity>(am, &AccessScope::root(), &conn)
+        .await
+        .expect("root insert into any tenant");
+
+    let deleted = ent::Entity::delete_many()
+        .secure()
+        .scope_with(&AccessScope::root())
+        .exec(&conn)
+        .await
+        .unwrap();
+    assert_eq!(deleted.rows_affected, 2);
+
+    let events = events.lock().unwrap().clone();
+    let ops: Vec<&str> = events.iter().map(|e| e.operation.as_str()).collect();
+    assert_eq!(ops, ["insert", "delete"]);
+    assert!(
+        events.iter().all(|e| e.level == tracing::Level::WARN),
+        "{events:?}"
+    );
+}
+
+#[tokio::test]
+async fn root_scope_update_is_audited_once() {
+    let db = setup("memdb_root_scope_update").await;
+    let (t1, t2) = (Uuid::new_v4(), Uuid::new_v4());
+    let id = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+    let am = ent::ActiveModel {
+        id: Set(id),
+        tenant_id: Set(t1),
+    };
+    secure_insert::<ent::Entity>(am, &AccessScope::for_tenant(t1), &conn)
+        .await
+        .expect("seed");
+
+    let capture = AuditCapture::default();
+    let events = Arc::clone(&capture.events);
+    let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(capture));
+
+    let am = ent::ActiveModel {
+        id: sea_orm::ActiveValue::Unchanged(id),
+        tenant_id: Set(t1),
+    };
+    secure_update_with_scope::<ent::Entity>(am, &AccessScope::root(), id, &conn)
+        .await
+        .expect("root update of another tenant's row");
+
+    let denied = secure_update_with_scope::<ent::Entity>(
+        ent::ActiveModel {
+            id: sea_orm::ActiveValue::Unchanged(id),
+            tenant_id: Set(t2),
+        },
+        &AccessScope::root(),
+        id,
+        &conn,
+    )
+    .await;
+    assert!(denied.is_err(), "tenant_id stays immutable under root");
+
+    let events = events.lock().unwrap().clone();
+    let ops: Vec<&str> = events.iter().map(|e| e.operation.as_str()).collect();
+    assert_eq!(ops, ["update", "update"], "{events:?}");
+    assert!(
+        events.iter().all(|e| e.level == tracing::Level::WARN),
+        "{events:?}"
+    );
+}
@@ -0,0 +1,220 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Tests for `DBProvider::transaction_with_retry`.
+//!
+//! Retryable failures are simulated from inside the closure; the unique
+//! violation is produced by a real insert through the secure API.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::secure::{ScopableEntity, TxConfig, TxRetryConfig, secure_insert};
+use modkit_db::{ConnectOpts, DBProvider, DbError, connect_db};
+use modkit_security::{AccessScope, pep_properties};
+use sea_orm::entity::prelude::*;
+use sea_orm::{RuntimeErr, Set};
+use sea_orm_migration::prelude as mig;
+use uuid::Uuid;
+
+mod ent {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "tx_retry_test")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i64,
+        pub tenant_id: Uuid,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl ScopableEntity for ent::Entity {
+    fn tenant_col() -> Option<<Self as EntityTrait>::Column> {
+        Some(ent::Column::TenantId)
+    }
+
+    fn resource_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+
+    fn owner_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+
+    fn type_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+
+    fn resolve_property(property: &str) -> Option<<Self as EntityTrait>::Column> {
+        (property == pep_properties::OWNER_TENANT_ID)
+            .then(Self::tenant_col)
+            .flatten()
+    }
+}
+
+struct CreateTxRetryTest;
+
+impl mig::MigrationName for CreateTxRetryTest {
+    fn name(&self) -> &'static str {
+        "m001_create_tx_retry_test"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateTxRetryTest {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("tx_retry_test"))
+                    .if_not_exists()
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("id"))
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tenant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("tx_retry_test"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+async fn provider(name: &str) -> DBProvider<DbError> {
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        ..Default::default()
+    };
+    let dsn = format!("sqlite:file:{name}?mode=memory&cache=shared");
+    let db = connect_db(&dsn, opts).await.expect("connect");
+    run_migrations_for_testing(&db, vec![Box::new(CreateTxRetryTest)])
+        .await
+        .expect("migrate");
+    DBProvider::new(db)
+}
+
+fn fast_retry() -> TxRetryConfig {
+    TxRetryConfig {
+        max_retries: 3,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(5),
+    }
+}
+
+fn serialization_failure() -> DbError {
+    DbError::Sea(DbErr::Exec(RuntimeErr::Internal(
+        "error returned from database: 40001: could not serialize access".to_owned(),
+    )))
+}
+
+#[tokio::test]
+async fn retries_serialization_failure_until_success() {
+    let db = provider("memdb_tx_retry_success").await;
+    let calls = Arc::new(AtomicU32::new(0));
+
+    let counter = Arc::clone(&calls);
+    let out = db
+        .transaction_with_retry(TxConfig::default(), fast_retry(), move |_tx| {
+            let counter = Arc::clone(&counter);
+            Box::pin(async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    return Err(serialization_failure());
+                }
+                Ok("done")
+            })
+        })
+        .await
+        .expect("third attempt should succeed");
+
+    assert_eq!(out, "done");
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn gives_up_after_max_retries() {
+    let db = provider("memdb_tx_retry_exhausted").await;
+    let calls = Arc::new(AtomicU32::new(0));
+
+    let counter = Arc::clone(&calls);
+    let err = db
+        .transaction_with_retry(TxConfig::default(), fast_retry(), move |_tx| {
+            let counter = Arc::clone(&counter);
+            Box::pin(async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Err::<(), _>(serialization_failure())
+            })
+        })
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, DbError::Sea(_)), "got: {err:?}");
+    assert_eq!(calls.load(Ordering::SeqCst), 4, "first attempt + 3 retries");
+}
+
+#[tokio::test]
+async fn unique_violation_is_not_retried() {
+    let db = provider("memdb_tx_retry_unique").await;
+    let tenant_id = Uuid::new_v4();
+    let scope = AccessScope::for_tenants(vec![tenant_id]);
+
+    let insert_scope = scope.clone();
+    db.transaction(move |tx| {
+        Box::pin(async move {
+            let am = ent::ActiveModel {
+                id: Set(1),
+                tenant_id: Set(tenant_id),
+            };
+            secure_insert::<ent::Entity>(am, &insert_scope, tx).await?;
+            Ok(())
+        })
+    })
+    .await
+    .expect("seed row");
+
+    let calls = Arc::new(AtomicU32::new(0));
+    let counter = Arc::clone(&calls);
+    let err = db
+        .transaction_with_retry(TxConfig::default(), fast_retry(), move |tx| {
+            let counter = Arc::clone(&counter);
+            let scope = scope.clone();
+            Box::pin(async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let am = ent::ActiveModel {
+                    id: Set(1),
+                    tenant_id: Set(tenant_id),
+                };
+                secure_insert::<ent::Entity>(am, &scope, tx).await?;
+                Ok(())
+            })
+        })
+        .await
+        .unwrap_err();
+
+    assert!(
+        err.to_string().to_lowercase().contains("unique"),
+        "expected unique violation, got: {err}"
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 1, "must fail on first attempt");
+}
@@ -0,0 +1,6 @@
+use modkit_macros::module;
+
+#[module(name = "x", deps = ["db"], optional_deps = ["db"])]
+pub struct X;
+
+fn main() {}
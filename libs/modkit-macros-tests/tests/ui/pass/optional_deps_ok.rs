@@ -0,0 +1,15 @@
+// Module that can run degraded when an optional dependency is not registered
+use modkit_macros::module;
+
+#[derive(Default)]
+#[module(name = "demo", deps = ["db"], optional_deps = ["types-registry"])]
+pub struct Demo;
+
+#[async_trait::async_trait]
+impl modkit::Module for Demo {
+    async fn init(&self, _ctx: &modkit::ModuleCtx) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+fn main() {}
@@ -19,6 +19,7 @@ mod utils;
 struct ModuleConfig {
     name: String,
     deps: Vec<String>,
+    optional_deps: Vec<String>,
     caps: Vec<Capability>,
     ctor: Option<Expr>,             // arbitrary constructor expression
     client: Option<Path>,           // trait path for client DX helpers
@@ -200,6 +201,7 @@ impl Parse for ModuleConfig {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut name: Option<String> = None;
         let mut deps: Vec<String> = Vec::new();
+        let mut optional_deps: Vec<String> = Vec::new();
         let mut caps: Vec<Capability> = Vec::new();
         let mut ctor: Option<Expr> = None;
         let mut client: Option<Path> = None;
@@ -207,6 +209,7 @@ impl Parse for ModuleConfig {
 
         let mut seen_name = false;
         let mut seen_deps = false;
+        let mut seen_optional_deps = false;
         let mut seen_caps = false;
         let mut seen_ctor = false;
         let mut seen_client = false;
@@ -299,32 +302,17 @@ impl Parse for ModuleConfig {
                         ));
                     }
                     seen_deps = true;
-                    let value = nv.value.clone();
-                    match value {
-                        Expr::Array(arr) => {
-                            for elem in arr.elems {
-                                match elem {
-                                    Expr::Lit(syn::ExprLit {
-                                        lit: Lit::Str(s), ..
-                                    }) => {
-                                        deps.push(s.value());
-                                    }
-                                    other => {
-                                        return Err(syn::Error::new_spanned(
-                                            other,
-                                            "deps must be an array of string literals, e.g. deps = [\"db\", \"auth\"]",
-                                        ));
-                                    }
-                                }
-                            }
-                        }
-                        other => {
-                            return Err(syn::Error::new_spanned(
-                                other,
-                                "deps must be an array, e.g. deps = [\"db\", \"auth\"]",
-                            ));
-                        }
+                    deps = parse_deps_array("deps", nv.value)?;
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("optional_deps") => {
+                    if seen_optional_deps {
+                        return Err(syn::Error::new_spanned(
+                            nv.path,
+                            "duplicate `optional_deps` parameter",
+                        ));
                     }
+                    seen_optional_deps = true;
+                    optional_deps = parse_deps_array("optional_deps", nv.value)?;
                 }
                 Meta::NameValue(nv) if nv.path.is_ident("capabilities") => {
                     if seen_caps {
@@ -398,9 +386,17 @@ impl Parse for ModuleConfig {
             )
         })?;
 
+        if let Some(dep) = optional_deps.iter().find(|d| deps.contains(d)) {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                format!("dependency '{dep}' is listed in both `deps` and `optional_deps`"),
+            ));
+        }
+
         Ok(ModuleConfig {
             name,
             deps,
+            optional_deps,
             caps,
             ctor,
             client,
@@ -409,6 +405,34 @@ impl Parse for ModuleConfig {
     }
 }
 
+/// Parse `deps = [...]` / `optional_deps = [...]` into module names.
+fn parse_deps_array(param: &str, value: Expr) -> syn::Result<Vec<String>> {
+    let Expr::Array(arr) = value else {
+        return Err(syn::Error::new_spanned(
+            value,
+            format!("{param} must be an array, e.g. {param} = [\"db\", \"auth\"]"),
+        ));
+    };
+
+    let mut deps = Vec::with_capacity(arr.elems.len());
+    for elem in arr.elems {
+        match elem {
+            Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(s), ..
+            }) => deps.push(s.value()),
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    format!(
+                        "{param} must be an array of string literals, e.g. {param} = [\"db\", \"auth\"]"
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(deps)
+}
+
 fn parse_lifecycle_list(list: &MetaList) -> syn::Result<LcModuleCfg> {
     let mut cfg = LcModuleCfg::default();
 
@@ -501,6 +525,18 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
         .iter()
         .map(|s| LitStr::new(s, Span::call_site()))
         .collect();
+    let optional_deps_registration = if config.optional_deps.is_empty() {
+        quote! {}
+    } else {
+        let optional_deps_lits: Vec<LitStr> = config
+            .optional_deps
+            .iter()
+            .map(|s| LitStr::new(s, Span::call_site()))
+            .collect();
+        quote! {
+            b.register_optional_deps_with_meta(#name_lit, &[#(#optional_deps_lits),*]);
+        }
+    };
 
     // Constructor expression (provided or Default::default())
     let constructor = if let Some(expr) = &ctor_expr_opt {
@@ -788,6 +824,7 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
                 &[#(#deps_lits),*],
                 module.clone() as Arc<dyn ::modkit::contracts::Module>
             );
+            #optional_deps_registration
 
             // capabilities
             #(#capability_registrations)*
@@ -75,6 +75,7 @@ pub struct QueryBuilder<S: Schema> {
     order: Vec<OrderKey>,
     select: Option<Vec<S::Field>>,
     limit: Option<u64>,
+    count: bool,
     _phantom: PhantomData<S>,
 }
 
@@ -87,6 +88,7 @@ impl<S: Schema> QueryBuilder<S> {
             order: Vec::new(),
             select: None,
             limit: None,
+            count: false,
             _phantom: PhantomData,
         }
     }
@@ -167,6 +169,19 @@ impl<S: Schema> QueryBuilder<S> {
         self
     }
 
+    /// Request the total number of matching items alongside the page.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder.with_count()
+    /// ```
+    #[must_use]
+    pub fn with_count(mut self) -> Self {
+        self.count = true;
+        self
+    }
+
     /// Build the final `ODataQuery` with computed filter hash.
     ///
     /// The filter hash is computed using the stable hashing algorithm from
@@ -200,7 +215,7 @@ impl<S: Schema> QueryBuilder<S> {
             query = query.with_select(names);
         }
 
-        query
+        query.with_count(self.count)
     }
 }
 
@@ -458,6 +473,17 @@ mod tests {
         assert_eq!(query.limit, Some(50));
     }
 
+    #[test]
+    fn test_with_count() {
+        assert!(!QueryBuilder::<UserSchema>::new().build().wants_count());
+        assert!(
+            QueryBuilder::<UserSchema>::new()
+                .with_count()
+                .build()
+                .wants_count()
+        );
+    }
+
     #[test]
     fn test_full_query_build() {
         let user_id = uuid::Uuid::nil();
@@ -126,6 +126,46 @@ impl SortDir {
     }
 }
 
+/// Placement of NULL values in an ordered result.
+///
+/// Databases disagree on the default (`PostgreSQL` puts NULLs last for `ASC`,
+/// `SQLite` and `MySQL` first), so translations emit it explicitly.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum NullsOrder {
+    /// NULL sorts lower than any value (the `OData` rule)
+    #[default]
+    #[serde(rename = "first")]
+    First,
+    /// NULL sorts higher than any value
+    #[serde(rename = "last")]
+    Last,
+}
+
+impl NullsOrder {
+    /// Flip the placement (First <-> Last)
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        match self {
+            NullsOrder::First => NullsOrder::Last,
+            NullsOrder::Last => NullsOrder::First,
+        }
+    }
+}
+
+impl SortDir {
+    /// Placement of NULLs in this direction, given their placement in ascending order.
+    ///
+    /// Descending order flips the placement, so reversing every key (backward
+    /// pagination) walks the same total order in the opposite direction.
+    #[must_use]
+    pub fn nulls(self, ascending: NullsOrder) -> NullsOrder {
+        match self {
+            SortDir::Asc => ascending,
+            SortDir::Desc => ascending.reverse(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct OrderKey {
     pub field: String,
@@ -353,7 +393,8 @@ pub fn validate_cursor_against(
 // Cursor v1
 #[derive(Clone, Debug)]
 pub struct CursorV1 {
-    pub k: Vec<String>,
+    /// Sort key values of the boundary row; `None` for a NULL key.
+    pub k: Vec<Option<String>>,
     pub o: SortDir,
     pub s: String,
     pub f: Option<String>,
@@ -369,7 +410,7 @@ impl CursorV1 {
         #[derive(serde::Serialize)]
         struct Wire<'a> {
             v: u8,
-            k: &'a [String],
+            k: &'a [Option<String>],
             o: &'a str,
             s: &'a str,
             #[serde(skip_serializing_if = "Option::is_none")]
@@ -402,7 +443,7 @@ impl CursorV1 {
         #[derive(serde::Deserialize)]
         struct Wire {
             v: u8,
-            k: Vec<String>,
+            k: Vec<Option<String>>,
             o: String,
             s: String,
             #[serde(default)]
@@ -468,6 +509,10 @@ pub struct ODataQuery {
     pub cursor: Option<CursorV1>,
     pub filter_hash: Option<String>,
     pub select: Option<Vec<String>>,
+    /// `$count=true`: include the total number of matching items in the page.
+    pub count: bool,
+    /// `$expand`: related entities to embed in each item.
+    pub expand: Option<Vec<String>>,
 }
 
 impl ODataQuery {
@@ -485,6 +530,17 @@ impl ODataQuery {
         self
     }
 
+    /// Use `order` only when no `$orderby` was given.
+    ///
+    /// Lets list endpoints default to a stable keyset such as `(created_at, id)`
+    /// while still honoring an explicit client ordering.
+    pub fn with_default_order(mut self, order: ODataOrderBy) -> Self {
+        if self.order.is_empty() {
+            self.order = order;
+        }
+        self
+    }
+
     pub fn with_limit(mut self, limit: u64) -> Self {
         self.limit = Some(limit);
         self
@@ -505,6 +561,16 @@ impl ODataQuery {
         self
     }
 
+    pub fn with_count(mut self, count: bool) -> Self {
+        self.count = count;
+        self
+    }
+
+    pub fn with_expand(mut self, relations: Vec<String>) -> Self {
+        self.expand = Some(relations);
+        self
+    }
+
     /// Get filter as AST
     #[must_use]
     pub fn filter(&self) -> Option<&ast::Expr> {
@@ -534,6 +600,88 @@ impl ODataQuery {
     pub fn selected_fields(&self) -> Option<&[String]> {
         self.select.as_deref()
     }
+
+    /// Check if the total count of matching items was requested
+    #[must_use]
+    pub fn wants_count(&self) -> bool {
+        self.count
+    }
+
+    /// Get relations requested via `$expand`
+    #[must_use]
+    pub fn expanded_relations(&self) -> Option<&[String]> {
+        self.expand.as_deref()
+    }
+
+    /// Check if `relation` was requested via `$expand`
+    #[must_use]
+    pub fn expands(&self, relation: &str) -> bool {
+        self.expand
+            .as_deref()
+            .is_some_and(|r| r.iter().any(|e| e == relation))
+    }
+
+    /// Returns the first `$expand` relation that is not in `allowed`, if any.
+    ///
+    /// Services call this with their declared expandable relations to reject
+    /// unknown expand targets before querying.
+    #[must_use]
+    pub fn unsupported_expand(&self, allowed: &[&str]) -> Option<&str> {
+        self.expand
+            .as_deref()?
+            .iter()
+            .map(String::as_str)
+            .find(|r| !allowed.contains(r))
+    }
+
+    /// Returns the first `$select` field whose top-level name is not in `allowed`, if any.
+    ///
+    /// Names compare case-insensitively, like the projection itself; for dotted
+    /// paths (`address.city`) only the first segment is checked.
+    #[must_use]
+    pub fn unsupported_select(&self, allowed: &[&str]) -> Option<&str> {
+        self.select
+            .as_deref()?
+            .iter()
+            .map(String::as_str)
+            .find(|f| {
+                let root = f.split('.').next().unwrap_or(f);
+                !allowed.iter().any(|a| a.eq_ignore_ascii_case(root))
+            })
+    }
+
+    /// Returns the first field referenced by `$filter` that is not in `allowed`, if any.
+    #[must_use]
+    pub fn unsupported_filter_field(&self, allowed: &[&str]) -> Option<&str> {
+        fn find<'a>(expr: &'a ast::Expr, allowed: &[&str]) -> Option<&'a str> {
+            match expr {
+                ast::Expr::And(l, r) | ast::Expr::Or(l, r) | ast::Expr::Compare(l, _, r) => {
+                    find(l, allowed).or_else(|| find(r, allowed))
+                }
+                ast::Expr::Not(e) => find(e, allowed),
+                ast::Expr::In(e, list) => {
+                    find(e, allowed).or_else(|| list.iter().find_map(|e| find(e, allowed)))
+                }
+                ast::Expr::Function(_, args) => args.iter().find_map(|e| find(e, allowed)),
+                ast::Expr::Identifier(name) => {
+                    (!allowed.iter().any(|a| a.eq_ignore_ascii_case(name))).then_some(name.as_str())
+                }
+                ast::Expr::Value(_) => None,
+            }
+        }
+
+        find(self.filter.as_deref()?, allowed)
+    }
+
+    /// Returns the first `$orderby` field that is not in `allowed`, if any.
+    #[must_use]
+    pub fn unsupported_order_field(&self, allowed: &[&str]) -> Option<&str> {
+        self.order
+            .0
+            .iter()
+            .map(|k| k.field.as_str())
+            .find(|f| !allowed.iter().any(|a| a.eq_ignore_ascii_case(f)))
+    }
 }
 
 impl From<Option<ast::Expr>> for ODataQuery {
@@ -6,6 +6,9 @@ pub struct PageInfo {
     pub next_cursor: Option<String>,
     pub prev_cursor: Option<String>,
     pub limit: u64,
+    /// Total number of items matching the filter, present when `$count=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -97,10 +100,18 @@ impl<T> Page<T> {
                 next_cursor: None,
                 prev_cursor: None,
                 limit,
+                total_count: None,
             },
         }
     }
 
+    /// Attach the total number of matching items
+    #[must_use]
+    pub fn with_total_count(mut self, total_count: u64) -> Self {
+        self.page_info.total_count = Some(total_count);
+        self
+    }
+
     /// Map items while preserving `page_info` (Domain->DTO mapping convenience)
     pub fn map_items<U>(self, mut f: impl FnMut(T) -> U) -> Page<U> {
         Page {
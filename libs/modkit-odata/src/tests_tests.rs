@@ -1,12 +1,12 @@
 // Created: 2026-04-07 by Constructor Tech
-use crate::{CursorV1, Error, ODataOrderBy, ODataQuery, OrderKey, SortDir, base64_url};
+use crate::{CursorV1, Error, NullsOrder, ODataOrderBy, ODataQuery, OrderKey, SortDir, base64_url};
 
 #[test]
 fn test_cursor_v1_encode_decode_round_trip() {
     let cursor = CursorV1 {
         k: vec![
-            "2023-11-14T12:00:00Z".to_owned(),
-            "123e4567-e89b-12d3-a456-426614174000".to_owned(),
+            Some("2023-11-14T12:00:00Z".to_owned()),
+            Some("123e4567-e89b-12d3-a456-426614174000".to_owned()),
         ],
         o: SortDir::Desc,
         s: "+created_at,-id".to_owned(),
@@ -27,7 +27,7 @@ fn test_cursor_v1_encode_decode_round_trip() {
 #[test]
 fn test_cursor_v1_encode_decode_without_filter_hash() {
     let cursor = CursorV1 {
-        k: vec!["value1".to_owned(), "value2".to_owned()],
+        k: vec![Some("value1".to_owned()), None],
         o: SortDir::Asc,
         s: "+field1,+field2".to_owned(),
         f: None,
@@ -181,6 +181,26 @@ fn test_odata_order_by_ensure_tiebreaker() {
     assert_eq!(with_tiebreaker.0[1].dir, SortDir::Desc);
 }
 
+#[test]
+fn test_with_default_order_only_applies_without_orderby() {
+    let default = ODataOrderBy(vec![OrderKey {
+        field: "created_at".to_owned(),
+        dir: SortDir::Desc,
+    }]);
+
+    let q = ODataQuery::default().with_default_order(default.clone());
+    assert_eq!(q.order.to_signed_tokens(), "-created_at");
+
+    let explicit = ODataOrderBy(vec![OrderKey {
+        field: "email".to_owned(),
+        dir: SortDir::Asc,
+    }]);
+    let q = ODataQuery::default()
+        .with_order(explicit)
+        .with_default_order(default);
+    assert_eq!(q.order.to_signed_tokens(), "+email");
+}
+
 #[test]
 fn test_odata_order_by_ensure_tiebreaker_already_present() {
     let order = ODataOrderBy(vec![
@@ -201,6 +221,15 @@ fn test_odata_order_by_ensure_tiebreaker_already_present() {
     assert_eq!(with_tiebreaker.0[1].dir, SortDir::Asc); // original direction preserved
 }
 
+#[test]
+fn test_sort_dir_nulls_placement() {
+    // OData default: null sorts lowest, i.e. first ascending and last descending
+    assert_eq!(SortDir::Asc.nulls(NullsOrder::default()), NullsOrder::First);
+    assert_eq!(SortDir::Desc.nulls(NullsOrder::default()), NullsOrder::Last);
+    assert_eq!(SortDir::Asc.nulls(NullsOrder::Last), NullsOrder::Last);
+    assert_eq!(SortDir::Desc.nulls(NullsOrder::Last), NullsOrder::First);
+}
+
 #[test]
 fn test_odata_query_builder_pattern() {
     use crate::ast::*;
@@ -217,7 +246,7 @@ fn test_odata_query_builder_pattern() {
     }]);
 
     let cursor = CursorV1 {
-        k: vec!["2023-11-14T12:00:00Z".to_owned()],
+        k: vec![Some("2023-11-14T12:00:00Z".to_owned())],
         o: SortDir::Desc,
         s: "-created_at".to_owned(),
         f: None,
@@ -390,3 +419,27 @@ fn test_parse_filter_string_error_contains_position() {
         "InvalidFilter should contain position and expectation info, got: {msg}"
     );
 }
+
+#[test]
+fn test_unsupported_fields_against_allow_list() {
+    let allowed = ["id", "email"];
+    let filter = crate::parse_filter_string("email eq 'a' and secret eq 1")
+        .unwrap()
+        .into_expr();
+    let q = ODataQuery::default()
+        .with_filter(filter)
+        .with_select(vec!["ID".to_owned(), "ssn".to_owned()])
+        .with_order(ODataOrderBy(vec![OrderKey {
+            field: "rank".to_owned(),
+            dir: SortDir::Asc,
+        }]));
+
+    assert_eq!(q.unsupported_select(&allowed), Some("ssn"));
+    assert_eq!(q.unsupported_filter_field(&allowed), Some("secret"));
+    assert_eq!(q.unsupported_order_field(&allowed), Some("rank"));
+
+    let ok = ODataQuery::default().with_select(vec!["email".to_owned()]);
+    assert_eq!(ok.unsupported_select(&allowed), None);
+    assert_eq!(ok.unsupported_filter_field(&allowed), None);
+    assert_eq!(ok.unsupported_order_field(&allowed), None);
+}
@@ -45,7 +45,7 @@ async fn test_cursor_pager_two_pages() {
     use modkit_odata::{CursorV1, SortDir};
 
     let cursor = CursorV1 {
-        k: vec!["2".to_owned()],
+        k: vec![Some("2".to_owned())],
         o: SortDir::Asc,
         s: "filter_hash".to_owned(),
         f: Some("filter_hash".to_owned()),
@@ -68,6 +68,7 @@ async fn test_cursor_pager_two_pages() {
             next_cursor: Some(encoded_cursor.clone()),
             prev_cursor: None,
             limit: 2,
+            total_count: None,
         },
     );
 
@@ -86,6 +87,7 @@ async fn test_cursor_pager_two_pages() {
             next_cursor: None,
             prev_cursor: Some(encoded_cursor),
             limit: 2,
+            total_count: None,
         },
     );
 
@@ -117,6 +119,7 @@ async fn test_cursor_pager_empty_page() {
             next_cursor: None,
             prev_cursor: None,
             limit: 10,
+            total_count: None,
         },
     );
 
@@ -138,7 +141,7 @@ async fn test_cursor_pager_error_propagation() {
     use modkit_odata::{CursorV1, SortDir};
 
     let cursor = CursorV1 {
-        k: vec!["1".to_owned()],
+        k: vec![Some("1".to_owned())],
         o: SortDir::Asc,
         s: "filter_hash".to_owned(),
         f: Some("filter_hash".to_owned()),
@@ -155,6 +158,7 @@ async fn test_cursor_pager_error_propagation() {
             next_cursor: Some(encoded_cursor),
             prev_cursor: None,
             limit: 1,
+            total_count: None,
         },
     );
 
@@ -185,7 +189,7 @@ async fn test_pages_pager_two_pages() {
     use modkit_odata::{CursorV1, SortDir};
 
     let cursor = CursorV1 {
-        k: vec!["2".to_owned()],
+        k: vec![Some("2".to_owned())],
         o: SortDir::Asc,
         s: "filter_hash".to_owned(),
         f: Some("filter_hash".to_owned()),
@@ -208,6 +212,7 @@ async fn test_pages_pager_two_pages() {
             next_cursor: Some(encoded_cursor.clone()),
             prev_cursor: None,
             limit: 2,
+            total_count: None,
         },
     );
 
@@ -220,6 +225,7 @@ async fn test_pages_pager_two_pages() {
             next_cursor: None,
             prev_cursor: Some(encoded_cursor),
             limit: 2,
+            total_count: None,
         },
     );
 
@@ -254,6 +260,7 @@ async fn test_pages_pager_single_page() {
             next_cursor: None,
             prev_cursor: None,
             limit: 10,
+            total_count: None,
         },
     );
 
@@ -282,6 +289,7 @@ async fn test_cursor_pager_invalid_cursor() {
             next_cursor: Some("invalid_cursor_string".to_owned()),
             prev_cursor: None,
             limit: 1,
+            total_count: None,
         },
     );
 
@@ -318,6 +326,7 @@ async fn test_pages_pager_invalid_cursor() {
             next_cursor: Some("invalid_cursor_string".to_owned()),
             prev_cursor: None,
             limit: 1,
+            total_count: None,
         },
     );
 
@@ -348,7 +357,7 @@ async fn test_pages_pager_error_propagation() {
     use modkit_odata::{CursorV1, SortDir};
 
     let cursor = CursorV1 {
-        k: vec!["1".to_owned()],
+        k: vec![Some("1".to_owned())],
         o: SortDir::Asc,
         s: "filter_hash".to_owned(),
         f: Some("filter_hash".to_owned()),
@@ -365,6 +374,7 @@ async fn test_pages_pager_error_propagation() {
             next_cursor: Some(encoded_cursor),
             prev_cursor: None,
             limit: 1,
+            total_count: None,
         },
     );
 
@@ -487,6 +487,7 @@ impl ScopeConstraint {
 pub struct AccessScope {
     constraints: Vec<ScopeConstraint>,
     unconstrained: bool,
+    root: bool,
 }
 
 impl Default for AccessScope {
@@ -505,6 +506,7 @@ impl AccessScope {
         Self {
             constraints,
             unconstrained: false,
+            root: false,
         }
     }
 
@@ -523,6 +525,23 @@ impl AccessScope {
         Self {
             constraints: Vec::new(),
             unconstrained: true,
+            root: false,
+        }
+    }
+
+    /// Create a privileged root scope that bypasses row-level filtering.
+    ///
+    /// Unlike [`allow_all`](Self::allow_all), this is **not** a PDP outcome:
+    /// it is an explicit bypass for system maintenance (migrations, repair
+    /// jobs, cross-tenant housekeeping). The secure query layer audits every
+    /// use of a root scope, so reach for it only when bypassing the scope is
+    /// the intent.
+    #[must_use]
+    pub fn root() -> Self {
+        Self {
+            constraints: Vec::new(),
+            unconstrained: true,
+            root: true,
         }
     }
 
@@ -532,6 +551,7 @@ impl AccessScope {
         Self {
             constraints: Vec::new(),
             unconstrained: false,
+            root: false,
         }
     }
 
@@ -583,6 +603,15 @@ impl AccessScope {
         self.unconstrained
     }
 
+    /// Returns `true` if this is a privileged [`root`](Self::root) scope.
+    ///
+    /// Root scopes are always unconstrained.
+    #[inline]
+    #[must_use]
+    pub fn is_root(&self) -> bool {
+        self.root
+    }
+
     /// Returns `true` if this scope denies all access.
     ///
     /// A scope is deny-all when it is not unconstrained and has no constraints.
@@ -800,6 +829,24 @@ mod tests {
         Uuid::parse_str(s).unwrap()
     }
 
+    // --- root ---
+
+    #[test]
+    fn root_scope_is_unconstrained_and_marked() {
+        let root = AccessScope::root();
+        assert!(root.is_root());
+        assert!(root.is_unconstrained());
+        assert!(!root.is_deny_all());
+
+        assert!(!AccessScope::allow_all().is_root());
+        assert_ne!(root, AccessScope::allow_all());
+    }
+
+    #[test]
+    fn root_marker_is_dropped_by_narrowing() {
+        assert!(!AccessScope::root().tenant_only().is_root());
+    }
+
     // --- ScopeFilter::Eq ---
 
     #[test]
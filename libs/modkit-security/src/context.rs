@@ -15,6 +15,34 @@ pub enum SecurityContextBuildError {
     MissingSubjectTenantId,
 }
 
+/// Error returned when an operation requires a tenant but none is available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum TenantRequiredError {
+    /// No tenant could be resolved.
+    #[error("tenant is required but missing")]
+    Missing,
+    /// The tenant is the nil UUID (e.g. an anonymous context).
+    #[error("tenant is required but is the nil UUID")]
+    Nil,
+}
+
+/// Validate an optional tenant ID, rejecting missing and nil tenants.
+///
+/// Shared by [`SecurityContext::require_tenant`] and callers that resolve the
+/// tenant from other sources (e.g. `AuthZ` evaluation requests).
+///
+/// # Errors
+///
+/// Returns [`TenantRequiredError::Missing`] for `None` and
+/// [`TenantRequiredError::Nil`] for the nil UUID.
+pub fn require_tenant_id(tenant_id: Option<Uuid>) -> Result<Uuid, TenantRequiredError> {
+    match tenant_id {
+        None => Err(TenantRequiredError::Missing),
+        Some(id) if id.is_nil() => Err(TenantRequiredError::Nil),
+        Some(id) => Ok(id),
+    }
+}
+
 /// `SecurityContext` encapsulates the security-related information for a request or operation.
 ///
 /// Built by the `AuthN` Resolver during authentication and passed through the request lifecycle.
@@ -32,6 +60,9 @@ pub struct SecurityContext {
     /// Empty means no scopes were asserted (treat as unrestricted for backward compatibility).
     #[serde(default)]
     token_scopes: Vec<String>,
+    /// Roles granted to the subject (e.g. mapped from the token's role claims).
+    #[serde(default)]
+    roles: Vec<String>,
     /// Original bearer token for PDP forwarding. Never serialized/persisted.
     /// Wrapped in `SecretString` so `Debug` redacts the value automatically.
     #[serde(skip)]
@@ -56,6 +87,7 @@ impl SecurityContext {
             subject_type: None,
             subject_tenant_id: Uuid::default(),
             token_scopes: Vec::new(),
+            roles: Vec::new(),
             bearer_token: None,
         }
     }
@@ -78,12 +110,42 @@ impl SecurityContext {
         self.subject_tenant_id
     }
 
+    /// Get the subject's tenant ID, failing if the context has no usable tenant.
+    ///
+    /// Anonymous contexts carry the nil UUID and are rejected, so callers can map
+    /// the error straight to `403 Forbidden`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TenantRequiredError::Nil`] when the tenant is the nil UUID.
+    pub fn require_tenant(&self) -> Result<Uuid, TenantRequiredError> {
+        require_tenant_id(Some(self.subject_tenant_id))
+    }
+
+    /// Tenants this context is scoped to; empty when there is no usable tenant.
+    #[must_use]
+    pub fn tenant_ids(&self) -> Vec<Uuid> {
+        self.require_tenant().into_iter().collect()
+    }
+
     /// Get the token scopes. `["*"]` means first-party / unrestricted.
     #[must_use]
     pub fn token_scopes(&self) -> &[String] {
         &self.token_scopes
     }
 
+    /// Get the roles granted to the subject.
+    #[must_use]
+    pub fn roles(&self) -> &[String] {
+        &self.roles
+    }
+
+    /// Whether the subject was granted `role`.
+    #[must_use]
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+
     /// Get the original bearer token (for PDP forwarding).
     #[must_use]
     pub fn bearer_token(&self) -> Option<&SecretString> {
@@ -97,6 +159,7 @@ pub struct SecurityContextBuilder {
     subject_type: Option<String>,
     subject_tenant_id: Option<Uuid>,
     token_scopes: Vec<String>,
+    roles: Vec<String>,
     bearer_token: Option<SecretString>,
 }
 
@@ -125,6 +188,12 @@ impl SecurityContextBuilder {
         self
     }
 
+    #[must_use]
+    pub fn roles(mut self, roles: Vec<String>) -> Self {
+        self.roles = roles;
+        self
+    }
+
     #[must_use]
     pub fn bearer_token(mut self, token: impl Into<SecretString>) -> Self {
         self.bearer_token = Some(token.into());
@@ -150,6 +219,7 @@ impl SecurityContextBuilder {
             subject_type: self.subject_type,
             subject_tenant_id,
             token_scopes: self.token_scopes,
+            roles: self.roles,
             bearer_token: self.bearer_token,
         })
     }
@@ -219,6 +289,36 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_require_tenant_present() {
+        let tenant_id = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440002").unwrap();
+        let ctx = SecurityContext::builder()
+            .subject_id(Uuid::parse_str("550e8400-e29b-41d4-a716-446655440001").unwrap())
+            .subject_tenant_id(tenant_id)
+            .build()
+            .unwrap();
+
+        assert_eq!(ctx.require_tenant(), Ok(tenant_id));
+        assert_eq!(ctx.tenant_ids(), vec![tenant_id]);
+    }
+
+    #[test]
+    fn test_require_tenant_nil() {
+        let ctx = SecurityContext::anonymous();
+
+        assert_eq!(ctx.require_tenant(), Err(TenantRequiredError::Nil));
+        assert!(ctx.tenant_ids().is_empty());
+    }
+
+    #[test]
+    fn test_require_tenant_id_absent() {
+        assert_eq!(require_tenant_id(None), Err(TenantRequiredError::Missing));
+        assert_eq!(
+            require_tenant_id(Some(Uuid::nil())),
+            Err(TenantRequiredError::Nil)
+        );
+    }
+
     #[test]
     fn test_security_context_anonymous() {
         let ctx = SecurityContext::anonymous();
@@ -303,6 +403,20 @@ mod tests {
         assert!(!serialized.contains("bearer_token"));
     }
 
+    #[test]
+    fn test_security_context_roles() {
+        let ctx = SecurityContext::builder()
+            .subject_id(Uuid::parse_str("550e8400-e29b-41d4-a716-446655440001").unwrap())
+            .subject_tenant_id(Uuid::parse_str("550e8400-e29b-41d4-a716-446655440002").unwrap())
+            .roles(vec!["admin".to_owned()])
+            .build()
+            .unwrap();
+
+        assert!(ctx.has_role("admin"));
+        assert!(!ctx.has_role("viewer"));
+        assert!(SecurityContext::anonymous().roles().is_empty());
+    }
+
     #[test]
     fn test_security_context_empty_scopes() {
         let ctx = SecurityContext::anonymous();
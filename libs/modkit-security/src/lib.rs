@@ -9,7 +9,9 @@ pub use access_scope::{
     AccessScope, EqScopeFilter, InGroupScopeFilter, InGroupSubtreeScopeFilter, InScopeFilter,
     ScopeConstraint, ScopeFilter, ScopeValue, pep_properties, rg_tables,
 };
-pub use context::{SecurityContext, SecurityContextBuildError};
+pub use context::{
+    SecurityContext, SecurityContextBuildError, TenantRequiredError, require_tenant_id,
+};
 
 pub use bin_codec::{
     SECCTX_BIN_VERSION, SecCtxDecodeError, SecCtxEncodeError, decode_bin, encode_bin,
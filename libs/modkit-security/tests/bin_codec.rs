@@ -13,6 +13,7 @@ fn round_trips_security_ctx_binary_payload() {
         .subject_id(subject_id)
         .subject_tenant_id(subject_tenant_id)
         .token_scopes(vec!["admin".to_owned(), "read:events".to_owned()])
+        .roles(vec!["auditor".to_owned()])
         .build()
         .unwrap();
 
@@ -23,6 +24,7 @@ fn round_trips_security_ctx_binary_payload() {
     assert_eq!(decoded.subject_id(), ctx.subject_id());
     assert_eq!(decoded.subject_tenant_id(), ctx.subject_tenant_id());
     assert_eq!(decoded.token_scopes(), ctx.token_scopes());
+    assert_eq!(decoded.roles(), ctx.roles());
     // bearer_token is #[serde(skip)] so not included in binary encoding
     assert!(decoded.bearer_token().is_none());
 }
@@ -1,5 +1,6 @@
 use std::fmt;
 
+use subtle::ConstantTimeEq;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// Opaque wrapper around a secret string value.
@@ -9,6 +10,9 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 /// controlled access when constructing HTTP headers or form bodies.
 ///
 /// On [`Drop`] the backing buffer is securely zeroed via the [`zeroize`] crate.
+///
+/// There is deliberately no `PartialEq`; compare secrets with
+/// [`ct_eq`](Self::ct_eq) so the comparison does not leak timing.
 #[derive(Zeroize, ZeroizeOnDrop)]
 pub struct SecretString(String);
 
@@ -25,6 +29,15 @@ impl SecretString {
     pub fn expose(&self) -> &str {
         &self.0
     }
+
+    /// Constant-time equality, e.g. for checking a presented `client_secret`.
+    ///
+    /// Runs in time independent of where the values differ; only the length
+    /// of the inputs may be observable.
+    #[must_use]
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        self.0.as_bytes().ct_eq(other.0.as_bytes()).into()
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -81,6 +94,29 @@ mod tests {
         assert!(!dbg.contains(secret), "Debug must not contain the secret");
     }
 
+    #[test]
+    fn formatting_contains_no_secret_bytes() {
+        let secret = "s3cr3t-client-value";
+        let s = SecretString::new(secret);
+        for out in [format!("{s:?}"), format!("{s:#?}"), format!("{s}")] {
+            assert!(!out.contains(secret), "formatting leaked the secret: {out}");
+            assert!(
+                !out.contains("s3cr3t"),
+                "formatting leaked a fragment: {out}"
+            );
+        }
+    }
+
+    #[test]
+    fn ct_eq_compares_values() {
+        let a = SecretString::new("client-secret");
+        assert!(a.ct_eq(&SecretString::new("client-secret")));
+        assert!(!a.ct_eq(&SecretString::new("client-secreT")));
+        assert!(!a.ct_eq(&SecretString::new("client-secret-longer")));
+        assert!(!a.ct_eq(&SecretString::new("")));
+        assert!(SecretString::new("").ct_eq(&SecretString::new("")));
+    }
+
     #[test]
     fn expose_returns_original_value() {
         let s = SecretString::new("hunter2");
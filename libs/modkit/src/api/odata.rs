@@ -19,6 +19,10 @@ pub struct ODataParams {
     pub orderby: Option<String>,
     #[serde(rename = "$select")]
     pub select: Option<String>,
+    #[serde(rename = "$count")]
+    pub count: Option<bool>,
+    #[serde(rename = "$expand")]
+    pub expand: Option<String>,
     pub limit: Option<u64>,
     pub cursor: Option<String>,
 }
@@ -29,6 +33,8 @@ pub const MAX_ORDERBY_LEN: usize = 1024;
 pub const MAX_ORDER_FIELDS: usize = 10;
 pub const MAX_SELECT_LEN: usize = 2048;
 pub const MAX_SELECT_FIELDS: usize = 100;
+pub const MAX_EXPAND_LEN: usize = 512;
+pub const MAX_EXPAND_FIELDS: usize = 10;
 
 /// Parse $select string into a list of field names.
 /// Format: "field1, field2, field3, ..."
@@ -76,6 +82,60 @@ pub fn parse_select(raw: &str) -> Result<Vec<String>, crate::api::problem::Probl
     Ok(fields)
 }
 
+/// Parse $expand string into a list of relation names.
+/// Format: "relation1, relation2, ..."
+/// Names are case-insensitive; nested expand options are not supported.
+///
+/// # Errors
+/// Returns a `Problem` if the expand string is invalid.
+#[allow(clippy::result_large_err)]
+pub fn parse_expand(raw: &str) -> Result<Vec<String>, crate::api::problem::Problem> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Err(crate::api::bad_request("$expand cannot be empty"));
+    }
+
+    if raw.len() > MAX_EXPAND_LEN {
+        return Err(crate::api::bad_request("$expand too long"));
+    }
+
+    let mut relations: Vec<String> = Vec::new();
+    for part in raw.split(',') {
+        let relation = part.trim().to_lowercase();
+        if relation.is_empty() {
+            continue;
+        }
+        if !relation
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_')
+        {
+            return Err(crate::api::bad_request(format!(
+                "invalid relation in $expand: {relation}"
+            )));
+        }
+        if relations.contains(&relation) {
+            return Err(crate::api::bad_request(format!(
+                "duplicate relation in $expand: {relation}"
+            )));
+        }
+        relations.push(relation);
+    }
+
+    if relations.is_empty() {
+        return Err(crate::api::bad_request(
+            "$expand must contain at least one relation",
+        ));
+    }
+
+    if relations.len() > MAX_EXPAND_FIELDS {
+        return Err(crate::api::bad_request(
+            "$expand contains too many relations",
+        ));
+    }
+
+    Ok(relations)
+}
+
 /// Parse $orderby string into `ODataOrderBy`.
 /// Format: "field1 [asc|desc], field2 [asc|desc], ..."
 /// Default direction is asc if not specified.
@@ -135,7 +195,7 @@ pub fn parse_orderby(raw: &str) -> Result<ODataOrderBy, modkit_odata::Error> {
 }
 
 /// Extract and validate full `OData` query from request parts.
-/// - Parses $filter, $orderby, limit, cursor
+/// - Parses $filter, $orderby, $select, $count, $expand, limit, cursor
 /// - Enforces budgets and validates formats
 /// - Returns unified `ODataQuery`
 ///
@@ -236,6 +296,17 @@ where
         query = query.with_select(fields);
     }
 
+    // Parse count
+    if let Some(count) = params.count {
+        query = query.with_count(count);
+    }
+
+    // Parse expand (targets are validated by the service that owns the relations)
+    if let Some(raw_expand) = params.expand.as_ref() {
+        let relations = parse_expand(raw_expand)?;
+        query = query.with_expand(relations);
+    }
+
     Ok(query)
 }
 
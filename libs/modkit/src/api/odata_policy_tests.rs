@@ -29,7 +29,7 @@ mod tests {
     async fn test_cursor_only_success() {
         // Create a valid cursor
         let cursor = CursorV1 {
-            k: vec!["test".to_owned()],
+            k: vec![Some("test".to_owned())],
             o: SortDir::Desc,
             s: "-id".to_owned(),
             f: None,
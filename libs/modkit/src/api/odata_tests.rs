@@ -214,6 +214,47 @@ mod tests {
         let _problem_response = result.unwrap_err();
     }
 
+    #[tokio::test]
+    async fn test_extract_odata_query_count() {
+        let request = Request::builder()
+            .uri("/?%24count=true&limit=5")
+            .body(())
+            .unwrap();
+        let (mut parts, _body) = request.into_parts();
+        let query = extract_odata_query(&mut parts, &()).await.unwrap();
+        assert!(query.wants_count());
+        assert_eq!(query.limit, Some(5));
+
+        let request = Request::builder().uri("/?%24count=false").body(()).unwrap();
+        let (mut parts, _body) = request.into_parts();
+        let query = extract_odata_query(&mut parts, &()).await.unwrap();
+        assert!(!query.wants_count());
+
+        let request = Request::builder().uri("/?%24count=yes").body(()).unwrap();
+        let (mut parts, _body) = request.into_parts();
+        assert!(extract_odata_query(&mut parts, &()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_extract_odata_query_expand() {
+        let request = Request::builder()
+            .uri("/?%24expand=Addresses,%20city")
+            .body(())
+            .unwrap();
+        let (mut parts, _body) = request.into_parts();
+        let query = extract_odata_query(&mut parts, &()).await.unwrap();
+        assert_eq!(
+            query.expanded_relations(),
+            Some(&["addresses".to_owned(), "city".to_owned()][..])
+        );
+        assert!(query.expands("addresses"));
+        assert_eq!(query.unsupported_expand(&["addresses"]), Some("city"));
+
+        for bad in ["", "a,a", "addresses($select=id)", "a/b"] {
+            assert!(parse_expand(bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+
     #[tokio::test]
     async fn test_extract_odata_query_invalid_cursor() {
         let uri = "/?cursor=invalid_cursor";
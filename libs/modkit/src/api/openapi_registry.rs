@@ -19,7 +19,7 @@ use utoipa::openapi::{
     request_body::RequestBodyBuilder,
     response::{ResponseBuilder, ResponsesBuilder},
     schema::{ComponentsBuilder, ObjectBuilder, Schema, SchemaFormat, SchemaType},
-    security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    security::{HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme},
 };
 
 use crate::api::{operation_builder, problem};
@@ -139,6 +139,14 @@ impl OpenApiRegistryImpl {
                 );
             }
 
+            // Per-route limits
+            if let Some(bytes) = spec.vendor_extensions.x_max_body_size {
+                ext.insert("x-max-body-size".to_owned(), serde_json::json!(bytes));
+            }
+            if let Some(ms) = spec.vendor_extensions.x_timeout {
+                ext.insert("x-timeout".to_owned(), serde_json::json!(ms));
+            }
+
             // Pagination
             if let Some(pagination) = spec.vendor_extensions.x_odata_filter.as_ref()
                 && let Ok(value) = serde_json::to_value(pagination)
@@ -291,12 +299,7 @@ impl OpenApiRegistryImpl {
             }
             op = op.responses(responses.build());
 
-            // Add security requirement if operation requires authentication
-            if spec.authenticated {
-                let sec_req = utoipa::openapi::security::SecurityRequirement::new(
-                    "bearerAuth",
-                    Vec::<String>::new(),
-                );
+            for sec_req in security_requirements(&spec) {
                 op = op.security(sec_req);
             }
 
@@ -466,6 +469,20 @@ fn collect_refs_from_json(value: &serde_json::Value, refs: &mut HashSet<String>)
     }
 }
 
+/// Security requirements for an operation: bearer auth when authenticated,
+/// bearer auth or none when authentication is optional.
+fn security_requirements(spec: &operation_builder::OperationSpec) -> Vec<SecurityRequirement> {
+    let bearer = || SecurityRequirement::new("bearerAuth", Vec::<String>::new());
+    if spec.authenticated {
+        vec![bearer()]
+    } else if spec.optional_auth {
+        // An empty requirement alongside bearerAuth marks the token as optional
+        vec![SecurityRequirement::default(), bearer()]
+    } else {
+        Vec::new()
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -503,6 +520,7 @@ mod tests {
             handler_id: "get_test".to_owned(),
             authenticated: false,
             is_public: false,
+            optional_auth: false,
             rate_limit: None,
             allowed_request_content_types: None,
             vendor_extensions: VendorExtensions::default(),
@@ -566,6 +584,7 @@ mod tests {
             handler_id: "get_users_id".to_owned(),
             authenticated: false,
             is_public: false,
+            optional_auth: false,
             rate_limit: None,
             allowed_request_content_types: None,
             vendor_extensions: VendorExtensions::default(),
@@ -587,6 +606,42 @@ mod tests {
         assert_eq!(get_op.get("summary").unwrap(), "Get user by ID");
     }
 
+    #[test]
+    fn test_build_openapi_with_optional_auth() {
+        let registry = OpenApiRegistryImpl::new();
+        let spec = OperationSpec {
+            method: Method::GET,
+            path: "/articles".to_owned(),
+            operation_id: Some("list_articles".to_owned()),
+            summary: None,
+            description: None,
+            tags: vec![],
+            params: vec![],
+            request_body: None,
+            responses: vec![ResponseSpec {
+                status: 200,
+                content_type: "application/json",
+                description: "Articles".to_owned(),
+                schema_name: None,
+            }],
+            handler_id: "get_articles".to_owned(),
+            authenticated: false,
+            is_public: true,
+            optional_auth: true,
+            rate_limit: None,
+            allowed_request_content_types: None,
+            vendor_extensions: VendorExtensions::default(),
+            license_requirement: None,
+        };
+
+        registry.register_operation(&spec);
+        let doc = registry.build_openapi(&OpenApiInfo::default()).unwrap();
+        let json = serde_json::to_value(&doc).unwrap();
+
+        let security = &json["paths"]["/articles"]["get"]["security"];
+        assert_eq!(security, &serde_json::json!([{}, { "bearerAuth": [] }]));
+    }
+
     #[test]
     fn test_ensure_schema_raw() {
         let registry = OpenApiRegistryImpl::new();
@@ -626,6 +681,7 @@ mod tests {
             handler_id: "post_upload".to_owned(),
             authenticated: false,
             is_public: false,
+            optional_auth: false,
             rate_limit: None,
             allowed_request_content_types: Some(vec!["application/octet-stream"]),
             vendor_extensions: VendorExtensions::default(),
@@ -705,6 +761,7 @@ mod tests {
             handler_id: "get_test".to_owned(),
             authenticated: false,
             is_public: false,
+            optional_auth: false,
             rate_limit: None,
             allowed_request_content_types: None,
             vendor_extensions: VendorExtensions::default(),
@@ -73,7 +73,7 @@ pub mod state {
     #[derive(Debug, Clone, Copy)]
     pub struct AuthNotSet;
 
-    /// Marker for auth requirement set (either `authenticated` or public)
+    /// Marker for auth requirement set (`authenticated`, public or optional)
     #[derive(Debug, Clone, Copy)]
     pub struct AuthSet;
 
@@ -210,6 +210,9 @@ pub struct OperationSpec {
     pub authenticated: bool,
     /// Explicitly mark route as public (no auth required)
     pub is_public: bool,
+    /// Public route that still validates a bearer token when one is sent.
+    /// Implies `is_public`.
+    pub optional_auth: bool,
     /// Optional rate & concurrency limits for this operation
     pub rate_limit: Option<RateLimitSpec>,
     /// Optional whitelist of allowed request Content-Type values (without parameters).
@@ -229,6 +232,12 @@ pub struct VendorExtensions {
     pub x_odata_filter: Option<ODataPagination<BTreeMap<String, Vec<String>>>>,
     #[serde(rename = "x-odata-orderby", skip_serializing_if = "Option::is_none")]
     pub x_odata_orderby: Option<ODataPagination<Vec<String>>>,
+    /// Request body limit in bytes, overriding the gateway default
+    #[serde(rename = "x-max-body-size", skip_serializing_if = "Option::is_none")]
+    pub x_max_body_size: Option<usize>,
+    /// Request timeout in milliseconds, overriding the gateway default
+    #[serde(rename = "x-timeout", skip_serializing_if = "Option::is_none")]
+    pub x_timeout: Option<u64>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -267,6 +276,14 @@ pub trait OperationBuilderODataExt<S, H, R> {
     #[must_use]
     fn with_odata_select(self) -> Self;
 
+    /// Adds optional `$count` query parameter to `OpenAPI`.
+    #[must_use]
+    fn with_odata_count(self) -> Self;
+
+    /// Adds optional `$expand` query parameter to `OpenAPI`, listing `relations`.
+    #[must_use]
+    fn with_odata_expand(self, relations: &[&str]) -> Self;
+
     /// Adds optional `$orderby` query parameter to `OpenAPI`.
     #[must_use]
     fn with_odata_orderby<T>(self) -> Self
@@ -340,6 +357,35 @@ where
         self
     }
 
+    fn with_odata_count(mut self) -> Self {
+        self.spec.params.push(ParamSpec {
+            name: "$count".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(
+                "Include the total number of matching items in `page_info.total_count`".to_owned(),
+            ),
+            param_type: "boolean".to_owned(),
+        });
+        self
+    }
+
+    fn with_odata_expand(mut self, relations: &[&str]) -> Self {
+        let mut description = "OData v4 expand expression (comma-separated)".to_owned();
+        for relation in relations {
+            description.push_str("\n- ");
+            description.push_str(relation);
+        }
+        self.spec.params.push(ParamSpec {
+            name: "$expand".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(description),
+            param_type: "string".to_owned(),
+        });
+        self
+    }
+
     fn with_odata_orderby<T>(mut self) -> Self
     where
         T: modkit_odata::filter::FilterField,
@@ -433,6 +479,7 @@ impl<S> OperationBuilder<Missing, Missing, S, AuthNotSet> {
                 handler_id,
                 authenticated: false,
                 is_public: false,
+                optional_auth: false,
                 rate_limit: None,
                 allowed_request_content_types: None,
                 vendor_extensions: VendorExtensions::default(),
@@ -509,6 +556,21 @@ where
         self
     }
 
+    /// Limit the request body of this operation to `bytes`, overriding the
+    /// gateway default. Published as the `x-max-body-size` extension.
+    pub fn max_body_size(mut self, bytes: usize) -> Self {
+        self.spec.vendor_extensions.x_max_body_size = Some(bytes);
+        self
+    }
+
+    /// Time out this operation after `timeout`, overriding the gateway
+    /// default. Published as the `x-timeout` extension, in milliseconds.
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.spec.vendor_extensions.x_timeout =
+            Some(u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX));
+        self
+    }
+
     /// Set the operation summary
     pub fn summary(mut self, text: impl Into<String>) -> Self {
         self.spec.summary = Some(text.into());
@@ -580,6 +642,23 @@ where
         self
     }
 
+    /// Add a request header parameter (string-typed)
+    pub fn header_param(
+        mut self,
+        name: impl Into<String>,
+        required: bool,
+        description: impl Into<String>,
+    ) -> Self {
+        self.spec.params.push(ParamSpec {
+            name: name.into(),
+            location: ParamLocation::Header,
+            required,
+            description: Some(description.into()),
+            param_type: "string".to_owned(),
+        });
+        self
+    }
+
     /// Attach a JSON request body by *schema name* that you've already registered.
     /// This variant sets a description (`Some(desc)`) and marks the body as **required**.
     pub fn json_request_schema(
@@ -926,6 +1005,7 @@ where
     pub fn authenticated(mut self) -> OperationBuilder<H, R, S, AuthSet, L> {
         self.spec.authenticated = true;
         self.spec.is_public = false;
+        self.spec.optional_auth = false;
         OperationBuilder {
             spec: self.spec,
             method_router: self.method_router,
@@ -963,6 +1043,49 @@ where
     pub fn public(mut self) -> OperationBuilder<H, R, S, AuthSet, LicenseSet> {
         self.spec.is_public = true;
         self.spec.authenticated = false;
+        self.spec.optional_auth = false;
+        OperationBuilder {
+            spec: self.spec,
+            method_router: self.method_router,
+            _has_handler: self._has_handler,
+            _has_response: self._has_response,
+            _state: self._state,
+            _auth_state: PhantomData,
+            _license_state: PhantomData,
+        }
+    }
+
+    /// Mark this route as public with optional authentication.
+    ///
+    /// Requests without a bearer token are served with an anonymous
+    /// `SecurityContext`, as for [`public`](Self::public). A token that is
+    /// present is still validated: a valid one yields the caller's context and
+    /// an invalid one is rejected rather than downgraded to anonymous.
+    ///
+    /// This method transitions from `AuthNotSet` to `AuthSet` state.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use axum::Router;
+    /// # use http::StatusCode;
+    /// # use modkit::api::{
+    /// #     openapi_registry::OpenApiRegistryImpl,
+    /// #     operation_builder::OperationBuilder,
+    /// # };
+    /// # async fn list_articles() -> &'static str { "[]" }
+    /// # let registry = OpenApiRegistryImpl::new();
+    /// # let router: Router<()> = Router::new();
+    /// let router = OperationBuilder::get("/blog/v1/articles")
+    ///     .optional_auth()
+    ///     .handler(list_articles)
+    ///     .json_response(StatusCode::OK, "Articles, with drafts for signed-in authors")
+    ///     .register(router, &registry);
+    /// # let _ = router;
+    /// ```
+    pub fn optional_auth(mut self) -> OperationBuilder<H, R, S, AuthSet, LicenseSet> {
+        self.spec.is_public = true;
+        self.spec.authenticated = false;
+        self.spec.optional_auth = true;
         OperationBuilder {
             spec: self.spec,
             method_router: self.method_router,
@@ -5,6 +5,8 @@
 //! - Consumers fetch by *interface type* (trait object): `get::<dyn my::Api>()`.
 //! - For plugin-like scenarios, multiple implementations of the same interface can coexist
 //!   under different scopes (e.g. selected by GTS instance ID).
+//! - A "latest" scope (`ClientScope::gts_type_latest`) resolves to the highest-version
+//!   GTS instance registered for a type; the resolution is cached until scoped registrations change.
 //!
 //! Implementation details:
 //! - Key = type name. We use `type_name::<T>()`, which works for `T = dyn Trait`.
@@ -35,6 +37,9 @@ impl fmt::Debug for TypeKey {
     }
 }
 
+const GTS_SCOPE_PREFIX: &str = "gts:";
+const GTS_LATEST_SCOPE_PREFIX: &str = "gts-latest:";
+
 /// A scope for resolving multiple implementations of the same interface type.
 ///
 /// This is intentionally opaque: the scope semantics are defined by the caller.
@@ -55,12 +60,35 @@ impl ClientScope {
     /// Internally we prefix the scope to avoid accidental collisions with other scope kinds.
     #[must_use]
     pub fn gts_id(gts_id: &str) -> Self {
-        let mut s = String::with_capacity("gts:".len() + gts_id.len());
-        s.push_str("gts:");
+        let mut s = String::with_capacity(GTS_SCOPE_PREFIX.len() + gts_id.len());
+        s.push_str(GTS_SCOPE_PREFIX);
         s.push_str(gts_id);
         Self(Arc::<str>::from(s))
     }
 
+    /// Create a scope that resolves to the highest-version instance of a GTS type.
+    ///
+    /// `type_id` is the type part of the instance IDs (e.g.
+    /// `gts.x.core.modkit.plugins.v1~x.core.tenant_resolver.plugin.v1~`); the trailing
+    /// `~` is added if missing. Among the `gts_id` scopes registered for the interface
+    /// whose last segment directly follows `type_id`, the one with the highest
+    /// `major.minor` version of that segment wins; ties go to the greatest ID.
+    #[must_use]
+    pub fn gts_type_latest(type_id: &str) -> Self {
+        let mut s = String::with_capacity(GTS_LATEST_SCOPE_PREFIX.len() + type_id.len() + 1);
+        s.push_str(GTS_LATEST_SCOPE_PREFIX);
+        s.push_str(type_id);
+        if !type_id.ends_with('~') {
+            s.push('~');
+        }
+        Self(Arc::<str>::from(s))
+    }
+
+    /// The GTS type ID of a [`gts_type_latest`](Self::gts_type_latest) scope.
+    fn gts_latest_type_id(&self) -> Option<&str> {
+        self.0.strip_prefix(GTS_LATEST_SCOPE_PREFIX)
+    }
+
     #[inline]
     #[must_use]
     pub fn as_str(&self) -> &str {
@@ -118,11 +146,16 @@ type ClientMap = HashMap<TypeKey, Boxed>;
 /// Internal map type for the scoped client hub.
 type ScopedClientMap = HashMap<ScopedKey, Boxed>;
 
+/// Resolved concrete scope per (type, "latest" scope).
+type LatestScopeMap = HashMap<ScopedKey, ClientScope>;
+
 /// Type-safe registry of clients keyed by interface type.
 #[derive(Default)]
 pub struct ClientHub {
     map: RwLock<ClientMap>,
     scoped_map: RwLock<ScopedClientMap>,
+    // Lock order: `scoped_map` before `latest_scopes`
+    latest_scopes: RwLock<LatestScopeMap>,
 }
 
 impl ClientHub {
@@ -132,8 +165,54 @@ impl ClientHub {
         Self {
             map: RwLock::new(HashMap::new()),
             scoped_map: RwLock::new(HashMap::new()),
+            latest_scopes: RwLock::new(HashMap::new()),
         }
     }
+
+    /// Map a "latest" scope to the concrete scope it currently resolves to;
+    /// other scopes are returned unchanged.
+    fn resolve_scope(&self, type_key: &TypeKey, scope: &ClientScope) -> ClientScope {
+        let Some(type_id) = scope.gts_latest_type_id() else {
+            return scope.clone();
+        };
+        let key = ScopedKey {
+            type_key: type_key.clone(),
+            scope: scope.clone(),
+        };
+        if let Some(resolved) = self.latest_scopes.read().get(&key) {
+            return resolved.clone();
+        }
+
+        // Hold the map while caching so a concurrent registration cannot be missed
+        let map = self.scoped_map.read();
+        let Some(resolved) = latest_gts_scope(&map, type_key, type_id) else {
+            return scope.clone();
+        };
+        self.latest_scopes.write().insert(key, resolved.clone());
+        resolved
+    }
+}
+
+/// Highest-version `gts_id` scope registered for `type_key` that is a direct instance of `type_id`.
+fn latest_gts_scope(
+    map: &ScopedClientMap,
+    type_key: &TypeKey,
+    type_id: &str,
+) -> Option<ClientScope> {
+    map.keys()
+        .filter(|key| &key.type_key == type_key)
+        .filter_map(|key| {
+            let instance_id = key.scope.as_str().strip_prefix(GTS_SCOPE_PREFIX)?;
+            let tail = instance_id.strip_prefix(type_id)?;
+            if tail.is_empty() || tail.contains('~') {
+                return None;
+            }
+            let id = gts::GtsID::new(instance_id).ok()?;
+            let last = id.gts_id_segments.last()?;
+            Some(((last.ver_major, last.ver_minor.unwrap_or(0)), &key.scope))
+        })
+        .max_by(|(va, sa), (vb, sb)| va.cmp(vb).then_with(|| sa.as_str().cmp(sb.as_str())))
+        .map(|(_, scope)| scope.clone())
 }
 
 impl ClientHub {
@@ -152,6 +231,7 @@ impl ClientHub {
     ///
     /// This enables multiple implementations of the same interface to coexist,
     /// distinguished by a caller-defined `ClientScope` (e.g., a GTS instance ID).
+    /// Invalidates cached "latest" scope resolutions.
     pub fn register_scoped<T>(&self, scope: ClientScope, client: Arc<T>)
     where
         T: ?Sized + Send + Sync + 'static,
@@ -162,6 +242,7 @@ impl ClientHub {
         };
         let mut w = self.scoped_map.write();
         w.insert(key, Box::new(client));
+        self.latest_scopes.write().clear();
     }
 
     /// Fetch a client by interface type `T`.
@@ -187,8 +268,26 @@ impl ClientHub {
         Err(ClientHubError::TypeMismatch { type_key })
     }
 
+    /// Try to fetch a client by interface type `T`.
+    ///
+    /// Returns `None` if not registered or if the stored type doesn't match;
+    /// meant for optional dependencies a module can run without.
+    pub fn try_get<T>(&self) -> Option<Arc<T>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        let type_key = TypeKey::of::<T>();
+        let r = self.map.read();
+        let boxed = r.get(&type_key)?;
+
+        boxed.downcast_ref::<Arc<T>>().cloned()
+    }
+
     /// Fetch a scoped client by interface type `T` and scope.
     ///
+    /// A [`ClientScope::gts_type_latest`] scope is resolved to the concrete
+    /// instance scope first.
+    ///
     /// # Errors
     /// Returns `ClientHubError::ScopedNotFound` if no client is registered for the `(type, scope)` pair.
     /// Returns `ClientHubError::ScopedTypeMismatch` if the stored type doesn't match.
@@ -196,10 +295,9 @@ impl ClientHub {
     where
         T: ?Sized + Send + Sync + 'static,
     {
-        let key = ScopedKey {
-            type_key: TypeKey::of::<T>(),
-            scope: scope.clone(),
-        };
+        let type_key = TypeKey::of::<T>();
+        let scope = self.resolve_scope(&type_key, scope);
+        let key = ScopedKey { type_key, scope };
         let r = self.scoped_map.read();
 
         let boxed = r.get(&key).ok_or_else(|| ClientHubError::ScopedNotFound {
@@ -218,15 +316,15 @@ impl ClientHub {
 
     /// Try to fetch a scoped client by interface type `T` and scope.
     ///
+    /// Resolves "latest" scopes like [`get_scoped`](Self::get_scoped).
     /// Returns `None` if not found or if the stored type doesn't match.
     pub fn try_get_scoped<T>(&self, scope: &ClientScope) -> Option<Arc<T>>
     where
         T: ?Sized + Send + Sync + 'static,
     {
-        let key = ScopedKey {
-            type_key: TypeKey::of::<T>(),
-            scope: scope.clone(),
-        };
+        let type_key = TypeKey::of::<T>();
+        let scope = self.resolve_scope(&type_key, scope);
+        let key = ScopedKey { type_key, scope };
         let r = self.scoped_map.read();
         let boxed = r.get(&key)?;
 
@@ -255,13 +353,36 @@ impl ClientHub {
         };
         let mut w = self.scoped_map.write();
         let boxed = w.remove(&key)?;
+        self.latest_scopes.write().clear();
         boxed.downcast::<Arc<T>>().ok().map(|b| *b)
     }
 
+    /// List the scopes under which clients of interface type `T` are registered.
+    ///
+    /// Intended for diagnostics (e.g. logging candidate plugin instances).
+    /// Scopes are returned sorted by their string form.
+    #[must_use]
+    pub fn list_scopes<T>(&self) -> Vec<ClientScope>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        let type_key = TypeKey::of::<T>();
+        let mut scopes: Vec<ClientScope> = self
+            .scoped_map
+            .read()
+            .keys()
+            .filter(|key| key.type_key == type_key)
+            .map(|key| key.scope.clone())
+            .collect();
+        scopes.sort_unstable_by(|a, b| a.as_str().cmp(b.as_str()));
+        scopes
+    }
+
     /// Clear everything (useful in tests).
     pub fn clear(&self) {
         self.map.write().clear();
         self.scoped_map.write().clear();
+        self.latest_scopes.write().clear();
     }
 
     /// Introspection: (total entries).
@@ -304,6 +425,15 @@ mod tests {
         assert_eq!(Arc::as_ptr(&api), Arc::as_ptr(&got));
     }
 
+    #[test]
+    fn try_get_returns_none_until_registered() {
+        let hub = ClientHub::new();
+        assert!(hub.try_get::<str>().is_none());
+
+        hub.register::<str>(Arc::from("client"));
+        assert_eq!(hub.try_get::<str>().as_deref(), Some("client"));
+    }
+
     #[tokio::test]
     async fn remove_works() {
         let hub = ClientHub::new();
@@ -385,6 +515,24 @@ mod tests {
         assert_eq!(got.as_deref(), Some("scoped"));
     }
 
+    #[test]
+    fn list_scopes_returns_registered_scopes_for_type() {
+        let hub = ClientHub::new();
+        let scope_a = ClientScope::gts_id(
+            "gts.x.core.modkit.plugins.v1~x.core.tenant_resolver.plugin.v1~contoso.app._.plugin.v1.0",
+        );
+        let scope_b = ClientScope::gts_id(
+            "gts.x.core.modkit.plugins.v1~x.core.tenant_resolver.plugin.v1~fabrikam.app._.plugin.v1.0",
+        );
+        hub.register_scoped::<dyn TestApi>(scope_b.clone(), Arc::new(ImplA(2)));
+        hub.register_scoped::<dyn TestApi>(scope_a.clone(), Arc::new(ImplA(1)));
+        hub.register_scoped::<str>(ClientScope::new("other"), Arc::from("other"));
+        hub.register::<dyn TestApi>(Arc::new(ImplA(0)));
+
+        assert_eq!(hub.list_scopes::<dyn TestApi>(), vec![scope_a, scope_b]);
+        assert!(hub.list_scopes::<u32>().is_empty());
+    }
+
     #[test]
     fn try_get_scoped_returns_none_on_miss() {
         let hub = ClientHub::new();
@@ -395,4 +543,88 @@ mod tests {
         let got = hub.try_get_scoped::<str>(&scope);
         assert!(got.is_none());
     }
+
+    const PLUGIN_TYPE: &str = "gts.x.core.modkit.plugins.v1~x.core.tenant_resolver.plugin.v1~";
+
+    fn plugin_scope(instance: &str) -> ClientScope {
+        ClientScope::gts_id(&format!("{PLUGIN_TYPE}{instance}"))
+    }
+
+    #[tokio::test]
+    async fn gts_type_latest_resolves_highest_version() {
+        let hub = ClientHub::new();
+        hub.register_scoped::<dyn TestApi>(
+            plugin_scope("contoso.app._.plugin.v2.0"),
+            Arc::new(ImplA(2)),
+        );
+        hub.register_scoped::<dyn TestApi>(
+            plugin_scope("contoso.app._.plugin.v1.0"),
+            Arc::new(ImplA(1)),
+        );
+        // Instances of other types never compete
+        hub.register_scoped::<dyn TestApi>(
+            ClientScope::gts_id(
+                "gts.x.core.modkit.plugins.v1~x.core.authn_resolver.plugin.v1~contoso.app._.plugin.v9.0",
+            ),
+            Arc::new(ImplA(9)),
+        );
+
+        let latest = ClientScope::gts_type_latest(PLUGIN_TYPE);
+        assert_eq!(
+            hub.get_scoped::<dyn TestApi>(&latest).unwrap().id().await,
+            2
+        );
+        assert_eq!(
+            hub.try_get_scoped::<dyn TestApi>(&latest)
+                .unwrap()
+                .id()
+                .await,
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn gts_type_latest_follows_registration_changes() {
+        let hub = ClientHub::new();
+        let latest = ClientScope::gts_type_latest(PLUGIN_TYPE.trim_end_matches('~'));
+        hub.register_scoped::<dyn TestApi>(
+            plugin_scope("contoso.app._.plugin.v1.0"),
+            Arc::new(ImplA(1)),
+        );
+        hub.register_scoped::<dyn TestApi>(
+            plugin_scope("contoso.app._.plugin.v2.0"),
+            Arc::new(ImplA(2)),
+        );
+        assert_eq!(
+            hub.get_scoped::<dyn TestApi>(&latest).unwrap().id().await,
+            2
+        );
+
+        hub.register_scoped::<dyn TestApi>(
+            plugin_scope("contoso.app._.plugin.v3.0"),
+            Arc::new(ImplA(3)),
+        );
+        assert_eq!(
+            hub.get_scoped::<dyn TestApi>(&latest).unwrap().id().await,
+            3
+        );
+
+        hub.remove_scoped::<dyn TestApi>(&plugin_scope("contoso.app._.plugin.v3.0"));
+        assert_eq!(
+            hub.get_scoped::<dyn TestApi>(&latest).unwrap().id().await,
+            2
+        );
+    }
+
+    #[test]
+    fn gts_type_latest_without_instances_is_not_found() {
+        let hub = ClientHub::new();
+        let latest = ClientScope::gts_type_latest(PLUGIN_TYPE);
+
+        assert!(matches!(
+            hub.get_scoped::<str>(&latest),
+            Err(ClientHubError::ScopedNotFound { scope, .. }) if scope == latest
+        ));
+        assert!(hub.try_get_scoped::<str>(&latest).is_none());
+    }
 }
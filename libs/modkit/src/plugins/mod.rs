@@ -97,6 +97,35 @@ impl GtsPluginSelector {
         let mut guard = self.cached.write();
         guard.take().is_some()
     }
+
+    /// Drops the cached instance ID so the next [`get_or_init`](Self::get_or_init)
+    /// re-runs resolution.
+    ///
+    /// Unlike [`reset`](Self::reset), this does not wait for an in-flight
+    /// resolution, so it can be called from sync code or hot error paths.
+    ///
+    /// Returns `true` if there was a cached value, `false` otherwise.
+    pub fn invalidate(&self) -> bool {
+        self.cached.write().take().is_some()
+    }
+
+    /// Like [`invalidate`](Self::invalidate), but only while the cache still
+    /// holds `instance_id`.
+    ///
+    /// Use this when `instance_id` turned out to be unusable: a concurrent
+    /// caller that has already re-resolved to another instance keeps its
+    /// result instead of forcing yet another resolution.
+    ///
+    /// Returns `true` if the cached value was dropped.
+    pub fn invalidate_if_selected(&self, instance_id: &str) -> bool {
+        let mut guard = self.cached.write();
+        if guard.as_deref() == Some(instance_id) {
+            *guard = None;
+            true
+        } else {
+            false
+        }
+    }
 }
 
 /// Error returned by [`choose_plugin_instance`].
@@ -121,6 +150,9 @@ pub enum ChoosePluginError {
     },
 }
 
+/// Vendor preference entry that matches an instance from any vendor.
+pub const ANY_VENDOR: &str = "*";
+
 /// Selects the best plugin instance for the given vendor.
 ///
 /// Accepts an iterator of `(gts_id, content)` pairs — typically
@@ -135,7 +167,9 @@ pub enum ChoosePluginError {
 ///
 /// Deserializes each entry as `BaseModkitPluginV1<P>`, filters by
 /// `vendor`, and returns the `gts_id` of the instance with the
-/// **lowest** priority value.
+/// **lowest** priority value. Among instances with equal priority the
+/// lexicographically smallest `gts_id` wins, so the result does not depend
+/// on the iteration order of `instances`.
 ///
 /// # Type Parameters
 ///
@@ -154,11 +188,35 @@ pub fn choose_plugin_instance<'a, P>(
 where
     P: for<'de> gts::GtsDeserialize<'de> + gts::GtsSchema,
 {
-    let mut best: Option<(&str, i16)> = None;
-    let mut count: usize = 0;
+    choose_plugin_instance_by_vendor_preference::<P>(&[vendor], instances)
+}
+
+/// Selects the best plugin instance from an ordered vendor preference list.
+///
+/// Vendors are tried in order: the first vendor with at least one instance
+/// wins, and within that vendor the instance is chosen exactly as in
+/// [`choose_plugin_instance`]. An [`ANY_VENDOR`] entry matches instances of
+/// every vendor, so `["acme", "hyperspot", "*"]` reads as "prefer acme, else
+/// hyperspot, else anything".
+///
+/// Every instance is validated, even when an earlier vendor already matched.
+///
+/// # Errors
+///
+/// - [`ChoosePluginError::InvalidPluginInstance`] if deserialization fails
+///   or the `content.id` doesn't match `gts_id`.
+/// - [`ChoosePluginError::PluginNotFound`] if no instance matches any of the
+///   vendors; `vendor` lists them comma-separated.
+pub fn choose_plugin_instance_by_vendor_preference<'a, P>(
+    vendors: &[impl AsRef<str>],
+    instances: impl IntoIterator<Item = (&'a str, &'a serde_json::Value)>,
+) -> Result<String, ChoosePluginError>
+where
+    P: for<'de> gts::GtsDeserialize<'de> + gts::GtsSchema,
+{
+    let mut candidates: Vec<(&str, String, i16)> = Vec::new();
 
     for (gts_id, content_val) in instances {
-        count += 1;
         let content: BaseModkitPluginV1<P> =
             serde_json::from_value(content_val.clone()).map_err(|e| {
                 tracing::error!(
@@ -182,27 +240,35 @@ where
             });
         }
 
-        if content.vendor != vendor {
-            continue;
-        }
+        candidates.push((gts_id, content.vendor, content.priority));
+    }
 
-        match &best {
-            None => best = Some((gts_id, content.priority)),
-            Some((_, cur_priority)) => {
-                if content.priority < *cur_priority {
-                    best = Some((gts_id, content.priority));
-                }
-            }
+    let vendor_list = vendors
+        .iter()
+        .map(AsRef::as_ref)
+        .collect::<Vec<_>>()
+        .join(", ");
+    tracing::debug!(
+        vendors = %vendor_list,
+        instance_count = candidates.len(),
+        "choose_plugin_instance"
+    );
+
+    for vendor in vendors.iter().map(AsRef::as_ref) {
+        // Ties on priority go to the lexicographically smallest `gts_id`
+        let best = candidates
+            .iter()
+            .filter(|(_, v, _)| vendor == ANY_VENDOR || v == vendor)
+            .min_by_key(|(gts_id, _, priority)| (*priority, *gts_id));
+        if let Some((gts_id, _, _)) = best {
+            return Ok((*gts_id).to_owned());
         }
     }
 
-    tracing::debug!(vendor, instance_count = count, "choose_plugin_instance");
-
-    best.map(|(gts_id, _)| gts_id.to_owned())
-        .ok_or_else(|| ChoosePluginError::PluginNotFound {
-            schema_id: P::SCHEMA_ID.to_owned(),
-            vendor: vendor.to_owned(),
-        })
+    Err(ChoosePluginError::PluginNotFound {
+        schema_id: P::SCHEMA_ID.to_owned(),
+        vendor: vendor_list,
+    })
 }
 
 #[cfg(test)]
@@ -212,6 +278,111 @@ mod tests {
     use std::sync::Arc;
     use std::sync::atomic::{AtomicUsize, Ordering};
 
+    use gts_macros::struct_to_gts_schema;
+
+    #[struct_to_gts_schema(
+        dir_path = "schemas",
+        base = BaseModkitPluginV1,
+        schema_id = "gts.x.core.modkit.plugin.v1~x.core.test.plugin.v1~",
+        description = "Test plugin specification",
+        properties = ""
+    )]
+    pub struct TestPluginSpecV1;
+
+    fn instance(suffix: &str, vendor: &str, priority: i16) -> (String, serde_json::Value) {
+        let gts_id = format!("{}{suffix}", TestPluginSpecV1::gts_schema_id());
+        let content = serde_json::json!({
+            "id": gts_id,
+            "vendor": vendor,
+            "priority": priority,
+            "properties": {}
+        });
+        (gts_id, content)
+    }
+
+    fn choose(instances: &[(String, serde_json::Value)]) -> Result<String, ChoosePluginError> {
+        choose_plugin_instance::<TestPluginSpecV1>(
+            "hyperspot",
+            instances.iter().map(|(id, c)| (id.as_str(), c)),
+        )
+    }
+
+    #[test]
+    fn choose_prefers_lowest_priority() {
+        let instances = vec![
+            instance("b.test._.plugin.v1", "hyperspot", 10),
+            instance("c.test._.plugin.v1", "hyperspot", 5),
+            instance("a.test._.plugin.v1", "other", 0),
+        ];
+        assert_eq!(choose(&instances).unwrap(), instances[1].0);
+    }
+
+    #[test]
+    fn choose_breaks_priority_ties_by_smallest_gts_id() {
+        let mut instances = vec![
+            instance("b.test._.plugin.v1", "hyperspot", 0),
+            instance("a.test._.plugin.v1", "hyperspot", 0),
+            instance("c.test._.plugin.v1", "hyperspot", 0),
+        ];
+        let expected = instances[1].0.clone();
+
+        for _ in 0..instances.len() {
+            assert_eq!(choose(&instances).unwrap(), expected);
+            instances.rotate_left(1);
+        }
+        instances.reverse();
+        assert_eq!(choose(&instances).unwrap(), expected);
+    }
+
+    fn choose_preferred(
+        vendors: &[&str],
+        instances: &[(String, serde_json::Value)],
+    ) -> Result<String, ChoosePluginError> {
+        choose_plugin_instance_by_vendor_preference::<TestPluginSpecV1>(
+            vendors,
+            instances.iter().map(|(id, c)| (id.as_str(), c)),
+        )
+    }
+
+    #[test]
+    fn preference_falls_through_to_next_vendor() {
+        let instances = vec![
+            instance("a.test._.plugin.v1", "other", 0),
+            instance("b.test._.plugin.v1", "fallback", 10),
+            instance("c.test._.plugin.v1", "fallback", 5),
+        ];
+        assert_eq!(
+            choose_preferred(&["preferred", "fallback"], &instances).unwrap(),
+            instances[2].0
+        );
+        assert_eq!(
+            choose_preferred(&["fallback", "other"], &instances).unwrap(),
+            instances[2].0
+        );
+    }
+
+    #[test]
+    fn preference_wildcard_matches_any_vendor() {
+        let instances = vec![
+            instance("a.test._.plugin.v1", "other", 3),
+            instance("b.test._.plugin.v1", "another", 1),
+        ];
+        assert_eq!(
+            choose_preferred(&["preferred", ANY_VENDOR], &instances).unwrap(),
+            instances[1].0
+        );
+    }
+
+    #[test]
+    fn preference_without_match_lists_vendors() {
+        let instances = vec![instance("a.test._.plugin.v1", "other", 0)];
+        let err = choose_preferred(&["preferred", "fallback"], &instances).unwrap_err();
+        assert!(matches!(
+            err,
+            ChoosePluginError::PluginNotFound { ref vendor, .. } if vendor == "preferred, fallback"
+        ));
+    }
+
     #[tokio::test]
     async fn resolve_called_once_returns_same_str() {
         let selector = GtsPluginSelector::new();
@@ -284,6 +455,47 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 2);
     }
 
+    #[tokio::test]
+    async fn invalidate_forces_re_resolution() {
+        let selector = GtsPluginSelector::new();
+        assert!(!selector.invalidate());
+
+        let id_a = selector
+            .get_or_init(|| async { Ok::<_, std::convert::Infallible>("a".to_owned()) })
+            .await
+            .unwrap();
+        assert_eq!(&*id_a, "a");
+
+        assert!(selector.invalidate());
+        assert!(!selector.invalidate());
+
+        let id_b = selector
+            .get_or_init(|| async { Ok::<_, std::convert::Infallible>("b".to_owned()) })
+            .await
+            .unwrap();
+        assert_eq!(&*id_b, "b");
+    }
+
+    #[tokio::test]
+    async fn invalidate_if_selected_keeps_a_newer_selection() {
+        let selector = GtsPluginSelector::new();
+        selector
+            .get_or_init(|| async { Ok::<_, std::convert::Infallible>("b".to_owned()) })
+            .await
+            .unwrap();
+
+        // A caller that saw the stale "a" fail must not drop the fresh "b".
+        assert!(!selector.invalidate_if_selected("a"));
+        let id = selector
+            .get_or_init(|| async { Ok::<_, std::convert::Infallible>("c".to_owned()) })
+            .await
+            .unwrap();
+        assert_eq!(&*id, "b");
+
+        assert!(selector.invalidate_if_selected("b"));
+        assert!(!selector.invalidate_if_selected("b"));
+    }
+
     #[tokio::test]
     async fn concurrent_get_or_init_resolves_once() {
         let selector = Arc::new(GtsPluginSelector::new());
@@ -213,6 +213,8 @@ impl Default for CapabilitySet {
 pub struct ModuleEntry {
     pub(crate) name: &'static str,
     pub(crate) deps: &'static [&'static str],
+    pub(crate) optional_deps: &'static [&'static str],
+    pub(crate) unavailable_deps: Vec<&'static str>,
     pub(crate) core: Arc<dyn contracts::Module>,
     pub(crate) caps: CapabilitySet,
 }
@@ -230,6 +232,18 @@ impl ModuleEntry {
         self.deps
     }
 
+    /// Returns the optional dependency names, whether registered or not.
+    #[must_use]
+    pub fn optional_deps(&self) -> &'static [&'static str] {
+        self.optional_deps
+    }
+
+    /// Returns the optional dependencies that no registered module provides.
+    #[must_use]
+    pub fn unavailable_deps(&self) -> &[&'static str] {
+        &self.unavailable_deps
+    }
+
     /// Returns the capability set.
     #[must_use]
     pub fn caps(&self) -> &CapabilitySet {
@@ -242,6 +256,7 @@ impl std::fmt::Debug for ModuleEntry {
         f.debug_struct("ModuleEntry")
             .field("name", &self.name)
             .field("deps", &self.deps)
+            .field("optional_deps", &self.optional_deps)
             .field("has_rest", &self.caps.has::<RestApiCap>())
             .field("is_rest_host", &self.caps.has::<ApiGatewayCap>())
             .field("has_db", &self.caps.has_db())
@@ -334,6 +349,7 @@ type GrpcHubEntry = (&'static str, Arc<dyn contracts::GrpcHubCapability>);
 pub struct RegistryBuilder {
     core: HashMap<&'static str, Arc<dyn contracts::Module>>,
     deps: HashMap<&'static str, &'static [&'static str]>,
+    optional_deps: HashMap<&'static str, &'static [&'static str]>,
     capabilities: HashMap<&'static str, Vec<Capability>>,
     rest_host: Option<RestHostEntry>,
     grpc_hub: Option<GrpcHubEntry>,
@@ -363,6 +379,16 @@ impl RegistryBuilder {
         self.deps.insert(name, deps);
     }
 
+    /// Declare dependencies that order `name` after them when registered,
+    /// but do not fail the build when no module provides them.
+    pub fn register_optional_deps_with_meta(
+        &mut self,
+        name: &'static str,
+        deps: &'static [&'static str],
+    ) {
+        self.optional_deps.insert(name, deps);
+    }
+
     pub fn register_rest_with_meta(
         &mut self,
         name: &'static str,
@@ -571,6 +597,16 @@ impl RegistryBuilder {
             }
         }
 
+        for (&n, &deps) in &self.optional_deps {
+            let u = *idx
+                .get(n)
+                .ok_or_else(|| RegistryError::UnknownModule(n.to_owned()))?;
+            // Absent optional deps impose no ordering
+            for v in deps.iter().filter_map(|d| idx.get(d)) {
+                adj[*v].push(u);
+            }
+        }
+
         Ok((names, adj, idx))
     }
 
@@ -588,6 +624,13 @@ impl RegistryBuilder {
                 .get(name)
                 .ok_or_else(|| RegistryError::MissingDeps(name.to_owned()))?;
 
+            let optional_deps = self.optional_deps.get(name).copied().unwrap_or_default();
+            let unavailable_deps = optional_deps
+                .iter()
+                .copied()
+                .filter(|d| !self.core.contains_key(d))
+                .collect();
+
             let core = self
                 .core
                 .get(name)
@@ -621,6 +664,8 @@ impl RegistryBuilder {
             let entry = ModuleEntry {
                 name,
                 deps,
+                optional_deps,
+                unavailable_deps,
                 core,
                 caps,
             };
@@ -717,6 +762,23 @@ pub enum RegistryError {
         #[source]
         source: anyhow::Error,
     },
+    #[error(
+        "module '{module}' cannot initialize: dependency '{depends_on}' has not been initialized yet"
+    )]
+    DependencyNotInitialized {
+        module: &'static str,
+        depends_on: &'static str,
+    },
+    #[error(
+        "initialization failed for module '{module}': required client is not registered (declared deps: [{}]); the providing module may be missing from `deps`",
+        deps.join(", ")
+    )]
+    InitMissingClient {
+        module: &'static str,
+        deps: &'static [&'static str],
+        #[source]
+        source: anyhow::Error,
+    },
     #[error("post-init failed for module '{module}'")]
     PostInit {
         module: &'static str,
@@ -1020,6 +1082,23 @@ mod tests {
         assert!(beta.caps().has::<RestApiCap>());
     }
 
+    #[test]
+    fn optional_deps_order_when_present_and_are_reported_when_absent() {
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("plugin", &[], Arc::new(DummyCore));
+        b.register_optional_deps_with_meta("plugin", &["types-registry", "audit"]);
+        b.register_core_with_meta("types-registry", &[], Arc::new(DummyCore));
+
+        let reg = b.build_topo_sorted().unwrap();
+        let order: Vec<_> = reg.modules().iter().map(|m| m.name).collect();
+        assert_eq!(order, vec!["types-registry", "plugin"]);
+
+        let plugin = reg.modules().iter().find(|e| e.name() == "plugin").unwrap();
+        assert!(plugin.deps().is_empty());
+        assert_eq!(plugin.optional_deps(), &["types-registry", "audit"]);
+        assert_eq!(plugin.unavailable_deps(), &["audit"]);
+    }
+
     #[test]
     fn test_module_registry_builds() {
         let registry = ModuleRegistry::discover_and_build();
@@ -21,7 +21,7 @@ use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use crate::backends::OopSpawnConfig;
-use crate::client_hub::ClientHub;
+use crate::client_hub::{ClientHub, ClientHubError};
 use crate::config::ConfigProvider;
 use crate::context::ModuleContextBuilder;
 use crate::registry::{
@@ -326,7 +326,33 @@ impl HostRuntime {
     async fn run_init_phase(&self) -> Result<(), RegistryError> {
         tracing::info!("Phase: init");
 
+        let mut initialized: HashSet<&'static str> = HashSet::new();
         for entry in self.registry.modules_by_system_priority() {
+            // System-first ordering can place a module ahead of a declared
+            // dependency; fail with both names instead of a client lookup error.
+            let available_optional = entry
+                .optional_deps
+                .iter()
+                .filter(|d| !entry.unavailable_deps.contains(d));
+            if let Some(&depends_on) = entry
+                .deps
+                .iter()
+                .chain(available_optional)
+                .find(|d| !initialized.contains(*d))
+            {
+                return Err(RegistryError::DependencyNotInitialized {
+                    module: entry.name,
+                    depends_on,
+                });
+            }
+            if !entry.unavailable_deps.is_empty() {
+                tracing::info!(
+                    module = entry.name,
+                    unavailable = ?entry.unavailable_deps,
+                    "Optional dependencies are not registered; module runs without them"
+                );
+            }
+
             let ctx =
                 self.ctx_builder
                     .for_module(entry.name)
@@ -340,16 +366,37 @@ impl HostRuntime {
                 .core
                 .init(&ctx)
                 .await
-                .map_err(|e| RegistryError::Init {
-                    module: entry.name,
-                    source: e,
-                })?;
+                .map_err(|e| Self::init_error(entry, e))?;
             tracing::info!(module = entry.name, "Initialized a module.");
+            initialized.insert(entry.name);
         }
 
         Ok(())
     }
 
+    /// Map an `init()` failure, surfacing unresolved hub clients together
+    /// with the module's declared dependencies.
+    fn init_error(entry: &ModuleEntry, source: anyhow::Error) -> RegistryError {
+        let missing_client = source.chain().any(|cause| {
+            matches!(
+                cause.downcast_ref::<ClientHubError>(),
+                Some(ClientHubError::NotFound { .. } | ClientHubError::ScopedNotFound { .. })
+            )
+        });
+        if missing_client {
+            RegistryError::InitMissingClient {
+                module: entry.name,
+                deps: entry.deps,
+                source,
+            }
+        } else {
+            RegistryError::Init {
+                module: entry.name,
+                source,
+            }
+        }
+    }
+
     /// `POST_INIT` phase: optional hook after ALL modules completed `init()`.
     ///
     /// This provides a global barrier between initialization-time registration
@@ -1154,6 +1201,152 @@ mod tests {
         );
     }
 
+    fn runtime_for(registry: ModuleRegistry) -> HostRuntime {
+        HostRuntime::new(
+            registry,
+            Arc::new(EmptyConfigProvider),
+            DbOptions::None,
+            Arc::new(ClientHub::new()),
+            CancellationToken::new(),
+            Uuid::new_v4(),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_init_fails_when_dependency_not_initialized() {
+        struct Noop;
+
+        #[async_trait::async_trait]
+        impl Module for Noop {
+            async fn init(&self, _ctx: &ModuleCtx) -> anyhow::Result<()> {
+                Ok(())
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl SystemCapability for Noop {}
+
+        // A system plugin depending on a user module: system-first ordering
+        // would otherwise init the plugin before its dependency.
+        let plugin = Arc::new(Noop);
+        let mut builder = RegistryBuilder::default();
+        builder.register_core_with_meta("types-registry", &[], Arc::new(Noop) as Arc<dyn Module>);
+        builder.register_core_with_meta(
+            "static-tr-plugin",
+            &["types-registry"],
+            plugin.clone() as Arc<dyn Module>,
+        );
+        builder.register_system_with_meta("static-tr-plugin", plugin as Arc<dyn SystemCapability>);
+
+        let err = runtime_for(builder.build_topo_sorted().unwrap())
+            .run_init_phase()
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            RegistryError::DependencyNotInitialized {
+                module: "static-tr-plugin",
+                depends_on: "types-registry",
+            }
+        ));
+        let msg = err.to_string();
+        assert!(
+            msg.contains("static-tr-plugin") && msg.contains("types-registry"),
+            "{msg}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_missing_client_names_module_and_deps() {
+        trait Registry: Send + Sync {}
+
+        struct NeedsRegistry;
+
+        #[async_trait::async_trait]
+        impl Module for NeedsRegistry {
+            async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+                ctx.client_hub().get::<dyn Registry>()?;
+                Ok(())
+            }
+        }
+
+        struct Noop;
+
+        #[async_trait::async_trait]
+        impl Module for Noop {
+            async fn init(&self, _ctx: &ModuleCtx) -> anyhow::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut builder = RegistryBuilder::default();
+        builder.register_core_with_meta("types-registry", &[], Arc::new(Noop) as Arc<dyn Module>);
+        builder.register_core_with_meta(
+            "static-tr-plugin",
+            &["types-registry"],
+            Arc::new(NeedsRegistry) as Arc<dyn Module>,
+        );
+
+        let err = runtime_for(builder.build_topo_sorted().unwrap())
+            .run_init_phase()
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            RegistryError::InitMissingClient {
+                module: "static-tr-plugin",
+                ..
+            }
+        ));
+        let msg = err.to_string();
+        assert!(
+            msg.contains("static-tr-plugin") && msg.contains("types-registry"),
+            "{msg}"
+        );
+        let source = std::error::Error::source(&err).unwrap().to_string();
+        assert!(source.contains("client not found"), "{source}");
+    }
+
+    #[tokio::test]
+    async fn test_init_succeeds_without_optional_dependency() {
+        use std::sync::atomic::AtomicBool;
+
+        trait Registry: Send + Sync {}
+
+        struct DegradedPlugin {
+            registry_available: AtomicBool,
+        }
+
+        #[async_trait::async_trait]
+        impl Module for DegradedPlugin {
+            async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+                let registry = ctx.client_hub().try_get::<dyn Registry>();
+                self.registry_available
+                    .store(registry.is_some(), Ordering::SeqCst);
+                Ok(())
+            }
+        }
+
+        let plugin = Arc::new(DegradedPlugin {
+            registry_available: AtomicBool::new(true),
+        });
+        let mut builder = RegistryBuilder::default();
+        builder.register_core_with_meta("static-tr-plugin", &[], plugin.clone() as Arc<dyn Module>);
+        builder.register_optional_deps_with_meta("static-tr-plugin", &["types-registry"]);
+        let registry = builder.build_topo_sorted().unwrap();
+        assert_eq!(
+            registry.modules()[0].unavailable_deps(),
+            &["types-registry"]
+        );
+
+        runtime_for(registry).run_init_phase().await.unwrap();
+
+        assert!(!plugin.registry_available.load(Ordering::SeqCst));
+    }
+
     #[tokio::test]
     async fn test_stop_phase_provides_fresh_deadline_token() {
         use std::sync::atomic::AtomicBool;
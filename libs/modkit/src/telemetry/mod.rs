@@ -14,4 +14,4 @@ pub use config::{
 #[cfg(feature = "otel")]
 pub use init::init_tracing;
 pub use init::{init_metrics_provider, shutdown_tracing};
-pub use throttled_log::ThrottledLog;
+pub use throttled_log::{KeyedThrottledLog, ThrottledLog};
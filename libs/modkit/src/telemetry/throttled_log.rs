@@ -1,35 +1,53 @@
-//! Lock-free throttled logging helper.
+//! Throttled logging helpers.
 //!
 //! Provides a reusable mechanism to limit log frequency without
-//! performing any logging itself.
+//! performing any logging itself: [`ThrottledLog`] is a single lock-free
+//! throttle and [`KeyedThrottledLog`] keeps one throttle per key.
 
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
+use parking_lot::Mutex;
+
+/// Bits of the packed state used for the per-window log count.
+const COUNT_BITS: u32 = 16;
+const COUNT_MASK: u64 = (1 << COUNT_BITS) - 1;
+
 /// A lock-free helper that decides whether logging is allowed at the current moment.
 ///
 /// Uses monotonic time (`Instant`) and atomic operations to ensure correct
 /// behavior under concurrency without any locks or allocations on the hot path.
 ///
+/// Each throttle interval lets through up to `burst` logs (1 by default).
+/// Rejected calls are counted so the next emitted log can report them.
+///
 /// # Example
 ///
 /// ```
 /// use std::time::Duration;
 /// use modkit::telemetry::ThrottledLog;
 ///
-/// let throttle = ThrottledLog::new(Duration::from_secs(10));
+/// let throttle = ThrottledLog::new(Duration::from_secs(10)).with_burst(3);
 ///
 /// if throttle.should_log() {
-///     // Perform logging here
+///     let suppressed = throttle.take_suppressed();
+///     // Perform logging here, e.g. "... (+{suppressed} suppressed)"
 /// }
 /// ```
 pub struct ThrottledLog {
     /// Monotonic start time for computing elapsed milliseconds.
     start: Instant,
-    /// Next allowed log time in milliseconds since `start`.
-    next_log_ms: AtomicU64,
+    /// Packed window state: end of the current window in milliseconds since
+    /// `start` (high bits) and logs allowed within it (low `COUNT_BITS` bits).
+    state: AtomicU64,
+    /// Calls rejected since the last [`ThrottledLog::take_suppressed`].
+    suppressed: AtomicU64,
     /// Throttle interval in milliseconds.
     throttle_ms: u64,
+    /// Logs allowed per interval.
+    burst: u64,
 }
 
 fn u64_millis(d: Duration) -> u64 {
@@ -37,33 +55,204 @@ fn u64_millis(d: Duration) -> u64 {
     u64::try_from(ms).unwrap_or(u64::MAX)
 }
 
+const fn pack(window_end_ms: u64, count: u64) -> u64 {
+    (window_end_ms << COUNT_BITS) | count
+}
+
+const fn unpack(state: u64) -> (u64, u64) {
+    (state >> COUNT_BITS, state & COUNT_MASK)
+}
+
 impl ThrottledLog {
     /// Creates a new throttled log helper with the given throttle interval.
     #[must_use]
     pub fn new(throttle: Duration) -> Self {
         Self {
             start: Instant::now(),
-            next_log_ms: AtomicU64::new(0),
-            throttle_ms: u64_millis(throttle),
+            state: AtomicU64::new(0),
+            suppressed: AtomicU64::new(0),
+            // Keep window ends representable in the packed state
+            throttle_ms: u64_millis(throttle).min(u64::MAX >> (COUNT_BITS + 1)),
+            burst: 1,
         }
     }
 
+    /// Allows up to `burst` logs per throttle interval (clamped to `1..=65535`).
+    #[must_use]
+    pub fn with_burst(mut self, burst: u16) -> Self {
+        self.burst = u64::from(burst.max(1));
+        self
+    }
+
     /// Returns `true` if logging is allowed at the current moment.
     ///
     /// Uses compare-and-swap to ensure that under concurrent calls,
-    /// only one caller per throttle interval receives `true`.
+    /// at most `burst` callers per throttle interval receive `true`.
     pub fn should_log(&self) -> bool {
         let now_ms = u64_millis(self.start.elapsed());
-        let next = self.next_log_ms.load(Ordering::Relaxed);
+        let allowed = self
+            .state
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |state| {
+                let (window_end, count) = unpack(state);
+                if now_ms >= window_end {
+                    Some(pack(now_ms.saturating_add(self.throttle_ms), 1))
+                } else if count < self.burst {
+                    Some(pack(window_end, count + 1))
+                } else {
+                    None
+                }
+            })
+            .is_ok();
 
-        if now_ms < next {
-            return false;
+        if !allowed {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
         }
+        allowed
+    }
+
+    /// Number of calls rejected since the last [`ThrottledLog::take_suppressed`].
+    #[must_use]
+    pub fn suppressed_count(&self) -> u64 {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+
+    /// Returns the suppressed count and resets it to zero.
+    ///
+    /// Call this when emitting a log so it can report how many were dropped.
+    pub fn take_suppressed(&self) -> u64 {
+        self.suppressed.swap(0, Ordering::Relaxed)
+    }
+}
+
+/// Per-key throttling, so one noisy source does not suppress another's logs.
+///
+/// Each key gets its own [`ThrottledLog`] with the same interval and burst.
+/// At most `capacity` keys are tracked; inserting a new key beyond that
+/// evicts the least recently used one, whose next call starts a fresh window
+/// and whose pending suppressed count is dropped.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use modkit::telemetry::KeyedThrottledLog;
+///
+/// let throttle = KeyedThrottledLog::new(Duration::from_secs(10), 256);
+///
+/// let gts_id = "gts.x.core.plugins.plugin.v1~";
+/// if throttle.should_log(&gts_id) {
+///     let suppressed = throttle.take_suppressed(&gts_id);
+///     // Perform logging here, e.g. "{gts_id}: ... (+{suppressed} suppressed)"
+/// }
+/// ```
+pub struct KeyedThrottledLog<K> {
+    entries: Mutex<KeyedEntries<K>>,
+    throttle: Duration,
+    burst: u16,
+    capacity: usize,
+}
+
+struct KeyedEntries<K> {
+    map: HashMap<K, KeyedEntry>,
+    /// Monotonic use counter; the entry with the lowest `last_used` is evicted.
+    tick: u64,
+    /// Keys by last use, oldest first
+    by_use: BTreeMap<u64, K>,
+}
+
+struct KeyedEntry {
+    log: ThrottledLog,
+    last_used: u64,
+}
+
+impl<K: Eq + Hash + Clone> KeyedThrottledLog<K> {
+    /// Creates a keyed throttle tracking at most `capacity` keys (at least 1).
+    #[must_use]
+    pub fn new(throttle: Duration, capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(KeyedEntries {
+                map: HashMap::new(),
+                tick: 0,
+                by_use: BTreeMap::new(),
+            }),
+            throttle,
+            burst: 1,
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Allows up to `burst` logs per key and throttle interval (see [`ThrottledLog::with_burst`]).
+    #[must_use]
+    pub fn with_burst(mut self, burst: u16) -> Self {
+        self.burst = burst;
+        self
+    }
+
+    /// Returns `true` if logging for `key` is allowed at the current moment.
+    ///
+    /// Runs in `O(log capacity)` under the internal lock.
+    pub fn should_log(&self, key: &K) -> bool {
+        let mut guard = self.entries.lock();
+        let entries = &mut *guard;
+        entries.tick += 1;
+        let tick = entries.tick;
+
+        if let Some(entry) = entries.map.get_mut(key) {
+            entries.by_use.remove(&entry.last_used);
+            entries.by_use.insert(tick, key.clone());
+            entry.last_used = tick;
+            return entry.log.should_log();
+        }
+
+        while entries.map.len() >= self.capacity {
+            let Some((_, oldest)) = entries.by_use.pop_first() else {
+                break;
+            };
+            entries.map.remove(&oldest);
+        }
+
+        let log = ThrottledLog::new(self.throttle).with_burst(self.burst);
+        let allowed = log.should_log();
+        entries.by_use.insert(tick, key.clone());
+        entries.map.insert(
+            key.clone(),
+            KeyedEntry {
+                log,
+                last_used: tick,
+            },
+        );
+        allowed
+    }
+
+    /// Number of calls for `key` rejected since its last [`KeyedThrottledLog::take_suppressed`].
+    #[must_use]
+    pub fn suppressed_count(&self, key: &K) -> u64 {
+        self.entries
+            .lock()
+            .map
+            .get(key)
+            .map_or(0, |entry| entry.log.suppressed_count())
+    }
 
-        let new_next = now_ms.saturating_add(self.throttle_ms);
-        self.next_log_ms
-            .compare_exchange(next, new_next, Ordering::Relaxed, Ordering::Relaxed)
-            .is_ok()
+    /// Returns the suppressed count for `key` and resets it to zero.
+    pub fn take_suppressed(&self, key: &K) -> u64 {
+        self.entries
+            .lock()
+            .map
+            .get(key)
+            .map_or(0, |entry| entry.log.take_suppressed())
+    }
+
+    /// Number of keys currently tracked.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.lock().map.len()
+    }
+
+    /// Returns `true` if no key is tracked.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
 
@@ -83,4 +272,83 @@ mod tests {
         assert!(throttle.should_log());
         assert!(!throttle.should_log());
     }
+
+    #[test]
+    fn burst_allows_first_n_then_counts_suppressed() {
+        let throttle = ThrottledLog::new(Duration::from_secs(10)).with_burst(3);
+
+        let allowed = (0..10).filter(|_| throttle.should_log()).count();
+        assert_eq!(allowed, 3);
+        assert_eq!(throttle.suppressed_count(), 7);
+
+        assert_eq!(throttle.take_suppressed(), 7);
+        assert_eq!(throttle.suppressed_count(), 0);
+
+        assert!(!throttle.should_log());
+        assert_eq!(throttle.suppressed_count(), 1);
+    }
+
+    #[test]
+    fn new_interval_restores_burst() {
+        let throttle = ThrottledLog::new(Duration::from_millis(20)).with_burst(2);
+        assert!(throttle.should_log());
+        assert!(throttle.should_log());
+        assert!(!throttle.should_log());
+
+        std::thread::sleep(Duration::from_millis(30));
+
+        assert!(throttle.should_log());
+        assert!(throttle.should_log());
+        assert!(!throttle.should_log());
+        assert_eq!(throttle.take_suppressed(), 2);
+    }
+
+    #[test]
+    fn zero_burst_is_treated_as_one() {
+        let throttle = ThrottledLog::new(Duration::from_secs(10)).with_burst(0);
+        assert!(throttle.should_log());
+        assert!(!throttle.should_log());
+    }
+
+    #[test]
+    fn keys_are_throttled_independently() {
+        let throttle = KeyedThrottledLog::new(Duration::from_secs(10), 16);
+
+        assert!(throttle.should_log(&"plugin-a"));
+        assert!(throttle.should_log(&"plugin-b"));
+
+        assert!(!throttle.should_log(&"plugin-a"));
+        assert!(!throttle.should_log(&"plugin-a"));
+        assert!(!throttle.should_log(&"plugin-b"));
+
+        assert_eq!(throttle.take_suppressed(&"plugin-a"), 2);
+        assert_eq!(throttle.suppressed_count(&"plugin-b"), 1);
+        assert_eq!(throttle.suppressed_count(&"plugin-c"), 0);
+    }
+
+    #[test]
+    fn keyed_burst_applies_per_key() {
+        let throttle = KeyedThrottledLog::new(Duration::from_secs(10), 16).with_burst(2);
+
+        let allowed_a = (0..5).filter(|_| throttle.should_log(&1)).count();
+        let allowed_b = (0..5).filter(|_| throttle.should_log(&2)).count();
+        assert_eq!((allowed_a, allowed_b), (2, 2));
+    }
+
+    #[test]
+    fn least_recently_used_key_is_evicted() {
+        let throttle = KeyedThrottledLog::new(Duration::from_secs(10), 2);
+
+        assert!(throttle.should_log(&"a"));
+        assert!(throttle.should_log(&"b"));
+        // Touch "a" so "b" becomes the least recently used
+        assert!(!throttle.should_log(&"a"));
+
+        assert!(throttle.should_log(&"c"));
+        assert_eq!(throttle.len(), 2);
+
+        // "a" kept its window, "b" was forgotten and starts afresh
+        assert!(!throttle.should_log(&"a"));
+        assert!(throttle.should_log(&"b"));
+    }
 }
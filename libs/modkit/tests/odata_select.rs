@@ -77,6 +77,8 @@ fn test_odata_params_with_select() {
         filter: None,
         orderby: None,
         select: Some("id, name".to_owned()),
+        count: None,
+        expand: None,
         limit: None,
         cursor: None,
     };
@@ -475,6 +477,7 @@ fn test_page_to_projected_json() {
             next_cursor: Some("abc123".to_owned()),
             prev_cursor: None,
             limit: 10,
+            total_count: None,
         },
     };
 
@@ -531,6 +534,7 @@ fn test_page_to_projected_json_without_fields() {
             next_cursor: None,
             prev_cursor: None,
             limit: 20,
+            total_count: None,
         },
     };
 
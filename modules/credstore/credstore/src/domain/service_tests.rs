@@ -231,6 +231,49 @@ async fn get_plugin_caches_resolved_instance() {
     );
 }
 
+#[tokio::test]
+async fn get_plugin_reselects_after_unavailable_plugin() {
+    // Only the low-priority instance is known at first, and its client never registers.
+    let stale_id = format!("{}test._.stale.v1", CredStorePluginSpecV1::gts_schema_id());
+    let mut stale_content = plugin_content(&stale_id, "hyperspot");
+    stale_content["priority"] = serde_json::json!(10);
+    let entity = |gts_id: &str, content| GtsEntity {
+        id: Uuid::nil(),
+        gts_id: gts_id.to_owned(),
+        segments: vec![],
+        is_schema: false,
+        content,
+        description: None,
+    };
+
+    let hub = Arc::new(ClientHub::default());
+    let registry = Arc::new(MockRegistry::new(vec![entity(&stale_id, stale_content)]));
+    hub.register::<dyn TypesRegistryClient>(registry.clone() as Arc<dyn TypesRegistryClient>);
+
+    let svc = Service::new(hub.clone(), "hyperspot".into());
+    let err = svc.get_plugin().await.err().expect("expected Err");
+    assert!(
+        matches!(&err, DomainError::PluginUnavailable { gts_id, .. } if *gts_id == stale_id),
+        "expected PluginUnavailable for the stale instance, got: {err:?}"
+    );
+
+    // A higher-priority instance appears and registers its client.
+    let instance_id = test_instance_id();
+    registry.push(entity(
+        &instance_id,
+        plugin_content(&instance_id, "hyperspot"),
+    ));
+    hub.register_scoped::<dyn CredStorePluginClientV1>(
+        ClientScope::gts_id(&instance_id),
+        MockPlugin::returns(None),
+    );
+
+    svc.get_plugin()
+        .await
+        .expect("new instance should be selected after invalidation");
+    assert_eq!(registry.list_calls.load(Ordering::SeqCst), 2);
+}
+
 // ── get ──────────────────────────────────────────────────────────────────
 
 #[tokio::test]
@@ -3,8 +3,8 @@
 //! Provides `MockRegistry` and `MockPlugin` used by both `service` and
 //! `local_client` test modules.
 
-use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use credstore_sdk::{
@@ -38,7 +38,7 @@ pub fn test_ctx() -> SecurityContext {
 // ── MockRegistry ──────────────────────────────────────────────────────────────
 
 pub struct MockRegistry {
-    pub instances: Vec<GtsEntity>,
+    pub instances: Mutex<Vec<GtsEntity>>,
     pub list_calls: AtomicUsize,
     list_error: Option<TypesRegistryError>,
 }
@@ -47,7 +47,7 @@ impl MockRegistry {
     #[must_use]
     pub fn new(instances: Vec<GtsEntity>) -> Self {
         Self {
-            instances,
+            instances: Mutex::new(instances),
             list_calls: AtomicUsize::new(0),
             list_error: None,
         }
@@ -56,11 +56,20 @@ impl MockRegistry {
     #[must_use]
     pub fn failing(err: TypesRegistryError) -> Self {
         Self {
-            instances: vec![],
+            instances: Mutex::new(vec![]),
             list_calls: AtomicUsize::new(0),
             list_error: Some(err),
         }
     }
+
+    /// Adds an instance, simulating a plugin registered after startup.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the instances lock is poisoned.
+    pub fn push(&self, entity: GtsEntity) {
+        self.instances.lock().unwrap().push(entity);
+    }
 }
 
 #[async_trait]
@@ -70,11 +79,13 @@ impl TypesRegistryClient for MockRegistry {
         if let Some(ref e) = self.list_error {
             return Err(e.clone());
         }
-        Ok(self.instances.clone())
+        Ok(self.instances.lock().unwrap().clone())
     }
 
     async fn get(&self, gts_id: &str) -> Result<GtsEntity, TypesRegistryError> {
         self.instances
+            .lock()
+            .unwrap()
             .iter()
             .find(|e| e.gts_id == gts_id)
             .cloned()
@@ -87,6 +98,13 @@ impl TypesRegistryClient for MockRegistry {
     ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
         Ok(vec![])
     }
+
+    async fn register_atomic(
+        &self,
+        _entities: Vec<serde_json::Value>,
+    ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+        Ok(vec![])
+    }
 }
 
 // ── MockPlugin ────────────────────────────────────────────────────────────────
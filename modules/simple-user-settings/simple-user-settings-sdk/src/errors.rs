@@ -13,6 +13,9 @@ pub enum SettingsError {
     #[error("Access forbidden")]
     Forbidden,
 
+    #[error("Settings were modified since they were read")]
+    PreconditionFailed,
+
     #[error("Internal error")]
     Internal,
 }
@@ -36,6 +39,11 @@ impl SettingsError {
         Self::Forbidden
     }
 
+    #[must_use]
+    pub fn precondition_failed() -> Self {
+        Self::PreconditionFailed
+    }
+
     #[must_use]
     pub fn internal() -> Self {
         Self::Internal
@@ -19,12 +19,16 @@ pub struct SimpleUserSettings {
     pub language: Option<String>,
 }
 
-/// Partial update data for user settings.
+/// Partial update data for user settings (RFC 7386 JSON Merge Patch semantics).
+///
+/// Uses `Option<Option<String>>` to distinguish "leave unchanged" (None)
+/// from "clear" (`Some(None)`) and "set" (`Some(Some(value))`).
 #[domain_model]
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[allow(clippy::option_option)]
 pub struct SimpleUserSettingsPatch {
-    pub theme: Option<String>,
-    pub language: Option<String>,
+    pub theme: Option<Option<String>>,
+    pub language: Option<Option<String>>,
 }
 
 /// Full update data for user settings.
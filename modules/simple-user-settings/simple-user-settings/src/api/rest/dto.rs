@@ -30,13 +30,29 @@ pub struct UpdateSimpleUserSettingsRequest {
     pub language: String,
 }
 
+/// JSON Merge Patch body (RFC 7386): an omitted field is left unchanged,
+/// an explicit `null` clears it.
 #[derive(Debug)]
 #[modkit_macros::api_dto(request)]
+#[allow(clippy::option_option)]
 pub struct PatchSimpleUserSettingsRequest {
-    #[serde(default)]
-    pub theme: Option<String>,
-    #[serde(default)]
-    pub language: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<String>, nullable)]
+    pub theme: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<String>, nullable)]
+    pub language: Option<Option<String>>,
+}
+
+/// Maps a present field to `Some`, so `null` becomes `Some(None)`; absent
+/// fields fall back to `None` through `#[serde(default)]`.
+#[allow(clippy::option_option)]
+fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    serde::Deserialize::deserialize(deserializer).map(Some)
 }
 
 impl From<PatchSimpleUserSettingsRequest> for SimpleUserSettingsPatch {
@@ -38,14 +38,14 @@ mod tests {
     #[test]
     fn test_patch_request_to_settings_patch() {
         let req = dto::PatchSimpleUserSettingsRequest {
-            theme: Some("dark".to_owned()),
-            language: None,
+            theme: Some(Some("dark".to_owned())),
+            language: Some(None),
         };
 
         let patch: SimpleUserSettingsPatch = req.into();
 
-        assert_eq!(patch.theme, Some("dark".to_owned()));
-        assert_eq!(patch.language, None);
+        assert_eq!(patch.theme, Some(Some("dark".to_owned())));
+        assert_eq!(patch.language, Some(None));
     }
 
     #[test]
@@ -94,7 +94,16 @@ mod tests {
         let json = r#"{"theme":"dark"}"#;
         let req: dto::PatchSimpleUserSettingsRequest = serde_json::from_str(json).unwrap();
 
-        assert_eq!(req.theme, Some("dark".to_owned()));
+        assert_eq!(req.theme, Some(Some("dark".to_owned())));
         assert_eq!(req.language, None);
     }
+
+    #[test]
+    fn test_patch_request_deserialization_null_clears() {
+        let json = r#"{"theme":null,"language":"fr"}"#;
+        let req: dto::PatchSimpleUserSettingsRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(req.theme, Some(None));
+        assert_eq!(req.language, Some(Some("fr".to_owned())));
+    }
 }
@@ -14,7 +14,11 @@ pub fn domain_error_to_problem(e: &DomainError, instance: &str) -> Problem {
         DomainError::Validation { field, message } => {
             build_validation_problem(field, message, instance, trace_id)
         }
+        DomainError::InvalidValue { field, message } => {
+            build_invalid_value_problem(field, message, instance, trace_id)
+        }
         DomainError::Forbidden(msg) => build_forbidden_problem(e, msg, instance, trace_id),
+        DomainError::PreconditionFailed => build_precondition_failed_problem(instance, trace_id),
         DomainError::Internal(msg) => build_internal_problem(e, msg, instance, trace_id),
         DomainError::Database(_) => build_database_problem(e, instance, trace_id),
     }
@@ -41,6 +45,19 @@ fn build_validation_problem(
     )
 }
 
+fn build_invalid_value_problem(
+    field: &str,
+    message: &str,
+    instance: &str,
+    trace_id: Option<String>,
+) -> Problem {
+    ErrorCode::settings_simple_user_settings_invalid_value_v1().with_context(
+        format!("Invalid value for '{field}': {message}"),
+        instance,
+        trace_id,
+    )
+}
+
 fn build_forbidden_problem(
     e: &DomainError,
     msg: &str,
@@ -57,6 +74,26 @@ fn build_forbidden_problem(
     )
 }
 
+fn build_precondition_failed_problem(instance: &str, trace_id: Option<String>) -> Problem {
+    ErrorCode::settings_simple_user_settings_precondition_failed_v1().with_context(
+        "Settings were modified since they were read; fetch them again and retry",
+        instance,
+        trace_id,
+    )
+}
+
+/// Problem for a conditional write sent without `If-Match`.
+pub fn precondition_required_problem(instance: &str) -> Problem {
+    let trace_id = tracing::Span::current()
+        .id()
+        .map(|id| id.into_u64().to_string());
+    ErrorCode::settings_simple_user_settings_precondition_required_v1().with_context(
+        "If-Match header is required; use the ETag from GET",
+        instance,
+        trace_id,
+    )
+}
+
 fn build_internal_problem(
     e: &DomainError,
     msg: &str,
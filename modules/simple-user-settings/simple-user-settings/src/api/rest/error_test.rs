@@ -29,6 +29,24 @@ mod tests {
         assert!(problem.detail.contains("exceeds max length"));
     }
 
+    #[test]
+    fn test_invalid_value_error_to_problem() {
+        let error = DomainError::invalid_value("theme", "unknown theme 'neon'");
+        let problem = domain_error_to_problem(&error, "/api/settings");
+
+        assert_eq!(problem.status, StatusCode::BAD_REQUEST);
+        assert!(problem.detail.contains("theme"));
+        assert!(problem.detail.contains("neon"));
+    }
+
+    #[test]
+    fn test_precondition_failed_error_to_problem() {
+        let problem = domain_error_to_problem(&DomainError::PreconditionFailed, "/api/settings");
+
+        assert_eq!(problem.status, StatusCode::PRECONDITION_FAILED);
+        assert_eq!(problem.instance, "/api/settings");
+    }
+
     #[test]
     fn test_database_error_to_problem() {
         let error = DomainError::Database(modkit_db::DbError::InvalidConfig(
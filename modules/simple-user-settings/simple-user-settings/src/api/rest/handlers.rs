@@ -1,43 +1,67 @@
 use std::sync::Arc;
 
+use axum::http::{HeaderMap, Uri, header};
 use axum::{Json, extract::Extension};
 use modkit::api::prelude::*;
 use modkit_security::SecurityContext;
-use simple_user_settings_sdk::models::SimpleUserSettingsUpdate;
+use simple_user_settings_sdk::models::{SimpleUserSettings, SimpleUserSettingsUpdate};
 
+use crate::api::rest::error::precondition_required_problem;
 use crate::api::rest::routes::ConcreteService;
+use crate::domain::etag::settings_etag;
 
 use super::dto::{
     PatchSimpleUserSettingsRequest, SimpleUserSettingsDto, UpdateSimpleUserSettingsRequest,
 };
 
+/// Settings body with the matching `ETag` header for later `If-Match` writes.
+fn with_etag(settings: SimpleUserSettings) -> impl IntoResponse {
+    let etag = settings_etag(&settings);
+    let dto: SimpleUserSettingsDto = settings.into();
+    ([(header::ETAG, etag)], Json(dto))
+}
+
+/// Writes are conditional: the client must echo the `ETag` it last read.
+#[allow(clippy::result_large_err)]
+fn require_if_match<'a>(headers: &'a HeaderMap, uri: &Uri) -> Result<&'a str, Problem> {
+    headers
+        .get(header::IF_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| precondition_required_problem(uri.path()))
+}
+
 pub async fn get_settings(
     Extension(ctx): Extension<SecurityContext>,
     Extension(svc): Extension<Arc<ConcreteService>>,
-) -> ApiResult<JsonBody<SimpleUserSettingsDto>> {
+) -> ApiResult<impl IntoResponse> {
     let settings = svc.get_settings(&ctx).await?;
-    Ok(Json(settings.into()))
+    Ok(with_etag(settings))
 }
 
 pub async fn update_settings(
     Extension(ctx): Extension<SecurityContext>,
     Extension(svc): Extension<Arc<ConcreteService>>,
+    uri: Uri,
+    headers: HeaderMap,
     Json(req): Json<UpdateSimpleUserSettingsRequest>,
 ) -> ApiResult<impl IntoResponse> {
+    let if_match = require_if_match(&headers, &uri)?;
     let update = SimpleUserSettingsUpdate {
         theme: req.theme,
         language: req.language,
     };
-    let settings = svc.update_settings(&ctx, update).await?;
-    let dto: SimpleUserSettingsDto = settings.into();
-    Ok((StatusCode::OK, Json(dto)))
+    let settings = svc.update_settings(&ctx, update, Some(if_match)).await?;
+    Ok((StatusCode::OK, with_etag(settings)))
 }
 
 pub async fn patch_settings(
     Extension(ctx): Extension<SecurityContext>,
     Extension(svc): Extension<Arc<ConcreteService>>,
+    uri: Uri,
+    headers: HeaderMap,
     Json(req): Json<PatchSimpleUserSettingsRequest>,
-) -> ApiResult<JsonBody<SimpleUserSettingsDto>> {
-    let settings = svc.patch_settings(&ctx, req.into()).await?;
-    Ok(Json(settings.into()))
+) -> ApiResult<impl IntoResponse> {
+    let if_match = require_if_match(&headers, &uri)?;
+    let settings = svc.patch_settings(&ctx, req.into(), Some(if_match)).await?;
+    Ok(with_etag(settings))
 }
@@ -28,7 +28,7 @@ pub fn register_routes(
     router = OperationBuilder::get("/simple-user-settings/v1/settings")
         .operation_id("simple_user_settings.get_settings")
         .summary("Get user settings")
-        .description("Retrieve settings for the authenticated user")
+        .description("Retrieve settings for the authenticated user; the ETag header is required as If-Match on writes")
         .tag("Settings")
         .authenticated()
         .require_license_features::<License>([])
@@ -50,6 +50,11 @@ pub fn register_routes(
         .tag("Settings")
         .authenticated()
         .require_license_features::<License>([])
+        .header_param(
+            "If-Match",
+            true,
+            "ETag from the last read; the write fails with 412 if the settings changed since",
+        )
         .json_request::<dto::UpdateSimpleUserSettingsRequest>(openapi, "Settings update data")
         .handler(handlers::update_settings)
         .json_response_with_schema::<dto::SimpleUserSettingsDto>(
@@ -61,17 +66,36 @@ pub fn register_routes(
         .error_401(openapi)
         .error_403(openapi)
         .error_422(openapi)
+        .problem_response(
+            openapi,
+            StatusCode::PRECONDITION_FAILED,
+            "Settings changed since they were read",
+        )
+        .problem_response(
+            openapi,
+            StatusCode::PRECONDITION_REQUIRED,
+            "If-Match header missing",
+        )
         .error_500(openapi)
         .register(router, openapi);
 
     router = OperationBuilder::patch("/simple-user-settings/v1/settings")
         .operation_id("simple_user_settings.patch_settings")
         .summary("Partially update user settings")
-        .description("Partial update of user settings (PATCH semantics)")
+        .description(
+            "Partial update of user settings (JSON Merge Patch, RFC 7386): omitted fields are \
+             left unchanged, `null` clears a field",
+        )
         .tag("Settings")
         .authenticated()
         .require_license_features::<License>([])
+        .header_param(
+            "If-Match",
+            true,
+            "ETag from the last read; the write fails with 412 if the settings changed since",
+        )
         .json_request::<dto::PatchSimpleUserSettingsRequest>(openapi, "Settings patch data")
+        .allow_content_types(&["application/json", "application/merge-patch+json"])
         .handler(handlers::patch_settings)
         .json_response_with_schema::<dto::SimpleUserSettingsDto>(
             openapi,
@@ -82,6 +106,16 @@ pub fn register_routes(
         .error_401(openapi)
         .error_403(openapi)
         .error_422(openapi)
+        .problem_response(
+            openapi,
+            StatusCode::PRECONDITION_FAILED,
+            "Settings changed since they were read",
+        )
+        .problem_response(
+            openapi,
+            StatusCode::PRECONDITION_REQUIRED,
+            "If-Match header missing",
+        )
         .error_500(openapi)
         .register(router, openapi);
 
@@ -4,12 +4,21 @@ use serde::Deserialize;
 pub struct SettingsConfig {
     #[serde(default = "default_max_field_length")]
     pub max_field_length: usize,
+    /// Accepted `theme` values (exact match).
+    #[serde(default = "default_allowed_themes")]
+    pub allowed_themes: Vec<String>,
+    /// Accepted `language` tags (case-insensitive). Empty accepts any
+    /// well-formed BCP 47 tag.
+    #[serde(default)]
+    pub allowed_languages: Vec<String>,
 }
 
 impl Default for SettingsConfig {
     fn default() -> Self {
         Self {
             max_field_length: default_max_field_length(),
+            allowed_themes: default_allowed_themes(),
+            allowed_languages: Vec::new(),
         }
     }
 }
@@ -17,3 +26,7 @@ impl Default for SettingsConfig {
 fn default_max_field_length() -> usize {
     100
 }
+
+fn default_allowed_themes() -> Vec<String> {
+    ["light", "dark", "system"].map(str::to_owned).to_vec()
+}
@@ -11,9 +11,15 @@ pub enum DomainError {
     #[error("Validation error on field '{field}': {message}")]
     Validation { field: String, message: String },
 
+    #[error("Invalid value for field '{field}': {message}")]
+    InvalidValue { field: String, message: String },
+
     #[error("Access forbidden: {0}")]
     Forbidden(String),
 
+    #[error("Settings were modified since they were read")]
+    PreconditionFailed,
+
     #[error("Internal error: {0}")]
     Internal(String),
 
@@ -29,6 +35,14 @@ impl DomainError {
         }
     }
 
+    /// A value outside the configured allow-list or format (maps to 400).
+    pub fn invalid_value(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::InvalidValue {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
     pub fn forbidden(message: impl Into<String>) -> Self {
         Self::Forbidden(message.into())
     }
@@ -53,8 +67,10 @@ impl From<DomainError> for SettingsError {
     fn from(e: DomainError) -> Self {
         match e {
             DomainError::NotFound => Self::not_found(),
-            DomainError::Validation { field, message } => Self::validation(field, message),
+            DomainError::Validation { field, message }
+            | DomainError::InvalidValue { field, message } => Self::validation(field, message),
             DomainError::Forbidden(_) => Self::forbidden(),
+            DomainError::PreconditionFailed => Self::precondition_failed(),
             DomainError::Internal(_) | DomainError::Database(_) => Self::internal(),
         }
     }
@@ -0,0 +1,73 @@
+//! Entity tags for optimistic concurrency on settings writes.
+//!
+//! The tag is derived from the stored field values, so it changes whenever
+//! any field changes and is identical across instances and restarts.
+
+use sha2::{Digest, Sha256};
+use simple_user_settings_sdk::models::SimpleUserSettings;
+
+/// Strong `ETag` (quoted) for the current settings representation.
+#[must_use]
+pub fn settings_etag(settings: &SimpleUserSettings) -> String {
+    let mut hasher = Sha256::new();
+    for field in [&settings.theme, &settings.language] {
+        // Length-prefix values so `None`, `Some("")` and adjacent fields stay distinct
+        match field {
+            Some(value) => {
+                hasher.update([1]);
+                hasher.update((value.len() as u64).to_be_bytes());
+                hasher.update(value.as_bytes());
+            }
+            None => hasher.update([0]),
+        }
+    }
+    format!("\"{}\"", hex::encode(&hasher.finalize()[..16]))
+}
+
+/// `If-Match` matches when it is `*` or lists `etag` (strong comparison, RFC 9110 §13.1.1).
+#[must_use]
+pub fn if_match_matches(if_match: &str, etag: &str) -> bool {
+    if_match
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate == etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn settings(theme: Option<&str>, language: Option<&str>) -> SimpleUserSettings {
+        SimpleUserSettings {
+            user_id: Uuid::nil(),
+            tenant_id: Uuid::nil(),
+            theme: theme.map(str::to_owned),
+            language: language.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn etag_tracks_field_values() {
+        let dark = settings_etag(&settings(Some("dark"), Some("en")));
+        assert_eq!(dark, settings_etag(&settings(Some("dark"), Some("en"))));
+        assert_ne!(dark, settings_etag(&settings(Some("light"), Some("en"))));
+        assert_ne!(
+            settings_etag(&settings(None, Some("en"))),
+            settings_etag(&settings(Some(""), Some("en")))
+        );
+        assert_ne!(
+            settings_etag(&settings(Some("ab"), Some("c"))),
+            settings_etag(&settings(Some("a"), Some("bc")))
+        );
+    }
+
+    #[test]
+    fn if_match_uses_strong_comparison() {
+        assert!(if_match_matches("\"abc\"", "\"abc\""));
+        assert!(if_match_matches("\"x\", \"abc\"", "\"abc\""));
+        assert!(if_match_matches("*", "\"abc\""));
+        assert!(!if_match_matches("W/\"abc\"", "\"abc\""));
+        assert!(!if_match_matches("\"x\"", "\"abc\""));
+    }
+}
@@ -0,0 +1,128 @@
+//! Well-formedness check for BCP 47 language tags (RFC 5646 §2.1).
+//!
+//! Only the syntax is checked; subtags are not looked up in the IANA registry.
+//! Grandfathered irregular tags (e.g. `i-klingon`) are not accepted.
+
+fn is_alpha(s: &str, len: std::ops::RangeInclusive<usize>) -> bool {
+    len.contains(&s.len()) && s.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+fn is_alnum(s: &str, len: std::ops::RangeInclusive<usize>) -> bool {
+    len.contains(&s.len()) && s.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+fn is_digits(s: &str, len: usize) -> bool {
+    s.len() == len && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_variant(s: &str) -> bool {
+    is_alnum(s, 5..=8) || (is_alnum(s, 4..=4) && s.as_bytes()[0].is_ascii_digit())
+}
+
+/// Whether `tag` is a well-formed BCP 47 language tag, e.g. `en`, `en-US`,
+/// `zh-Hant-TW`, `de-CH-1996`, `sr-Latn-RS-u-ca-gregory` or `x-private`.
+#[must_use]
+pub fn is_well_formed(tag: &str) -> bool {
+    let subtags: Vec<&str> = tag.split('-').collect();
+    let mut rest = subtags.as_slice();
+
+    // privateuse: "x" 1*("-" 1*8alphanum)
+    let private_use = |rest: &[&str]| !rest.is_empty() && rest.iter().all(|s| is_alnum(s, 1..=8));
+
+    match rest {
+        [first, tail @ ..] if first.eq_ignore_ascii_case("x") => return private_use(tail),
+        [first, tail @ ..] if is_alpha(first, 2..=3) => {
+            rest = tail;
+            // Up to three extlang subtags
+            let mut extlangs = 0;
+            while let [s, tail @ ..] = rest
+                && extlangs < 3
+                && is_alpha(s, 3..=3)
+            {
+                rest = tail;
+                extlangs += 1;
+            }
+        }
+        [first, tail @ ..] if is_alpha(first, 4..=8) => rest = tail,
+        _ => return false,
+    }
+
+    if let [s, tail @ ..] = rest
+        && is_alpha(s, 4..=4)
+    {
+        rest = tail; // script
+    }
+    if let [s, tail @ ..] = rest
+        && (is_alpha(s, 2..=2) || is_digits(s, 3))
+    {
+        rest = tail; // region
+    }
+    while let [s, tail @ ..] = rest
+        && is_variant(s)
+    {
+        rest = tail;
+    }
+
+    // extensions: singleton 1*("-" 2*8alphanum)
+    while let [singleton, tail @ ..] = rest
+        && singleton.len() == 1
+        && !singleton.eq_ignore_ascii_case("x")
+        && is_alnum(singleton, 1..=1)
+    {
+        let count = tail.iter().take_while(|s| is_alnum(s, 2..=8)).count();
+        if count == 0 {
+            return false;
+        }
+        rest = &tail[count..];
+    }
+
+    match rest {
+        [] => true,
+        [x, tail @ ..] if x.eq_ignore_ascii_case("x") => private_use(tail),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_well_formed;
+
+    #[test]
+    fn accepts_well_formed_tags() {
+        for tag in [
+            "en",
+            "en-US",
+            "EN-us",
+            "zh-Hant-TW",
+            "es-419",
+            "de-CH-1996",
+            "sl-rozaj-biske",
+            "zh-yue-HK",
+            "sr-Latn-RS-u-ca-gregory",
+            "en-US-x-twain",
+            "x-private",
+        ] {
+            assert!(is_well_formed(tag), "{tag} should be well-formed");
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_tags() {
+        for tag in [
+            "",
+            "e",
+            "toolonglanguage",
+            "en_US",
+            "en-",
+            "-en",
+            "en--US",
+            "en-US-u",
+            "en-a-b-c",
+            "123",
+            "en-x",
+            "klingon!",
+        ] {
+            assert!(!is_well_formed(tag), "{tag} should be rejected");
+        }
+    }
+}
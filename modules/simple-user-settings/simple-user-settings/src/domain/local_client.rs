@@ -37,7 +37,7 @@ impl<R: SettingsRepository + 'static> SimpleUserSettingsClientV1 for LocalClient
         update: SimpleUserSettingsUpdate,
     ) -> Result<SimpleUserSettings, SettingsError> {
         self.service
-            .update_settings(ctx, update)
+            .update_settings(ctx, update, None)
             .await
             .map_err(Into::into)
     }
@@ -48,7 +48,7 @@ impl<R: SettingsRepository + 'static> SimpleUserSettingsClientV1 for LocalClient
         patch: SimpleUserSettingsPatch,
     ) -> Result<SimpleUserSettings, SettingsError> {
         self.service
-            .patch_settings(ctx, patch)
+            .patch_settings(ctx, patch, None)
             .await
             .map_err(Into::into)
     }
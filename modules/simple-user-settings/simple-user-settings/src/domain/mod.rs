@@ -4,7 +4,9 @@
 #![allow(de0301_no_infra_in_domain)]
 
 pub mod error;
+pub mod etag;
 pub mod fields;
+pub mod language_tag;
 pub mod local_client;
 pub mod repo;
 pub mod service;
@@ -29,6 +29,31 @@ where
         language: Option<String>,
     ) -> Result<SimpleUserSettings, DomainError>;
 
+    /// Insert settings for a user who has none yet.
+    ///
+    /// Returns `false` when a row already exists, e.g. one written concurrently.
+    async fn insert_if_absent<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        user_id: Uuid,
+        tenant_id: Uuid,
+        theme: Option<String>,
+        language: Option<String>,
+    ) -> Result<bool, DomainError>;
+
+    /// Overwrite theme/language only if the stored row still equals `current`.
+    ///
+    /// Returns `false` when the row was changed or removed concurrently.
+    async fn replace_if_unchanged<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        current: &SimpleUserSettings,
+        theme: Option<String>,
+        language: Option<String>,
+    ) -> Result<bool, DomainError>;
+
     async fn upsert_patch<C: DBRunner>(
         &self,
         conn: &C,
@@ -3,14 +3,18 @@ use std::sync::Arc;
 use authz_resolver_sdk::PolicyEnforcer;
 use authz_resolver_sdk::pep::{AccessRequest, ResourceType};
 use modkit_db::DBProvider;
+use modkit_db::secure::DBRunner;
 use modkit_macros::domain_model;
-use modkit_security::{SecurityContext, pep_properties};
+use modkit_security::{AccessScope, SecurityContext, pep_properties};
 use simple_user_settings_sdk::models::{
     SimpleUserSettings, SimpleUserSettingsPatch, SimpleUserSettingsUpdate,
 };
+use uuid::Uuid;
 
 use super::error::DomainError;
+use super::etag::{if_match_matches, settings_etag};
 use super::fields::SettingsFields;
+use super::language_tag;
 use super::repo::SettingsRepository;
 
 pub(crate) type DbProvider = DBProvider<modkit_db::DbError>;
@@ -29,6 +33,16 @@ pub(crate) mod actions {
     pub const UPDATE: &str = "update";
 }
 
+/// Settings returned for a user who has never saved any.
+fn empty_settings(user_id: Uuid, tenant_id: Uuid) -> SimpleUserSettings {
+    SimpleUserSettings {
+        user_id,
+        tenant_id,
+        theme: None,
+        language: None,
+    }
+}
+
 // ============================================================================
 // Service Configuration
 // ============================================================================
@@ -36,12 +50,19 @@ pub(crate) mod actions {
 #[domain_model]
 pub struct ServiceConfig {
     pub max_field_length: usize,
+    /// Accepted `theme` values (exact match).
+    pub allowed_themes: Vec<String>,
+    /// Accepted `language` tags (case-insensitive); empty accepts any
+    /// well-formed BCP 47 tag.
+    pub allowed_languages: Vec<String>,
 }
 
 impl Default for ServiceConfig {
     fn default() -> Self {
         Self {
             max_field_length: 100,
+            allowed_themes: ["light", "dark", "system"].map(str::to_owned).to_vec(),
+            allowed_languages: Vec::new(),
         }
     }
 }
@@ -93,25 +114,25 @@ impl<R: SettingsRepository> Service<R> {
 
         let conn = self.db.conn().map_err(DomainError::from)?;
 
-        if let Some(settings) = self.repo.find_by_user(&conn, &scope).await? {
-            Ok(settings)
-        } else {
-            Ok(SimpleUserSettings {
-                user_id,
-                tenant_id,
-                theme: None,
-                language: None,
-            })
-        }
+        Ok(self
+            .repo
+            .find_by_user(&conn, &scope)
+            .await?
+            .unwrap_or_else(|| empty_settings(user_id, tenant_id)))
     }
 
+    /// Replace all settings.
+    ///
+    /// With `if_match`, the write only happens if the stored settings still have
+    /// that `ETag`; otherwise [`DomainError::PreconditionFailed`] is returned.
     pub async fn update_settings(
         &self,
         ctx: &SecurityContext,
         update: SimpleUserSettingsUpdate,
+        if_match: Option<&str>,
     ) -> Result<SimpleUserSettings, DomainError> {
-        self.validate_field(SettingsFields::THEME, &update.theme)?;
-        self.validate_field(SettingsFields::LANGUAGE, &update.language)?;
+        self.validate_theme(&update.theme)?;
+        self.validate_language(&update.language)?;
 
         let user_id = ctx.subject_id();
         let tenant_id = ctx.subject_tenant_id();
@@ -129,6 +150,14 @@ impl<R: SettingsRepository> Service<R> {
 
         let conn = self.db.conn().map_err(DomainError::from)?;
 
+        if let Some(if_match) = if_match {
+            return self
+                .write_if_match(&conn, &scope, user_id, tenant_id, if_match, |_| {
+                    (Some(update.theme), Some(update.language))
+                })
+                .await;
+        }
+
         let settings = self
             .repo
             .upsert_full(
@@ -143,16 +172,20 @@ impl<R: SettingsRepository> Service<R> {
         Ok(settings)
     }
 
+    /// Update only the provided fields; a field patched to `None` is cleared.
+    ///
+    /// `if_match` behaves as in [`Self::update_settings`].
     pub async fn patch_settings(
         &self,
         ctx: &SecurityContext,
         patch: SimpleUserSettingsPatch,
+        if_match: Option<&str>,
     ) -> Result<SimpleUserSettings, DomainError> {
-        if let Some(ref theme) = patch.theme {
-            self.validate_field(SettingsFields::THEME, theme)?;
+        if let Some(Some(ref theme)) = patch.theme {
+            self.validate_theme(theme)?;
         }
-        if let Some(ref language) = patch.language {
-            self.validate_field(SettingsFields::LANGUAGE, language)?;
+        if let Some(Some(ref language)) = patch.language {
+            self.validate_language(language)?;
         }
 
         let user_id = ctx.subject_id();
@@ -171,6 +204,17 @@ impl<R: SettingsRepository> Service<R> {
 
         let conn = self.db.conn().map_err(DomainError::from)?;
 
+        if let Some(if_match) = if_match {
+            return self
+                .write_if_match(&conn, &scope, user_id, tenant_id, if_match, |current| {
+                    (
+                        patch.theme.unwrap_or_else(|| current.theme.clone()),
+                        patch.language.unwrap_or_else(|| current.language.clone()),
+                    )
+                })
+                .await;
+        }
+
         let settings = self
             .repo
             .upsert_patch(&conn, &scope, user_id, tenant_id, patch)
@@ -178,6 +222,94 @@ impl<R: SettingsRepository> Service<R> {
         Ok(settings)
     }
 
+    /// Conditional write: check `if_match` against the stored settings, then
+    /// write the values produced by `apply` only if nothing changed meanwhile.
+    async fn write_if_match<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        user_id: Uuid,
+        tenant_id: Uuid,
+        if_match: &str,
+        apply: impl FnOnce(&SimpleUserSettings) -> (Option<String>, Option<String>),
+    ) -> Result<SimpleUserSettings, DomainError> {
+        let stored = self.repo.find_by_user(conn, scope).await?;
+        let current = stored
+            .clone()
+            .unwrap_or_else(|| empty_settings(user_id, tenant_id));
+
+        if !if_match_matches(if_match, &settings_etag(&current)) {
+            return Err(DomainError::PreconditionFailed);
+        }
+
+        let (theme, language) = apply(&current);
+        let written = match stored {
+            Some(stored) => {
+                self.repo
+                    .replace_if_unchanged(conn, scope, &stored, theme.clone(), language.clone())
+                    .await?
+            }
+            // The ETag was checked against "no settings"; a row created since
+            // then must not be overwritten.
+            None => {
+                self.repo
+                    .insert_if_absent(
+                        conn,
+                        scope,
+                        user_id,
+                        tenant_id,
+                        theme.clone(),
+                        language.clone(),
+                    )
+                    .await?
+            }
+        };
+        if !written {
+            return Err(DomainError::PreconditionFailed);
+        }
+        Ok(SimpleUserSettings {
+            user_id,
+            tenant_id,
+            theme,
+            language,
+        })
+    }
+
+    fn validate_theme(&self, theme: &str) -> Result<(), DomainError> {
+        self.validate_field(SettingsFields::THEME, theme)?;
+        if !self.config.allowed_themes.iter().any(|t| t == theme) {
+            return Err(DomainError::invalid_value(
+                SettingsFields::THEME,
+                format!(
+                    "unknown theme '{theme}', expected one of: {}",
+                    self.config.allowed_themes.join(", ")
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_language(&self, language: &str) -> Result<(), DomainError> {
+        self.validate_field(SettingsFields::LANGUAGE, language)?;
+        if !language_tag::is_well_formed(language) {
+            return Err(DomainError::invalid_value(
+                SettingsFields::LANGUAGE,
+                format!("'{language}' is not a BCP 47 language tag"),
+            ));
+        }
+        let allowed = &self.config.allowed_languages;
+        if !allowed.is_empty() && !allowed.iter().any(|l| l.eq_ignore_ascii_case(language)) {
+            return Err(DomainError::invalid_value(
+                SettingsFields::LANGUAGE,
+                format!(
+                    "unsupported language '{language}', expected one of: {}",
+                    allowed.join(", ")
+                ),
+            ));
+        }
+        Ok(())
+    }
+
     fn validate_field(&self, field: &str, value: &str) -> Result<(), DomainError> {
         if value.len() > self.config.max_field_length {
             return Err(DomainError::validation(
@@ -14,12 +14,16 @@ mod tests {
         models::{EvaluationRequest, EvaluationResponse, EvaluationResponseContext},
     };
     use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::secure::DBRunner;
     use modkit_db::{ConnectOpts, DBProvider, Db, connect_db};
-    use modkit_security::{SecurityContext, pep_properties};
-    use simple_user_settings_sdk::models::{SimpleUserSettingsPatch, SimpleUserSettingsUpdate};
+    use modkit_security::{AccessScope, SecurityContext, pep_properties};
+    use simple_user_settings_sdk::models::{
+        SimpleUserSettings, SimpleUserSettingsPatch, SimpleUserSettingsUpdate,
+    };
     use uuid::Uuid;
 
     use crate::domain::error::DomainError;
+    use crate::domain::repo::SettingsRepository;
     use crate::domain::service::{Service, ServiceConfig};
     use crate::infra::storage::migrations::Migrator;
     use crate::infra::storage::sea_orm_repo::SeaOrmSettingsRepository;
@@ -150,6 +154,7 @@ mod tests {
                     theme: "dark".to_owned(),
                     language: "en".to_owned(),
                 },
+                None,
             )
             .await
             .unwrap();
@@ -178,6 +183,7 @@ mod tests {
                     theme: "light".to_owned(),
                     language: "es".to_owned(),
                 },
+                None,
             )
             .await
             .unwrap();
@@ -195,6 +201,7 @@ mod tests {
             db,
             ServiceConfig {
                 max_field_length: 10,
+                ..ServiceConfig::default()
             },
         );
         let ctx = create_test_context();
@@ -207,6 +214,7 @@ mod tests {
                     theme: too_long,
                     language: "en".to_owned(),
                 },
+                None,
             )
             .await;
 
@@ -222,6 +230,7 @@ mod tests {
             db,
             ServiceConfig {
                 max_field_length: 10,
+                ..ServiceConfig::default()
             },
         );
         let ctx = create_test_context();
@@ -234,6 +243,7 @@ mod tests {
                     theme: "dark".to_owned(),
                     language: too_long,
                 },
+                None,
             )
             .await;
 
@@ -260,6 +270,7 @@ mod tests {
                     theme: "dark".to_owned(),
                     language: "en".to_owned(),
                 },
+                None,
             )
             .await
             .unwrap();
@@ -269,9 +280,10 @@ mod tests {
             .patch_settings(
                 &ctx,
                 SimpleUserSettingsPatch {
-                    theme: Some("light".to_owned()),
+                    theme: Some(Some("light".to_owned())),
                     language: None,
                 },
+                None,
             )
             .await
             .unwrap();
@@ -287,6 +299,7 @@ mod tests {
             db,
             ServiceConfig {
                 max_field_length: 10,
+                ..ServiceConfig::default()
             },
         );
         let ctx = create_test_context();
@@ -297,8 +310,9 @@ mod tests {
                 &ctx,
                 SimpleUserSettingsPatch {
                     theme: None,
-                    language: Some(too_long),
+                    language: Some(Some(too_long)),
                 },
+                None,
             )
             .await;
 
@@ -321,6 +335,7 @@ mod tests {
                     theme: "dark".to_owned(),
                     language: "en".to_owned(),
                 },
+                None,
             )
             .await
             .unwrap();
@@ -333,6 +348,7 @@ mod tests {
                     theme: None,
                     language: None,
                 },
+                None,
             )
             .await
             .unwrap();
@@ -353,15 +369,97 @@ mod tests {
             .patch_settings(
                 &ctx,
                 SimpleUserSettingsPatch {
-                    theme: Some("dark".to_owned()),
+                    theme: Some(Some("dark".to_owned())),
+                    language: None,
+                },
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.theme, Some("dark".to_owned()));
+        assert_eq!(result.language, None);
+    }
+
+    async fn seeded_service() -> (ConcreteService, SecurityContext) {
+        let db = inmem_db().await;
+        let service = build_service(db, ServiceConfig::default());
+        let ctx = create_test_context();
+        service
+            .update_settings(
+                &ctx,
+                SimpleUserSettingsUpdate {
+                    theme: "dark".to_owned(),
+                    language: "en".to_owned(),
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        (service, ctx)
+    }
+
+    #[tokio::test]
+    async fn test_patch_settings_omitted_language_is_kept() {
+        let (service, ctx) = seeded_service().await;
+
+        let result = service
+            .patch_settings(
+                &ctx,
+                SimpleUserSettingsPatch {
+                    theme: Some(Some("light".to_owned())),
                     language: None,
                 },
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.theme, Some("light".to_owned()));
+        assert_eq!(result.language, Some("en".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn test_patch_settings_null_language_clears_it() {
+        let (service, ctx) = seeded_service().await;
+
+        let result = service
+            .patch_settings(
+                &ctx,
+                SimpleUserSettingsPatch {
+                    theme: None,
+                    language: Some(None),
+                },
+                None,
             )
             .await
             .unwrap();
 
         assert_eq!(result.theme, Some("dark".to_owned()));
         assert_eq!(result.language, None);
+
+        let stored = service.get_settings(&ctx).await.unwrap();
+        assert_eq!(stored.language, None);
+    }
+
+    #[tokio::test]
+    async fn test_patch_settings_language_value_updates_it() {
+        let (service, ctx) = seeded_service().await;
+
+        let result = service
+            .patch_settings(
+                &ctx,
+                SimpleUserSettingsPatch {
+                    theme: None,
+                    language: Some(Some("fr".to_owned())),
+                },
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.theme, Some("dark".to_owned()));
+        assert_eq!(result.language, Some("fr".to_owned()));
     }
 
     // =========================================================================
@@ -393,6 +491,7 @@ mod tests {
                     theme: "dark".to_owned(),
                     language: "en".to_owned(),
                 },
+                None,
             )
             .await
             .unwrap();
@@ -429,6 +528,7 @@ mod tests {
                     theme: "dark".to_owned(),
                     language: "en".to_owned(),
                 },
+                None,
             )
             .await
             .unwrap();
@@ -439,4 +539,330 @@ mod tests {
         assert_eq!(result.language, None);
         assert_eq!(result.tenant_id, tenant2.subject_tenant_id());
     }
+
+    // =========================================================================
+    // Allow-list validation tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_unknown_theme_is_rejected() {
+        let db = inmem_db().await;
+        let service = build_service(db, ServiceConfig::default());
+        let ctx = create_test_context();
+
+        let result = service
+            .update_settings(
+                &ctx,
+                SimpleUserSettingsUpdate {
+                    theme: "neon".to_owned(),
+                    language: "en".to_owned(),
+                },
+                None,
+            )
+            .await;
+        assert!(
+            matches!(result, Err(DomainError::InvalidValue { ref field, .. }) if field == "theme")
+        );
+
+        let result = service
+            .patch_settings(
+                &ctx,
+                SimpleUserSettingsPatch {
+                    theme: Some(Some("dark".to_owned())),
+                    language: None,
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.theme, Some("dark".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_language_tag_is_rejected() {
+        let db = inmem_db().await;
+        let service = build_service(db, ServiceConfig::default());
+        let ctx = create_test_context();
+
+        for language in ["en_US", "english!", "e"] {
+            let result = service
+                .patch_settings(
+                    &ctx,
+                    SimpleUserSettingsPatch {
+                        theme: None,
+                        language: Some(Some(language.to_owned())),
+                    },
+                    None,
+                )
+                .await;
+            assert!(
+                matches!(result, Err(DomainError::InvalidValue { ref field, .. }) if field == "language"),
+                "{language} should be rejected"
+            );
+        }
+
+        let result = service
+            .patch_settings(
+                &ctx,
+                SimpleUserSettingsPatch {
+                    theme: None,
+                    language: Some(Some("pt-BR".to_owned())),
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.language, Some("pt-BR".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn test_language_allow_list_is_case_insensitive() {
+        let db = inmem_db().await;
+        let service = build_service(
+            db,
+            ServiceConfig {
+                allowed_languages: vec!["en-US".to_owned(), "fr".to_owned()],
+                ..ServiceConfig::default()
+            },
+        );
+        let ctx = create_test_context();
+
+        let patch = |language: &str| SimpleUserSettingsPatch {
+            theme: None,
+            language: Some(Some(language.to_owned())),
+        };
+
+        assert!(
+            service
+                .patch_settings(&ctx, patch("en-us"), None)
+                .await
+                .is_ok()
+        );
+        let result = service.patch_settings(&ctx, patch("de"), None).await;
+        assert!(matches!(result, Err(DomainError::InvalidValue { .. })));
+    }
+
+    // =========================================================================
+    // If-Match / ETag tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_update_with_matching_etag_succeeds() {
+        use crate::domain::etag::settings_etag;
+
+        let db = inmem_db().await;
+        let service = build_service(db, ServiceConfig::default());
+        let ctx = create_test_context();
+
+        // First write against the empty (never saved) representation
+        let etag = settings_etag(&service.get_settings(&ctx).await.unwrap());
+        let created = service
+            .update_settings(
+                &ctx,
+                SimpleUserSettingsUpdate {
+                    theme: "dark".to_owned(),
+                    language: "en".to_owned(),
+                },
+                Some(&etag),
+            )
+            .await
+            .unwrap();
+        assert_ne!(settings_etag(&created), etag);
+
+        let etag = settings_etag(&service.get_settings(&ctx).await.unwrap());
+        assert_eq!(etag, settings_etag(&created));
+        let patched = service
+            .patch_settings(
+                &ctx,
+                SimpleUserSettingsPatch {
+                    theme: Some(Some("light".to_owned())),
+                    language: None,
+                },
+                Some(&etag),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(patched.theme, Some("light".to_owned()));
+        assert_eq!(patched.language, Some("en".to_owned()));
+        assert_eq!(service.get_settings(&ctx).await.unwrap(), patched);
+    }
+
+    #[tokio::test]
+    async fn test_update_with_stale_etag_fails_precondition() {
+        use crate::domain::etag::settings_etag;
+
+        let db = inmem_db().await;
+        let service = build_service(db, ServiceConfig::default());
+        let ctx = create_test_context();
+
+        let _ = service
+            .update_settings(
+                &ctx,
+                SimpleUserSettingsUpdate {
+                    theme: "dark".to_owned(),
+                    language: "en".to_owned(),
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        let stale = settings_etag(&service.get_settings(&ctx).await.unwrap());
+
+        // Another writer changes the settings
+        let _ = service
+            .patch_settings(
+                &ctx,
+                SimpleUserSettingsPatch {
+                    theme: Some(Some("light".to_owned())),
+                    language: None,
+                },
+                Some(&stale),
+            )
+            .await
+            .unwrap();
+
+        let result = service
+            .update_settings(
+                &ctx,
+                SimpleUserSettingsUpdate {
+                    theme: "system".to_owned(),
+                    language: "fr".to_owned(),
+                },
+                Some(&stale),
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::PreconditionFailed)));
+
+        let result = service
+            .patch_settings(
+                &ctx,
+                SimpleUserSettingsPatch {
+                    theme: None,
+                    language: Some(Some("fr".to_owned())),
+                },
+                Some(&stale),
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::PreconditionFailed)));
+
+        // The concurrent write is preserved
+        let current = service.get_settings(&ctx).await.unwrap();
+        assert_eq!(current.theme, Some("light".to_owned()));
+        assert_eq!(current.language, Some("en".to_owned()));
+    }
+
+    /// Repository whose reads never see a stored row, as if every read ran
+    /// just before a concurrent writer created it.
+    struct StaleReadRepo(SeaOrmSettingsRepository);
+
+    #[async_trait]
+    impl SettingsRepository for StaleReadRepo {
+        async fn find_by_user<C: DBRunner>(
+            &self,
+            _conn: &C,
+            _scope: &AccessScope,
+        ) -> Result<Option<SimpleUserSettings>, DomainError> {
+            Ok(None)
+        }
+
+        async fn upsert_full<C: DBRunner>(
+            &self,
+            conn: &C,
+            scope: &AccessScope,
+            user_id: Uuid,
+            tenant_id: Uuid,
+            theme: Option<String>,
+            language: Option<String>,
+        ) -> Result<SimpleUserSettings, DomainError> {
+            self.0
+                .upsert_full(conn, scope, user_id, tenant_id, theme, language)
+                .await
+        }
+
+        async fn insert_if_absent<C: DBRunner>(
+            &self,
+            conn: &C,
+            scope: &AccessScope,
+            user_id: Uuid,
+            tenant_id: Uuid,
+            theme: Option<String>,
+            language: Option<String>,
+        ) -> Result<bool, DomainError> {
+            self.0
+                .insert_if_absent(conn, scope, user_id, tenant_id, theme, language)
+                .await
+        }
+
+        async fn replace_if_unchanged<C: DBRunner>(
+            &self,
+            conn: &C,
+            scope: &AccessScope,
+            current: &SimpleUserSettings,
+            theme: Option<String>,
+            language: Option<String>,
+        ) -> Result<bool, DomainError> {
+            self.0
+                .replace_if_unchanged(conn, scope, current, theme, language)
+                .await
+        }
+
+        async fn upsert_patch<C: DBRunner>(
+            &self,
+            conn: &C,
+            scope: &AccessScope,
+            user_id: Uuid,
+            tenant_id: Uuid,
+            patch: SimpleUserSettingsPatch,
+        ) -> Result<SimpleUserSettings, DomainError> {
+            self.0
+                .upsert_patch(conn, scope, user_id, tenant_id, patch)
+                .await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_first_conditional_write_does_not_overwrite_concurrent_insert() {
+        use crate::domain::etag::settings_etag;
+
+        let db = inmem_db().await;
+        let service = build_service(db.clone(), ServiceConfig::default());
+        let ctx = create_test_context();
+        let empty_etag = settings_etag(&service.get_settings(&ctx).await.unwrap());
+
+        // Another writer creates the settings after the ETag was read
+        let _ = service
+            .update_settings(
+                &ctx,
+                SimpleUserSettingsUpdate {
+                    theme: "dark".to_owned(),
+                    language: "en".to_owned(),
+                },
+                None,
+            )
+            .await
+            .unwrap();
+
+        let stale_service = Service::new(
+            Arc::new(DBProvider::new(db)),
+            Arc::new(StaleReadRepo(SeaOrmSettingsRepository::new())),
+            PolicyEnforcer::new(Arc::new(MockAuthZResolver)),
+            ServiceConfig::default(),
+        );
+        let result = stale_service
+            .update_settings(
+                &ctx,
+                SimpleUserSettingsUpdate {
+                    theme: "light".to_owned(),
+                    language: "fr".to_owned(),
+                },
+                Some(&empty_etag),
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::PreconditionFailed)));
+
+        let current = service.get_settings(&ctx).await.unwrap();
+        assert_eq!(current.theme, Some("dark".to_owned()));
+        assert_eq!(current.language, Some("en".to_owned()));
+    }
 }
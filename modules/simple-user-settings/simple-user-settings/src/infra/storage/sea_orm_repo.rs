@@ -1,7 +1,10 @@
 use async_trait::async_trait;
-use modkit_db::secure::{DBRunner, ScopeError, SecureEntityExt, SecureInsertExt, SecureOnConflict};
+use modkit_db::secure::{
+    DBRunner, ScopeError, SecureEntityExt, SecureInsertExt, SecureOnConflict, SecureUpdateExt,
+};
 use modkit_security::AccessScope;
-use sea_orm::{ActiveValue, EntityTrait};
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveValue, ColumnTrait, Condition, DbErr, EntityTrait};
 use simple_user_settings_sdk::models::{SimpleUserSettings, SimpleUserSettingsPatch};
 use uuid::Uuid;
 
@@ -103,6 +106,75 @@ impl SettingsRepository for SeaOrmSettingsRepository {
         })
     }
 
+    async fn insert_if_absent<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        user_id: Uuid,
+        tenant_id: Uuid,
+        theme: Option<String>,
+        language: Option<String>,
+    ) -> Result<bool, DomainError> {
+        let active_model = entity::ActiveModel {
+            tenant_id: ActiveValue::Set(tenant_id),
+            user_id: ActiveValue::Set(user_id),
+            theme: ActiveValue::Set(theme),
+            language: ActiveValue::Set(language),
+        };
+
+        // Insert-if-absent: an existing row wins and is left untouched
+        let mut on_conflict = SecureOnConflict::<SettingsEntity>::columns([
+            entity::Column::TenantId,
+            entity::Column::UserId,
+        ]);
+        on_conflict.inner_mut().do_nothing();
+
+        let inserted = SettingsEntity::insert(active_model.clone())
+            .secure()
+            .scope_with_model(scope, &active_model)
+            .map_err(map_scope_error)?
+            .on_conflict(on_conflict)
+            .exec(conn)
+            .await;
+        match inserted {
+            Ok(_) => Ok(true),
+            Err(ScopeError::Db(DbErr::RecordNotInserted)) => Ok(false),
+            Err(e) => Err(map_scope_error(e)),
+        }
+    }
+
+    async fn replace_if_unchanged<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        current: &SimpleUserSettings,
+        theme: Option<String>,
+        language: Option<String>,
+    ) -> Result<bool, DomainError> {
+        let matches = |col: entity::Column, value: &Option<String>| match value {
+            Some(v) => col.eq(v.clone()),
+            None => col.is_null(),
+        };
+
+        // Compare-and-swap: the WHERE clause pins the values the caller validated
+        let result = SettingsEntity::update_many()
+            .secure()
+            .scope_with(scope)
+            .col_expr(entity::Column::Theme, Expr::value(theme))
+            .col_expr(entity::Column::Language, Expr::value(language))
+            .filter(
+                Condition::all()
+                    .add(entity::Column::UserId.eq(current.user_id))
+                    .add(matches(entity::Column::Theme, &current.theme))
+                    .add(matches(entity::Column::Language, &current.language)),
+            )
+            .exec(conn)
+            .await
+            .map_err(map_scope_error)?;
+
+        Ok(result.rows_affected == 1)
+    }
+
     async fn upsert_patch<C: DBRunner>(
         &self,
         conn: &C,
@@ -123,13 +195,13 @@ impl SettingsRepository for SeaOrmSettingsRepository {
         // Merge patch with existing values
         let (theme, language) = match existing {
             Some(e) => {
-                let theme = patch.theme.or(e.theme);
-                let language = patch.language.or(e.language);
+                let theme = patch.theme.unwrap_or(e.theme);
+                let language = patch.language.unwrap_or(e.language);
                 (theme, language)
             }
             None => {
                 // No existing record - use patch values directly
-                (patch.theme, patch.language)
+                (patch.theme.flatten(), patch.language.flatten())
             }
         };
 
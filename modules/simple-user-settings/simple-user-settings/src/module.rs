@@ -65,6 +65,8 @@ impl Module for SettingsModule {
 
         let service_config = ServiceConfig {
             max_field_length: cfg.max_field_length,
+            allowed_themes: cfg.allowed_themes,
+            allowed_languages: cfg.allowed_languages,
         };
         let service = Arc::new(Service::new(db, repo, policy_enforcer, service_config));
         self.service
@@ -1,6 +1,16 @@
-#![allow(unused_imports)]
-use axum::http::StatusCode;
-use axum::response::IntoResponse;
+//! Embedded static assets with HTTP cache validation.
+//!
+//! Assets carry a strong `ETag` derived from their content and, when known, a
+//! `Last-Modified` date. Conditional requests are answered with `304 Not Modified`:
+//! `If-None-Match` takes precedence over `If-Modified-Since` (RFC 9110 §13.2.2).
+#![cfg_attr(not(feature = "embed_elements"), allow(dead_code))]
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+
+use crate::config::StaticAssetsConfig;
 
 #[cfg(feature = "embed_elements")]
 use rust_embed::RustEmbed;
@@ -10,21 +20,144 @@ use rust_embed::RustEmbed;
 #[folder = "assets/elements/"]
 pub struct ElementsAssets;
 
+/// Compiled `Cache-Control` values for static assets.
+#[derive(Clone, Debug)]
+pub struct AssetCachePolicy {
+    cache_control: HeaderValue,
+    immutable_cache_control: HeaderValue,
+}
+
+impl AssetCachePolicy {
+    /// # Errors
+    /// Returns an error if a configured `Cache-Control` value is not a valid header value.
+    pub fn from_config(config: &StaticAssetsConfig) -> anyhow::Result<Self> {
+        let parse = |v: &str| {
+            HeaderValue::from_str(v)
+                .map_err(|e| anyhow::anyhow!("Invalid static_assets Cache-Control '{v}': {e}"))
+        };
+        Ok(Self {
+            cache_control: parse(&config.cache_control)?,
+            immutable_cache_control: parse(&config.immutable_cache_control)?,
+        })
+    }
+
+    fn cache_control_for(&self, file: &str) -> HeaderValue {
+        if is_fingerprinted(file) {
+            self.immutable_cache_control.clone()
+        } else {
+            self.cache_control.clone()
+        }
+    }
+}
+
+/// Strong `ETag` from a content SHA-256 digest (quoted, first 128 bits).
+fn etag_from_sha256(digest: &[u8]) -> String {
+    format!("\"{}\"", hex::encode(&digest[..16]))
+}
+
+/// Whether the file name contains a content hash segment, e.g. `app.3f9a1c2b.js`.
+fn is_fingerprinted(file: &str) -> bool {
+    let name = file.rsplit('/').next().unwrap_or(file);
+    let mut segments = name.split('.');
+    // Skip the base name; the hash is one of the inner segments
+    segments.next();
+    let inner: Vec<&str> = segments.collect();
+    inner.len() >= 2
+        && inner[..inner.len() - 1]
+            .iter()
+            .any(|s| s.len() >= 8 && s.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// `If-None-Match` matches when it is `*` or lists our tag (weak comparison).
+fn if_none_match_matches(value: &str, etag: &str) -> bool {
+    let strip_weak = |t: &str| t.trim().trim_start_matches("W/").to_owned();
+    let ours = strip_weak(etag);
+    value
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || strip_weak(candidate) == ours)
+}
+
+fn is_not_modified(req_headers: &HeaderMap, etag: &str, last_modified: Option<SystemTime>) -> bool {
+    if let Some(inm) = req_headers.get(header::IF_NONE_MATCH) {
+        return inm.to_str().is_ok_and(|v| if_none_match_matches(v, etag));
+    }
+
+    match (
+        last_modified,
+        req_headers
+            .get(header::IF_MODIFIED_SINCE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| httpdate::parse_http_date(v).ok()),
+    ) {
+        // HTTP dates have second precision
+        (Some(modified), Some(since)) => truncate_to_secs(modified) <= since,
+        _ => false,
+    }
+}
+
+fn truncate_to_secs(t: SystemTime) -> SystemTime {
+    t.duration_since(UNIX_EPOCH)
+        .map_or(t, |d| UNIX_EPOCH + Duration::from_secs(d.as_secs()))
+}
+
+/// Build the response for a static asset, honoring conditional request headers.
+pub fn asset_response(
+    req_headers: &HeaderMap,
+    file: &str,
+    data: Vec<u8>,
+    etag: &str,
+    last_modified: Option<SystemTime>,
+    policy: &AssetCachePolicy,
+) -> Response {
+    let mut headers = HeaderMap::new();
+    if let Ok(v) = HeaderValue::from_str(etag) {
+        headers.insert(header::ETAG, v);
+    }
+    if let Some(modified) = last_modified
+        && let Ok(v) = HeaderValue::from_str(&httpdate::fmt_http_date(modified))
+    {
+        headers.insert(header::LAST_MODIFIED, v);
+    }
+    headers.insert(header::CACHE_CONTROL, policy.cache_control_for(file));
+
+    if is_not_modified(req_headers, etag, last_modified) {
+        return (StatusCode::NOT_MODIFIED, headers).into_response();
+    }
+
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(content_type_for(file)),
+    );
+    (headers, data).into_response()
+}
+
 #[cfg(feature = "embed_elements")]
 pub async fn serve_elements_asset(
+    axum::extract::State(policy): axum::extract::State<AssetCachePolicy>,
     axum::extract::Path(file): axum::extract::Path<String>,
-) -> Result<impl IntoResponse, StatusCode> {
+    req_headers: HeaderMap,
+) -> Result<Response, StatusCode> {
     if let Some(content) = ElementsAssets::get(&file) {
-        let mime_type = content_type_for(&file);
-        let body = content.data.into_owned();
-        Ok(([(axum::http::header::CONTENT_TYPE, mime_type)], body))
+        // rust-embed precomputes the SHA-256 at build time
+        let etag = etag_from_sha256(&content.metadata.sha256_hash());
+        let last_modified = content
+            .metadata
+            .last_modified()
+            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+        Ok(asset_response(
+            &req_headers,
+            &file,
+            content.data.into_owned(),
+            &etag,
+            last_modified,
+            &policy,
+        ))
     } else {
         tracing::warn!("Elements asset not found: {}", file);
         Err(StatusCode::NOT_FOUND)
     }
 }
 
-#[cfg(feature = "embed_elements")]
 fn content_type_for(file: &str) -> &'static str {
     match file.rsplit('.').next().unwrap_or("") {
         "css" => "text/css; charset=utf-8",
@@ -37,3 +170,111 @@ fn content_type_for(file: &str) -> &'static str {
         _ => "application/octet-stream",
     }
 }
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    fn etag_for(data: &[u8]) -> String {
+        etag_from_sha256(&Sha256::digest(data))
+    }
+
+    fn policy() -> AssetCachePolicy {
+        AssetCachePolicy::from_config(&StaticAssetsConfig::default()).unwrap()
+    }
+
+    fn serve(req_headers: &HeaderMap, file: &str, data: &[u8], modified: SystemTime) -> Response {
+        asset_response(
+            req_headers,
+            file,
+            data.to_vec(),
+            &etag_for(data),
+            Some(modified),
+            &policy(),
+        )
+    }
+
+    fn header_str<'a>(resp: &'a Response, name: &header::HeaderName) -> &'a str {
+        resp.headers().get(name).unwrap().to_str().unwrap()
+    }
+
+    #[test]
+    fn repeat_request_with_matching_etag_returns_304() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let first = serve(&HeaderMap::new(), "styles.min.css", b"body{}", modified);
+        assert_eq!(first.status(), StatusCode::OK);
+        let etag = header_str(&first, &header::ETAG).to_owned();
+        assert_eq!(
+            header_str(&first, &header::LAST_MODIFIED),
+            "Tue, 14 Nov 2023 22:13:20 GMT"
+        );
+
+        let mut req = HeaderMap::new();
+        req.insert(header::IF_NONE_MATCH, etag.parse().unwrap());
+        let second = serve(&req, "styles.min.css", b"body{}", modified);
+        assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(header_str(&second, &header::ETAG), etag);
+    }
+
+    #[test]
+    fn changed_content_produces_new_etag_and_full_response() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let old_etag = etag_for(b"body{}");
+        let new_etag = etag_for(b"body{color:red}");
+        assert_ne!(old_etag, new_etag);
+        assert_eq!(old_etag, etag_for(b"body{}"));
+
+        let mut req = HeaderMap::new();
+        req.insert(header::IF_NONE_MATCH, old_etag.parse().unwrap());
+        let resp = serve(&req, "styles.min.css", b"body{color:red}", modified);
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(header_str(&resp, &header::ETAG), new_etag);
+    }
+
+    #[test]
+    fn if_modified_since_honored_when_no_if_none_match() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut req = HeaderMap::new();
+        req.insert(
+            header::IF_MODIFIED_SINCE,
+            "Tue, 14 Nov 2023 22:13:20 GMT".parse().unwrap(),
+        );
+        assert_eq!(
+            serve(&req, "a.js", b"x", modified).status(),
+            StatusCode::NOT_MODIFIED
+        );
+
+        let newer = modified + Duration::from_mins(1);
+        assert_eq!(serve(&req, "a.js", b"x", newer).status(), StatusCode::OK);
+
+        // If-None-Match wins over If-Modified-Since
+        req.insert(header::IF_NONE_MATCH, "\"other\"".parse().unwrap());
+        assert_eq!(serve(&req, "a.js", b"x", modified).status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn weak_and_listed_etags_match() {
+        assert!(if_none_match_matches("W/\"abc\"", "\"abc\""));
+        assert!(if_none_match_matches("\"x\", \"abc\"", "\"abc\""));
+        assert!(if_none_match_matches("*", "\"abc\""));
+        assert!(!if_none_match_matches("\"x\"", "\"abc\""));
+    }
+
+    #[test]
+    fn fingerprinted_assets_get_immutable_cache_control() {
+        let modified = UNIX_EPOCH;
+        let resp = serve(&HeaderMap::new(), "app.3f9a1c2b.js", b"x", modified);
+        assert_eq!(
+            header_str(&resp, &header::CACHE_CONTROL),
+            "public, max-age=31536000, immutable"
+        );
+
+        let resp = serve(&HeaderMap::new(), "web-components.min.js", b"x", modified);
+        assert_eq!(
+            header_str(&resp, &header::CACHE_CONTROL),
+            "public, no-cache"
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 fn default_require_auth_by_default() -> bool {
@@ -8,6 +10,14 @@ fn default_body_limit_bytes() -> usize {
     16 * 1024 * 1024
 }
 
+fn default_header_limit_count() -> usize {
+    100
+}
+
+fn default_header_limit_bytes() -> usize {
+    32 * 1024
+}
+
 /// API gateway configuration - reused from `api_gateway` module
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(deny_unknown_fields)]
@@ -48,6 +58,12 @@ pub struct ApiGatewayConfig {
     #[serde(default)]
     pub prefix_path: String,
 
+    /// Host names accepted in `Host` and `X-Forwarded-Host` (e.g. `"api.example.com"`,
+    /// `"*.example.com"` for any subdomain). Requests naming another host get 400.
+    /// Empty (the default) accepts any host.
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+
     /// Route-level policy configuration.
     /// Allows early rejection of requests based on token scopes without calling the PDP.
     /// Rules are evaluated in declaration order (first match wins).
@@ -57,6 +73,62 @@ pub struct ApiGatewayConfig {
     /// HTTP metrics configuration.
     #[serde(default)]
     pub metrics: MetricsConfig,
+
+    /// Global and per-route in-flight request limits with load shedding.
+    #[serde(default)]
+    pub concurrency_limit: ConcurrencyLimitConfig,
+
+    /// Wire format of error responses.
+    #[serde(default)]
+    pub error_format: ErrorFormat,
+
+    /// HTTP caching of embedded static assets (docs UI).
+    #[serde(default)]
+    pub static_assets: StaticAssetsConfig,
+
+    /// How request paths with a trailing slash (e.g. `/users/`) are routed.
+    #[serde(default)]
+    pub trailing_slash: TrailingSlashPolicy,
+
+    /// Debug logging of request and response bodies (off by default).
+    #[serde(default)]
+    pub body_logging: BodyLoggingConfig,
+    /// Replay of `POST` responses for repeated `Idempotency-Key`s (off by default).
+    #[serde(default)]
+    pub idempotency: IdempotencyConfig,
+}
+
+/// Handling of request paths that end with a slash.
+///
+/// Routes are registered without a trailing slash; this policy decides what
+/// happens when a client requests `/users/` instead of `/users`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrailingSlashPolicy {
+    /// Route the path as is: `/users/` only matches a route registered with the slash.
+    #[default]
+    Strict,
+    /// Answer `308 Permanent Redirect` to the path without the trailing slash, keeping the query string.
+    RedirectToCanonical,
+    /// Strip the trailing slash before routing and serve the canonical route directly.
+    Ignore,
+}
+
+/// Wire format of error responses produced by the gateway and its handlers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorFormat {
+    /// Pass errors through unchanged. Handlers and gateway middleware already emit
+    /// RFC 9457 problem documents, but bare router/timeout/limit responses keep
+    /// their empty or plain-text bodies.
+    #[default]
+    Problem,
+    /// Rewrite every error into the canonical `{ "code", "message", "request_id", "details" }` body.
+    Json,
+    /// Like [`Problem`](Self::Problem), but also normalizes the responses it would
+    /// leave alone: bare and plain-text errors and canonical JSON bodies become
+    /// `application/problem+json` documents, and `instance` is set to the request path.
+    NormalizedProblem,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -66,6 +138,10 @@ pub struct Defaults {
     pub rate_limit: RateLimitDefaults,
     /// Global request body size limit in bytes
     pub body_limit_bytes: usize,
+    /// Maximum number of request headers (0 = unlimited)
+    pub header_limit_count: usize,
+    /// Maximum total size of request header names and values in bytes (0 = unlimited)
+    pub header_limit_bytes: usize,
 }
 
 impl Default for Defaults {
@@ -73,6 +149,8 @@ impl Default for Defaults {
         Self {
             rate_limit: RateLimitDefaults::default(),
             body_limit_bytes: default_body_limit_bytes(),
+            header_limit_count: default_header_limit_count(),
+            header_limit_bytes: default_header_limit_bytes(),
         }
     }
 }
@@ -129,6 +207,202 @@ impl Default for CorsConfig {
     }
 }
 
+/// Concurrency limit (load shedding) configuration.
+///
+/// # Example YAML
+///
+/// ```yaml
+/// concurrency_limit:
+///   enabled: true
+///   global_max_in_flight: 512
+///   max_queued: 64
+///   queue_timeout_ms: 500
+///   per_subject_max_in_flight: 32
+///   routes:
+///     - path: "/reports/v1/**"
+///       max_in_flight: 4
+/// ```
+///
+/// # Behavior
+///
+/// - A request must hold a global permit (if `global_max_in_flight > 0`) and a
+///   permit of the first matching route rule (if any) before reaching the handler
+/// - When no permit is free, up to `max_queued` requests wait for at most
+///   `queue_timeout_ms`; everything beyond that is shed with 503
+/// - Each authenticated subject may hold at most `per_subject_max_in_flight`
+///   requests (if set); its excess is shed with 429 without queueing, so one
+///   noisy client cannot take the global capacity from the others
+/// - Permits are released when the response is produced, the request is
+///   cancelled, or the handler panics
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct ConcurrencyLimitConfig {
+    /// Whether concurrency limiting is enabled.
+    pub enabled: bool,
+    /// Maximum number of in-flight requests across all routes (0 = unlimited).
+    pub global_max_in_flight: usize,
+    /// Maximum number of requests waiting for a permit per limit (0 = shed immediately).
+    pub max_queued: usize,
+    /// Maximum time a queued request waits for a permit before being shed.
+    pub queue_timeout_ms: u64,
+    /// Maximum number of in-flight requests per authenticated subject (0 = unlimited).
+    /// Must be below `global_max_in_flight` when both are set.
+    pub per_subject_max_in_flight: usize,
+    /// Per-route limits evaluated in declaration order (first match wins).
+    pub routes: Vec<RouteConcurrencyLimit>,
+}
+
+impl Default for ConcurrencyLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            global_max_in_flight: 0,
+            max_queued: 0,
+            queue_timeout_ms: 1000,
+            per_subject_max_in_flight: 0,
+            routes: Vec::new(),
+        }
+    }
+}
+
+/// A per-route in-flight limit.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RouteConcurrencyLimit {
+    /// Path pattern to match. Supports glob syntax (`*` = one segment, `**` = any depth).
+    pub path: String,
+    /// HTTP method to match. If not specified, matches any method.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// Maximum number of in-flight requests for matching routes. Must be greater than zero.
+    pub max_in_flight: usize,
+}
+
+/// HTTP caching configuration for embedded static assets.
+///
+/// Every asset is served with `ETag` and (when known) `Last-Modified`, and
+/// conditional requests (`If-None-Match` / `If-Modified-Since`) are answered
+/// with `304 Not Modified`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct StaticAssetsConfig {
+    /// `Cache-Control` for regular assets; clients revalidate using the `ETag`.
+    pub cache_control: String,
+    /// `Cache-Control` for fingerprinted assets whose file name contains a
+    /// content hash (e.g. `app.3f9a1c2b.js`) and therefore never change.
+    pub immutable_cache_control: String,
+}
+
+impl Default for StaticAssetsConfig {
+    fn default() -> Self {
+        Self {
+            cache_control: "public, no-cache".to_owned(),
+            immutable_cache_control: "public, max-age=31536000, immutable".to_owned(),
+        }
+    }
+}
+
+/// Debug logging of request and response bodies.
+///
+/// # Example YAML
+///
+/// ```yaml
+/// body_logging:
+///   enabled: true
+///   max_bytes: 4096
+///   redact_keys: ["password", "token", "client_secret"]
+///   exclude_routes: ["/uploads/v1/**"]
+/// ```
+///
+/// # Behavior
+///
+/// - Bodies are copied as they stream through; at most `max_bytes` per body are
+///   kept and longer bodies are logged truncated with a marker
+/// - Values of JSON object members whose key matches `redact_keys`
+///   (case-insensitive, at any depth) are replaced with `"[REDACTED]"`
+/// - Requests matching `exclude_routes` and `text/event-stream` responses are
+///   never captured
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct BodyLoggingConfig {
+    /// Whether body logging is enabled.
+    pub enabled: bool,
+    /// Maximum number of bytes logged per body.
+    pub max_bytes: usize,
+    /// JSON keys whose values are redacted.
+    pub redact_keys: Vec<String>,
+    /// Path patterns that are never logged, e.g. streaming routes.
+    /// Supports glob syntax (`*` = one segment, `**` = any depth).
+    pub exclude_routes: Vec<String>,
+}
+
+impl Default for BodyLoggingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_bytes: 4096,
+            redact_keys: [
+                "password",
+                "token",
+                "access_token",
+                "refresh_token",
+                "id_token",
+                "secret",
+                "client_secret",
+                "api_key",
+            ]
+            .into_iter()
+            .map(str::to_owned)
+            .collect(),
+            exclude_routes: Vec::new(),
+        }
+    }
+}
+
+/// `Idempotency-Key` support for `POST` requests.
+///
+/// # Example YAML
+///
+/// ```yaml
+/// idempotency:
+///   enabled: true
+///   ttl_secs: 86400
+///   max_entries: 10000
+///   max_response_bytes: 1048576
+/// ```
+///
+/// # Behavior
+///
+/// - The first response to a `POST` carrying `Idempotency-Key` is stored per
+///   (subject, path, key) for `ttl_secs` and replayed for duplicates with
+///   `Idempotent-Replayed: true`, without invoking the handler again
+/// - A duplicate arriving while the first request is still running gets `409 Conflict`
+/// - `5xx` responses, responses over `max_response_bytes` and anonymous
+///   requests are never stored
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct IdempotencyConfig {
+    /// Whether `Idempotency-Key` handling is enabled.
+    pub enabled: bool,
+    /// How long a stored response is replayed, in seconds.
+    pub ttl_secs: u64,
+    /// Maximum number of stored responses; new keys are not stored while full.
+    pub max_entries: usize,
+    /// Maximum size of a stored response body in bytes.
+    pub max_response_bytes: usize,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_secs: 24 * 60 * 60,
+            max_entries: 10_000,
+            max_response_bytes: 1024 * 1024,
+        }
+    }
+}
+
 /// HTTP metrics configuration.
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(deny_unknown_fields, default)]
@@ -154,6 +428,12 @@ pub struct OpenApiConfig {
     /// API description (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// `OAuth2` flows documented as an `oauth2` security scheme next to `bearerAuth` (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oauth2: Option<OpenApiOAuth2Config>,
+    /// Opaque-token introspection documented as an `introspection` security scheme (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub introspection: Option<OpenApiIntrospectionConfig>,
 }
 
 impl Default for OpenApiConfig {
@@ -162,10 +442,58 @@ impl Default for OpenApiConfig {
             title: "API Documentation".to_owned(),
             version: "0.1.0".to_owned(),
             description: None,
+            oauth2: None,
+            introspection: None,
         }
     }
 }
 
+/// `OAuth2` flows advertised in the `OpenAPI` document.
+///
+/// # Example YAML
+///
+/// ```yaml
+/// openapi:
+///   oauth2:
+///     token_url: "https://idp.example.com/oauth2/token"
+///     authorization_url: "https://idp.example.com/oauth2/authorize"
+///     scopes:
+///       "read:events": "Read events"
+/// ```
+///
+/// Scopes required by `route_policies` rules are added to `scopes` automatically.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct OpenApiOAuth2Config {
+    /// Token endpoint, advertised as a client-credentials flow.
+    pub token_url: String,
+    /// Authorization endpoint; when set, an authorization-code flow is advertised too.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorization_url: Option<String>,
+    /// Scopes and their descriptions.
+    #[serde(default)]
+    pub scopes: BTreeMap<String, String>,
+}
+
+/// Opaque bearer tokens validated through an RFC 7662 introspection endpoint.
+///
+/// `OpenAPI` has no introspection scheme, so it is advertised as a second
+/// HTTP bearer scheme whose description names the endpoint.
+///
+/// # Example YAML
+///
+/// ```yaml
+/// openapi:
+///   introspection:
+///     url: "https://idp.example.com/oauth2/introspect"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct OpenApiIntrospectionConfig {
+    /// Introspection endpoint the gateway's `AuthN` plugin calls.
+    pub url: String,
+}
+
 /// Route-level policy configuration.
 ///
 /// Enables coarse-grained early rejection of requests based on token scopes
@@ -1,14 +1,27 @@
+//! Gateway error types and the canonical JSON error body.
+//!
+//! Every error rendered with [`ErrorResponse`] has the same machine-readable shape:
+//!
+//! ```json
+//! { "code": "forbidden", "message": "...", "request_id": "abc123", "details": null }
+//! ```
+//!
+//! `code` is one of the stable [`ErrorCode`] strings and never depends on the
+//! human-readable message, so clients can branch on it safely.
+
+use std::sync::Arc;
+
 use axum::{
     Json,
     http::StatusCode,
     response::{IntoResponse, Response},
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-/// Type alias for error response tuple (without details).
-/// We keep `details` separately as `Option<String>` to avoid lifetime issues.
-type ErrorResponseTuple<'a> = (StatusCode, &'static str, &'a str);
+use authn_resolver_sdk::AuthNResolverError;
+use modkit::api::Problem;
+use modkit_auth::AuthError;
 
 #[derive(Debug, Error)]
 pub enum AppError {
@@ -28,83 +41,415 @@ pub enum AppError {
     Internal(#[source] anyhow::Error),
 }
 
-#[derive(Serialize)]
-struct ErrorBody<'a> {
-    code: &'a str,
-    message: &'a str,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    request_id: Option<&'a str>,
-    #[cfg(feature = "debug-errors")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    details: Option<&'a str>,
+/// Stable machine-readable error codes emitted by the gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    BadRequest,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    MethodNotAllowed,
+    Conflict,
+    PreconditionFailed,
+    PayloadTooLarge,
+    HeaderFieldsTooLarge,
+    UnsupportedMediaType,
+    UnprocessableEntity,
+    RateLimited,
+    Internal,
+    ServiceUnavailable,
+    GatewayTimeout,
 }
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response {
-        use AppError::{
-            BadRequest, Conflict, Forbidden, Internal, NotFound, TooManyRequests, Unauthorized,
-        };
+impl ErrorCode {
+    /// The wire representation of this code.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::BadRequest => "bad_request",
+            Self::Unauthorized => "unauthorized",
+            Self::Forbidden => "forbidden",
+            Self::NotFound => "not_found",
+            Self::MethodNotAllowed => "method_not_allowed",
+            Self::Conflict => "conflict",
+            Self::PreconditionFailed => "precondition_failed",
+            Self::PayloadTooLarge => "payload_too_large",
+            Self::HeaderFieldsTooLarge => "header_fields_too_large",
+            Self::UnsupportedMediaType => "unsupported_media_type",
+            Self::UnprocessableEntity => "unprocessable_entity",
+            Self::RateLimited => "rate_limited",
+            Self::Internal => "internal_error",
+            Self::ServiceUnavailable => "service_unavailable",
+            Self::GatewayTimeout => "gateway_timeout",
+        }
+    }
 
-        // Extract request_id from current span context if available
-        // Note: In real handlers we will get request_id from extensions
-        let request_id = "unknown";
+    /// Map an HTTP status to its canonical code.
+    ///
+    /// Unlisted 4xx statuses map to `bad_request`, everything else to `internal_error`.
+    #[must_use]
+    pub fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED => Self::Unauthorized,
+            StatusCode::FORBIDDEN => Self::Forbidden,
+            StatusCode::NOT_FOUND => Self::NotFound,
+            StatusCode::METHOD_NOT_ALLOWED => Self::MethodNotAllowed,
+            StatusCode::CONFLICT => Self::Conflict,
+            StatusCode::PRECONDITION_FAILED => Self::PreconditionFailed,
+            StatusCode::PAYLOAD_TOO_LARGE => Self::PayloadTooLarge,
+            StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE => Self::HeaderFieldsTooLarge,
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => Self::UnsupportedMediaType,
+            StatusCode::UNPROCESSABLE_ENTITY => Self::UnprocessableEntity,
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited,
+            StatusCode::SERVICE_UNAVAILABLE => Self::ServiceUnavailable,
+            StatusCode::GATEWAY_TIMEOUT => Self::GatewayTimeout,
+            s if s.is_client_error() => Self::BadRequest,
+            _ => Self::Internal,
+        }
+    }
 
-        // Keep details as owned String to avoid dangling references
-        #[cfg(feature = "debug-errors")]
-        let mut dbg_details: Option<String> = None;
-
-        // Map AppError to tuple (status, code, safe_msg)
-        let (status, code, safe_msg): ErrorResponseTuple = match &self {
-            BadRequest(m) => (StatusCode::BAD_REQUEST, "bad_request", m.as_str()),
-            Unauthorized(m) => (StatusCode::UNAUTHORIZED, "unauthorized", m.as_str()),
-            Forbidden(m) => (StatusCode::FORBIDDEN, "forbidden", m.as_str()),
-            NotFound(m) => (StatusCode::NOT_FOUND, "not_found", m.as_str()),
-            Conflict(m) => (StatusCode::CONFLICT, "conflict", m.as_str()),
-            TooManyRequests => (
-                StatusCode::TOO_MANY_REQUESTS,
-                "rate_limited",
-                "rate limited",
-            ),
-            #[cfg_attr(not(feature = "debug-errors"), allow(unused_variables))]
-            Internal(err) => {
-                #[cfg(feature = "debug-errors")]
-                {
-                    // Save error details as String, later exposed as Option<&str>
-                    dbg_details = Some(err.to_string());
-                }
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "internal_error",
-                    "internal error",
-                )
+    /// Map an `AuthN` resolver error to its canonical code.
+    #[must_use]
+    pub const fn from_authn_error(err: &AuthNResolverError) -> Self {
+        match err {
+            AuthNResolverError::Unauthorized(_) => Self::Unauthorized,
+            AuthNResolverError::NoPluginAvailable | AuthNResolverError::ServiceUnavailable(_) => {
+                Self::ServiceUnavailable
             }
+            AuthNResolverError::TokenAcquisitionFailed(_) | AuthNResolverError::Internal(_) => {
+                Self::Internal
+            }
+        }
+    }
+}
+
+/// Maps an [`AuthError`] raised by the gateway to the HTTP status and code sent
+/// to the client.
+///
+/// Deployments can install their own with
+/// [`ApiGateway::set_auth_error_mapper`](crate::ApiGateway::set_auth_error_mapper),
+/// e.g. to answer `404` instead of `403` so that denied resources are
+/// indistinguishable from missing ones.
+pub type AuthErrorMapper = Arc<dyn Fn(&AuthError) -> (StatusCode, ErrorCode) + Send + Sync>;
+
+/// The gateway's built-in [`AuthError`] mapping.
+#[must_use]
+pub const fn default_auth_error_mapping(err: &AuthError) -> (StatusCode, ErrorCode) {
+    match err {
+        AuthError::Unauthenticated
+        | AuthError::InvalidToken(_)
+        | AuthError::ValidationFailed(_)
+        | AuthError::IssuerMismatch { .. }
+        | AuthError::AudienceMismatch { .. }
+        | AuthError::TokenExpired => (StatusCode::UNAUTHORIZED, ErrorCode::Unauthorized),
+        AuthError::Forbidden => (StatusCode::FORBIDDEN, ErrorCode::Forbidden),
+        AuthError::JwksFetchFailed(_) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            ErrorCode::ServiceUnavailable,
+        ),
+        AuthError::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::Internal),
+    }
+}
+
+/// An [`AuthErrorMapper`] applying [`default_auth_error_mapping`].
+#[must_use]
+pub fn default_auth_error_mapper() -> AuthErrorMapper {
+    Arc::new(default_auth_error_mapping)
+}
+
+/// Render an auth rejection as an RFC 9457 problem, using `mapper` for the
+/// status and code.
+///
+/// `detail` is only sent when the mapper keeps the default mapping; a
+/// remapped error gets the generic reason of its new status so the response
+/// does not reveal the original cause.
+#[must_use]
+pub fn auth_error_response(mapper: &AuthErrorMapper, err: &AuthError, detail: &str) -> Response {
+    let (status, code) = mapper(err);
+    let reason = status.canonical_reason().unwrap_or("Error");
+    let detail = if (status, code) == default_auth_error_mapping(err) {
+        detail
+    } else {
+        reason
+    };
+
+    let mut problem = Problem::new(status, reason, detail);
+    if code != ErrorCode::from_status(status) {
+        problem = problem.with_code(code.as_str());
+    }
+    problem.into_response()
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Canonical JSON error body: `{ "code", "message", "request_id", "details" }`.
+///
+/// `request_id` and `details` are always present (possibly `null`) so the
+/// schema is identical for every error.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub code: String,
+    pub message: String,
+    pub request_id: Option<String>,
+    pub details: Option<serde_json::Value>,
+}
+
+impl ErrorResponse {
+    #[must_use]
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code: code.as_str().to_owned(),
+            message: message.into(),
+            request_id: None,
+            details: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    /// Build a body for a bare status (no upstream error payload).
+    #[must_use]
+    pub fn from_status(status: StatusCode) -> Self {
+        Self::new(
+            ErrorCode::from_status(status),
+            status.canonical_reason().unwrap_or("error"),
+        )
+    }
+
+    /// Convert an RFC 9457 problem (as produced by handlers, the error layer,
+    /// auth and scope enforcement) into the canonical body.
+    ///
+    /// The code is derived from the status; the application-level problem code,
+    /// validation errors and context are preserved under `details`.
+    #[must_use]
+    pub fn from_problem(problem: &Problem) -> Self {
+        let mut details = serde_json::Map::new();
+        if !problem.code.is_empty() {
+            details.insert("code".to_owned(), problem.code.clone().into());
+        }
+        if let Some(errors) = &problem.errors
+            && let Ok(errors) = serde_json::to_value(errors)
+        {
+            details.insert("errors".to_owned(), errors);
+        }
+        if let Some(context) = &problem.context {
+            details.insert("context".to_owned(), context.clone());
+        }
+
+        let message = if problem.detail.is_empty() {
+            problem.title.clone()
+        } else {
+            problem.detail.clone()
         };
+        let body = Self::new(ErrorCode::from_status(problem.status), message);
+        if details.is_empty() {
+            body
+        } else {
+            body.with_details(serde_json::Value::Object(details))
+        }
+    }
+
+    /// Convert an `AuthN` resolver error into the canonical body.
+    ///
+    /// The message is generic on purpose: resolver messages may contain token details.
+    #[must_use]
+    pub fn from_authn_error(err: &AuthNResolverError) -> Self {
+        let code = ErrorCode::from_authn_error(err);
+        let message = match code {
+            ErrorCode::Unauthorized => "Authentication failed",
+            ErrorCode::ServiceUnavailable => "Authentication service unavailable",
+            _ => "Internal authentication error",
+        };
+        Self::new(code, message)
+    }
+
+    /// Render the body with the given status.
+    #[must_use]
+    pub fn into_response_with_status(self, status: StatusCode) -> Response {
+        (status, Json(self)).into_response()
+    }
+}
+
+impl AppError {
+    /// HTTP status and canonical code for this error.
+    #[must_use]
+    pub const fn status_and_code(&self) -> (StatusCode, ErrorCode) {
+        match self {
+            Self::BadRequest(_) => (StatusCode::BAD_REQUEST, ErrorCode::BadRequest),
+            Self::Unauthorized(_) => (StatusCode::UNAUTHORIZED, ErrorCode::Unauthorized),
+            Self::Forbidden(_) => (StatusCode::FORBIDDEN, ErrorCode::Forbidden),
+            Self::NotFound(_) => (StatusCode::NOT_FOUND, ErrorCode::NotFound),
+            Self::Conflict(_) => (StatusCode::CONFLICT, ErrorCode::Conflict),
+            Self::TooManyRequests => (StatusCode::TOO_MANY_REQUESTS, ErrorCode::RateLimited),
+            Self::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::Internal),
+        }
+    }
+
+    /// Render this error as the canonical body, tagged with `request_id`.
+    #[must_use]
+    pub fn into_response_with_request_id(self, request_id: Option<&str>) -> Response {
+        let (status, code) = self.status_and_code();
 
         // Log error with appropriate severity
         match &self {
-            Internal(err) => tracing::error!(
-                request_id = %request_id,
+            AppError::Internal(err) => tracing::error!(
+                request_id = ?request_id,
                 error = %err,
                 status = status.as_u16(),
                 "request failed"
             ),
             other => tracing::warn!(
-                request_id = %request_id,
+                request_id = ?request_id,
                 error = %other,
                 status = status.as_u16(),
                 "request failed"
             ),
         }
 
-        // Build JSON response body
-        let body = ErrorBody {
-            code,
-            message: safe_msg,
-            request_id: Some(request_id),
-            #[cfg(feature = "debug-errors")]
-            details: dbg_details.as_deref(),
+        let message = match &self {
+            AppError::BadRequest(m)
+            | AppError::Unauthorized(m)
+            | AppError::Forbidden(m)
+            | AppError::NotFound(m)
+            | AppError::Conflict(m) => m.clone(),
+            AppError::TooManyRequests => "rate limited".to_owned(),
+            AppError::Internal(_) => "internal error".to_owned(),
         };
 
-        (status, Json(body)).into_response()
+        let mut body = ErrorResponse::new(code, message);
+        body.request_id = request_id.map(str::to_owned);
+
+        #[cfg(feature = "debug-errors")]
+        if let AppError::Internal(err) = &self {
+            body = body.with_details(serde_json::Value::String(err.to_string()));
+        }
+
+        body.into_response_with_status(status)
+    }
+}
+
+impl IntoResponse for AppError {
+    /// The request id is not reachable from here; when the gateway renders
+    /// errors in the canonical JSON format it is filled in by the
+    /// error format middleware.
+    fn into_response(self) -> Response {
+        self.into_response_with_request_id(None)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_stable_strings() {
+        assert_eq!(ErrorCode::Forbidden.as_str(), "forbidden");
+        assert_eq!(ErrorCode::NotFound.as_str(), "not_found");
+        assert_eq!(ErrorCode::Internal.as_str(), "internal_error");
+        assert_eq!(
+            ErrorCode::from_status(StatusCode::IM_A_TEAPOT),
+            ErrorCode::BadRequest
+        );
+        assert_eq!(
+            ErrorCode::from_status(StatusCode::BAD_GATEWAY),
+            ErrorCode::Internal
+        );
+    }
+
+    #[test]
+    fn authn_errors_map_to_codes() {
+        let err = AuthNResolverError::Unauthorized("expired token abc".to_owned());
+        let body = ErrorResponse::from_authn_error(&err);
+        assert_eq!(body.code, "unauthorized");
+        assert!(!body.message.contains("abc"));
+
+        let err = AuthNResolverError::NoPluginAvailable;
+        assert_eq!(
+            ErrorResponse::from_authn_error(&err).code,
+            "service_unavailable"
+        );
+    }
+
+    #[test]
+    fn problem_converts_with_details() {
+        let problem = Problem::new(StatusCode::FORBIDDEN, "Forbidden", "Access denied")
+            .with_code("AUTHZ_DENIED");
+        let body = ErrorResponse::from_problem(&problem).with_request_id("rid-1");
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "code": "forbidden",
+                "message": "Access denied",
+                "request_id": "rid-1",
+                "details": { "code": "AUTHZ_DENIED" }
+            })
+        );
+    }
+
+    #[test]
+    fn default_auth_mapping_keeps_forbidden_as_403() {
+        assert_eq!(
+            default_auth_error_mapping(&AuthError::Forbidden),
+            (StatusCode::FORBIDDEN, ErrorCode::Forbidden)
+        );
+        assert_eq!(
+            default_auth_error_mapping(&AuthError::TokenExpired),
+            (StatusCode::UNAUTHORIZED, ErrorCode::Unauthorized)
+        );
+
+        let resp = auth_error_response(
+            &default_auth_error_mapper(),
+            &AuthError::Forbidden,
+            "Insufficient token scopes for this resource",
+        );
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn custom_auth_mapper_can_hide_forbidden_as_404() {
+        let mapper: AuthErrorMapper = Arc::new(|err| match err {
+            AuthError::Forbidden => (StatusCode::NOT_FOUND, ErrorCode::NotFound),
+            other => default_auth_error_mapping(other),
+        });
+
+        let resp = auth_error_response(
+            &mapper,
+            &AuthError::Forbidden,
+            "Insufficient token scopes for this resource",
+        );
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem["detail"], "Not Found");
+        assert!(!body.windows(6).any(|w| w == b"scopes"));
+
+        let resp = auth_error_response(&mapper, &AuthError::Unauthenticated, "Missing token");
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn app_error_renders_canonical_body() {
+        let resp = AppError::NotFound("user 42 not found".to_owned())
+            .into_response_with_request_id(Some("rid-2"));
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
 }
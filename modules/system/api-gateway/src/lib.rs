@@ -14,8 +14,13 @@ mod config;
 mod cors;
 pub mod error;
 pub mod middleware;
+mod openapi_security;
 mod router_cache;
 mod web;
 
 // === RE-EXPORTS ===
-pub use config::{ApiGatewayConfig, CorsConfig};
+pub use config::{
+    ApiGatewayConfig, BodyLoggingConfig, CorsConfig, ErrorFormat, IdempotencyConfig,
+    TrailingSlashPolicy,
+};
+pub use web::FallbackRoute;
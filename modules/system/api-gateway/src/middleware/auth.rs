@@ -1,11 +1,12 @@
 use axum::http::Method;
-use axum::response::IntoResponse;
+use std::time::{Duration, Instant};
 use std::{collections::HashMap, sync::Arc};
 
+use crate::error::{AuthErrorMapper, auth_error_response};
 use crate::middleware::common;
 
-use authn_resolver_sdk::{AuthNResolverClient, AuthNResolverError};
-use modkit::api::Problem;
+use authn_resolver_sdk::{AuthNResolverClient, AuthNResolverError, AuthenticationResult};
+use modkit_auth::{AuthDecision, AuthError, AuthMetrics, AuthOutcome};
 use modkit_security::SecurityContext;
 
 /// Route matcher for a specific HTTP method (authenticated routes).
@@ -80,12 +81,17 @@ fn convert_axum_path_to_matchit(path: &str) -> String {
 }
 
 /// Whether a route requires authentication.
+///
+/// Routes declare their mode through `OperationBuilder`: `.authenticated()`
+/// → `Required`, `.optional_auth()` → `Optional`, `.public()` → `None`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AuthRequirement {
-    /// No authentication required (public route).
+    /// No authentication (public route); any `Authorization` header is ignored.
     None,
     /// Authentication required.
     Required,
+    /// A bearer token is validated when present; otherwise anonymous.
+    Optional,
 }
 
 /// Gateway-specific route policy implementation
@@ -93,6 +99,7 @@ pub enum AuthRequirement {
 pub struct GatewayRoutePolicy {
     route_matchers: Arc<HashMap<Method, RouteMatcher>>,
     public_matchers: Arc<HashMap<Method, PublicRouteMatcher>>,
+    optional_matchers: Arc<HashMap<Method, PublicRouteMatcher>>,
     require_auth_by_default: bool,
 }
 
@@ -106,10 +113,21 @@ impl GatewayRoutePolicy {
         Self {
             route_matchers,
             public_matchers,
+            optional_matchers: Arc::new(HashMap::new()),
             require_auth_by_default,
         }
     }
 
+    /// Set the routes on which authentication is optional.
+    #[must_use]
+    pub fn with_optional_matchers(
+        mut self,
+        optional_matchers: Arc<HashMap<Method, PublicRouteMatcher>>,
+    ) -> Self {
+        self.optional_matchers = optional_matchers;
+        self
+    }
+
     /// Resolve the authentication requirement for a given (method, path).
     #[must_use]
     pub fn resolve(&self, method: &Method, path: &str) -> AuthRequirement {
@@ -119,6 +137,15 @@ impl GatewayRoutePolicy {
             .get(method)
             .is_some_and(|matcher| matcher.find(path));
 
+        // Optional-auth routes validate a token only when one is sent
+        let is_optional = self
+            .optional_matchers
+            .get(method)
+            .is_some_and(|matcher| matcher.find(path));
+        if !is_authenticated && is_optional {
+            return AuthRequirement::Optional;
+        }
+
         // Check if route is explicitly public using pattern matching
         let is_public = self
             .public_matchers
@@ -141,6 +168,10 @@ impl GatewayRoutePolicy {
 pub struct AuthState {
     pub authn_client: Arc<dyn AuthNResolverClient>,
     pub route_policy: GatewayRoutePolicy,
+    /// Status and code for rejections raised by the gateway itself.
+    pub error_mapper: AuthErrorMapper,
+    /// Receives one [`AuthDecision`] per authentication attempt.
+    pub metrics: Arc<dyn AuthMetrics>,
 }
 
 /// Helper to build `GatewayRoutePolicy` from operation requirements.
@@ -153,6 +184,7 @@ pub fn build_route_policy(
     cfg: &crate::config::ApiGatewayConfig,
     authenticated_routes: std::collections::HashSet<(Method, String)>,
     public_routes: std::collections::HashSet<(Method, String)>,
+    optional_routes: std::collections::HashSet<(Method, String)>,
 ) -> Result<GatewayRoutePolicy, anyhow::Error> {
     // Build route matchers per HTTP method (authenticated routes)
     let mut route_matchers_map: HashMap<Method, RouteMatcher> = HashMap::new();
@@ -182,11 +214,25 @@ pub fn build_route_policy(
             .map_err(|e| anyhow::anyhow!("Failed to insert public route pattern '{path}': {e}"))?;
     }
 
+    // Build optional-auth matchers per HTTP method
+    let mut optional_matchers_map: HashMap<Method, PublicRouteMatcher> = HashMap::new();
+
+    for (method, path) in optional_routes {
+        let matcher = optional_matchers_map
+            .entry(method)
+            .or_insert_with(PublicRouteMatcher::new);
+        let matchit_path = convert_axum_path_to_matchit(&path);
+        matcher.insert(&matchit_path).map_err(|e| {
+            anyhow::anyhow!("Failed to insert optional-auth route pattern '{path}': {e}")
+        })?;
+    }
+
     Ok(GatewayRoutePolicy::new(
         Arc::new(route_matchers_map),
         Arc::new(public_matchers_map),
         cfg.require_auth_by_default,
-    ))
+    )
+    .with_optional_matchers(Arc::new(optional_matchers_map)))
 }
 
 /// Authentication middleware that uses the `AuthN` Resolver to validate bearer tokens.
@@ -196,6 +242,8 @@ pub fn build_route_policy(
 /// 2. Resolves the route's auth requirement via `GatewayRoutePolicy`
 /// 3. For public routes: inserts anonymous `SecurityContext`
 /// 4. For required routes: extracts bearer token, calls `AuthN` Resolver, inserts `SecurityContext`
+/// 5. For optional routes: as for required routes when a bearer token is sent,
+///    otherwise as for public routes
 pub async fn authn_middleware(
     axum::extract::State(state): axum::extract::State<AuthState>,
     mut req: axum::extract::Request,
@@ -224,46 +272,107 @@ pub async fn authn_middleware(
         }
         AuthRequirement::Required => {
             let Some(token) = extract_bearer_token(req.headers()) else {
-                return Problem::new(
-                    axum::http::StatusCode::UNAUTHORIZED,
-                    "Unauthorized",
+                state.metrics.record_decision(&AuthDecision::new(
+                    AuthOutcome::Unauthenticated,
+                    Duration::ZERO,
+                ));
+                return auth_error_response(
+                    &state.error_mapper,
+                    &AuthError::Unauthenticated,
                     "Missing or invalid Authorization header",
-                )
-                .into_response();
+                );
             };
 
-            match state.authn_client.authenticate(token).await {
+            let started = Instant::now();
+            let result = state.authn_client.authenticate(token).await;
+            record_authn_decision(&state, started, &result);
+            match result {
                 Ok(result) => {
                     req.extensions_mut().insert(result.security_context);
                     next.run(req).await
                 }
-                Err(err) => authn_error_to_response(&err),
+                Err(err) => authn_error_to_response(&state.error_mapper, &err),
+            }
+        }
+        AuthRequirement::Optional => {
+            let Some(token) = extract_bearer_token(req.headers()) else {
+                req.extensions_mut().insert(SecurityContext::anonymous());
+                return next.run(req).await;
+            };
+
+            // A token that was sent must be valid; it is never downgraded to anonymous
+            let started = Instant::now();
+            let result = state.authn_client.authenticate(token).await;
+            record_authn_decision(&state, started, &result);
+            match result {
+                Ok(result) => {
+                    req.extensions_mut().insert(result.security_context);
+                    next.run(req).await
+                }
+                Err(err) => authn_error_to_response(&state.error_mapper, &err),
             }
         }
     }
 }
 
-/// Convert `AuthNResolverError` to an RFC-9457 Problem Details response.
-fn authn_error_to_response(err: &AuthNResolverError) -> axum::response::Response {
-    log_authn_error(err);
-    let (status, title, detail) = match err {
-        AuthNResolverError::Unauthorized(_) => (
-            axum::http::StatusCode::UNAUTHORIZED,
-            "Unauthorized",
-            "Authentication failed",
-        ),
-        AuthNResolverError::NoPluginAvailable | AuthNResolverError::ServiceUnavailable(_) => (
-            axum::http::StatusCode::SERVICE_UNAVAILABLE,
-            "Service Unavailable",
-            "Authentication service unavailable",
-        ),
-        AuthNResolverError::TokenAcquisitionFailed(_) | AuthNResolverError::Internal(_) => (
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-            "Internal Server Error",
-            "Internal authentication error",
+/// Report the outcome of an `AuthN` resolver call to `state.metrics`.
+fn record_authn_decision(
+    state: &AuthState,
+    started: Instant,
+    result: &Result<AuthenticationResult, AuthNResolverError>,
+) {
+    let decision = match result {
+        Ok(result) => AuthDecision::new(AuthOutcome::Allowed, started.elapsed())
+            .with_subject(result.security_context.subject_id().to_string()),
+        Err(err) => AuthDecision::new(
+            AuthOutcome::from_error(&authn_error_to_auth_error(err)),
+            started.elapsed(),
         ),
     };
-    Problem::new(status, title, detail).into_response()
+    state.metrics.record_decision(&decision);
+}
+
+/// Convert `AuthNResolverError` to an RFC-9457 Problem Details response,
+/// using `mapper` for the status and code like every other auth rejection.
+fn authn_error_to_response(
+    mapper: &AuthErrorMapper,
+    err: &AuthNResolverError,
+) -> axum::response::Response {
+    log_authn_error(err);
+    let detail = match err {
+        AuthNResolverError::Unauthorized(_) => "Authentication failed",
+        AuthNResolverError::NoPluginAvailable | AuthNResolverError::ServiceUnavailable(_) => {
+            "Authentication service unavailable"
+        }
+        AuthNResolverError::TokenAcquisitionFailed(_) | AuthNResolverError::Internal(_) => {
+            "Internal authentication error"
+        }
+    };
+    auth_error_response(mapper, &authn_error_to_auth_error(err), detail)
+}
+
+/// The [`AuthError`] a mapper sees for an `AuthN` resolver failure.
+///
+/// Resolvers only report `Unauthorized(reason)`, so an expired token is
+/// recognized by its reason. An unreachable resolver is reported as
+/// `JwksFetchFailed`, the variant for unavailable verification backends.
+fn authn_error_to_auth_error(err: &AuthNResolverError) -> AuthError {
+    match err {
+        AuthNResolverError::Unauthorized(reason)
+            if reason.to_ascii_lowercase().contains("expired") =>
+        {
+            AuthError::TokenExpired
+        }
+        AuthNResolverError::Unauthorized(reason) => AuthError::InvalidToken(reason.clone()),
+        AuthNResolverError::NoPluginAvailable => {
+            AuthError::JwksFetchFailed("no AuthN plugin available".to_owned())
+        }
+        AuthNResolverError::ServiceUnavailable(reason) => {
+            AuthError::JwksFetchFailed(reason.clone())
+        }
+        AuthNResolverError::TokenAcquisitionFailed(reason)
+        | AuthNResolverError::Internal(reason) => AuthError::Internal(reason.clone()),
+    }
 }
 
 /// Log authentication errors at appropriate levels.
@@ -451,6 +560,50 @@ mod tests {
         assert_eq!(result, AuthRequirement::Required);
     }
 
+    #[test]
+    fn optional_route_returns_optional_regardless_of_default() {
+        let mut optional_matchers = HashMap::new();
+        let mut matcher = PublicRouteMatcher::new();
+        matcher.insert("/articles/{id}").unwrap();
+        optional_matchers.insert(Method::GET, matcher);
+        let optional_matchers = Arc::new(optional_matchers);
+
+        for require_auth_by_default in [true, false] {
+            let policy = build_test_policy(HashMap::new(), HashMap::new(), require_auth_by_default)
+                .with_optional_matchers(Arc::clone(&optional_matchers));
+
+            assert_eq!(
+                policy.resolve(&Method::GET, "/articles/42"),
+                AuthRequirement::Optional
+            );
+            assert_ne!(
+                policy.resolve(&Method::POST, "/articles/42"),
+                AuthRequirement::Optional
+            );
+        }
+    }
+
+    #[test]
+    fn authenticated_route_has_priority_over_optional() {
+        let mut route_matchers = HashMap::new();
+        let mut matcher = RouteMatcher::new();
+        matcher.insert("/articles").unwrap();
+        route_matchers.insert(Method::GET, matcher);
+
+        let mut optional_matchers = HashMap::new();
+        let mut matcher = PublicRouteMatcher::new();
+        matcher.insert("/articles").unwrap();
+        optional_matchers.insert(Method::GET, matcher);
+
+        let policy = build_test_policy(route_matchers, HashMap::new(), false)
+            .with_optional_matchers(Arc::new(optional_matchers));
+
+        assert_eq!(
+            policy.resolve(&Method::GET, "/articles"),
+            AuthRequirement::Required
+        );
+    }
+
     #[test]
     fn different_methods_resolve_independently() {
         let mut route_matchers = HashMap::new();
@@ -0,0 +1,438 @@
+//! Request/response body logging middleware (for debugging).
+//!
+//! Emits one `tracing::info!` event with target `body_log` per request body and
+//! per response body, once the body has been fully streamed (or dropped). Only
+//! the first `max_bytes` of each body are kept, and sensitive JSON values are
+//! redacted by key name before logging.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use bytes::{Bytes, BytesMut};
+use glob::{MatchOptions, Pattern};
+use http_body::Frame;
+
+use super::request_id::XRequestId;
+use crate::config::BodyLoggingConfig;
+
+/// Replacement for redacted JSON values.
+const REDACTED: &str = "\"[REDACTED]\"";
+
+/// Compiled body logging settings shared by all requests.
+#[derive(Clone, Debug)]
+pub struct BodyLogger {
+    max_bytes: usize,
+    redact_keys: Arc<[String]>,
+    exclude_routes: Arc<[Pattern]>,
+}
+
+impl BodyLogger {
+    /// Build the logger from configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any `exclude_routes` glob pattern is invalid.
+    pub fn from_config(config: &BodyLoggingConfig) -> Result<Self, anyhow::Error> {
+        let exclude_routes = config
+            .exclude_routes
+            .iter()
+            .map(|path| {
+                Pattern::new(path).map_err(|e| {
+                    anyhow::anyhow!("Invalid glob pattern '{path}' in body_logging: {e}")
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        tracing::warn!(
+            max_bytes = config.max_bytes,
+            redact_keys = ?config.redact_keys,
+            "Body logging enabled: request and response bodies are written to logs"
+        );
+
+        Ok(Self {
+            max_bytes: config.max_bytes,
+            redact_keys: config.redact_keys.iter().cloned().collect(),
+            exclude_routes: exclude_routes.into(),
+        })
+    }
+
+    fn is_excluded(&self, path: &str) -> bool {
+        let match_opts = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::default()
+        };
+        self.exclude_routes
+            .iter()
+            .any(|p| p.matches_with(path, match_opts))
+    }
+
+    /// Render a captured body for the log: redacted if JSON, truncated with a marker.
+    fn render(&self, captured: &[u8], total_bytes: u64, is_json: bool) -> String {
+        let text = String::from_utf8_lossy(captured);
+        let rendered = if is_json && !self.redact_keys.is_empty() {
+            redact_json(&text, &self.redact_keys)
+        } else {
+            text.into_owned()
+        };
+        if total_bytes > captured.len() as u64 {
+            format!("{rendered}...[truncated, {total_bytes} bytes total]")
+        } else {
+            rendered
+        }
+    }
+}
+
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.to_ascii_lowercase().contains("json"))
+}
+
+fn is_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("text/event-stream"))
+}
+
+/// Middleware that logs request and response bodies.
+///
+/// Bodies are not buffered: frames are forwarded as they arrive while a copy of
+/// the first `max_bytes` is kept for the log, so streaming is unaffected.
+/// Excluded routes and `text/event-stream` responses are passed through untouched.
+pub async fn body_logging_middleware(
+    State(logger): State<BodyLogger>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if logger.is_excluded(req.uri().path()) {
+        return next.run(req).await;
+    }
+
+    let method = req.method().to_string();
+    let uri = req.uri().path_and_query().map_or_else(
+        || req.uri().path().to_owned(),
+        std::string::ToString::to_string,
+    );
+    let request_id = req
+        .extensions()
+        .get::<XRequestId>()
+        .map_or_else(String::new, |x| x.0.clone());
+
+    let request_ctx = BodyLogContext {
+        logger: logger.clone(),
+        direction: "request",
+        request_id: request_id.clone(),
+        method: method.clone(),
+        uri: uri.clone(),
+        status: None,
+        is_json: is_json(req.headers()),
+    };
+    let req = req.map(|body| Body::new(TeeBody::new(body, request_ctx)));
+
+    let response = next.run(req).await;
+
+    if is_event_stream(response.headers()) {
+        return response;
+    }
+
+    let response_ctx = BodyLogContext {
+        logger,
+        direction: "response",
+        request_id,
+        method,
+        uri,
+        status: Some(response.status().as_u16()),
+        is_json: is_json(response.headers()),
+    };
+    response.map(|body| Body::new(TeeBody::new(body, response_ctx)))
+}
+
+/// All data needed to emit a body log once the body completes.
+struct BodyLogContext {
+    logger: BodyLogger,
+    direction: &'static str,
+    request_id: String,
+    method: String,
+    uri: String,
+    status: Option<u16>,
+    is_json: bool,
+}
+
+impl BodyLogContext {
+    fn emit(self, captured: &[u8], total_bytes: u64) {
+        let body = self.logger.render(captured, total_bytes, self.is_json);
+        tracing::info!(
+            target: "body_log",
+            direction = self.direction,
+            request_id = %self.request_id,
+            method = %self.method,
+            uri = %self.uri,
+            status = self.status,
+            body_bytes = total_bytes,
+            body = %body,
+        );
+    }
+}
+
+/// A body wrapper that forwards frames unchanged while copying the first
+/// `max_bytes` of data, then emits the body log once the body is fully
+/// consumed or dropped.
+struct TeeBody {
+    inner: Body,
+    captured: BytesMut,
+    total_bytes: u64,
+    log_ctx: Option<BodyLogContext>,
+}
+
+impl TeeBody {
+    fn new(inner: Body, log_ctx: BodyLogContext) -> Self {
+        Self {
+            inner,
+            captured: BytesMut::new(),
+            total_bytes: 0,
+            log_ctx: Some(log_ctx),
+        }
+    }
+
+    fn capture(&mut self, data: &Bytes) {
+        self.total_bytes = self.total_bytes.saturating_add(data.len() as u64);
+        if let Some(ctx) = &self.log_ctx {
+            let room = ctx.logger.max_bytes.saturating_sub(self.captured.len());
+            self.captured
+                .extend_from_slice(&data[..room.min(data.len())]);
+        }
+    }
+
+    fn emit(&mut self) {
+        if let Some(ctx) = self.log_ctx.take() {
+            ctx.emit(&self.captured, self.total_bytes);
+        }
+    }
+}
+
+impl http_body::Body for TeeBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+
+        match Pin::new(&mut this.inner).poll_frame(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Some(Ok(frame))) => {
+                if let Some(data) = frame.data_ref() {
+                    this.capture(data);
+                }
+                Poll::Ready(Some(Ok(frame)))
+            }
+            Poll::Ready(other) => {
+                // Body finished or errored: log what was seen so far.
+                this.emit();
+                Poll::Ready(other)
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl Drop for TeeBody {
+    fn drop(&mut self) {
+        // Also covers bodies the handler never read and client disconnects.
+        self.emit();
+    }
+}
+
+/// Replace the values of JSON object members whose key is in `keys`
+/// (case-insensitive, at any depth) with `"[REDACTED]"`.
+///
+/// Works on the raw text so that truncated documents are redacted too; a value
+/// cut off by truncation is redacted up to the end of the text.
+fn redact_json(text: &str, keys: &[String]) -> String {
+    let bytes = text.as_bytes();
+    let mut out = String::with_capacity(text.len());
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != b'"' {
+            i += 1;
+            continue;
+        }
+
+        let (key_end, terminated) = string_end(bytes, i);
+        if !terminated {
+            break;
+        }
+        let key = &text[i + 1..key_end - 1];
+        let colon = skip_whitespace(bytes, key_end);
+        if bytes.get(colon) != Some(&b':') || !keys.iter().any(|k| k.eq_ignore_ascii_case(key)) {
+            i = key_end;
+            continue;
+        }
+
+        let value_start = skip_whitespace(bytes, colon + 1);
+        if value_start >= bytes.len() {
+            break;
+        }
+        let value_end = value_end(bytes, value_start);
+        out.push_str(&text[copied..value_start]);
+        out.push_str(REDACTED);
+        copied = value_end;
+        i = value_end;
+    }
+
+    out.push_str(&text[copied..]);
+    out
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+        i += 1;
+    }
+    i
+}
+
+/// End of the JSON string starting at `start` (a `"`), past the closing quote.
+/// Returns `(bytes.len(), false)` for an unterminated string.
+fn string_end(bytes: &[u8], start: usize) -> (usize, bool) {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return (i + 1, true),
+            _ => i += 1,
+        }
+    }
+    (bytes.len(), false)
+}
+
+/// End of the JSON value starting at `start`, or `bytes.len()` if it is cut off.
+fn value_end(bytes: &[u8], start: usize) -> usize {
+    match bytes[start] {
+        b'"' => string_end(bytes, start).0,
+        b'{' | b'[' => {
+            let mut depth = 0usize;
+            let mut i = start;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'"' => {
+                        i = string_end(bytes, i).0;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return i + 1;
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            bytes.len()
+        }
+        _ => {
+            let mut i = start;
+            while i < bytes.len()
+                && !matches!(bytes[i], b',' | b'}' | b']')
+                && !bytes[i].is_ascii_whitespace()
+            {
+                i += 1;
+            }
+            i
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn keys() -> Vec<String> {
+        vec!["password".to_owned(), "token".to_owned()]
+    }
+
+    fn logger(max_bytes: usize) -> BodyLogger {
+        BodyLogger::from_config(&BodyLoggingConfig {
+            enabled: true,
+            max_bytes,
+            redact_keys: keys(),
+            exclude_routes: vec!["/events/**".to_owned()],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn redacts_matching_keys_at_any_depth() {
+        let input = r#"{"user":"bob","Password":"hunter2","auth":{"token":{"v":"x,}"},"n":1},"list":[{"password":42}]}"#;
+        assert_eq!(
+            redact_json(input, &keys()),
+            r#"{"user":"bob","Password":"[REDACTED]","auth":{"token":"[REDACTED]","n":1},"list":[{"password":"[REDACTED]"}]}"#
+        );
+    }
+
+    #[test]
+    fn keeps_values_equal_to_a_key_name() {
+        let input = r#"{"field": "password", "other": ["token"]}"#;
+        assert_eq!(redact_json(input, &keys()), input);
+    }
+
+    #[test]
+    fn redacts_value_cut_off_by_truncation() {
+        assert_eq!(
+            redact_json(r#"{"name":"a","password":"hun"#, &keys()),
+            r#"{"name":"a","password":"[REDACTED]""#
+        );
+    }
+
+    #[test]
+    fn render_marks_truncated_body() {
+        let rendered = logger(4).render(b"abcd", 10, false);
+        assert_eq!(rendered, "abcd...[truncated, 10 bytes total]");
+    }
+
+    #[test]
+    fn render_leaves_non_json_unredacted() {
+        let body = br#"{"password":"x"}"#;
+        assert_eq!(
+            logger(1024).render(body, body.len() as u64, false),
+            r#"{"password":"x"}"#
+        );
+    }
+
+    #[test]
+    fn excluded_routes_match_glob() {
+        let logger = logger(16);
+        assert!(logger.is_excluded("/events/v1/stream"));
+        assert!(!logger.is_excluded("/users/v1/users"));
+    }
+
+    #[test]
+    fn rejects_invalid_glob() {
+        let config = BodyLoggingConfig {
+            exclude_routes: vec!["/bad/[".to_owned()],
+            ..BodyLoggingConfig::default()
+        };
+        assert!(BodyLogger::from_config(&config).is_err());
+    }
+}
@@ -0,0 +1,485 @@
+//! Concurrency Limit Middleware
+//!
+//! Bounds the number of in-flight requests globally and per route. When a limit
+//! is saturated, requests either wait in a bounded queue or are shed with
+//! `503 Service Unavailable` so downstream services are not overwhelmed.
+//!
+//! Each authenticated subject can additionally be held to a fair share of that
+//! capacity; its excess is shed with `429 Too Many Requests`.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use axum::Router;
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use glob::{MatchOptions, Pattern};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::config::ConcurrencyLimitConfig;
+use crate::middleware::common;
+use modkit::api::Problem;
+use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+/// A single in-flight limit with its bounded wait queue.
+#[derive(Debug)]
+struct Limit {
+    semaphore: Arc<Semaphore>,
+    queued: AtomicUsize,
+}
+
+impl Limit {
+    fn new(max_in_flight: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_in_flight)),
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    /// Acquire a permit, waiting in the queue if there is room for it.
+    ///
+    /// Returns `None` when the request must be shed.
+    async fn acquire(
+        &self,
+        max_queued: usize,
+        queue_timeout: Duration,
+    ) -> Option<OwnedSemaphorePermit> {
+        if let Ok(permit) = Arc::clone(&self.semaphore).try_acquire_owned() {
+            return Some(permit);
+        }
+
+        // Reserve a queue slot; give up immediately if the queue is full.
+        self.queued
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < max_queued).then_some(n + 1)
+            })
+            .ok()?;
+        let _slot = QueueSlot(&self.queued);
+
+        tokio::time::timeout(queue_timeout, Arc::clone(&self.semaphore).acquire_owned())
+            .await
+            .ok()?
+            .ok()
+    }
+
+    fn available(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+}
+
+/// Releases a reserved queue slot when dropped (including on cancellation).
+struct QueueSlot<'a>(&'a AtomicUsize);
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// In-flight request counts per subject, bounded by `max_in_flight` each.
+///
+/// Only subjects with requests in flight have an entry, so the map is bounded
+/// by the number of concurrently active clients.
+#[derive(Debug)]
+struct SubjectLimits {
+    max_in_flight: usize,
+    in_flight: Mutex<HashMap<Uuid, usize>>,
+}
+
+impl SubjectLimits {
+    /// Take one of `subject`'s slots, or `None` if it holds all of them.
+    fn try_acquire(self: &Arc<Self>, subject: Uuid) -> Option<SubjectPermit> {
+        let mut in_flight = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let count = in_flight.entry(subject).or_insert(0);
+        if *count >= self.max_in_flight {
+            return None;
+        }
+        *count += 1;
+        Some(SubjectPermit {
+            limits: Arc::clone(self),
+            subject,
+        })
+    }
+
+    fn in_flight(&self, subject: Uuid) -> usize {
+        self.in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&subject)
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// Releases a subject's slot when dropped (including on cancellation).
+struct SubjectPermit {
+    limits: Arc<SubjectLimits>,
+    subject: Uuid,
+}
+
+impl Drop for SubjectPermit {
+    fn drop(&mut self) {
+        let mut in_flight = self
+            .limits
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(count) = in_flight.get_mut(&self.subject) {
+            *count -= 1;
+            if *count == 0 {
+                in_flight.remove(&self.subject);
+            }
+        }
+    }
+}
+
+/// Marker returned when a limit is saturated and its queue is full.
+struct Saturated;
+
+/// A compiled per-route rule: glob pattern + optional method + its limit.
+#[derive(Debug)]
+struct RouteLimit {
+    pattern: Pattern,
+    /// HTTP method to match (uppercase). None means match any method.
+    method: Option<String>,
+    limit: Limit,
+}
+
+/// Compiled concurrency limits shared by all requests.
+#[derive(Clone, Debug)]
+pub struct ConcurrencyLimiter {
+    global: Option<Arc<Limit>>,
+    routes: Arc<[RouteLimit]>,
+    subjects: Option<Arc<SubjectLimits>>,
+    max_queued: usize,
+    queue_timeout: Duration,
+}
+
+impl ConcurrencyLimiter {
+    /// Build the limiter from configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any glob pattern is invalid, a route rule has `max_in_flight == 0`,
+    /// or the per-subject limit does not leave global capacity for other subjects.
+    pub fn from_config(config: &ConcurrencyLimitConfig) -> Result<Self, anyhow::Error> {
+        let per_subject = config.per_subject_max_in_flight;
+        if per_subject > 0
+            && config.global_max_in_flight > 0
+            && per_subject >= config.global_max_in_flight
+        {
+            return Err(anyhow::anyhow!(
+                "Concurrency limit per_subject_max_in_flight = {per_subject} must be below \
+                 global_max_in_flight = {}, or a single subject can take all capacity",
+                config.global_max_in_flight
+            ));
+        }
+
+        let mut routes = Vec::with_capacity(config.routes.len());
+
+        for rule in &config.routes {
+            if rule.max_in_flight == 0 {
+                return Err(anyhow::anyhow!(
+                    "Concurrency limit rule for path '{}' has max_in_flight = 0, \
+                     which would reject every request",
+                    rule.path
+                ));
+            }
+
+            let pattern = Pattern::new(&rule.path).map_err(|e| {
+                anyhow::anyhow!(
+                    "Invalid glob pattern '{}' in concurrency_limit: {e}",
+                    rule.path
+                )
+            })?;
+
+            routes.push(RouteLimit {
+                pattern,
+                method: rule.method.as_ref().map(|m| m.to_uppercase()),
+                limit: Limit::new(rule.max_in_flight),
+            });
+        }
+
+        let global = (config.global_max_in_flight > 0)
+            .then(|| Arc::new(Limit::new(config.global_max_in_flight)));
+
+        let subjects = (per_subject > 0).then(|| {
+            Arc::new(SubjectLimits {
+                max_in_flight: per_subject,
+                in_flight: Mutex::new(HashMap::new()),
+            })
+        });
+
+        tracing::info!(
+            global_max_in_flight = config.global_max_in_flight,
+            per_subject_max_in_flight = per_subject,
+            route_rules = routes.len(),
+            max_queued = config.max_queued,
+            "Concurrency limiting enabled"
+        );
+
+        Ok(Self {
+            global,
+            routes: Arc::from(routes),
+            subjects,
+            max_queued: config.max_queued,
+            queue_timeout: Duration::from_millis(config.queue_timeout_ms),
+        })
+    }
+
+    /// Find the first route rule matching the given path and method.
+    fn route_limit(&self, path: &str, method: &str) -> Option<&Limit> {
+        let match_opts = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::default()
+        };
+
+        self.routes
+            .iter()
+            .find(|rule| {
+                rule.pattern.matches_with(path, match_opts)
+                    && rule
+                        .method
+                        .as_ref()
+                        .is_none_or(|m| m.eq_ignore_ascii_case(method))
+            })
+            .map(|rule| &rule.limit)
+    }
+
+    /// Acquire a permit from `limit`, if one applies.
+    ///
+    /// Returns `Ok(None)` when no limit applies and `Err` when the request must be shed.
+    async fn acquire(
+        &self,
+        limit: Option<&Limit>,
+    ) -> Result<Option<OwnedSemaphorePermit>, Saturated> {
+        match limit {
+            Some(limit) => limit
+                .acquire(self.max_queued, self.queue_timeout)
+                .await
+                .map(Some)
+                .ok_or(Saturated),
+            None => Ok(None),
+        }
+    }
+
+    /// Number of free global permits, or `None` when no global limit is configured.
+    #[must_use]
+    pub fn global_available(&self) -> Option<usize> {
+        self.global.as_ref().map(|g| g.available())
+    }
+
+    /// Number of free permits for the route rule matching `path`/`method`, if any.
+    #[must_use]
+    pub fn route_available(&self, path: &str, method: &str) -> Option<usize> {
+        self.route_limit(path, method).map(Limit::available)
+    }
+
+    /// Number of free permits for `subject`, or `None` when no per-subject limit is configured.
+    #[must_use]
+    pub fn subject_available(&self, subject: Uuid) -> Option<usize> {
+        self.subjects
+            .as_ref()
+            .map(|s| s.max_in_flight - s.in_flight(subject))
+    }
+}
+
+fn shed_response() -> Response {
+    let mut resp = Problem::new(
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Service Unavailable",
+        "Server is at capacity, please retry later",
+    )
+    .into_response();
+    resp.headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from_static("1"));
+    resp
+}
+
+fn subject_shed_response() -> Response {
+    let mut resp = Problem::new(
+        StatusCode::TOO_MANY_REQUESTS,
+        "Too Many Requests",
+        "Too many concurrent requests for this client, please retry later",
+    )
+    .into_response();
+    resp.headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from_static("1"));
+    resp
+}
+
+/// Concurrency limit middleware.
+///
+/// Acquires the route permit first (narrower limit) and then the global permit,
+/// so a saturated route does not hold global capacity while it waits. Permits are
+/// owned by this future and are therefore released on completion, cancellation,
+/// or when the handler panics and the future is dropped during unwinding.
+pub async fn concurrency_limit_middleware(
+    axum::extract::State(limiter): axum::extract::State<ConcurrencyLimiter>,
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    let path = common::resolve_path(&req, req.uri().path());
+    let method = req.method().as_str();
+
+    let Ok(_route_permit) = limiter.acquire(limiter.route_limit(&path, method)).await else {
+        tracing::warn!(path = %path, method = %method, "Route concurrency limit reached; shedding request");
+        return shed_response();
+    };
+
+    let Ok(_global_permit) = limiter.acquire(limiter.global.as_deref()).await else {
+        tracing::warn!(path = %path, method = %method, "Global concurrency limit reached; shedding request");
+        return shed_response();
+    };
+
+    next.run(req).await
+}
+
+/// Layer the per-subject fair share onto `router` when `limiter` has a per-subject limit.
+pub fn apply_subject_limit(router: Router, limiter: Option<&ConcurrencyLimiter>) -> Router {
+    match limiter.filter(|l| l.subjects.is_some()) {
+        Some(limiter) => router.layer(axum::middleware::from_fn_with_state(
+            limiter.clone(),
+            subject_concurrency_limit_middleware,
+        )),
+        None => router,
+    }
+}
+
+/// Per-subject fair-share middleware.
+///
+/// Runs inside auth so the `SecurityContext` is known. Requests without one, or
+/// from the anonymous (nil) subject, are not attributable and pass through. The
+/// excess of a subject at its limit is shed with 429 instead of waiting, so it
+/// releases its global permit right away and leaves capacity for other subjects.
+pub async fn subject_concurrency_limit_middleware(
+    axum::extract::State(limiter): axum::extract::State<ConcurrencyLimiter>,
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    let (Some(subjects), Some(subject)) = (
+        limiter.subjects.as_ref(),
+        req.extensions()
+            .get::<SecurityContext>()
+            .map(SecurityContext::subject_id)
+            .filter(|id| !id.is_nil()),
+    ) else {
+        return next.run(req).await;
+    };
+
+    let Some(_subject_permit) = subjects.try_acquire(subject) else {
+        tracing::warn!(subject_id = %subject, "Per-subject concurrency limit reached; shedding request");
+        return subject_shed_response();
+    };
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::config::RouteConcurrencyLimit;
+
+    fn config_with_route(path: &str, method: Option<&str>, max: usize) -> ConcurrencyLimitConfig {
+        ConcurrencyLimitConfig {
+            enabled: true,
+            routes: vec![RouteConcurrencyLimit {
+                path: path.to_owned(),
+                method: method.map(String::from),
+                max_in_flight: max,
+            }],
+            ..ConcurrencyLimitConfig::default()
+        }
+    }
+
+    #[test]
+    fn rejects_zero_route_limit() {
+        let result = ConcurrencyLimiter::from_config(&config_with_route("/a", None, 0));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_glob() {
+        let result = ConcurrencyLimiter::from_config(&config_with_route("/a/[", None, 1));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn global_limit_disabled_when_zero() {
+        let limiter = ConcurrencyLimiter::from_config(&ConcurrencyLimitConfig::default()).unwrap();
+        assert_eq!(limiter.global_available(), None);
+    }
+
+    #[test]
+    fn route_rule_respects_method() {
+        let limiter =
+            ConcurrencyLimiter::from_config(&config_with_route("/reports/**", Some("post"), 3))
+                .unwrap();
+        assert_eq!(limiter.route_available("/reports/v1/x", "POST"), Some(3));
+        assert_eq!(limiter.route_available("/reports/v1/x", "GET"), None);
+        assert_eq!(limiter.route_available("/other", "POST"), None);
+    }
+
+    #[test]
+    fn rejects_per_subject_limit_not_below_global() {
+        let config = ConcurrencyLimitConfig {
+            enabled: true,
+            global_max_in_flight: 8,
+            per_subject_max_in_flight: 8,
+            ..ConcurrencyLimitConfig::default()
+        };
+        assert!(ConcurrencyLimiter::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn subject_permits_are_counted_per_subject() {
+        let config = ConcurrencyLimitConfig {
+            enabled: true,
+            per_subject_max_in_flight: 2,
+            ..ConcurrencyLimitConfig::default()
+        };
+        let limiter = ConcurrencyLimiter::from_config(&config).unwrap();
+        let subjects = limiter.subjects.as_ref().unwrap();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let held = [subjects.try_acquire(a), subjects.try_acquire(a)];
+        assert!(held.iter().all(Option::is_some));
+        assert!(subjects.try_acquire(a).is_none());
+        assert_eq!(limiter.subject_available(b), Some(2));
+        assert!(subjects.try_acquire(b).is_some());
+
+        drop(held);
+        assert_eq!(limiter.subject_available(a), Some(2));
+        assert!(subjects.in_flight.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn queued_request_gets_permit_when_released() {
+        let limit = Limit::new(1);
+        let held = limit.acquire(0, Duration::ZERO).await.unwrap();
+        assert!(limit.acquire(0, Duration::from_millis(10)).await.is_none());
+
+        let release = async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(held);
+        };
+        let (permit, ()) = tokio::join!(limit.acquire(1, Duration::from_secs(5)), release);
+        assert!(permit.is_some());
+        assert_eq!(limit.queued.load(Ordering::Acquire), 0);
+    }
+
+    #[tokio::test]
+    async fn full_queue_sheds_immediately() {
+        let limit = Limit::new(1);
+        let _held = limit.acquire(0, Duration::ZERO).await.unwrap();
+        limit.queued.store(1, Ordering::Release);
+        assert!(limit.acquire(1, Duration::from_secs(5)).await.is_none());
+    }
+}
@@ -0,0 +1,233 @@
+//! Error Format Middleware
+//!
+//! Rewrites error responses (4xx/5xx) into one wire format:
+//! - [`canonical_error_middleware`]: the canonical JSON body defined in
+//!   [`crate::error::ErrorResponse`], tagged with the request id.
+//! - [`problem_error_middleware`]: an RFC 7807 problem document served as
+//!   `application/problem+json`, with `instance` set to the request path.
+//!
+//! Problem documents and canonical bodies are converted field by field, plain
+//! text becomes the message, and empty bodies (from the router, timeout or body
+//! limit layers) are replaced by a body derived from the status code.
+//!
+//! Neither middleware drops a payload it cannot convert: bodies larger than
+//! [`MAX_ERROR_BODY_BYTES`] and JSON they do not recognise are passed through
+//! unchanged.
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::Response;
+use bytes::{Bytes, BytesMut};
+use http_body::{Body as _, Frame};
+use http_body_util::BodyExt;
+
+use crate::config::ErrorFormat;
+use crate::error::{ErrorCode, ErrorResponse};
+use crate::middleware::request_id::XRequestId;
+use modkit::api::{APPLICATION_PROBLEM_JSON, Problem};
+
+/// Upper bound on error bodies we are willing to buffer for conversion.
+const MAX_ERROR_BODY_BYTES: usize = 64 * 1024;
+
+/// Layer the middleware matching `format` onto `router`; `Problem` leaves it untouched.
+pub fn apply_error_format(router: Router, format: ErrorFormat) -> Router {
+    match format {
+        ErrorFormat::Problem => router,
+        ErrorFormat::Json => router.layer(axum::middleware::from_fn(canonical_error_middleware)),
+        ErrorFormat::NormalizedProblem => {
+            router.layer(axum::middleware::from_fn(problem_error_middleware))
+        }
+    }
+}
+
+/// Error format middleware: converts error responses to the canonical JSON body.
+pub async fn canonical_error_middleware(
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    let request_id = req.extensions().get::<XRequestId>().map(|r| r.0.clone());
+
+    let resp = next.run(req).await;
+    rewrite_error_body(resp, "application/json", |status, content_type, bytes| {
+        let mut canonical = if bytes.is_empty() {
+            ErrorResponse::from_status(status)
+        } else {
+            to_canonical(status, content_type, bytes)?
+        };
+        if canonical.request_id.is_none() {
+            canonical.request_id = request_id;
+        }
+        serde_json::to_vec(&canonical).ok()
+    })
+    .await
+}
+
+/// Error format middleware: converts error responses to RFC 7807 problem documents.
+pub async fn problem_error_middleware(
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    let path = req.uri().path().to_owned();
+
+    let resp = next.run(req).await;
+    rewrite_error_body(
+        resp,
+        APPLICATION_PROBLEM_JSON,
+        |status, content_type, bytes| {
+            let mut problem = if bytes.is_empty() {
+                let reason = status.canonical_reason().unwrap_or("Error");
+                Problem::new(status, reason, reason)
+            } else {
+                to_problem(status, content_type, bytes)?
+            };
+            if problem.instance.is_empty() {
+                problem.instance = path;
+            }
+            serde_json::to_vec(&problem).ok()
+        },
+    )
+    .await
+}
+
+/// Replace the body of an error response with the output of `convert`.
+///
+/// `convert` gets the status, the original content type and the buffered body
+/// (empty for bare responses); returning `None` keeps the original body, as
+/// does a body too large to buffer.
+async fn rewrite_error_body(
+    resp: Response,
+    content_type: &'static str,
+    convert: impl FnOnce(StatusCode, Option<&str>, &[u8]) -> Option<Vec<u8>>,
+) -> Response {
+    let status = resp.status();
+    if !status.is_client_error() && !status.is_server_error() {
+        return resp;
+    }
+
+    let (mut parts, body) = resp.into_parts();
+    let bytes = match buffer_error_body(body).await {
+        Buffered::Complete(bytes) => bytes,
+        Buffered::Oversized(body) => return Response::from_parts(parts, body),
+    };
+    let original_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok());
+    let Some(json) = convert(status, original_type, &bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    parts
+        .headers
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(json))
+}
+
+/// Convert a non-empty error body into a problem; `None` means "leave it as it is".
+fn to_problem(status: StatusCode, content_type: Option<&str>, bytes: &[u8]) -> Option<Problem> {
+    let reason = status.canonical_reason().unwrap_or("Error");
+    let content_type = content_type?;
+    if content_type.starts_with(APPLICATION_PROBLEM_JSON) {
+        serde_json::from_slice(bytes).ok()
+    } else if content_type.starts_with("application/json") {
+        let body: ErrorResponse = serde_json::from_slice(bytes).ok()?;
+        let mut problem = Problem::new(status, reason, body.message).with_code(body.code);
+        if let Some(details) = body.details {
+            problem = problem.with_context(details);
+        }
+        Some(problem)
+    } else if content_type.starts_with("text/plain") {
+        let detail = std::str::from_utf8(bytes).ok()?.trim();
+        Some(Problem::new(status, reason, detail))
+    } else {
+        None
+    }
+}
+
+/// Convert a non-empty error body; `None` means "leave it as it is".
+///
+/// Plain text (as emitted by the timeout and body limit layers) becomes the
+/// message; JSON that is neither a problem nor a canonical body is not ours
+/// to rewrite.
+fn to_canonical(
+    status: StatusCode,
+    content_type: Option<&str>,
+    bytes: &[u8],
+) -> Option<ErrorResponse> {
+    let content_type = content_type?;
+    if content_type.starts_with(APPLICATION_PROBLEM_JSON) {
+        let problem: Problem = serde_json::from_slice(bytes).ok()?;
+        Some(ErrorResponse::from_problem(&problem))
+    } else if content_type.starts_with("application/json") {
+        serde_json::from_slice(bytes).ok()
+    } else if content_type.starts_with("text/plain") {
+        let message = std::str::from_utf8(bytes).ok()?.trim();
+        Some(ErrorResponse::new(ErrorCode::from_status(status), message))
+    } else {
+        None
+    }
+}
+
+/// Outcome of [`buffer_error_body`].
+enum Buffered {
+    /// The whole body, at most [`MAX_ERROR_BODY_BYTES`] long.
+    Complete(Bytes),
+    /// The body exceeded the limit; this replays it unchanged.
+    Oversized(Body),
+}
+
+/// Buffer an error body for conversion without losing it when it is too large.
+///
+/// A read error ends the body early; whatever was read so far is kept.
+async fn buffer_error_body(mut body: Body) -> Buffered {
+    if body.size_hint().lower() > MAX_ERROR_BODY_BYTES as u64 {
+        return Buffered::Oversized(body);
+    }
+
+    let mut buffered = BytesMut::new();
+    while let Some(Ok(frame)) = body.frame().await {
+        // Error responses carry no trailers worth keeping.
+        let Ok(data) = frame.into_data() else {
+            continue;
+        };
+        buffered.extend_from_slice(&data);
+        if buffered.len() > MAX_ERROR_BODY_BYTES {
+            return Buffered::Oversized(Body::new(PrefixedBody {
+                prefix: Some(buffered.freeze()),
+                inner: body,
+            }));
+        }
+    }
+    Buffered::Complete(buffered.freeze())
+}
+
+/// Re-emits the bytes already read from `inner`, then the rest of `inner`.
+struct PrefixedBody {
+    prefix: Option<Bytes>,
+    inner: Body,
+}
+
+impl http_body::Body for PrefixedBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        if let Some(prefix) = this.prefix.take() {
+            return Poll::Ready(Some(Ok(Frame::data(prefix))));
+        }
+        Pin::new(&mut this.inner).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.prefix.is_none() && self.inner.is_end_stream()
+    }
+}
@@ -0,0 +1,118 @@
+//! Header Limit Middleware
+//!
+//! Rejects requests whose headers exceed the configured count or total size
+//! with `431 Request Header Fields Too Large`, complementing the body limit.
+
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use modkit::api::Problem;
+
+use crate::config::Defaults;
+
+/// Configured header limits; `0` disables the respective check.
+#[derive(Debug, Clone, Copy)]
+pub struct HeaderLimits {
+    pub max_count: usize,
+    pub max_bytes: usize,
+}
+
+impl HeaderLimits {
+    #[must_use]
+    pub fn from_defaults(defaults: &Defaults) -> Self {
+        Self {
+            max_count: defaults.header_limit_count,
+            max_bytes: defaults.header_limit_bytes,
+        }
+    }
+
+    /// Describe the violated limit, if any.
+    fn check(&self, headers: &axum::http::HeaderMap) -> Option<String> {
+        let count = headers.len();
+        if self.max_count > 0 && count > self.max_count {
+            return Some(format!(
+                "Request has {count} headers; at most {} are allowed",
+                self.max_count
+            ));
+        }
+
+        if self.max_bytes > 0 {
+            let bytes: usize = headers
+                .iter()
+                .map(|(name, value)| name.as_str().len() + value.len())
+                .sum();
+            if bytes > self.max_bytes {
+                return Some(format!(
+                    "Request headers total {bytes} bytes; at most {} are allowed",
+                    self.max_bytes
+                ));
+            }
+        }
+
+        None
+    }
+}
+
+pub async fn header_limit_middleware(
+    State(limits): State<HeaderLimits>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if let Some(detail) = limits.check(req.headers()) {
+        return Problem::new(
+            StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+            "Request Header Fields Too Large",
+            detail,
+        )
+        .into_response();
+    }
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use axum::http::{HeaderMap, HeaderName, HeaderValue};
+
+    fn headers(count: usize, value_len: usize) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for i in 0..count {
+            map.insert(
+                HeaderName::try_from(format!("x-h{i}")).unwrap(),
+                HeaderValue::from_str(&"v".repeat(value_len)).unwrap(),
+            );
+        }
+        map
+    }
+
+    #[test]
+    fn within_limits_passes() {
+        let limits = HeaderLimits {
+            max_count: 5,
+            max_bytes: 100,
+        };
+        assert!(limits.check(&headers(5, 2)).is_none());
+    }
+
+    #[test]
+    fn too_many_headers_rejected() {
+        let limits = HeaderLimits {
+            max_count: 5,
+            max_bytes: 0,
+        };
+        assert!(limits.check(&headers(6, 1)).is_some());
+    }
+
+    #[test]
+    fn too_many_bytes_rejected() {
+        let limits = HeaderLimits {
+            max_count: 0,
+            max_bytes: 10,
+        };
+        // "x-h0" + 7 bytes of value = 11 bytes
+        assert!(limits.check(&headers(1, 7)).is_some());
+    }
+}
@@ -0,0 +1,274 @@
+//! Host Validation Middleware
+//!
+//! Rejects requests whose `Host` (or `:authority`) or `X-Forwarded-Host` names
+//! a host outside the configured allow-list with `400 Bad Request`, so a
+//! spoofed host never ends up in absolute URLs built from the request.
+
+use std::sync::Arc;
+
+use axum::Router;
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, StatusCode, Uri, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use modkit::api::Problem;
+
+const X_FORWARDED_HOST: &str = "x-forwarded-host";
+
+/// Probe endpoints served whatever the `Host`, so orchestrators that probe by
+/// pod IP keep working.
+const HEALTH_PATHS: [&str; 2] = ["/health", "/healthz"];
+
+/// A single allowed host: an exact name or a `*.` wildcard over subdomains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HostPattern {
+    Exact(String),
+    /// `*.example.com`, stored as `.example.com`; matches any subdomain but
+    /// not `example.com` itself.
+    Subdomains(String),
+}
+
+impl HostPattern {
+    fn parse(pattern: &str) -> Result<Self, anyhow::Error> {
+        let pattern = pattern.trim().to_ascii_lowercase();
+        let (wildcard, name) = match pattern.strip_prefix("*.") {
+            Some(name) => (true, name),
+            None => (false, pattern.as_str()),
+        };
+        if name.is_empty() || name.contains(['*', ':', '/']) {
+            return Err(anyhow::anyhow!(
+                "Invalid allowed host '{pattern}': expected a host name, optionally prefixed with '*.'"
+            ));
+        }
+        Ok(if wildcard {
+            Self::Subdomains(format!(".{name}"))
+        } else {
+            Self::Exact(name.to_owned())
+        })
+    }
+
+    fn matches(&self, host: &str) -> bool {
+        match self {
+            Self::Exact(name) => host == name,
+            Self::Subdomains(suffix) => host.len() > suffix.len() && host.ends_with(suffix),
+        }
+    }
+}
+
+/// Allow-list of host names.
+#[derive(Debug, Clone, Default)]
+pub struct AllowedHosts {
+    patterns: Arc<[HostPattern]>,
+}
+
+impl AllowedHosts {
+    /// Build the allow-list from `allowed_hosts` config entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry is not a host name or `*.` wildcard.
+    pub fn from_config(hosts: &[String]) -> Result<Self, anyhow::Error> {
+        let patterns = hosts
+            .iter()
+            .map(|host| HostPattern::parse(host))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            patterns: patterns.into(),
+        })
+    }
+
+    fn allows(&self, host: &str) -> bool {
+        let host = normalize(host);
+        self.patterns.iter().any(|p| p.matches(&host))
+    }
+
+    /// Describe the rejected host, if any.
+    fn check(&self, uri: &Uri, headers: &HeaderMap) -> Option<String> {
+        let host = headers
+            .get(header::HOST)
+            .map(|value| value.to_str().unwrap_or_default())
+            .or_else(|| uri.host());
+        match host {
+            None => return Some("Request has no Host header".to_owned()),
+            Some(host) if !self.allows(host) => {
+                return Some(format!("Host '{host}' is not allowed"));
+            }
+            Some(_) => {}
+        }
+
+        // Every hop a proxy appended must be allowed too
+        for value in headers.get_all(X_FORWARDED_HOST) {
+            let Ok(value) = value.to_str() else {
+                return Some("X-Forwarded-Host is not valid ASCII".to_owned());
+            };
+            if let Some(host) = value.split(',').find(|host| !self.allows(host)) {
+                return Some(format!("Forwarded host '{}' is not allowed", host.trim()));
+            }
+        }
+
+        None
+    }
+}
+
+/// Lowercase `host` and strip its port, keeping bracketed IPv6 literals intact.
+fn normalize(host: &str) -> String {
+    let host = host.trim();
+    let without_port = match host.rsplit_once(':') {
+        Some((name, port)) if !name.is_empty() && port.bytes().all(|b| b.is_ascii_digit()) => name,
+        _ => host,
+    };
+    without_port.trim_end_matches('.').to_ascii_lowercase()
+}
+
+pub async fn host_validation_middleware(
+    State(allowed): State<AllowedHosts>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if HEALTH_PATHS.contains(&req.uri().path()) {
+        return next.run(req).await;
+    }
+    if let Some(detail) = allowed.check(req.uri(), req.headers()) {
+        return Problem::new(StatusCode::BAD_REQUEST, "Invalid Host", detail).into_response();
+    }
+
+    next.run(req).await
+}
+
+/// Wrap `router` with the allow-list check unless `allowed_hosts` is empty.
+///
+/// `/health` and `/healthz` are never checked.
+///
+/// # Errors
+///
+/// Returns an error if an entry is not a host name or `*.` wildcard.
+pub fn apply_host_validation(
+    router: Router,
+    allowed_hosts: &[String],
+) -> Result<Router, anyhow::Error> {
+    if allowed_hosts.is_empty() {
+        return Ok(router);
+    }
+    let allowed = AllowedHosts::from_config(allowed_hosts)?;
+    Ok(router.layer(axum::middleware::from_fn_with_state(
+        allowed,
+        host_validation_middleware,
+    )))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn allowed(hosts: &[&str]) -> AllowedHosts {
+        AllowedHosts::from_config(&hosts.iter().map(|h| (*h).to_owned()).collect::<Vec<_>>())
+            .unwrap()
+    }
+
+    fn headers(host: &str, forwarded: Option<&str>) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        map.insert(header::HOST, HeaderValue::from_str(host).unwrap());
+        if let Some(forwarded) = forwarded {
+            map.insert(X_FORWARDED_HOST, HeaderValue::from_str(forwarded).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn exact_host_ignores_case_port_and_trailing_dot() {
+        let hosts = allowed(&["api.example.com"]);
+        let uri = Uri::from_static("/");
+        for host in [
+            "api.example.com",
+            "API.Example.com:8443",
+            "api.example.com.",
+        ] {
+            assert!(hosts.check(&uri, &headers(host, None)).is_none(), "{host}");
+        }
+        assert!(hosts.check(&uri, &headers("evil.com", None)).is_some());
+    }
+
+    #[test]
+    fn wildcard_matches_subdomains_only() {
+        let hosts = allowed(&["*.example.com"]);
+        assert!(hosts.allows("a.example.com"));
+        assert!(hosts.allows("a.b.example.com:80"));
+        assert!(!hosts.allows("example.com"));
+        assert!(!hosts.allows("badexample.com"));
+    }
+
+    #[test]
+    fn every_forwarded_host_must_be_allowed() {
+        let hosts = allowed(&["api.example.com", "*.internal"]);
+        let uri = Uri::from_static("/");
+        assert!(
+            hosts
+                .check(
+                    &uri,
+                    &headers("gw.internal", Some("api.example.com, lb.internal"))
+                )
+                .is_none()
+        );
+        assert!(
+            hosts
+                .check(
+                    &uri,
+                    &headers("gw.internal", Some("api.example.com, evil.com"))
+                )
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn authority_is_used_without_host_header() {
+        let hosts = allowed(&["api.example.com"]);
+        let uri = Uri::from_static("https://api.example.com/ping");
+        assert!(hosts.check(&uri, &HeaderMap::new()).is_none());
+        assert!(
+            hosts
+                .check(&Uri::from_static("/ping"), &HeaderMap::new())
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn health_probes_skip_the_allow_list() {
+        use axum::body::Body;
+        use axum::routing::get;
+        use tower::ServiceExt;
+
+        let router = Router::new()
+            .route("/health", get(|| async { "ok" }))
+            .route("/healthz", get(|| async { "ok" }))
+            .route("/ping", get(|| async { "pong" }));
+        let router = apply_host_validation(router, &["api.example.com".to_owned()]).unwrap();
+
+        let status = |path: &'static str| {
+            let router = router.clone();
+            async move {
+                let req = Request::builder()
+                    .uri(path)
+                    .header(header::HOST, "10.0.0.7:8080")
+                    .body(Body::empty())
+                    .unwrap();
+                router.oneshot(req).await.unwrap().status()
+            }
+        };
+
+        assert_eq!(status("/health").await, StatusCode::OK);
+        assert_eq!(status("/healthz").await, StatusCode::OK);
+        assert_eq!(status("/ping").await, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn invalid_patterns_are_rejected() {
+        for pattern in ["", "*", "api.*.com", "api.example.com:8080", "http://x"] {
+            assert!(
+                AllowedHosts::from_config(&[pattern.to_owned()]).is_err(),
+                "{pattern}"
+            );
+        }
+    }
+}
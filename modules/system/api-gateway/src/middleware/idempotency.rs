@@ -0,0 +1,386 @@
+//! Idempotency-Key Middleware
+//!
+//! Makes retried `POST` requests safe: the first response for an
+//! `Idempotency-Key` is stored per (subject, path, key) for a TTL and replayed
+//! for duplicates instead of invoking the handler again.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use bytes::Bytes;
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use http_body::Body as _;
+use modkit::api::Problem;
+use modkit_security::SecurityContext;
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use crate::config::IdempotencyConfig;
+
+/// Request header carrying the client-chosen idempotency key.
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Response header set on replayed responses.
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// Maximum accepted length of an idempotency key.
+const MAX_KEY_LEN: usize = 255;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct StoreKey {
+    subject: Uuid,
+    path: String,
+    key: String,
+}
+
+#[derive(Debug, Clone)]
+struct StoredResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl StoredResponse {
+    fn replay(self) -> Response {
+        let mut response = Response::new(Body::from(self.body));
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers;
+        response
+            .headers_mut()
+            .insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+        response
+    }
+}
+
+#[derive(Debug)]
+enum Slot {
+    /// The first request for the key is still being handled.
+    InFlight,
+    Completed {
+        response: StoredResponse,
+        expires_at: Instant,
+    },
+}
+
+/// What to do with a request carrying an idempotency key.
+enum Claim {
+    /// The key is now held by this request; run the handler.
+    Acquired,
+    /// Replay the stored response.
+    Replay(StoredResponse),
+    /// Another request with the same key is still running.
+    InProgress,
+    /// The store is full; run the handler without storing the response.
+    Bypass,
+}
+
+/// Completed keys paired with their expiry, oldest first.
+type ExpiryQueue = VecDeque<(Instant, StoreKey)>;
+
+/// Stored responses shared by all requests.
+#[derive(Clone, Debug)]
+pub struct IdempotencyStore {
+    slots: Arc<DashMap<StoreKey, Slot>>,
+    /// Completed keys in expiry order. Every response is kept for the same
+    /// TTL, so appending on completion keeps the queue sorted and expired
+    /// responses are evicted from the front without scanning the store.
+    expiries: Arc<Mutex<ExpiryQueue>>,
+    ttl: Duration,
+    max_entries: usize,
+    max_response_bytes: usize,
+}
+
+impl IdempotencyStore {
+    #[must_use]
+    pub fn from_config(config: &IdempotencyConfig) -> Self {
+        Self {
+            slots: Arc::new(DashMap::new()),
+            expiries: Arc::new(Mutex::new(VecDeque::new())),
+            ttl: Duration::from_secs(config.ttl_secs),
+            max_entries: config.max_entries,
+            max_response_bytes: config.max_response_bytes,
+        }
+    }
+
+    fn claim(&self, key: &StoreKey) -> Claim {
+        let now = Instant::now();
+        self.evict_expired(now);
+        let full = self.slots.len() >= self.max_entries;
+
+        match self.slots.entry(key.clone()) {
+            Entry::Occupied(mut occupied) => match occupied.get() {
+                Slot::InFlight => Claim::InProgress,
+                Slot::Completed { expires_at, .. } if *expires_at <= now => {
+                    occupied.insert(Slot::InFlight);
+                    Claim::Acquired
+                }
+                Slot::Completed { response, .. } => Claim::Replay(response.clone()),
+            },
+            Entry::Vacant(_) if full => Claim::Bypass,
+            Entry::Vacant(vacant) => {
+                vacant.insert(Slot::InFlight);
+                Claim::Acquired
+            }
+        }
+    }
+
+    /// Store `response` for `key` until the TTL runs out.
+    fn complete(&self, key: StoreKey, response: StoredResponse) {
+        let mut expiries = self.expiries.lock();
+        let expires_at = Instant::now() + self.ttl;
+        self.slots.insert(
+            key.clone(),
+            Slot::Completed {
+                response,
+                expires_at,
+            },
+        );
+        expiries.push_back((expires_at, key));
+    }
+
+    /// Drop the responses that expired by `now`, oldest first.
+    ///
+    /// A key re-stored after expiring keeps a stale queue entry; its slot
+    /// then carries a later expiry and survives.
+    fn evict_expired(&self, now: Instant) {
+        let mut expiries = self.expiries.lock();
+        while let Some((expires_at, _)) = expiries.front()
+            && *expires_at <= now
+        {
+            let Some((_, key)) = expiries.pop_front() else {
+                break;
+            };
+            self.slots.remove_if(
+                &key,
+                |_, slot| matches!(slot, Slot::Completed { expires_at, .. } if *expires_at <= now),
+            );
+        }
+    }
+}
+
+/// Releases a claimed key unless a response was stored for it, so that a
+/// failed, oversized or cancelled request can be retried with the same key.
+struct ClaimGuard<'a> {
+    store: &'a IdempotencyStore,
+    key: Option<StoreKey>,
+}
+
+impl ClaimGuard<'_> {
+    fn complete(mut self, response: StoredResponse) {
+        if let Some(key) = self.key.take() {
+            self.store.complete(key, response);
+        }
+    }
+}
+
+impl Drop for ClaimGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.store
+                .slots
+                .remove_if(&key, |_, slot| matches!(slot, Slot::InFlight));
+        }
+    }
+}
+
+fn problem(status: StatusCode, title: &str, detail: &str) -> Response {
+    Problem::new(status, title, detail).into_response()
+}
+
+/// Replays the stored response for a repeated `POST` with the same `Idempotency-Key`.
+///
+/// Must run inside auth: keys are scoped to the authenticated subject, and
+/// anonymous requests are passed through untouched.
+pub async fn idempotency_middleware(
+    State(store): State<IdempotencyStore>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if req.method() != Method::POST {
+        return next.run(req).await;
+    }
+    let Some(key) = req.headers().get(&IDEMPOTENCY_KEY) else {
+        return next.run(req).await;
+    };
+    let key = match key.to_str() {
+        Ok(k) if !k.is_empty() && k.len() <= MAX_KEY_LEN => k.to_owned(),
+        _ => {
+            return problem(
+                StatusCode::BAD_REQUEST,
+                "Invalid Idempotency-Key",
+                &format!("Idempotency-Key must be 1 to {MAX_KEY_LEN} visible ASCII characters"),
+            );
+        }
+    };
+
+    let subject = req
+        .extensions()
+        .get::<SecurityContext>()
+        .map_or_else(Uuid::nil, SecurityContext::subject_id);
+    if subject.is_nil() {
+        return next.run(req).await;
+    }
+
+    let key = StoreKey {
+        subject,
+        path: req.uri().path().to_owned(),
+        key,
+    };
+
+    match store.claim(&key) {
+        Claim::Acquired => {}
+        Claim::Replay(response) => return response.replay(),
+        Claim::InProgress => {
+            return problem(
+                StatusCode::CONFLICT,
+                "Conflict",
+                "A request with this Idempotency-Key is still being processed",
+            );
+        }
+        Claim::Bypass => return next.run(req).await,
+    }
+
+    let guard = ClaimGuard {
+        store: &store,
+        key: Some(key),
+    };
+
+    let response = next.run(req).await;
+
+    // Server errors are not stored so that the client can retry
+    if response.status().is_server_error() {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let fits = body
+        .size_hint()
+        .upper()
+        .is_some_and(|n| n <= store.max_response_bytes as u64);
+    if !fits {
+        return Response::from_parts(parts, body);
+    }
+
+    let bytes = match axum::body::to_bytes(body, store.max_response_bytes).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to buffer response for Idempotency-Key");
+            return problem(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal Server Error",
+                "Failed to read the response body",
+            );
+        }
+    };
+
+    let mut headers = parts.headers.clone();
+    headers.remove(crate::middleware::request_id::header());
+    guard.complete(StoredResponse {
+        status: parts.status,
+        headers,
+        body: bytes.clone(),
+    });
+
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn store(max_entries: usize) -> IdempotencyStore {
+        IdempotencyStore::from_config(&IdempotencyConfig {
+            enabled: true,
+            max_entries,
+            ..IdempotencyConfig::default()
+        })
+    }
+
+    fn key(k: &str) -> StoreKey {
+        StoreKey {
+            subject: Uuid::from_u128(1),
+            path: "/orders".to_owned(),
+            key: k.to_owned(),
+        }
+    }
+
+    fn stored() -> StoredResponse {
+        StoredResponse {
+            status: StatusCode::CREATED,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(b"{}"),
+        }
+    }
+
+    #[test]
+    fn duplicate_while_in_flight_is_in_progress() {
+        let store = store(10);
+        assert!(matches!(store.claim(&key("a")), Claim::Acquired));
+        assert!(matches!(store.claim(&key("a")), Claim::InProgress));
+    }
+
+    #[test]
+    fn dropped_claim_releases_key() {
+        let store = store(10);
+        assert!(matches!(store.claim(&key("a")), Claim::Acquired));
+        drop(ClaimGuard {
+            store: &store,
+            key: Some(key("a")),
+        });
+        assert!(matches!(store.claim(&key("a")), Claim::Acquired));
+    }
+
+    #[test]
+    fn completed_claim_replays_until_expiry() {
+        let mut store = store(10);
+        assert!(matches!(store.claim(&key("a")), Claim::Acquired));
+        ClaimGuard {
+            store: &store,
+            key: Some(key("a")),
+        }
+        .complete(stored());
+        assert!(
+            matches!(store.claim(&key("a")), Claim::Replay(r) if r.status == StatusCode::CREATED)
+        );
+
+        store.ttl = Duration::ZERO;
+        assert!(matches!(store.claim(&key("b")), Claim::Acquired));
+        ClaimGuard {
+            store: &store,
+            key: Some(key("b")),
+        }
+        .complete(stored());
+        assert!(matches!(store.claim(&key("b")), Claim::Acquired));
+    }
+
+    #[test]
+    fn expired_responses_free_room_in_a_full_store() {
+        let mut store = store(1);
+        store.ttl = Duration::ZERO;
+        assert!(matches!(store.claim(&key("a")), Claim::Acquired));
+        ClaimGuard {
+            store: &store,
+            key: Some(key("a")),
+        }
+        .complete(stored());
+
+        assert!(matches!(store.claim(&key("b")), Claim::Acquired));
+        assert!(!store.slots.contains_key(&key("a")));
+        assert!(store.expiries.lock().is_empty());
+    }
+
+    #[test]
+    fn full_store_bypasses_new_keys() {
+        let store = store(1);
+        assert!(matches!(store.claim(&key("a")), Claim::Acquired));
+        assert!(matches!(store.claim(&key("b")), Claim::Bypass));
+    }
+}
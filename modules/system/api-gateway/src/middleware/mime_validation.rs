@@ -163,6 +163,7 @@ mod tests {
             handler_id: "test".to_owned(),
             authenticated: false,
             is_public: false,
+            optional_auth: false,
             license_requirement: None,
             rate_limit: None,
             allowed_request_content_types: Some(vec!["multipart/form-data", "application/pdf"]),
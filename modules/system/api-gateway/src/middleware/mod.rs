@@ -1,9 +1,16 @@
 pub mod access_log;
 pub mod auth;
+pub mod body_logging;
 pub mod common;
+pub mod concurrency_limit;
+pub mod error_format;
+pub mod header_limit;
+pub mod host_validation;
 pub mod http_metrics;
+pub mod idempotency;
 pub mod license_validation;
 pub mod mime_validation;
 pub mod rate_limit;
 pub mod request_id;
+pub mod route_limits;
 pub mod scope_enforcement;
@@ -0,0 +1,213 @@
+//! Per-Route Limits Middleware
+//!
+//! Applies the request body limit and timeout declared on an operation through
+//! the `x-max-body-size` and `x-timeout` extensions, falling back to the
+//! gateway defaults for every other route.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::{Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http_body_util::Limited;
+use modkit::api::{OperationSpec, Problem};
+
+use crate::config::Defaults;
+use crate::middleware::common;
+
+/// Timeout for routes that do not declare `x-timeout`.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Limits declared by a single operation.
+#[derive(Debug, Clone, Copy, Default)]
+struct RouteOverride {
+    body_bytes: Option<usize>,
+    timeout: Option<Duration>,
+}
+
+/// Body limit and timeout for every route, keyed by (method, path).
+#[derive(Debug, Clone)]
+pub struct RouteLimits {
+    default_body_bytes: usize,
+    default_timeout: Duration,
+    routes: Arc<HashMap<(Method, String), RouteOverride>>,
+}
+
+impl RouteLimits {
+    #[must_use]
+    pub fn from_specs(specs: &[OperationSpec], defaults: &Defaults) -> Self {
+        let routes = specs
+            .iter()
+            .filter_map(|spec| {
+                let ext = &spec.vendor_extensions;
+                let limits = RouteOverride {
+                    body_bytes: ext.x_max_body_size,
+                    timeout: ext.x_timeout.map(Duration::from_millis),
+                };
+                (limits.body_bytes.is_some() || limits.timeout.is_some())
+                    .then(|| ((spec.method.clone(), spec.path.clone()), limits))
+            })
+            .collect();
+
+        Self {
+            default_body_bytes: defaults.body_limit_bytes,
+            default_timeout: DEFAULT_TIMEOUT,
+            routes: Arc::new(routes),
+        }
+    }
+
+    /// Largest body limit of any route, used to lift axum's extractor limit
+    /// so that it never undercuts a route's own limit.
+    #[must_use]
+    pub fn max_body_bytes(&self) -> usize {
+        self.routes
+            .values()
+            .filter_map(|r| r.body_bytes)
+            .fold(self.default_body_bytes, usize::max)
+    }
+
+    fn route(&self, req: &Request) -> RouteOverride {
+        let Some(matched) = req.extensions().get::<MatchedPath>() else {
+            return RouteOverride::default();
+        };
+        let path = common::resolve_path(req, matched.as_str());
+        self.routes
+            .get(&(req.method().clone(), path))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn body_limit(&self, req: &Request) -> usize {
+        self.route(req)
+            .body_bytes
+            .unwrap_or(self.default_body_bytes)
+    }
+
+    fn timeout(&self, req: &Request) -> Duration {
+        self.route(req).timeout.unwrap_or(self.default_timeout)
+    }
+}
+
+/// Rejects bodies over the route's limit with `413 Payload Too Large`.
+///
+/// A declared `Content-Length` is checked up front; other bodies are capped
+/// while they are read, so handlers see a length-limit error instead.
+pub async fn route_body_limit_middleware(
+    State(limits): State<RouteLimits>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let limit = limits.body_limit(&req);
+
+    let declared = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    if declared.is_some_and(|len| len > limit as u64) {
+        return Problem::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "Payload Too Large",
+            format!("Request body exceeds the {limit} byte limit for this endpoint"),
+        )
+        .into_response();
+    }
+
+    let req = req.map(|body| Body::new(Limited::new(body, limit)));
+    next.run(req).await
+}
+
+/// Answers `504 Gateway Timeout` when the route's timeout elapses.
+pub async fn route_timeout_middleware(
+    State(limits): State<RouteLimits>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let timeout = limits.timeout(&req);
+    tokio::time::timeout(timeout, next.run(req))
+        .await
+        .unwrap_or_else(|_| StatusCode::GATEWAY_TIMEOUT.into_response())
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use modkit::api::operation_builder::VendorExtensions;
+
+    fn spec(path: &str, ext: VendorExtensions) -> OperationSpec {
+        OperationSpec {
+            method: Method::POST,
+            path: path.to_owned(),
+            operation_id: None,
+            summary: None,
+            description: None,
+            tags: vec![],
+            params: vec![],
+            request_body: None,
+            responses: vec![],
+            handler_id: path.to_owned(),
+            authenticated: false,
+            is_public: true,
+            optional_auth: false,
+            rate_limit: None,
+            allowed_request_content_types: None,
+            vendor_extensions: ext,
+            license_requirement: None,
+        }
+    }
+
+    #[test]
+    fn only_annotated_routes_get_overrides() {
+        let specs = vec![
+            spec(
+                "/small",
+                VendorExtensions {
+                    x_max_body_size: Some(1024),
+                    ..VendorExtensions::default()
+                },
+            ),
+            spec(
+                "/slow",
+                VendorExtensions {
+                    x_timeout: Some(90_000),
+                    ..VendorExtensions::default()
+                },
+            ),
+            spec("/plain", VendorExtensions::default()),
+        ];
+
+        let limits = RouteLimits::from_specs(&specs, &Defaults::default());
+
+        assert_eq!(limits.routes.len(), 2);
+        let slow = limits.routes[&(Method::POST, "/slow".to_owned())];
+        assert_eq!(slow.timeout, Some(Duration::from_secs(90)));
+        assert_eq!(slow.body_bytes, None);
+    }
+
+    #[test]
+    fn max_body_bytes_covers_default_and_overrides() {
+        let defaults = Defaults::default();
+        let larger = defaults.body_limit_bytes * 2;
+        let specs = vec![spec(
+            "/upload",
+            VendorExtensions {
+                x_max_body_size: Some(larger),
+                ..VendorExtensions::default()
+            },
+        )];
+
+        assert_eq!(
+            RouteLimits::from_specs(&[], &defaults).max_body_bytes(),
+            defaults.body_limit_bytes
+        );
+        assert_eq!(
+            RouteLimits::from_specs(&specs, &defaults).max_body_bytes(),
+            larger
+        );
+    }
+}
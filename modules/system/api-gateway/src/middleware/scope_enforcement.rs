@@ -6,13 +6,15 @@
 //! See `docs/arch/authorization/DESIGN.md` section "Gateway Scope Enforcement" for details.
 
 use std::sync::Arc;
+use std::time::Duration;
 
-use axum::response::IntoResponse;
 use glob::{MatchOptions, Pattern};
 
 use crate::config::RoutePoliciesConfig;
+use crate::error::{AuthErrorMapper, auth_error_response};
 use crate::middleware::common;
 use modkit::api::Problem;
+use modkit_auth::{AuthDecision, AuthError, AuthMetrics, AuthOutcome};
 use modkit_security::SecurityContext;
 
 /// Compiled scope enforcement rules for efficient runtime matching.
@@ -85,20 +87,20 @@ impl ScopeEnforcementRules {
         })
     }
 
-    /// Check if the given path and method match any protected route.
-    ///
-    /// Returns `true` if the path/method matches at least one scope enforcement rule.
-    fn matches_protected_route(&self, path: &str, method: &str) -> bool {
+    /// Find the first rule matching the given path and method.
+    fn find_rule(&self, path: &str, method: &str) -> Option<&CompiledRule> {
         if !self.enabled {
-            return false;
+            return None;
         }
 
+        // Match options: require `/` to be matched literally so `*` doesn't cross path segments
         let match_opts = MatchOptions {
             require_literal_separator: true,
             ..MatchOptions::default()
         };
 
-        self.rules.iter().any(|rule| {
+        // First match wins: more specific rules override broader ones when declared first.
+        self.rules.iter().find(|rule| {
             let path_matches = rule.pattern.matches_with(path, match_opts);
             let method_matches = rule
                 .method
@@ -108,6 +110,21 @@ impl ScopeEnforcementRules {
         })
     }
 
+    /// Check if the given path and method match any protected route.
+    ///
+    /// Returns `true` if the path/method matches at least one scope enforcement rule.
+    fn matches_protected_route(&self, path: &str, method: &str) -> bool {
+        self.find_rule(path, method).is_some()
+    }
+
+    /// Scopes of the first rule matching the given path and method; a token
+    /// needs any one of them.
+    #[must_use]
+    pub fn required_scopes(&self, path: &str, method: &str) -> Option<&[String]> {
+        self.find_rule(path, method)
+            .map(|rule| rule.required_scopes.as_slice())
+    }
+
     /// Check if the given path, method, and token scopes satisfy the scope requirements.
     ///
     /// Returns `Ok(())` if access is allowed, or `Err(problem)` if denied.
@@ -123,52 +140,37 @@ impl ScopeEnforcementRules {
             return Ok(());
         }
 
-        // Match options: require `/` to be matched literally so `*` doesn't cross path segments
-        let match_opts = MatchOptions {
-            require_literal_separator: true,
-            ..MatchOptions::default()
+        // First match wins: only the first matching rule's scopes are checked.
+        let Some(rule) = self.find_rule(path, method) else {
+            // No rule matched — allow (unprotected route)
+            return Ok(());
         };
 
-        // First match wins: find the first matching rule and check scopes against it only.
-        // This allows more specific rules to override broader ones when declared first.
-        for rule in self.rules.iter() {
-            let path_matches = rule.pattern.matches_with(path, match_opts);
-            let method_matches = rule
-                .method
-                .as_ref()
-                .is_none_or(|m| m.eq_ignore_ascii_case(method));
+        // Check if token has ANY of the required scopes
+        let has_required_scope = rule
+            .required_scopes
+            .iter()
+            .any(|required| token_scopes.contains(required));
 
-            if path_matches && method_matches {
-                // Check if token has ANY of the required scopes
-                let has_required_scope = rule
-                    .required_scopes
-                    .iter()
-                    .any(|required| token_scopes.contains(required));
-
-                if has_required_scope {
-                    return Ok(());
-                }
-
-                tracing::warn!(
-                    path = %path,
-                    method = %method,
-                    pattern = %rule.pattern,
-                    rule_method = ?rule.method,
-                    required_scopes = ?rule.required_scopes,
-                    token_scopes = ?token_scopes,
-                    "Route policy enforcement denied: insufficient scopes"
-                );
-
-                return Err(Problem::new(
-                    axum::http::StatusCode::FORBIDDEN,
-                    "Forbidden",
-                    "Insufficient token scopes for this resource",
-                ));
-            }
+        if has_required_scope {
+            return Ok(());
         }
 
-        // No rule matched — allow (unprotected route)
-        Ok(())
+        tracing::warn!(
+            path = %path,
+            method = %method,
+            pattern = %rule.pattern,
+            rule_method = ?rule.method,
+            required_scopes = ?rule.required_scopes,
+            token_scopes = ?token_scopes,
+            "Route policy enforcement denied: insufficient scopes"
+        );
+
+        Err(Problem::new(
+            axum::http::StatusCode::FORBIDDEN,
+            "Forbidden",
+            "Insufficient token scopes for this resource",
+        ))
     }
 }
 
@@ -176,12 +178,17 @@ impl ScopeEnforcementRules {
 #[derive(Clone)]
 pub struct ScopeEnforcementState {
     pub rules: ScopeEnforcementRules,
+    /// Status and code for scope rejections.
+    pub error_mapper: AuthErrorMapper,
+    /// Receives an [`AuthDecision`] for every scope rejection.
+    pub metrics: Arc<dyn AuthMetrics>,
 }
 
 /// Scope enforcement middleware.
 ///
 /// Checks if the request's token scopes satisfy the configured requirements
-/// for the matched route pattern. Returns 403 Forbidden if scopes are insufficient.
+/// for the matched route pattern. Returns 403 Forbidden if scopes are insufficient,
+/// unless the state's error mapper says otherwise.
 ///
 /// This middleware MUST run AFTER the auth middleware (which populates `SecurityContext`).
 pub async fn scope_enforcement_middleware(
@@ -211,12 +218,11 @@ pub async fn scope_enforcement_middleware(
                 method = %method,
                 "Route policy enforcement denied: no SecurityContext for protected route"
             );
-            return Problem::new(
-                axum::http::StatusCode::UNAUTHORIZED,
-                "Unauthorized",
+            return auth_error_response(
+                &state.error_mapper,
+                &AuthError::Unauthenticated,
                 "Authentication required for this resource",
-            )
-            .into_response();
+            );
         }
         return next.run(req).await;
     };
@@ -226,7 +232,11 @@ pub async fn scope_enforcement_middleware(
         .rules
         .check(&path, method, security_context.token_scopes())
     {
-        return problem.into_response();
+        state.metrics.record_decision(
+            &AuthDecision::new(AuthOutcome::Forbidden, Duration::ZERO)
+                .with_subject(security_context.subject_id().to_string()),
+        );
+        return auth_error_response(&state.error_mapper, &AuthError::Forbidden, &problem.detail);
     }
 
     next.run(req).await
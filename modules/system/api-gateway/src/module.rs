@@ -16,24 +16,26 @@ use modkit::api::{OpenApiRegistry, OpenApiRegistryImpl};
 use modkit::lifecycle::ReadySignal;
 use parking_lot::Mutex;
 use std::net::SocketAddr;
-use std::time::Duration;
 use tokio_util::sync::CancellationToken;
+use tower::ServiceExt;
 use tower_http::{
     catch_panic::CatchPanicLayer,
-    limit::RequestBodyLimitLayer,
     request_id::{PropagateRequestIdLayer, SetRequestIdLayer},
-    timeout::TimeoutLayer,
 };
 use tracing::debug;
 
 use authn_resolver_sdk::AuthNResolverClient;
 
 use crate::config::ApiGatewayConfig;
+use crate::error::{AuthErrorMapper, default_auth_error_mapper};
 use crate::middleware::auth;
+use crate::middleware::concurrency_limit as concurrency;
+use modkit_auth::{AuthMetrics, LoggingMetrics};
 use modkit_security::SecurityContext;
 use modkit_security::constants::{DEFAULT_SUBJECT_ID, DEFAULT_TENANT_ID};
 
 use crate::middleware;
+use crate::openapi_security;
 use crate::router_cache::RouterCache;
 use crate::web;
 
@@ -52,16 +54,28 @@ pub struct ApiGateway {
     pub(crate) openapi_registry: Arc<OpenApiRegistryImpl>,
     // Built router cache for zero-lock hot path access
     pub(crate) router_cache: RouterCache<axum::Router>,
-    // Store the finalized router from REST phase for serving
-    pub(crate) final_router: Mutex<Option<axum::Router>>,
+    // Module routes handed over in the REST phase; the base of every rebuilt router
+    pub(crate) rest_routes: Mutex<Option<axum::Router>>,
+    // Routes added after startup (e.g. by plugins); merged into every rebuilt router
+    pub(crate) runtime_routes: Mutex<Router>,
     // AuthN Resolver client (resolved during init, None when auth_disabled)
     pub(crate) authn_client: Mutex<Option<Arc<dyn AuthNResolverClient>>>,
+    // Status/code mapping for auth rejections raised by the gateway
+    pub(crate) auth_error_mapper: Mutex<AuthErrorMapper>,
+    // Receives one decision summary per authentication attempt or scope rejection
+    pub(crate) auth_metrics: Mutex<Arc<dyn AuthMetrics>>,
+    // Handler for unmatched routes (e.g. an SPA index for non-API paths)
+    pub(crate) fallback: Mutex<Option<web::FallbackRoute>>,
 
     // Duplicate detection (per (method, path) and per handler id)
     pub(crate) registered_routes: DashMap<(Method, String), ()>,
     pub(crate) registered_handlers: DashMap<String, ()>,
 }
 
+fn default_auth_metrics() -> Arc<dyn AuthMetrics> {
+    Arc::new(LoggingMetrics.with_decision_level(tracing::Level::DEBUG))
+}
+
 impl Default for ApiGateway {
     fn default() -> Self {
         let default_router = Router::new();
@@ -69,8 +83,12 @@ impl Default for ApiGateway {
             config: ArcSwap::from_pointee(ApiGatewayConfig::default()),
             openapi_registry: Arc::new(OpenApiRegistryImpl::new()),
             router_cache: RouterCache::new(default_router),
-            final_router: Mutex::new(None),
+            rest_routes: Mutex::new(None),
+            runtime_routes: Mutex::new(Router::new()),
             authn_client: Mutex::new(None),
+            auth_error_mapper: Mutex::new(default_auth_error_mapper()),
+            auth_metrics: Mutex::new(default_auth_metrics()),
+            fallback: Mutex::new(None),
             registered_routes: DashMap::new(),
             registered_handlers: DashMap::new(),
         }
@@ -99,8 +117,12 @@ impl ApiGateway {
             config: ArcSwap::from_pointee(config),
             openapi_registry: Arc::new(OpenApiRegistryImpl::new()),
             router_cache: RouterCache::new(default_router),
-            final_router: Mutex::new(None),
+            rest_routes: Mutex::new(None),
+            runtime_routes: Mutex::new(Router::new()),
             authn_client: Mutex::new(None),
+            auth_error_mapper: Mutex::new(default_auth_error_mapper()),
+            auth_metrics: Mutex::new(default_auth_metrics()),
+            fallback: Mutex::new(None),
             registered_routes: DashMap::new(),
             registered_handlers: DashMap::new(),
         }
@@ -126,15 +148,64 @@ impl ApiGateway {
     /// # Errors
     /// Returns an error if router building fails.
     pub fn rebuild_and_cache_router(&self) -> Result<()> {
-        let new_router = self.build_router()?;
-        self.router_cache.store(new_router);
+        self.router_cache.rebuild(|| self.build_fresh_router())?;
         Ok(())
     }
 
+    /// Mark the cached router as stale so the next [`build_router`](Self::build_router)
+    /// rebuilds it from the current route set.
+    ///
+    /// Called automatically whenever an operation or runtime route is registered.
+    pub fn invalidate_router_cache(&self) {
+        self.router_cache.invalidate();
+    }
+
+    /// Add routes after startup (e.g. endpoints registered by a plugin) and
+    /// invalidate the cached router so they become reachable on the next rebuild.
+    pub fn add_runtime_routes(&self, routes: Router) {
+        {
+            let mut runtime_routes = self.runtime_routes.lock();
+            let current = std::mem::take(&mut *runtime_routes);
+            *runtime_routes = current.merge(routes);
+        }
+        self.invalidate_router_cache();
+    }
+
+    /// Replace the mapping from auth rejections to HTTP status and code, and
+    /// invalidate the cached router so the next rebuild uses it.
+    ///
+    /// Applies to every auth rejection of the gateway: missing credentials,
+    /// tokens the `AuthN` resolver rejects and insufficient token scopes; see
+    /// [`AuthErrorMapper`].
+    pub fn set_auth_error_mapper(&self, mapper: AuthErrorMapper) {
+        *self.auth_error_mapper.lock() = mapper;
+        self.invalidate_router_cache();
+    }
+
+    /// Replace the sink of auth decision summaries, and invalidate the cached
+    /// router so the next rebuild uses it.
+    ///
+    /// Defaults to [`LoggingMetrics`] with the `auth_decision` record at `DEBUG`.
+    pub fn set_auth_metrics(&self, metrics: Arc<dyn AuthMetrics>) {
+        *self.auth_metrics.lock() = metrics;
+        self.invalidate_router_cache();
+    }
+
+    /// Register the handler for requests that match no route, and invalidate
+    /// the cached router so the next rebuild uses it.
+    ///
+    /// Takes effect on the served router with the next request; see
+    /// [`FallbackRoute`](web::FallbackRoute) for how API and app paths are told apart.
+    pub fn set_fallback(&self, fallback: web::FallbackRoute) {
+        *self.fallback.lock() = Some(fallback);
+        self.invalidate_router_cache();
+    }
+
     /// Build route policy from operation specs.
     fn build_route_policy_from_specs(&self) -> Result<auth::GatewayRoutePolicy> {
         let mut authenticated_routes = std::collections::HashSet::new();
         let mut public_routes = std::collections::HashSet::new();
+        let mut optional_routes = std::collections::HashSet::new();
 
         // Always mark built-in health check routes as public
         public_routes.insert((Method::GET, "/health".to_owned()));
@@ -152,6 +223,10 @@ impl ApiGateway {
                 authenticated_routes.insert(route_key.clone());
             }
 
+            if spec.optional_auth {
+                optional_routes.insert(route_key.clone());
+            }
+
             if spec.is_public {
                 public_routes.insert(route_key);
             }
@@ -160,14 +235,21 @@ impl ApiGateway {
         let config = self.get_cached_config();
         let requirements_count = authenticated_routes.len();
         let public_routes_count = public_routes.len();
+        let optional_routes_count = optional_routes.len();
 
-        let route_policy = auth::build_route_policy(&config, authenticated_routes, public_routes)?;
+        let route_policy = auth::build_route_policy(
+            &config,
+            authenticated_routes,
+            public_routes,
+            optional_routes,
+        )?;
 
         tracing::info!(
             auth_disabled = config.auth_disabled,
             require_auth_by_default = config.require_auth_by_default,
             requirements_count = requirements_count,
             public_routes_count = public_routes_count,
+            optional_routes_count = optional_routes_count,
             "Route policy built from operation specs"
         );
 
@@ -227,7 +309,7 @@ impl ApiGateway {
         //
         // Desired request execution order (outermost -> innermost):
         // SetRequestId -> PropagateRequestId -> Trace -> push_req_id_to_extensions
-        // -> Timeout -> BodyLimit -> CORS -> MIME validation -> RateLimit -> ErrorMapping -> Auth -> ScopeEnforcement -> License -> Router
+        // -> ConcurrencyLimit -> Timeout -> HostValidation -> HeaderLimit -> BodyLimit -> CORS -> MIME validation -> RateLimit -> ErrorMapping -> Auth -> SubjectConcurrencyLimit -> ScopeEnforcement -> License -> Idempotency -> MethodNotAllowed -> Router
         //
         // Therefore we must add layers in the reverse order (innermost -> outermost) below.
         // Due future refactoring, this order must be maintained.
@@ -239,7 +321,12 @@ impl ApiGateway {
 
         let config = self.get_cached_config();
 
-        // Collect specs once; used by MIME validation + rate limiting maps.
+        // Shared by the global/route limits (outer) and the per-subject share (inner to auth)
+        let concurrency_limiter = (config.concurrency_limit.enabled)
+            .then(|| concurrency::ConcurrencyLimiter::from_config(&config.concurrency_limit))
+            .transpose()?;
+
+        // Collect specs once; used by the Allow header, license, MIME validation + rate limiting maps.
         let specs: Vec<_> = self
             .openapi_registry
             .operation_specs
@@ -247,6 +334,24 @@ impl ApiGateway {
             .map(|e| e.value().clone())
             .collect();
 
+        // 13) Allow header for 405 responses (wraps the router so it sees method mismatches)
+        let allowed_methods = web::AllowedMethods::from_specs(&specs);
+        router = router.layer(from_fn(
+            move |req: axum::extract::Request, next: axum::middleware::Next| {
+                let allowed = allowed_methods.clone();
+                web::method_not_allowed_middleware(allowed, req, next)
+            },
+        ));
+
+        // 12.5) Idempotency-Key replay (inner to auth and policies so only
+        // authorized requests are replayed; keys are scoped to the subject)
+        if config.idempotency.enabled {
+            router = router.layer(from_fn_with_state(
+                middleware::idempotency::IdempotencyStore::from_config(&config.idempotency),
+                middleware::idempotency::idempotency_middleware,
+            ));
+        }
+
         // 12) License validation
         let license_map = middleware::license_validation::LicenseRequirementMap::from_specs(&specs);
 
@@ -270,14 +375,20 @@ impl ApiGateway {
             let scope_rules = middleware::scope_enforcement::ScopeEnforcementRules::from_config(
                 &config.route_policies,
             )?;
-            let scope_state =
-                middleware::scope_enforcement::ScopeEnforcementState { rules: scope_rules };
+            let scope_state = middleware::scope_enforcement::ScopeEnforcementState {
+                rules: scope_rules,
+                error_mapper: self.auth_error_mapper.lock().clone(),
+                metrics: self.auth_metrics.lock().clone(),
+            };
             router = router.layer(from_fn_with_state(
                 scope_state,
                 middleware::scope_enforcement::scope_enforcement_middleware,
             ));
         }
 
+        // 10.5) Per-subject concurrency fair share (inner to auth so the subject is known)
+        router = concurrency::apply_subject_limit(router, concurrency_limiter.as_ref());
+
         // 10) Auth
         if config.auth_disabled {
             // Build security contexts for compatibility during migration
@@ -304,6 +415,8 @@ impl ApiGateway {
             let auth_state = auth::AuthState {
                 authn_client: client,
                 route_policy,
+                error_mapper: self.auth_error_mapper.lock().clone(),
+                metrics: self.auth_metrics.lock().clone(),
             };
             router = router.layer(from_fn_with_state(auth_state, auth::authn_middleware));
         } else {
@@ -340,19 +453,49 @@ impl ApiGateway {
             router = router.layer(crate::cors::build_cors_layer(&config));
         }
 
-        // 7) Body limit
-        router = router.layer(RequestBodyLimitLayer::new(config.defaults.body_limit_bytes));
-        router = router.layer(DefaultBodyLimit::max(config.defaults.body_limit_bytes));
+        // 7) Body limit (per route via `x-max-body-size`, else the default);
+        // axum's extractor limit is lifted to the largest so it never undercuts a route
+        let route_limits =
+            middleware::route_limits::RouteLimits::from_specs(&specs, &config.defaults);
+        router = router.layer(from_fn_with_state(
+            route_limits.clone(),
+            middleware::route_limits::route_body_limit_middleware,
+        ));
+        router = router.layer(DefaultBodyLimit::max(route_limits.max_body_bytes()));
 
-        // 6) Timeout
-        router = router.layer(TimeoutLayer::with_status_code(
-            axum::http::StatusCode::GATEWAY_TIMEOUT,
-            Duration::from_secs(30),
+        // 6.5) Header limits (checked before the body is read, like the body limit)
+        router = router.layer(from_fn_with_state(
+            middleware::header_limit::HeaderLimits::from_defaults(&config.defaults),
+            middleware::header_limit::header_limit_middleware,
         ));
 
+        // 6.2) Host allow-list (rejects spoofed Host / X-Forwarded-Host before the header and body limits;
+        // health probes are exempt, as they are from prefix nesting)
+        router = middleware::host_validation::apply_host_validation(router, &config.allowed_hosts)?;
+
+        // 6) Timeout (per route via `x-timeout`, else 30s)
+        router = router.layer(from_fn_with_state(
+            route_limits,
+            middleware::route_limits::route_timeout_middleware,
+        ));
+
+        // 5.5) Concurrency limit / load shedding (inner to CatchPanic so a panicking
+        // handler drops its permits while unwinding; outer to Timeout so queue wait
+        // is bounded by `queue_timeout_ms` rather than the request timeout)
+        if let Some(limiter) = concurrency_limiter {
+            router = router.layer(from_fn_with_state(
+                limiter,
+                concurrency::concurrency_limit_middleware,
+            ));
+        }
+
         // 5) CatchPanic (converts panics to 500 before metrics sees them)
         router = router.layer(CatchPanicLayer::new());
 
+        // 4.5) Error format (outer to CatchPanic/Timeout/limits so their bare
+        // error responses are rewritten too; inner to push_req_id for the request id)
+        router = middleware::error_format::apply_error_format(router, config.error_format);
+
         // 4) HTTP metrics (layer — captures all middleware responses including auth/rate-limit/timeout)
         let http_metrics = Arc::new(middleware::http_metrics::HttpMetrics::new(
             Self::MODULE_NAME,
@@ -363,6 +506,16 @@ impl ApiGateway {
             middleware::http_metrics::http_metrics_middleware,
         ));
 
+        // 3.6) Body logging (debug aid, off by default; inner to push_req_id for the request id)
+        if config.body_logging.enabled {
+            let body_logger =
+                middleware::body_logging::BodyLogger::from_config(&config.body_logging)?;
+            router = router.layer(from_fn_with_state(
+                body_logger,
+                middleware::body_logging::body_logging_middleware,
+            ));
+        }
+
         // 3.5) Structured access log (runs after push_req_id populates XRequestId extension)
         router = router.layer(from_fn(middleware::access_log::access_log_middleware));
 
@@ -440,36 +593,79 @@ impl ApiGateway {
 
     /// Build the HTTP router from registered routes and operations.
     ///
+    /// Returns the cached router while it is in use and not invalidated;
+    /// otherwise rebuilds it and atomically swaps it into the cache.
+    ///
     /// # Errors
     /// Returns an error if router building or middleware setup fails.
     pub fn build_router(&self) -> Result<Router> {
         // If the cached router is currently held elsewhere (e.g., by the running server),
         // return it without rebuilding to avoid unnecessary allocations.
         let cached_router = self.router_cache.load();
-        if Arc::strong_count(&cached_router) > 1 {
+        if !self.router_cache.is_stale() && Arc::strong_count(&cached_router) > 1 {
             tracing::debug!("Using cached router");
             return Ok((*cached_router).clone());
         }
+        drop(cached_router);
 
-        tracing::debug!("Building new router (standalone/fallback mode)");
-        // In standalone mode (no REST pipeline), register both health endpoints here.
-        // In normal operation, rest_prepare() registers these instead.
-        let mut router = Router::new()
-            .route("/health", get(web::health_check))
-            .route("/healthz", get(|| async { "ok" }));
+        let router = self.router_cache.rebuild(|| self.build_fresh_router())?;
+        Ok((*router).clone())
+    }
+
+    /// Build a new router from the current route set without consulting the cache.
+    ///
+    /// After `rest_finalize` the base is the module routes of the REST phase
+    /// (plus docs); before it, only the health endpoints (standalone mode).
+    fn build_fresh_router(&self) -> Result<Router> {
+        let config = self.get_cached_config();
+        let rest_routes = self.rest_routes.lock().clone();
+        let mut router = if let Some(rest_routes) = rest_routes {
+            tracing::debug!("Building router from REST phase routes");
+            if config.enable_docs {
+                self.add_openapi_routes(rest_routes)?
+            } else {
+                rest_routes
+            }
+        } else {
+            tracing::debug!("Building new router (standalone/fallback mode)");
+            // In standalone mode (no REST pipeline), register both health endpoints here.
+            // In normal operation, rest_prepare() registers these instead.
+            Router::new()
+                .route("/health", get(web::health_check))
+                .route("/healthz", get(|| async { "ok" }))
+        };
+        router = router.merge(self.runtime_routes.lock().clone());
 
         // Apply all middleware layers including auth, above the router
         let authn_client = self.authn_client.lock().clone();
         router = self.apply_middleware_stack(router, authn_client)?;
 
-        let config = self.get_cached_config();
         let prefix = Self::normalize_prefix_path(&config.prefix_path)?;
-        router = Self::apply_prefix_nesting(router, &prefix);
-
-        // Cache the built router for future use
-        self.router_cache.store(router.clone());
+        let router = Self::apply_prefix_nesting(router, &prefix);
+        let router = web::apply_fallback(
+            router,
+            self.fallback.lock().clone(),
+            &prefix,
+            config.error_format,
+        );
+        Ok(web::apply_trailing_slash_policy(
+            router,
+            config.trailing_slash,
+        ))
+    }
 
-        Ok(router)
+    /// Router for the next live request: the cached one, rebuilt first if the
+    /// route set changed. A failed rebuild keeps serving the previous router.
+    fn live_router(&self) -> Arc<Router> {
+        if self.router_cache.is_stale() {
+            match self.router_cache.rebuild(|| self.build_fresh_router()) {
+                Ok(router) => return router,
+                Err(e) => {
+                    tracing::error!(error = %e, "Router rebuild failed; serving previous router");
+                }
+            }
+        }
+        self.router_cache.load()
     }
 
     /// Build `OpenAPI` specification from registered routes and components.
@@ -481,9 +677,17 @@ impl ApiGateway {
         let info = modkit::api::OpenApiInfo {
             title: config.openapi.title.clone(),
             version: config.openapi.version.clone(),
-            description: config.openapi.description,
+            description: config.openapi.description.clone(),
         };
-        self.openapi_registry.build_openapi(&info)
+        let mut doc = self.openapi_registry.build_openapi(&info)?;
+
+        // Document auth as the gateway enforces it, not only as routes declare it
+        let route_policy = self.build_route_policy_from_specs()?;
+        let scope_rules = middleware::scope_enforcement::ScopeEnforcementRules::from_config(
+            &config.route_policies,
+        )?;
+        openapi_security::apply_security(&mut doc, &config, &route_policy, &scope_rules);
+        Ok(doc)
     }
 
     /// Parse bind address from configuration string.
@@ -493,17 +697,24 @@ impl ApiGateway {
             .map_err(|e| anyhow::anyhow!("Invalid bind address '{bind_addr}': {e}"))
     }
 
-    /// Get the finalized router or build a default one.
-    fn get_or_build_router(self: &Arc<Self>) -> anyhow::Result<Router> {
-        let stored = { self.final_router.lock().take() };
-
-        if let Some(router) = stored {
-            tracing::debug!("Using router from REST phase");
-            Ok(router)
-        } else {
+    /// Router handed to the server: every request is dispatched to the
+    /// current [`live_router`](Self::live_router), so routes added at runtime
+    /// and cache invalidations reach live traffic.
+    fn dispatch_router(self: &Arc<Self>) -> anyhow::Result<Router> {
+        if self.rest_routes.lock().is_none() {
             tracing::debug!("No router from REST phase, building default router");
-            self.build_router()
+            self.rebuild_and_cache_router()?;
         }
+
+        let gateway = Arc::clone(self);
+        Ok(
+            Router::new().fallback_service(tower::service_fn(
+                move |req: axum::extract::Request| {
+                    let router = (*gateway.live_router()).clone();
+                    async move { router.oneshot(req).await }
+                },
+            )),
+        )
     }
 
     /// Background HTTP server: bind, notify ready, serve until cancelled.
@@ -517,7 +728,7 @@ impl ApiGateway {
     ) -> anyhow::Result<()> {
         let cfg = self.get_cached_config();
         let addr = Self::parse_bind_address(&cfg.bind_addr)?;
-        let router = self.get_or_build_router()?;
+        let router = self.dispatch_router()?;
 
         // Bind the socket, only now consider the service "ready"
         let listener = tokio::net::TcpListener::bind(addr).await?;
@@ -630,9 +841,10 @@ impl ApiGateway {
 
         #[cfg(feature = "embed_elements")]
         {
+            let policy = crate::assets::AssetCachePolicy::from_config(&config.static_assets)?;
             router = router.route(
                 "/docs/assets/{*file}",
-                get(crate::assets::serve_elements_asset),
+                get(crate::assets::serve_elements_asset).with_state(policy),
             );
         }
 
@@ -690,27 +902,18 @@ impl modkit::contracts::ApiGatewayCapability for ApiGateway {
     fn rest_finalize(
         &self,
         _ctx: &modkit::context::ModuleCtx,
-        mut router: axum::Router,
+        router: axum::Router,
     ) -> anyhow::Result<axum::Router> {
-        let config = self.get_cached_config();
+        // Keep the module routes: `serve()` and every later rebuild start from them
+        *self.rest_routes.lock() = Some(router);
 
-        if config.enable_docs {
-            router = self.add_openapi_routes(router)?;
-        }
-
-        // Apply middleware stack (including auth) to the final router
+        // Docs, runtime routes and the middleware stack (including auth) are
+        // applied by the rebuild
         tracing::debug!("Applying middleware stack to finalized router");
-        let authn_client = self.authn_client.lock().clone();
-        router = self.apply_middleware_stack(router, authn_client)?;
-
-        let prefix = Self::normalize_prefix_path(&config.prefix_path)?;
-        router = Self::apply_prefix_nesting(router, &prefix);
-
-        // Keep the finalized router to be used by `serve()`
-        *self.final_router.lock() = Some(router.clone());
+        let router = self.router_cache.rebuild(|| self.build_fresh_router())?;
 
         tracing::info!("REST host finalized router with OpenAPI endpoints and auth middleware");
-        Ok(router)
+        Ok((*router).clone())
     }
 
     fn as_registry(&self) -> &dyn modkit::contracts::OpenApiRegistry {
@@ -745,6 +948,9 @@ impl OpenApiRegistry for ApiGateway {
         // Delegate to the internal registry
         self.openapi_registry.register_operation(spec);
         self.log_operation_registration(spec);
+
+        // Route policy, rate limits etc. are derived from specs: rebuild on next use
+        self.invalidate_router_cache();
     }
 
     fn ensure_schema_raw(
@@ -895,6 +1101,94 @@ mod normalize_prefix_path_tests {
     }
 }
 
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod live_routing_tests {
+    use super::*;
+    use axum::Json;
+    use axum::body::Body;
+    use modkit::api::OperationBuilder;
+    use modkit::contracts::ApiGatewayCapability;
+    use serde_json::Value;
+
+    struct EmptyConfig;
+
+    impl modkit::config::ConfigProvider for EmptyConfig {
+        fn get_module_config(&self, _module: &str) -> Option<&Value> {
+            None
+        }
+    }
+
+    async fn ok_handler() -> Json<Value> {
+        Json(serde_json::json!({"ok": true}))
+    }
+
+    async fn status(server: &Router, uri: &str) -> http::StatusCode {
+        server
+            .clone()
+            .oneshot(http::Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn runtime_routes_reach_the_served_router() {
+        let gateway = Arc::new(ApiGateway::new(ApiGatewayConfig {
+            auth_disabled: true,
+            ..Default::default()
+        }));
+        let ctx = modkit::context::ModuleCtx::new(
+            "api-gateway",
+            uuid::Uuid::new_v4(),
+            Arc::new(EmptyConfig),
+            Arc::new(modkit::ClientHub::new()),
+            CancellationToken::new(),
+            None,
+        );
+
+        let module_routes = OperationBuilder::get("/tests/v1/static")
+            .operation_id("static:get")
+            .public()
+            .json_response(http::StatusCode::OK, "Static route")
+            .handler(get(ok_handler))
+            .register(
+                gateway.rest_prepare(&ctx, Router::new()).unwrap(),
+                gateway.as_ref(),
+            );
+        let _finalized = gateway.rest_finalize(&ctx, module_routes).unwrap();
+
+        // The router `serve()` hands to the listener
+        let server = gateway.dispatch_router().unwrap();
+        assert_eq!(
+            status(&server, "/tests/v1/static").await,
+            http::StatusCode::OK
+        );
+        assert_eq!(
+            status(&server, "/tests/v1/late").await,
+            http::StatusCode::NOT_FOUND
+        );
+
+        let late_routes = OperationBuilder::get("/tests/v1/late")
+            .operation_id("late:get")
+            .public()
+            .json_response(http::StatusCode::OK, "Late route")
+            .handler(get(ok_handler))
+            .register(Router::new(), gateway.as_ref());
+        gateway.add_runtime_routes(late_routes);
+
+        assert_eq!(
+            status(&server, "/tests/v1/late").await,
+            http::StatusCode::OK
+        );
+        assert_eq!(
+            status(&server, "/tests/v1/static").await,
+            http::StatusCode::OK
+        );
+        assert_eq!(status(&server, "/health").await, http::StatusCode::OK);
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod problem_openapi_tests {
@@ -0,0 +1,247 @@
+//! Security metadata of the emitted `OpenAPI` document.
+//!
+//! The registry documents each operation from its `OperationBuilder` flags
+//! alone. The gateway knows the effective setup, so it rewrites
+//! `securitySchemes` and every operation's `security` from it:
+//! `require_auth_by_default` protects undeclared routes, and `route_policies`
+//! scopes become the `oauth2` alternatives of the routes they guard.
+
+use axum::http::Method;
+use utoipa::openapi::OpenApi;
+use utoipa::openapi::extensions::Extensions;
+use utoipa::openapi::security::{
+    AuthorizationCode, ClientCredentials, Flow, HttpAuthScheme, HttpBuilder, OAuth2, Scopes,
+    SecurityRequirement, SecurityScheme,
+};
+
+use crate::config::{ApiGatewayConfig, OpenApiIntrospectionConfig, OpenApiOAuth2Config};
+use crate::middleware::auth::{AuthRequirement, GatewayRoutePolicy};
+use crate::middleware::scope_enforcement::ScopeEnforcementRules;
+
+/// Name of the HTTP bearer (JWT) security scheme.
+pub const BEARER_SCHEME: &str = "bearerAuth";
+/// Name of the `OAuth2` security scheme, present when `openapi.oauth2` is configured.
+pub const OAUTH2_SCHEME: &str = "oauth2";
+/// Name of the opaque-token scheme, present when `openapi.introspection` is configured.
+pub const INTROSPECTION_SCHEME: &str = "introspection";
+/// Operation extension listing the route policy scopes when no `oauth2`
+/// scheme is documented to carry them.
+pub const REQUIRED_SCOPES_EXTENSION: &str = "x-required-scopes";
+
+/// Replace the document's security schemes and requirements with the ones
+/// `config` and `route_policy` actually enforce.
+pub fn apply_security(
+    doc: &mut OpenApi,
+    config: &ApiGatewayConfig,
+    route_policy: &GatewayRoutePolicy,
+    scope_rules: &ScopeEnforcementRules,
+) {
+    let oauth2 = config.openapi.oauth2.as_ref();
+    let introspection = config.openapi.introspection.as_ref();
+    let components = doc.components.get_or_insert_with(Default::default);
+    components.security_schemes.clear();
+    components.security_schemes.insert(
+        BEARER_SCHEME.to_owned(),
+        SecurityScheme::Http(
+            HttpBuilder::new()
+                .scheme(HttpAuthScheme::Bearer)
+                .bearer_format("JWT")
+                .build(),
+        ),
+    );
+    if let Some(introspection) = introspection {
+        components.security_schemes.insert(
+            INTROSPECTION_SCHEME.to_owned(),
+            introspection_scheme(introspection),
+        );
+    }
+    if let Some(oauth2) = oauth2 {
+        components
+            .security_schemes
+            .insert(OAUTH2_SCHEME.to_owned(), oauth2_scheme(oauth2, config));
+    }
+
+    let mut token_schemes = vec![BEARER_SCHEME];
+    if introspection.is_some() {
+        token_schemes.push(INTROSPECTION_SCHEME);
+    }
+
+    for (path, item) in &mut doc.paths.paths {
+        let operations = [
+            (Method::GET, &mut item.get),
+            (Method::PUT, &mut item.put),
+            (Method::POST, &mut item.post),
+            (Method::DELETE, &mut item.delete),
+            (Method::OPTIONS, &mut item.options),
+            (Method::HEAD, &mut item.head),
+            (Method::PATCH, &mut item.patch),
+            (Method::TRACE, &mut item.trace),
+        ];
+        for (method, operation) in operations {
+            let Some(operation) = operation.as_mut() else {
+                continue;
+            };
+            let scopes = scope_rules
+                .required_scopes(path, method.as_str())
+                .unwrap_or_default();
+            operation.security = operation_security(
+                &route_policy.resolve(&method, path),
+                &token_schemes,
+                oauth2.is_some(),
+                scopes,
+            );
+            if operation.security.is_some() && oauth2.is_none() && !scopes.is_empty() {
+                operation
+                    .extensions
+                    .get_or_insert_with(Extensions::default)
+                    .merge(Extensions::from_iter([(
+                        REQUIRED_SCOPES_EXTENSION,
+                        serde_json::json!(scopes),
+                    )]));
+            }
+        }
+    }
+}
+
+/// Requirements for one operation.
+///
+/// `token_schemes` are the plain token schemes; `scopes` are the operation's
+/// route policy scopes. When `oauth2` is documented and scopes apply, only
+/// the scoped `oauth2` alternatives are listed, since a token without one of
+/// them is rejected whatever scheme carried it.
+fn operation_security(
+    requirement: &AuthRequirement,
+    token_schemes: &[&str],
+    oauth2: bool,
+    scopes: &[String],
+) -> Option<Vec<SecurityRequirement>> {
+    let mut requirements = match requirement {
+        AuthRequirement::None => return None,
+        // An empty requirement alongside the schemes marks the token as optional
+        AuthRequirement::Optional => vec![SecurityRequirement::default()],
+        AuthRequirement::Required => Vec::new(),
+    };
+    if oauth2 && !scopes.is_empty() {
+        // Any one of the scopes is enough, so each is its own alternative
+        requirements.extend(
+            scopes
+                .iter()
+                .map(|scope| SecurityRequirement::new(OAUTH2_SCHEME, [scope.as_str()])),
+        );
+        return Some(requirements);
+    }
+    requirements.extend(
+        token_schemes
+            .iter()
+            .map(|scheme| SecurityRequirement::new(*scheme, Vec::<String>::new())),
+    );
+    if oauth2 {
+        requirements.push(SecurityRequirement::new(
+            OAUTH2_SCHEME,
+            Vec::<String>::new(),
+        ));
+    }
+    Some(requirements)
+}
+
+fn introspection_scheme(introspection: &OpenApiIntrospectionConfig) -> SecurityScheme {
+    SecurityScheme::Http(
+        HttpBuilder::new()
+            .scheme(HttpAuthScheme::Bearer)
+            .description(Some(format!(
+                "Opaque token validated through RFC 7662 introspection at {}",
+                introspection.url
+            )))
+            .build(),
+    )
+}
+
+fn oauth2_scheme(oauth2: &OpenApiOAuth2Config, config: &ApiGatewayConfig) -> SecurityScheme {
+    let mut scopes = oauth2.scopes.clone();
+    if config.route_policies.enabled {
+        for scope in config
+            .route_policies
+            .rules
+            .iter()
+            .flat_map(|rule| &rule.required_scopes)
+        {
+            scopes.entry(scope.clone()).or_default();
+        }
+    }
+    let scopes = || Scopes::from_iter(scopes.clone());
+
+    let mut flows = vec![Flow::ClientCredentials(ClientCredentials::new(
+        &oauth2.token_url,
+        scopes(),
+    ))];
+    if let Some(authorization_url) = &oauth2.authorization_url {
+        flows.push(Flow::AuthorizationCode(AuthorizationCode::new(
+            authorization_url,
+            &oauth2.token_url,
+            scopes(),
+        )));
+    }
+    SecurityScheme::OAuth2(OAuth2::new(flows))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn security_json(requirements: Option<Vec<SecurityRequirement>>) -> serde_json::Value {
+        serde_json::to_value(requirements).unwrap()
+    }
+
+    #[test]
+    fn public_operation_has_no_security() {
+        assert!(operation_security(&AuthRequirement::None, &[BEARER_SCHEME], true, &[]).is_none());
+    }
+
+    #[test]
+    fn optional_operation_allows_anonymous() {
+        assert_eq!(
+            security_json(operation_security(
+                &AuthRequirement::Optional,
+                &[BEARER_SCHEME],
+                false,
+                &[]
+            )),
+            serde_json::json!([{}, { "bearerAuth": [] }])
+        );
+    }
+
+    #[test]
+    fn route_policy_scopes_replace_plain_token_schemes() {
+        let scopes = ["read:events".to_owned(), "write:events".to_owned()];
+        assert_eq!(
+            security_json(operation_security(
+                &AuthRequirement::Required,
+                &[BEARER_SCHEME, INTROSPECTION_SCHEME],
+                true,
+                &scopes
+            )),
+            serde_json::json!([
+                { "oauth2": ["read:events"] },
+                { "oauth2": ["write:events"] }
+            ])
+        );
+    }
+
+    #[test]
+    fn unscoped_operation_accepts_any_token_scheme() {
+        assert_eq!(
+            security_json(operation_security(
+                &AuthRequirement::Required,
+                &[BEARER_SCHEME, INTROSPECTION_SCHEME],
+                true,
+                &[]
+            )),
+            serde_json::json!([
+                { "bearerAuth": [] },
+                { "introspection": [] },
+                { "oauth2": [] }
+            ])
+        );
+    }
+}
@@ -4,7 +4,9 @@
 //! Axum routers to eliminate lock contention on hot paths.
 
 use arc_swap::ArcSwap;
+use parking_lot::Mutex;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Lock-free cache for read-mostly router data
 ///
@@ -31,9 +33,17 @@ use std::sync::Arc;
 /// // Cold path: rebuild and swap router
 /// let new_router = build_new_router().await;
 /// cache.store(new_router);
+///
+/// // Route set changed: mark stale, then rebuild once
+/// cache.invalidate();
+/// let router = cache.rebuild(|| build_new_router_sync())?;
 /// ```
 pub struct RouterCache<T> {
     inner: ArcSwap<T>,
+    // Set when the source data (e.g. the route set) changed after the last build
+    stale: AtomicBool,
+    // Serializes rebuilds so concurrent invalidations produce one consistent swap at a time
+    rebuild_lock: Mutex<()>,
 }
 
 impl<T> RouterCache<T> {
@@ -41,6 +51,48 @@ impl<T> RouterCache<T> {
     pub fn new(initial: T) -> Self {
         Self {
             inner: ArcSwap::from_pointee(initial),
+            stale: AtomicBool::new(false),
+            rebuild_lock: Mutex::new(()),
+        }
+    }
+
+    /// Mark the cached router as stale
+    ///
+    /// The current instance keeps serving `load()` calls until the next
+    /// [`rebuild`](Self::rebuild), so in-flight requests never observe a
+    /// partially built router.
+    pub fn invalidate(&self) {
+        self.stale.store(true, Ordering::Release);
+    }
+
+    /// Whether the cached router was invalidated since the last rebuild
+    pub fn is_stale(&self) -> bool {
+        self.stale.load(Ordering::Acquire)
+    }
+
+    /// Rebuild the router and atomically swap it in
+    ///
+    /// Rebuilds are serialized. The stale flag is cleared *before* `build`
+    /// runs, so an invalidation that races with the build is not lost and
+    /// triggers another rebuild next time. If `build` fails, the previous
+    /// router stays in place and the cache remains stale.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error produced by `build`.
+    pub fn rebuild<E>(&self, build: impl FnOnce() -> Result<T, E>) -> Result<Arc<T>, E> {
+        let _guard = self.rebuild_lock.lock();
+        self.stale.store(false, Ordering::Release);
+        match build() {
+            Ok(router) => {
+                let router = Arc::new(router);
+                self.inner.store(Arc::clone(&router));
+                Ok(router)
+            }
+            Err(e) => {
+                self.invalidate();
+                Err(e)
+            }
         }
     }
 
@@ -149,6 +201,8 @@ impl<T> Clone for RouterCache<T> {
     fn clone(&self) -> Self {
         Self {
             inner: ArcSwap::new(self.inner.load_full()),
+            stale: AtomicBool::new(self.is_stale()),
+            rebuild_lock: Mutex::new(()),
         }
     }
 }
@@ -170,7 +224,8 @@ where
         let current = self.inner.load();
         f.debug_struct("RouterCache")
             .field("current", &*current)
-            .finish()
+            .field("stale", &self.is_stale())
+            .finish_non_exhaustive()
     }
 }
 
@@ -368,4 +423,49 @@ mod tests {
         assert!(debug_str.contains("RouterCache"));
         assert!(debug_str.contains("updated"));
     }
+
+    #[test]
+    fn test_invalidate_and_rebuild() {
+        let cache = RouterCache::new(TestRouter::new(1, "v1"));
+        assert!(!cache.is_stale());
+
+        let before = cache.load();
+        cache.invalidate();
+        assert!(cache.is_stale());
+        // Readers keep seeing the old instance until rebuild
+        assert!(Arc::ptr_eq(&before, &cache.load()));
+
+        let rebuilt = cache
+            .rebuild(|| Ok::<_, ()>(TestRouter::new(2, "v2")))
+            .unwrap();
+        assert!(!cache.is_stale());
+        assert!(Arc::ptr_eq(&rebuilt, &cache.load()));
+        assert_eq!(cache.load().name, "v2");
+    }
+
+    #[test]
+    fn test_failed_rebuild_keeps_previous_and_stays_stale() {
+        let cache = RouterCache::new(TestRouter::new(1, "v1"));
+        cache.invalidate();
+
+        let result = cache.rebuild(|| Err::<TestRouter, _>("boom"));
+        assert_eq!(result.unwrap_err(), "boom");
+        assert!(cache.is_stale());
+        assert_eq!(cache.load().name, "v1");
+    }
+
+    #[test]
+    fn test_invalidation_during_rebuild_is_not_lost() {
+        let cache = RouterCache::new(TestRouter::new(1, "v1"));
+        cache.invalidate();
+
+        cache
+            .rebuild(|| {
+                // Route set changes again while we're building
+                cache.invalidate();
+                Ok::<_, ()>(TestRouter::new(2, "v2"))
+            })
+            .unwrap();
+        assert!(cache.is_stale());
+    }
 }
@@ -1,10 +1,19 @@
 use axum::{
-    http::StatusCode,
-    response::{Html, Json},
+    Router,
+    extract::{MatchedPath, Request},
+    http::{HeaderValue, StatusCode, Uri, header, uri::PathAndQuery},
+    middleware::{Next, from_fn},
+    response::{Html, IntoResponse, Json, Redirect, Response},
     routing::{MethodRouter, get},
 };
 use chrono::{SecondsFormat, Utc};
+use modkit::api::{OperationSpec, Problem};
 use serde_json::{Value, json};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use crate::config::{ErrorFormat, TrailingSlashPolicy};
+use crate::middleware::{common, error_format};
 
 /// Returns a 501 Not Implemented handler for operations without implementations
 #[allow(dead_code)]
@@ -20,6 +29,214 @@ pub fn placeholder_handler_501() -> MethodRouter {
     })
 }
 
+/// Registered methods per route path, used to build the `Allow` header on 405 responses
+#[derive(Clone, Default)]
+pub struct AllowedMethods {
+    by_path: Arc<HashMap<String, HeaderValue>>,
+}
+
+impl AllowedMethods {
+    #[must_use]
+    pub fn from_specs(specs: &[OperationSpec]) -> Self {
+        let mut methods: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+        for spec in specs {
+            methods
+                .entry(spec.path.as_str())
+                .or_default()
+                .insert(spec.method.as_str());
+        }
+
+        let by_path = methods
+            .into_iter()
+            .filter_map(|(path, methods)| {
+                let joined = methods.into_iter().collect::<Vec<_>>().join(", ");
+                HeaderValue::from_str(&joined)
+                    .ok()
+                    .map(|value| (path.to_owned(), value))
+            })
+            .collect();
+
+        Self {
+            by_path: Arc::new(by_path),
+        }
+    }
+
+    fn get(&self, path: &str) -> Option<&HeaderValue> {
+        self.by_path.get(path)
+    }
+}
+
+/// Rewrites the `Allow` header of 405 responses to list the methods registered for the path
+///
+/// Axum already answers 405 when the path matches but the method does not; this
+/// replaces its implicit list (which includes `HEAD` for every `GET`) with the
+/// operations actually registered. Paths without registered operations keep
+/// Axum's header as is.
+pub async fn method_not_allowed_middleware(
+    allowed: AllowedMethods,
+    req: Request,
+    next: Next,
+) -> Response {
+    let path = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| common::resolve_path(&req, p.as_str()));
+
+    let mut response = next.run(req).await;
+    if response.status() != StatusCode::METHOD_NOT_ALLOWED {
+        return response;
+    }
+
+    if let Some(value) = path.as_deref().and_then(|p| allowed.get(p)) {
+        response.headers_mut().insert(header::ALLOW, value.clone());
+    }
+    response
+}
+
+/// Apply the trailing-slash policy in front of routing
+///
+/// The policy has to see the request before Axum matches it against the
+/// routes, so the router is wrapped as the fallback of an outer router
+/// instead of receiving another per-route layer.
+pub fn apply_trailing_slash_policy(router: Router, policy: TrailingSlashPolicy) -> Router {
+    if policy == TrailingSlashPolicy::Strict {
+        return router;
+    }
+
+    Router::new()
+        .fallback_service(router)
+        .layer(from_fn(move |req: Request, next: Next| {
+            trailing_slash_middleware(policy, req, next)
+        }))
+}
+
+async fn trailing_slash_middleware(
+    policy: TrailingSlashPolicy,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let Some(canonical) = canonical_path_and_query(req.uri()) else {
+        return next.run(req).await;
+    };
+
+    match policy {
+        TrailingSlashPolicy::Strict => next.run(req).await,
+        TrailingSlashPolicy::RedirectToCanonical => {
+            Redirect::permanent(canonical.as_str()).into_response()
+        }
+        TrailingSlashPolicy::Ignore => {
+            let mut parts = req.uri().clone().into_parts();
+            parts.path_and_query = Some(canonical);
+            if let Ok(uri) = Uri::from_parts(parts) {
+                *req.uri_mut() = uri;
+            }
+            next.run(req).await
+        }
+    }
+}
+
+/// Path (and query) without trailing slashes, or `None` if the path is already canonical
+fn canonical_path_and_query(uri: &Uri) -> Option<PathAndQuery> {
+    let path = uri.path();
+    if path == "/" || !path.ends_with('/') {
+        return None;
+    }
+
+    let trimmed = match path.trim_end_matches('/') {
+        "" => "/",
+        trimmed => trimmed,
+    };
+    let canonical = match uri.query() {
+        Some(query) => format!("{trimmed}?{query}"),
+        None => trimmed.to_owned(),
+    };
+    canonical.parse().ok()
+}
+
+/// Handler for requests that match no registered route
+///
+/// Paths under one of the API prefixes keep answering `404 Not Found` as an
+/// error document; every other path is served by the app handler, e.g. an SPA
+/// `index.html`. Prefixes are relative to `prefix_path` and matched on whole
+/// segments, so with `prefix_path: /cf` the prefix `/api` covers `/cf/api/...`.
+#[derive(Clone)]
+pub struct FallbackRoute {
+    api_prefixes: Arc<[String]>,
+    app: MethodRouter,
+}
+
+impl FallbackRoute {
+    #[must_use]
+    pub fn new<I, S>(api_prefixes: I, app: MethodRouter) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let api_prefixes = api_prefixes
+            .into_iter()
+            .map(|prefix| prefix.into().trim_end_matches('/').to_owned())
+            .collect();
+        Self { api_prefixes, app }
+    }
+
+    /// Whether `path` lies under `prefix_path` joined with one of the API prefixes.
+    fn is_api_path(&self, prefix_path: &str, path: &str) -> bool {
+        let Some(path) = strip_segment_prefix(path, prefix_path) else {
+            return false;
+        };
+        self.api_prefixes
+            .iter()
+            .any(|prefix| strip_segment_prefix(path, prefix).is_some())
+    }
+}
+
+/// `path` without `prefix`, if `prefix` covers whole leading segments of it.
+fn strip_segment_prefix<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    path.strip_prefix(prefix)
+        .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Install the fallback route on the finished router
+///
+/// The fallback sits outside the gateway middleware stack so app paths are
+/// served without authentication; API misses still get the configured error format.
+pub fn apply_fallback(
+    router: Router,
+    fallback: Option<FallbackRoute>,
+    prefix_path: &str,
+    format: ErrorFormat,
+) -> Router {
+    let Some(fallback) = fallback else {
+        return router;
+    };
+
+    let prefix_path: Arc<str> = prefix_path.into();
+    let fallback_router = Router::new().fallback(move |req: Request| {
+        fallback_handler(fallback.clone(), Arc::clone(&prefix_path), req)
+    });
+    router.fallback_service(error_format::apply_error_format(fallback_router, format))
+}
+
+async fn fallback_handler(
+    fallback: FallbackRoute,
+    prefix_path: Arc<str>,
+    req: Request,
+) -> Response {
+    let path = req.uri().path().to_owned();
+    if fallback.is_api_path(&prefix_path, &path) {
+        return Problem::new(
+            StatusCode::NOT_FOUND,
+            "Not Found",
+            format!("No route matches {path}"),
+        )
+        .with_instance(path)
+        .into_response();
+    }
+
+    let Ok(response) = tower::ServiceExt::oneshot(fallback.app, req).await;
+    response
+}
+
 pub async fn health_check() -> Json<Value> {
     Json(json!({
         "status": "healthy",
@@ -500,6 +500,98 @@ async fn test_openapi_includes_security_metadata() {
     );
 }
 
+/// `OpenAPI` document of `TestAuthModule` under the given gateway config.
+fn openapi_spec(gateway_config: serde_json::Value) -> serde_json::Value {
+    let config = serde_json::from_value(gateway_config).expect("valid gateway config");
+    let api_gateway = api_gateway::ApiGateway::new(config);
+    let _router = TestAuthModule
+        .register_rest(&create_test_module_ctx(), Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+
+    let openapi = api_gateway
+        .build_openapi()
+        .expect("Failed to build OpenAPI");
+    serde_json::to_value(&openapi).expect("Failed to serialize")
+}
+
+#[tokio::test]
+async fn test_openapi_documents_oauth2_scopes_from_route_policies() {
+    let spec = openapi_spec(json!({
+        "bind_addr": "0.0.0.0:8080",
+        "openapi": {
+            "oauth2": {
+                "token_url": "https://idp.example.com/token",
+                "scopes": { "read:profile": "Read the caller profile" }
+            }
+        },
+        "route_policies": {
+            "enabled": true,
+            "rules": [
+                { "path": "/tests/v1/api/protected", "required_scopes": ["api:read", "api:admin"] }
+            ]
+        }
+    }));
+
+    let flow = spec
+        .pointer("/components/securitySchemes/oauth2/flows/clientCredentials")
+        .expect("client credentials flow");
+    assert_eq!(flow["tokenUrl"], "https://idp.example.com/token");
+    assert_eq!(
+        flow["scopes"],
+        json!({ "api:admin": "", "api:read": "", "read:profile": "Read the caller profile" })
+    );
+
+    assert_eq!(
+        spec.pointer("/paths/~1tests~1v1~1api~1protected/get/security"),
+        Some(&json!([
+            { "oauth2": ["api:read"] },
+            { "oauth2": ["api:admin"] }
+        ])),
+        "a scoped route is only reachable with one of its scopes"
+    );
+    assert!(
+        spec.pointer("/paths/~1tests~1v1~1api~1public/get/security")
+            .is_none(),
+        "public route lists no scheme"
+    );
+}
+
+#[tokio::test]
+async fn test_openapi_documents_introspection_and_scopes_without_oauth2() {
+    let spec = openapi_spec(json!({
+        "bind_addr": "0.0.0.0:8080",
+        "openapi": {
+            "introspection": { "url": "https://idp.example.com/introspect" }
+        },
+        "route_policies": {
+            "enabled": true,
+            "rules": [
+                { "path": "/tests/v1/api/protected", "required_scopes": ["api:read"] }
+            ]
+        }
+    }));
+
+    let scheme = spec
+        .pointer("/components/securitySchemes/introspection")
+        .expect("introspection scheme");
+    assert_eq!(scheme["scheme"], "bearer");
+    assert!(
+        scheme["description"]
+            .as_str()
+            .is_some_and(|d| d.contains("https://idp.example.com/introspect")),
+        "description should name the endpoint: {scheme}"
+    );
+
+    let protected = spec
+        .pointer("/paths/~1tests~1v1~1api~1protected/get")
+        .expect("protected operation");
+    assert_eq!(
+        protected["security"],
+        json!([{ "bearerAuth": [] }, { "introspection": [] }])
+    );
+    assert_eq!(protected["x-required-scopes"], json!(["api:read"]));
+}
+
 #[tokio::test]
 async fn test_route_pattern_matching_with_path_params() {
     // This test verifies that routes with path parameters (e.g., /users/{id})
@@ -708,11 +800,30 @@ impl RestApiCapability for TestAuthEnabledModule {
             .json_response_with_schema::<TestResponse>(openapi, http::StatusCode::OK, "Success")
             .register(router, openapi);
 
+        // Optional-auth route: anonymous without a token, authenticated with one
+        let router = OperationBuilder::get("/tests/v1/api/optional-ctx")
+            .operation_id("test_auth.optional_ctx")
+            .optional_auth()
+            .summary("Optional-auth endpoint with security context")
+            .handler(protected_handler)
+            .json_response_with_schema::<TestResponse>(openapi, http::StatusCode::OK, "Success")
+            .error_401(openapi)
+            .register(router, openapi);
+
         Ok(router)
     }
 }
 
 async fn create_router(config: serde_json::Value, mock: MockAuthNResolverClient) -> Router {
+    create_router_with(config, mock, |_| {}).await
+}
+
+/// Like [`create_router`], letting `configure` adjust the gateway before finalizing.
+async fn create_router_with(
+    config: serde_json::Value,
+    mock: MockAuthNResolverClient,
+    configure: impl FnOnce(&api_gateway::ApiGateway),
+) -> Router {
     let hub = Arc::new(ClientHub::new());
     hub.register::<dyn AuthNResolverClient>(Arc::new(mock));
 
@@ -728,6 +839,7 @@ async fn create_router(config: serde_json::Value, mock: MockAuthNResolverClient)
 
     let api_gateway = api_gateway::ApiGateway::default();
     api_gateway.init(&api_ctx).await.expect("Failed to init");
+    configure(&api_gateway);
 
     let mut router = Router::new();
     let test_module = TestAuthEnabledModule;
@@ -881,6 +993,113 @@ async fn test_invalid_token_returns_401() {
     );
 }
 
+#[tokio::test]
+async fn test_custom_mapper_rewrites_expired_token_rejection() {
+    use api_gateway::error::{ErrorCode, default_auth_error_mapping};
+    use modkit_auth::AuthError;
+
+    let mock =
+        mock_returning_error(|| AuthNResolverError::Unauthorized("token expired".to_owned()));
+    let config = json!({
+        "api-gateway": {
+            "config": {
+                "bind_addr": "0.0.0.0:8080",
+                "enable_docs": false,
+                "auth_disabled": false,
+            }
+        }
+    });
+    let mapper: api_gateway::error::AuthErrorMapper = Arc::new(|err: &AuthError| match err {
+        AuthError::TokenExpired => (StatusCode::FORBIDDEN, ErrorCode::Forbidden),
+        other => default_auth_error_mapping(other),
+    });
+    let router = create_router_with(config, mock, |gateway| {
+        gateway.set_auth_error_mapper(mapper);
+    })
+    .await;
+
+    let response = router
+        .oneshot(
+            Request::builder()
+                .uri("/tests/v1/api/protected")
+                .header(header::AUTHORIZATION, "Bearer expired-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Request failed");
+
+    assert_eq!(
+        response.status(),
+        StatusCode::FORBIDDEN,
+        "Custom mapper should rewrite the 401 for an expired token"
+    );
+}
+
+/// Collects the outcome of every recorded auth decision.
+#[derive(Default)]
+struct RecordingMetrics {
+    outcomes: std::sync::Mutex<Vec<modkit_auth::AuthOutcome>>,
+}
+
+impl modkit_auth::AuthMetrics for RecordingMetrics {
+    fn record_event(
+        &self,
+        _event: modkit_auth::AuthEvent,
+        _labels: &modkit_auth::AuthMetricLabels,
+    ) {
+    }
+
+    fn record_duration(&self, _duration_ms: u64, _labels: &modkit_auth::AuthMetricLabels) {}
+
+    fn record_decision(&self, decision: &modkit_auth::AuthDecision) {
+        self.outcomes.lock().unwrap().push(decision.outcome);
+    }
+}
+
+#[tokio::test]
+async fn test_auth_decisions_are_recorded() {
+    use modkit_auth::AuthOutcome;
+
+    let metrics = Arc::new(RecordingMetrics::default());
+    let mock = mock_accepting_token("good-token", Uuid::new_v4(), Uuid::new_v4());
+    let config = json!({
+        "api-gateway": {
+            "config": {
+                "bind_addr": "0.0.0.0:8080",
+                "enable_docs": false,
+                "auth_disabled": false,
+            }
+        }
+    });
+    let sink = Arc::clone(&metrics);
+    let router = create_router_with(config, mock, move |gateway| {
+        gateway.set_auth_metrics(sink);
+    })
+    .await;
+
+    for token in [Some("good-token"), Some("bad-token"), None] {
+        let mut request = Request::builder().uri("/tests/v1/api/protected");
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        router
+            .clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .expect("Request failed");
+    }
+
+    assert_eq!(
+        *metrics.outcomes.lock().unwrap(),
+        vec![
+            AuthOutcome::Allowed,
+            AuthOutcome::Unauthenticated,
+            AuthOutcome::Unauthenticated
+        ]
+    );
+}
+
 #[tokio::test]
 async fn test_no_plugin_available_returns_503() {
     let mock = mock_returning_error(|| AuthNResolverError::NoPluginAvailable);
@@ -987,6 +1206,100 @@ async fn test_public_route_with_auth_enabled() {
     );
 }
 
+#[tokio::test]
+async fn test_public_route_ignores_invalid_token() {
+    let mock = mock_accepting_token("good-token", Uuid::new_v4(), Uuid::new_v4());
+    let router = create_auth_enabled_router(mock, false).await;
+
+    let request = |uri: &str| {
+        Request::builder()
+            .uri(uri)
+            .header(header::AUTHORIZATION, "Bearer bad-token")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    // The same bad token is rejected where auth is required...
+    let response = router
+        .clone()
+        .oneshot(request("/tests/v1/api/protected"))
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // ...and never validated on a public route
+    let response = router
+        .oneshot(request("/tests/v1/api/public-ctx"))
+        .await
+        .expect("Request failed");
+    assert_eq!(
+        response.status(),
+        StatusCode::OK,
+        "Public route must ignore the Authorization header"
+    );
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["user_id"], Uuid::default().to_string());
+}
+
+async fn get_optional_ctx(router: Router, token: Option<&str>) -> axum::response::Response {
+    let mut request = Request::builder().uri("/tests/v1/api/optional-ctx");
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    router
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .expect("Request failed")
+}
+
+#[tokio::test]
+async fn test_optional_auth_route_without_token_is_anonymous() {
+    let mock = mock_accepting_token("good-token", Uuid::new_v4(), Uuid::new_v4());
+    let router = create_auth_enabled_router(mock, false).await;
+
+    let response = get_optional_ctx(router, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["user_id"], Uuid::default().to_string());
+}
+
+#[tokio::test]
+async fn test_optional_auth_route_with_valid_token_is_authenticated() {
+    let subject_id = Uuid::new_v4();
+    let mock = mock_accepting_token("good-token", subject_id, Uuid::new_v4());
+    let router = create_auth_enabled_router(mock, false).await;
+
+    let response = get_optional_ctx(router, Some("good-token")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["user_id"], subject_id.to_string());
+}
+
+#[tokio::test]
+async fn test_optional_auth_route_with_invalid_token_returns_401() {
+    let mock = mock_accepting_token("good-token", Uuid::new_v4(), Uuid::new_v4());
+    let router = create_auth_enabled_router(mock, false).await;
+
+    let response = get_optional_ctx(router, Some("bad-token")).await;
+    assert_eq!(
+        response.status(),
+        StatusCode::UNAUTHORIZED,
+        "Invalid token on an optional-auth route must not fall back to anonymous"
+    );
+}
+
 #[tokio::test]
 async fn test_public_route_with_prefix_auth_enabled() {
     // Mock that would reject any token — proves it is never called for public routes
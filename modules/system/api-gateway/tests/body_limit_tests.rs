@@ -223,3 +223,112 @@ async fn test_openapi_includes_413_response() {
         "413 Payload Too Large response should be documented"
     );
 }
+
+pub struct PerRouteLimitTestModule;
+
+#[async_trait]
+impl Module for PerRouteLimitTestModule {
+    async fn init(&self, _ctx: &modkit::ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl RestApiCapability for PerRouteLimitTestModule {
+    fn register_rest(
+        &self,
+        _ctx: &modkit::ModuleCtx,
+        router: axum::Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<axum::Router> {
+        let router = OperationBuilder::post("/files/v1/avatar")
+            .operation_id("test:avatar")
+            .summary("Upload endpoint with a 1KB limit")
+            .max_body_size(1024)
+            .timeout(std::time::Duration::from_secs(5))
+            .json_request::<LargePayload>(openapi, "Small payload")
+            .public()
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(post(upload_handler))
+            .register(router, openapi);
+
+        let router = OperationBuilder::post("/files/v1/upload")
+            .operation_id("test:upload")
+            .summary("Upload endpoint using the default limit")
+            .json_request::<LargePayload>(openapi, "Large payload")
+            .public()
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(post(upload_handler))
+            .register(router, openapi);
+
+        Ok(router)
+    }
+}
+
+async fn post_payload(router: Router, uri: &str, len: usize) -> http::StatusCode {
+    use tower::ServiceExt;
+
+    let body = serde_json::to_vec(&serde_json::json!({ "data": "x".repeat(len) })).unwrap();
+    router
+        .oneshot(
+            http::Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(axum::body::Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn test_operation_body_limit_overrides_default() {
+    let api_gateway = api_gateway::ApiGateway::default();
+    let ctx = create_test_module_ctx_with_body_limit(16 * 1024 * 1024);
+    api_gateway.init(&ctx).await.expect("Failed to init");
+
+    let router = PerRouteLimitTestModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    let router = api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize router");
+
+    assert_eq!(
+        post_payload(router.clone(), "/files/v1/avatar", 2048).await,
+        http::StatusCode::PAYLOAD_TOO_LARGE,
+        "2KB body must exceed the operation's 1KB limit"
+    );
+    assert_eq!(
+        post_payload(router.clone(), "/files/v1/avatar", 512).await,
+        http::StatusCode::OK
+    );
+    assert_eq!(
+        post_payload(router, "/files/v1/upload", 2048).await,
+        http::StatusCode::OK,
+        "routes without x-max-body-size use the global default"
+    );
+}
+
+#[tokio::test]
+async fn test_openapi_includes_route_limit_extensions() {
+    let api_gateway = api_gateway::ApiGateway::default();
+    let ctx = create_test_module_ctx_with_body_limit(1024);
+    api_gateway.init(&ctx).await.expect("Failed to init");
+
+    let _router = PerRouteLimitTestModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+
+    let openapi = api_gateway
+        .build_openapi()
+        .expect("Failed to build OpenAPI");
+    let json = serde_json::to_value(&openapi).expect("Failed to serialize");
+
+    let avatar = json.pointer("/paths/~1files~1v1~1avatar/post").unwrap();
+    assert_eq!(avatar["x-max-body-size"], 1024);
+    assert_eq!(avatar["x-timeout"], 5000);
+    let upload = json.pointer("/paths/~1files~1v1~1upload/post").unwrap();
+    assert!(upload.get("x-max-body-size").is_none());
+}
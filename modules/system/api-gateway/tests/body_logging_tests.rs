@@ -0,0 +1,151 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    Router,
+    body::{Body, Bytes},
+    http::{Request, StatusCode, header},
+    middleware::from_fn_with_state,
+    response::IntoResponse,
+    routing::post,
+};
+use tower::util::ServiceExt;
+use tracing_subscriber::layer::SubscriberExt;
+
+use api_gateway::BodyLoggingConfig;
+use api_gateway::middleware::body_logging::{BodyLogger, body_logging_middleware};
+
+type Fields = HashMap<String, String>;
+
+/// A tracing layer that captures events with target `body_log`.
+#[derive(Clone, Default)]
+struct CapturingLayer {
+    events: Arc<Mutex<Vec<Fields>>>,
+}
+
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for CapturingLayer {
+    fn on_event(
+        &self,
+        event: &tracing::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        if event.metadata().target() != "body_log" {
+            return;
+        }
+        let mut fields = HashMap::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        self.events.lock().unwrap().push(fields);
+    }
+}
+
+struct FieldVisitor<'a>(&'a mut Fields);
+
+impl tracing::field::Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_owned(), format!("{value:?}"));
+    }
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.0.insert(field.name().to_owned(), value.to_owned());
+    }
+    fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+        self.0.insert(field.name().to_owned(), value.to_string());
+    }
+}
+
+fn test_app(max_bytes: usize) -> Router {
+    let logger = BodyLogger::from_config(&BodyLoggingConfig {
+        enabled: true,
+        max_bytes,
+        exclude_routes: vec!["/stream/**".to_owned()],
+        ..BodyLoggingConfig::default()
+    })
+    .unwrap();
+
+    Router::new()
+        .route("/echo", post(echo))
+        .route("/stream/upload", post(echo))
+        .layer(from_fn_with_state(logger, body_logging_middleware))
+}
+
+async fn echo(body: Bytes) -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/json")], body)
+}
+
+/// Send a JSON body through the test app and return the response body and
+/// every `body_log` event, keyed by direction.
+async fn run_with_capture(app: Router, uri: &str, body: &str) -> (Bytes, HashMap<String, Fields>) {
+    let layer = CapturingLayer::default();
+    let events = layer.events.clone();
+    let subscriber = tracing_subscriber::registry().with(layer);
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let req = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_owned()))
+        .unwrap();
+    let response = app.oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    let by_direction = events
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|fields| (fields["direction"].clone(), fields.clone()))
+        .collect();
+    (body, by_direction)
+}
+
+#[tokio::test]
+async fn logged_bodies_have_password_redacted() {
+    let payload = r#"{"email":"bob@example.com","password":"hunter2"}"#;
+
+    let (body, events) = run_with_capture(test_app(4096), "/echo", payload).await;
+
+    // The handler and the client see the original body
+    assert_eq!(body, payload.as_bytes());
+
+    assert_eq!(events.len(), 2, "one record per request and response body");
+    for direction in ["request", "response"] {
+        let fields = &events[direction];
+        assert_eq!(
+            fields["body"],
+            r#"{"email":"bob@example.com","password":"[REDACTED]"}"#
+        );
+        assert_eq!(fields["body_bytes"], payload.len().to_string());
+        assert_eq!(fields["method"], "POST");
+        assert_eq!(fields["uri"], "/echo");
+    }
+    assert_eq!(events["response"]["status"], "200");
+}
+
+#[tokio::test]
+async fn bodies_over_the_cap_are_truncated_with_marker() {
+    let payload = format!(r#"{{"data":"{}"}}"#, "x".repeat(100));
+
+    let (body, events) = run_with_capture(test_app(16), "/echo", &payload).await;
+
+    assert_eq!(body, payload.as_bytes(), "truncation only affects the log");
+    let logged = &events["request"]["body"];
+    assert_eq!(
+        *logged,
+        format!(
+            r#"{{"data":"xxxxxxx...[truncated, {} bytes total]"#,
+            payload.len()
+        )
+    );
+}
+
+#[tokio::test]
+async fn excluded_routes_are_not_logged() {
+    let (body, events) = run_with_capture(test_app(4096), "/stream/upload", "{}").await;
+
+    assert_eq!(body, b"{}".as_slice());
+    assert!(events.is_empty());
+}
@@ -0,0 +1,416 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for global and per-route concurrency limits (load shedding)
+
+use anyhow::Result;
+use async_trait::async_trait;
+use authn_resolver_sdk::{
+    AuthNResolverClient, AuthNResolverError, AuthenticationResult, ClientCredentialsRequest,
+};
+use axum::{
+    Router,
+    body::Body,
+    extract::Json,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use modkit::{
+    Module, ModuleCtx, RestApiCapability,
+    api::OperationBuilder,
+    api::operation_builder::LicenseFeature,
+    config::ConfigProvider,
+    contracts::{ApiGatewayCapability, OpenApiRegistry},
+};
+use modkit_security::SecurityContext;
+use std::sync::Arc;
+use tokio::time::{Duration, sleep};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct TestConfigProvider {
+    config: serde_json::Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+        if module == "api-gateway" {
+            Some(&self.config)
+        } else {
+            None
+        }
+    }
+}
+
+fn create_test_module_ctx(concurrency_limit: &serde_json::Value) -> ModuleCtx {
+    create_module_ctx(concurrency_limit, None)
+}
+
+/// Like [`create_test_module_ctx`], with auth enabled through `authn` when given.
+fn create_module_ctx(
+    concurrency_limit: &serde_json::Value,
+    authn: Option<Arc<dyn AuthNResolverClient>>,
+) -> ModuleCtx {
+    let config = serde_json::json!({
+        "config": {
+            "bind_addr": "127.0.0.1:0",
+            "auth_disabled": authn.is_none(),
+            "concurrency_limit": concurrency_limit,
+        }
+    });
+
+    let hub = Arc::new(modkit::ClientHub::new());
+    if let Some(authn) = authn {
+        hub.register::<dyn AuthNResolverClient>(authn);
+    }
+
+    ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        hub,
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    )
+}
+
+/// `AuthN` mock treating the bearer token as the subject ID.
+struct SubjectTokenAuthN;
+
+#[async_trait]
+impl AuthNResolverClient for SubjectTokenAuthN {
+    async fn authenticate(
+        &self,
+        bearer_token: &str,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        let subject_id = Uuid::parse_str(bearer_token)
+            .map_err(|_| AuthNResolverError::Unauthorized("invalid token".to_owned()))?;
+        Ok(AuthenticationResult {
+            security_context: SecurityContext::builder()
+                .subject_id(subject_id)
+                .subject_tenant_id(Uuid::new_v4())
+                .build()
+                .unwrap(),
+        })
+    }
+
+    async fn exchange_client_credentials(
+        &self,
+        _request: &ClientCredentialsRequest,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        Err(AuthNResolverError::Internal(
+            "not implemented in mock".to_owned(),
+        ))
+    }
+}
+
+struct License;
+
+impl AsRef<str> for License {
+    fn as_ref(&self) -> &'static str {
+        "gts.x.core.lic.feat.v1~x.core.global.base.v1"
+    }
+}
+
+impl LicenseFeature for License {}
+
+pub struct ConcurrencyTestModule;
+
+#[async_trait]
+impl Module for ConcurrencyTestModule {
+    async fn init(&self, _ctx: &modkit::ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl RestApiCapability for ConcurrencyTestModule {
+    fn register_rest(
+        &self,
+        _ctx: &modkit::ModuleCtx,
+        router: axum::Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<axum::Router> {
+        let router = OperationBuilder::get("/tests/v1/slow")
+            .operation_id("test:slow")
+            .public()
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(get(slow_handler))
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/tests/v1/other")
+            .operation_id("test:other")
+            .public()
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(get(slow_handler))
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/tests/v1/slow-authn")
+            .operation_id("test:slow_authn")
+            .authenticated()
+            .require_license_features::<License>([])
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(get(slow_handler))
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/tests/v1/fast")
+            .operation_id("test:fast")
+            .public()
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(get(fast_handler))
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/tests/v1/panic")
+            .operation_id("test:panic")
+            .public()
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(get(panic_handler))
+            .register(router, openapi);
+
+        Ok(router)
+    }
+}
+
+async fn slow_handler() -> Json<serde_json::Value> {
+    sleep(Duration::from_millis(300)).await;
+    Json(serde_json::json!({"ok": true}))
+}
+
+async fn fast_handler() -> Json<serde_json::Value> {
+    Json(serde_json::json!({"ok": true}))
+}
+
+#[allow(clippy::unused_async)]
+async fn panic_handler() -> Json<serde_json::Value> {
+    panic!("handler failure");
+}
+
+async fn build_router(concurrency_limit: &serde_json::Value) -> Router {
+    build_router_with(create_test_module_ctx(concurrency_limit)).await
+}
+
+async fn build_router_with(ctx: ModuleCtx) -> Router {
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+
+    let router = ConcurrencyTestModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize router")
+}
+
+fn spawn_get(router: &Router, uri: &'static str) -> tokio::task::JoinHandle<StatusCode> {
+    let router = router.clone();
+    tokio::spawn(async move { get_status(router, uri).await })
+}
+
+async fn get_status(router: Router, uri: &str) -> StatusCode {
+    router
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn route_limit_sheds_excess_request_and_returns_permits() {
+    let router = build_router(&serde_json::json!({
+        "enabled": true,
+        "routes": [{ "path": "/tests/v1/slow", "max_in_flight": 2 }]
+    }))
+    .await;
+
+    let in_flight = [
+        spawn_get(&router, "/tests/v1/slow"),
+        spawn_get(&router, "/tests/v1/slow"),
+    ];
+    sleep(Duration::from_millis(50)).await;
+
+    let shed = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/tests/v1/slow")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(shed.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert!(shed.headers().contains_key(http::header::RETRY_AFTER));
+
+    // Other routes are not affected by the per-route limit
+    assert_eq!(
+        get_status(router.clone(), "/tests/v1/fast").await,
+        StatusCode::OK
+    );
+
+    for handle in in_flight {
+        assert_eq!(handle.await.unwrap(), StatusCode::OK);
+    }
+
+    // Permits were returned after completion
+    let again = [
+        spawn_get(&router, "/tests/v1/slow"),
+        spawn_get(&router, "/tests/v1/slow"),
+    ];
+    for handle in again {
+        assert_eq!(handle.await.unwrap(), StatusCode::OK);
+    }
+}
+
+#[tokio::test]
+async fn global_limit_applies_across_routes() {
+    let router = build_router(&serde_json::json!({
+        "enabled": true,
+        "global_max_in_flight": 2
+    }))
+    .await;
+
+    let in_flight = [
+        spawn_get(&router, "/tests/v1/slow"),
+        spawn_get(&router, "/tests/v1/other"),
+    ];
+    sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(
+        get_status(router.clone(), "/tests/v1/fast").await,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    for handle in in_flight {
+        assert_eq!(handle.await.unwrap(), StatusCode::OK);
+    }
+    assert_eq!(
+        get_status(router.clone(), "/tests/v1/fast").await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn queued_request_is_served_when_permit_frees_up() {
+    let router = build_router(&serde_json::json!({
+        "enabled": true,
+        "max_queued": 1,
+        "queue_timeout_ms": 5000,
+        "routes": [{ "path": "/tests/v1/slow", "max_in_flight": 1 }]
+    }))
+    .await;
+
+    let first = spawn_get(&router, "/tests/v1/slow");
+    sleep(Duration::from_millis(50)).await;
+    let queued = spawn_get(&router, "/tests/v1/slow");
+    sleep(Duration::from_millis(50)).await;
+
+    // Limit saturated and queue full -> shed
+    assert_eq!(
+        get_status(router.clone(), "/tests/v1/slow").await,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    assert_eq!(first.await.unwrap(), StatusCode::OK);
+    assert_eq!(queued.await.unwrap(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn permits_released_when_handler_panics() {
+    let router = build_router(&serde_json::json!({
+        "enabled": true,
+        "global_max_in_flight": 1
+    }))
+    .await;
+
+    for _ in 0..3 {
+        assert_eq!(
+            get_status(router.clone(), "/tests/v1/panic").await,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    assert_eq!(
+        get_status(router.clone(), "/tests/v1/fast").await,
+        StatusCode::OK
+    );
+}
+
+fn spawn_get_as(router: &Router, subject: Uuid) -> tokio::task::JoinHandle<StatusCode> {
+    let router = router.clone();
+    tokio::spawn(async move { get_status_as(router, subject).await })
+}
+
+async fn get_status_as(router: Router, subject: Uuid) -> StatusCode {
+    router
+        .oneshot(
+            Request::builder()
+                .uri("/tests/v1/slow-authn")
+                .header(http::header::AUTHORIZATION, format!("Bearer {subject}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn saturated_subject_is_shed_while_others_are_served() {
+    let router = build_router_with(create_module_ctx(
+        &serde_json::json!({
+            "enabled": true,
+            "global_max_in_flight": 4,
+            "per_subject_max_in_flight": 2
+        }),
+        Some(Arc::new(SubjectTokenAuthN)),
+    ))
+    .await;
+    let (noisy, quiet) = (Uuid::new_v4(), Uuid::new_v4());
+
+    let in_flight = [spawn_get_as(&router, noisy), spawn_get_as(&router, noisy)];
+    sleep(Duration::from_millis(50)).await;
+
+    // The noisy subject is at its share: its excess is shed with 429 ...
+    let shed = [spawn_get_as(&router, noisy), spawn_get_as(&router, noisy)];
+    for handle in shed {
+        assert_eq!(handle.await.unwrap(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    // ... while the rest of the global capacity still serves another subject
+    let served = [spawn_get_as(&router, quiet), spawn_get_as(&router, quiet)];
+    for handle in in_flight.into_iter().chain(served) {
+        assert_eq!(handle.await.unwrap(), StatusCode::OK);
+    }
+
+    // The noisy subject's slots were returned after completion
+    assert_eq!(get_status_as(router.clone(), noisy).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn per_subject_limit_must_leave_global_capacity() {
+    let api_gateway = api_gateway::ApiGateway::default();
+    let ctx = create_test_module_ctx(&serde_json::json!({
+        "enabled": true,
+        "global_max_in_flight": 2,
+        "per_subject_max_in_flight": 2
+    }));
+    api_gateway.init(&ctx).await.expect("Failed to init");
+
+    let router = ConcurrencyTestModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    assert!(api_gateway.rest_finalize(&ctx, router).is_err());
+}
+
+#[tokio::test]
+async fn invalid_route_limit_fails_finalize() {
+    let api_gateway = api_gateway::ApiGateway::default();
+    let ctx = create_test_module_ctx(&serde_json::json!({
+        "enabled": true,
+        "routes": [{ "path": "/tests/v1/slow", "max_in_flight": 0 }]
+    }));
+    api_gateway.init(&ctx).await.expect("Failed to init");
+
+    let router = ConcurrencyTestModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    assert!(api_gateway.rest_finalize(&ctx, router).is_err());
+}
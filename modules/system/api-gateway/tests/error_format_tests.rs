@@ -0,0 +1,386 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for the error wire formats (`error_format: json` / `normalized_problem`)
+
+use anyhow::Result;
+use async_trait::async_trait;
+use authn_resolver_sdk::{
+    AuthNResolverClient, AuthNResolverError, AuthenticationResult, ClientCredentialsRequest,
+};
+use axum::{
+    Router,
+    body::Body,
+    extract::Json,
+    http::{Request, StatusCode, header},
+    response::IntoResponse,
+    routing::get,
+};
+use modkit::{
+    ClientHub, Module, ModuleCtx, RestApiCapability,
+    api::{OperationBuilder, Problem, operation_builder::LicenseFeature},
+    config::ConfigProvider,
+    contracts::{ApiGatewayCapability, OpenApiRegistry},
+};
+use modkit_security::SecurityContext;
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct TestConfigProvider {
+    config: serde_json::Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+        if module == "api-gateway" {
+            Some(&self.config)
+        } else {
+            None
+        }
+    }
+}
+
+/// Authenticates every token as a subject holding only the `api:read` scope.
+struct ReadOnlyAuthNClient;
+
+#[async_trait]
+impl AuthNResolverClient for ReadOnlyAuthNClient {
+    async fn authenticate(
+        &self,
+        _bearer_token: &str,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        Ok(AuthenticationResult {
+            security_context: SecurityContext::builder()
+                .subject_id(Uuid::new_v4())
+                .subject_tenant_id(Uuid::new_v4())
+                .token_scopes(vec!["api:read".to_owned()])
+                .build()
+                .unwrap(),
+        })
+    }
+
+    async fn exchange_client_credentials(
+        &self,
+        _request: &ClientCredentialsRequest,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        Err(AuthNResolverError::Internal(
+            "not implemented in mock".to_owned(),
+        ))
+    }
+}
+
+fn create_test_module_ctx(error_format: &str) -> ModuleCtx {
+    let config = serde_json::json!({
+        "config": {
+            "bind_addr": "127.0.0.1:0",
+            "auth_disabled": false,
+            "error_format": error_format,
+            "route_policies": {
+                "enabled": true,
+                "rules": [
+                    { "path": "/tests/v1/forbidden", "required_scopes": ["api:admin"] }
+                ]
+            }
+        }
+    });
+
+    let hub = Arc::new(ClientHub::new());
+    hub.register::<dyn AuthNResolverClient>(Arc::new(ReadOnlyAuthNClient));
+
+    ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        hub,
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    )
+}
+
+pub struct ErrorsModule;
+
+#[async_trait]
+impl Module for ErrorsModule {
+    async fn init(&self, _ctx: &modkit::ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct License;
+
+impl AsRef<str> for License {
+    fn as_ref(&self) -> &'static str {
+        "gts.x.core.lic.feat.v1~x.core.global.base.v1"
+    }
+}
+
+impl LicenseFeature for License {}
+
+impl RestApiCapability for ErrorsModule {
+    fn register_rest(
+        &self,
+        _ctx: &modkit::ModuleCtx,
+        router: axum::Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<axum::Router> {
+        let router = OperationBuilder::get("/tests/v1/forbidden")
+            .operation_id("test:forbidden")
+            .authenticated()
+            .require_license_features::<License>([])
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(get(admin_handler))
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/tests/v1/missing")
+            .operation_id("test:missing")
+            .public()
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(get(not_found_handler))
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/tests/v1/app-error")
+            .operation_id("test:app-error")
+            .public()
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(get(app_error_handler))
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/tests/v1/upstream-error")
+            .operation_id("test:upstream-error")
+            .public()
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(get(upstream_error_handler))
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/tests/v1/large-error")
+            .operation_id("test:large-error")
+            .public()
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(get(large_error_handler))
+            .register(router, openapi);
+
+        Ok(router)
+    }
+}
+
+/// Only reachable with the `api:admin` scope, which the test subject lacks.
+async fn admin_handler() -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "ok": true }))
+}
+
+/// JSON error in a shape the gateway does not know.
+async fn upstream_error_handler() -> impl IntoResponse {
+    (
+        StatusCode::BAD_GATEWAY,
+        Json(serde_json::json!({ "upstream": "billing", "reason": "timeout" })),
+    )
+}
+
+/// A problem too large to buffer for conversion.
+async fn large_error_handler() -> Problem {
+    Problem::new(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Internal Server Error",
+        "x".repeat(LARGE_DETAIL_LEN),
+    )
+}
+
+const LARGE_DETAIL_LEN: usize = 100 * 1024;
+
+async fn not_found_handler() -> Result<Json<serde_json::Value>, Problem> {
+    Err(modkit::api::not_found("User not found"))
+}
+
+async fn app_error_handler() -> Result<Json<serde_json::Value>, api_gateway::error::AppError> {
+    Err(api_gateway::error::AppError::Conflict(
+        "Email already taken".to_owned(),
+    ))
+}
+
+async fn build_router(error_format: &str) -> Router {
+    let api_gateway = api_gateway::ApiGateway::default();
+    let ctx = create_test_module_ctx(error_format);
+    api_gateway.init(&ctx).await.expect("Failed to init");
+
+    let router = ErrorsModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize router")
+}
+
+async fn get_json(router: Router, uri: &str) -> (StatusCode, Option<String>, serde_json::Value) {
+    let resp = router
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("x-request-id", "req-42")
+                .header(header::AUTHORIZATION, "Bearer test-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_owned);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, content_type, serde_json::from_slice(&body).unwrap())
+}
+
+fn assert_canonical_schema(json: &serde_json::Value) {
+    let obj = json.as_object().expect("error body must be an object");
+    let mut keys: Vec<_> = obj.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["code", "details", "message", "request_id"]);
+}
+
+#[tokio::test]
+async fn authorizer_forbidden_serializes_to_canonical_body() {
+    let router = build_router("json").await;
+
+    let (status, content_type, json) = get_json(router, "/tests/v1/forbidden").await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(content_type.as_deref(), Some("application/json"));
+    assert_canonical_schema(&json);
+    assert_eq!(json["code"], "forbidden");
+    assert_eq!(
+        json["message"],
+        "Insufficient token scopes for this resource"
+    );
+    assert_eq!(json["request_id"], "req-42");
+}
+
+#[tokio::test]
+async fn unrecognized_json_error_is_passed_through() {
+    let router = build_router("json").await;
+
+    let (status, content_type, json) = get_json(router, "/tests/v1/upstream-error").await;
+
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert_eq!(content_type.as_deref(), Some("application/json"));
+    assert_eq!(
+        json,
+        serde_json::json!({ "upstream": "billing", "reason": "timeout" })
+    );
+}
+
+#[tokio::test]
+async fn oversized_error_body_is_passed_through() {
+    let router = build_router("json").await;
+
+    let (status, content_type, json) = get_json(router, "/tests/v1/large-error").await;
+
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        content_type.as_deref(),
+        Some(modkit::api::APPLICATION_PROBLEM_JSON)
+    );
+    assert_eq!(
+        json["detail"].as_str().map(str::len),
+        Some(LARGE_DETAIL_LEN)
+    );
+}
+
+#[tokio::test]
+async fn handler_not_found_serializes_to_canonical_body() {
+    let router = build_router("json").await;
+
+    let (status, _, json) = get_json(router, "/tests/v1/missing").await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_canonical_schema(&json);
+    assert_eq!(json["code"], "not_found");
+    assert_eq!(json["message"], "User not found");
+    assert_eq!(json["request_id"], "req-42");
+}
+
+#[tokio::test]
+async fn unmatched_route_and_app_error_get_canonical_body() {
+    let router = build_router("json").await;
+
+    let (status, _, json) = get_json(router.clone(), "/tests/v1/nope").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_canonical_schema(&json);
+    assert_eq!(json["code"], "not_found");
+    assert_eq!(json["request_id"], "req-42");
+
+    let (status, _, json) = get_json(router, "/tests/v1/app-error").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(json["code"], "conflict");
+    assert_eq!(json["message"], "Email already taken");
+    assert_eq!(json["request_id"], "req-42");
+}
+
+#[tokio::test]
+async fn problem_format_is_left_unchanged_by_default() {
+    let router = build_router("problem").await;
+
+    let (status, content_type, json) = get_json(router, "/tests/v1/forbidden").await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(
+        content_type.as_deref(),
+        Some(modkit::api::APPLICATION_PROBLEM_JSON)
+    );
+    assert_eq!(json["title"], "Forbidden");
+}
+
+#[tokio::test]
+async fn normalized_problem_renders_unmatched_route_as_rfc7807() {
+    let router = build_router("normalized_problem").await;
+
+    let (status, content_type, json) = get_json(router, "/tests/v1/nope").await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(
+        content_type.as_deref(),
+        Some(modkit::api::APPLICATION_PROBLEM_JSON)
+    );
+    assert_eq!(json["type"], "about:blank");
+    assert_eq!(json["title"], "Not Found");
+    assert_eq!(json["status"], 404);
+    assert_eq!(json["detail"], "Not Found");
+    assert_eq!(json["instance"], "/tests/v1/nope");
+}
+
+#[tokio::test]
+async fn normalized_problem_converts_app_error_and_keeps_handler_problems() {
+    let router = build_router("normalized_problem").await;
+
+    let (status, content_type, json) = get_json(router.clone(), "/tests/v1/app-error").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(
+        content_type.as_deref(),
+        Some(modkit::api::APPLICATION_PROBLEM_JSON)
+    );
+    assert_eq!(json["status"], 409);
+    assert_eq!(json["title"], "Conflict");
+    assert_eq!(json["detail"], "Email already taken");
+    assert_eq!(json["code"], "conflict");
+    assert_eq!(json["instance"], "/tests/v1/app-error");
+
+    let (status, _, json) = get_json(router, "/tests/v1/missing").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(json["status"], 404);
+    assert_eq!(json["detail"], "User not found");
+    assert_eq!(json["instance"], "/tests/v1/missing");
+}
+
+#[tokio::test]
+async fn normalized_problem_passes_unrecognized_json_through() {
+    let router = build_router("normalized_problem").await;
+
+    let (status, content_type, json) = get_json(router, "/tests/v1/upstream-error").await;
+
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert_eq!(content_type.as_deref(), Some("application/json"));
+    assert_eq!(json["upstream"], "billing");
+}
@@ -0,0 +1,188 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for the fallback route (SPA paths vs. API 404s)
+
+use anyhow::Result;
+use async_trait::async_trait;
+use axum::{
+    Router,
+    body::Body,
+    extract::Json,
+    http::{Request, StatusCode},
+    response::Html,
+    routing::get,
+};
+use modkit::{
+    Module, ModuleCtx, RestApiCapability,
+    api::OperationBuilder,
+    config::ConfigProvider,
+    contracts::{ApiGatewayCapability, OpenApiRegistry},
+};
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const SPA_INDEX: &str = "<!doctype html><div id=\"app\"></div>";
+
+struct TestConfigProvider {
+    config: serde_json::Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+        if module == "api-gateway" {
+            Some(&self.config)
+        } else {
+            None
+        }
+    }
+}
+
+fn create_test_module_ctx(config: serde_json::Value) -> ModuleCtx {
+    let mut base = serde_json::json!({
+        "bind_addr": "127.0.0.1:0",
+        "auth_disabled": true,
+    });
+    if let (Some(base), serde_json::Value::Object(extra)) = (base.as_object_mut(), config) {
+        base.extend(extra);
+    }
+
+    ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider {
+            config: serde_json::json!({ "config": base }),
+        }),
+        Arc::new(modkit::ClientHub::new()),
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    )
+}
+
+pub struct UsersModule;
+
+#[async_trait]
+impl Module for UsersModule {
+    async fn init(&self, _ctx: &modkit::ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl RestApiCapability for UsersModule {
+    fn register_rest(
+        &self,
+        _ctx: &modkit::ModuleCtx,
+        router: axum::Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<axum::Router> {
+        let router = OperationBuilder::get("/api/v1/users")
+            .operation_id("test:list-users")
+            .public()
+            .json_response(http::StatusCode::OK, "Success")
+            .handler(get(|| async { Json(serde_json::json!([])) }))
+            .register(router, openapi);
+
+        Ok(router)
+    }
+}
+
+async fn build_router(config: serde_json::Value) -> Router {
+    let api_gateway = api_gateway::ApiGateway::default();
+    let ctx = create_test_module_ctx(config);
+    api_gateway.init(&ctx).await.expect("Failed to init");
+    api_gateway.set_fallback(api_gateway::FallbackRoute::new(
+        ["/api"],
+        get(|| async { Html(SPA_INDEX) }),
+    ));
+
+    let router = UsersModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize router")
+}
+
+async fn send(router: Router, uri: &str) -> (StatusCode, Option<String>, String) {
+    let resp = router
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_owned);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        content_type,
+        String::from_utf8(body.to_vec()).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn unknown_api_path_returns_json_404() {
+    let router = build_router(serde_json::json!({})).await;
+
+    let (status, content_type, body) = send(router, "/api/unknown").await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(
+        content_type.as_deref(),
+        Some(modkit::api::APPLICATION_PROBLEM_JSON)
+    );
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["status"], 404);
+    assert_eq!(json["instance"], "/api/unknown");
+}
+
+#[tokio::test]
+async fn unknown_app_path_serves_spa_fallback() {
+    let router = build_router(serde_json::json!({})).await;
+
+    let (status, content_type, body) = send(router.clone(), "/app/unknown").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.unwrap().starts_with("text/html"));
+    assert_eq!(body, SPA_INDEX);
+
+    // A shared leading string is not a shared prefix segment
+    let (status, _, body) = send(router.clone(), "/apidocs").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, SPA_INDEX);
+
+    let (status, _, body) = send(router, "/api/v1/users").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "[]");
+}
+
+#[tokio::test]
+async fn fallback_follows_prefix_path_and_error_format() {
+    let router = build_router(serde_json::json!({
+        "prefix_path": "/cf",
+        "error_format": "json",
+    }))
+    .await;
+
+    let (status, content_type, body) = send(router.clone(), "/cf/api/unknown").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(content_type.as_deref(), Some("application/json"));
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["code"], "not_found");
+
+    let (status, _, body) = send(router.clone(), "/cf/api/v1/users").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "[]");
+
+    let (status, _, body) = send(router.clone(), "/cf/app/unknown").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, SPA_INDEX);
+
+    // Outside `prefix_path` there is no API, only the app
+    let (status, _, body) = send(router, "/api/unknown").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, SPA_INDEX);
+}
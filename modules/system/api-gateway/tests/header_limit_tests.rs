@@ -0,0 +1,68 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for request header count and size limits
+
+use axum::{Router, body::Body, routing::get};
+use modkit::api::OperationBuilder;
+use tower::ServiceExt;
+
+fn build_router(max_count: usize, max_bytes: usize) -> Router {
+    let mut config = api_gateway::ApiGatewayConfig {
+        auth_disabled: true,
+        ..Default::default()
+    };
+    config.defaults.header_limit_count = max_count;
+    config.defaults.header_limit_bytes = max_bytes;
+    let gateway = api_gateway::ApiGateway::new(config);
+
+    let routes = OperationBuilder::get("/tests/v1/ping")
+        .operation_id("headers:ping")
+        .public()
+        .json_response(http::StatusCode::OK, "Pong")
+        .handler(get(|| async { "pong" }))
+        .register(Router::new(), &gateway);
+    gateway.add_runtime_routes(routes);
+    gateway.build_router().unwrap()
+}
+
+fn request_with_headers(count: usize, value: &str) -> http::Request<Body> {
+    let mut builder = http::Request::builder().uri("/tests/v1/ping");
+    for i in 0..count {
+        builder = builder.header(format!("x-test-{i}"), value);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn test_normal_request_passes() {
+    let router = build_router(10, 1024);
+
+    let response = router.oneshot(request_with_headers(3, "v")).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_too_many_headers_returns_431() {
+    let router = build_router(10, 0);
+
+    let response = router.oneshot(request_with_headers(11, "v")).await.unwrap();
+    assert_eq!(
+        response.status(),
+        http::StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE
+    );
+}
+
+#[tokio::test]
+async fn test_oversized_headers_return_431() {
+    let router = build_router(0, 1024);
+
+    let value = "v".repeat(2048);
+    let response = router
+        .oneshot(request_with_headers(1, &value))
+        .await
+        .unwrap();
+    assert_eq!(
+        response.status(),
+        http::StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE
+    );
+}
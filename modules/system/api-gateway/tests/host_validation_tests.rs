@@ -0,0 +1,101 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for the `Host` / `X-Forwarded-Host` allow-list
+
+use axum::{Router, body::Body, routing::get};
+use modkit::api::OperationBuilder;
+use tower::ServiceExt;
+
+fn build_router(allowed_hosts: &[&str]) -> Router {
+    let config = api_gateway::ApiGatewayConfig {
+        auth_disabled: true,
+        allowed_hosts: allowed_hosts.iter().map(|h| (*h).to_owned()).collect(),
+        ..Default::default()
+    };
+    let gateway = api_gateway::ApiGateway::new(config);
+
+    let routes = OperationBuilder::get("/tests/v1/ping")
+        .operation_id("hosts:ping")
+        .public()
+        .json_response(http::StatusCode::OK, "Pong")
+        .handler(get(|| async { "pong" }))
+        .register(Router::new(), &gateway);
+    gateway.add_runtime_routes(routes);
+    gateway.build_router().unwrap()
+}
+
+fn request(host: &str, forwarded_host: Option<&str>) -> http::Request<Body> {
+    let mut builder = http::Request::builder()
+        .uri("/tests/v1/ping")
+        .header(http::header::HOST, host);
+    if let Some(forwarded_host) = forwarded_host {
+        builder = builder.header("x-forwarded-host", forwarded_host);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+async fn status(router: &Router, req: http::Request<Body>) -> http::StatusCode {
+    router.clone().oneshot(req).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_allowed_host_passes() {
+    let router = build_router(&["api.example.com"]);
+
+    assert_eq!(
+        status(&router, request("api.example.com:8080", None)).await,
+        http::StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn test_unexpected_host_returns_400() {
+    let router = build_router(&["api.example.com"]);
+
+    assert_eq!(
+        status(&router, request("evil.example.net", None)).await,
+        http::StatusCode::BAD_REQUEST
+    );
+}
+
+#[tokio::test]
+async fn test_unexpected_forwarded_host_returns_400() {
+    let router = build_router(&["api.example.com"]);
+
+    assert_eq!(
+        status(
+            &router,
+            request("api.example.com", Some("evil.example.net"))
+        )
+        .await,
+        http::StatusCode::BAD_REQUEST
+    );
+}
+
+#[tokio::test]
+async fn test_wildcard_allows_subdomains() {
+    let router = build_router(&["*.example.com"]);
+
+    assert_eq!(
+        status(
+            &router,
+            request("eu.api.example.com", Some("api.example.com"))
+        )
+        .await,
+        http::StatusCode::OK
+    );
+    assert_eq!(
+        status(&router, request("example.com", None)).await,
+        http::StatusCode::BAD_REQUEST
+    );
+}
+
+#[tokio::test]
+async fn test_empty_allow_list_accepts_any_host() {
+    let router = build_router(&[]);
+
+    assert_eq!(
+        status(&router, request("anything.test", None)).await,
+        http::StatusCode::OK
+    );
+}
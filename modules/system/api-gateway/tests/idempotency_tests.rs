@@ -0,0 +1,139 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use axum::{
+    Router,
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    middleware::{Next, from_fn, from_fn_with_state},
+    response::{IntoResponse, Response},
+    routing::post,
+};
+use modkit_security::SecurityContext;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+use api_gateway::IdempotencyConfig;
+use api_gateway::middleware::idempotency::{
+    IDEMPOTENCY_KEY, IDEMPOTENT_REPLAYED, IdempotencyStore, idempotency_middleware,
+};
+
+/// Stand-in for the auth middleware: the subject comes from `x-subject`.
+async fn fake_auth(mut req: Request<Body>, next: Next) -> Response {
+    let subject = req
+        .headers()
+        .get("x-subject")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.parse::<Uuid>().ok())
+        .unwrap_or_default();
+    let ctx = SecurityContext::builder()
+        .subject_id(subject)
+        .subject_tenant_id(Uuid::from_u128(100))
+        .build()
+        .unwrap();
+    req.extensions_mut().insert(ctx);
+    next.run(req).await
+}
+
+async fn create_order(State(calls): State<Arc<AtomicUsize>>) -> impl IntoResponse {
+    let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+    (StatusCode::CREATED, format!(r#"{{"order":{n}}}"#))
+}
+
+fn test_app(calls: Arc<AtomicUsize>) -> Router {
+    let store = IdempotencyStore::from_config(&IdempotencyConfig {
+        enabled: true,
+        ..IdempotencyConfig::default()
+    });
+
+    Router::new()
+        .route("/orders", post(create_order))
+        .with_state(calls)
+        .layer(from_fn_with_state(store, idempotency_middleware))
+        .layer(from_fn(fake_auth))
+}
+
+fn post_order(key: Option<&str>, subject: u128) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri("/orders")
+        .header("x-subject", Uuid::from_u128(subject).to_string());
+    if let Some(key) = key {
+        builder = builder.header(IDEMPOTENCY_KEY, key);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+async fn send(app: &Router, req: Request<Body>) -> (StatusCode, Option<String>, String) {
+    let response = app.clone().oneshot(req).await.unwrap();
+    let status = response.status();
+    let replayed = response
+        .headers()
+        .get(IDEMPOTENT_REPLAYED)
+        .map(|v| v.to_str().unwrap().to_owned());
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, replayed, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn same_key_invokes_handler_once_and_replays_response() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let app = test_app(calls.clone());
+
+    let first = send(&app, post_order(Some("key-1"), 1)).await;
+    let second = send(&app, post_order(Some("key-1"), 1)).await;
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1, "handler must run once");
+    assert_eq!(
+        first,
+        (StatusCode::CREATED, None, r#"{"order":1}"#.to_owned())
+    );
+    assert_eq!(second.0, first.0);
+    assert_eq!(second.1.as_deref(), Some("true"));
+    assert_eq!(second.2, first.2);
+}
+
+#[tokio::test]
+async fn keys_are_scoped_to_subject_and_value() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let app = test_app(calls.clone());
+
+    send(&app, post_order(Some("key-1"), 1)).await;
+    let other_subject = send(&app, post_order(Some("key-1"), 2)).await;
+    let other_key = send(&app, post_order(Some("key-2"), 1)).await;
+
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert_eq!(other_subject.1, None);
+    assert_eq!(other_key.1, None);
+}
+
+#[tokio::test]
+async fn requests_without_key_or_subject_are_not_replayed() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let app = test_app(calls.clone());
+
+    send(&app, post_order(None, 1)).await;
+    send(&app, post_order(None, 1)).await;
+    // Anonymous (nil subject)
+    send(&app, post_order(Some("key-1"), 0)).await;
+    send(&app, post_order(Some("key-1"), 0)).await;
+
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn invalid_key_is_rejected() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let app = test_app(calls.clone());
+
+    let long_key = "k".repeat(256);
+    let (status, _, _) = send(&app, post_order(Some(&long_key), 1)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
@@ -205,3 +205,173 @@ async fn test_schema_registration() {
     // This test would verify that schemas were registered, but since the
     // schema registry is internal, we just verify no compilation errors
 }
+
+async fn late_handler() -> Json<serde_json::Value> {
+    Json(serde_json::json!({"late": true}))
+}
+
+async fn get_status(router: Router, uri: &str) -> http::StatusCode {
+    use tower::ServiceExt;
+
+    router
+        .oneshot(
+            http::Request::builder()
+                .uri(uri)
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn test_runtime_route_reachable_after_cache_invalidation() {
+    use modkit::api::OperationBuilder;
+
+    let gateway = api_gateway::ApiGateway::new(api_gateway::ApiGatewayConfig {
+        auth_disabled: true,
+        ..Default::default()
+    });
+
+    // First use: router gets built and cached; hold it like a running server would
+    let first = gateway.build_router().unwrap();
+    let held = gateway.get_cached_router();
+    assert_eq!(
+        get_status(first.clone(), "/tests/v1/late").await,
+        http::StatusCode::NOT_FOUND
+    );
+
+    // Without changes, the cached instance is reused
+    let _cached = gateway.build_router().unwrap();
+    assert!(Arc::ptr_eq(&held, &gateway.get_cached_router()));
+
+    // A plugin registers a new endpoint at runtime
+    let routes = OperationBuilder::get("/tests/v1/late")
+        .operation_id("late:get")
+        .public()
+        .json_response(http::StatusCode::OK, "Late route")
+        .handler(get(late_handler))
+        .register(Router::new(), &gateway);
+    gateway.add_runtime_routes(routes);
+
+    let rebuilt = gateway.build_router().unwrap();
+    assert_eq!(
+        get_status(rebuilt, "/tests/v1/late").await,
+        http::StatusCode::OK
+    );
+
+    // The stale router is no longer cached, but requests already holding it
+    // keep a consistent (old) view
+    assert!(!Arc::ptr_eq(&held, &gateway.get_cached_router()));
+    assert_eq!(
+        get_status(first, "/tests/v1/late").await,
+        http::StatusCode::NOT_FOUND
+    );
+}
+
+async fn send(router: Router, method: http::Method, uri: &str) -> axum::response::Response {
+    use tower::ServiceExt;
+
+    router
+        .oneshot(
+            http::Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_method_not_allowed_lists_allowed_methods() {
+    use modkit::api::OperationBuilder;
+
+    let gateway = api_gateway::ApiGateway::new(api_gateway::ApiGatewayConfig {
+        auth_disabled: true,
+        ..Default::default()
+    });
+
+    let routes = OperationBuilder::get("/tests/v1/users")
+        .operation_id("users:list")
+        .public()
+        .json_response(http::StatusCode::OK, "Users")
+        .handler(get(list_users_handler))
+        .register(Router::new(), &gateway);
+    let routes = OperationBuilder::post("/tests/v1/users")
+        .operation_id("users:create")
+        .public()
+        .json_response(http::StatusCode::CREATED, "User created")
+        .handler(axum::routing::post(create_user_handler))
+        .register(routes, &gateway);
+    gateway.add_runtime_routes(routes);
+    let app = gateway.build_router().unwrap();
+
+    let response = send(app.clone(), http::Method::DELETE, "/tests/v1/users").await;
+    assert_eq!(response.status(), http::StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(
+        response.headers().get(http::header::ALLOW).unwrap(),
+        "GET, POST"
+    );
+
+    let response = send(app, http::Method::DELETE, "/tests/v1/unknown").await;
+    assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+}
+
+fn users_router(trailing_slash: api_gateway::TrailingSlashPolicy) -> Router {
+    use modkit::api::OperationBuilder;
+
+    let gateway = api_gateway::ApiGateway::new(api_gateway::ApiGatewayConfig {
+        auth_disabled: true,
+        trailing_slash,
+        ..Default::default()
+    });
+
+    let routes = OperationBuilder::get("/tests/v1/users")
+        .operation_id("users:list")
+        .public()
+        .json_response(http::StatusCode::OK, "Users")
+        .handler(get(list_users_handler))
+        .register(Router::new(), &gateway);
+    gateway.add_runtime_routes(routes);
+    gateway.build_router().unwrap()
+}
+
+#[tokio::test]
+async fn test_trailing_slash_strict_keeps_slash_distinct() {
+    let app = users_router(api_gateway::TrailingSlashPolicy::Strict);
+
+    let response = send(app.clone(), http::Method::GET, "/tests/v1/users").await;
+    assert_eq!(response.status(), http::StatusCode::OK);
+
+    let response = send(app, http::Method::GET, "/tests/v1/users/").await;
+    assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_trailing_slash_redirects_to_canonical_with_query() {
+    let app = users_router(api_gateway::TrailingSlashPolicy::RedirectToCanonical);
+
+    let response = send(app.clone(), http::Method::GET, "/tests/v1/users/?limit=5").await;
+    assert_eq!(response.status(), http::StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(
+        response.headers().get(http::header::LOCATION).unwrap(),
+        "/tests/v1/users?limit=5"
+    );
+
+    let response = send(app, http::Method::GET, "/tests/v1/users").await;
+    assert_eq!(response.status(), http::StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_trailing_slash_ignored_serves_canonical_route() {
+    let app = users_router(api_gateway::TrailingSlashPolicy::Ignore);
+
+    let response = send(app.clone(), http::Method::GET, "/tests/v1/users/").await;
+    assert_eq!(response.status(), http::StatusCode::OK);
+
+    let response = send(app, http::Method::GET, "/tests/v1/users").await;
+    assert_eq!(response.status(), http::StatusCode::OK);
+}
@@ -51,6 +51,7 @@ async fn test_middleware_allows_configured_content_type() {
         handler_id: "test".to_owned(),
         authenticated: false,
         is_public: true,
+        optional_auth: false,
         license_requirement: None,
         rate_limit: None,
         allowed_request_content_types: Some(vec!["application/json"]),
@@ -96,6 +97,7 @@ async fn test_middleware_strips_content_type_parameters() {
         handler_id: "test".to_owned(),
         authenticated: false,
         is_public: true,
+        optional_auth: false,
         license_requirement: None,
         rate_limit: None,
         allowed_request_content_types: Some(vec!["application/json"]),
@@ -141,6 +143,7 @@ async fn test_middleware_rejects_disallowed_content_type() {
         handler_id: "test".to_owned(),
         authenticated: false,
         is_public: true,
+        optional_auth: false,
         license_requirement: None,
         rate_limit: None,
         allowed_request_content_types: Some(vec!["application/json"]),
@@ -192,6 +195,7 @@ async fn test_middleware_rejects_missing_content_type() {
         handler_id: "test".to_owned(),
         authenticated: false,
         is_public: true,
+        optional_auth: false,
         license_requirement: None,
         rate_limit: None,
         allowed_request_content_types: Some(vec!["multipart/form-data"]),
@@ -267,6 +271,7 @@ async fn test_middleware_allows_multiple_content_types() {
         handler_id: "test".to_owned(),
         authenticated: false,
         is_public: true,
+        optional_auth: false,
         license_requirement: None,
         rate_limit: None,
         allowed_request_content_types: Some(vec![
@@ -60,9 +60,13 @@ impl Service {
                 tracing::warn!(
                     plugin_gts_id = %instance_id,
                     vendor = %self.vendor,
+                    suppressed = self.unavailable_log_throttle.take_suppressed(),
                     "Plugin client not registered yet"
                 );
             }
+            // Re-resolve next time in case a different instance has since
+            // registered, unless another caller already has
+            self.selector.invalidate_if_selected(&instance_id);
             Err(DomainError::PluginUnavailable {
                 gts_id: instance_id.to_string(),
                 reason: "client not registered yet".into(),
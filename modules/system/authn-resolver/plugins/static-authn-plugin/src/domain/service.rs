@@ -6,6 +6,7 @@ use std::collections::HashMap;
 use modkit_macros::domain_model;
 use modkit_security::SecurityContext;
 use secrecy::{ExposeSecret, SecretString};
+use subtle::ConstantTimeEq;
 
 use crate::config::{AuthNMode, IdentityConfig, StaticAuthNPluginConfig};
 use authn_resolver_sdk::{AuthenticationResult, ClientCredentialsRequest};
@@ -93,7 +94,14 @@ impl Service {
         request: &ClientCredentialsRequest,
     ) -> Option<AuthenticationResult> {
         let entry = self.s2s_credentials.get(&request.client_id)?;
-        if entry.client_secret.expose_secret() != request.client_secret.expose_secret() {
+        // Constant-time so a mismatch does not leak the matching prefix length
+        let matches: bool = entry
+            .client_secret
+            .expose_secret()
+            .as_bytes()
+            .ct_eq(request.client_secret.expose_secret().as_bytes())
+            .into();
+        if !matches {
             return None;
         }
         build_result(&entry.identity, None)
@@ -43,6 +43,49 @@ pub enum EnforcerError {
     CompileFailed(#[from] ConstraintCompileError),
 }
 
+impl EnforcerError {
+    /// Why access was denied, or `None` if the PDP could not be evaluated.
+    ///
+    /// All deny kinds are authorization failures (HTTP 403); `EvaluationFailed`
+    /// is an infrastructure failure and has no deny kind.
+    #[must_use]
+    pub fn deny_kind(&self) -> Option<DenyKind> {
+        match self {
+            Self::Denied { .. } => Some(DenyKind::PdpDenied),
+            Self::CompileFailed(ConstraintCompileError::ConstraintsRequiredButAbsent) => {
+                Some(DenyKind::ConstraintsRequiredButAbsent)
+            }
+            Self::CompileFailed(ConstraintCompileError::AllConstraintsFailed { .. }) => {
+                Some(DenyKind::UnsupportedConstraints)
+            }
+            Self::EvaluationFailed(_) => None,
+        }
+    }
+}
+
+/// Machine-readable reason the PEP denied access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DenyKind {
+    /// The PDP returned `decision: false`.
+    PdpDenied,
+    /// Constraints were required but the PDP returned none.
+    ConstraintsRequiredButAbsent,
+    /// Every constraint returned by the PDP failed to compile.
+    UnsupportedConstraints,
+}
+
+impl DenyKind {
+    /// Stable reason code, suitable for API error bodies.
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::PdpDenied => "PDP_DENIED",
+            Self::ConstraintsRequiredButAbsent => "CONSTRAINTS_REQUIRED_BUT_ABSENT",
+            Self::UnsupportedConstraints => "UNSUPPORTED_CONSTRAINTS",
+        }
+    }
+}
+
 /// Per-request evaluation parameters for advanced authorization scenarios.
 ///
 /// Used with [`PolicyEnforcer::access_scope_with()`] when the simple
@@ -293,6 +293,51 @@ async fn access_scope_anonymous_denied_by_pdp() {
     ));
 }
 
+/// Mock that grants access but returns no constraints.
+struct NoConstraintsMock;
+
+#[async_trait]
+impl AuthZResolverClient for NoConstraintsMock {
+    async fn evaluate(
+        &self,
+        _req: EvaluationRequest,
+    ) -> Result<EvaluationResponse, AuthZResolverError> {
+        Ok(EvaluationResponse {
+            decision: true,
+            context: EvaluationResponseContext::default(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn deny_kinds_distinguish_pdp_deny_from_missing_constraints() {
+    let ctx = test_ctx();
+
+    let denied = enforcer(DenyMock::new())
+        .access_scope(&ctx, &TEST_RESOURCE, "get", None)
+        .await
+        .unwrap_err();
+    let absent = enforcer(NoConstraintsMock)
+        .access_scope(&ctx, &TEST_RESOURCE, "get", None)
+        .await
+        .unwrap_err();
+    let failed = enforcer(FailMock)
+        .access_scope(&ctx, &TEST_RESOURCE, "get", None)
+        .await
+        .unwrap_err();
+
+    assert_eq!(denied.deny_kind(), Some(DenyKind::PdpDenied));
+    assert_eq!(
+        absent.deny_kind(),
+        Some(DenyKind::ConstraintsRequiredButAbsent)
+    );
+    assert_ne!(
+        DenyKind::PdpDenied.code(),
+        DenyKind::ConstraintsRequiredButAbsent.code()
+    );
+    assert_eq!(failed.deny_kind(), None);
+}
+
 // ── builder methods ──────────────────────────────────────────────
 
 #[test]
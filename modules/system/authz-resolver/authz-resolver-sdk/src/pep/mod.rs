@@ -12,7 +12,7 @@ pub mod compiler;
 pub mod enforcer;
 
 pub use compiler::{ConstraintCompileError, compile_to_access_scope};
-pub use enforcer::{AccessRequest, EnforcerError, PolicyEnforcer, ResourceType};
+pub use enforcer::{AccessRequest, DenyKind, EnforcerError, PolicyEnforcer, ResourceType};
 
 /// Trait for types that can be converted into `serde_json::Value` for PDP
 /// evaluation requests and predicate construction.
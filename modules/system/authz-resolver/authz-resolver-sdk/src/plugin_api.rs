@@ -20,4 +20,13 @@ pub trait AuthZResolverPluginClient: Send + Sync {
         &self,
         request: EvaluationRequest,
     ) -> Result<EvaluationResponse, AuthZResolverError>;
+
+    /// Revision of the policy and config the plugin currently evaluates with.
+    ///
+    /// Plugins that reload at runtime bump it on every reload; the resolver
+    /// drops its cached decisions when it changes. Static plugins keep the
+    /// default.
+    fn policy_revision(&self) -> u64 {
+        0
+    }
 }
@@ -1,5 +1,7 @@
 //! Configuration for the `AuthZ` resolver.
 
+use std::time::Duration;
+
 use serde::Deserialize;
 
 /// Configuration.
@@ -8,12 +10,22 @@ use serde::Deserialize;
 pub struct AuthZResolverConfig {
     /// Vendor selector used to pick a plugin implementation.
     pub vendor: String,
+
+    /// How long a decision is reused for an identical evaluation request
+    /// (e.g. `"2s"`). Disabled when unset.
+    #[serde(with = "modkit_utils::humantime_serde::option")]
+    pub decision_cache_ttl: Option<Duration>,
+
+    /// Maximum number of cached decisions; the oldest are evicted first.
+    pub decision_cache_capacity: usize,
 }
 
 impl Default for AuthZResolverConfig {
     fn default() -> Self {
         Self {
             vendor: "hyperspot".to_owned(),
+            decision_cache_ttl: None,
+            decision_cache_capacity: 10_000,
         }
     }
 }
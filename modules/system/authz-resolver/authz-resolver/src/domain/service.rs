@@ -1,7 +1,8 @@
 //! Domain service for the `AuthZ` resolver.
 
-use std::sync::Arc;
-use std::time::Duration;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
 
 use authz_resolver_sdk::{
     AuthZResolverPluginClient, AuthZResolverPluginSpecV1, EvaluationRequest, EvaluationResponse,
@@ -18,6 +19,135 @@ use super::error::DomainError;
 /// Throttle interval for unavailable plugin warnings.
 const UNAVAILABLE_LOG_THROTTLE: Duration = Duration::from_secs(10);
 
+/// Short-lived cache of plugin decisions, allow and deny alike.
+#[domain_model]
+struct DecisionCache {
+    ttl: Duration,
+    capacity: usize,
+    state: Mutex<DecisionCacheState>,
+}
+
+#[domain_model]
+#[derive(Default)]
+struct DecisionCacheState {
+    /// Keyed by the canonical request, so distinct requests never share an entry.
+    entries: HashMap<String, (Instant, EvaluationResponse)>,
+    /// Keys in insertion order; with a single TTL this is also expiry order.
+    /// A key re-inserted after expiry appears twice; the stale copy is
+    /// recognized by its instant and skipped.
+    order: VecDeque<(Instant, String)>,
+    /// Plugin policy revision the entries were evaluated under.
+    revision: u64,
+}
+
+impl DecisionCache {
+    fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity: capacity.max(1),
+            state: Mutex::new(DecisionCacheState::default()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, DecisionCacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Cached decision for `key`, dropping everything first if the plugin
+    /// reloaded since the entries were evaluated.
+    fn get(&self, key: &str, revision: u64) -> Option<EvaluationResponse> {
+        let mut state = self.lock();
+        if state.revision != revision {
+            state.entries.clear();
+            state.order.clear();
+            state.revision = revision;
+            return None;
+        }
+        state
+            .entries
+            .get(key)
+            .filter(|(evaluated_at, _)| evaluated_at.elapsed() < self.ttl)
+            .map(|(_, response)| response.clone())
+    }
+
+    fn insert(&self, key: String, revision: u64, response: EvaluationResponse) {
+        let mut state = self.lock();
+        if state.revision != revision {
+            // Evaluated under a newer (or older) policy than the cached entries
+            return;
+        }
+        let now = Instant::now();
+        state.order.push_back((now, key.clone()));
+        state.entries.insert(key, (now, response));
+
+        // Pop expired entries, then the oldest ones beyond capacity
+        while let Some((inserted_at, key)) = state.order.front() {
+            let expired = inserted_at.elapsed() >= self.ttl;
+            if !expired && state.entries.len() <= self.capacity {
+                break;
+            }
+            let (inserted_at, key) = (*inserted_at, key.clone());
+            state.order.pop_front();
+            if state
+                .entries
+                .get(&key)
+                .is_some_and(|(evaluated_at, _)| *evaluated_at == inserted_at)
+            {
+                state.entries.remove(&key);
+            }
+        }
+    }
+
+    fn clear(&self) {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.order.clear();
+    }
+}
+
+/// Cache key for a request: subject, action, resource and evaluation context.
+///
+/// The request serialized as JSON with object keys sorted, so that property
+/// maps built in a different order yield the same key. The context is part of
+/// the key because it shapes the returned constraints. The bearer token is
+/// not serialized and therefore not part of the key.
+fn decision_key(request: &EvaluationRequest) -> Result<String, DomainError> {
+    let mut key = String::new();
+    write_canonical(&serde_json::to_value(request)?, &mut key);
+    Ok(key)
+}
+
+fn write_canonical(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort_unstable();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                // Serializing a string cannot fail
+                out.push_str(&serde_json::Value::from(key.as_str()).to_string());
+                out.push(':');
+                write_canonical(&map[key], out);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
 /// `AuthZ` resolver service.
 #[domain_model]
 pub struct Service {
@@ -25,6 +155,8 @@ pub struct Service {
     vendor: String,
     selector: GtsPluginSelector,
     unavailable_log_throttle: ThrottledLog,
+    /// Optional memoization of plugin decisions.
+    decision_cache: Option<DecisionCache>,
 }
 
 impl Service {
@@ -35,6 +167,29 @@ impl Service {
             vendor,
             selector: GtsPluginSelector::new(),
             unavailable_log_throttle: ThrottledLog::new(UNAVAILABLE_LOG_THROTTLE),
+            decision_cache: None,
+        }
+    }
+
+    /// Reuse plugin decisions for identical requests for `ttl`, keeping at
+    /// most `capacity` of them (the oldest are evicted first).
+    ///
+    /// Deny decisions are cached as well; plugin errors never are. Cached
+    /// decisions are dropped when the plugin reports a new
+    /// [`policy_revision`](AuthZResolverPluginClient::policy_revision).
+    #[must_use]
+    pub fn with_decision_cache(mut self, ttl: Duration, capacity: usize) -> Self {
+        self.decision_cache = Some(DecisionCache::new(ttl, capacity));
+        self
+    }
+
+    /// Drop all cached decisions.
+    ///
+    /// Done automatically whenever the plugin instance is re-resolved or
+    /// reports a new policy revision.
+    pub fn invalidate_decisions(&self) {
+        if let Some(cache) = &self.decision_cache {
+            cache.clear();
         }
     }
 
@@ -52,9 +207,15 @@ impl Service {
                 tracing::warn!(
                     plugin_gts_id = %instance_id,
                     vendor = %self.vendor,
+                    suppressed = self.unavailable_log_throttle.take_suppressed(),
                     "Plugin client not registered yet"
                 );
             }
+            // Re-resolve next time in case a different instance has since
+            // registered, unless another caller already has
+            if self.selector.invalidate_if_selected(&instance_id) {
+                self.invalidate_decisions();
+            }
             Err(DomainError::PluginUnavailable {
                 gts_id: instance_id.to_string(),
                 reason: "client not registered yet".into(),
@@ -102,6 +263,21 @@ impl Service {
         request: EvaluationRequest,
     ) -> Result<EvaluationResponse, DomainError> {
         let plugin = self.get_plugin().await?;
-        plugin.evaluate(request).await.map_err(DomainError::from)
+        let Some(cache) = &self.decision_cache else {
+            return plugin.evaluate(request).await.map_err(DomainError::from);
+        };
+
+        let key = decision_key(&request)?;
+        let revision = plugin.policy_revision();
+        if let Some(response) = cache.get(&key, revision) {
+            return Ok(response);
+        }
+        let response = plugin.evaluate(request).await?;
+        cache.insert(key, revision, response.clone());
+        Ok(response)
     }
 }
+
+#[cfg(test)]
+#[path = "service_tests.rs"]
+mod service_tests;
@@ -0,0 +1,259 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+use authz_resolver_sdk::{
+    Action, AuthZResolverError, EvaluationRequestContext, EvaluationResponseContext, Resource,
+    Subject,
+};
+use modkit::client_hub::{ClientHub, ClientScope};
+use types_registry_sdk::{GtsEntity, RegisterResult, TypesRegistryError};
+use uuid::Uuid;
+
+use super::*;
+
+// ── mocks ────────────────────────────────────────────────────────────────
+
+struct MockRegistry {
+    instances: Vec<GtsEntity>,
+}
+
+#[async_trait]
+impl TypesRegistryClient for MockRegistry {
+    async fn list(&self, _query: ListQuery) -> Result<Vec<GtsEntity>, TypesRegistryError> {
+        Ok(self.instances.clone())
+    }
+
+    async fn get(&self, gts_id: &str) -> Result<GtsEntity, TypesRegistryError> {
+        self.instances
+            .iter()
+            .find(|e| e.gts_id == gts_id)
+            .cloned()
+            .ok_or_else(|| TypesRegistryError::not_found(gts_id))
+    }
+
+    async fn register(
+        &self,
+        _entities: Vec<serde_json::Value>,
+    ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+        Ok(vec![])
+    }
+
+    async fn register_atomic(
+        &self,
+        _entities: Vec<serde_json::Value>,
+    ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+        Ok(vec![])
+    }
+}
+
+/// PDP allowing every action but `delete`, counting evaluations.
+///
+/// `allow_delete` stands in for a policy reload flipping that decision;
+/// a reload that is reported also bumps `revision`.
+#[derive(Default)]
+struct CountingPlugin {
+    allow_delete: AtomicBool,
+    calls: AtomicUsize,
+    revision: AtomicU64,
+}
+
+#[async_trait]
+impl AuthZResolverPluginClient for CountingPlugin {
+    async fn evaluate(
+        &self,
+        request: EvaluationRequest,
+    ) -> Result<EvaluationResponse, AuthZResolverError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(EvaluationResponse {
+            decision: request.action.name != "delete" || self.allow_delete.load(Ordering::SeqCst),
+            context: EvaluationResponseContext::default(),
+        })
+    }
+
+    fn policy_revision(&self) -> u64 {
+        self.revision.load(Ordering::SeqCst)
+    }
+}
+
+// ── helpers ──────────────────────────────────────────────────────────────
+
+fn wired_hub() -> (Arc<ClientHub>, Arc<CountingPlugin>) {
+    let gts_id = format!(
+        "{}test._.mock.v1",
+        AuthZResolverPluginSpecV1::gts_schema_id()
+    );
+    let entity = GtsEntity {
+        id: Uuid::nil(),
+        gts_id: gts_id.clone(),
+        segments: vec![],
+        is_schema: false,
+        content: serde_json::json!({
+            "id": gts_id,
+            "vendor": "hyperspot",
+            "priority": 0,
+            "properties": {}
+        }),
+        description: None,
+    };
+    let hub = Arc::new(ClientHub::default());
+    hub.register::<dyn TypesRegistryClient>(Arc::new(MockRegistry {
+        instances: vec![entity],
+    }) as Arc<dyn TypesRegistryClient>);
+
+    let plugin = Arc::new(CountingPlugin::default());
+    hub.register_scoped::<dyn AuthZResolverPluginClient>(
+        ClientScope::gts_id(&gts_id),
+        plugin.clone() as Arc<dyn AuthZResolverPluginClient>,
+    );
+
+    (hub, plugin)
+}
+
+fn request(action: &str, properties: &[(&str, &str)]) -> EvaluationRequest {
+    EvaluationRequest {
+        subject: Subject {
+            id: Uuid::from_u128(1),
+            subject_type: Some("user".to_owned()),
+            properties: HashMap::new(),
+        },
+        action: Action {
+            name: action.to_owned(),
+        },
+        resource: Resource {
+            resource_type: "gts.x.core.users.user.v1~".to_owned(),
+            id: None,
+            properties: properties
+                .iter()
+                .map(|(k, v)| ((*k).to_owned(), serde_json::json!(v)))
+                .collect(),
+        },
+        context: EvaluationRequestContext {
+            tenant_context: None,
+            token_scopes: vec![],
+            require_constraints: false,
+            capabilities: vec![],
+            supported_properties: vec![],
+            bearer_token: None,
+        },
+    }
+}
+
+fn cached_service(hub: Arc<ClientHub>) -> Service {
+    Service::new(hub, "hyperspot".to_owned()).with_decision_cache(Duration::from_mins(1), 100)
+}
+
+// ── decision cache ───────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn repeated_identical_request_hits_cache() {
+    let (hub, plugin) = wired_hub();
+    let svc = cached_service(hub);
+
+    for _ in 0..5 {
+        assert!(svc.evaluate(request("get", &[])).await.unwrap().decision);
+        assert!(!svc.evaluate(request("delete", &[])).await.unwrap().decision);
+    }
+
+    assert_eq!(
+        plugin.calls.load(Ordering::SeqCst),
+        2,
+        "allow and deny are each evaluated once"
+    );
+}
+
+#[tokio::test]
+async fn resource_attributes_are_part_of_the_key() {
+    let (hub, plugin) = wired_hub();
+    let svc = cached_service(hub);
+    let props = [("owner", "a"), ("region", "eu"), ("tier", "gold")];
+    let mut reversed = props;
+    reversed.reverse();
+
+    svc.evaluate(request("get", &props)).await.unwrap();
+    svc.evaluate(request("get", &reversed)).await.unwrap();
+    assert_eq!(
+        plugin.calls.load(Ordering::SeqCst),
+        1,
+        "order is irrelevant"
+    );
+
+    svc.evaluate(request("get", &[("owner", "b")]))
+        .await
+        .unwrap();
+    assert_eq!(plugin.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn reported_reload_drops_cached_decisions() {
+    let (hub, plugin) = wired_hub();
+    let svc = cached_service(hub);
+
+    assert!(!svc.evaluate(request("delete", &[])).await.unwrap().decision);
+
+    // An unreported policy change is masked by the cached deny
+    plugin.allow_delete.store(true, Ordering::SeqCst);
+    assert!(!svc.evaluate(request("delete", &[])).await.unwrap().decision);
+
+    // Once the plugin reports the reload, the next request is re-evaluated
+    plugin.revision.fetch_add(1, Ordering::SeqCst);
+    assert!(svc.evaluate(request("delete", &[])).await.unwrap().decision);
+    assert!(svc.evaluate(request("delete", &[])).await.unwrap().decision);
+    assert_eq!(plugin.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn invalidate_decisions_drops_cached_decisions() {
+    let (hub, plugin) = wired_hub();
+    let svc = cached_service(hub);
+
+    assert!(!svc.evaluate(request("delete", &[])).await.unwrap().decision);
+    plugin.allow_delete.store(true, Ordering::SeqCst);
+
+    svc.invalidate_decisions();
+    assert!(svc.evaluate(request("delete", &[])).await.unwrap().decision);
+    assert_eq!(plugin.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn cache_evicts_oldest_beyond_capacity() {
+    let (hub, plugin) = wired_hub();
+    let svc =
+        Service::new(hub, "hyperspot".to_owned()).with_decision_cache(Duration::from_mins(1), 2);
+
+    for owner in ["a", "b", "c"] {
+        svc.evaluate(request("get", &[("owner", owner)]))
+            .await
+            .unwrap();
+    }
+    assert_eq!(plugin.calls.load(Ordering::SeqCst), 3);
+
+    // "b" and "c" are still cached, "a" was evicted
+    svc.evaluate(request("get", &[("owner", "c")]))
+        .await
+        .unwrap();
+    svc.evaluate(request("get", &[("owner", "b")]))
+        .await
+        .unwrap();
+    assert_eq!(plugin.calls.load(Ordering::SeqCst), 3);
+    svc.evaluate(request("get", &[("owner", "a")]))
+        .await
+        .unwrap();
+    assert_eq!(plugin.calls.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn decision_key_is_the_canonical_request() {
+    let key = decision_key(&request("get", &[("b", "2"), ("a", "1")])).unwrap();
+    assert!(key.contains(r#""properties":{"a":"1","b":"2"}"#), "{key}");
+}
+
+#[tokio::test]
+async fn without_cache_every_request_reaches_plugin() {
+    let (hub, plugin) = wired_hub();
+    let svc = Service::new(hub, "hyperspot".to_owned());
+
+    for _ in 0..3 {
+        svc.evaluate(request("get", &[])).await.unwrap();
+    }
+    assert_eq!(plugin.calls.load(Ordering::SeqCst), 3);
+}
@@ -75,7 +75,11 @@ impl Module for AuthZResolver {
 
         // Create service
         let hub = ctx.client_hub();
-        let svc = Arc::new(Service::new(hub, cfg.vendor));
+        let mut svc = Service::new(hub, cfg.vendor);
+        if let Some(ttl) = cfg.decision_cache_ttl {
+            svc = svc.with_decision_cache(ttl, cfg.decision_cache_capacity);
+        }
+        let svc = Arc::new(svc);
         self.service
             .set(svc.clone())
             .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
@@ -1,6 +1,9 @@
 //! Configuration for the static `AuthZ` resolver plugin.
 
+use std::collections::HashMap;
+
 use serde::Deserialize;
+use uuid::Uuid;
 
 /// Plugin configuration.
 #[derive(Debug, Clone, Deserialize)]
@@ -10,7 +13,22 @@ pub struct StaticAuthZPluginConfig {
     pub vendor: String,
 
     /// Plugin priority (lower = higher priority).
+    ///
+    /// Defaults to 100 so that any real PDP registered with a lower value
+    /// (e.g. `tr-authz-plugin` at 50) supersedes this plugin.
     pub priority: i16,
+
+    /// Resource properties to echo back as `eq` predicates when present on
+    /// the request (e.g. `city_id`), for owner/city-style scoping in dev.
+    pub echo_resource_properties: Vec<String>,
+
+    /// Tenants each subject (by subject ID) may access, for modeling
+    /// cross-tenant access in dev.
+    ///
+    /// A listed subject gets an `in` predicate over exactly these tenants
+    /// instead of its own tenant. Subjects not listed are unaffected. Nil
+    /// tenants are rejected by [`validate`](Self::validate).
+    pub allowed_tenants: HashMap<Uuid, Vec<Uuid>>,
 }
 
 impl Default for StaticAuthZPluginConfig {
@@ -18,6 +36,33 @@ impl Default for StaticAuthZPluginConfig {
         Self {
             vendor: "hyperspot".to_owned(),
             priority: 100,
+            echo_resource_properties: Vec::new(),
+            allowed_tenants: HashMap::new(),
+        }
+    }
+}
+
+impl StaticAuthZPluginConfig {
+    /// Validate the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a subject's `allowed_tenants` lists the nil tenant,
+    /// which would otherwise slip past the nil-tenant check applied to tenants
+    /// taken from the request.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for (subject_id, tenants) in &self.allowed_tenants {
+            if tenants.iter().any(Uuid::is_nil) {
+                anyhow::bail!(
+                    "static-authz-plugin: allowed_tenants of subject {subject_id} lists the nil tenant"
+                );
+            }
         }
+        Ok(())
     }
 }
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[path = "config_tests.rs"]
+mod config_tests;
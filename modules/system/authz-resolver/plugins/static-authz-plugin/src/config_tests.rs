@@ -0,0 +1,24 @@
+use super::*;
+
+#[test]
+fn validate_accepts_allowed_tenants() {
+    let cfg = StaticAuthZPluginConfig {
+        allowed_tenants: HashMap::from([(Uuid::new_v4(), vec![Uuid::new_v4(), Uuid::new_v4()])]),
+        ..Default::default()
+    };
+    cfg.validate().expect("non-nil tenants should pass");
+}
+
+#[test]
+fn validate_rejects_nil_allowed_tenant() {
+    let subject = Uuid::new_v4();
+    let cfg = StaticAuthZPluginConfig {
+        allowed_tenants: HashMap::from([(subject, vec![Uuid::new_v4(), Uuid::nil()])]),
+        ..Default::default()
+    };
+    let err = cfg.validate().unwrap_err();
+    assert!(
+        err.to_string().contains(&subject.to_string()),
+        "error should name the subject: {err}"
+    );
+}
@@ -1,33 +1,91 @@
 // Updated: 2026-04-14 by Constructor Tech
 //! Service implementation for the static `AuthZ` resolver plugin.
 
+use std::collections::HashMap;
+
 use authz_resolver_sdk::{
-    Constraint, EvaluationRequest, EvaluationResponse, EvaluationResponseContext, InPredicate,
-    Predicate,
+    Constraint, EqPredicate, EvaluationRequest, EvaluationResponse, EvaluationResponseContext,
+    InPredicate, Predicate,
 };
 use modkit_macros::domain_model;
-use modkit_security::pep_properties;
+use modkit_security::{pep_properties, require_tenant_id};
 use uuid::Uuid;
 
+use crate::config::StaticAuthZPluginConfig;
+
 /// Static `AuthZ` resolver service.
 ///
 /// - Returns `decision: true` with an `in` predicate on `pep_properties::OWNER_TENANT_ID`
-///   scoped to the context tenant from the request (for all operations including CREATE).
+///   scoped to the context tenant from the request (for all operations including CREATE),
+///   or to the subject's configured allow-list of tenants.
+/// - Adds an `eq` predicate for each configured echo property carried as a
+///   scalar in `resource.properties`.
 /// - Denies access (`decision: false`) when no valid tenant can be resolved.
 #[domain_model]
 #[derive(Default)]
-pub struct Service;
+pub struct Service {
+    echo_resource_properties: Vec<String>,
+    allowed_tenants: HashMap<Uuid, Vec<Uuid>>,
+}
 
 impl Service {
     #[must_use]
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Create a service from plugin configuration.
+    #[must_use]
+    pub fn from_config(cfg: &StaticAuthZPluginConfig) -> Self {
+        Self {
+            echo_resource_properties: cfg.echo_resource_properties.clone(),
+            allowed_tenants: cfg.allowed_tenants.clone(),
+        }
     }
 
     /// Evaluate an authorization request.
     #[must_use]
-    #[allow(clippy::unused_self)] // &self reserved for future config/state
     pub fn evaluate(&self, request: &EvaluationRequest) -> EvaluationResponse {
+        let Some(tenants) = self.owner_tenants(request) else {
+            return EvaluationResponse {
+                decision: false,
+                context: EvaluationResponseContext::default(),
+            };
+        };
+
+        let mut predicates = vec![Predicate::In(InPredicate::new(
+            pep_properties::OWNER_TENANT_ID,
+            tenants,
+        ))];
+        for name in &self.echo_resource_properties {
+            // Only scalars make sense as equality values
+            if let Some(value) = request.resource.properties.get(name)
+                && (value.is_string() || value.is_number() || value.is_boolean())
+            {
+                predicates.push(Predicate::Eq(EqPredicate::new(name, value.clone())));
+            }
+        }
+
+        EvaluationResponse {
+            decision: true,
+            context: EvaluationResponseContext {
+                constraints: vec![Constraint { predicates }],
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Tenants the subject may access: its configured allow-list, or else the
+    /// single tenant resolved from the request.
+    ///
+    /// Returns `None` when no valid tenant can be resolved.
+    fn owner_tenants(&self, request: &EvaluationRequest) -> Option<Vec<Uuid>> {
+        if let Some(allowed) = self.allowed_tenants.get(&request.subject.id)
+            && !allowed.is_empty()
+        {
+            return Some(allowed.clone());
+        }
+
         // Always scope to context tenant (all CRUD operations get constraints)
         let tenant_id = request
             .context
@@ -44,34 +102,8 @@ impl Service {
                     .and_then(|s| Uuid::parse_str(s).ok())
             });
 
-        let Some(tid) = tenant_id else {
-            // No tenant resolvable from context or subject - deny access.
-            return EvaluationResponse {
-                decision: false,
-                context: EvaluationResponseContext::default(),
-            };
-        };
-
-        if tid == Uuid::default() {
-            // Nil UUID tenant - deny rather than grant unrestricted access.
-            return EvaluationResponse {
-                decision: false,
-                context: EvaluationResponseContext::default(),
-            };
-        }
-
-        EvaluationResponse {
-            decision: true,
-            context: EvaluationResponseContext {
-                constraints: vec![Constraint {
-                    predicates: vec![Predicate::In(InPredicate::new(
-                        pep_properties::OWNER_TENANT_ID,
-                        [tid],
-                    ))],
-                }],
-                ..Default::default()
-            },
-        }
+        // Missing or nil tenant - deny rather than grant unrestricted access.
+        require_tenant_id(tenant_id).ok().map(|tid| vec![tid])
     }
 }
 
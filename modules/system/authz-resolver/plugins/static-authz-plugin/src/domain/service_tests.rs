@@ -1,5 +1,6 @@
 // Created: 2026-04-14 by Constructor Tech
 use super::*;
+use crate::config::StaticAuthZPluginConfig;
 use authz_resolver_sdk::pep::IntoPropertyValue;
 use authz_resolver_sdk::{Action, EvaluationRequestContext, Resource, Subject, TenantContext};
 use std::collections::HashMap;
@@ -125,3 +126,96 @@ fn missing_tenant_context_and_subject_property_is_denied() {
     assert!(!response.decision);
     assert!(response.context.constraints.is_empty());
 }
+
+fn echo_service(properties: &[&str]) -> Service {
+    Service::from_config(&StaticAuthZPluginConfig {
+        echo_resource_properties: properties.iter().map(|p| (*p).to_owned()).collect(),
+        ..StaticAuthZPluginConfig::default()
+    })
+}
+
+#[test]
+fn configured_resource_property_is_echoed_as_eq_predicate() {
+    let tenant_id = Uuid::parse_str("33333333-3333-3333-3333-333333333333").unwrap();
+    let city_id = Uuid::parse_str("44444444-4444-4444-4444-444444444444").unwrap();
+    let mut request = make_request(true, Some(tenant_id));
+    request.resource.properties.insert(
+        "city_id".to_owned(),
+        serde_json::Value::String(city_id.to_string()),
+    );
+
+    let response = echo_service(&["city_id"]).evaluate(&request);
+
+    assert!(response.decision);
+    let predicates = &response.context.constraints[0].predicates;
+    assert_eq!(predicates.len(), 2);
+    match &predicates[1] {
+        Predicate::Eq(eq) => {
+            assert_eq!(eq.property, "city_id");
+            assert_eq!(eq.value, city_id.into_filter_value());
+        }
+        other => panic!("Expected Eq predicate, got: {other:?}"),
+    }
+}
+
+#[test]
+fn unconfigured_or_absent_properties_are_not_echoed() {
+    let tenant_id = Uuid::parse_str("33333333-3333-3333-3333-333333333333").unwrap();
+    let mut request = make_request(true, Some(tenant_id));
+    request
+        .resource
+        .properties
+        .insert("city_id".to_owned(), serde_json::json!("somewhere"));
+    request
+        .resource
+        .properties
+        .insert("tags".to_owned(), serde_json::json!(["a", "b"]));
+
+    // Not configured: no echo even though the request carries it
+    let response = Service::new().evaluate(&request);
+    assert_eq!(response.context.constraints[0].predicates.len(), 1);
+
+    // Configured but absent, or not a scalar: skipped
+    let response = echo_service(&["owner_id", "tags"]).evaluate(&request);
+    assert_eq!(response.context.constraints[0].predicates.len(), 1);
+}
+
+#[test]
+fn subject_allow_list_scopes_to_all_allowed_tenants() {
+    let own_tenant = Uuid::parse_str("33333333-3333-3333-3333-333333333333").unwrap();
+    let allowed = [
+        Uuid::parse_str("55555555-5555-5555-5555-555555555555").unwrap(),
+        Uuid::parse_str("66666666-6666-6666-6666-666666666666").unwrap(),
+    ];
+    let request = make_request(true, Some(own_tenant));
+    let service = Service::from_config(&StaticAuthZPluginConfig {
+        allowed_tenants: HashMap::from([(request.subject.id, allowed.to_vec())]),
+        ..StaticAuthZPluginConfig::default()
+    });
+
+    let response = service.evaluate(&request);
+
+    assert!(response.decision);
+    let predicates = &response.context.constraints[0].predicates;
+    assert_eq!(predicates.len(), 1);
+    match &predicates[0] {
+        Predicate::In(in_pred) => {
+            assert_eq!(in_pred.property, pep_properties::OWNER_TENANT_ID);
+            assert_eq!(
+                in_pred.values,
+                allowed.map(IntoPropertyValue::into_filter_value).to_vec()
+            );
+        }
+        other => panic!("Expected In predicate, got: {other:?}"),
+    }
+
+    // Other subjects keep their own tenant
+    let mut other = make_request(true, Some(own_tenant));
+    other.subject.id = Uuid::parse_str("77777777-7777-7777-7777-777777777777").unwrap();
+    match &service.evaluate(&other).context.constraints[0].predicates[0] {
+        Predicate::In(in_pred) => {
+            assert_eq!(in_pred.values, vec![own_tenant.into_filter_value()]);
+        }
+        other => panic!("Expected In predicate, got: {other:?}"),
+    }
+}
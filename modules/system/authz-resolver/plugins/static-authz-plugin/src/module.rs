@@ -14,7 +14,14 @@ use types_registry_sdk::{RegisterResult, TypesRegistryClient};
 use crate::config::StaticAuthZPluginConfig;
 use crate::domain::Service;
 
+/// GTS instance suffix of the static plugin.
+const INSTANCE_SUFFIX: &str = "hyperspot.builtin.static_authz_resolver.plugin.v1";
+
 /// Static `AuthZ` resolver plugin module.
+///
+/// Registers a plugin instance with the configured vendor and priority, so a
+/// real PDP registered under the same vendor with a lower priority value is
+/// selected instead of this plugin.
 #[modkit::module(
     name = "static-authz-plugin",
     deps = ["types-registry"]
@@ -35,32 +42,24 @@ impl Default for StaticAuthZPlugin {
 impl Module for StaticAuthZPlugin {
     async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
         let cfg: StaticAuthZPluginConfig = ctx.config_or_default()?;
+        cfg.validate()?;
         info!(
             vendor = %cfg.vendor,
             priority = cfg.priority,
             "Loaded plugin configuration"
         );
 
-        // Generate plugin instance ID
-        let instance_id = AuthZResolverPluginSpecV1::gts_make_instance_id(
-            "hyperspot.builtin.static_authz_resolver.plugin.v1",
-        );
-
         // Register plugin instance in types-registry
         let registry = ctx.client_hub().get::<dyn TypesRegistryClient>()?;
-        let instance = BaseModkitPluginV1::<AuthZResolverPluginSpecV1> {
-            id: instance_id.clone(),
-            vendor: cfg.vendor.clone(),
-            priority: cfg.priority,
-            properties: AuthZResolverPluginSpecV1,
-        };
+        let instance = plugin_instance(&cfg);
+        let instance_id = instance.id.clone();
         let instance_json = serde_json::to_value(&instance)?;
 
         let results = registry.register(vec![instance_json]).await?;
         RegisterResult::ensure_all_ok(&results)?;
 
         // Create service
-        let service = Arc::new(Service::new());
+        let service = Arc::new(Service::from_config(&cfg));
         self.service
             .set(service.clone())
             .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
@@ -77,3 +76,18 @@ impl Module for StaticAuthZPlugin {
         Ok(())
     }
 }
+
+/// Plugin instance registered in types-registry for `cfg`.
+fn plugin_instance(cfg: &StaticAuthZPluginConfig) -> BaseModkitPluginV1<AuthZResolverPluginSpecV1> {
+    BaseModkitPluginV1 {
+        id: AuthZResolverPluginSpecV1::gts_make_instance_id(INSTANCE_SUFFIX),
+        vendor: cfg.vendor.clone(),
+        priority: cfg.priority,
+        properties: AuthZResolverPluginSpecV1,
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[path = "module_tests.rs"]
+mod module_tests;
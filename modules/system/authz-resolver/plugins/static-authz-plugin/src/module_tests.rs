@@ -0,0 +1,64 @@
+use modkit::plugins::choose_plugin_instance;
+
+use super::*;
+
+/// A competing PDP instance registered under `vendor` with `priority`.
+fn pdp_instance(vendor: &str, priority: i16) -> BaseModkitPluginV1<AuthZResolverPluginSpecV1> {
+    BaseModkitPluginV1 {
+        id: AuthZResolverPluginSpecV1::gts_make_instance_id("acme.pdp._.plugin.v1"),
+        vendor: vendor.to_owned(),
+        priority,
+        properties: AuthZResolverPluginSpecV1,
+    }
+}
+
+/// Runs the same selection as the `AuthZ` resolver over `instances`.
+fn select(instances: &[&BaseModkitPluginV1<AuthZResolverPluginSpecV1>]) -> String {
+    let registered: Vec<(String, serde_json::Value)> = instances
+        .iter()
+        .map(|i| (i.id.to_string(), serde_json::to_value(i).unwrap()))
+        .collect();
+    choose_plugin_instance::<AuthZResolverPluginSpecV1>(
+        "hyperspot",
+        registered
+            .iter()
+            .map(|(id, content)| (id.as_str(), content)),
+    )
+    .unwrap()
+}
+
+#[test]
+fn plugin_instance_uses_configured_vendor_and_priority() {
+    let cfg = StaticAuthZPluginConfig {
+        vendor: "acme".to_owned(),
+        priority: 7,
+        ..StaticAuthZPluginConfig::default()
+    };
+
+    let instance = plugin_instance(&cfg);
+
+    assert_eq!(instance.vendor, "acme");
+    assert_eq!(instance.priority, 7);
+    assert!(instance.id.ends_with(INSTANCE_SUFFIX));
+}
+
+#[test]
+fn lower_priority_number_is_selected() {
+    let static_instance = plugin_instance(&StaticAuthZPluginConfig::default());
+    let pdp = pdp_instance("hyperspot", 10);
+
+    assert_eq!(
+        select(&[&static_instance, &pdp]),
+        pdp.id.to_string(),
+        "a real PDP with a lower priority value supersedes the static plugin"
+    );
+
+    let static_instance = plugin_instance(&StaticAuthZPluginConfig {
+        priority: 5,
+        ..StaticAuthZPluginConfig::default()
+    });
+    assert_eq!(
+        select(&[&pdp, &static_instance]),
+        static_instance.id.to_string()
+    );
+}
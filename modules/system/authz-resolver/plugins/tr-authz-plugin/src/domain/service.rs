@@ -329,6 +329,7 @@ impl Service {
                 TenantId(tenant_id),
                 &GetDescendantsOptions {
                     status: tenant_statuses.to_vec(),
+                    exclude_status: vec![],
                     barrier_mode,
                     max_depth: None,
                 },
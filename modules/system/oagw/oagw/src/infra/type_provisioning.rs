@@ -774,6 +774,13 @@ mod tests {
             unimplemented!()
         }
 
+        async fn register_atomic(
+            &self,
+            _entities: Vec<serde_json::Value>,
+        ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+            unimplemented!()
+        }
+
         async fn list(&self, query: ListQuery) -> Result<Vec<GtsEntity>, TypesRegistryError> {
             (self.list_fn)(query)
         }
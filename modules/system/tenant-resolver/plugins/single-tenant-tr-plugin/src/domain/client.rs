@@ -5,6 +5,7 @@
 //! In single-tenant mode:
 //! - There is only one tenant (the one from the security context)
 //! - It has no parent and no children
+//! - Any other tenant id is reported as `TenantNotFound`, never echoed back
 //! - Hierarchy operations return minimal results
 
 use async_trait::async_trait;
@@ -52,6 +52,26 @@ async fn get_tenant_returns_error_for_different_id() {
     }
 }
 
+#[tokio::test]
+async fn get_tenant_not_found_for_random_ids() {
+    let service = Service;
+    let ctx_tenant = TenantId(Uuid::parse_str(TENANT_A).unwrap());
+    let ctx = ctx_for_tenant(ctx_tenant);
+
+    for _ in 0..5 {
+        let random = TenantId(Uuid::new_v4());
+        let err = service.get_tenant(&ctx, random).await.unwrap_err();
+        assert!(
+            matches!(err, TenantResolverError::TenantNotFound { tenant_id } if tenant_id == random),
+            "expected TenantNotFound for {random}, got: {err:?}"
+        );
+    }
+    assert_eq!(
+        service.get_tenant(&ctx, ctx_tenant).await.unwrap().id,
+        ctx_tenant
+    );
+}
+
 #[tokio::test]
 async fn get_tenant_rejects_nil_uuid() {
     let service = Service;
@@ -363,3 +383,31 @@ async fn get_root_tenant_internal_for_nil_context() {
         .expect_err("nil-tenant context should produce Internal");
     assert!(matches!(err, TenantResolverError::Internal(_)));
 }
+
+// ==================== get_root tests ====================
+
+#[tokio::test]
+async fn get_root_returns_context_tenant_for_itself() {
+    let service = Service;
+    let tenant_id = TenantId(Uuid::parse_str(TENANT_A).unwrap());
+    let ctx = ctx_for_tenant(tenant_id);
+
+    let root = service
+        .get_root(&ctx, tenant_id, &GetAncestorsOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(root.id, tenant_id);
+}
+
+#[tokio::test]
+async fn get_root_not_found_for_different_id() {
+    let service = Service;
+    let ctx = ctx_for_tenant(TenantId(Uuid::parse_str(TENANT_A).unwrap()));
+    let other = TenantId(Uuid::parse_str(TENANT_B).unwrap());
+
+    let err = service
+        .get_root(&ctx, other, &GetAncestorsOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, TenantResolverError::TenantNotFound { tenant_id } if tenant_id == other));
+}
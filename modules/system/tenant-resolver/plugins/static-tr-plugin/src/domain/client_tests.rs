@@ -600,6 +600,60 @@ async fn get_descendants_pre_order() {
     );
 }
 
+// ==================== stream_descendants tests ====================
+
+#[tokio::test]
+async fn stream_descendants_yields_large_subtree_across_pages() {
+    use futures::TryStreamExt;
+    use std::collections::HashSet;
+    use tenant_resolver_sdk::StreamDescendantsOptions;
+
+    // Root -> 10 children -> 99 grandchildren each: 1000 descendants
+    let mut tenants = vec![tenant(TENANT_A, "Root", TenantStatus::Active)];
+    for c in 0..10u128 {
+        let child = Uuid::from_u128(c + 1).to_string();
+        tenants.push(tenant_with_parent(&child, "Child", TENANT_A));
+        for g in 0..99u128 {
+            let grandchild = Uuid::from_u128((c + 1) << 64 | (g + 1)).to_string();
+            tenants.push(tenant_with_parent(&grandchild, "Grandchild", &child));
+        }
+    }
+    let cfg = StaticTrPluginConfig {
+        tenants,
+        ..Default::default()
+    };
+    let service = Service::from_config(&cfg).expect("valid config");
+    let ctx = ctx_for_tenant(TENANT_A);
+    let root = TenantId(Uuid::parse_str(TENANT_A).unwrap());
+
+    let options = StreamDescendantsOptions {
+        page_size: 64,
+        ..Default::default()
+    };
+    let pages: Vec<Vec<TenantInfo>> = service
+        .stream_descendants(&ctx, root, &options)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(pages.len(), 16, "1000 tenants in pages of 64");
+    assert!(
+        pages
+            .iter()
+            .all(|page| !page.is_empty() && page.len() <= 64)
+    );
+
+    let mut seen = HashSet::new();
+    for info in pages.iter().flatten() {
+        // Pre-order carries over page boundaries: parents come first
+        let parent = info.parent_id.expect("descendants have a parent");
+        assert!(parent == root || seen.contains(&parent));
+        assert!(seen.insert(info.id), "tenant {} streamed twice", info.id);
+    }
+    assert_eq!(seen.len(), 1000);
+    assert!(!seen.contains(&root), "starting tenant is not streamed");
+}
+
 // ==================== is_ancestor tests ====================
 
 #[tokio::test]
@@ -10,8 +10,9 @@ use modkit_security::SecurityContext;
 use crate::error::TenantResolverError;
 use crate::models::{
     GetAncestorsOptions, GetAncestorsResponse, GetDescendantsOptions, GetDescendantsResponse,
-    GetTenantsOptions, IsAncestorOptions, TenantId, TenantInfo,
+    GetTenantsOptions, IsAncestorOptions, StreamDescendantsOptions, TenantId, TenantInfo,
 };
+use crate::stream::{TenantPageStream, chunk_descendants};
 
 /// Public API trait for the tenant resolver module.
 ///
@@ -27,9 +28,16 @@ use crate::models::{
 /// // Get ancestor chain
 /// let response = resolver.get_ancestors(&ctx, tenant_id, &GetAncestorsOptions::default()).await?;
 ///
+/// // Top-most ancestor (the tenant itself if already root)
+/// let root = resolver.get_root(&ctx, tenant_id, &GetAncestorsOptions::default()).await?;
+///
 /// // Get descendants subtree
 /// let descendants = resolver.get_descendants(&ctx, tenant_id, &GetDescendantsOptions::default()).await?;
 ///
+/// // Stream a large subtree page by page
+/// let mut pages = resolver.stream_descendants(&ctx, tenant_id, &StreamDescendantsOptions::default());
+/// while let Some(page) = pages.try_next().await? { /* ... */ }
+///
 /// // Check ancestry
 /// let is_anc = resolver.is_ancestor(&ctx, parent_id, child_id, &IsAncestorOptions::default()).await?;
 /// ```
@@ -131,6 +139,33 @@ pub trait TenantResolverClient: Send + Sync {
         options: &GetAncestorsOptions,
     ) -> Result<GetAncestorsResponse, TenantResolverError>;
 
+    /// Get the top-most ancestor of a tenant.
+    ///
+    /// Returns the tenant itself if it is already a root. Follows the same
+    /// barrier rules as [`Self::get_ancestors`]: with `BarrierMode::Respect`
+    /// the first `self_managed` tenant in the chain is the root.
+    ///
+    /// The default implementation derives the root from [`Self::get_ancestors`].
+    ///
+    /// # Errors
+    ///
+    /// - `TenantNotFound` if the tenant does not exist
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Security context
+    /// * `id` - The tenant ID to find the root for
+    /// * `options` - Hierarchy traversal options
+    async fn get_root(
+        &self,
+        ctx: &SecurityContext,
+        id: TenantId,
+        options: &GetAncestorsOptions,
+    ) -> Result<TenantInfo, TenantResolverError> {
+        let chain = self.get_ancestors(ctx, id, options).await?;
+        self.get_tenant(ctx, chain.root_id()).await
+    }
+
     /// Get descendants subtree of the given tenant.
     ///
     /// Returns the requested tenant along with all its descendant tenants
@@ -160,6 +195,42 @@ pub trait TenantResolverClient: Send + Sync {
         options: &GetDescendantsOptions,
     ) -> Result<GetDescendantsResponse, TenantResolverError>;
 
+    /// Stream the descendants of the given tenant in pages of [`TenantInfo`].
+    ///
+    /// Same traversal, barrier and filter semantics as
+    /// [`Self::get_descendants`], but the subtree is delivered in pages of at
+    /// most `options.page_size` tenants (pre-order), so large subtrees can be
+    /// processed incrementally. The starting tenant itself is not included.
+    ///
+    /// The default implementation chunks the [`Self::get_descendants`]
+    /// response and resolves each page with [`Self::get_tenants`].
+    ///
+    /// # Errors
+    ///
+    /// - `TenantNotFound` (as the first item) if the tenant does not exist
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Security context
+    /// * `id` - The tenant ID to stream descendants for
+    /// * `options` - Traversal options and page size
+    fn stream_descendants<'a>(
+        &'a self,
+        ctx: &'a SecurityContext,
+        id: TenantId,
+        options: &StreamDescendantsOptions,
+    ) -> TenantPageStream<'a> {
+        let descendants = options.descendants.clone();
+        chunk_descendants(
+            async move { self.get_descendants(ctx, id, &descendants).await },
+            options.page_size,
+            move |ids| async move {
+                self.get_tenants(ctx, &ids, &GetTenantsOptions::default())
+                    .await
+            },
+        )
+    }
+
     /// Check if `ancestor_id` is an ancestor of `descendant_id`.
     ///
     /// Returns `true` if `ancestor_id` is in the parent chain of `descendant_id`.
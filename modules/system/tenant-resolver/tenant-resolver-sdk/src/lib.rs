@@ -42,14 +42,16 @@ pub mod error;
 pub mod gts;
 pub mod models;
 pub mod plugin_api;
+pub mod stream;
 
 // Re-export main types at crate root
 pub use api::TenantResolverClient;
 pub use error::TenantResolverError;
 pub use gts::TenantResolverPluginSpecV1;
 pub use models::{
-    BarrierMode, GetAncestorsOptions, GetAncestorsResponse, GetDescendantsOptions,
-    GetDescendantsResponse, GetTenantsOptions, HasStatus, IsAncestorOptions, TenantId, TenantInfo,
-    TenantRef, TenantStatus, matches_status,
+    BarrierMode, DEFAULT_DESCENDANTS_PAGE_SIZE, GetAncestorsOptions, GetAncestorsResponse,
+    GetDescendantsOptions, GetDescendantsResponse, GetTenantsOptions, HasStatus, IsAncestorOptions,
+    StreamDescendantsOptions, TenantId, TenantInfo, TenantRef, TenantStatus, matches_status,
 };
 pub use plugin_api::TenantResolverPluginClient;
+pub use stream::TenantPageStream;
@@ -159,7 +159,7 @@ impl HasStatus for TenantRef {
 /// // Ignore barriers (traverse through self-managed tenants)
 /// let mode = BarrierMode::Ignore;
 /// ```
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BarrierMode {
     /// Respect all barriers - stop traversal at barrier boundaries (default).
     #[default]
@@ -226,6 +226,13 @@ pub struct GetTenantsOptions {
 ///     status: vec![TenantStatus::Active],
 ///     barrier_mode: BarrierMode::Ignore,
 ///     max_depth: Some(2),
+///     ..Default::default()
+/// };
+///
+/// // Everything except suspended tenants (and their subtrees)
+/// let opts = GetDescendantsOptions {
+///     exclude_status: vec![TenantStatus::Suspended],
+///     ..Default::default()
 /// };
 /// ```
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -234,12 +241,63 @@ pub struct GetDescendantsOptions {
     /// Does NOT apply to the starting tenant.
     #[serde(default)]
     pub status: Vec<TenantStatus>,
+    /// Drop descendants with any of these statuses, together with their
+    /// subtrees. Empty means nothing is excluded.
+    /// Does NOT apply to the starting tenant.
+    #[serde(default)]
+    pub exclude_status: Vec<TenantStatus>,
     /// How to handle barriers during traversal.
     pub barrier_mode: BarrierMode,
     /// Maximum depth to traverse (`None` = unlimited, `Some(1)` = direct children only).
     pub max_depth: Option<u32>,
 }
 
+/// Default number of tenants per page for
+/// [`stream_descendants`](crate::TenantResolverClient::stream_descendants).
+pub const DEFAULT_DESCENDANTS_PAGE_SIZE: usize = 100;
+
+/// Options for [`stream_descendants`](crate::TenantResolverClient::stream_descendants).
+///
+/// # Example
+///
+/// ```
+/// use tenant_resolver_sdk::{GetDescendantsOptions, StreamDescendantsOptions, TenantStatus};
+///
+/// // Default: same traversal as `get_descendants`, 100 tenants per page
+/// let opts = StreamDescendantsOptions::default();
+///
+/// // Active tenants only, 500 per page
+/// let opts = StreamDescendantsOptions {
+///     descendants: GetDescendantsOptions {
+///         status: vec![TenantStatus::Active],
+///         ..Default::default()
+///     },
+///     page_size: 500,
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamDescendantsOptions {
+    /// Filter, barrier mode and depth, as for `get_descendants`.
+    #[serde(flatten)]
+    pub descendants: GetDescendantsOptions,
+    /// Maximum number of tenants per page (`0` is treated as `1`).
+    #[serde(default = "default_page_size")]
+    pub page_size: usize,
+}
+
+fn default_page_size() -> usize {
+    DEFAULT_DESCENDANTS_PAGE_SIZE
+}
+
+impl Default for StreamDescendantsOptions {
+    fn default() -> Self {
+        Self {
+            descendants: GetDescendantsOptions::default(),
+            page_size: DEFAULT_DESCENDANTS_PAGE_SIZE,
+        }
+    }
+}
+
 /// Request parameters for [`is_ancestor`](crate::TenantResolverClient::is_ancestor).
 ///
 /// # Example
@@ -282,6 +340,15 @@ pub struct GetAncestorsResponse {
     pub ancestors: Vec<TenantRef>,
 }
 
+impl GetAncestorsResponse {
+    /// ID of the top-most tenant in the chain: the last ancestor, or the
+    /// requested tenant itself when it has no ancestors.
+    #[must_use]
+    pub fn root_id(&self) -> TenantId {
+        self.ancestors.last().unwrap_or(&self.tenant).id
+    }
+}
+
 /// Response for `get_descendants` containing the requested tenant and its descendants.
 ///
 /// Uses [`TenantRef`] (without name) for efficiency. If names are needed,
@@ -10,8 +10,9 @@ use modkit_security::SecurityContext;
 use crate::error::TenantResolverError;
 use crate::models::{
     GetAncestorsOptions, GetAncestorsResponse, GetDescendantsOptions, GetDescendantsResponse,
-    GetTenantsOptions, IsAncestorOptions, TenantId, TenantInfo,
+    GetTenantsOptions, IsAncestorOptions, StreamDescendantsOptions, TenantId, TenantInfo,
 };
+use crate::stream::{TenantPageStream, chunk_descendants};
 
 /// Plugin API trait for tenant resolver implementations.
 ///
@@ -108,6 +109,35 @@ pub trait TenantResolverPluginClient: Send + Sync {
         options: &GetAncestorsOptions,
     ) -> Result<GetAncestorsResponse, TenantResolverError>;
 
+    /// Get the top-most ancestor of a tenant.
+    ///
+    /// Returns the tenant itself if it has no ancestors. With
+    /// `BarrierMode::Respect` (default) the walk stops at the first
+    /// `self_managed` tenant, which is then returned as the root.
+    ///
+    /// The default implementation derives the root from [`Self::get_ancestors`]
+    /// and resolves it with [`Self::get_tenant`]; override it if the data
+    /// source can answer directly.
+    ///
+    /// # Errors
+    ///
+    /// - `TenantNotFound` if the tenant doesn't exist in the plugin's data source
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Security context
+    /// * `id` - The tenant ID to find the root for
+    /// * `options` - Hierarchy traversal options
+    async fn get_root(
+        &self,
+        ctx: &SecurityContext,
+        id: TenantId,
+        options: &GetAncestorsOptions,
+    ) -> Result<TenantInfo, TenantResolverError> {
+        let chain = self.get_ancestors(ctx, id, options).await?;
+        self.get_tenant(ctx, chain.root_id()).await
+    }
+
     /// Get descendants subtree of the given tenant.
     ///
     /// Returns the requested tenant along with all its descendant tenants.
@@ -134,6 +164,43 @@ pub trait TenantResolverPluginClient: Send + Sync {
         options: &GetDescendantsOptions,
     ) -> Result<GetDescendantsResponse, TenantResolverError>;
 
+    /// Stream the descendants of the given tenant in pages of [`TenantInfo`].
+    ///
+    /// Same traversal, barrier and filter semantics as
+    /// [`Self::get_descendants`]; the starting tenant itself is not included.
+    ///
+    /// The default implementation fetches the subtree once with
+    /// [`Self::get_descendants`] and resolves it page by page with
+    /// [`Self::get_tenants`]. Plugins backed by a paginated data source should
+    /// override it so memory stays bounded by the page size.
+    ///
+    /// # Errors
+    ///
+    /// - `TenantNotFound` (as the first item) if the tenant doesn't exist in
+    ///   the plugin's data source
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Security context
+    /// * `id` - The tenant ID to stream descendants for
+    /// * `options` - Traversal options and page size
+    fn stream_descendants<'a>(
+        &'a self,
+        ctx: &'a SecurityContext,
+        id: TenantId,
+        options: &StreamDescendantsOptions,
+    ) -> TenantPageStream<'a> {
+        let descendants = options.descendants.clone();
+        chunk_descendants(
+            async move { self.get_descendants(ctx, id, &descendants).await },
+            options.page_size,
+            move |ids| async move {
+                self.get_tenants(ctx, &ids, &GetTenantsOptions::default())
+                    .await
+            },
+        )
+    }
+
     /// Check if `ancestor_id` is an ancestor of `descendant_id`.
     ///
     /// Returns `true` if `ancestor_id` is in the parent chain of `descendant_id`.
@@ -0,0 +1,57 @@
+//! Paged streaming of descendant subtrees.
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+
+use crate::error::TenantResolverError;
+use crate::models::{GetDescendantsResponse, TenantId, TenantInfo};
+
+/// Stream of descendant pages returned by `stream_descendants`.
+///
+/// Pages follow the pre-order of `get_descendants`; each holds at most
+/// `page_size` tenants and is never empty.
+pub type TenantPageStream<'a> = BoxStream<'a, Result<Vec<TenantInfo>, TenantResolverError>>;
+
+/// Build a page stream by chunking a full `get_descendants` response.
+///
+/// This is the default behind both `stream_descendants` methods: `descendants`
+/// is awaited once, then every chunk of `page_size` IDs is resolved to
+/// [`TenantInfo`] with `get_tenants` only when the consumer polls for it.
+/// Tenants that disappear between the two calls are skipped.
+pub fn chunk_descendants<'a, D, G, Fut>(
+    descendants: D,
+    page_size: usize,
+    get_tenants: G,
+) -> TenantPageStream<'a>
+where
+    D: Future<Output = Result<GetDescendantsResponse, TenantResolverError>> + Send + 'a,
+    G: Fn(Vec<TenantId>) -> Fut + Send + 'a,
+    Fut: Future<Output = Result<Vec<TenantInfo>, TenantResolverError>> + Send + 'a,
+{
+    let page_size = page_size.max(1);
+    stream::once(descendants)
+        .map_ok(move |response| {
+            let pages: Vec<Vec<TenantId>> = response
+                .descendants
+                .chunks(page_size)
+                .map(|chunk| chunk.iter().map(|t| t.id).collect())
+                .collect();
+            stream::iter(pages).map(Ok)
+        })
+        .try_flatten()
+        .and_then(move |ids| {
+            let infos = get_tenants(ids.clone());
+            async move { Ok(in_order(&ids, infos.await?)) }
+        })
+        .try_filter(|page| std::future::ready(!page.is_empty()))
+        .boxed()
+}
+
+/// Reorder `infos` to follow `ids`; `get_tenants` does not guarantee order.
+fn in_order(ids: &[TenantId], infos: Vec<TenantInfo>) -> Vec<TenantInfo> {
+    let mut by_id: HashMap<TenantId, TenantInfo> =
+        infos.into_iter().map(|info| (info.id, info)).collect();
+    ids.iter().filter_map(|id| by_id.remove(id)).collect()
+}
@@ -1,5 +1,7 @@
 //! Configuration for the tenant resolver module.
 
+use std::time::Duration;
+
 use serde::Deserialize;
 
 /// Module configuration.
@@ -11,12 +13,23 @@ pub struct TenantResolverConfig {
     /// The module queries types-registry for plugin instances matching
     /// this vendor and selects the one with lowest priority.
     pub vendor: String,
+
+    /// Vendors tried in order when no instance of `vendor` is registered.
+    /// A `"*"` entry matches any vendor.
+    pub fallback_vendors: Vec<String>,
+
+    /// How long `is_ancestor` reuses a fetched descendant set for the same
+    /// caller and ancestor (e.g. `"5s"`). Disabled when unset.
+    #[serde(with = "modkit_utils::humantime_serde::option")]
+    pub is_ancestor_cache_ttl: Option<Duration>,
 }
 
 impl Default for TenantResolverConfig {
     fn default() -> Self {
         Self {
             vendor: "hyperspot".to_owned(),
+            fallback_vendors: Vec::new(),
+            is_ancestor_cache_ttl: None,
         }
     }
 }
@@ -4,12 +4,19 @@ use modkit_macros::domain_model;
 use tenant_resolver_sdk::TenantResolverError;
 use uuid::Uuid;
 
+/// Underlying cause kept on errors that would otherwise only carry a message.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
 /// Internal domain errors.
 #[domain_model]
 #[derive(thiserror::Error, Debug)]
 pub enum DomainError {
-    #[error("types registry is not available: {0}")]
-    TypesRegistryUnavailable(String),
+    #[error("types registry is not available: {message}")]
+    TypesRegistryUnavailable {
+        message: String,
+        #[source]
+        source: Option<BoxError>,
+    },
 
     #[error("no plugin instances found for vendor '{vendor}'")]
     PluginNotFound { vendor: String },
@@ -27,25 +34,49 @@ pub enum DomainError {
     #[error("unauthorized")]
     Unauthorized,
 
-    #[error("internal error: {0}")]
-    Internal(String),
+    #[error("internal error: {message}")]
+    Internal {
+        message: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+}
+
+impl DomainError {
+    /// `Internal` error that keeps `source` as its cause.
+    pub fn internal(source: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Self::Internal {
+            message: source.to_string(),
+            source: Some(Box::new(source)),
+        }
+    }
+
+    /// `TypesRegistryUnavailable` error that keeps `source` as its cause.
+    pub fn types_registry_unavailable(
+        source: impl std::error::Error + Send + Sync + 'static,
+    ) -> Self {
+        Self::TypesRegistryUnavailable {
+            message: source.to_string(),
+            source: Some(Box::new(source)),
+        }
+    }
 }
 
 impl From<types_registry_sdk::TypesRegistryError> for DomainError {
     fn from(e: types_registry_sdk::TypesRegistryError) -> Self {
-        Self::Internal(e.to_string())
+        Self::internal(e)
     }
 }
 
 impl From<modkit::client_hub::ClientHubError> for DomainError {
     fn from(e: modkit::client_hub::ClientHubError) -> Self {
-        Self::Internal(e.to_string())
+        Self::internal(e)
     }
 }
 
 impl From<serde_json::Error> for DomainError {
     fn from(e: serde_json::Error) -> Self {
-        Self::Internal(e.to_string())
+        Self::internal(e)
     }
 }
 
@@ -76,7 +107,10 @@ impl From<TenantResolverError> for DomainError {
                 gts_id: "unknown".to_owned(),
                 reason: msg,
             },
-            TenantResolverError::Internal(msg) => Self::Internal(msg),
+            TenantResolverError::Internal(message) => Self::Internal {
+                message,
+                source: None,
+            },
         }
     }
 }
@@ -95,9 +129,8 @@ impl From<DomainError> for TenantResolverError {
                 tenant_id: tenant_resolver_sdk::TenantId(tenant_id),
             },
             DomainError::Unauthorized => Self::Unauthorized,
-            DomainError::TypesRegistryUnavailable(reason) | DomainError::Internal(reason) => {
-                Self::Internal(reason)
-            }
+            DomainError::TypesRegistryUnavailable { message, .. }
+            | DomainError::Internal { message, .. } => Self::Internal(message),
         }
     }
 }
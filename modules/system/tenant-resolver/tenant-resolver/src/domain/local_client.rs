@@ -3,12 +3,13 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
 use modkit_macros::domain_model;
 use modkit_security::SecurityContext;
 use tenant_resolver_sdk::{
     GetAncestorsOptions, GetAncestorsResponse, GetDescendantsOptions, GetDescendantsResponse,
-    GetTenantsOptions, IsAncestorOptions, TenantId, TenantInfo, TenantResolverClient,
-    TenantResolverError,
+    GetTenantsOptions, IsAncestorOptions, StreamDescendantsOptions, TenantId, TenantInfo,
+    TenantPageStream, TenantResolverClient, TenantResolverError,
 };
 
 use super::{DomainError, Service};
@@ -80,6 +81,18 @@ impl TenantResolverClient for TenantResolverLocalClient {
             .map_err(|e| log_and_convert("get_ancestors", e))
     }
 
+    async fn get_root(
+        &self,
+        ctx: &SecurityContext,
+        id: TenantId,
+        options: &GetAncestorsOptions,
+    ) -> Result<TenantInfo, TenantResolverError> {
+        self.svc
+            .get_root(ctx, id, options)
+            .await
+            .map_err(|e| log_and_convert("get_root", e))
+    }
+
     async fn get_descendants(
         &self,
         ctx: &SecurityContext,
@@ -92,6 +105,18 @@ impl TenantResolverClient for TenantResolverLocalClient {
             .map_err(|e| log_and_convert("get_descendants", e))
     }
 
+    fn stream_descendants<'a>(
+        &'a self,
+        ctx: &'a SecurityContext,
+        id: TenantId,
+        options: &StreamDescendantsOptions,
+    ) -> TenantPageStream<'a> {
+        self.svc
+            .stream_descendants(ctx, id, options)
+            .map_err(|e| log_and_convert("stream_descendants", e))
+            .boxed()
+    }
+
     async fn is_ancestor(
         &self,
         ctx: &SecurityContext,
@@ -6,4 +6,4 @@ pub mod service;
 
 pub use error::DomainError;
 pub use local_client::TenantResolverLocalClient;
-pub use service::Service;
+pub use service::{PluginCandidate, PluginSelection, Service};
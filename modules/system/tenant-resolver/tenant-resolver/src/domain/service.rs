@@ -3,27 +3,145 @@
 //! Plugin discovery is lazy: resolved on first API call after
 //! types-registry is ready.
 
-use std::sync::Arc;
-use std::time::Duration;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
 
+use futures::StreamExt;
+use futures::stream::BoxStream;
 use modkit::client_hub::{ClientHub, ClientScope};
-use modkit::plugins::{GtsPluginSelector, choose_plugin_instance};
+use modkit::gts::BaseModkitPluginV1;
+use modkit::plugins::{GtsPluginSelector, choose_plugin_instance_by_vendor_preference};
 use modkit::telemetry::ThrottledLog;
 use modkit_macros::domain_model;
 use modkit_security::SecurityContext;
 use tenant_resolver_sdk::{
-    GetAncestorsOptions, GetAncestorsResponse, GetDescendantsOptions, GetDescendantsResponse,
-    GetTenantsOptions, IsAncestorOptions, TenantId, TenantInfo, TenantResolverPluginClient,
-    TenantResolverPluginSpecV1,
+    BarrierMode, GetAncestorsOptions, GetAncestorsResponse, GetDescendantsOptions,
+    GetDescendantsResponse, GetTenantsOptions, HasStatus, IsAncestorOptions,
+    StreamDescendantsOptions, TenantId, TenantInfo, TenantResolverPluginClient,
+    TenantResolverPluginSpecV1, TenantStatus, matches_status,
 };
 use tracing::info;
-use types_registry_sdk::{ListQuery, TypesRegistryClient};
+use types_registry_sdk::{GtsEntity, ListQuery, TypesRegistryClient};
+use uuid::Uuid;
 
 use super::error::DomainError;
 
 /// Throttle interval for unavailable plugin warnings.
 const UNAVAILABLE_LOG_THROTTLE: Duration = Duration::from_secs(10);
 
+/// Deepest hierarchy `get_tenant_depth` accepts from a plugin.
+///
+/// Longer ancestor chains point at a broken plugin (e.g. a parent cycle).
+/// The plugin walks the chain in a single `get_ancestors` call, so the limit
+/// is checked on its answer rather than during the walk.
+const MAX_TENANT_DEPTH: usize = 64;
+
+/// Cache key for `is_ancestor` subtrees: caller, ancestor and barrier mode.
+///
+/// The caller is part of the key because plugins may authorize per context.
+type SubtreeKey = (Uuid, TenantId, BarrierMode);
+
+/// Largest descendant set `is_ancestor` caches for an ancestor.
+///
+/// Bigger subtrees stop being fetched once they cross the limit and their
+/// checks go straight to the plugin.
+const MAX_CACHED_SUBTREE_LEN: usize = 10_000;
+
+/// Descendant set of an ancestor, as seen by the `is_ancestor` cache.
+#[domain_model]
+#[derive(Clone)]
+enum Subtree {
+    Known(Arc<HashSet<TenantId>>),
+    /// More than `MAX_CACHED_SUBTREE_LEN` descendants; checks go to the plugin.
+    TooLarge,
+}
+
+/// A cached subtree and the instant it was fetched.
+type SubtreeEntry = (Instant, Subtree);
+
+/// Short-lived cache of descendant sets used to answer `is_ancestor`.
+#[domain_model]
+struct SubtreeCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<SubtreeKey, SubtreeEntry>>,
+}
+
+impl SubtreeCache {
+    fn get(&self, key: &SubtreeKey) -> Option<Subtree> {
+        let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries
+            .get(key)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, subtree)| subtree.clone())
+    }
+
+    fn insert(&self, key: SubtreeKey, subtree: Subtree) {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        // Drop expired entries so the map stays bounded by the live working set
+        entries.retain(|_, (fetched_at, _)| fetched_at.elapsed() < self.ttl);
+        entries.insert(key, (Instant::now(), subtree));
+    }
+}
+
+/// Drops descendants rejected by the status filters along with their subtrees.
+///
+/// Relies on pre-order: a parent is always seen before its children, possibly
+/// in an earlier call (e.g. on an earlier streamed page).
+struct StatusPruner<'a> {
+    include: &'a [TenantStatus],
+    exclude: &'a [TenantStatus],
+    pruned: HashSet<TenantId>,
+}
+
+impl<'a> StatusPruner<'a> {
+    fn new(include: &'a [TenantStatus], exclude: &'a [TenantStatus]) -> Self {
+        Self {
+            include,
+            exclude,
+            pruned: HashSet::new(),
+        }
+    }
+
+    /// Whether `tenant` passes the filters and none of its ancestors was pruned.
+    fn keep<T: HasStatus>(
+        &mut self,
+        tenant: &T,
+        id: TenantId,
+        parent_id: Option<TenantId>,
+    ) -> bool {
+        let keep = parent_id.is_none_or(|p| !self.pruned.contains(&p))
+            && matches_status(tenant, self.include)
+            && !self.exclude.contains(&tenant.status());
+        if !keep {
+            self.pruned.insert(id);
+        }
+        keep
+    }
+}
+
+/// A registered plugin instance considered during selection.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginCandidate {
+    pub gts_id: String,
+    pub vendor: String,
+    /// Lower wins among instances of the same vendor.
+    pub priority: i16,
+}
+
+/// Diagnostic snapshot of the plugin selection; see [`Service::plugin_selection`].
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginSelection {
+    /// Vendors plugins are selected for, most preferred first.
+    pub vendors: Vec<String>,
+    /// GTS ID of the selected instance.
+    pub selected_gts_id: String,
+    /// All registered instances of every vendor, by priority then GTS ID.
+    pub candidates: Vec<PluginCandidate>,
+}
+
 /// Tenant resolver service.
 ///
 /// Discovers plugins via types-registry and delegates API calls.
@@ -38,11 +156,14 @@ const UNAVAILABLE_LOG_THROTTLE: Duration = Duration::from_secs(10);
 #[domain_model]
 pub struct Service {
     hub: Arc<ClientHub>,
-    vendor: String,
+    /// Vendor preference, most preferred first; never empty.
+    vendors: Vec<String>,
     /// Shared selector for plugin instance IDs.
     selector: GtsPluginSelector,
     /// Throttle for plugin unavailable warnings.
     unavailable_log_throttle: ThrottledLog,
+    /// Optional memoization of descendant sets for `is_ancestor`.
+    subtree_cache: Option<SubtreeCache>,
 }
 
 impl Service {
@@ -51,12 +172,38 @@ impl Service {
     pub fn new(hub: Arc<ClientHub>, vendor: String) -> Self {
         Self {
             hub,
-            vendor,
+            vendors: vec![vendor],
             selector: GtsPluginSelector::new(),
             unavailable_log_throttle: ThrottledLog::new(UNAVAILABLE_LOG_THROTTLE),
+            subtree_cache: None,
         }
     }
 
+    /// Fall back to `vendors`, in order, when no instance of the primary
+    /// vendor is registered. A `"*"` entry matches any vendor.
+    #[must_use]
+    pub fn with_fallback_vendors(mut self, vendors: Vec<String>) -> Self {
+        self.vendors.extend(vendors);
+        self
+    }
+
+    /// Answer `is_ancestor` from the ancestor's descendant set, streamed once
+    /// and reused for `ttl`, so a burst of `is_ancestor(X, *)` checks that hit
+    /// the subtree costs a single fetch.
+    ///
+    /// Only positive answers come from the cache: a tenant outside the set is
+    /// checked with the plugin, so unknown tenants still yield
+    /// `TenantNotFound`. Subtrees larger than `MAX_CACHED_SUBTREE_LEN` are not
+    /// cached and every check for that ancestor goes to the plugin.
+    #[must_use]
+    pub fn with_is_ancestor_cache(mut self, ttl: Duration) -> Self {
+        self.subtree_cache = Some(SubtreeCache {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        });
+        self
+    }
+
     /// Lazily resolves and returns the plugin client.
     async fn get_plugin(&self) -> Result<Arc<dyn TenantResolverPluginClient>, DomainError> {
         let instance_id = self.selector.get_or_init(|| self.resolve_plugin()).await?;
@@ -71,10 +218,15 @@ impl Service {
             if self.unavailable_log_throttle.should_log() {
                 tracing::warn!(
                     plugin_gts_id = %instance_id,
-                    vendor = %self.vendor,
+                    vendors = ?self.vendors,
+                    suppressed = self.unavailable_log_throttle.take_suppressed(),
+                    registered = ?self.hub.list_scopes::<dyn TenantResolverPluginClient>(),
                     "Plugin client not registered yet"
                 );
             }
+            // Re-resolve next time in case a different instance has since
+            // registered, unless another caller already has
+            self.selector.invalidate_if_selected(&instance_id);
             Err(DomainError::PluginUnavailable {
                 gts_id: instance_id.to_string(),
                 reason: "client not registered yet".into(),
@@ -82,28 +234,33 @@ impl Service {
         }
     }
 
-    /// Resolves the plugin instance from types-registry.
-    #[tracing::instrument(skip_all, fields(vendor = %self.vendor))]
-    async fn resolve_plugin(&self) -> Result<String, DomainError> {
-        info!("Resolving tenant resolver plugin");
-
+    /// Lists the registered tenant resolver plugin instances.
+    async fn list_plugin_instances(&self) -> Result<Vec<GtsEntity>, DomainError> {
         let registry = self
             .hub
             .get::<dyn TypesRegistryClient>()
-            .map_err(|e| DomainError::TypesRegistryUnavailable(e.to_string()))?;
+            .map_err(DomainError::types_registry_unavailable)?;
 
         let plugin_type_id = TenantResolverPluginSpecV1::gts_schema_id().clone();
 
-        let instances = registry
+        Ok(registry
             .list(
                 ListQuery::new()
                     .with_pattern(format!("{plugin_type_id}*"))
                     .with_is_type(false),
             )
-            .await?;
+            .await?)
+    }
+
+    /// Resolves the plugin instance from types-registry.
+    #[tracing::instrument(skip_all, fields(vendors = ?self.vendors))]
+    async fn resolve_plugin(&self) -> Result<String, DomainError> {
+        info!("Resolving tenant resolver plugin");
+
+        let instances = self.list_plugin_instances().await?;
 
-        let gts_id = choose_plugin_instance::<TenantResolverPluginSpecV1>(
-            &self.vendor,
+        let gts_id = choose_plugin_instance_by_vendor_preference::<TenantResolverPluginSpecV1>(
+            &self.vendors,
             instances.iter().map(|e| (e.gts_id.as_str(), &e.content)),
         )?;
         info!(plugin_gts_id = %gts_id, "Selected tenant resolver plugin instance");
@@ -111,6 +268,48 @@ impl Service {
         Ok(gts_id)
     }
 
+    /// Report which plugin instance is selected and which were considered.
+    ///
+    /// Reuses the memoized selection when there is one, resolving only if no
+    /// instance has been selected yet. Candidates are listed fresh from
+    /// types-registry, so they may include instances registered after the
+    /// selection was made.
+    ///
+    /// # Errors
+    ///
+    /// - Plugin resolution errors
+    /// - `InvalidPluginInstance` if a registered instance is malformed
+    pub async fn plugin_selection(&self) -> Result<PluginSelection, DomainError> {
+        let selected = self.selector.get_or_init(|| self.resolve_plugin()).await?;
+
+        let mut candidates = self
+            .list_plugin_instances()
+            .await?
+            .into_iter()
+            .map(|e| {
+                let plugin: BaseModkitPluginV1<TenantResolverPluginSpecV1> =
+                    serde_json::from_value(e.content).map_err(|err| {
+                        DomainError::InvalidPluginInstance {
+                            gts_id: e.gts_id.clone(),
+                            reason: err.to_string(),
+                        }
+                    })?;
+                Ok(PluginCandidate {
+                    gts_id: e.gts_id,
+                    vendor: plugin.vendor,
+                    priority: plugin.priority,
+                })
+            })
+            .collect::<Result<Vec<_>, DomainError>>()?;
+        candidates.sort_by(|a, b| (a.priority, &a.gts_id).cmp(&(b.priority, &b.gts_id)));
+
+        Ok(PluginSelection {
+            vendors: self.vendors.clone(),
+            selected_gts_id: selected.to_string(),
+            candidates,
+        })
+    }
+
     /// Get tenant information by ID.
     ///
     /// Returns tenant info regardless of status - the consumer can decide
@@ -187,8 +386,61 @@ impl Service {
             .map_err(DomainError::from)
     }
 
+    /// Get the top-most ancestor of a tenant (the tenant itself if root).
+    ///
+    /// # Errors
+    ///
+    /// - `TenantNotFound` if tenant doesn't exist
+    /// - Plugin resolution errors
+    #[tracing::instrument(skip_all, fields(tenant.id = %id))]
+    pub async fn get_root(
+        &self,
+        ctx: &SecurityContext,
+        id: TenantId,
+        options: &GetAncestorsOptions,
+    ) -> Result<TenantInfo, DomainError> {
+        let plugin = self.get_plugin().await?;
+        plugin
+            .get_root(ctx, id, options)
+            .await
+            .map_err(DomainError::from)
+    }
+
+    /// Get how many levels below the root a tenant is (the root has depth 0).
+    ///
+    /// Barriers are ignored so the depth always counts from the real root.
+    ///
+    /// # Errors
+    ///
+    /// - `TenantNotFound` if tenant doesn't exist
+    /// - `Internal` if the plugin reports a chain deeper than `MAX_TENANT_DEPTH`
+    /// - Plugin resolution errors
+    #[tracing::instrument(skip_all, fields(tenant.id = %id))]
+    pub async fn get_tenant_depth(
+        &self,
+        ctx: &SecurityContext,
+        id: TenantId,
+    ) -> Result<usize, DomainError> {
+        let options = GetAncestorsOptions {
+            barrier_mode: BarrierMode::Ignore,
+        };
+        let depth = self.get_ancestors(ctx, id, &options).await?.ancestors.len();
+        if depth > MAX_TENANT_DEPTH {
+            return Err(DomainError::Internal {
+                message: format!(
+                    "tenant {id} is {depth} levels deep, exceeding the limit of {MAX_TENANT_DEPTH}"
+                ),
+                source: None,
+            });
+        }
+        Ok(depth)
+    }
+
     /// Get descendants subtree of the given tenant.
     ///
+    /// Status filters are re-applied to the plugin's response: a descendant
+    /// rejected by `status` or `exclude_status` is dropped with its subtree.
+    ///
     /// # Errors
     ///
     /// - `TenantNotFound` if tenant doesn't exist
@@ -201,14 +453,55 @@ impl Service {
         options: &GetDescendantsOptions,
     ) -> Result<GetDescendantsResponse, DomainError> {
         let plugin = self.get_plugin().await?;
-        plugin
-            .get_descendants(ctx, id, options)
-            .await
-            .map_err(DomainError::from)
+        let mut response = plugin.get_descendants(ctx, id, options).await?;
+        // Applied here as well so every plugin honours the filter
+        if !options.status.is_empty() || !options.exclude_status.is_empty() {
+            let mut pruner = StatusPruner::new(&options.status, &options.exclude_status);
+            response
+                .descendants
+                .retain(|t| pruner.keep(t, t.id, t.parent_id));
+        }
+        Ok(response)
+    }
+
+    /// Stream descendants of the given tenant in pages.
+    ///
+    /// Delegates to the plugin's `stream_descendants` and re-applies the status
+    /// filters like [`Self::get_descendants`]; a pruned tenant's subtree is
+    /// dropped even when it continues on later pages. Pages emptied by the
+    /// filter are skipped.
+    ///
+    /// # Errors
+    ///
+    /// - `TenantNotFound` if tenant doesn't exist
+    /// - Plugin resolution errors
+    pub fn stream_descendants<'a>(
+        &'a self,
+        ctx: &'a SecurityContext,
+        id: TenantId,
+        options: &StreamDescendantsOptions,
+    ) -> BoxStream<'a, Result<Vec<TenantInfo>, DomainError>> {
+        let options = options.clone();
+        async_stream::try_stream! {
+            let plugin = self.get_plugin().await?;
+            let filter = &options.descendants;
+            let mut pruner = StatusPruner::new(&filter.status, &filter.exclude_status);
+            let mut pages = plugin.stream_descendants(ctx, id, &options);
+            while let Some(page) = pages.next().await {
+                let mut page: Vec<TenantInfo> = page?;
+                page.retain(|t| pruner.keep(t, t.id, t.parent_id));
+                if !page.is_empty() {
+                    yield page;
+                }
+            }
+        }
+        .boxed()
     }
 
     /// Check if `ancestor_id` is an ancestor of `descendant_id`.
     ///
+    /// See [`Self::with_is_ancestor_cache`] for the memoized variant.
+    ///
     /// # Errors
     ///
     /// - `TenantNotFound` if either tenant doesn't exist
@@ -222,9 +515,61 @@ impl Service {
         options: &IsAncestorOptions,
     ) -> Result<bool, DomainError> {
         let plugin = self.get_plugin().await?;
+
+        if let Some(cache) = &self.subtree_cache {
+            let key = (ctx.subject_id(), ancestor_id, options.barrier_mode);
+            let subtree = if let Some(subtree) = cache.get(&key) {
+                subtree
+            } else {
+                let subtree =
+                    Self::fetch_subtree(plugin.as_ref(), ctx, ancestor_id, options).await?;
+                cache.insert(key, subtree.clone());
+                subtree
+            };
+            if let Subtree::Known(ids) = subtree
+                && ids.contains(&descendant_id)
+            {
+                return Ok(true);
+            }
+        }
+
         plugin
             .is_ancestor(ctx, ancestor_id, descendant_id, options)
             .await
             .map_err(DomainError::from)
     }
+
+    /// Collect the descendant IDs of `ancestor_id`, giving up once the subtree
+    /// grows past `MAX_CACHED_SUBTREE_LEN`.
+    ///
+    /// Barrier handling matches `is_ancestor`: with `Respect`, barrier tenants
+    /// and their subtrees are not descendants.
+    async fn fetch_subtree(
+        plugin: &dyn TenantResolverPluginClient,
+        ctx: &SecurityContext,
+        ancestor_id: TenantId,
+        options: &IsAncestorOptions,
+    ) -> Result<Subtree, DomainError> {
+        let stream_options = StreamDescendantsOptions {
+            descendants: GetDescendantsOptions {
+                barrier_mode: options.barrier_mode,
+                ..GetDescendantsOptions::default()
+            },
+            ..StreamDescendantsOptions::default()
+        };
+        let mut subtree = HashSet::new();
+        let mut pages = plugin.stream_descendants(ctx, ancestor_id, &stream_options);
+        while let Some(page) = pages.next().await {
+            subtree.extend(page?.into_iter().map(|t| t.id));
+            if subtree.len() > MAX_CACHED_SUBTREE_LEN {
+                return Ok(Subtree::TooLarge);
+            }
+        }
+        Ok(Subtree::Known(Arc::new(subtree)))
+    }
 }
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[path = "service_tests.rs"]
+mod service_tests;
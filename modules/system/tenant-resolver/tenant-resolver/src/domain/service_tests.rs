@@ -0,0 +1,727 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use modkit::client_hub::{ClientHub, ClientScope};
+use tenant_resolver_sdk::{TenantRef, TenantResolverError, TenantStatus};
+use types_registry_sdk::{GtsEntity, RegisterResult, TypesRegistryError};
+use uuid::Uuid;
+
+use super::*;
+
+// ── mocks ────────────────────────────────────────────────────────────────
+
+struct MockRegistry {
+    instances: Vec<GtsEntity>,
+}
+
+#[async_trait]
+impl TypesRegistryClient for MockRegistry {
+    async fn list(&self, _query: ListQuery) -> Result<Vec<GtsEntity>, TypesRegistryError> {
+        Ok(self.instances.clone())
+    }
+
+    async fn get(&self, gts_id: &str) -> Result<GtsEntity, TypesRegistryError> {
+        self.instances
+            .iter()
+            .find(|e| e.gts_id == gts_id)
+            .cloned()
+            .ok_or_else(|| TypesRegistryError::not_found(gts_id))
+    }
+
+    async fn register(
+        &self,
+        _entities: Vec<serde_json::Value>,
+    ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+        Ok(vec![])
+    }
+
+    async fn register_atomic(
+        &self,
+        _entities: Vec<serde_json::Value>,
+    ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+        Ok(vec![])
+    }
+}
+
+/// Plugin serving a fixed root → child → grandchild chain, plus any
+/// `extra_children` of the root and a `deep_chain` nested one below another
+/// under the grandchild, and counting hierarchy calls.
+struct CountingPlugin {
+    root: TenantId,
+    child: TenantId,
+    grandchild: TenantId,
+    extra_children: Mutex<HashSet<TenantId>>,
+    deep_chain: Mutex<Vec<TenantId>>,
+    suspended: Mutex<HashSet<TenantId>>,
+    descendants_calls: AtomicUsize,
+    is_ancestor_calls: AtomicUsize,
+}
+
+impl CountingPlugin {
+    fn parent_of(&self, id: TenantId) -> Option<TenantId> {
+        if id == self.child || self.extra_children.lock().unwrap().contains(&id) {
+            Some(self.root)
+        } else if id == self.grandchild {
+            Some(self.child)
+        } else {
+            let chain = self.deep_chain.lock().unwrap();
+            match chain.iter().position(|t| *t == id)? {
+                0 => Some(self.grandchild),
+                i => Some(chain[i - 1]),
+            }
+        }
+    }
+
+    fn find(&self, id: TenantId) -> Result<TenantInfo, TenantResolverError> {
+        if id != self.root && self.parent_of(id).is_none() {
+            return Err(TenantResolverError::TenantNotFound { tenant_id: id });
+        }
+        Ok(TenantInfo {
+            id,
+            name: id.to_string(),
+            status: if self.suspended.lock().unwrap().contains(&id) {
+                TenantStatus::Suspended
+            } else {
+                TenantStatus::Active
+            },
+            tenant_type: None,
+            parent_id: self.parent_of(id),
+            self_managed: false,
+        })
+    }
+
+    fn tenant_ref(&self, id: TenantId) -> Result<TenantRef, TenantResolverError> {
+        self.find(id).map(TenantRef::from)
+    }
+}
+
+#[async_trait]
+impl TenantResolverPluginClient for CountingPlugin {
+    async fn get_tenant(
+        &self,
+        _ctx: &SecurityContext,
+        id: TenantId,
+    ) -> Result<TenantInfo, TenantResolverError> {
+        self.find(id)
+    }
+
+    async fn get_root_tenant(
+        &self,
+        _ctx: &SecurityContext,
+    ) -> Result<TenantInfo, TenantResolverError> {
+        self.find(self.root)
+    }
+
+    async fn get_tenants(
+        &self,
+        _ctx: &SecurityContext,
+        ids: &[TenantId],
+        _options: &GetTenantsOptions,
+    ) -> Result<Vec<TenantInfo>, TenantResolverError> {
+        Ok(ids.iter().filter_map(|id| self.find(*id).ok()).collect())
+    }
+
+    async fn get_ancestors(
+        &self,
+        _ctx: &SecurityContext,
+        id: TenantId,
+        _options: &GetAncestorsOptions,
+    ) -> Result<GetAncestorsResponse, TenantResolverError> {
+        let tenant = self.tenant_ref(id)?;
+        let mut ancestors = Vec::new();
+        let mut current = tenant.parent_id;
+        while let Some(parent) = current {
+            let parent = self.tenant_ref(parent)?;
+            current = parent.parent_id;
+            ancestors.push(parent);
+        }
+        Ok(GetAncestorsResponse { tenant, ancestors })
+    }
+
+    async fn get_descendants(
+        &self,
+        _ctx: &SecurityContext,
+        id: TenantId,
+        _options: &GetDescendantsOptions,
+    ) -> Result<GetDescendantsResponse, TenantResolverError> {
+        self.descendants_calls.fetch_add(1, Ordering::SeqCst);
+        let extra_children: Vec<TenantId> = self
+            .extra_children
+            .lock()
+            .unwrap()
+            .iter()
+            .copied()
+            .collect();
+        let descendants = [self.child, self.grandchild]
+            .into_iter()
+            .chain(extra_children)
+            .filter(|t| {
+                let mut current = self.parent_of(*t);
+                while let Some(parent) = current {
+                    if parent == id {
+                        return true;
+                    }
+                    current = self.parent_of(parent);
+                }
+                false
+            })
+            .map(|t| self.tenant_ref(t))
+            .collect::<Result<_, _>>()?;
+        Ok(GetDescendantsResponse {
+            tenant: self.tenant_ref(id)?,
+            descendants,
+        })
+    }
+
+    async fn is_ancestor(
+        &self,
+        _ctx: &SecurityContext,
+        ancestor_id: TenantId,
+        descendant_id: TenantId,
+        _options: &IsAncestorOptions,
+    ) -> Result<bool, TenantResolverError> {
+        self.is_ancestor_calls.fetch_add(1, Ordering::SeqCst);
+        self.find(ancestor_id)?;
+        let mut current = self.find(descendant_id)?.parent_id;
+        while let Some(parent) = current {
+            if parent == ancestor_id {
+                return Ok(true);
+            }
+            current = self.parent_of(parent);
+        }
+        Ok(false)
+    }
+}
+
+// ── helpers ──────────────────────────────────────────────────────────────
+
+fn test_ctx() -> SecurityContext {
+    SecurityContext::builder()
+        .subject_id(Uuid::nil())
+        .subject_tenant_id(Uuid::nil())
+        .build()
+        .unwrap()
+}
+
+fn plugin_entity(suffix: &str, vendor: &str, priority: i16) -> GtsEntity {
+    let gts_id = format!("{}{suffix}", TenantResolverPluginSpecV1::gts_schema_id());
+    GtsEntity {
+        id: Uuid::nil(),
+        gts_id: gts_id.clone(),
+        segments: vec![],
+        is_schema: false,
+        content: serde_json::json!({
+            "id": gts_id,
+            "vendor": vendor,
+            "priority": priority,
+            "properties": {}
+        }),
+        description: None,
+    }
+}
+
+fn wired_hub() -> (Arc<ClientHub>, Arc<CountingPlugin>) {
+    wired_hub_with(Vec::new())
+}
+
+/// Like [`wired_hub`], with `extra` plugin instances registered alongside the mock.
+fn wired_hub_with(extra: Vec<GtsEntity>) -> (Arc<ClientHub>, Arc<CountingPlugin>) {
+    let entity = plugin_entity("test._.mock.v1", "hyperspot", 0);
+    let instance_id = entity.gts_id.clone();
+    let hub = Arc::new(ClientHub::default());
+
+    hub.register::<dyn TypesRegistryClient>(Arc::new(MockRegistry {
+        instances: std::iter::once(entity).chain(extra).collect(),
+    }) as Arc<dyn TypesRegistryClient>);
+
+    let plugin = Arc::new(CountingPlugin {
+        root: TenantId(Uuid::new_v4()),
+        child: TenantId(Uuid::new_v4()),
+        grandchild: TenantId(Uuid::new_v4()),
+        extra_children: Mutex::new(HashSet::new()),
+        deep_chain: Mutex::new(Vec::new()),
+        suspended: Mutex::new(HashSet::new()),
+        descendants_calls: AtomicUsize::new(0),
+        is_ancestor_calls: AtomicUsize::new(0),
+    });
+    hub.register_scoped::<dyn TenantResolverPluginClient>(
+        ClientScope::gts_id(&instance_id),
+        plugin.clone() as Arc<dyn TenantResolverPluginClient>,
+    );
+
+    (hub, plugin)
+}
+
+// ── is_ancestor cache ────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn cached_is_ancestor_fetches_subtree_once_per_ancestor() {
+    let (hub, plugin) = wired_hub();
+    let svc =
+        Service::new(hub, "hyperspot".to_owned()).with_is_ancestor_cache(Duration::from_mins(1));
+    let ctx = test_ctx();
+    let opts = IsAncestorOptions::default();
+
+    for i in 0..10 {
+        let descendant = if i % 2 == 0 {
+            plugin.child
+        } else {
+            plugin.grandchild
+        };
+        assert!(
+            svc.is_ancestor(&ctx, plugin.root, descendant, &opts)
+                .await
+                .unwrap()
+        );
+    }
+
+    assert_eq!(plugin.descendants_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(plugin.is_ancestor_calls.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn cached_is_ancestor_asks_plugin_outside_the_subtree() {
+    let (hub, plugin) = wired_hub();
+    let svc =
+        Service::new(hub, "hyperspot".to_owned()).with_is_ancestor_cache(Duration::from_mins(1));
+    let ctx = test_ctx();
+    let opts = IsAncestorOptions::default();
+
+    assert!(
+        !svc.is_ancestor(&ctx, plugin.root, plugin.root, &opts)
+            .await
+            .unwrap(),
+        "a tenant is not its own ancestor"
+    );
+    let unknown = TenantId(Uuid::new_v4());
+    let err = svc
+        .is_ancestor(&ctx, plugin.root, unknown, &opts)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, DomainError::TenantNotFound { tenant_id } if tenant_id == unknown.0),
+        "unexpected error: {err:?}"
+    );
+
+    assert_eq!(plugin.descendants_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(plugin.is_ancestor_calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn cached_is_ancestor_skips_subtrees_over_the_limit() {
+    let (hub, plugin) = wired_hub();
+    plugin
+        .extra_children
+        .lock()
+        .unwrap()
+        .extend((0..MAX_CACHED_SUBTREE_LEN).map(|_| TenantId(Uuid::new_v4())));
+    let svc =
+        Service::new(hub, "hyperspot".to_owned()).with_is_ancestor_cache(Duration::from_mins(1));
+    let ctx = test_ctx();
+    let opts = IsAncestorOptions::default();
+
+    for _ in 0..3 {
+        assert!(
+            svc.is_ancestor(&ctx, plugin.root, plugin.child, &opts)
+                .await
+                .unwrap()
+        );
+    }
+
+    assert_eq!(plugin.descendants_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(plugin.is_ancestor_calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn cached_is_ancestor_refetches_after_ttl() {
+    let (hub, plugin) = wired_hub();
+    let svc = Service::new(hub, "hyperspot".to_owned()).with_is_ancestor_cache(Duration::ZERO);
+    let ctx = test_ctx();
+    let opts = IsAncestorOptions::default();
+
+    for _ in 0..3 {
+        assert!(
+            svc.is_ancestor(&ctx, plugin.root, plugin.child, &opts)
+                .await
+                .unwrap()
+        );
+    }
+
+    assert_eq!(plugin.descendants_calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn uncached_is_ancestor_delegates_to_plugin() {
+    let (hub, plugin) = wired_hub();
+    let svc = Service::new(hub, "hyperspot".to_owned());
+    let ctx = test_ctx();
+    let opts = IsAncestorOptions::default();
+
+    for _ in 0..10 {
+        assert!(
+            svc.is_ancestor(&ctx, plugin.root, plugin.child, &opts)
+                .await
+                .unwrap()
+        );
+    }
+
+    assert_eq!(plugin.is_ancestor_calls.load(Ordering::SeqCst), 10);
+    assert_eq!(plugin.descendants_calls.load(Ordering::SeqCst), 0);
+}
+
+// ── get_root ─────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn get_root_of_deep_tenant_returns_top_most_ancestor() {
+    let (hub, plugin) = wired_hub();
+    let svc = Service::new(hub, "hyperspot".to_owned());
+
+    let root = svc
+        .get_root(
+            &test_ctx(),
+            plugin.grandchild,
+            &GetAncestorsOptions::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(root.id, plugin.root);
+    assert_eq!(root.parent_id, None);
+}
+
+#[tokio::test]
+async fn get_root_of_root_returns_itself() {
+    let (hub, plugin) = wired_hub();
+    let svc = Service::new(hub, "hyperspot".to_owned());
+
+    let root = svc
+        .get_root(&test_ctx(), plugin.root, &GetAncestorsOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(root.id, plugin.root);
+}
+
+#[tokio::test]
+async fn get_root_of_unknown_tenant_is_not_found() {
+    let (hub, _plugin) = wired_hub();
+    let svc = Service::new(hub, "hyperspot".to_owned());
+    let unknown = TenantId(Uuid::new_v4());
+
+    let err = svc
+        .get_root(&test_ctx(), unknown, &GetAncestorsOptions::default())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, DomainError::TenantNotFound { tenant_id } if tenant_id == unknown.0));
+}
+
+// ── get_tenant_depth ─────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn root_tenant_has_depth_zero() {
+    let (hub, plugin) = wired_hub();
+    let svc = Service::new(hub, "hyperspot".to_owned());
+
+    let depth = svc
+        .get_tenant_depth(&test_ctx(), plugin.root)
+        .await
+        .unwrap();
+
+    assert_eq!(depth, 0);
+}
+
+#[tokio::test]
+async fn grandchild_tenant_has_depth_two() {
+    let (hub, plugin) = wired_hub();
+    let svc = Service::new(hub, "hyperspot".to_owned());
+    let ctx = test_ctx();
+
+    assert_eq!(svc.get_tenant_depth(&ctx, plugin.child).await.unwrap(), 1);
+    assert_eq!(
+        svc.get_tenant_depth(&ctx, plugin.grandchild).await.unwrap(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn tenant_depth_over_the_limit_is_internal_error() {
+    let (hub, plugin) = wired_hub();
+    let svc = Service::new(hub, "hyperspot".to_owned());
+    let ctx = test_ctx();
+    // Grandchild is at depth 2, so the chain's last tenant is at the limit.
+    let chain: Vec<TenantId> = (0..MAX_TENANT_DEPTH - 2)
+        .map(|_| TenantId(Uuid::new_v4()))
+        .collect();
+    let at_limit = *chain.last().unwrap();
+    plugin.deep_chain.lock().unwrap().extend(chain);
+
+    assert_eq!(
+        svc.get_tenant_depth(&ctx, at_limit).await.unwrap(),
+        MAX_TENANT_DEPTH
+    );
+
+    let too_deep = TenantId(Uuid::new_v4());
+    plugin.deep_chain.lock().unwrap().push(too_deep);
+    let err = svc.get_tenant_depth(&ctx, too_deep).await.unwrap_err();
+
+    assert!(
+        matches!(err, DomainError::Internal { ref message, .. } if message.contains("exceeding the limit")),
+        "expected Internal error, got: {err:?}"
+    );
+}
+
+// ── get_descendants status filter ────────────────────────────────────────
+
+fn exclude_suspended() -> GetDescendantsOptions {
+    GetDescendantsOptions {
+        exclude_status: vec![TenantStatus::Suspended],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn excluding_suspended_prunes_tenant_and_its_subtree() {
+    let (hub, plugin) = wired_hub();
+    plugin.suspended.lock().unwrap().insert(plugin.child);
+    let svc = Service::new(hub, "hyperspot".to_owned());
+
+    let response = svc
+        .get_descendants(&test_ctx(), plugin.root, &exclude_suspended())
+        .await
+        .unwrap();
+
+    assert_eq!(response.tenant.id, plugin.root);
+    assert!(
+        response.descendants.is_empty(),
+        "suspended child and its active grandchild must both be dropped"
+    );
+}
+
+#[tokio::test]
+async fn excluding_suspended_keeps_active_ancestors() {
+    let (hub, plugin) = wired_hub();
+    plugin.suspended.lock().unwrap().insert(plugin.grandchild);
+    let svc = Service::new(hub, "hyperspot".to_owned());
+
+    let response = svc
+        .get_descendants(&test_ctx(), plugin.root, &exclude_suspended())
+        .await
+        .unwrap();
+
+    let ids: Vec<TenantId> = response.descendants.iter().map(|t| t.id).collect();
+    assert_eq!(ids, vec![plugin.child]);
+}
+
+#[tokio::test]
+async fn status_filter_does_not_apply_to_starting_tenant() {
+    let (hub, plugin) = wired_hub();
+    plugin.suspended.lock().unwrap().insert(plugin.child);
+    let svc = Service::new(hub, "hyperspot".to_owned());
+
+    let response = svc
+        .get_descendants(&test_ctx(), plugin.child, &exclude_suspended())
+        .await
+        .unwrap();
+
+    assert_eq!(response.tenant.id, plugin.child);
+    assert_eq!(response.tenant.status, TenantStatus::Suspended);
+    assert_eq!(response.descendants.len(), 1);
+}
+
+// ── stream_descendants ───────────────────────────────────────────────────
+
+#[tokio::test]
+async fn stream_descendants_pages_follow_pre_order() {
+    let (hub, plugin) = wired_hub();
+    let svc = Service::new(hub, "hyperspot".to_owned());
+    let options = StreamDescendantsOptions {
+        page_size: 1,
+        ..Default::default()
+    };
+
+    let pages: Vec<Vec<TenantInfo>> = svc
+        .stream_descendants(&test_ctx(), plugin.root, &options)
+        .try_collect()
+        .await
+        .unwrap();
+
+    let ids: Vec<Vec<TenantId>> = pages
+        .iter()
+        .map(|page| page.iter().map(|t| t.id).collect())
+        .collect();
+    assert_eq!(ids, vec![vec![plugin.child], vec![plugin.grandchild]]);
+}
+
+#[tokio::test]
+async fn stream_descendants_prunes_subtree_across_pages() {
+    let (hub, plugin) = wired_hub();
+    plugin.suspended.lock().unwrap().insert(plugin.child);
+    let svc = Service::new(hub, "hyperspot".to_owned());
+    let options = StreamDescendantsOptions {
+        descendants: exclude_suspended(),
+        page_size: 1,
+    };
+
+    let pages: Vec<Vec<TenantInfo>> = svc
+        .stream_descendants(&test_ctx(), plugin.root, &options)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert!(
+        pages.is_empty(),
+        "grandchild on the next page must be dropped with its suspended parent"
+    );
+}
+
+#[tokio::test]
+async fn streamed_and_collected_descendants_prune_alike() {
+    let (hub, plugin) = wired_hub();
+    let sibling = TenantId(Uuid::new_v4());
+    plugin.extra_children.lock().unwrap().insert(sibling);
+    plugin.suspended.lock().unwrap().insert(plugin.child);
+    let svc = Service::new(hub, "hyperspot".to_owned());
+    let options = StreamDescendantsOptions {
+        descendants: exclude_suspended(),
+        page_size: 1,
+    };
+
+    let collected: HashSet<TenantId> = svc
+        .get_descendants(&test_ctx(), plugin.root, &options.descendants)
+        .await
+        .unwrap()
+        .descendants
+        .iter()
+        .map(|t| t.id)
+        .collect();
+    let pages: Vec<Vec<TenantInfo>> = svc
+        .stream_descendants(&test_ctx(), plugin.root, &options)
+        .try_collect()
+        .await
+        .unwrap();
+    let streamed: HashSet<TenantId> = pages.iter().flatten().map(|t| t.id).collect();
+
+    assert_eq!(collected, HashSet::from([sibling]));
+    assert_eq!(streamed, collected);
+}
+
+#[tokio::test]
+async fn stream_descendants_of_unknown_tenant_is_not_found() {
+    let (hub, _plugin) = wired_hub();
+    let svc = Service::new(hub, "hyperspot".to_owned());
+    let unknown = TenantId(Uuid::new_v4());
+    let ctx = test_ctx();
+
+    let mut pages = svc.stream_descendants(&ctx, unknown, &StreamDescendantsOptions::default());
+
+    let err = pages.try_next().await.unwrap_err();
+    assert!(matches!(err, DomainError::TenantNotFound { tenant_id } if tenant_id == unknown.0));
+}
+
+// ── plugin selection diagnostics ─────────────────────────────────────────
+
+#[tokio::test]
+async fn plugin_selection_reports_selected_instance_and_candidates() {
+    let (hub, plugin) = wired_hub_with(vec![
+        plugin_entity("test._.fallback.v1", "hyperspot", 10),
+        plugin_entity("test._.other.v1", "acme", -5),
+    ]);
+    let svc = Service::new(hub, "hyperspot".to_owned());
+
+    svc.get_tenant(&test_ctx(), plugin.root).await.unwrap();
+    let selection = svc.plugin_selection().await.unwrap();
+
+    let mock = plugin_entity("test._.mock.v1", "hyperspot", 0);
+    assert_eq!(selection.vendors, vec!["hyperspot"]);
+    assert_eq!(selection.selected_gts_id, mock.gts_id);
+    let candidates: Vec<(&str, &str, i16)> = selection
+        .candidates
+        .iter()
+        .map(|c| {
+            (
+                c.gts_id.rsplit('~').next().unwrap(),
+                c.vendor.as_str(),
+                c.priority,
+            )
+        })
+        .collect();
+    assert_eq!(
+        candidates,
+        vec![
+            ("test._.other.v1", "acme", -5),
+            ("test._.mock.v1", "hyperspot", 0),
+            ("test._.fallback.v1", "hyperspot", 10),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn plugin_selection_reuses_memoized_instance() {
+    let (hub, _plugin) = wired_hub();
+    let svc = Service {
+        selector: GtsPluginSelector::pre_cached("memoized-instance".to_owned()),
+        ..Service::new(hub, "hyperspot".to_owned())
+    };
+
+    let selection = svc.plugin_selection().await.unwrap();
+
+    assert_eq!(selection.selected_gts_id, "memoized-instance");
+    assert_eq!(selection.candidates.len(), 1);
+}
+
+// ── vendor preference ────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn falls_back_to_next_vendor_when_preferred_is_absent() {
+    let (hub, plugin) = wired_hub();
+    let svc =
+        Service::new(hub, "acme".to_owned()).with_fallback_vendors(vec!["hyperspot".to_owned()]);
+
+    svc.get_tenant(&test_ctx(), plugin.root).await.unwrap();
+    let selection = svc.plugin_selection().await.unwrap();
+
+    assert_eq!(selection.vendors, vec!["acme", "hyperspot"]);
+    assert_eq!(
+        selection.selected_gts_id,
+        plugin_entity("test._.mock.v1", "hyperspot", 0).gts_id
+    );
+}
+
+#[tokio::test]
+async fn plugin_not_found_when_no_preferred_vendor_is_present() {
+    let (hub, plugin) = wired_hub();
+    let svc = Service::new(hub, "acme".to_owned()).with_fallback_vendors(vec!["globex".to_owned()]);
+
+    let err = svc.get_tenant(&test_ctx(), plugin.root).await.unwrap_err();
+
+    assert!(
+        matches!(err, DomainError::PluginNotFound { ref vendor } if vendor == "acme, globex"),
+        "unexpected error: {err:?}"
+    );
+}
+
+// ── error causes ─────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn missing_types_registry_keeps_client_hub_error_as_source() {
+    let svc = Service::new(Arc::new(ClientHub::default()), "hyperspot".to_owned());
+
+    let err = svc
+        .get_tenant(&test_ctx(), TenantId(Uuid::nil()))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, DomainError::TypesRegistryUnavailable { .. }));
+    let source = std::error::Error::source(&err).expect("cause should be preserved");
+    assert!(
+        source
+            .downcast_ref::<modkit::client_hub::ClientHubError>()
+            .is_some()
+    );
+    assert_eq!(
+        err.to_string(),
+        format!("types registry is not available: {source}")
+    );
+}
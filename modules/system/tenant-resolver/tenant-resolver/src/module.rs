@@ -50,7 +50,7 @@ impl Module for TenantResolver {
     async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
         let cfg: TenantResolverConfig = ctx.config_or_default()?;
         tracing::Span::current().record("vendor", cfg.vendor.as_str());
-        info!(vendor = %cfg.vendor);
+        info!(vendor = %cfg.vendor, fallback_vendors = ?cfg.fallback_vendors);
 
         // Register plugin schema in types-registry
         let registry = ctx.client_hub().get::<dyn TypesRegistryClient>()?;
@@ -75,7 +75,11 @@ impl Module for TenantResolver {
 
         // Create service
         let hub = ctx.client_hub();
-        let svc = Arc::new(Service::new(hub, cfg.vendor));
+        let mut svc = Service::new(hub, cfg.vendor).with_fallback_vendors(cfg.fallback_vendors);
+        if let Some(ttl) = cfg.is_ancestor_cache_ttl {
+            svc = svc.with_is_ancestor_cache(ttl);
+        }
+        let svc = Arc::new(svc);
         self.service
             .set(svc.clone())
             .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
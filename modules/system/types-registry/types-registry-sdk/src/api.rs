@@ -3,10 +3,14 @@
 //! This trait defines the public API for the `types-registry` module.
 //! GTS schemas and instances are global resources, so no security context is required.
 
+use std::collections::HashMap;
+use std::time::Duration;
+
 use async_trait::async_trait;
 
 use crate::error::TypesRegistryError;
-use crate::models::{GtsEntity, ListQuery, RegisterResult};
+use crate::models::{GtsEntity, ListQuery, RegisterResult, TypeSchema};
+use crate::refs;
 
 /// Public API trait for the `types-registry` module.
 ///
@@ -63,6 +67,94 @@ pub trait TypesRegistryClient: Send + Sync {
         entities: Vec<serde_json::Value>,
     ) -> Result<Vec<RegisterResult>, TypesRegistryError>;
 
+    /// Register GTS entities in batch, all-or-nothing.
+    ///
+    /// Behaves like [`register`](Self::register), except that if any entity
+    /// fails none of the batch is persisted. The failing entity reports its
+    /// own error and every other entity reports
+    /// [`TypesRegistryError::RolledBack`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` only for catastrophic failures (e.g., database unavailable).
+    /// Per-item errors are returned in the `RegisterResult::Err` variant.
+    async fn register_atomic(
+        &self,
+        entities: Vec<serde_json::Value>,
+    ) -> Result<Vec<RegisterResult>, TypesRegistryError>;
+
+    /// Register GTS entities in batch, replacing differing content.
+    ///
+    /// Behaves like [`register`](Self::register), except that an entity whose
+    /// GTS ID is already registered with different content replaces the
+    /// stored one instead of failing with `AlreadyExists`.
+    ///
+    /// The default implementation reports that overwriting is not supported.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` only for catastrophic failures or if the registry does
+    /// not support overwriting. Per-item errors are returned in the
+    /// `RegisterResult::Err` variant.
+    async fn register_overwrite(
+        &self,
+        _entities: Vec<serde_json::Value>,
+    ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+        Err(TypesRegistryError::internal(
+            "overwriting registration is not supported by this registry client",
+        ))
+    }
+
+    /// Register GTS instances that expire unless refreshed within `ttl`.
+    ///
+    /// Behaves like [`register`](Self::register), but every registered
+    /// instance gets a lease: once `ttl` elapses without a
+    /// [`touch`](Self::touch) or another registration with a TTL, the instance
+    /// is hidden from [`get`](Self::get) and [`list`](Self::list) and removed
+    /// by the registry's background sweep. Registering an instance again
+    /// without a TTL makes it permanent. Types cannot expire; passing a type
+    /// fails that entity with `ValidationFailed`.
+    ///
+    /// The default implementation reports that expiry is not supported.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Heartbeat: re-register (or touch) more often than the TTL
+    /// registry.register_with_ttl(vec![instance], Duration::from_secs(30)).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` only for catastrophic failures or if the registry does
+    /// not support expiry. Per-item errors are returned in the
+    /// `RegisterResult::Err` variant.
+    async fn register_with_ttl(
+        &self,
+        _entities: Vec<serde_json::Value>,
+        _ttl: Duration,
+    ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+        Err(TypesRegistryError::internal(
+            "instance expiry is not supported by this registry client",
+        ))
+    }
+
+    /// Extend the lease of an instance registered with a TTL.
+    ///
+    /// The instance expires one full TTL after the call. Touching an entity
+    /// registered without a TTL is a no-op.
+    ///
+    /// The default implementation reports that expiry is not supported.
+    ///
+    /// # Errors
+    ///
+    /// * `NotFound` - If the entity does not exist or has already expired
+    async fn touch(&self, _gts_id: &str) -> Result<(), TypesRegistryError> {
+        Err(TypesRegistryError::internal(
+            "instance expiry is not supported by this registry client",
+        ))
+    }
+
     /// List GTS entities with optional filtering.
     ///
     /// # Arguments
@@ -71,7 +163,8 @@ pub trait TypesRegistryClient: Send + Sync {
     ///
     /// # Returns
     ///
-    /// A vector of `GtsEntity` objects matching the query.
+    /// A vector of `GtsEntity` objects matching the query. Expired instances
+    /// are left out unless `query.include_expired` is set.
     async fn list(&self, query: ListQuery) -> Result<Vec<GtsEntity>, TypesRegistryError>;
 
     /// Retrieve a single GTS entity by its identifier.
@@ -89,4 +182,321 @@ pub trait TypesRegistryClient: Send + Sync {
     /// * `NotFound` - If no entity with the given GTS ID exists
     /// * `InvalidGtsId` - If the GTS ID format is invalid
     async fn get(&self, gts_id: &str) -> Result<GtsEntity, TypesRegistryError>;
+
+    /// Retrieve several GTS entities in one call.
+    ///
+    /// The returned vector is aligned with `gts_ids`: position `i` holds the
+    /// entity for `gts_ids[i]`, or the error [`get`](Self::get) would have
+    /// returned for it (e.g. `NotFound`). Duplicate IDs are looked up once per
+    /// occurrence.
+    ///
+    /// The default implementation calls [`get`](Self::get) for each ID;
+    /// registry clients override it to resolve the whole batch in one round trip.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let results = registry.get_many(&[order_type, missing_type]).await?;
+    /// assert!(results[0].is_ok());
+    /// assert!(results[1].as_ref().is_err_and(TypesRegistryError::is_not_found));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` only for catastrophic failures (e.g., database unavailable).
+    /// Per-ID errors are returned in the corresponding position.
+    async fn get_many(
+        &self,
+        gts_ids: &[String],
+    ) -> Result<Vec<Result<GtsEntity, TypesRegistryError>>, TypesRegistryError> {
+        let mut results = Vec::with_capacity(gts_ids.len());
+        for gts_id in gts_ids {
+            results.push(self.get(gts_id).await);
+        }
+        Ok(results)
+    }
+
+    /// Retrieve a type schema with every GTS `$ref` replaced by the referenced schema.
+    ///
+    /// `$ref` values naming a GTS type (`gts.…~` or `gts://gts.…~`) are fetched
+    /// with [`get`](Self::get) and inlined recursively; other references, such as
+    /// local `#/definitions/…` pointers, are left as they are. Inlined copies drop
+    /// their `$id` and `$schema`, and keywords next to a `$ref` take precedence
+    /// over the referenced schema's. A `$ref` back to `gts_id` itself becomes the
+    /// local reference `#`, so recursive types resolve too.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let schema = registry.resolve_refs("gts.acme.core.events.order_placed.v1~").await?;
+    /// // No `$ref: "gts://gts.acme.core.models.address.v1~"` left in `schema`
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `NotFound` - If `gts_id` or any type it references is not registered
+    /// * `InvalidGtsId` - If `gts_id` or a referenced GTS ID is not a type
+    /// * `ValidationFailed` - If the references form a cycle that does not pass
+    ///   through `gts_id`
+    async fn resolve_refs(&self, gts_id: &str) -> Result<TypeSchema, TypesRegistryError> {
+        let mut schemas = HashMap::new();
+        let mut pending = vec![gts_id.to_owned()];
+        while let Some(id) = pending.pop() {
+            if schemas.contains_key(&id) {
+                continue;
+            }
+            let entity = self.get(&id).await?;
+            if !entity.is_schema {
+                return Err(TypesRegistryError::invalid_gts_id(format!(
+                    "{id} is not a type"
+                )));
+            }
+            refs::collect_refs(&entity.content, &mut pending);
+            schemas.insert(id, entity.content);
+        }
+
+        refs::Inliner::new(gts_id, &schemas)
+            .inline(&schemas[gts_id])
+            .map(TypeSchema::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    /// Registry serving a fixed set of entities by GTS ID.
+    struct MockRegistry {
+        entities: HashMap<String, GtsEntity>,
+    }
+
+    impl MockRegistry {
+        fn with_types(types: &[serde_json::Value]) -> Self {
+            let entities = types
+                .iter()
+                .map(|content| {
+                    let gts_id = content["$id"]
+                        .as_str()
+                        .unwrap()
+                        .trim_start_matches("gts://")
+                        .to_owned();
+                    let entity = GtsEntity::new(
+                        Uuid::nil(),
+                        gts_id.clone(),
+                        vec![],
+                        true,
+                        content.clone(),
+                        None,
+                    );
+                    (gts_id, entity)
+                })
+                .collect();
+            Self { entities }
+        }
+    }
+
+    #[async_trait]
+    impl TypesRegistryClient for MockRegistry {
+        async fn register(
+            &self,
+            _entities: Vec<serde_json::Value>,
+        ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+            unimplemented!()
+        }
+
+        async fn register_atomic(
+            &self,
+            _entities: Vec<serde_json::Value>,
+        ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+            unimplemented!()
+        }
+
+        async fn list(&self, _query: ListQuery) -> Result<Vec<GtsEntity>, TypesRegistryError> {
+            unimplemented!()
+        }
+
+        async fn get(&self, gts_id: &str) -> Result<GtsEntity, TypesRegistryError> {
+            self.entities
+                .get(gts_id)
+                .cloned()
+                .ok_or_else(|| TypesRegistryError::not_found(gts_id))
+        }
+    }
+
+    fn address_type() -> serde_json::Value {
+        json!({
+            "$id": "gts://gts.acme.core.models.address.v1~",
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "properties": { "city": { "type": "string" } }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_resolve_refs_inlines_registered_type() {
+        let registry = MockRegistry::with_types(&[
+            address_type(),
+            json!({
+                "$id": "gts://gts.acme.core.models.customer.v1~",
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {
+                    "billing": { "$ref": "gts://gts.acme.core.models.address.v1~" },
+                    "shipping": { "$ref": "gts.acme.core.models.address.v1~" },
+                    "tags": { "$ref": "#/definitions/tags" }
+                }
+            }),
+        ]);
+
+        let schema = registry
+            .resolve_refs("gts.acme.core.models.customer.v1~")
+            .await
+            .unwrap();
+
+        let expected_address = json!({
+            "type": "object",
+            "properties": { "city": { "type": "string" } }
+        });
+        assert_eq!(schema["$id"], "gts://gts.acme.core.models.customer.v1~");
+        assert_eq!(schema["properties"]["billing"], expected_address);
+        assert_eq!(schema["properties"]["shipping"], expected_address);
+        assert_eq!(
+            schema["properties"]["tags"],
+            json!({ "$ref": "#/definitions/tags" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_many_default_resolves_each_position() {
+        let registry = MockRegistry::with_types(&[address_type()]);
+
+        let ids = [
+            "gts.acme.core.models.missing.v1~".to_owned(),
+            "gts.acme.core.models.address.v1~".to_owned(),
+        ];
+        let results = registry.get_many(&ids).await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].as_ref().unwrap_err().is_not_found());
+        assert_eq!(
+            results[1].as_ref().unwrap().gts_id,
+            "gts.acme.core.models.address.v1~"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_refs_follows_transitive_refs() {
+        let registry = MockRegistry::with_types(&[
+            address_type(),
+            json!({
+                "$id": "gts://gts.acme.core.models.customer.v1~",
+                "type": "object",
+                "properties": {
+                    "address": { "$ref": "gts://gts.acme.core.models.address.v1~" }
+                }
+            }),
+            json!({
+                "$id": "gts://gts.acme.core.events.order_placed.v1~",
+                "allOf": [{ "$ref": "gts://gts.acme.core.models.customer.v1~" }]
+            }),
+        ]);
+
+        let schema = registry
+            .resolve_refs("gts.acme.core.events.order_placed.v1~")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            schema["allOf"][0]["properties"]["address"]["properties"]["city"],
+            json!({ "type": "string" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_refs_missing_ref_is_not_found() {
+        let registry = MockRegistry::with_types(&[json!({
+            "$id": "gts://gts.acme.core.models.customer.v1~",
+            "type": "object",
+            "properties": {
+                "address": { "$ref": "gts://gts.acme.core.models.address.v1~" }
+            }
+        })]);
+
+        let err = registry
+            .resolve_refs("gts.acme.core.models.customer.v1~")
+            .await
+            .unwrap_err();
+
+        assert!(err.is_not_found());
+        assert!(err.to_string().contains("gts.acme.core.models.address.v1~"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_refs_turns_refs_to_root_into_local_refs() {
+        let registry = MockRegistry::with_types(&[
+            json!({
+                "$id": "gts://gts.acme.core.models.node.v1~",
+                "type": "object",
+                "properties": {
+                    "parent": { "$ref": "gts://gts.acme.core.models.node.v1~" },
+                    "edge": { "$ref": "gts://gts.acme.core.models.edge.v1~" }
+                }
+            }),
+            json!({
+                "$id": "gts://gts.acme.core.models.edge.v1~",
+                "type": "object",
+                "properties": {
+                    "target": { "$ref": "gts://gts.acme.core.models.node.v1~" }
+                }
+            }),
+        ]);
+
+        let schema = registry
+            .resolve_refs("gts.acme.core.models.node.v1~")
+            .await
+            .unwrap();
+
+        assert_eq!(schema["properties"]["parent"], json!({ "$ref": "#" }));
+        assert_eq!(
+            schema["properties"]["edge"]["properties"]["target"],
+            json!({ "$ref": "#" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_refs_rejects_cycles() {
+        let registry = MockRegistry::with_types(&[
+            json!({
+                "$id": "gts://gts.acme.core.models.graph.v1~",
+                "type": "object",
+                "properties": {
+                    "root": { "$ref": "gts://gts.acme.core.models.node.v1~" }
+                }
+            }),
+            json!({
+                "$id": "gts://gts.acme.core.models.node.v1~",
+                "type": "object",
+                "properties": {
+                    "edge": { "$ref": "gts://gts.acme.core.models.edge.v1~" }
+                }
+            }),
+            json!({
+                "$id": "gts://gts.acme.core.models.edge.v1~",
+                "type": "object",
+                "properties": {
+                    "target": { "$ref": "gts://gts.acme.core.models.node.v1~" }
+                }
+            }),
+        ]);
+
+        let err = registry
+            .resolve_refs("gts.acme.core.models.graph.v1~")
+            .await
+            .unwrap_err();
+
+        assert!(err.is_validation_failed());
+        assert!(err.to_string().contains("cyclic $ref"), "{err}");
+    }
 }
@@ -27,6 +27,10 @@ pub enum TypesRegistryError {
     #[error("Not in ready mode")]
     NotInReadyMode,
 
+    /// The entity was valid but its atomic batch was rolled back.
+    #[error("Rolled back: {0}")]
+    RolledBack(String),
+
     /// An internal error occurred.
     #[error("Internal error: {0}")]
     Internal(String),
@@ -63,6 +67,12 @@ impl TypesRegistryError {
         Self::NotInReadyMode
     }
 
+    /// Creates a `RolledBack` error.
+    #[must_use]
+    pub fn rolled_back(message: impl Into<String>) -> Self {
+        Self::RolledBack(message.into())
+    }
+
     /// Creates an `Internal` error.
     #[must_use]
     pub fn internal(message: impl Into<String>) -> Self {
@@ -92,6 +102,12 @@ impl TypesRegistryError {
     pub const fn is_invalid_gts_id(&self) -> bool {
         matches!(self, Self::InvalidGtsId(_))
     }
+
+    /// Returns `true` if this entity was rolled back with its atomic batch.
+    #[must_use]
+    pub const fn is_rolled_back(&self) -> bool {
+        matches!(self, Self::RolledBack(_))
+    }
 }
 
 #[cfg(test)]
@@ -116,6 +132,9 @@ mod tests {
         let err = TypesRegistryError::not_in_ready_mode();
         assert!(matches!(err, TypesRegistryError::NotInReadyMode));
 
+        let err = TypesRegistryError::rolled_back("entity at index 2 failed");
+        assert!(err.is_rolled_back());
+
         let err = TypesRegistryError::internal("database error");
         assert!(matches!(err, TypesRegistryError::Internal(_)));
     }
@@ -143,6 +162,9 @@ mod tests {
         let err = TypesRegistryError::NotInReadyMode;
         assert_eq!(err.to_string(), "Not in ready mode");
 
+        let err = TypesRegistryError::RolledBack("entity at index 0 failed".to_owned());
+        assert_eq!(err.to_string(), "Rolled back: entity at index 0 failed");
+
         let err = TypesRegistryError::Internal("unexpected".to_owned());
         assert_eq!(err.to_string(), "Internal error: unexpected");
     }
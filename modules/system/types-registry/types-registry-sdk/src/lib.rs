@@ -32,11 +32,12 @@
 pub mod api;
 pub mod error;
 pub mod models;
+mod refs;
 
 // Re-export main types at crate root for convenience
 pub use api::TypesRegistryClient;
 pub use error::TypesRegistryError;
 pub use models::{
     DynGtsEntity, DynRegisterResult, GtsEntity, GtsInstanceEntity, GtsTypeEntity, InstanceObject,
-    ListQuery, RegisterResult, RegisterSummary, SegmentMatchScope, TypeSchema,
+    ListQuery, RegisterFailure, RegisterResult, RegisterSummary, SegmentMatchScope, TypeSchema,
 };
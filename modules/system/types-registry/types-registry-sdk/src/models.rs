@@ -3,9 +3,15 @@
 //! These are transport-agnostic data structures that define the contract
 //! between the `types-registry` module and its consumers.
 
+use std::sync::LazyLock;
+
 use gts::GtsIdSegment;
 use uuid::Uuid;
 
+/// GTS UUID namespace: `Uuid::new_v5(&Uuid::NAMESPACE_URL, b"gts")`.
+static GTS_UUID_NAMESPACE: LazyLock<Uuid> =
+    LazyLock::new(|| Uuid::new_v5(&Uuid::NAMESPACE_URL, b"gts"));
+
 /// A registered GTS entity.
 ///
 /// This represents either a type definition or an instance that has been
@@ -33,6 +39,8 @@ pub struct GtsEntity<C = serde_json::Value> {
     /// The namespace is derived as `Uuid::new_v5(&Uuid::NAMESPACE_URL, b"gts")`,
     /// which is a GTS specification-defined constant ensuring consistent
     /// UUID generation across all implementations.
+    ///
+    /// Use [`GtsEntity::derive_id`] to compute it from a GTS ID string.
     pub id: Uuid,
 
     /// The full GTS identifier string.
@@ -360,36 +368,94 @@ impl<C> RegisterResult<C> {
     ///
     /// Returns the first `TypesRegistryError` found in the results.
     pub fn ensure_all_ok(results: &[Self]) -> Result<(), crate::TypesRegistryError> {
+        match Self::first_error(results) {
+            Some((_, error)) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the first failed registration as `(gts_id, error)`, if any.
+    ///
+    /// Unlike [`RegisterResult::ensure_all_ok`], this keeps the GTS ID of the
+    /// failed item (when known) and does not clone the error.
+    #[must_use]
+    pub fn first_error(results: &[Self]) -> Option<(Option<&str>, &crate::TypesRegistryError)> {
+        results.iter().find_map(|result| match result {
+            Self::Ok(_) => None,
+            Self::Err { gts_id, error } => Some((gts_id.as_deref(), error)),
+        })
+    }
+
+    /// Splits results into successfully registered entities and failures.
+    ///
+    /// Failures are returned as `(gts_id, error)` pairs. Both vectors preserve
+    /// the order of the input.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let results = registry.register(&ctx, entities).await?;
+    /// let (registered, failed) = RegisterResult::partition(results);
+    /// for (gts_id, error) in &failed {
+    ///     tracing::warn!(?gts_id, %error, "registration failed");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn partition(
+        results: impl IntoIterator<Item = Self>,
+    ) -> (Vec<GtsEntity<C>>, Vec<RegisterFailure>) {
+        let mut succeeded = Vec::new();
+        let mut failed = Vec::new();
         for result in results {
-            if let Self::Err { error, .. } = result {
-                return Err(error.clone());
+            match result {
+                Self::Ok(entity) => succeeded.push(entity),
+                Self::Err { gts_id, error } => failed.push((gts_id, error)),
             }
         }
-        Ok(())
+        (succeeded, failed)
     }
 }
 
 /// Type alias for dynamic register results using `serde_json::Value` as content.
 pub type DynRegisterResult = RegisterResult<serde_json::Value>;
 
+/// A failed registration: the GTS ID (if it could be extracted) and the error.
+pub type RegisterFailure = (Option<String>, crate::TypesRegistryError);
+
 /// Summary of a batch registration operation.
 ///
-/// Provides aggregate counts for quick success/failure assessment.
+/// Provides aggregate counts for quick success/failure assessment, plus the
+/// positions of failed items and a sample error for triage.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct RegisterSummary {
     /// Number of successfully registered entities.
     pub succeeded: usize,
     /// Number of failed registrations.
     pub failed: usize,
+    /// Indices of the failed items in the input batch, in ascending order.
+    pub failed_indices: Vec<usize>,
+    /// Message of the first failed registration, if any.
+    pub first_error: Option<String>,
 }
 
 impl RegisterSummary {
     /// Creates a new summary from a slice of register results.
     #[must_use]
     pub fn from_results<C>(results: &[RegisterResult<C>]) -> Self {
-        let succeeded = results.iter().filter(|r| r.is_ok()).count();
-        let failed = results.len() - succeeded;
-        Self { succeeded, failed }
+        let failed_indices: Vec<usize> = results
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.is_err())
+            .map(|(i, _)| i)
+            .collect();
+        let first_error = RegisterResult::first_error(results).map(|(_, error)| error.to_string());
+        let failed = failed_indices.len();
+        Self {
+            succeeded: results.len() - failed,
+            failed,
+            failed_indices,
+            first_error,
+        }
     }
 
     /// Returns `true` if all registrations succeeded.
@@ -469,12 +535,33 @@ impl<C> GtsEntity<C> {
     }
 }
 
+impl GtsEntity {
+    /// Derives the deterministic entity UUID for a GTS ID.
+    ///
+    /// This is the same value as [`GtsEntity::id`] of the registered entity, so
+    /// consumers can compute it without registering or fetching the entity.
+    /// The GTS ID is not validated; surrounding whitespace is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use types_registry_sdk::GtsEntity;
+    ///
+    /// let id = GtsEntity::derive_id("gts.acme.core.events.user_created.v1~");
+    /// assert_eq!(id.get_version_num(), 5);
+    /// ```
+    #[must_use]
+    pub fn derive_id(gts_id: &str) -> Uuid {
+        Uuid::new_v5(&GTS_UUID_NAMESPACE, gts_id.trim().as_bytes())
+    }
+}
+
 /// Specifies which segments in a chained GTS ID the filters should match against.
 ///
 /// GTS IDs can be chained (e.g., `gts.vendor.pkg.ns.type.v1~instance.id`),
 /// containing multiple segments. This enum controls whether filters like
-/// `vendor`, `package`, and `namespace` apply to just the primary (first)
-/// segment or to any segment in the chain.
+/// `vendor`, `package`, `namespace`, and `type_name` apply to just the primary
+/// (first) segment or to any segment in the chain.
 ///
 /// # Example
 ///
@@ -511,8 +598,8 @@ impl SegmentMatchScope {
 ///
 /// # Segment Matching
 ///
-/// The `segment_scope` field controls how `vendor`, `package`, and `namespace`
-/// filters are applied to chained GTS IDs. By default, filters match
+/// The `segment_scope` field controls how `vendor`, `package`, `namespace`, and
+/// `type_name` filters are applied to chained GTS IDs. By default, filters match
 /// any segment in the chain.
 ///
 /// # Example
@@ -528,6 +615,10 @@ impl SegmentMatchScope {
 ///     .with_is_type(true)
 ///     .with_vendor("acme");
 ///
+/// // Discover plugin instances of a given type
+/// let query = ListQuery::instances_only()
+///     .with_pattern("gts.acme.core.plugins.auth.v1~*");
+///
 /// // List entities where only the primary segment has vendor "acme"
 /// let query = ListQuery::default()
 ///     .with_vendor("acme")
@@ -536,6 +627,10 @@ impl SegmentMatchScope {
 /// // List entities matching a pattern
 /// let query = ListQuery::default()
 ///     .with_pattern("gts.acme.core.*");
+///
+/// // List all versions of the "user_created" type and its instances
+/// let query = ListQuery::default()
+///     .with_type_name("user_created");
 /// ```
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ListQuery {
@@ -562,11 +657,25 @@ pub struct ListQuery {
     /// Which segments this applies to is controlled by `segment_scope`.
     pub namespace: Option<String>,
 
-    /// Controls which segments the `vendor`, `package`, and `namespace`
-    /// filters are matched against.
+    /// Filter by type name (e.g. `user_created`), regardless of version.
+    ///
+    /// Which segments this applies to is controlled by `segment_scope`.
+    pub type_name: Option<String>,
+
+    /// Filter by a case-insensitive substring of the entity description.
+    ///
+    /// Entities without a description never match.
+    pub description_contains: Option<String>,
+
+    /// Controls which segments the `vendor`, `package`, `namespace`, and
+    /// `type_name` filters are matched against.
     ///
     /// Defaults to `Any` (matches any segment in the chain).
     pub segment_scope: SegmentMatchScope,
+
+    /// Also return instances whose TTL has elapsed but that have not been
+    /// swept yet. Defaults to `false`.
+    pub include_expired: bool,
 }
 
 impl ListQuery {
@@ -576,6 +685,23 @@ impl ListQuery {
         Self::default()
     }
 
+    /// Creates a `ListQuery` matching only instances.
+    ///
+    /// Shorthand for `ListQuery::new().with_is_type(false)`, typically combined
+    /// with a pattern when discovering plugin instances.
+    #[must_use]
+    pub fn instances_only() -> Self {
+        Self::new().with_is_type(false)
+    }
+
+    /// Creates a `ListQuery` matching only type definitions.
+    ///
+    /// Shorthand for `ListQuery::new().with_is_type(true)`.
+    #[must_use]
+    pub fn types_only() -> Self {
+        Self::new().with_is_type(true)
+    }
+
     /// Sets the pattern filter.
     #[must_use]
     pub fn with_pattern(mut self, pattern: impl Into<String>) -> Self {
@@ -611,6 +737,20 @@ impl ListQuery {
         self
     }
 
+    /// Sets the type name filter.
+    #[must_use]
+    pub fn with_type_name(mut self, type_name: impl Into<String>) -> Self {
+        self.type_name = Some(type_name.into());
+        self
+    }
+
+    /// Sets the description substring filter.
+    #[must_use]
+    pub fn with_description_contains(mut self, text: impl Into<String>) -> Self {
+        self.description_contains = Some(text.into());
+        self
+    }
+
     /// Sets the segment match scope.
     #[must_use]
     pub const fn with_segment_scope(mut self, scope: SegmentMatchScope) -> Self {
@@ -618,6 +758,13 @@ impl ListQuery {
         self
     }
 
+    /// Sets whether expired instances that are not swept yet are returned.
+    #[must_use]
+    pub const fn with_include_expired(mut self, include_expired: bool) -> Self {
+        self.include_expired = include_expired;
+        self
+    }
+
     /// Returns `true` if no filters are set.
     #[must_use]
     pub fn is_empty(&self) -> bool {
@@ -626,6 +773,8 @@ impl ListQuery {
             && self.vendor.is_none()
             && self.package.is_none()
             && self.namespace.is_none()
+            && self.type_name.is_none()
+            && self.description_contains.is_none()
     }
 }
 
@@ -676,6 +825,96 @@ mod tests {
         assert!(instance.is_instance());
     }
 
+    #[test]
+    fn test_derive_id_matches_gts_rust_and_is_stable() {
+        let gts_id = "gts.acme.core.events.user_created.v1~";
+        let id = GtsEntity::derive_id(gts_id);
+
+        assert_eq!(id, gts::GtsID::new(gts_id).unwrap().to_uuid());
+        assert_eq!(
+            id,
+            Uuid::parse_str("aec3d391-db14-5a5c-99fa-c434b77e7ed6").unwrap()
+        );
+        assert_ne!(
+            id,
+            GtsEntity::derive_id("gts.acme.core.events.user_created.v2~")
+        );
+    }
+
+    fn mixed_batch() -> Vec<DynRegisterResult> {
+        let entity = |gts_id: &str| {
+            GtsEntity::new(
+                GtsEntity::derive_id(gts_id),
+                gts_id,
+                vec![],
+                true,
+                serde_json::json!({}),
+                None,
+            )
+        };
+        vec![
+            RegisterResult::Ok(entity("gts.acme.core.events.a.v1~")),
+            RegisterResult::Err {
+                gts_id: Some("gts.acme.core.events.b.v1~".to_owned()),
+                error: crate::TypesRegistryError::already_exists("gts.acme.core.events.b.v1~"),
+            },
+            RegisterResult::Ok(entity("gts.acme.core.events.c.v1~")),
+            RegisterResult::Err {
+                gts_id: None,
+                error: crate::TypesRegistryError::invalid_gts_id("No GTS ID field found"),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_register_result_partition_matches_summary() {
+        let results = mixed_batch();
+        let summary = RegisterSummary::from_results(&results);
+
+        let (registered, failed) = RegisterResult::partition(results);
+
+        assert_eq!(registered.len(), summary.succeeded);
+        assert_eq!(failed.len(), summary.failed);
+        assert_eq!(registered[0].gts_id, "gts.acme.core.events.a.v1~");
+        assert_eq!(registered[1].gts_id, "gts.acme.core.events.c.v1~");
+        assert_eq!(failed[0].0.as_deref(), Some("gts.acme.core.events.b.v1~"));
+        assert!(failed[1].0.is_none());
+    }
+
+    #[test]
+    fn test_register_summary_records_failed_indices_and_first_error() {
+        let results = mixed_batch();
+        let summary = RegisterSummary::from_results(&results);
+
+        assert_eq!(summary.succeeded, 2);
+        assert_eq!(summary.failed, 2);
+        assert_eq!(summary.failed_indices, vec![1, 3]);
+        assert_eq!(
+            summary.first_error.as_deref(),
+            Some("Entity already exists: gts.acme.core.events.b.v1~")
+        );
+
+        let all_ok: Vec<_> = results.into_iter().filter(RegisterResult::is_ok).collect();
+        let summary = RegisterSummary::from_results(&all_ok);
+        assert!(summary.all_succeeded());
+        assert!(summary.failed_indices.is_empty());
+        assert_eq!(summary.first_error, None);
+    }
+
+    #[test]
+    fn test_register_result_first_error() {
+        let results = mixed_batch();
+
+        let (gts_id, error) = RegisterResult::first_error(&results).unwrap();
+        assert_eq!(gts_id, Some("gts.acme.core.events.b.v1~"));
+        assert!(error.is_already_exists());
+        assert!(RegisterResult::ensure_all_ok(&results).is_err());
+
+        let all_ok: Vec<_> = results.into_iter().filter(RegisterResult::is_ok).collect();
+        assert!(RegisterResult::first_error(&all_ok).is_none());
+        assert!(RegisterResult::ensure_all_ok(&all_ok).is_ok());
+    }
+
     #[test]
     fn test_list_query_builder() {
         let query = ListQuery::new()
@@ -683,17 +922,47 @@ mod tests {
             .with_is_type(true)
             .with_vendor("acme")
             .with_package("core")
-            .with_namespace("events");
+            .with_namespace("events")
+            .with_type_name("user_created")
+            .with_description_contains("event");
 
         assert_eq!(query.pattern, Some("gts.acme.*".to_owned()));
         assert_eq!(query.is_type, Some(true));
         assert_eq!(query.vendor, Some("acme".to_owned()));
         assert_eq!(query.package, Some("core".to_owned()));
         assert_eq!(query.namespace, Some("events".to_owned()));
+        assert_eq!(query.type_name, Some("user_created".to_owned()));
+        assert_eq!(query.description_contains, Some("event".to_owned()));
         assert_eq!(query.segment_scope, SegmentMatchScope::Any);
         assert!(!query.is_empty());
     }
 
+    #[test]
+    fn test_list_query_kind_constructors() {
+        assert_eq!(ListQuery::instances_only().is_type, Some(false));
+        assert_eq!(ListQuery::types_only().is_type, Some(true));
+        assert!(!ListQuery::instances_only().is_empty());
+    }
+
+    #[test]
+    fn test_list_query_instances_only_composes_with_filters() {
+        let query = ListQuery::instances_only()
+            .with_pattern("gts.acme.core.plugins.auth.v1~*")
+            .with_vendor("acme")
+            .with_type_name("auth");
+
+        assert_eq!(
+            query,
+            ListQuery::new()
+                .with_is_type(false)
+                .with_pattern("gts.acme.core.plugins.auth.v1~*")
+                .with_vendor("acme")
+                .with_type_name("auth")
+        );
+        assert_eq!(query.is_type, Some(false));
+        assert_eq!(query.vendor, Some("acme".to_owned()));
+    }
+
     #[test]
     fn test_list_query_empty() {
         let query = ListQuery::default();
@@ -0,0 +1,227 @@
+//! `$ref` dereferencing against registered GTS types.
+//!
+//! Used by [`TypesRegistryClient::resolve_refs`](crate::TypesRegistryClient::resolve_refs)
+//! once every referenced schema has been fetched.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::error::TypesRegistryError;
+
+const GTS_URI_PREFIX: &str = "gts://";
+
+/// The GTS ID named by a `$ref` value, or `None` for non-GTS references.
+fn gts_ref(reference: &str) -> Option<&str> {
+    let id = reference.strip_prefix(GTS_URI_PREFIX).unwrap_or(reference);
+    id.starts_with("gts.").then_some(id)
+}
+
+/// The GTS `$ref` target of a schema object, if any.
+fn ref_target(map: &serde_json::Map<String, Value>) -> Option<&str> {
+    map.get("$ref").and_then(Value::as_str).and_then(gts_ref)
+}
+
+/// Appends the GTS ID of every GTS `$ref` in `schema` to `out`.
+pub fn collect_refs(schema: &Value, out: &mut Vec<String>) {
+    match schema {
+        Value::Object(map) => {
+            if let Some(id) = ref_target(map) {
+                out.push(id.to_owned());
+            }
+            for value in map.values() {
+                collect_refs(value, out);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_refs(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Inlines GTS `$ref`s of the schema registered as `root`.
+pub struct Inliner<'a> {
+    schemas: &'a HashMap<String, Value>,
+    /// GTS IDs being inlined on the current path, starting with the root.
+    stack: Vec<String>,
+    /// Inlined copies of the types resolved so far, shared by every `$ref` to them.
+    resolved: HashMap<String, Value>,
+}
+
+impl<'a> Inliner<'a> {
+    /// `schemas` must hold `root` and every type it references.
+    pub fn new(root: &str, schemas: &'a HashMap<String, Value>) -> Self {
+        Self {
+            schemas,
+            stack: vec![root.to_owned()],
+            resolved: HashMap::new(),
+        }
+    }
+
+    /// Returns `schema` with every GTS `$ref` replaced by the referenced schema.
+    ///
+    /// A `$ref` back to the root becomes the local reference `#`, so recursive
+    /// types stay representable. Any other `$ref` to a type being inlined on
+    /// the current path is a cycle.
+    pub fn inline(&mut self, schema: &Value) -> Result<Value, TypesRegistryError> {
+        match schema {
+            Value::Object(map) => {
+                let Some(id) = ref_target(map) else {
+                    return map
+                        .iter()
+                        .map(|(key, value)| Ok((key.clone(), self.inline(value)?)))
+                        .collect::<Result<_, _>>()
+                        .map(Value::Object);
+                };
+
+                let mut resolved = if self.stack[0] == id {
+                    serde_json::Map::from_iter([("$ref".to_owned(), Value::from("#"))])
+                } else {
+                    match self.inline_type(id)? {
+                        Value::Object(resolved) => resolved,
+                        other => return Ok(other),
+                    }
+                };
+                for (key, value) in map.iter().filter(|(key, _)| *key != "$ref") {
+                    resolved.insert(key.clone(), self.inline(value)?);
+                }
+                Ok(Value::Object(resolved))
+            }
+            Value::Array(items) => items
+                .iter()
+                .map(|item| self.inline(item))
+                .collect::<Result<_, _>>()
+                .map(Value::Array),
+            other => Ok(other.clone()),
+        }
+    }
+
+    /// The inlined copy of the type `id`, resolved once and then reused.
+    fn inline_type(&mut self, id: &str) -> Result<Value, TypesRegistryError> {
+        if let Some(resolved) = self.resolved.get(id) {
+            return Ok(resolved.clone());
+        }
+        if self.stack.iter().any(|seen| seen == id) {
+            return Err(TypesRegistryError::validation_failed(format!(
+                "cyclic $ref: {} -> {id}",
+                self.stack.join(" -> ")
+            )));
+        }
+        let referenced = self
+            .schemas
+            .get(id)
+            .ok_or_else(|| TypesRegistryError::not_found(id))?;
+
+        self.stack.push(id.to_owned());
+        let resolved = self.inline(referenced);
+        self.stack.pop();
+
+        let mut resolved = resolved?;
+        if let Value::Object(map) = &mut resolved {
+            // An inlined copy must not change the base URI of the outer schema
+            map.remove("$id");
+            map.remove("$schema");
+        }
+        self.resolved.insert(id.to_owned(), resolved.clone());
+        Ok(resolved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_gts_ref() {
+        assert_eq!(
+            gts_ref("gts://gts.acme.core.events.base.v1~"),
+            Some("gts.acme.core.events.base.v1~")
+        );
+        assert_eq!(
+            gts_ref("gts.acme.core.events.base.v1~"),
+            Some("gts.acme.core.events.base.v1~")
+        );
+        assert_eq!(gts_ref("#/definitions/address"), None);
+        assert_eq!(gts_ref("https://example.com/schema.json"), None);
+    }
+
+    #[test]
+    fn test_collect_refs_skips_local_refs() {
+        let schema = json!({
+            "allOf": [{ "$ref": "gts://gts.acme.core.events.base.v1~" }],
+            "properties": {
+                "address": { "$ref": "#/definitions/address" },
+                "owner": { "$ref": "gts.acme.core.models.user.v1~" }
+            }
+        });
+
+        let mut refs = Vec::new();
+        collect_refs(&schema, &mut refs);
+        refs.sort();
+
+        assert_eq!(
+            refs,
+            vec![
+                "gts.acme.core.events.base.v1~",
+                "gts.acme.core.models.user.v1~"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inline_refs_keeps_sibling_keywords() {
+        let schemas = HashMap::from([(
+            "gts.acme.core.models.user.v1~".to_owned(),
+            json!({
+                "$id": "gts://gts.acme.core.models.user.v1~",
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "description": "A user"
+            }),
+        )]);
+        let schema = json!({
+            "$ref": "gts://gts.acme.core.models.user.v1~",
+            "description": "The owner"
+        });
+
+        let mut inliner = Inliner::new("gts.acme.core.models.owned.v1~", &schemas);
+        let result = inliner.inline(&schema).unwrap();
+
+        assert_eq!(
+            result,
+            json!({ "type": "object", "description": "The owner" })
+        );
+        assert_eq!(inliner.stack.len(), 1);
+    }
+
+    #[test]
+    fn test_inline_refs_resolves_shared_type_once() {
+        let schemas = HashMap::from([(
+            "gts.acme.core.models.address.v1~".to_owned(),
+            json!({ "type": "object" }),
+        )]);
+        let schema = json!({
+            "properties": {
+                "billing": { "$ref": "gts.acme.core.models.address.v1~" },
+                "shipping": { "$ref": "gts.acme.core.models.address.v1~" }
+            }
+        });
+
+        let mut inliner = Inliner::new("gts.acme.core.models.customer.v1~", &schemas);
+        let result = inliner.inline(&schema).unwrap();
+
+        assert_eq!(result["properties"]["billing"], json!({ "type": "object" }));
+        assert_eq!(
+            result["properties"]["shipping"],
+            json!({ "type": "object" })
+        );
+        assert_eq!(
+            inliner.resolved.keys().collect::<Vec<_>>(),
+            ["gts.acme.core.models.address.v1~"]
+        );
+    }
+}
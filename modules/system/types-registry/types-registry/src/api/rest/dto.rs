@@ -74,6 +74,14 @@ impl From<GtsEntity> for GtsEntityDto {
 pub struct RegisterEntitiesRequest {
     /// Array of GTS entities to register.
     pub entities: Vec<serde_json::Value>,
+    /// Replace entities whose GTS ID is already registered with different
+    /// content instead of reporting a conflict.
+    #[serde(default)]
+    pub overwrite: bool,
+    /// Roll back the whole batch if any entity fails, so that either every
+    /// entity is registered or none is.
+    #[serde(default)]
+    pub atomic: bool,
 }
 
 /// Result of registering a single entity.
@@ -163,6 +171,12 @@ pub struct ListEntitiesQuery {
     /// Filter by namespace.
     #[serde(default)]
     pub namespace: Option<String>,
+    /// Filter by type name (any version).
+    #[serde(default)]
+    pub type_name: Option<String>,
+    /// Filter by a case-insensitive substring of the description.
+    #[serde(default)]
+    pub description_contains: Option<String>,
     /// Segment match scope: "primary" or "any" (default).
     #[serde(default)]
     pub segment_scope: Option<String>,
@@ -194,6 +208,14 @@ impl ListEntitiesQuery {
             query = query.with_namespace(namespace);
         }
 
+        if let Some(ref type_name) = self.type_name {
+            query = query.with_type_name(type_name);
+        }
+
+        if let Some(ref text) = self.description_contains {
+            query = query.with_description_contains(text);
+        }
+
         if let Some(ref scope) = self.segment_scope {
             match scope.as_str() {
                 "primary" => query = query.with_segment_scope(SegmentMatchScope::Primary),
@@ -362,6 +384,8 @@ mod tests {
             vendor: Some("acme".to_owned()),
             package: None,
             namespace: None,
+            type_name: None,
+            description_contains: None,
             segment_scope: Some("primary".to_owned()),
         };
 
@@ -380,6 +404,8 @@ mod tests {
             vendor: None,
             package: Some("core".to_owned()),
             namespace: Some("events".to_owned()),
+            type_name: Some("user_created".to_owned()),
+            description_contains: Some("event".to_owned()),
             segment_scope: Some("any".to_owned()),
         };
 
@@ -387,6 +413,8 @@ mod tests {
         assert_eq!(query.is_type, Some(false));
         assert_eq!(query.package, Some("core".to_owned()));
         assert_eq!(query.namespace, Some("events".to_owned()));
+        assert_eq!(query.type_name, Some("user_created".to_owned()));
+        assert_eq!(query.description_contains, Some("event".to_owned()));
         assert_eq!(query.segment_scope, SegmentMatchScope::Any);
     }
 
@@ -398,6 +426,8 @@ mod tests {
             vendor: None,
             package: None,
             namespace: None,
+            type_name: None,
+            description_contains: None,
             segment_scope: Some("invalid".to_owned()),
         };
 
@@ -445,6 +475,7 @@ mod tests {
         let summary = RegisterSummary {
             succeeded: 5,
             failed: 2,
+            ..RegisterSummary::default()
         };
         let dto: RegisterSummaryDto = summary.into();
         assert_eq!(dto.total, 7);
@@ -28,7 +28,11 @@ pub async fn register_entities(
         return Err(DomainError::NotInReadyMode.into());
     }
 
-    let results = service.register_validated(req.entities);
+    let results = if req.atomic {
+        service.register_validated_atomic(&req.entities, req.overwrite)
+    } else {
+        service.register_validated(req.entities, req.overwrite)
+    };
 
     let summary = RegisterSummary::from_results(&results);
     let result_dtos: Vec<RegisterResultDto> = results.into_iter().map(Into::into).collect();
@@ -113,6 +117,8 @@ mod tests {
                 "$schema": JSON_SCHEMA_DRAFT_07,
                 "type": "object"
             })],
+            overwrite: false,
+            atomic: false,
         };
 
         let result = register_entities(Extension(service), Json(req)).await;
@@ -153,6 +159,8 @@ mod tests {
                 "$schema": JSON_SCHEMA_DRAFT_07,
                 "type": "object"
             })],
+            overwrite: false,
+            atomic: false,
         };
 
         let result = register_entities(Extension(service), Json(req)).await;
@@ -57,7 +57,7 @@ pub fn register_routes(
         .operation_id("types_registry.list")
         .summary("List GTS entities")
         .description(
-            "List registered GTS entities with optional filtering by pattern, kind, vendor, package, or namespace.",
+            "List registered GTS entities with optional filtering by pattern, kind, vendor, package, namespace, type name, or description.",
         )
         .tag(API_TAG)
         .authenticated()
@@ -67,6 +67,8 @@ pub fn register_routes(
         .query_param("vendor", false, "Filter by vendor")
         .query_param("package", false, "Filter by package")
         .query_param("namespace", false, "Filter by namespace")
+        .query_param("type_name", false, "Filter by type name, across versions (e.g., user_created)")
+        .query_param("description_contains", false, "Filter by case-insensitive description substring")
         .query_param("segmentScope", false, "Segment match scope: 'primary' or 'any' (default)")
         .handler(handlers::list_entities)
         .json_response_with_schema::<ListEntitiesResponse>(
@@ -1,5 +1,7 @@
 //! Configuration for the Types Registry module.
 
+use std::time::Duration;
+
 use serde::Deserialize;
 
 /// Configuration for the Types Registry module.
@@ -13,6 +15,11 @@ pub struct TypesRegistryConfig {
     /// Fields to check for schema ID reference (in order of priority).
     /// Default: `["$schema", "gtsTid", "type"]`
     pub schema_id_fields: Vec<String>,
+
+    /// How often instances registered with a TTL are checked for expiry
+    /// (e.g. `"5s"`). Default: `"1s"`
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub expiry_sweep_interval: Duration,
 }
 
 impl Default for TypesRegistryConfig {
@@ -20,6 +27,7 @@ impl Default for TypesRegistryConfig {
         Self {
             entity_id_fields: vec!["$id".to_owned(), "gtsId".to_owned(), "id".to_owned()],
             schema_id_fields: vec!["$schema".to_owned(), "gtsTid".to_owned(), "type".to_owned()],
+            expiry_sweep_interval: Duration::from_secs(1),
         }
     }
 }
@@ -44,6 +52,15 @@ mod tests {
         let cfg = TypesRegistryConfig::default();
         assert_eq!(cfg.entity_id_fields, vec!["$id", "gtsId", "id"]);
         assert_eq!(cfg.schema_id_fields, vec!["$schema", "gtsTid", "type"]);
+        assert_eq!(cfg.expiry_sweep_interval, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_expiry_sweep_interval_from_humantime() {
+        let cfg: TypesRegistryConfig =
+            serde_json::from_value(serde_json::json!({ "expiry_sweep_interval": "250ms" }))
+                .unwrap();
+        assert_eq!(cfg.expiry_sweep_interval, Duration::from_millis(250));
     }
 
     #[test]
@@ -1,6 +1,7 @@
 //! Local client implementing the `TypesRegistryApi` trait.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use modkit_macros::domain_model;
@@ -37,6 +38,32 @@ impl TypesRegistryClient for TypesRegistryLocalClient {
         Ok(self.service.register(entities))
     }
 
+    async fn register_atomic(
+        &self,
+        entities: Vec<serde_json::Value>,
+    ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+        Ok(self.service.register_atomic(&entities))
+    }
+
+    async fn register_overwrite(
+        &self,
+        entities: Vec<serde_json::Value>,
+    ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+        Ok(self.service.register_overwrite(entities))
+    }
+
+    async fn register_with_ttl(
+        &self,
+        entities: Vec<serde_json::Value>,
+        ttl: Duration,
+    ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+        Ok(self.service.register_with_ttl(entities, ttl))
+    }
+
+    async fn touch(&self, gts_id: &str) -> Result<(), TypesRegistryError> {
+        self.service.touch(gts_id).map_err(TypesRegistryError::from)
+    }
+
     async fn list(&self, query: ListQuery) -> Result<Vec<GtsEntity>, TypesRegistryError> {
         self.service.list(&query).map_err(TypesRegistryError::from)
     }
@@ -44,6 +71,18 @@ impl TypesRegistryClient for TypesRegistryLocalClient {
     async fn get(&self, gts_id: &str) -> Result<GtsEntity, TypesRegistryError> {
         self.service.get(gts_id).map_err(TypesRegistryError::from)
     }
+
+    async fn get_many(
+        &self,
+        gts_ids: &[String],
+    ) -> Result<Vec<Result<GtsEntity, TypesRegistryError>>, TypesRegistryError> {
+        Ok(self
+            .service
+            .get_many(gts_ids)
+            .into_iter()
+            .map(|result| result.map_err(TypesRegistryError::from))
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -94,6 +133,39 @@ mod tests {
         assert_eq!(retrieved.gts_id, "gts.acme.core.events.user_created.v1~");
     }
 
+    #[tokio::test]
+    async fn test_register_overwrite_replaces_differing_content() {
+        let client = create_client();
+        client.service.switch_to_ready().unwrap();
+
+        let original = json!({
+            "$id": "gts://gts.acme.core.events.user_created.v1~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object",
+            "properties": { "userId": { "type": "string" } }
+        });
+        let changed = json!({
+            "$id": "gts://gts.acme.core.events.user_created.v1~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object",
+            "properties": { "email": { "type": "string" } }
+        });
+        client.register(vec![original]).await.unwrap();
+
+        let rejected = client.register(vec![changed.clone()]).await.unwrap();
+        assert!(rejected[0].is_err());
+
+        let results = client.register_overwrite(vec![changed]).await.unwrap();
+        assert!(results[0].is_ok());
+
+        let stored = client
+            .get("gts.acme.core.events.user_created.v1~")
+            .await
+            .unwrap();
+        assert!(stored.content["properties"].get("email").is_some());
+        assert!(stored.content["properties"].get("userId").is_none());
+    }
+
     #[tokio::test]
     async fn test_list_entities() {
         let client = create_client();
@@ -133,4 +205,70 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().is_not_found());
     }
+
+    #[tokio::test]
+    async fn test_get_many_returns_results_aligned_with_input() {
+        let client = create_client();
+
+        let types = ["user_created", "order_placed"].map(|name| {
+            json!({
+                "$id": format!("gts://gts.acme.core.events.{name}.v1~"),
+                "$schema": JSON_SCHEMA_DRAFT_07,
+                "type": "object"
+            })
+        });
+        client.register(types.to_vec()).await.unwrap();
+        client.service.switch_to_ready().unwrap();
+
+        let ids = [
+            "gts.acme.core.events.order_placed.v1~",
+            "gts.unknown.pkg.ns.type.v1~",
+            "gts.acme.core.events.user_created.v1~",
+            "gts.acme.core.events.order_placed.v1~",
+        ]
+        .map(str::to_owned);
+        let results = client.get_many(&ids).await.unwrap();
+
+        assert_eq!(results.len(), ids.len());
+        for i in [0, 2, 3] {
+            assert_eq!(results[i].as_ref().unwrap().gts_id, ids[i]);
+        }
+        assert!(results[1].as_ref().unwrap_err().is_not_found());
+        assert!(client.get_many(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_refs_inlines_registered_type() {
+        let client = create_client();
+
+        let address = json!({
+            "$id": "gts://gts.acme.core.models.address.v1~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object",
+            "properties": { "city": { "type": "string" } }
+        });
+        let customer = json!({
+            "$id": "gts://gts.acme.core.models.customer.v1~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object",
+            "properties": {
+                "address": { "$ref": "gts://gts.acme.core.models.address.v1~" }
+            }
+        });
+        client.register(vec![address, customer]).await.unwrap();
+        client.service.switch_to_ready().unwrap();
+
+        let schema = client
+            .resolve_refs("gts.acme.core.models.customer.v1~")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            schema["properties"]["address"],
+            json!({
+                "type": "object",
+                "properties": { "city": { "type": "string" } }
+            })
+        );
+    }
 }
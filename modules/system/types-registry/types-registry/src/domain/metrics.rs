@@ -0,0 +1,87 @@
+//! Metrics hook for registry operations.
+
+use std::time::Duration;
+
+use modkit_macros::domain_model;
+
+/// Registry operation a metric is recorded for.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RegistryOperation {
+    /// Any of the batch `register*` calls.
+    Register,
+    /// `list`.
+    List,
+    /// `get`.
+    Get,
+    /// `get_many`; a batch with any missing ID reports `Error`.
+    GetMany,
+}
+
+impl RegistryOperation {
+    /// Label value used for the `operation` attribute.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Register => "register",
+            Self::List => "list",
+            Self::Get => "get",
+            Self::GetMany => "get_many",
+        }
+    }
+}
+
+/// Outcome of a registry operation.
+///
+/// A `register` call reports `Error` when any entity of its batch failed.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperationOutcome {
+    Ok,
+    Error,
+}
+
+impl OperationOutcome {
+    /// Label value used for the `outcome` attribute.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// Output port for recording registry metrics.
+///
+/// The OpenTelemetry implementation lives in `infra/metrics.rs`; the service
+/// defaults to [`NoOpMetrics`].
+pub trait TypesRegistryMetricsPort: Send + Sync {
+    /// Count one completed operation (counter).
+    fn record_operation(&self, operation: RegistryOperation, outcome: OperationOutcome);
+
+    /// Record how long an operation took (histogram).
+    fn record_latency(
+        &self,
+        operation: RegistryOperation,
+        outcome: OperationOutcome,
+        latency: Duration,
+    );
+}
+
+/// Metrics implementation discarding everything (default).
+#[domain_model]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoOpMetrics;
+
+impl TypesRegistryMetricsPort for NoOpMetrics {
+    fn record_operation(&self, _operation: RegistryOperation, _outcome: OperationOutcome) {}
+
+    fn record_latency(
+        &self,
+        _operation: RegistryOperation,
+        _outcome: OperationOutcome,
+        _latency: Duration,
+    ) {
+    }
+}
@@ -3,11 +3,13 @@
 //! Contains business logic, error types, and repository traits.
 
 pub mod error;
+pub mod metrics;
 pub mod repo;
 pub mod service;
 // === LOCAL CLIENT ===
 pub mod local_client;
 
 pub use error::DomainError;
+pub use metrics::{NoOpMetrics, TypesRegistryMetricsPort};
 pub use repo::GtsRepository;
 pub use service::TypesRegistryService;
@@ -19,7 +19,8 @@ pub trait GtsRepository: Send + Sync {
     /// # Errors
     ///
     /// Returns an error if:
-    /// - The entity already exists
+    /// - An entity with the same GTS ID but different content already exists
+    ///   (re-registering identical content succeeds)
     /// - Validation fails (when `validate` is true)
     fn register(
         &self,
@@ -27,6 +28,34 @@ pub trait GtsRepository: Send + Sync {
         validate: bool,
     ) -> Result<GtsEntity, DomainError>;
 
+    /// Registers a GTS entity, replacing any existing entity with the same GTS ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if validation fails (when `validate` is true); the
+    /// previous entity, if any, is kept in that case.
+    fn register_overwrite(
+        &self,
+        entity: &serde_json::Value,
+        validate: bool,
+    ) -> Result<GtsEntity, DomainError>;
+
+    /// Registers a batch of GTS entities all-or-nothing.
+    ///
+    /// Entities are registered in order with the same rules as [`Self::register`],
+    /// or [`Self::register_overwrite`] when `overwrite` is set.
+    ///
+    /// # Errors
+    ///
+    /// On the first failure the store is restored to its state before the
+    /// batch and the index of the failing entity is returned with its error.
+    fn register_atomic(
+        &self,
+        entities: &[serde_json::Value],
+        validate: bool,
+        overwrite: bool,
+    ) -> Result<Vec<GtsEntity>, (usize, DomainError)>;
+
     /// Retrieves a GTS entity by its identifier.
     ///
     /// # Arguments
@@ -45,6 +74,15 @@ pub trait GtsRepository: Send + Sync {
     /// * `query` - Query parameters for filtering
     fn list(&self, query: &ListQuery) -> Result<Vec<GtsEntity>, DomainError>;
 
+    /// Removes the entities with the given GTS IDs from both stores.
+    ///
+    /// Unknown IDs are ignored. Returns how many entities were removed.
+    ///
+    /// Required rather than defaulted: the service hides an expired instance
+    /// only while its lease exists, so a repository that ignored removal would
+    /// bring every swept instance back.
+    fn remove(&self, gts_ids: &[String]) -> usize;
+
     /// Checks if an entity with the given GTS ID exists.
     fn exists(&self, gts_id: &str) -> bool;
 
@@ -1,14 +1,40 @@
 //! Domain service for the Types Registry module.
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use modkit_macros::domain_model;
-use types_registry_sdk::{GtsEntity, ListQuery, RegisterResult};
+use parking_lot::Mutex;
+use tokio::time::Instant;
+use types_registry_sdk::{GtsEntity, ListQuery, RegisterResult, TypesRegistryError};
 
 use super::error::DomainError;
+use super::metrics::{NoOpMetrics, OperationOutcome, RegistryOperation, TypesRegistryMetricsPort};
 use super::repo::GtsRepository;
 use crate::config::TypesRegistryConfig;
 
+/// Expiry of an instance registered with a TTL.
+#[domain_model]
+#[derive(Debug, Clone, Copy)]
+struct Lease {
+    ttl: Duration,
+    expires_at: Instant,
+}
+
+impl Lease {
+    fn starting_now(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            expires_at: Instant::now() + ttl,
+        }
+    }
+
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at <= now
+    }
+}
+
 /// Domain service for GTS entity operations.
 ///
 /// This service orchestrates business logic and delegates storage
@@ -17,13 +43,28 @@ use crate::config::TypesRegistryConfig;
 pub struct TypesRegistryService {
     repo: Arc<dyn GtsRepository>,
     config: TypesRegistryConfig,
+    /// Leases of instances registered with a TTL, by GTS ID.
+    leases: Mutex<HashMap<String, Lease>>,
+    metrics: Arc<dyn TypesRegistryMetricsPort>,
 }
 
 impl TypesRegistryService {
     /// Creates a new `TypesRegistryService` with the given repository and config.
     #[must_use]
     pub fn new(repo: Arc<dyn GtsRepository>, config: TypesRegistryConfig) -> Self {
-        Self { repo, config }
+        Self {
+            repo,
+            config,
+            leases: Mutex::new(HashMap::new()),
+            metrics: Arc::new(NoOpMetrics),
+        }
+    }
+
+    /// Records `register`, `list` and `get` outcomes and latencies to `metrics`.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: Arc<dyn TypesRegistryMetricsPort>) -> Self {
+        self.metrics = metrics;
+        self
     }
 
     /// Registers GTS entities in batch.
@@ -36,7 +77,60 @@ impl TypesRegistryService {
     #[must_use]
     pub fn register(&self, entities: Vec<serde_json::Value>) -> Vec<RegisterResult> {
         let validate = self.repo.is_ready();
-        self.register_internal(entities, validate)
+        self.register_internal(entities, validate, false)
+    }
+
+    /// Registers GTS instances that expire unless refreshed within `ttl`.
+    ///
+    /// Validation follows the ready state as in [`Self::register`]. Each
+    /// registered instance (re)starts a lease of `ttl`; types are rejected
+    /// with `ValidationFailed` since they cannot expire.
+    ///
+    /// Returns a `RegisterResult` for each input entity, preserving order.
+    #[must_use]
+    pub fn register_with_ttl(
+        &self,
+        entities: Vec<serde_json::Value>,
+        ttl: Duration,
+    ) -> Vec<RegisterResult> {
+        let started = Instant::now();
+        let validate = self.repo.is_ready();
+        // Registered under the lease lock, which the sweep holds while removing,
+        // so an instance renewed here is never dropped by a sweep that saw its
+        // previous, expired lease
+        let mut leases = self.leases.lock();
+        let results: Vec<RegisterResult> = entities
+            .into_iter()
+            .map(|entity| {
+                let gts_id = self.extract_gts_id(&entity);
+                if gts_id.as_deref().is_some_and(|id| id.ends_with('~')) {
+                    return RegisterResult::Err {
+                        gts_id,
+                        error: TypesRegistryError::validation_failed(
+                            "TTL is only supported for instances",
+                        ),
+                    };
+                }
+                self.register_one(&entity, gts_id, validate, false)
+            })
+            .collect();
+        Self::apply_leases(&mut leases, &results, Some(ttl));
+        drop(leases);
+        self.observe_register(started, &results);
+        results
+    }
+
+    /// Registers GTS entities in batch, replacing differing content.
+    ///
+    /// Validation follows the ready state as in [`Self::register`]. An entity
+    /// whose GTS ID is already registered with different content replaces it
+    /// instead of failing with `AlreadyExists`.
+    ///
+    /// Returns a `RegisterResult` for each input entity, preserving order.
+    #[must_use]
+    pub fn register_overwrite(&self, entities: Vec<serde_json::Value>) -> Vec<RegisterResult> {
+        let validate = self.repo.is_ready();
+        self.register_internal(entities, validate, true)
     }
 
     /// Registers GTS entities in batch with forced validation.
@@ -44,43 +138,264 @@ impl TypesRegistryService {
     /// This method always validates entities regardless of ready state.
     /// Used by REST API to ensure all externally registered entities are validated.
     ///
+    /// With `overwrite`, an entity whose GTS ID is already registered with
+    /// different content replaces it instead of failing with `AlreadyExists`.
+    ///
     /// Returns a `RegisterResult` for each input entity, preserving order.
     #[must_use]
-    pub fn register_validated(&self, entities: Vec<serde_json::Value>) -> Vec<RegisterResult> {
-        self.register_internal(entities, true)
+    pub fn register_validated(
+        &self,
+        entities: Vec<serde_json::Value>,
+        overwrite: bool,
+    ) -> Vec<RegisterResult> {
+        self.register_internal(entities, true, overwrite)
     }
 
-    /// Internal registration method with explicit validation control.
+    /// Registers GTS entities in batch, all-or-nothing.
+    ///
+    /// Validation follows the ready state as in [`Self::register`]. If any
+    /// entity fails, nothing from the batch is persisted: the failing entity
+    /// reports its own error and every other entity reports `RolledBack`.
+    ///
+    /// Returns a `RegisterResult` for each input entity, preserving order.
+    #[must_use]
+    pub fn register_atomic(&self, entities: &[serde_json::Value]) -> Vec<RegisterResult> {
+        let validate = self.repo.is_ready();
+        self.register_atomic_internal(entities, validate, false)
+    }
+
+    /// Atomic counterpart of [`Self::register_validated`].
+    ///
+    /// Always validates and, with `overwrite`, replaces differing content.
+    /// Any failure rolls back the whole batch as in [`Self::register_atomic`].
+    #[must_use]
+    pub fn register_validated_atomic(
+        &self,
+        entities: &[serde_json::Value],
+        overwrite: bool,
+    ) -> Vec<RegisterResult> {
+        self.register_atomic_internal(entities, true, overwrite)
+    }
+
+    /// Internal registration method with explicit validation and overwrite control.
     fn register_internal(
         &self,
         entities: Vec<serde_json::Value>,
         validate: bool,
+        overwrite: bool,
     ) -> Vec<RegisterResult> {
-        let mut results = Vec::with_capacity(entities.len());
-
-        for entity in entities {
-            let gts_id = self.extract_gts_id(&entity);
-            let result = match self.repo.register(&entity, validate) {
-                Ok(registered) => RegisterResult::Ok(registered),
-                Err(e) => RegisterResult::Err {
-                    gts_id,
-                    error: e.into(),
-                },
-            };
-            results.push(result);
+        let started = Instant::now();
+        let results: Vec<RegisterResult> = entities
+            .into_iter()
+            .map(|entity| {
+                let gts_id = self.extract_gts_id(&entity);
+                self.register_one(&entity, gts_id, validate, overwrite)
+            })
+            .collect();
+        self.update_leases(&results, None);
+        self.observe_register(started, &results);
+        results
+    }
+
+    /// Registers a single entity, reporting failures under `gts_id`.
+    fn register_one(
+        &self,
+        entity: &serde_json::Value,
+        gts_id: Option<String>,
+        validate: bool,
+        overwrite: bool,
+    ) -> RegisterResult {
+        let registered = if overwrite {
+            self.repo.register_overwrite(entity, validate)
+        } else {
+            self.repo.register(entity, validate)
+        };
+        match registered {
+            Ok(registered) => RegisterResult::Ok(registered),
+            Err(e) => RegisterResult::Err {
+                gts_id,
+                error: e.into(),
+            },
         }
+    }
 
+    /// Starts a lease for every registered entity, or ends it when `ttl` is `None`.
+    fn update_leases(&self, results: &[RegisterResult], ttl: Option<Duration>) {
+        Self::apply_leases(&mut self.leases.lock(), results, ttl);
+    }
+
+    fn apply_leases(
+        leases: &mut HashMap<String, Lease>,
+        results: &[RegisterResult],
+        ttl: Option<Duration>,
+    ) {
+        for result in results {
+            if let RegisterResult::Ok(entity) = result {
+                match ttl {
+                    Some(ttl) => {
+                        leases.insert(entity.gts_id.clone(), Lease::starting_now(ttl));
+                    }
+                    None => {
+                        leases.remove(&entity.gts_id);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Internal all-or-nothing registration; see [`Self::register_atomic`].
+    fn register_atomic_internal(
+        &self,
+        entities: &[serde_json::Value],
+        validate: bool,
+        overwrite: bool,
+    ) -> Vec<RegisterResult> {
+        let started = Instant::now();
+        let results = match self.repo.register_atomic(entities, validate, overwrite) {
+            Ok(registered) => {
+                let results: Vec<RegisterResult> =
+                    registered.into_iter().map(RegisterResult::Ok).collect();
+                self.update_leases(&results, None);
+                results
+            }
+            Err((failed, e)) => {
+                let mut error = Some(e);
+                entities
+                    .iter()
+                    .enumerate()
+                    .map(|(i, entity)| RegisterResult::Err {
+                        gts_id: self.extract_gts_id(entity),
+                        error: match error.take_if(|_| i == failed) {
+                            Some(e) => e.into(),
+                            None => TypesRegistryError::rolled_back(format!(
+                                "entity at index {failed} failed"
+                            )),
+                        },
+                    })
+                    .collect()
+            }
+        };
+        self.observe_register(started, &results);
         results
     }
 
+    /// Records one `register` call, failed if any entity of the batch failed.
+    fn observe_register(&self, started: Instant, results: &[RegisterResult]) {
+        let failed = results.iter().any(RegisterResult::is_err);
+        self.observe(RegistryOperation::Register, started, failed);
+    }
+
+    fn observe(&self, operation: RegistryOperation, started: Instant, failed: bool) {
+        let outcome = if failed {
+            OperationOutcome::Error
+        } else {
+            OperationOutcome::Ok
+        };
+        self.metrics.record_operation(operation, outcome);
+        self.metrics
+            .record_latency(operation, outcome, started.elapsed());
+    }
+
     /// Retrieves a single GTS entity by its identifier.
+    ///
+    /// Expired instances are reported as `NotFound`.
     pub fn get(&self, gts_id: &str) -> Result<GtsEntity, DomainError> {
-        self.repo.get(gts_id)
+        let started = Instant::now();
+        let result = if self.is_expired(gts_id, started) {
+            Err(DomainError::not_found(gts_id))
+        } else {
+            self.repo.get(gts_id)
+        };
+        self.observe(RegistryOperation::Get, started, result.is_err());
+        result
+    }
+
+    /// Retrieves several GTS entities, one result per requested ID.
+    ///
+    /// Results are aligned with `gts_ids`; a missing or expired ID yields
+    /// `NotFound` at its position without failing the rest of the batch.
+    pub fn get_many(&self, gts_ids: &[String]) -> Vec<Result<GtsEntity, DomainError>> {
+        let started = Instant::now();
+        let results: Vec<_> = gts_ids
+            .iter()
+            .map(|gts_id| {
+                if self.is_expired(gts_id, started) {
+                    Err(DomainError::not_found(gts_id))
+                } else {
+                    self.repo.get(gts_id)
+                }
+            })
+            .collect();
+        let failed = results.iter().any(Result::is_err);
+        self.observe(RegistryOperation::GetMany, started, failed);
+        results
     }
 
     /// Lists GTS entities matching the given query.
+    ///
+    /// Expired instances are left out unless `query.include_expired` is set.
     pub fn list(&self, query: &ListQuery) -> Result<Vec<GtsEntity>, DomainError> {
-        self.repo.list(query)
+        let started = Instant::now();
+        let result = self.repo.list(query).map(|mut entities| {
+            if !query.include_expired {
+                entities.retain(|entity| !self.is_expired(&entity.gts_id, started));
+            }
+            entities
+        });
+        self.observe(RegistryOperation::List, started, result.is_err());
+        result
+    }
+
+    /// Extends the lease of an instance registered with a TTL by one full TTL.
+    ///
+    /// Entities registered without a TTL are left as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the entity does not exist or has already expired.
+    pub fn touch(&self, gts_id: &str) -> Result<(), DomainError> {
+        {
+            let mut leases = self.leases.lock();
+            if let Some(lease) = leases.get_mut(gts_id) {
+                if lease.is_expired(Instant::now()) {
+                    return Err(DomainError::not_found(gts_id));
+                }
+                *lease = Lease::starting_now(lease.ttl);
+                return Ok(());
+            }
+        }
+        self.repo.get(gts_id).map(|_| ())
+    }
+
+    /// Removes every instance whose lease has elapsed.
+    ///
+    /// Called periodically by the module's background sweep. Returns the
+    /// number of removed instances.
+    pub fn sweep_expired(&self) -> usize {
+        let now = Instant::now();
+        // The lease lock is held through the removal, so no lease can be
+        // renewed between the expiry check and dropping its instance
+        let mut leases = self.leases.lock();
+        let expired: Vec<String> = leases
+            .iter()
+            .filter(|(_, lease)| lease.is_expired(now))
+            .map(|(gts_id, _)| gts_id.clone())
+            .collect();
+        if expired.is_empty() {
+            return 0;
+        }
+        let removed = self.repo.remove(&expired);
+        for gts_id in &expired {
+            leases.remove(gts_id);
+        }
+        removed
+    }
+
+    fn is_expired(&self, gts_id: &str, now: Instant) -> bool {
+        self.leases
+            .lock()
+            .get(gts_id)
+            .is_some_and(|lease| lease.is_expired(now))
     }
 
     /// Switches the registry from configuration mode to ready mode.
@@ -185,6 +500,27 @@ mod tests {
             ))
         }
 
+        fn register_overwrite(
+            &self,
+            entity: &serde_json::Value,
+            validate: bool,
+        ) -> Result<GtsEntity, DomainError> {
+            self.register(entity, validate)
+        }
+
+        fn register_atomic(
+            &self,
+            entities: &[serde_json::Value],
+            validate: bool,
+            _overwrite: bool,
+        ) -> Result<Vec<GtsEntity>, (usize, DomainError)> {
+            entities
+                .iter()
+                .enumerate()
+                .map(|(i, e)| self.register(e, validate).map_err(|err| (i, err)))
+                .collect()
+        }
+
         fn get(&self, gts_id: &str) -> Result<GtsEntity, DomainError> {
             if gts_id.contains("notfound") {
                 return Err(DomainError::not_found(gts_id));
@@ -210,6 +546,10 @@ mod tests {
             )])
         }
 
+        fn remove(&self, gts_ids: &[String]) -> usize {
+            gts_ids.len()
+        }
+
         fn exists(&self, _gts_id: &str) -> bool {
             true
         }
@@ -373,4 +713,73 @@ mod tests {
         );
         assert!(!service.is_ready());
     }
+
+    /// Sink keeping every recorded counter increment and latency label.
+    #[derive(Default)]
+    struct RecordingMetrics {
+        operations: Mutex<Vec<(RegistryOperation, OperationOutcome)>>,
+        latencies: Mutex<Vec<(RegistryOperation, OperationOutcome)>>,
+    }
+
+    impl RecordingMetrics {
+        fn count(&self, operation: RegistryOperation, outcome: OperationOutcome) -> usize {
+            self.operations
+                .lock()
+                .iter()
+                .filter(|recorded| **recorded == (operation, outcome))
+                .count()
+        }
+    }
+
+    impl TypesRegistryMetricsPort for RecordingMetrics {
+        fn record_operation(&self, operation: RegistryOperation, outcome: OperationOutcome) {
+            self.operations.lock().push((operation, outcome));
+        }
+
+        fn record_latency(
+            &self,
+            operation: RegistryOperation,
+            outcome: OperationOutcome,
+            _latency: Duration,
+        ) {
+            self.latencies.lock().push((operation, outcome));
+        }
+    }
+
+    #[test]
+    fn test_failed_register_increments_error_counter() {
+        let metrics = Arc::new(RecordingMetrics::default());
+        let service = TypesRegistryService::new(
+            Arc::new(MockRepo::new()),
+            crate::config::TypesRegistryConfig::default(),
+        )
+        .with_metrics(metrics.clone());
+
+        let results = service.register(vec![json!({"$id": "gts://gts.acme.core.events.fail.v1~"})]);
+        assert!(results[0].is_err());
+        assert_eq!(
+            metrics.count(RegistryOperation::Register, OperationOutcome::Error),
+            1
+        );
+        assert_eq!(
+            metrics.count(RegistryOperation::Register, OperationOutcome::Ok),
+            0
+        );
+
+        assert!(service.get("gts.acme.core.events.notfound.v1~").is_err());
+        service.list(&ListQuery::default()).unwrap();
+        assert_eq!(
+            metrics.count(RegistryOperation::Get, OperationOutcome::Error),
+            1
+        );
+        assert_eq!(
+            metrics.count(RegistryOperation::List, OperationOutcome::Ok),
+            1
+        );
+        assert_eq!(
+            *metrics.latencies.lock(),
+            *metrics.operations.lock(),
+            "every operation records its latency under the same labels"
+        );
+    }
 }
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Histogram, Meter};
+
+use crate::domain::metrics::{OperationOutcome, RegistryOperation, TypesRegistryMetricsPort};
+
+/// OpenTelemetry-backed implementation of [`TypesRegistryMetricsPort`].
+pub struct TypesRegistryMetricsMeter {
+    operations: Counter<u64>,
+    latency_ms: Histogram<f64>,
+}
+
+impl TypesRegistryMetricsMeter {
+    #[must_use]
+    pub fn new(meter: &Meter) -> Self {
+        Self {
+            operations: meter
+                .u64_counter("types_registry.operations")
+                .with_description("Number of registry operations by operation and outcome")
+                .build(),
+            latency_ms: meter
+                .f64_histogram("types_registry.operation_latency_ms")
+                .with_description("Registry operation latency (ms)")
+                .build(),
+        }
+    }
+}
+
+fn labels(operation: RegistryOperation, outcome: OperationOutcome) -> [KeyValue; 2] {
+    [
+        KeyValue::new("operation", operation.as_str()),
+        KeyValue::new("outcome", outcome.as_str()),
+    ]
+}
+
+impl TypesRegistryMetricsPort for TypesRegistryMetricsMeter {
+    fn record_operation(&self, operation: RegistryOperation, outcome: OperationOutcome) {
+        self.operations.add(1, &labels(operation, outcome));
+    }
+
+    fn record_latency(
+        &self,
+        operation: RegistryOperation,
+        outcome: OperationOutcome,
+        latency: Duration,
+    ) {
+        self.latency_ms
+            .record(latency.as_secs_f64() * 1000.0, &labels(operation, outcome));
+    }
+}
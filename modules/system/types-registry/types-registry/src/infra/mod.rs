@@ -2,6 +2,8 @@
 //!
 //! Contains storage implementations and adapters.
 
+pub mod metrics;
 pub mod storage;
 
+pub use metrics::TypesRegistryMetricsMeter;
 pub use storage::InMemoryGtsRepository;
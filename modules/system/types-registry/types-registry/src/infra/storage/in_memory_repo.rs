@@ -1,8 +1,9 @@
 //! In-memory repository implementation using gts-rust.
 
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use gts::{GtsConfig, GtsID, GtsIdSegment, GtsOps, GtsWildcard};
+use gts::{GtsConfig, GtsID, GtsIdSegment, GtsOps, GtsStore, GtsWildcard};
 use parking_lot::Mutex;
 use types_registry_sdk::{GtsEntity, ListQuery, SegmentMatchScope};
 
@@ -12,6 +13,13 @@ use super::debug_diagnostics::{
 use crate::domain::error::DomainError;
 use crate::domain::repo::GtsRepository;
 
+/// What a store already holds under the GTS ID being registered.
+enum Existing {
+    Absent,
+    Identical,
+    Different(serde_json::Value),
+}
+
 /// In-memory repository for GTS entities using gts-rust.
 ///
 /// Implements two-phase storage:
@@ -47,11 +55,11 @@ impl InMemoryGtsRepository {
     fn to_gts_entity(gts_id: &str, content: &serde_json::Value) -> Result<GtsEntity, DomainError> {
         let parsed = GtsID::new(gts_id).map_err(|e| DomainError::invalid_gts_id(e.to_string()))?;
 
-        let segments: Vec<GtsIdSegment> = parsed.gts_id_segments.clone();
+        let segments: Vec<GtsIdSegment> = parsed.gts_id_segments;
 
         let is_schema = gts_id.ends_with('~');
 
-        let id = parsed.to_uuid();
+        let id = GtsEntity::derive_id(gts_id);
 
         let description = content
             .get("description")
@@ -88,6 +96,103 @@ impl InMemoryGtsRepository {
         None
     }
 
+    /// Registers an entity in whichever store is active for the current phase.
+    ///
+    /// Re-registering identical content is a no-op. Different content under an
+    /// existing GTS ID is `AlreadyExists` unless `overwrite` is set; a replaced
+    /// entity that then fails validation is restored.
+    fn register_entity(
+        &self,
+        entity: &serde_json::Value,
+        validate: bool,
+        overwrite: bool,
+    ) -> Result<GtsEntity, DomainError> {
+        let ready = self.is_ready.load(Ordering::SeqCst);
+        let mut ops = self.active_store(ready).lock();
+        self.register_locked(&mut ops, ready, entity, validate, overwrite)
+    }
+
+    /// The store that receives registrations in the given phase.
+    fn active_store(&self, ready: bool) -> &Mutex<GtsOps> {
+        if ready {
+            &self.persistent
+        } else {
+            &self.temporary
+        }
+    }
+
+    /// Registration against an already-locked store; see [`Self::register_entity`].
+    fn register_locked(
+        &self,
+        ops: &mut GtsOps,
+        ready: bool,
+        entity: &serde_json::Value,
+        validate: bool,
+        overwrite: bool,
+    ) -> Result<GtsEntity, DomainError> {
+        let gts_id = self
+            .extract_gts_id(entity)
+            .ok_or_else(|| DomainError::invalid_gts_id("No GTS ID field found in entity"))?;
+
+        GtsID::new(&gts_id).map_err(|e| DomainError::invalid_gts_id(e.to_string()))?;
+
+        let previous = match Self::existing_content(ops, &gts_id, entity) {
+            Existing::Absent => None,
+            Existing::Identical => return Self::to_gts_entity(&gts_id, entity),
+            Existing::Different(_) if !overwrite => {
+                return Err(DomainError::already_exists(&gts_id));
+            }
+            Existing::Different(previous) => Some(previous),
+        };
+
+        // Configuration phase defers validation to `switch_to_ready`
+        let result = ops.add_entity(entity, ready && validate);
+        if !result.ok {
+            // Debug logging for registration failure
+            if !ready {
+                log_registration_failure(Some(&gts_id), entity, &result.error);
+            } else if gts_id.ends_with('~') {
+                log_schema_validation_failure(&gts_id, entity, &result.error);
+            } else {
+                log_instance_validation_failure(&gts_id, entity, &result.error, ops);
+            }
+            if let Some(previous) = previous {
+                ops.add_entity(&previous, false);
+            }
+            return Err(DomainError::validation_failed(result.error));
+        }
+
+        Self::to_gts_entity(&gts_id, entity)
+    }
+
+    /// Compares `entity` with what is stored under `gts_id`.
+    fn existing_content(ops: &mut GtsOps, gts_id: &str, entity: &serde_json::Value) -> Existing {
+        match ops.store.get(gts_id) {
+            None => Existing::Absent,
+            Some(existing) if existing.content == *entity => Existing::Identical,
+            Some(existing) => Existing::Different(existing.content.clone()),
+        }
+    }
+
+    /// Copies the stored entities whose GTS ID passes `keep`.
+    fn snapshot(ops: &GtsOps, keep: impl Fn(&str) -> bool) -> Vec<gts::GtsEntity> {
+        ops.store
+            .items()
+            .filter(|(gts_id, _)| keep(gts_id))
+            .map(|(_, e)| e.clone())
+            .collect()
+    }
+
+    /// Replaces the store contents with `entities`.
+    fn restore(ops: &mut GtsOps, entities: Vec<gts::GtsEntity>) {
+        let mut store = GtsStore::new(None);
+        for entity in entities {
+            // Snapshot entities were accepted before, so they have an ID
+            _ = store.register(entity);
+        }
+        ops.store = store;
+    }
+
     /// Checks if an entity matches the given query filters.
     fn matches_query(entity: &GtsEntity, query: &ListQuery) -> bool {
         if let Some(ref pattern) = query.pattern
@@ -131,6 +236,23 @@ impl InMemoryGtsRepository {
             return false;
         }
 
+        if let Some(ref type_name) = query.type_name
+            && !segments_to_check.iter().any(|s| s.type_name == *type_name)
+        {
+            return false;
+        }
+
+        if let Some(ref text) = query.description_contains {
+            let text = text.to_lowercase();
+            if !entity
+                .description
+                .as_ref()
+                .is_some_and(|d| d.to_lowercase().contains(&text))
+            {
+                return false;
+            }
+        }
+
         true
     }
 }
@@ -141,58 +263,41 @@ impl GtsRepository for InMemoryGtsRepository {
         entity: &serde_json::Value,
         validate: bool,
     ) -> Result<GtsEntity, DomainError> {
-        let gts_id = self
-            .extract_gts_id(entity)
-            .ok_or_else(|| DomainError::invalid_gts_id("No GTS ID field found in entity"))?;
-
-        GtsID::new(&gts_id).map_err(|e| DomainError::invalid_gts_id(e.to_string()))?;
-
-        if self.is_ready.load(Ordering::SeqCst) {
-            let mut persistent = self.persistent.lock();
-
-            if let Some(existing) = persistent.store.get(&gts_id) {
-                if existing.content == *entity {
-                    return Self::to_gts_entity(&gts_id, entity);
-                }
-                return Err(DomainError::already_exists(&gts_id));
-            }
-
-            let result = persistent.add_entity(entity, validate);
-            if !result.ok {
-                // Debug logging for registration failure
-                if gts_id.ends_with('~') {
-                    log_schema_validation_failure(&gts_id, entity, &result.error);
-                } else {
-                    log_instance_validation_failure(
-                        &gts_id,
-                        entity,
-                        &result.error,
-                        &mut persistent,
-                    );
-                }
-                return Err(DomainError::validation_failed(result.error));
-            }
+        self.register_entity(entity, validate, false)
+    }
 
-            Self::to_gts_entity(&gts_id, entity)
-        } else {
-            let mut temporary = self.temporary.lock();
+    fn register_overwrite(
+        &self,
+        entity: &serde_json::Value,
+        validate: bool,
+    ) -> Result<GtsEntity, DomainError> {
+        self.register_entity(entity, validate, true)
+    }
 
-            if let Some(existing) = temporary.store.get(&gts_id) {
-                if existing.content == *entity {
-                    return Self::to_gts_entity(&gts_id, entity);
+    fn register_atomic(
+        &self,
+        entities: &[serde_json::Value],
+        validate: bool,
+        overwrite: bool,
+    ) -> Result<Vec<GtsEntity>, (usize, DomainError)> {
+        let ready = self.is_ready.load(Ordering::SeqCst);
+        let mut ops = self.active_store(ready).lock();
+
+        // The store has no removal API, so rollback restores a full snapshot
+        let snapshot = Self::snapshot(&ops, |_| true);
+
+        let mut registered = Vec::with_capacity(entities.len());
+        for (index, entity) in entities.iter().enumerate() {
+            match self.register_locked(&mut ops, ready, entity, validate, overwrite) {
+                Ok(entity) => registered.push(entity),
+                Err(e) => {
+                    Self::restore(&mut ops, snapshot);
+                    return Err((index, e));
                 }
-                return Err(DomainError::already_exists(&gts_id));
             }
-
-            let result = temporary.add_entity(entity, false);
-            if !result.ok {
-                // Debug logging for registration failure (even in config phase)
-                log_registration_failure(Some(&gts_id), entity, &result.error);
-                return Err(DomainError::validation_failed(result.error));
-            }
-
-            Self::to_gts_entity(&gts_id, entity)
         }
+
+        Ok(registered)
     }
 
     fn get(&self, gts_id: &str) -> Result<GtsEntity, DomainError> {
@@ -220,6 +325,27 @@ impl GtsRepository for InMemoryGtsRepository {
         Ok(results)
     }
 
+    fn remove(&self, gts_ids: &[String]) -> usize {
+        let gts_ids: HashSet<&str> = gts_ids.iter().map(String::as_str).collect();
+        let mut removed = 0;
+        for store in [&self.temporary, &self.persistent] {
+            let mut ops = store.lock();
+            let present = gts_ids
+                .iter()
+                .filter(|gts_id| ops.store.get(gts_id).is_some())
+                .count();
+            if present == 0 {
+                continue;
+            }
+            // The store has no removal API, so it is rebuilt without the IDs:
+            // one pass over the store, and only when it holds one of them
+            let kept = Self::snapshot(&ops, |gts_id| !gts_ids.contains(gts_id));
+            Self::restore(&mut ops, kept);
+            removed += present;
+        }
+        removed
+    }
+
     fn exists(&self, gts_id: &str) -> bool {
         let mut persistent = self.persistent.lock();
         persistent.store.get(gts_id).is_some()
@@ -473,6 +599,32 @@ mod tests {
         assert_eq!(results.len(), 2);
     }
 
+    #[test]
+    fn test_remove_drops_only_the_given_ids() {
+        let repo = InMemoryGtsRepository::new(default_config());
+        repo.switch_to_ready().unwrap();
+        for id in ["user_created", "order_placed", "invoice_paid"] {
+            let entity = json!({
+                "$id": format!("gts://gts.acme.core.events.{id}.v1~"),
+                "$schema": JSON_SCHEMA_DRAFT_07,
+                "type": "object"
+            });
+            repo.register(&entity, true).unwrap();
+        }
+
+        let removed = repo.remove(&[
+            "gts.acme.core.events.user_created.v1~".to_owned(),
+            "gts.acme.core.events.order_placed.v1~".to_owned(),
+            "gts.acme.core.events.unknown.v1~".to_owned(),
+        ]);
+
+        assert_eq!(removed, 2);
+        assert!(!repo.exists("gts.acme.core.events.user_created.v1~"));
+        assert!(!repo.exists("gts.acme.core.events.order_placed.v1~"));
+        assert!(repo.exists("gts.acme.core.events.invoice_paid.v1~"));
+        assert_eq!(repo.remove(&[]), 0);
+    }
+
     #[test]
     fn test_get_not_found() {
         let repo = InMemoryGtsRepository::new(default_config());
@@ -542,6 +694,90 @@ mod tests {
         assert!(matches!(result, Err(DomainError::AlreadyExists(_))));
     }
 
+    #[test]
+    fn test_register_overwrite_replaces_different_content() {
+        let repo = InMemoryGtsRepository::new(default_config());
+
+        let original = json!({
+            "$id": "gts://gts.acme.core.events.user_created.v1~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object"
+        });
+        let replacement = json!({
+            "$id": "gts://gts.acme.core.events.user_created.v1~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object",
+            "description": "Replacement"
+        });
+
+        repo.register(&original, false).unwrap();
+        let result = repo.register_overwrite(&replacement, false).unwrap();
+        assert_eq!(result.description.as_deref(), Some("Replacement"));
+
+        repo.switch_to_ready().unwrap();
+        let stored = repo.get("gts.acme.core.events.user_created.v1~").unwrap();
+        assert_eq!(stored.content, replacement);
+    }
+
+    #[test]
+    fn test_register_overwrite_in_ready_mode() {
+        let repo = InMemoryGtsRepository::new(default_config());
+        repo.switch_to_ready().unwrap();
+
+        let original = json!({
+            "$id": "gts://gts.acme.core.events.user_created.v1~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object"
+        });
+        let replacement = json!({
+            "$id": "gts://gts.acme.core.events.user_created.v1~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object",
+            "description": "Replacement"
+        });
+
+        repo.register(&original, true).unwrap();
+        assert!(repo.register_overwrite(&original, true).is_ok());
+        repo.register_overwrite(&replacement, true).unwrap();
+
+        let stored = repo.get("gts.acme.core.events.user_created.v1~").unwrap();
+        assert_eq!(stored.content, replacement);
+    }
+
+    #[test]
+    fn test_register_overwrite_keeps_previous_on_validation_failure() {
+        let repo = InMemoryGtsRepository::new(default_config());
+        repo.switch_to_ready().unwrap();
+
+        repo.register(
+            &json!({
+                "$id": "gts://gts.acme.core.models.widget.v1~",
+                "$schema": JSON_SCHEMA_DRAFT_07,
+                "type": "object",
+                "properties": { "color": { "type": "string" } },
+                "required": ["color"]
+            }),
+            true,
+        )
+        .unwrap();
+        let valid = json!({
+            "id": "gts.acme.core.models.widget.v1~acme.core.models.red.v1",
+            "color": "red"
+        });
+        repo.register(&valid, true).unwrap();
+
+        let invalid = json!({
+            "id": "gts.acme.core.models.widget.v1~acme.core.models.red.v1"
+        });
+        let result = repo.register_overwrite(&invalid, true);
+        assert!(matches!(result, Err(DomainError::ValidationFailed(_))));
+
+        let stored = repo
+            .get("gts.acme.core.models.widget.v1~acme.core.models.red.v1")
+            .unwrap();
+        assert_eq!(stored.content, valid);
+    }
+
     #[test]
     fn test_exists() {
         let repo = InMemoryGtsRepository::new(default_config());
@@ -682,6 +918,67 @@ mod tests {
         assert_eq!(result.description, Some("A user created event".to_owned()));
     }
 
+    #[test]
+    fn test_list_with_description_contains_filter() {
+        let repo = InMemoryGtsRepository::new(default_config());
+
+        for entity in [
+            json!({
+                "$id": "gts://gts.acme.core.events.user_created.v1~",
+                "$schema": JSON_SCHEMA_DRAFT_07,
+                "type": "object",
+                "description": "A user created event"
+            }),
+            json!({
+                "$id": "gts://gts.acme.core.models.invoice.v1~",
+                "$schema": JSON_SCHEMA_DRAFT_07,
+                "type": "object",
+                "description": "An invoice document"
+            }),
+            json!({
+                "$id": "gts://gts.acme.core.models.order.v1~",
+                "$schema": JSON_SCHEMA_DRAFT_07,
+                "type": "object"
+            }),
+        ] {
+            repo.register(&entity, false).unwrap();
+        }
+        repo.switch_to_ready().unwrap();
+
+        let query = ListQuery::default().with_description_contains("event");
+        let results = repo.list(&query).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].gts_id, "gts.acme.core.events.user_created.v1~");
+
+        // Case-insensitive, and combined with the other filters
+        let query = ListQuery::default()
+            .with_description_contains("USER CREATED")
+            .with_namespace("events");
+        assert_eq!(repo.list(&query).unwrap().len(), 1);
+
+        let query = ListQuery::default()
+            .with_description_contains("event")
+            .with_namespace("models");
+        assert!(repo.list(&query).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_registered_id_matches_derive_id() {
+        let repo = InMemoryGtsRepository::new(default_config());
+
+        let entity = json!({
+            "$id": "gts://gts.acme.core.events.user_created.v1~",
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object"
+        });
+
+        let registered = repo.register(&entity, false).unwrap();
+        assert_eq!(
+            registered.id,
+            GtsEntity::derive_id("gts.acme.core.events.user_created.v1~")
+        );
+    }
+
     #[test]
     fn test_register_instance() {
         let repo = InMemoryGtsRepository::new(default_config());
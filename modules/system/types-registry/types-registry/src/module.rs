@@ -6,13 +6,14 @@ use async_trait::async_trait;
 use modkit::api::OpenApiRegistry;
 use modkit::contracts::SystemCapability;
 use modkit::{Module, ModuleCtx, RestApiCapability};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info};
 use types_registry_sdk::TypesRegistryClient;
 
 use crate::config::TypesRegistryConfig;
 use crate::domain::local_client::TypesRegistryLocalClient;
 use crate::domain::service::TypesRegistryService;
-use crate::infra::InMemoryGtsRepository;
+use crate::infra::{InMemoryGtsRepository, TypesRegistryMetricsMeter};
 
 /// Types Registry module.
 ///
@@ -22,6 +23,7 @@ use crate::infra::InMemoryGtsRepository;
 ///
 /// - `system` — Core infrastructure module, initialized early in startup
 /// - `rest` — Exposes REST API endpoints
+/// - `stateful` — Sweeps instances registered with a TTL once they expire
 ///
 /// ## Note
 ///
@@ -30,16 +32,49 @@ use crate::infra::InMemoryGtsRepository;
 /// separation of concerns and avoids circular dependencies.
 #[modkit::module(
     name = "types-registry",
-    capabilities = [system, rest]
+    capabilities = [system, rest, stateful],
+    lifecycle(entry = "sweep_expired", stop_timeout = "1s")
 )]
 pub struct TypesRegistryModule {
     service: OnceLock<Arc<TypesRegistryService>>,
+    sweep_interval: OnceLock<std::time::Duration>,
 }
 
 impl Default for TypesRegistryModule {
     fn default() -> Self {
         Self {
             service: OnceLock::new(),
+            sweep_interval: OnceLock::new(),
+        }
+    }
+}
+
+impl TypesRegistryModule {
+    /// Background task removing expired instances until cancelled.
+    async fn sweep_expired(&self, cancel: CancellationToken) -> anyhow::Result<()> {
+        let service = self
+            .service
+            .get()
+            .ok_or_else(|| anyhow::anyhow!("Service not initialized"))?
+            .clone();
+        let period = self
+            .sweep_interval
+            .get()
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("Service not initialized"))?;
+
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => return Ok(()),
+                _ = interval.tick() => {
+                    let removed = service.sweep_expired();
+                    if removed > 0 {
+                        debug!(removed, "types_registry removed expired instances");
+                    }
+                }
+            }
         }
     }
 }
@@ -53,9 +88,18 @@ impl Module for TypesRegistryModule {
             cfg.entity_id_fields, cfg.schema_id_fields
         );
 
+        self.sweep_interval
+            .set(cfg.expiry_sweep_interval)
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
         let gts_config = cfg.to_gts_config();
         let repo = Arc::new(InMemoryGtsRepository::new(gts_config));
-        let service = Arc::new(TypesRegistryService::new(repo, cfg));
+        let scope =
+            opentelemetry::InstrumentationScope::builder(Self::MODULE_NAME.to_owned()).build();
+        let metrics = Arc::new(TypesRegistryMetricsMeter::new(
+            &opentelemetry::global::meter_with_scope(scope),
+        ));
+        let service = Arc::new(TypesRegistryService::new(repo, cfg).with_metrics(metrics));
 
         self.service
             .set(service.clone())
@@ -0,0 +1,130 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for instances registered with a TTL
+
+mod common;
+
+use std::time::Duration;
+
+use common::create_service;
+use serde_json::json;
+use types_registry::domain::error::DomainError;
+use types_registry_sdk::ListQuery;
+
+const PLUGIN_TYPE: &str = "gts.acme.core.plugins.worker.v1~";
+const PLUGIN_INSTANCE: &str = "gts.acme.core.plugins.worker.v1~acme.core.instances.w1.v1";
+const TTL: Duration = Duration::from_secs(10);
+
+fn ready_service() -> std::sync::Arc<types_registry::domain::service::TypesRegistryService> {
+    let service = create_service();
+    let results = service.register(vec![json!({
+        "$id": format!("gts://{PLUGIN_TYPE}"),
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object",
+        "properties": { "endpoint": { "type": "string" } }
+    })]);
+    assert!(results[0].is_ok(), "{:?}", results[0]);
+    service.switch_to_ready().unwrap();
+    service
+}
+
+fn plugin_instance() -> serde_json::Value {
+    json!({ "id": PLUGIN_INSTANCE, "endpoint": "http://worker-1" })
+}
+
+fn listed_instances(
+    service: &types_registry::domain::service::TypesRegistryService,
+    query: ListQuery,
+) -> Vec<String> {
+    service
+        .list(&query.with_is_type(false))
+        .unwrap()
+        .into_iter()
+        .map(|e| e.gts_id)
+        .collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_instance_with_ttl_disappears_after_expiry() {
+    let service = ready_service();
+    let results = service.register_with_ttl(vec![plugin_instance()], TTL);
+    assert!(results[0].is_ok(), "{:?}", results[0]);
+    assert!(service.get(PLUGIN_INSTANCE).is_ok());
+
+    tokio::time::advance(TTL).await;
+
+    // Expired instances are hidden before the sweep runs ...
+    assert!(matches!(
+        service.get(PLUGIN_INSTANCE),
+        Err(DomainError::NotFound(_))
+    ));
+    assert!(listed_instances(&service, ListQuery::default()).is_empty());
+    assert_eq!(
+        listed_instances(&service, ListQuery::default().with_include_expired(true)),
+        vec![PLUGIN_INSTANCE]
+    );
+
+    // ... and removed for good by it
+    assert_eq!(service.sweep_expired(), 1);
+    assert!(listed_instances(&service, ListQuery::default().with_include_expired(true)).is_empty());
+    assert!(service.get(PLUGIN_TYPE).is_ok(), "types never expire");
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_touch_keeps_instance_alive() {
+    let service = ready_service();
+    assert!(service.register_with_ttl(vec![plugin_instance()], TTL)[0].is_ok());
+
+    for _ in 0..3 {
+        tokio::time::advance(TTL / 2).await;
+        service.touch(PLUGIN_INSTANCE).unwrap();
+        assert_eq!(service.sweep_expired(), 0);
+    }
+    assert!(service.get(PLUGIN_INSTANCE).is_ok());
+
+    tokio::time::advance(TTL).await;
+    assert!(matches!(
+        service.touch(PLUGIN_INSTANCE),
+        Err(DomainError::NotFound(_))
+    ));
+    assert_eq!(service.sweep_expired(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_registering_without_ttl_makes_instance_permanent() {
+    let service = ready_service();
+    assert!(service.register_with_ttl(vec![plugin_instance()], TTL)[0].is_ok());
+    assert!(service.register(vec![plugin_instance()])[0].is_ok());
+
+    tokio::time::advance(TTL * 2).await;
+
+    assert_eq!(service.sweep_expired(), 0);
+    assert!(service.get(PLUGIN_INSTANCE).is_ok());
+    // Touching an entity without a lease is a no-op
+    service.touch(PLUGIN_INSTANCE).unwrap();
+}
+
+#[tokio::test]
+async fn test_types_cannot_be_registered_with_ttl() {
+    let service = create_service();
+    let results = service.register_with_ttl(
+        vec![json!({
+            "$id": format!("gts://{PLUGIN_TYPE}"),
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object"
+        })],
+        TTL,
+    );
+
+    let error = results[0].as_result().unwrap_err();
+    assert!(error.is_validation_failed(), "{error}");
+}
+
+#[tokio::test]
+async fn test_touch_unknown_entity_is_not_found() {
+    let service = ready_service();
+    assert!(matches!(
+        service.touch(PLUGIN_INSTANCE),
+        Err(DomainError::NotFound(_))
+    ));
+}
@@ -8,7 +8,7 @@ use axum::extract::Json;
 use common::create_service;
 use serde_json::json;
 use types_registry::api::rest::dto::ListEntitiesQuery;
-use types_registry_sdk::ListQuery;
+use types_registry_sdk::{ListQuery, SegmentMatchScope};
 
 // =============================================================================
 // List and Query Tests
@@ -87,6 +87,68 @@ async fn test_list_entities_with_namespace_filter() {
     assert_eq!(results.len(), 1);
 }
 
+#[tokio::test]
+async fn test_list_entities_with_type_name_filter() {
+    let service = create_service();
+
+    let entities = vec![
+        json!({ "$id": "gts://gts.acme.core.events.user_created.v1~", "$schema": "http://json-schema.org/draft-07/schema#", "type": "object" }),
+        json!({ "$id": "gts://gts.acme.core.events.user_created.v2~", "$schema": "http://json-schema.org/draft-07/schema#", "type": "object" }),
+        json!({ "$id": "gts://gts.acme.core.events.user_deleted.v1~", "$schema": "http://json-schema.org/draft-07/schema#", "type": "object" }),
+        json!({ "$id": "gts://gts.acme.core.models.audit_entry.v1~", "$schema": "http://json-schema.org/draft-07/schema#", "type": "object" }),
+    ];
+
+    _ = service.register(entities);
+    service.switch_to_ready().unwrap();
+
+    _ = service.register(vec![
+        json!({ "id": "gts.acme.core.events.user_created.v1~acme.core.instances.u1.v1" }),
+        json!({ "id": "gts.acme.core.models.audit_entry.v1~acme.core.events.user_created.v1" }),
+    ]);
+
+    // Both versions, the instance of v1, and the chained instance whose second segment matches
+    let query = ListQuery::default().with_type_name("user_created");
+    let mut ids: Vec<_> = service
+        .list(&query)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.gts_id)
+        .collect();
+    ids.sort_unstable();
+    assert_eq!(
+        ids,
+        [
+            "gts.acme.core.events.user_created.v1~",
+            "gts.acme.core.events.user_created.v1~acme.core.instances.u1.v1",
+            "gts.acme.core.events.user_created.v2~",
+            "gts.acme.core.models.audit_entry.v1~acme.core.events.user_created.v1",
+        ]
+    );
+
+    // Primary scope excludes the chained instance of a different type
+    let query = ListQuery::default()
+        .with_type_name("user_created")
+        .with_segment_scope(SegmentMatchScope::Primary);
+    let results = service.list(&query).unwrap();
+    assert_eq!(results.len(), 3);
+    assert!(
+        results
+            .iter()
+            .all(|e| e.primary_segment().unwrap().type_name == "user_created")
+    );
+
+    let query = ListQuery::default()
+        .with_type_name("user_created")
+        .with_is_type(true);
+    assert_eq!(service.list(&query).unwrap().len(), 2);
+
+    let query = ListQuery::default().with_type_name("user_deleted");
+    assert_eq!(service.list(&query).unwrap().len(), 1);
+
+    let query = ListQuery::default().with_type_name("user");
+    assert!(service.list(&query).unwrap().is_empty());
+}
+
 #[tokio::test]
 async fn test_list_entities_with_combined_filters() {
     let service = create_service();
@@ -8,7 +8,7 @@ use axum::http::StatusCode;
 use common::create_service;
 use serde_json::json;
 use types_registry::api::rest::dto::RegisterEntitiesRequest;
-use types_registry_sdk::ListQuery;
+use types_registry_sdk::{ListQuery, RegisterResult, RegisterSummary};
 
 // =============================================================================
 // Anonymous Entity Rejection Tests
@@ -275,6 +275,100 @@ async fn test_large_batch_registration() {
     assert_eq!(all.len(), 100);
 }
 
+// =============================================================================
+// Atomic Batch Registration Tests
+// =============================================================================
+
+/// Five distinct types with an invalid GTS ID at index 2.
+fn batch_with_one_invalid() -> Vec<serde_json::Value> {
+    (0..5)
+        .map(|i| {
+            let id = if i == 2 {
+                "invalid-gts-id".to_owned()
+            } else {
+                format!("gts://gts.acme.core.events.atomic_{i}.v1~")
+            };
+            json!({
+                "$id": id,
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object"
+            })
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_atomic_batch_with_invalid_entity_persists_nothing() {
+    let service = create_service();
+
+    let results = service.register_atomic(&batch_with_one_invalid());
+    let summary = RegisterSummary::from_results(&results);
+    assert_eq!(summary.succeeded, 0);
+    assert_eq!(summary.failed, 5);
+
+    for (i, result) in results.iter().enumerate() {
+        let RegisterResult::Err { error, .. } = result else {
+            panic!("entity {i} should have failed");
+        };
+        if i == 2 {
+            assert!(error.is_invalid_gts_id(), "unexpected error: {error}");
+        } else {
+            assert!(error.is_rolled_back(), "unexpected error: {error}");
+        }
+    }
+
+    service.switch_to_ready().unwrap();
+    assert!(service.list(&ListQuery::default()).unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_default_batch_with_invalid_entity_persists_valid_ones() {
+    let service = create_service();
+
+    let results = service.register(batch_with_one_invalid());
+    let summary = RegisterSummary::from_results(&results);
+    assert_eq!(summary.succeeded, 4);
+    assert_eq!(summary.failed, 1);
+
+    service.switch_to_ready().unwrap();
+    assert_eq!(service.list(&ListQuery::default()).unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn test_atomic_batch_rollback_keeps_earlier_registrations() {
+    let service = create_service();
+    service.switch_to_ready().unwrap();
+
+    let existing = json!({
+        "$id": "gts://gts.acme.core.events.existing.v1~",
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object"
+    });
+    assert!(service.register(vec![existing])[0].is_ok());
+
+    let results = service.register_atomic(&batch_with_one_invalid());
+    assert!(results.iter().all(RegisterResult::is_err));
+
+    let all = service.list(&ListQuery::default()).unwrap();
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].gts_id, "gts.acme.core.events.existing.v1~");
+}
+
+#[tokio::test]
+async fn test_atomic_batch_all_valid_persists_everything() {
+    let service = create_service();
+
+    let mut entities = batch_with_one_invalid();
+    entities.remove(2);
+
+    let results = service.register_atomic(&entities);
+    assert_eq!(results.len(), 4);
+    assert!(results.iter().all(RegisterResult::is_ok));
+
+    service.switch_to_ready().unwrap();
+    assert_eq!(service.list(&ListQuery::default()).unwrap().len(), 4);
+}
+
 // =============================================================================
 // REST Handler Registration Tests
 // =============================================================================
@@ -295,6 +389,8 @@ async fn test_rest_register_handler_integration() {
             "type": "object",
             "description": "Test type for REST handler"
         })],
+        overwrite: false,
+        atomic: false,
     };
 
     let result = register_entities(Extension(service), Json(request)).await;
@@ -316,7 +412,11 @@ async fn test_rest_register_empty_request() {
     // Switch to ready first so REST API works
     service.switch_to_ready().unwrap();
 
-    let request = RegisterEntitiesRequest { entities: vec![] };
+    let request = RegisterEntitiesRequest {
+        entities: vec![],
+        overwrite: false,
+        atomic: false,
+    };
 
     let result = register_entities(Extension(service), Json(request)).await;
     assert!(result.is_ok());
@@ -342,6 +442,8 @@ async fn test_rest_register_with_invalid_entities() {
             json!({ "$id": "invalid-id", "type": "object" }),
             json!({ "no_id": true }),
         ],
+        overwrite: false,
+        atomic: false,
     };
 
     let result = register_entities(Extension(service), Json(request)).await;
@@ -353,3 +455,27 @@ async fn test_rest_register_with_invalid_entities() {
     assert_eq!(response.summary.succeeded, 0);
     assert_eq!(response.summary.failed, 2);
 }
+
+#[tokio::test]
+async fn test_rest_register_atomic_rolls_back_batch() {
+    use axum::extract::{Extension, Json};
+    use types_registry::api::rest::handlers::register_entities;
+
+    let service = create_service();
+    service.switch_to_ready().unwrap();
+
+    let request = RegisterEntitiesRequest {
+        entities: batch_with_one_invalid(),
+        overwrite: false,
+        atomic: true,
+    };
+
+    let (status, Json(response)) = register_entities(Extension(service.clone()), Json(request))
+        .await
+        .unwrap();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response.summary.total, 5);
+    assert_eq!(response.summary.succeeded, 0);
+    assert_eq!(response.summary.failed, 5);
+    assert!(service.list(&ListQuery::default()).unwrap().is_empty());
+}